@@ -0,0 +1,37 @@
+//! Hardware attestation included in Offers, see `ya_agreement_utils::attestation`.
+use anyhow::bail;
+use std::path::{Path, PathBuf};
+use structopt::StructOpt;
+
+use ya_agreement_utils::Attestation;
+use ya_client_model::NodeId;
+
+#[derive(StructOpt, Clone, Debug)]
+pub struct AttestationConfig {
+    /// Command generating attestation quote of this machine (TPM, SEV-SNP or SGX).
+    /// It's called with hex encoded report data as the only argument and has to print
+    /// `{"type": "tpm" | "sev-snp" | "sgx", "quote": "<hex>"}`.
+    #[structopt(long, env = "ATTESTATION_COMMAND")]
+    pub attestation_command: Option<PathBuf>,
+}
+
+/// Runs attestation command for `node_id` and checks that the quote is bound to it.
+pub async fn quote(command: &Path, node_id: NodeId) -> anyhow::Result<Attestation> {
+    let report_data = hex::encode(Attestation::report_data(&node_id));
+    let output = tokio::process::Command::new(command)
+        .arg(&report_data)
+        .output()
+        .await?;
+    if !output.status.success() {
+        bail!(
+            "Attestation command {} failed ({}): {}",
+            command.display(),
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    let attestation: Attestation = serde_json::from_slice(&output.stdout)?;
+    attestation.verify_binding(&node_id)?;
+    Ok(attestation)
+}
@@ -0,0 +1,222 @@
+//! Benchmarks of this machine, included in Offers, see `ya_agreement_utils::benchmark`.
+use anyhow::Context;
+use rand::Rng;
+use std::fs::{self, File, OpenOptions};
+use std::io::{Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
+
+pub use ya_agreement_utils::BenchmarkScores;
+
+use crate::events::Event;
+use crate::startup_config::{FileMonitor, FileMonitorConfig};
+
+/// Results of the reference machine, which score 1.0.
+const REFERENCE_CPU_OPS: f64 = 250_000_000.;
+const REFERENCE_MEM_BYTES: f64 = 8. * 1024. * 1024. * 1024.;
+const REFERENCE_DISK_IOPS: f64 = 1_000.;
+
+const CPU_BATCH: u64 = 1_000_000;
+const MEM_BUFFER_BYTES: usize = 64 * 1024 * 1024;
+const DISK_FILE_BYTES: u64 = 16 * 1024 * 1024;
+const DISK_BLOCK_BYTES: usize = 4096;
+const DISK_FILE: &str = "benchmark.tmp";
+
+/// Scores saved by the last `benchmark run`, none if benchmarks weren't run.
+pub fn load(path: &Path) -> anyhow::Result<BenchmarkScores> {
+    if !path.exists() {
+        return Ok(BenchmarkScores::default());
+    }
+    let file = File::open(path)?;
+    serde_json::from_reader(file).with_context(|| format!("invalid {}", path.display()))
+}
+
+pub fn save(path: &Path, scores: &BenchmarkScores) -> anyhow::Result<()> {
+    fs::write(path, serde_json::to_string_pretty(scores)?)?;
+    Ok(())
+}
+
+/// Keeps scores of the benchmark file, which is written by `benchmark run`.
+pub struct Manager {
+    state: Arc<Mutex<BenchmarkScores>>,
+    monitor: Option<FileMonitor>,
+    sender: Option<watch::Sender<Event>>,
+    receiver: watch::Receiver<Event>,
+}
+
+impl Manager {
+    pub fn try_new(path: &Path) -> anyhow::Result<Self> {
+        let (sender, receiver) = watch::channel(Event::Initialized);
+        Ok(Manager {
+            state: Arc::new(Mutex::new(load(path)?)),
+            monitor: None,
+            sender: Some(sender),
+            receiver,
+        })
+    }
+
+    pub fn spawn_monitor(&mut self, path: &Path) -> anyhow::Result<()> {
+        let tx = self.sender.take().unwrap();
+        let state = self.state.clone();
+        let handler = move |p: PathBuf| match load(&p) {
+            Ok(scores) => {
+                let changed = {
+                    let mut state = state.lock().unwrap();
+                    let changed = *state != scores;
+                    *state = scores;
+                    changed
+                };
+                if changed {
+                    log::info!("Benchmark scores updated from {:?}", p);
+                    tx.send(Event::HardwareChanged).unwrap_or_default();
+                }
+            }
+            Err(e) => log::warn!("Error reading benchmark scores from {:?}: {:?}", p, e),
+        };
+        // Benchmark file doesn't exist until benchmarks are run.
+        let monitor = FileMonitor::spawn_with(
+            path,
+            FileMonitor::on_modified(handler),
+            FileMonitorConfig::silent(),
+        )?;
+        self.monitor = Some(monitor);
+        Ok(())
+    }
+
+    pub fn event_receiver(&self) -> watch::Receiver<Event> {
+        self.receiver.clone()
+    }
+
+    pub fn reload(&self) {
+        if let Some(monitor) = &self.monitor {
+            monitor.reload();
+        }
+    }
+
+    pub fn scores(&self) -> BenchmarkScores {
+        self.state.lock().unwrap().clone()
+    }
+}
+
+/// Runs each benchmark for `duration`. Disk is tested in `work_dir`.
+pub fn run(work_dir: &Path, threads: usize, duration: Duration) -> anyhow::Result<BenchmarkScores> {
+    log::info!("Running CPU benchmark on a single thread");
+    let cpu_single = cpu_ops(duration);
+    log::info!("Running CPU benchmark on {} threads", threads);
+    let cpu_multi = (0..threads.max(1))
+        .map(|_| std::thread::spawn(move || cpu_ops(duration)))
+        .collect::<Vec<_>>()
+        .into_iter()
+        .map(|thread| thread.join().unwrap_or_default())
+        .sum::<f64>();
+    log::info!("Running memory bandwidth benchmark");
+    let mem_bandwidth = mem_bytes(duration);
+    log::info!("Running disk benchmark in {}", work_dir.display());
+    let disk_iops =
+        disk_iops(&work_dir.join(DISK_FILE), duration).context("disk benchmark failed")?;
+
+    Ok(BenchmarkScores {
+        cpu_single: Some(score(cpu_single, REFERENCE_CPU_OPS)),
+        cpu_multi: Some(score(cpu_multi, REFERENCE_CPU_OPS)),
+        mem_bandwidth: Some(score(mem_bandwidth, REFERENCE_MEM_BYTES)),
+        disk_iops: Some(score(disk_iops, REFERENCE_DISK_IOPS)),
+    })
+}
+
+fn score(result: f64, reference: f64) -> f64 {
+    (result / reference * 100.).round() / 100.
+}
+
+/// Iterations of a xorshift generator per second.
+fn cpu_ops(duration: Duration) -> f64 {
+    let start = Instant::now();
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    let mut ops = 0;
+    while start.elapsed() < duration {
+        for _ in 0..CPU_BATCH {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+        }
+        ops += CPU_BATCH;
+    }
+    // Keeps the loop from being optimized out.
+    log::trace!("CPU benchmark state: {}", state);
+    ops as f64 / start.elapsed().as_secs_f64()
+}
+
+/// Bytes copied between buffers larger than CPU caches per second.
+fn mem_bytes(duration: Duration) -> f64 {
+    let src = vec![1u8; MEM_BUFFER_BYTES];
+    let mut dst = vec![0u8; MEM_BUFFER_BYTES];
+    let start = Instant::now();
+    let mut bytes = 0;
+    while start.elapsed() < duration {
+        dst.copy_from_slice(&src);
+        bytes += MEM_BUFFER_BYTES;
+    }
+    log::trace!("Memory benchmark state: {}", dst[bytes % MEM_BUFFER_BYTES]);
+    bytes as f64 / start.elapsed().as_secs_f64()
+}
+
+/// Synchronous random writes of 4 KiB blocks per second.
+fn disk_iops(path: &Path, duration: Duration) -> std::io::Result<f64> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .truncate(true)
+        .open(path)?;
+    file.set_len(DISK_FILE_BYTES)?;
+
+    let block = [0xa5u8; DISK_BLOCK_BYTES];
+    let blocks = DISK_FILE_BYTES / DISK_BLOCK_BYTES as u64;
+    let mut rng = rand::thread_rng();
+    let start = Instant::now();
+    let mut ops = 0;
+    let result = loop {
+        if start.elapsed() >= duration {
+            break Ok(ops as f64 / start.elapsed().as_secs_f64());
+        }
+        let offset = rng.gen_range(0..blocks) * DISK_BLOCK_BYTES as u64;
+        if let Err(e) = file
+            .seek(SeekFrom::Start(offset))
+            .and_then(|_| file.write_all(&block))
+            .and_then(|_| file.sync_data())
+        {
+            break Err(e);
+        }
+        ops += 1;
+    };
+
+    drop(file);
+    let _ = fs::remove_file(path);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_score() {
+        assert_eq!(score(REFERENCE_CPU_OPS, REFERENCE_CPU_OPS), 1.);
+        assert_eq!(score(2_000., REFERENCE_DISK_IOPS), 2.);
+        assert_eq!(score(1., 3.), 0.33);
+    }
+
+    #[test]
+    fn test_save_load() {
+        let dir = tempdir::TempDir::new("benchmark").unwrap();
+        let path = dir.path().join("benchmark.json");
+        assert!(load(&path).unwrap().is_empty());
+
+        let scores = BenchmarkScores {
+            cpu_single: Some(1.2),
+            ..Default::default()
+        };
+        save(&path, &scores).unwrap();
+        assert_eq!(load(&path).unwrap(), scores);
+    }
+}
@@ -1,4 +1,5 @@
 //! Command line handling
+pub mod benchmark;
 pub mod clean;
 pub mod config;
 pub mod exe_unit;
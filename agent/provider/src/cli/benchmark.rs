@@ -0,0 +1,58 @@
+use std::time::Duration;
+use structopt::StructOpt;
+
+use crate::benchmark::{self, BenchmarkScores};
+use crate::startup_config::ProviderConfig;
+
+#[derive(StructOpt, Clone, Debug)]
+#[structopt(rename_all = "kebab-case")]
+pub enum BenchmarkConfig {
+    /// Run benchmarks and publish their scores in next Offers
+    Run {
+        /// Number of threads of the multi-core CPU benchmark, all threads by default
+        #[structopt(long)]
+        threads: Option<usize>,
+        /// Duration of each benchmark
+        #[structopt(long, default_value = "3s", parse(try_from_str = humantime::parse_duration))]
+        duration: Duration,
+    },
+    /// Show scores published in Offers
+    Show,
+    /// Stop publishing scores in Offers
+    Clear,
+}
+
+impl BenchmarkConfig {
+    pub fn run(self, config: ProviderConfig) -> anyhow::Result<()> {
+        let path = config.benchmark_file.as_path();
+        match self {
+            BenchmarkConfig::Run { threads, duration } => {
+                let work_dir = config.data_dir.get_or_create()?;
+                let threads = threads.unwrap_or_else(num_cpus::get);
+                let scores = benchmark::run(&work_dir, threads, duration)?;
+                benchmark::save(path, &scores)?;
+                print_scores(&config, &scores)?;
+            }
+            BenchmarkConfig::Show => print_scores(&config, &benchmark::load(path)?)?,
+            BenchmarkConfig::Clear => benchmark::save(path, &BenchmarkScores::default())?,
+        }
+        Ok(())
+    }
+}
+
+fn print_scores(config: &ProviderConfig, scores: &BenchmarkScores) -> anyhow::Result<()> {
+    if config.json {
+        println!("{}", serde_json::to_string_pretty(scores)?);
+        return Ok(());
+    }
+    if scores.is_empty() {
+        println!("Benchmarks weren't run");
+        return Ok(());
+    }
+    let show = |score: Option<f64>| score.map(|s| s.to_string()).unwrap_or_default();
+    println!("CPU single-core:  {}", show(scores.cpu_single));
+    println!("CPU multi-core:   {}", show(scores.cpu_multi));
+    println!("Memory bandwidth: {}", show(scores.mem_bandwidth));
+    println!("Disk IOPS:        {}", show(scores.disk_iops));
+    Ok(())
+}
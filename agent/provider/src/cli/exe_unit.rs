@@ -1,18 +1,100 @@
-use crate::startup_config::ProviderConfig;
+use anyhow::{anyhow, Context};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
 use structopt::StructOpt;
 
+use ya_client_model::activity::{CommandResult, ExeScriptCommandResult};
+
+use crate::execution::{exe_unit_cache_dir, ExeUnitsRegistry};
+use crate::hardware::Profiles;
+use crate::startup_config::ProviderConfig;
+
+const TEST_DIR: &str = "exe-unit-test";
+
 #[derive(StructOpt, Clone, Debug)]
 #[structopt(rename_all = "kebab-case")]
 pub enum ExeUnitsConfig {
     List,
+    /// Run a test task on an ExeUnit to validate the runtime setup
+    Test(TestConfig),
     // TODO: Install command - could download ExeUnit and add to descriptor file.
     // TODO: Update command - could update ExeUnit.
 }
 
+#[derive(StructOpt, Clone, Debug)]
+#[structopt(rename_all = "kebab-case")]
+pub struct TestConfig {
+    /// ExeUnit name
+    pub name: String,
+    /// Task package to deploy. Without it only the runtime self-test is run
+    #[structopt(long)]
+    pub image: Option<String>,
+    /// Benchmark command run after the task package is started
+    #[structopt(long)]
+    pub entry_point: Option<String>,
+    /// Benchmark command argument
+    #[structopt(long = "arg", number_of_values = 1, allow_hyphen_values = true)]
+    pub args: Vec<String>,
+    /// Container path of a file transferred out after the benchmark
+    #[structopt(long)]
+    pub output: Option<String>,
+}
+
+/// Outcome of `exe-unit test`, one step per executed command.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestReport {
+    pub name: String,
+    pub steps: Vec<TestStep>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TestStep {
+    pub name: String,
+    pub passed: bool,
+    pub duration_secs: f64,
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+impl TestReport {
+    pub fn passed(&self) -> bool {
+        self.steps.iter().all(|step| step.passed)
+    }
+
+    pub fn duration_secs(&self) -> f64 {
+        self.steps.iter().map(|step| step.duration_secs).sum()
+    }
+}
+
+impl TestStep {
+    fn new(name: &str, duration: Duration, result: anyhow::Result<()>) -> Self {
+        TestStep {
+            name: name.to_string(),
+            passed: result.is_ok(),
+            duration_secs: duration.as_secs_f64(),
+            message: result.err().map(|e| format!("{:#}", e)),
+        }
+    }
+}
+
+/// Results file written by the ExeUnit started with `from-file --results`.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BatchResults {
+    started_at: DateTime<Utc>,
+    results: Vec<ExeScriptCommandResult>,
+}
+
 impl ExeUnitsConfig {
-    pub fn run(self, config: ProviderConfig) -> anyhow::Result<()> {
+    pub async fn run(self, config: ProviderConfig) -> anyhow::Result<()> {
         match self {
             ExeUnitsConfig::List => list(config),
+            ExeUnitsConfig::Test(test_config) => test(config, test_config).await,
         }
     }
 }
@@ -33,3 +115,166 @@ fn list(config: ProviderConfig) -> anyhow::Result<()> {
     }
     Ok(())
 }
+
+async fn test(config: ProviderConfig, test_config: TestConfig) -> anyhow::Result<()> {
+    let registry = config.registry()?;
+    registry.find_exeunit(&test_config.name)?.validate()?;
+
+    let mut report = TestReport {
+        name: test_config.name.clone(),
+        steps: Vec::new(),
+    };
+
+    let started = Instant::now();
+    let result = registry.test_runtime(&test_config.name);
+    report
+        .steps
+        .push(TestStep::new("runtime test", started.elapsed(), result));
+
+    if let (true, Some(image)) = (report.passed(), &test_config.image) {
+        let started = Instant::now();
+        match run_task(&config, &registry, &test_config, image).await {
+            Ok(steps) => report.steps.extend(steps),
+            Err(e) => report
+                .steps
+                .push(TestStep::new("task", started.elapsed(), Err(e))),
+        }
+    }
+
+    if config.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        println!("ExeUnit [{}] test:\n", report.name);
+        for step in &report.steps {
+            let status = if step.passed { "ok" } else { "FAILED" };
+            println!(
+                "  {:<16}{:>10.3}s  {}",
+                step.name, step.duration_secs, status
+            );
+            if let Some(message) = &step.message {
+                println!("    {}", message);
+            }
+        }
+        println!("\n  {:<16}{:>10.3}s", "total", report.duration_secs());
+    }
+
+    match report.passed() {
+        true => Ok(()),
+        false => Err(anyhow!("ExeUnit [{}] test failed", report.name)),
+    }
+}
+
+/// Runs a task on the active profile's resources, as an Activity would.
+async fn run_task(
+    config: &ProviderConfig,
+    registry: &ExeUnitsRegistry,
+    test_config: &TestConfig,
+    image: &str,
+) -> anyhow::Result<Vec<TestStep>> {
+    let data_dir = config.data_dir.get_or_create()?;
+    let test_dir = data_dir.join(TEST_DIR).join(&test_config.name);
+    if test_dir.exists() {
+        fs::remove_dir_all(&test_dir)?;
+    }
+    let work_dir = test_dir.join("work");
+    fs::create_dir_all(&work_dir)?;
+
+    let profiles = Profiles::load_or_create(config)?;
+    let profile = profiles
+        .get(profiles.active())
+        .ok_or_else(|| anyhow!("Active profile [{}] doesn't exist", profiles.active()))?;
+    let resources = profile.resources.split(profile.slots);
+
+    let agreement = serde_json::json!({
+        "agreementId": format!("{}-test", test_config.name),
+        "demand": {
+            "properties": {
+                "golem": { "srv": { "comp": { "task_package": image } } }
+            }
+        },
+        "offer": {
+            "properties": {
+                "golem": {
+                    "inf": {
+                        "cpu": { "threads": resources.cpu_threads },
+                        "mem": { "gib": resources.mem_gib },
+                        "storage": { "gib": resources.storage_gib },
+                    },
+                    "runtime": { "name": test_config.name },
+                    "com": {
+                        "usage": { "vector": ["golem.usage.duration_sec", "golem.usage.cpu_sec"] }
+                    },
+                }
+            }
+        },
+    });
+
+    let mut steps = vec!["deploy", "start"];
+    let mut commands = vec![
+        serde_json::json!({ "deploy": {} }),
+        serde_json::json!({ "start": { "args": [] } }),
+    ];
+    if let Some(entry_point) = &test_config.entry_point {
+        steps.push("run");
+        commands.push(serde_json::json!({
+            "run": { "entry_point": entry_point, "args": test_config.args }
+        }));
+    }
+    if let Some(output) = &test_config.output {
+        steps.push("transfer");
+        commands.push(serde_json::json!({
+            "transfer": {
+                "from": format!("container:{}", output),
+                "to": format!("file://{}", test_dir.join("output").display()),
+            }
+        }));
+    }
+
+    let agreement_path = test_dir.join("agreement.json");
+    let commands_path = test_dir.join("commands.json");
+    let results_path = test_dir.join("results.json");
+    fs::write(&agreement_path, serde_json::to_string_pretty(&agreement)?)?;
+    fs::write(&commands_path, serde_json::to_string_pretty(&commands)?)?;
+
+    let args = vec![
+        "from-file".to_string(),
+        "--agreement".to_string(),
+        path_arg(&agreement_path),
+        "--work-dir".to_string(),
+        path_arg(&work_dir),
+        "--cache-dir".to_string(),
+        path_arg(&exe_unit_cache_dir(&data_dir)),
+        "--results".to_string(),
+        path_arg(&results_path),
+        path_arg(&commands_path),
+    ];
+    registry
+        .run_exeunit_with_output(&test_config.name, args, &work_dir)
+        .await?;
+
+    let results = fs::read_to_string(&results_path)
+        .context("ExeUnit terminated without finishing the task")?;
+    let results: BatchResults = serde_json::from_str(&results)?;
+
+    let mut previous = results.started_at;
+    Ok(results
+        .results
+        .into_iter()
+        .zip(steps)
+        .map(|(result, name)| {
+            let duration = (result.event_date - previous).to_std().unwrap_or_default();
+            previous = result.event_date;
+            let result = match result.result {
+                CommandResult::Ok => Ok(()),
+                CommandResult::Error => Err(anyhow!(result
+                    .message
+                    .unwrap_or_else(|| "unknown error".to_string()))),
+            };
+            TestStep::new(name, duration, result)
+        })
+        .collect())
+}
+
+fn path_arg(path: &Path) -> String {
+    path.display().to_string()
+}
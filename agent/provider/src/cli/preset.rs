@@ -1,4 +1,4 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 
 use anyhow::{anyhow, bail, Result};
 use dialoguer::{Input, Select};
@@ -217,6 +217,9 @@ pub fn create(config: ProviderConfig, params: PresetNoInteractive) -> anyhow::Re
             .exe_unit
             .ok_or_else(|| anyhow!("ExeUnit is required."))?,
         pricing_model: params.pricing.unwrap_or_else(|| "linear".to_string()),
+        security_profile: params
+            .security_profile
+            .filter(|profile| !profile.is_empty()),
         ..Default::default()
     };
 
@@ -315,6 +318,9 @@ fn update_presets(
             if let Some(new_pricing_model) = params.pricing {
                 preset.pricing_model = new_pricing_model;
             }
+            if let Some(profile) = params.security_profile {
+                preset.security_profile = Some(profile).filter(|profile| !profile.is_empty());
+            }
             let exe_unit_desc = registry.find_exeunit(&preset.exeunit_name)?;
 
             for (name, price) in params.price.iter() {
@@ -355,6 +361,24 @@ fn validate_preset(config: &ProviderConfig, preset: &Preset) -> anyhow::Result<(
         bail!("Not supported pricing model.")
     }
 
+    // Profiles are interpreted by ExeUnit, only their existence is checked here.
+    if let Some(profile) = &preset.security_profile {
+        let profiles: HashMap<String, serde_json::Value> =
+            std::fs::read_to_string(&config.security_profiles_file)
+                .map_err(anyhow::Error::from)
+                .and_then(|content| Ok(serde_json::from_str(&content)?))
+                .map_err(|e| {
+                    anyhow!(
+                        "Can't read security profiles from {}: {}",
+                        config.security_profiles_file.display(),
+                        e
+                    )
+                })?;
+        if !profiles.contains_key(profile) {
+            bail!("Unknown security profile: {:?}", profile)
+        }
+    }
+
     Ok(())
 }
 
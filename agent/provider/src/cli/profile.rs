@@ -1,5 +1,5 @@
 use crate::hardware::ProfileError;
-use crate::hardware::{Profiles, Resources, UpdateResources};
+use crate::hardware::{Profile, Profiles, UpdateResources};
 use crate::startup_config::{ProviderConfig, UpdateNames};
 use structopt::StructOpt;
 
@@ -14,7 +14,7 @@ pub enum ProfileConfig {
     Create {
         name: String,
         #[structopt(flatten)]
-        resources: Resources,
+        profile: Profile,
     },
     /// Update a profile
     Update {
@@ -38,12 +38,12 @@ impl ProfileConfig {
                     let profiles = Profiles::load_or_create(&config)?.list();
                     println!("{}", serde_json::to_string_pretty(&profiles)?);
                 }
-                ProfileConfig::Create { name, resources } => {
+                ProfileConfig::Create { name, profile } => {
                     let mut profiles = Profiles::load_or_create(&config)?;
                     if profiles.get(&name).is_some() {
                         return Err(ProfileError::AlreadyExists(name).into());
                     }
-                    profiles.add(name, resources)?;
+                    profiles.add(name, profile)?;
                     profiles.save(path)?;
                 }
                 ProfileConfig::Update { names, resources } => {
@@ -76,26 +76,29 @@ fn update_profiles(
 ) -> anyhow::Result<()> {
     let mut profiles = Profiles::load_or_create(&config)?;
 
-    fn update_profile(resources: &mut Resources, new_resources: UpdateResources) {
+    fn update_profile(profile: &mut Profile, new_resources: UpdateResources) {
         if let Some(cpu_threads) = new_resources.cpu_threads {
-            resources.cpu_threads = cpu_threads;
+            profile.resources.cpu_threads = cpu_threads;
         }
         if let Some(mem_gib) = new_resources.mem_gib {
-            resources.mem_gib = mem_gib;
+            profile.resources.mem_gib = mem_gib;
         }
         if let Some(storage_gib) = new_resources.storage_gib {
-            resources.storage_gib = storage_gib;
+            profile.resources.storage_gib = storage_gib;
+        }
+        if let Some(slots) = new_resources.slots {
+            profile.slots = slots.max(1);
         }
     }
 
     if names.all {
-        for resources in profiles.list().values_mut() {
-            update_profile(resources, new_resources);
+        for profile in profiles.list().values_mut() {
+            update_profile(profile, new_resources);
         }
     } else {
         for name in names.name {
             match profiles.get_mut(&name) {
-                Some(resources) => update_profile(resources, new_resources),
+                Some(profile) => update_profile(profile, new_resources),
                 _ => return Err(ProfileError::Unknown(name).into()),
             }
         }
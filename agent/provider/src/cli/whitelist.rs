@@ -4,26 +4,38 @@ use structopt::StructOpt;
 
 use ya_manifest_utils::{
     matching::domain::{pattern_to_id, DomainPattern, DomainPatterns},
-    ArgMatch,
+    ArgMatch, OutboundPolicy,
 };
 use ya_utils_cli::{CommandOutput, ResponseTable};
 
 use crate::cli::println_conditional;
+use crate::market::PresetManager;
 use crate::startup_config::ProviderConfig;
 
 #[derive(StructOpt, Clone, Debug)]
 #[structopt(
     rename_all = "kebab-case",
     help = "Domain Whitelist allows to accept Demands with Computation Payload Manifests 
-which declare usage of Outbound Network but arrive with no signature."
+which declare usage of Outbound Network but arrive with no signature.
+Whitelist of a preset replaces the node-global one for its Offers."
 )]
 pub enum WhitelistConfig {
     /// List domain whitelist patterns
-    List,
+    List(List),
     /// Add new domain whitelist patterns
     Add(Add),
     /// Remove domain whitelist patterns
     Remove(Remove),
+    /// Limit outbound ports and traffic of a preset
+    Limit(Limit),
+}
+
+#[derive(StructOpt, Clone, Debug)]
+#[structopt(rename_all = "kebab-case")]
+pub struct List {
+    /// Preset to list patterns of, node-global patterns by default
+    #[structopt(long)]
+    preset: Option<String>,
 }
 
 #[derive(StructOpt, Clone, Debug)]
@@ -47,6 +59,10 @@ Adding URL as a pattern ('regex' or 'strict') will not work."
 Regex patterns are by default wrapped with '.*' patterns."
     )]
     pattern_type: ArgMatch,
+
+    /// Preset to add patterns to, node-global whitelist by default
+    #[structopt(long)]
+    preset: Option<String>,
 }
 
 #[derive(StructOpt, Clone, Debug)]
@@ -58,30 +74,93 @@ pub struct Remove {
 To find pattern's id use 'whitelist list' command."
     )]
     ids: Vec<String>,
+
+    /// Preset to remove patterns from, node-global whitelist by default
+    #[structopt(long)]
+    preset: Option<String>,
+}
+
+#[derive(StructOpt, Clone, Debug)]
+#[structopt(rename_all = "kebab-case")]
+pub struct Limit {
+    /// Preset to limit
+    #[structopt(long)]
+    preset: String,
+
+    /// Space separated destination ports allowed, any port when not set
+    #[structopt(long)]
+    ports: Vec<u16>,
+
+    /// Maximum number of bytes exchanged with external hosts by a single Activity
+    #[structopt(long)]
+    max_bytes: Option<u64>,
 }
 
 impl WhitelistConfig {
     pub fn run(self, config: ProviderConfig) -> anyhow::Result<()> {
         match self {
-            WhitelistConfig::List => list(config),
+            WhitelistConfig::List(cmd) => list(config, cmd),
             WhitelistConfig::Add(cmd) => add(config, cmd),
             WhitelistConfig::Remove(cmd) => remove(config, cmd),
+            WhitelistConfig::Limit(cmd) => limit(config, cmd),
+        }
+    }
+}
+
+fn load_patterns(
+    config: &ProviderConfig,
+    preset: &Option<String>,
+) -> anyhow::Result<DomainPatterns> {
+    match preset {
+        Some(name) => {
+            let presets = PresetManager::load_or_create(&config.presets_file)?;
+            Ok(DomainPatterns {
+                patterns: presets.get(name)?.outbound.whitelist,
+            })
         }
+        None => DomainPatterns::load_or_create(&config.domain_whitelist_file),
     }
 }
 
-fn list(config: ProviderConfig) -> anyhow::Result<()> {
-    let domain_patterns = DomainPatterns::load_or_create(&config.domain_whitelist_file)?;
+fn save_patterns(
+    config: &ProviderConfig,
+    preset: &Option<String>,
+    patterns: DomainPatterns,
+) -> anyhow::Result<()> {
+    match preset {
+        Some(name) => update_outbound(config, name, |outbound| {
+            outbound.whitelist = patterns.patterns;
+        }),
+        None => patterns.save(&config.domain_whitelist_file),
+    }
+}
+
+fn update_outbound(
+    config: &ProviderConfig,
+    preset: &str,
+    f: impl FnOnce(&mut OutboundPolicy),
+) -> anyhow::Result<()> {
+    let mut presets = PresetManager::load_or_create(&config.presets_file)?;
+    presets.update_preset(preset, |preset| {
+        f(&mut preset.outbound);
+        Ok(())
+    })?;
+    presets.save_to_file(&config.presets_file)
+}
+
+fn list(config: ProviderConfig, list: List) -> anyhow::Result<()> {
+    let domain_patterns = load_patterns(&config, &list.preset)?;
     let table = WhitelistTable::from(domain_patterns);
     table.print(&config)
 }
 
 fn add(config: ProviderConfig, add: Add) -> anyhow::Result<()> {
-    let domain_patterns = DomainPatterns::load_or_create(&config.domain_whitelist_file)?;
+    let preset = add.preset.clone();
+    let domain_patterns = load_patterns(&config, &preset)?;
     let mut domain_patterns = DomainPatternIds::from(domain_patterns);
     let added = domain_patterns.add(add);
     let domain_patterns: DomainPatterns = domain_patterns.into();
-    domain_patterns.save(&config.domain_whitelist_file)?;
+    save_patterns(&config, &preset, domain_patterns)?;
     if !added.processed.is_empty() {
         println_conditional(&config, "Added patterns:");
         WhitelistTable::from(DomainPatterns {
@@ -109,11 +188,11 @@ fn add(config: ProviderConfig, add: Add) -> anyhow::Result<()> {
 }
 
 fn remove(config: ProviderConfig, remove: Remove) -> anyhow::Result<()> {
-    let domain_patterns = DomainPatterns::load_or_create(&config.domain_whitelist_file)?;
+    let domain_patterns = load_patterns(&config, &remove.preset)?;
     let mut domain_patterns = DomainPatternIds::from(domain_patterns);
     let removed = domain_patterns.remove(remove.ids);
     let domain_patterns: DomainPatterns = domain_patterns.into();
-    domain_patterns.save(&config.domain_whitelist_file)?;
+    save_patterns(&config, &remove.preset, domain_patterns)?;
     if !removed.processed.is_empty() {
         let table = WhitelistTable::from(DomainPatterns {
             patterns: removed.processed,
@@ -133,6 +212,21 @@ fn remove(config: ProviderConfig, remove: Remove) -> anyhow::Result<()> {
     Ok(())
 }
 
+fn limit(config: ProviderConfig, limit: Limit) -> anyhow::Result<()> {
+    update_outbound(&config, &limit.preset, |outbound| {
+        outbound.ports = limit.ports;
+        outbound.max_bytes = limit.max_bytes;
+    })?;
+    let presets = PresetManager::load_or_create(&config.presets_file)?;
+    let outbound = presets.get(&limit.preset)?.outbound;
+    if config.json {
+        println!("{}", serde_json::to_string_pretty(&outbound)?);
+    } else {
+        println!("Outbound of preset [{}] limited.", limit.preset);
+    }
+    Ok(())
+}
+
 struct WhitelistTable {
     table: ResponseTable,
 }
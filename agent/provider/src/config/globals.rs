@@ -13,7 +13,7 @@ fn default_subnet() -> Option<String> {
     Some(DEFAULT_SUBNET.into())
 }
 
-#[derive(Clone, Debug, Default, Serialize, derive_more::Display)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, derive_more::Display)]
 #[display(
     fmt = "{}{}{}",
     "node_name.as_ref().map(|nn| format!(\"Node name: {}\", nn)).unwrap_or_else(|| \"\".into())",
@@ -151,6 +151,8 @@ impl From<PresetV0> for Preset {
                     _ => None,
                 })
                 .collect(),
+            outbound: Default::default(),
+            security_profile: None,
         }
     }
 }
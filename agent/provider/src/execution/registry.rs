@@ -315,6 +315,14 @@ impl ExeUnitsRegistry {
 
         Ok(())
     }
+
+    pub fn test_runtime(&self, name: &str) -> anyhow::Result<()> {
+        self.find_exeunit(name)?
+            .runtime_path
+            .as_ref()
+            .map(|p| test_runtime(p))
+            .unwrap_or(Ok(()))
+    }
 }
 
 #[derive(Error, Debug)]
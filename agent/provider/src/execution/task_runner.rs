@@ -29,6 +29,7 @@ use super::registry::{ExeUnitDesc, ExeUnitsRegistry};
 use super::task::Task;
 use crate::market::provider_market::NewAgreement;
 use crate::market::Preset;
+use crate::startup_config::SECURITY_PROFILES_JSON;
 use crate::tasks::{AgreementBroken, AgreementClosed};
 
 const EXE_UNIT_DIR: &str = "exe-unit";
@@ -128,6 +129,12 @@ pub struct TaskRunnerConfig {
     pub process_termination_timeout: Duration,
     #[structopt(long, env, parse(try_from_str = humantime::parse_duration), default_value = "10s")]
     pub exeunit_state_retry_interval: Duration,
+    /// Delegated cgroup v2 directory, in which ExeUnits place Activities enforcing their limits
+    #[structopt(long, env)]
+    pub exe_unit_cgroup: Option<PathBuf>,
+    /// Disk bandwidth limit of an Activity in bytes per second, requires `exe-unit-cgroup`
+    #[structopt(long, env)]
+    pub exe_unit_io_max_bps: Option<u64>,
     #[structopt(skip = "you-forgot-to-set-session-id")]
     pub session_id: String,
 }
@@ -155,6 +162,7 @@ pub struct TaskRunner {
     event_ts: DateTime<Utc>,
     tasks_dir: PathBuf,
     cache_dir: PathBuf,
+    security_profiles_file: PathBuf,
 }
 
 impl TaskRunner {
@@ -206,6 +214,7 @@ impl TaskRunner {
             event_ts: Utc::now(),
             tasks_dir,
             cache_dir,
+            security_profiles_file: data_dir.join(SECURITY_PROFILES_JSON),
         })
     }
 
@@ -273,11 +282,27 @@ impl TaskRunner {
 
         let exeunit_name = exe_unit_name_from(agreement)?;
 
+        // Each slot offers a share of resources, so we can't run more Activities at once.
+        if let Some(slots) = activity_slots_from(agreement) {
+            if self.tasks.len() >= slots as usize {
+                bail!(
+                    "Can't create activity [{}]: all {} activity slot(s) are in use.",
+                    msg.activity_id,
+                    slots
+                );
+            }
+        }
+
+        // ExeUnit reads the profile selected by the Agreement from the profiles file.
+        let security_profiles =
+            security_profile_from(agreement).map(|_| self.security_profiles_file.clone());
+
         let task = match self.create_task(
             &exeunit_name,
             &msg.activity_id,
             &msg.agreement_id,
             msg.requestor_pub_key.as_deref(),
+            security_profiles.as_deref(),
         ) {
             Ok(task) => task,
             Err(error) => bail!("Error creating activity: {:?}: {}", msg, error),
@@ -415,6 +440,7 @@ impl TaskRunner {
         activity_id: &str,
         agreement_id: &str,
         requestor_pub_key: Option<&str>,
+        security_profiles: Option<&Path>,
     ) -> Result<Task> {
         let working_dir = self
             .tasks_dir
@@ -462,6 +488,25 @@ impl TaskRunner {
             args.extend(["--requestor-pub-key", req_pub_key].iter());
         }
 
+        // Bandwidth is enforced in the Activity cgroup.
+        let io_max_bps = self.config.exe_unit_io_max_bps.map(|bps| bps.to_string());
+        if let Some(cgroup) = &self.config.exe_unit_cgroup {
+            args.extend(["--cgroup", cgroup.to_str().ok_or_else(|| anyhow!("None"))?].iter());
+            if let Some(io_max_bps) = &io_max_bps {
+                args.extend(["--io-max-bps", io_max_bps.as_str()].iter());
+            }
+        }
+
+        if let Some(path) = security_profiles {
+            args.extend(
+                [
+                    "--security-profiles",
+                    path.to_str().ok_or_else(|| anyhow!("None"))?,
+                ]
+                .iter(),
+            );
+        }
+
         let args = args.iter().map(ToString::to_string).collect();
 
         log::info!(
@@ -533,6 +578,16 @@ fn exe_unit_name_from(agreement: &AgreementView) -> Result<String> {
     Ok(agreement.pointer_typed::<String>(runtime_key_str)?)
 }
 
+fn activity_slots_from(agreement: &AgreementView) -> Option<u32> {
+    let slots_key_str = "/offer/properties/golem/inf/slots";
+    agreement.pointer_typed::<u32>(slots_key_str).ok()
+}
+
+fn security_profile_from(agreement: &AgreementView) -> Option<String> {
+    let profile_key_str = "/offer/properties/golem/srv/caps/security/profile";
+    agreement.pointer_typed::<String>(profile_key_str).ok()
+}
+
 async fn set_activity_terminated(
     api: Arc<ActivityProviderApi>,
     activity_id: &str,
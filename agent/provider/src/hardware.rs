@@ -71,6 +71,32 @@ pub struct Resources {
     pub storage_gib: f64,
 }
 
+/// Resources shared by up to `slots` concurrently running activities.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, StructOpt)]
+#[structopt(rename_all = "kebab-case")]
+pub struct Profile {
+    #[serde(flatten)]
+    #[structopt(flatten)]
+    pub resources: Resources,
+    /// Number of concurrent activities, each assigned an equal share of resources
+    #[serde(default = "default_slots")]
+    #[structopt(long, default_value = "1")]
+    pub slots: u32,
+}
+
+fn default_slots() -> u32 {
+    1
+}
+
+impl From<Resources> for Profile {
+    fn from(resources: Resources) -> Self {
+        Profile {
+            resources,
+            slots: default_slots(),
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, Serialize, Deserialize, StructOpt)]
 #[structopt(rename_all = "kebab-case")]
 #[structopt(group = clap::ArgGroup::with_name("up-res").multiple(true).required(true))]
@@ -84,6 +110,9 @@ pub struct UpdateResources {
     /// Free partition space
     #[structopt(long, group = "up-res")]
     pub storage_gib: Option<f64>,
+    /// Number of concurrent activities
+    #[structopt(long, group = "up-res")]
+    pub slots: Option<u32>,
 }
 
 impl Resources {
@@ -139,6 +168,16 @@ impl Resources {
         self.cpu_threads <= 0 || self.mem_gib <= 0. || self.storage_gib <= 0.
     }
 
+    /// Share of resources assigned to one of `slots` concurrent activities.
+    pub fn split(&self, slots: u32) -> Self {
+        let slots = slots.max(1);
+        Resources {
+            cpu_threads: MIN_CAPS.cpu_threads.max(self.cpu_threads / slots as i32),
+            mem_gib: self.mem_gib / slots as f64,
+            storage_gib: self.storage_gib / slots as f64,
+        }
+    }
+
     pub fn cap(mut self, res: &Resources) -> Self {
         self.cpu_threads = MIN_CAPS
             .cpu_threads
@@ -218,7 +257,7 @@ impl From<Resources> for InfNodeInfo {
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Profiles {
     active: String,
-    profiles: HashMap<String, Resources>,
+    profiles: HashMap<String, Profile>,
 }
 
 impl Profiles {
@@ -235,7 +274,7 @@ impl Profiles {
                 let mut profiles = Self::try_with_config(path, config)?;
                 let default_caps = Resources::default_caps(path)?;
                 for profile in profiles.profiles.values_mut() {
-                    *profile = profile.cap(&default_caps);
+                    profile.resources = profile.resources.cap(&default_caps);
                 }
                 profiles.save(path)?;
                 Ok(profiles)
@@ -262,33 +301,35 @@ impl Profiles {
     fn try_with_config<P: AsRef<Path>>(path: P, config: &ProviderConfig) -> Result<Self, Error> {
         let resources = Resources::try_with_config(path.as_ref(), config)?;
         let active = DEFAULT_PROFILE_NAME.to_string();
-        let profiles = vec![(active.clone(), resources)].into_iter().collect();
+        let profiles = vec![(active.clone(), resources.into())]
+            .into_iter()
+            .collect();
         Ok(Profiles { active, profiles })
     }
 }
 
 impl Profiles {
     #[inline]
-    pub fn list(&self) -> HashMap<String, Resources> {
+    pub fn list(&self) -> HashMap<String, Profile> {
         self.profiles.clone()
     }
 
     #[inline]
-    pub fn get(&self, name: impl ToString) -> Option<&Resources> {
+    pub fn get(&self, name: impl ToString) -> Option<&Profile> {
         self.profiles.get(&name.to_string())
     }
 
     #[inline]
-    pub fn get_mut(&mut self, name: impl ToString) -> Option<&mut Resources> {
+    pub fn get_mut(&mut self, name: impl ToString) -> Option<&mut Profile> {
         self.profiles.get_mut(&name.to_string())
     }
 
     #[inline]
-    pub fn add(&mut self, name: impl ToString, resources: Resources) -> Result<(), Error> {
-        if resources < Resources::new_empty() {
+    pub fn add(&mut self, name: impl ToString, profile: Profile) -> Result<(), Error> {
+        if profile.resources < Resources::new_empty() || profile.slots == 0 {
             return Err(Error::InsufficientResources);
         }
-        self.profiles.insert(name.to_string(), resources);
+        self.profiles.insert(name.to_string(), profile);
         Ok(())
     }
 
@@ -334,6 +375,7 @@ struct ManagerState {
     res_cap: Resources,
     res_remaining: Resources,
     res_alloc: HashMap<String, Resources>,
+    slots: u32,
 }
 
 impl ManagerState {
@@ -347,23 +389,27 @@ impl ManagerState {
         let name = name.to_string();
         log::info!("Activating hardware profile '{}'", name);
         self.profiles.set_active(&name)?;
-        let res = self
+        let profile = self
             .profiles
             .get(&self.profiles.active)
             .cloned()
-            .ok_or(ProfileError::Unknown(name))?
-            .cap(&self.res_available);
+            .ok_or(ProfileError::Unknown(name))?;
+        let res = profile.resources.cap(&self.res_available);
+        let slots = profile.slots.max(1);
 
-        if res == self.res_cap {
-            Ok(false)
-        } else {
+        if res == self.res_cap && slots == self.slots {
+            return Ok(false);
+        }
+        if res != self.res_cap {
             let delta = self.res_cap - res;
             self.res_cap = res;
             self.res_remaining = self.res_remaining - delta;
             log::info!("Hardware resources cap: {:?}", self.res_cap);
             log::info!("Hardware resources remaining: {:?}", self.res_remaining);
-            Ok(true)
         }
+        self.slots = slots;
+        log::info!("Concurrent activity slots: {}", self.slots);
+        Ok(true)
     }
 }
 
@@ -377,6 +423,7 @@ impl Manager {
             res_cap: Resources::new_empty(),
             res_remaining: Resources::new_empty(),
             res_alloc: HashMap::new(),
+            slots: default_slots(),
         };
         state.change_profile(state.profiles.active.clone())?;
 
@@ -415,6 +462,13 @@ impl Manager {
     pub fn event_receiver(&self) -> watch::Receiver<Event> {
         self.receiver.clone()
     }
+
+    /// Re-reads hardware profiles file, changes are published like on file modification.
+    pub fn reload(&self) {
+        if let Some(monitor) = &self.monitor {
+            monitor.reload();
+        }
+    }
 }
 
 impl Manager {
@@ -424,6 +478,19 @@ impl Manager {
         state.res_cap
     }
 
+    #[inline]
+    pub fn slots(&self) -> u32 {
+        let state = self.state.lock().unwrap();
+        state.slots
+    }
+
+    /// Resources assigned to a single activity slot.
+    #[inline]
+    pub fn slot_resources(&self) -> Resources {
+        let state = self.state.lock().unwrap();
+        state.res_cap.split(state.slots)
+    }
+
     #[allow(dead_code)]
     pub fn allocate(&mut self, id: String, res: Resources) -> Result<(), Error> {
         let mut state = self.state.lock().unwrap();
@@ -515,7 +582,9 @@ mod tests {
             mem_gib: 8.,
             storage_gib: 100.,
         };
-        let profiles = vec![(active.clone(), resources)].into_iter().collect();
+        let profiles = vec![(active.clone(), resources.into())]
+            .into_iter()
+            .collect();
         Profiles { active, profiles }
     }
 
@@ -573,6 +642,33 @@ mod tests {
         assert_eq!(res.storage_gib, 0.1);
     }
 
+    #[test]
+    fn split_into_slots() {
+        let res = Resources {
+            cpu_threads: 8,
+            mem_gib: 24.,
+            storage_gib: 200.,
+        };
+
+        let slot = res.split(3);
+        assert_eq!(slot.cpu_threads, 2);
+        assert_eq!(slot.mem_gib, 8.);
+        assert_eq!(slot.storage_gib, 200. / 3.);
+
+        let slot = res.split(16);
+        assert_eq!(slot.cpu_threads, 1);
+        assert_eq!(res.split(0), res);
+    }
+
+    #[test]
+    fn profile_without_slots() {
+        let profile: Profile =
+            serde_json::from_str(r#"{"cpu_threads": 4, "mem_gib": 8.0, "storage_gib": 100.0}"#)
+                .unwrap();
+        assert_eq!(profile.slots, 1);
+        assert_eq!(profile.resources.cpu_threads, 4);
+    }
+
     #[test]
     fn allocation() {
         let res = Resources {
@@ -586,6 +682,7 @@ mod tests {
             res_remaining: res,
             res_alloc: HashMap::new(),
             profiles: profiles(),
+            slots: 1,
         };
         let (tx, rx) = watch::channel(Event::Initialized);
         let mut man = Manager {
@@ -626,6 +723,7 @@ mod tests {
             res_remaining: res,
             res_alloc: HashMap::new(),
             profiles: profiles(),
+            slots: 1,
         };
         let (tx, rx) = watch::channel(Event::Initialized);
         let mut man = Manager {
@@ -1,3 +1,6 @@
+pub mod alerts;
+pub mod attestation;
+pub mod benchmark;
 pub mod cli;
 pub mod config;
 pub mod dir;
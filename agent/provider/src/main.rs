@@ -2,7 +2,7 @@ use actix::Actor;
 use std::env;
 use structopt::{clap, StructOpt};
 
-use ya_provider::provider_agent::{Initialize, ProviderAgent, Shutdown};
+use ya_provider::provider_agent::{Initialize, ProviderAgent, Reload, Shutdown};
 use ya_provider::signal::SignalMonitor;
 use ya_provider::startup_config::{Commands, StartupConfig};
 use ya_utils_process::lock::ProcLock;
@@ -15,7 +15,7 @@ async fn main() -> anyhow::Result<()> {
     match &cli_args.commands {
         Commands::Run(_) => (), // logging is handled by ProviderAgent
         _ => {
-            ya_file_logging::start_logger("info", None, &[], false)?;
+            ya_file_logging::start_logger("info", None, &[], false, cli_args.config.log_format)?;
         }
     }
 
@@ -27,6 +27,8 @@ async fn main() -> anyhow::Result<()> {
     config.presets_file = data_dir.join(config.presets_file);
     config.hardware_file = data_dir.join(config.hardware_file);
     config.rules_file = data_dir.join(config.rules_file);
+    config.security_profiles_file = data_dir.join(config.security_profiles_file);
+    config.benchmark_file = data_dir.join(config.benchmark_file);
 
     match cli_args.commands {
         Commands::Run(args) => {
@@ -35,8 +37,17 @@ async fn main() -> anyhow::Result<()> {
             let agent = ProviderAgent::new(args, config).await?.start();
             agent.send(Initialize).await??;
 
-            let (_, signal) = SignalMonitor::default().await;
-            log::info!("{} received, Shutting down {}...", signal, app_name);
+            let mut signals = SignalMonitor::default();
+            loop {
+                let (signal, name) = (&mut signals).await;
+                if SignalMonitor::is_reload(signal) {
+                    log::info!("{} received, reloading configuration...", name);
+                    agent.send(Reload).await?;
+                    continue;
+                }
+                log::info!("{} received, Shutting down {}...", name, app_name);
+                break;
+            }
             agent.send(Shutdown).await??;
             Ok(())
         }
@@ -44,10 +55,11 @@ async fn main() -> anyhow::Result<()> {
         Commands::Preset(presets_cmd) => presets_cmd.run(config),
         Commands::PreInstall(preinstall_cmd) => preinstall_cmd.run(config),
         Commands::Profile(profile_cmd) => profile_cmd.run(config),
-        Commands::ExeUnit(exe_unit_cmd) => exe_unit_cmd.run(config),
+        Commands::ExeUnit(exe_unit_cmd) => exe_unit_cmd.run(config).await,
         Commands::Keystore(keystore_cmd) => keystore_cmd.run(config),
         Commands::Whitelist(whitelist_cmd) => whitelist_cmd.run(config),
         Commands::Clean(clean_cmd) => clean_cmd.run(config),
         Commands::Rule(outbound_cmd) => outbound_cmd.run(config),
+        Commands::Benchmark(benchmark_cmd) => benchmark_cmd.run(config),
     }
 }
@@ -19,4 +19,12 @@ pub struct MarketConfig {
     pub session_id: String,
     #[structopt(long, env, parse(try_from_str = humantime::parse_duration), default_value = "20s")]
     pub process_market_events_timeout: std::time::Duration,
+    /// Offers are resubscribed after this time, so they don't expire on the market.
+    /// Should be shorter than subscription TTL of yagna. Zero disables renewal,
+    /// then Offers are resubscribed only after they expire.
+    #[structopt(long, env, parse(try_from_str = humantime::parse_duration), default_value = "0s")]
+    pub offer_renewal_interval: std::time::Duration,
+    /// Offers are renewed randomly up to this time earlier, to spread their broadcasts.
+    #[structopt(long, env, parse(try_from_str = humantime::parse_duration), default_value = "2min")]
+    pub offer_renewal_jitter: std::time::Duration,
 }
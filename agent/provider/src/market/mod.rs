@@ -2,7 +2,8 @@ pub mod config;
 pub mod negotiator;
 pub mod presets;
 pub mod provider_market;
+pub mod reputation;
 pub mod termination_reason;
 
-pub use presets::{Preset, PresetManager, Presets};
+pub use presets::{Preset, PresetManager, Presets, SECURITY_PROFILE_PROPERTY};
 pub use provider_market::{CreateOffer, ProviderMarket};
@@ -3,7 +3,7 @@ use std::ops::Not;
 use ya_agreement_utils::{Error, OfferDefinition};
 use ya_manifest_utils::policy::{Match, Policy, PolicyConfig};
 use ya_manifest_utils::{
-    decode_manifest, Feature, CAPABILITIES_PROPERTY, DEMAND_MANIFEST_CERT_PROPERTY,
+    decode_manifest, Feature, OutboundPolicy, CAPABILITIES_PROPERTY, DEMAND_MANIFEST_CERT_PROPERTY,
     DEMAND_MANIFEST_NODE_DESCRIPTOR_PROPERTY, DEMAND_MANIFEST_PROPERTY,
     DEMAND_MANIFEST_SIG_ALGORITHM_PROPERTY, DEMAND_MANIFEST_SIG_PROPERTY,
 };
@@ -67,11 +67,16 @@ impl NegotiatorComponent for ManifestSignature {
             .ok();
 
         if manifest.is_outbound_requested() {
+            let policy = match OutboundPolicy::from_offer(&offer) {
+                Ok(policy) => policy,
+                Err(e) => return rejection(format!("invalid outbound policy: {:?}", e)),
+            };
             match self.rules_manager.check_outbound_rules(
                 manifest,
                 demand.issuer,
                 manifest_sig,
                 node_descriptor,
+                &policy,
             ) {
                 crate::rules::CheckRulesResult::Accept => acceptance(offer),
                 crate::rules::CheckRulesResult::Reject(msg) => rejection(msg),
@@ -8,16 +8,21 @@ use crate::market::negotiator::{
     AgreementResult, NegotiationResult, NegotiatorComponent, ProposalView,
 };
 
+const SLOTS_PROPERTY: &str = "/golem/inf/slots";
+
 /// Negotiator that can limit number of running agreements.
+/// Offers with activity slots allow at least as many agreements as slots.
 pub struct MaxAgreements {
     active_agreements: HashSet<String>,
     max_agreements: u32,
+    configured_max_agreements: u32,
 }
 
 impl MaxAgreements {
     pub fn new(config: &LimitAgreementsNegotiatorConfig) -> MaxAgreements {
         MaxAgreements {
             max_agreements: config.max_simultaneous_agreements,
+            configured_max_agreements: config.max_simultaneous_agreements,
             active_agreements: HashSet::new(),
         }
     }
@@ -54,6 +59,12 @@ impl NegotiatorComponent for MaxAgreements {
         &mut self,
         offer_template: OfferDefinition,
     ) -> anyhow::Result<OfferDefinition> {
+        let slots = offer_template
+            .clone()
+            .into_template()
+            .pointer_typed::<u32>(SLOTS_PROPERTY)
+            .unwrap_or(1);
+        self.max_agreements = self.configured_max_agreements.max(slots);
         Ok(offer_template)
     }
 
@@ -64,7 +75,8 @@ impl NegotiatorComponent for MaxAgreements {
     ) -> anyhow::Result<()> {
         self.active_agreements.remove(agreement_id);
 
-        let free_slots = self.max_agreements as usize - self.active_agreements.len();
+        let free_slots =
+            (self.max_agreements as usize).saturating_sub(self.active_agreements.len());
         log::info!("Negotiator: {} free slot(s) for agreements.", free_slots);
         Ok(())
     }
@@ -72,6 +72,8 @@ pub struct CreateOffer {
 pub struct ReactToProposal {
     pub prev_proposal: Proposal,
     pub demand: Proposal,
+    /// Local reputation score of Requestor. None if it couldn't be queried.
+    pub reputation: Option<f64>,
 }
 
 /// Reactions to events from market. These function make market decisions
@@ -141,11 +143,13 @@ impl NegotiatorAddr {
         &self,
         prev_proposal: Proposal,
         demand: Proposal,
+        reputation: Option<f64>,
     ) -> Result<ProposalResponse> {
         self.on_proposal
             .send(ReactToProposal {
                 demand,
                 prev_proposal,
+                reputation,
             })
             .await?
     }
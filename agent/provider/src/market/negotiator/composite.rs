@@ -24,6 +24,7 @@ use crate::provider_agent::AgentNegotiatorsConfig;
 /// Negotiator that can limit number of running agreements.
 pub struct CompositeNegotiator {
     components: NegotiatorsPack,
+    min_reputation: f64,
 }
 
 impl CompositeNegotiator {
@@ -57,7 +58,10 @@ impl CompositeNegotiator {
                 )),
             );
 
-        Ok(CompositeNegotiator { components })
+        Ok(CompositeNegotiator {
+            components,
+            min_reputation: config.reputation_config.min_requestor_reputation,
+        })
     }
 }
 
@@ -74,6 +78,26 @@ impl Handler<ReactToProposal> for CompositeNegotiator {
     type Result = anyhow::Result<ProposalResponse>;
 
     fn handle(&mut self, msg: ReactToProposal, _: &mut Context<Self>) -> Self::Result {
+        // Requestors, that we can't get reputation of, aren't filtered.
+        if let Some(score) = msg.reputation {
+            if score < self.min_reputation {
+                log::info!(
+                    "Rejecting Proposal [{}]. Reputation score {:.2} of Requestor [{}] is below {:.2}.",
+                    msg.demand.proposal_id,
+                    score,
+                    msg.demand.issuer_id,
+                    self.min_reputation
+                );
+                return Ok(ProposalResponse::RejectProposal {
+                    reason: Some(reason_with_extra(
+                        format!("Requestor reputation score {:.2} is too low", score),
+                        serde_json::json!({ "golem.proposal.rejection.is-final": true }),
+                    )),
+                    is_final: true,
+                });
+            }
+        }
+
         // In current implementation we don't allow to change constraints, so we take
         // them from initial Offer.
         let constraints = msg.prev_proposal.constraints;
@@ -55,6 +55,15 @@ pub struct PaymentTimeoutConfig {
     pub payment_timeout_required_duration: std::time::Duration,
 }
 
+/// Configuration for filtering Requestors by reputation
+#[derive(StructOpt, Clone, Debug)]
+pub struct ReputationConfig {
+    /// Proposals of Requestors with lower local reputation score are rejected.
+    /// Zero disables filtering.
+    #[structopt(long, env, default_value = "0")]
+    pub min_requestor_reputation: f64,
+}
+
 /// Configuration for LimitAgreements Negotiator.
 #[derive(StructOpt, Clone, Debug)]
 pub struct CompositeNegotiatorConfig {
@@ -68,6 +77,8 @@ pub struct CompositeNegotiatorConfig {
     pub payment_timeout_config: PaymentTimeoutConfig,
     #[structopt(flatten)]
     pub policy_config: PolicyConfig,
+    #[structopt(flatten)]
+    pub reputation_config: ReputationConfig,
 }
 
 #[derive(StructOpt, Clone, Debug)]
@@ -8,11 +8,16 @@ use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
 use tokio::sync::watch;
 
+use ya_manifest_utils::OutboundPolicy;
+
 pub use crate::config::presets::Presets;
 use crate::events::Event;
 use crate::execution::ExeUnitsRegistry;
 use crate::startup_config::FileMonitor;
 
+/// Offer property naming the security profile, which ExeUnit applies to the runtime.
+pub const SECURITY_PROFILE_PROPERTY: &str = "golem.srv.caps.security.profile";
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(rename_all = "kebab-case")]
 /// Preset describing offer, that can be saved and loaded from disk.
@@ -23,6 +28,12 @@ pub struct Preset {
     pub initial_price: f64,
     // It's important that all values are sorted, so that other tools can easily detect changes.
     pub usage_coeffs: BTreeMap<String, f64>,
+    /// Outbound network access of Activities, node-global rules apply when empty.
+    #[serde(default, skip_serializing_if = "OutboundPolicy::is_empty")]
+    pub outbound: OutboundPolicy,
+    /// Security profile from `security_profiles.json` applied to runtimes of Activities.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub security_profile: Option<String>,
 }
 
 impl Preset {
@@ -84,6 +95,13 @@ impl PresetManager {
         self.receiver.clone()
     }
 
+    /// Re-reads presets file, changes are published like on file modification.
+    pub fn reload(&self) {
+        if let Some(monitor) = &self.monitor {
+            monitor.reload();
+        }
+    }
+
     pub fn load_or_create(presets_file: &Path) -> Result<PresetManager> {
         if presets_file.exists() {
             Self::from_file(presets_file)
@@ -226,6 +244,8 @@ impl Default for Preset {
             exeunit_name: "wasmtime".to_string(),
             pricing_model: "linear".to_string(),
             usage_coeffs,
+            outbound: Default::default(),
+            security_profile: None,
         }
     }
 }
@@ -236,6 +256,8 @@ impl PartialEq for Preset {
             && self.exeunit_name == other.exeunit_name
             && self.pricing_model == other.pricing_model
             && self.usage_coeffs == other.usage_coeffs
+            && self.outbound == other.outbound
+            && self.security_profile == other.security_profile
     }
 }
 
@@ -291,5 +313,52 @@ fn display_preset(
         )?;
     }
 
+    let outbound = &preset.outbound;
+    if !outbound.is_empty() {
+        writeln!(f, "Outbound:")?;
+        if !outbound.whitelist.is_empty() {
+            let domains: Vec<&str> = outbound
+                .whitelist
+                .iter()
+                .map(|pattern| pattern.domain.as_str())
+                .collect();
+            writeln!(
+                f,
+                "    {:width$}{}",
+                "Whitelist:",
+                domains.join(", "),
+                width = align_coeff
+            )?;
+        }
+        if !outbound.ports.is_empty() {
+            let ports: Vec<String> = outbound.ports.iter().map(u16::to_string).collect();
+            writeln!(
+                f,
+                "    {:width$}{}",
+                "Ports:",
+                ports.join(", "),
+                width = align_coeff
+            )?;
+        }
+        if let Some(max_bytes) = outbound.max_bytes {
+            writeln!(
+                f,
+                "    {:width$}{}",
+                "Data cap:",
+                bytesize::to_string(max_bytes, true),
+                width = align_coeff
+            )?;
+        }
+    }
+    if let Some(profile) = &preset.security_profile {
+        writeln!(
+            f,
+            "{:width$}{}",
+            "Security profile:",
+            profile,
+            width = align
+        )?;
+    }
+
     Ok(())
 }
@@ -2,7 +2,7 @@ use actix::prelude::*;
 use actix::AsyncContext;
 use anyhow::{anyhow, Error, Result};
 use backoff::backoff::Backoff;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use derive_more::Display;
 use futures::prelude::*;
 use futures_util::FutureExt;
@@ -10,6 +10,7 @@ use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::time::timeout;
 
 use ya_agreement_utils::{AgreementView, OfferDefinition};
@@ -31,11 +32,14 @@ use super::negotiator::{AgreementResponse, AgreementResult, NegotiatorAddr, Prop
 use super::Preset;
 use crate::display::EnableDisplay;
 use crate::market::config::MarketConfig;
+use crate::market::reputation::ReputationApi;
 use crate::market::termination_reason::GolemReason;
 use crate::provider_agent::AgentNegotiatorsConfig;
 use crate::tasks::task_manager::ClosingCause;
 use crate::tasks::{AgreementBroken, AgreementClosed, CloseAgreement};
 
+const RENEWAL_RETRY_DELAY: Duration = Duration::from_secs(30);
+
 // =========================================== //
 // Public exposed messages
 // =========================================== //
@@ -56,6 +60,24 @@ pub struct Shutdown;
 #[rtype(result = "Result<()>")]
 pub struct Unsubscribe(pub OfferKind);
 
+/// Fetches current version of the Agreement, which could be amended since it was signed.
+#[derive(Message)]
+#[rtype(result = "Result<AgreementView>")]
+pub struct GetAgreement {
+    pub agreement_id: String,
+}
+
+/// Returns state of the Provider on the market.
+#[derive(Message)]
+#[rtype(result = "MarketStatus")]
+pub struct GetMarketStatus;
+
+#[derive(Clone, Debug, MessageResponse)]
+pub struct MarketStatus {
+    pub offers: usize,
+    pub last_proposal: Option<DateTime<Utc>>,
+}
+
 pub enum OfferKind {
     Any,
     WithPresets(Vec<String>),
@@ -83,6 +105,10 @@ struct Subscription {
     offer: NewOffer,
 }
 
+#[derive(Message)]
+#[rtype(result = "()")]
+struct ProposalReceived;
+
 #[derive(Message)]
 #[rtype(result = "Result<()>")]
 struct AgreementFinalized {
@@ -113,8 +139,10 @@ pub struct SubscriptionProposal {
 pub struct ProviderMarket {
     negotiator: Arc<NegotiatorAddr>,
     api: Arc<MarketProviderApi>,
+    reputation: Arc<ReputationApi>,
     subscriptions: HashMap<String, Subscription>,
     postponed_demands: Vec<SubscriptionProposal>,
+    last_proposal: Option<DateTime<Utc>>,
     config: Arc<MarketConfig>,
     agent_negotiators_cfg: Arc<AgentNegotiatorsConfig>,
 
@@ -131,6 +159,7 @@ struct AsyncCtx {
     market: Addr<ProviderMarket>,
     config: Arc<MarketConfig>,
     api: Arc<MarketProviderApi>,
+    reputation: Arc<ReputationApi>,
     negotiator: Arc<NegotiatorAddr>,
 }
 
@@ -141,14 +170,17 @@ impl ProviderMarket {
 
     pub fn new(
         api: MarketProviderApi,
+        reputation: ReputationApi,
         config: MarketConfig,
         agent_negotiators_cfg: AgentNegotiatorsConfig,
     ) -> ProviderMarket {
         ProviderMarket {
             negotiator: Arc::new(NegotiatorAddr::default()),
             api: Arc::new(api),
+            reputation: Arc::new(reputation),
             subscriptions: HashMap::new(),
             postponed_demands: Vec::new(),
+            last_proposal: None,
             config: Arc::new(config),
             agent_negotiators_cfg: Arc::new(agent_negotiators_cfg),
             agreement_signed_signal: SignalSlot::<NewAgreement>::default(),
@@ -161,6 +193,7 @@ impl ProviderMarket {
         AsyncCtx {
             config: self.config.clone(),
             api: self.api.clone(),
+            reputation: self.reputation.clone(),
             market: ctx.address(),
             negotiator: self.negotiator.clone(),
         }
@@ -178,6 +211,14 @@ impl ProviderMarket {
             ctx.spawn(collect_negotiation_events(actx, msg.clone()).into_actor(self));
 
         self.handles.insert(msg.id.clone(), abort_handle);
+        if !self.config.offer_renewal_interval.is_zero() {
+            let jitter = self
+                .config
+                .offer_renewal_jitter
+                .mul_f64(rand::random::<f64>());
+            let delay = self.config.offer_renewal_interval.saturating_sub(jitter);
+            ctx.notify_later(RenewOffer(msg.id.clone()), delay);
+        }
         self.subscriptions.insert(msg.id.clone(), msg);
         Ok(())
     }
@@ -257,6 +298,7 @@ async fn dispatch_event(
 ) -> Result<()> {
     match event {
         ProviderEvent::ProposalEvent { proposal, .. } => {
+            ctx.market.do_send(ProposalReceived);
             process_proposal(ctx, subscription, proposal).await
         }
         ProviderEvent::AgreementEvent { agreement, .. } => {
@@ -322,9 +364,22 @@ async fn process_proposal(
         },
     };
 
+    let reputation = ctx
+        .reputation
+        .score(&demand.issuer_id)
+        .await
+        .map_err(|e| {
+            log::debug!(
+                "Failed to get reputation of Requestor [{}]. {}",
+                demand.issuer_id,
+                e
+            )
+        })
+        .ok();
+
     let action = ctx
         .negotiator
-        .react_to_proposal(prev_proposal, demand.clone())
+        .react_to_proposal(prev_proposal, demand.clone(), reputation)
         .await
         .map_err(|e| {
             anyhow!(
@@ -565,6 +620,70 @@ impl Handler<ReSubscribe> for ProviderMarket {
     }
 }
 
+/// Subscribes the same Offer again before the old subscription expires
+/// and unsubscribes the old one, so the Provider doesn't drop off the market.
+#[derive(Message)]
+#[rtype(result = "()")]
+struct RenewOffer(String);
+
+impl Handler<RenewOffer> for ProviderMarket {
+    type Result = ActorResponse<Self, ()>;
+
+    fn handle(&mut self, msg: RenewOffer, _ctx: &mut Self::Context) -> Self::Result {
+        let old_id = msg.0;
+        let sub = match self.subscriptions.get(&old_id) {
+            Some(sub) => sub.clone(),
+            // Unsubscribed in the meantime.
+            None => return ActorResponse::reply(()),
+        };
+        let api = self.api.clone();
+        let offer = sub.offer.clone();
+
+        ActorResponse::r#async(
+            async move { api.subscribe(&offer).await }
+                .into_actor(self)
+                .map(move |result, myself, ctx| match result {
+                    Ok(id) => {
+                        log::info!(
+                            "Renewed Offer [{}] of preset [{}] as [{}].",
+                            old_id,
+                            sub.preset.name,
+                            id
+                        );
+                        ctx.notify(Subscription {
+                            id,
+                            offer: sub.offer,
+                            preset: sub.preset,
+                        });
+
+                        let market = ctx.address();
+                        ctx.spawn(
+                            async move {
+                                let msg = Unsubscribe(OfferKind::WithIds(vec![old_id]));
+                                match market.send(msg).await {
+                                    Err(e) => log::warn!("Failed to unsubscribe renewed Offer: {}", e),
+                                    Ok(Err(e)) => log::warn!("Failed to unsubscribe renewed Offer: {}", e),
+                                    Ok(Ok(())) => (),
+                                }
+                            }
+                            .into_actor(myself),
+                        );
+                    }
+                    Err(e) => {
+                        log::warn!(
+                            "Failed to renew Offer [{}] of preset [{}], retrying in {:?}. Error: {}",
+                            old_id,
+                            sub.preset.name,
+                            RENEWAL_RETRY_DELAY,
+                            e
+                        );
+                        ctx.notify_later(RenewOffer(old_id), RENEWAL_RETRY_DELAY);
+                    }
+                }),
+        )
+    }
+}
+
 #[derive(Message)]
 #[rtype(result = "Result<()>")]
 struct PostponeDemand(SubscriptionProposal);
@@ -912,6 +1031,39 @@ impl Handler<Unsubscribe> for ProviderMarket {
     }
 }
 
+impl Handler<ProposalReceived> for ProviderMarket {
+    type Result = ();
+
+    fn handle(&mut self, _msg: ProposalReceived, _ctx: &mut Context<Self>) -> Self::Result {
+        self.last_proposal = Some(Utc::now());
+    }
+}
+
+impl Handler<GetMarketStatus> for ProviderMarket {
+    type Result = MarketStatus;
+
+    fn handle(&mut self, _msg: GetMarketStatus, _ctx: &mut Context<Self>) -> Self::Result {
+        MarketStatus {
+            offers: self.subscriptions.len(),
+            last_proposal: self.last_proposal,
+        }
+    }
+}
+
+impl Handler<GetAgreement> for ProviderMarket {
+    type Result = ResponseFuture<Result<AgreementView>>;
+
+    fn handle(&mut self, msg: GetAgreement, _ctx: &mut Context<Self>) -> Self::Result {
+        let api = self.api.clone();
+        async move {
+            let agreement = api.get_agreement(&msg.agreement_id).await?;
+            AgreementView::try_from(&agreement)
+                .map_err(|e| anyhow!("Invalid agreement. Error: {e}"))
+        }
+        .boxed_local()
+    }
+}
+
 forward_actix_handler!(ProviderMarket, Subscription, on_subscription);
 forward_actix_handler!(ProviderMarket, NewAgreement, on_agreement_approved);
 actix_signal_handler!(ProviderMarket, CloseAgreement, agreement_terminated_signal);
@@ -0,0 +1,51 @@
+use anyhow::{anyhow, bail};
+use serde::Deserialize;
+use std::time::Duration;
+
+use ya_client::cli::ApiOpts;
+use ya_client::model::NodeId;
+use ya_client::web::rest_api_url;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Deserialize)]
+struct Reputation {
+    score: f64,
+}
+
+/// Queries local reputation of Requestors, which yagna market scores
+/// using outcomes of our previous Agreements with them.
+pub struct ReputationApi {
+    url: String,
+    app_key: String,
+}
+
+impl ReputationApi {
+    pub fn new(opts: &ApiOpts) -> anyhow::Result<ReputationApi> {
+        let url = match &opts.market_url {
+            Some(url) => url.clone(),
+            None => rest_api_url().join("market-api/v1/")?,
+        };
+        Ok(ReputationApi {
+            url: format!("{}/reputation", url.as_str().trim_end_matches('/')),
+            app_key: opts.app_key.clone(),
+        })
+    }
+
+    /// Score in range [0, 1]. Nodes without any history have score 0.5.
+    pub async fn score(&self, node_id: &NodeId) -> anyhow::Result<f64> {
+        let url = format!("{}/{}", self.url, node_id);
+        let mut response = awc::Client::new()
+            .get(&url)
+            .bearer_auth(&self.app_key)
+            .timeout(REQUEST_TIMEOUT)
+            .send()
+            .await
+            .map_err(|e| anyhow!("{}", e))?;
+        if !response.status().is_success() {
+            bail!("{} responded with {}", url, response.status());
+        }
+        let reputation: Reputation = response.json().await.map_err(|e| anyhow!("{}", e))?;
+        Ok(reputation.score)
+    }
+}
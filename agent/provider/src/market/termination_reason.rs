@@ -10,6 +10,7 @@ use strum::EnumMessage;
 
 use crate::display::EnableDisplay;
 
+use ya_agreement_utils::termination::{TerminationReason, TERMINATION_REASON_PROPERTY};
 use ya_client::model::market::Reason;
 use ya_client::model::payment::{DebitNoteEventType, Rejection};
 
@@ -46,6 +47,9 @@ pub enum BreakReason {
     #[display(fmt = "Requestor is unreachable more than {}", "_0.display()")]
     #[strum(message = "RequestorUnreachable")]
     RequestorUnreachable(chrono::Duration),
+    #[display(fmt = "Provider is shutting down")]
+    #[strum(message = "Shutdown")]
+    Shutdown,
 }
 
 impl TryFrom<DebitNoteEventType> for BreakReason {
@@ -74,18 +78,27 @@ pub struct GolemReason {
 
 impl GolemReason {
     pub fn new(reason: &BreakReason) -> GolemReason {
-        GolemReason {
-            message: reason.to_string(),
-            code: reason.get_message().unwrap_or("Unknown").to_string(),
-            extra: HashMap::new(),
-        }
+        Self::with_code(
+            reason.to_string(),
+            reason.get_message().unwrap_or("Unknown"),
+        )
     }
 
     pub fn success() -> GolemReason {
+        Self::with_code("Finished with success.".to_string(), "Success")
+    }
+
+    /// Reason with the code classified in `golem.termination.reason`,
+    /// so Requestor doesn't need to know Provider's codes.
+    fn with_code(message: String, code: &str) -> GolemReason {
+        let category = TerminationReason::from_provider_code(Some(code));
         GolemReason {
-            message: "Finished with success.".to_string(),
-            code: "Success".to_string(),
-            extra: HashMap::new(),
+            message,
+            code: code.to_string(),
+            extra: HashMap::from([(
+                TERMINATION_REASON_PROPERTY.to_string(),
+                Value::from(category.as_str()),
+            )]),
         }
     }
 
@@ -110,4 +123,18 @@ mod test {
         let g1: GolemReason = g.to_client().unwrap().to_value().unwrap();
         assert_eq!(g, g1)
     }
+
+    #[test]
+    fn test_termination_reason() {
+        let g = GolemReason::new(&BreakReason::DebitNoteNotPaid(chrono::Duration::hours(1)));
+        assert_eq!(g.code, "DebitNoteNotPaid");
+        assert_eq!(
+            TerminationReason::from_reason(g.to_client().as_ref(), false),
+            TerminationReason::PaymentTimeout
+        );
+        assert_eq!(
+            GolemReason::new(&BreakReason::Shutdown).extra[TERMINATION_REASON_PROPERTY],
+            "operator-shutdown"
+        );
+    }
 }
@@ -7,6 +7,7 @@ use tokio::sync::watch;
 
 use super::factory::PaymentModelFactory;
 use super::model::{PaymentDescription, PaymentModel};
+use super::pricing::AmendedPricing;
 use crate::display::EnableDisplay;
 
 use ya_agreement_utils::AgreementView;
@@ -50,7 +51,13 @@ pub struct AgreementPayment {
     pub agreement_id: String,
     pub approved_ts: DateTime<Utc>,
     pub payment_model: Arc<dyn PaymentModel>,
+    pub usage_coeffs: Vec<f64>,
     pub activities: HashMap<String, ActivityPayment>,
+    // Pricing of Activities, which were running when the Agreement was amended.
+    // Other Activities are priced with `payment_model`.
+    pub activity_models: HashMap<String, Arc<dyn PaymentModel>>,
+    // Cost sent in the last DebitNote of each Activity.
+    pub last_costs: HashMap<String, CostInfo>,
 
     pub update_interval: std::time::Duration,
     pub accept_timeout: Option<chrono::Duration>,
@@ -80,6 +87,7 @@ impl AgreementPayment {
     pub fn new(agreement: &AgreementView) -> Result<AgreementPayment> {
         let payment_description = PaymentDescription::new(agreement)?;
         let payment_model = PaymentModelFactory::create(&payment_description)?;
+        let usage_coeffs = payment_description.get_usage_coefficients()?;
         let update_interval = payment_description.get_update_interval()?;
         let accept_timeout = payment_description.get_debit_note_accept_timeout()?;
         let payment_timeout = payment_description.get_payment_timeout()?;
@@ -107,7 +115,10 @@ impl AgreementPayment {
             agreement_id: agreement.id.clone(),
             approved_ts,
             activities: HashMap::new(),
+            activity_models: HashMap::new(),
+            last_costs: HashMap::new(),
             payment_model,
+            usage_coeffs,
             update_interval,
             accept_timeout,
             payment_timeout,
@@ -172,6 +183,56 @@ impl AgreementPayment {
         Err(anyhow!("Activity [{}] didn't exist before.", activity_id))
     }
 
+    pub fn activity_model(&self, activity_id: &str) -> Arc<dyn PaymentModel> {
+        self.activity_models
+            .get(activity_id)
+            .cloned()
+            .unwrap_or_else(|| self.payment_model.clone())
+    }
+
+    /// Follows pricing of amended Agreement. Running Activities keep cost of their
+    /// last DebitNote, only usage above it is priced with amended coefficients.
+    /// Returns false, if pricing didn't change.
+    pub fn update_pricing(&mut self, agreement: &AgreementView) -> Result<bool> {
+        let payment_description = PaymentDescription::new(agreement)?;
+        let usage_coeffs = payment_description.get_usage_coefficients()?;
+        if usage_coeffs == self.usage_coeffs {
+            return Ok(false);
+        }
+
+        let payment_model = PaymentModelFactory::create(&payment_description)?;
+        if payment_model.expected_usage_len() != self.payment_model.expected_usage_len() {
+            bail!(
+                "Amended pricing expects usage vector of length {}, but Agreement has {}.",
+                payment_model.expected_usage_len(),
+                self.payment_model.expected_usage_len()
+            );
+        }
+
+        for (activity_id, activity) in &self.activities {
+            if let ActivityPayment::Running { .. } = activity {
+                match self.last_costs.get(activity_id) {
+                    Some(last_cost) => {
+                        let pricing = AmendedPricing {
+                            base_usage: last_cost.usage.clone(),
+                            base_cost: last_cost.cost.clone(),
+                            amended: payment_model.clone(),
+                        };
+                        self.activity_models
+                            .insert(activity_id.clone(), Arc::new(pricing));
+                    }
+                    // Nothing was charged yet, so the whole usage is priced with amended model.
+                    None => {
+                        self.activity_models.remove(activity_id);
+                    }
+                }
+            }
+        }
+        self.payment_model = payment_model;
+        self.usage_coeffs = usage_coeffs;
+        Ok(true)
+    }
+
     pub fn count_active_activities(&self) -> usize {
         self.activities
             .iter()
@@ -280,8 +341,23 @@ impl ActivitiesWaiter {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::convert::TryFrom;
     use std::str::FromStr;
 
+    use ya_agreement_utils::agreement::expand;
+
+    fn agreement(coeffs: serde_json::Value) -> AgreementView {
+        AgreementView::try_from(expand(serde_json::json!({
+            "agreementId": "agreement",
+            "timestamp": "2023-01-01T00:00:00Z",
+            "demand.properties": {},
+            "offer.properties": {
+                "golem.com.pricing.model.linear.coeffs": coeffs,
+            },
+        })))
+        .unwrap()
+    }
+
     #[test]
     fn test_round() {
         let x = BigDecimal::from_str("12345.123456789").unwrap();
@@ -296,4 +372,37 @@ mod tests {
         let y = BigDecimal::from_str("12345").unwrap();
         assert_eq!(x.round(15), y);
     }
+
+    #[test]
+    fn test_update_pricing() {
+        let mut payment =
+            AgreementPayment::new(&agreement(serde_json::json!([1.0, 10.0]))).unwrap();
+        payment.add_created_activity("charged");
+        payment.add_created_activity("not-charged");
+        payment.last_costs.insert(
+            "charged".to_string(),
+            CostInfo::new(vec![5.0], BigDecimal::from(15)),
+        );
+
+        let unchanged = agreement(serde_json::json!([1.0, 10.0]));
+        assert!(!payment.update_pricing(&unchanged).unwrap());
+
+        let amended = agreement(serde_json::json!([3.0, 10.0]));
+        assert!(payment.update_pricing(&amended).unwrap());
+
+        // 15 charged for first 5 units of usage, next 2 units cost 3 each.
+        let cost = payment
+            .activity_model("charged")
+            .compute_cost(&[7.0])
+            .unwrap();
+        assert_eq!(cost, BigDecimal::from(21));
+        let cost = payment
+            .activity_model("not-charged")
+            .compute_cost(&[7.0])
+            .unwrap();
+        assert_eq!(cost, BigDecimal::from(31));
+
+        let invalid = agreement(serde_json::json!([1.0, 1.0, 10.0]));
+        assert!(payment.update_pricing(&invalid).is_err());
+    }
 }
@@ -28,7 +28,7 @@ use ya_utils_actix::{actix_signal_handler, forward_actix_handler};
 
 use crate::execution::{ActivityDestroyed, CreateActivity};
 use crate::interval::RelativeInterval;
-use crate::market::provider_market::NewAgreement;
+use crate::market::provider_market::{GetAgreement, NewAgreement, ProviderMarket};
 use crate::market::termination_reason::BreakReason;
 use crate::tasks::{AgreementBroken, AgreementClosed, BreakAgreement};
 
@@ -48,6 +48,13 @@ pub struct UpdateCost {
     pub interval_ctx: RelativeInterval,
 }
 
+/// Checks if Agreement was amended with new pricing.
+#[derive(Message)]
+#[rtype(result = "Result<()>")]
+struct UpdatePricing {
+    pub agreement_id: String,
+}
+
 /// Changes activity state to Finalized and computes final cost.
 /// Sent by ActivityDestroyed handler after last debit note was sent to Requestor.
 #[derive(Message, Clone)]
@@ -139,6 +146,7 @@ struct ProviderCtx {
 /// Sends payments events to Requestor through payment API.
 pub struct Payments {
     context: Arc<ProviderCtx>,
+    market: Addr<ProviderMarket>,
     agreements: HashMap<String, AgreementPayment>,
 
     invoices_to_pay: Vec<Invoice>,
@@ -153,6 +161,7 @@ impl Payments {
     pub fn new(
         activity_api: ActivityProviderApi,
         payment_api: PaymentApi,
+        market: Addr<ProviderMarket>,
         config: PaymentsConfig,
     ) -> Payments {
         let provider_ctx = ProviderCtx {
@@ -166,6 +175,7 @@ impl Payments {
         Payments {
             agreements: HashMap::new(),
             context: Arc::new(provider_ctx),
+            market,
             invoices_to_pay: vec![],
             earnings: BigDecimal::zero(),
             break_agreement_signal: SignalSlot::<BreakAgreement>::default(),
@@ -175,7 +185,7 @@ impl Payments {
     pub fn on_signed_agreement(
         &mut self,
         msg: NewAgreement,
-        _ctx: &mut Context<Self>,
+        ctx: &mut Context<Self>,
     ) -> Result<()> {
         log::info!(
             "Payments got signed agreement [{}]. Waiting for activities creation...",
@@ -184,6 +194,12 @@ impl Payments {
 
         match AgreementPayment::new(&msg.agreement) {
             Ok(agreement) => {
+                ctx.notify_later(
+                    UpdatePricing {
+                        agreement_id: msg.agreement.id.clone(),
+                    },
+                    agreement.update_interval,
+                );
                 self.agreements.insert(msg.agreement.id.clone(), agreement);
                 Ok(())
             }
@@ -558,7 +574,7 @@ impl Handler<ActivityDestroyed> for Payments {
 
         agreement.activity_destroyed(&msg.activity_id).unwrap();
 
-        let payment_model = agreement.payment_model.clone();
+        let payment_model = agreement.activity_model(&msg.activity_id);
         let last_payable_debit_node = match agreement.payment_timeout {
             // Ensure that last debit note is always payable, by
             Some(timeout) => Utc::now() - timeout,
@@ -642,11 +658,11 @@ impl Handler<UpdateCost> for Payments {
                 let last_payable_debit_node = agreement.last_payable_debit_note;
                 let accept_timeout = agreement.accept_timeout;
                 let invoice_info = msg.invoice_info.clone();
-                let payment_model = agreement.payment_model.clone();
+                let payment_model = agreement.activity_model(&msg.invoice_info.activity_id);
                 let context = self.context.clone();
 
                 let debit_note_future = async move {
-                    let (debit_note, _cost) = compute_cost_and_send_debit_note(
+                    let (debit_note, cost) = compute_cost_and_send_debit_note(
                         context.clone(),
                         payment_model.clone(),
                         last_payable_debit_node,
@@ -654,7 +670,7 @@ impl Handler<UpdateCost> for Payments {
                     )
                         .await
                         .log_err()?;
-                    Ok(debit_note)
+                    Ok((debit_note, cost))
                 }
                     .into_actor(self)
                     .map(move |result: Result<_, anyhow::Error>, myself, ctx| {
@@ -676,12 +692,13 @@ impl Handler<UpdateCost> for Payments {
                                     }
                                 }
                             },
-                            Ok(debit_note) => {
+                            Ok((debit_note, cost)) => {
                                 // Payment due date is always set _before_ sending the DebitNote.
                                 // The following synchronises the acceptance timeout check.
                                 if let Some(agreement) = myself.agreements
                                     .get_mut(&msg.invoice_info.agreement_id)
                                     {
+                                        agreement.last_costs.insert(msg.invoice_info.activity_id.clone(), cost);
                                         agreement.last_send_debit_note = debit_note.timestamp;
                                         if debit_note.payment_due_date.is_some() {
                                             agreement.last_payable_debit_note = debit_note.timestamp
@@ -721,6 +738,49 @@ impl Handler<UpdateCost> for Payments {
     }
 }
 
+/// Amended Agreement can change prices, while Activities are running. Pricing is checked
+/// with the same interval as DebitNotes are sent, until the Agreement is paid.
+impl Handler<UpdatePricing> for Payments {
+    type Result = ActorResponse<Self, Result<(), Error>>;
+
+    fn handle(&mut self, msg: UpdatePricing, _ctx: &mut Context<Self>) -> Self::Result {
+        if !self.agreements.contains_key(&msg.agreement_id) {
+            return ActorResponse::reply(Ok(()));
+        }
+
+        let market = self.market.clone();
+        let agreement_id = msg.agreement_id.clone();
+        let future = async move { market.send(GetAgreement { agreement_id }).await? }
+            .into_actor(self)
+            .map(move |result: Result<_>, myself, ctx| {
+                let agreement = match myself.agreements.get_mut(&msg.agreement_id) {
+                    Some(agreement) => agreement,
+                    None => return Ok(()),
+                };
+
+                match result.and_then(|view| agreement.update_pricing(&view)) {
+                    Ok(true) => log::info!(
+                        "Agreement [{}] was amended with new pricing {:?}.",
+                        msg.agreement_id,
+                        agreement.usage_coeffs
+                    ),
+                    Ok(false) => (),
+                    Err(e) => log::warn!(
+                        "Can't check pricing of agreement [{}]. {}",
+                        msg.agreement_id,
+                        e
+                    ),
+                }
+
+                let delay = agreement.update_interval;
+                ctx.notify_later(msg, delay);
+                Ok(())
+            });
+
+        ActorResponse::r#async(future)
+    }
+}
+
 impl Handler<FinalizeActivity> for Payments {
     type Result = <FinalizeActivity as Message>::Result;
 
@@ -1,6 +1,7 @@
 use anyhow::{anyhow, Result};
 use bigdecimal::{BigDecimal, FromPrimitive};
 use serde_json::json;
+use std::sync::Arc;
 
 use ya_agreement_utils::ComInfo;
 use ya_client::model::{payment::Account, NodeId};
@@ -76,6 +77,26 @@ impl LinearPricing {
     }
 }
 
+/// Pricing of Activity running, when its Agreement was amended. Activity keeps
+/// `base_cost` charged before, usage above `base_usage` is priced with `amended` model.
+pub struct AmendedPricing {
+    pub base_usage: Vec<f64>,
+    pub base_cost: BigDecimal,
+    pub amended: Arc<dyn PaymentModel>,
+}
+
+impl PaymentModel for AmendedPricing {
+    fn compute_cost(&self, usage: &[f64]) -> Result<BigDecimal> {
+        let amended_cost =
+            self.amended.compute_cost(usage)? - self.amended.compute_cost(&self.base_usage)?;
+        Ok(&self.base_cost + amended_cost)
+    }
+
+    fn expected_usage_len(&self) -> usize {
+        self.amended.expected_usage_len()
+    }
+}
+
 /// Helper for building offer.
 pub struct LinearPricingOffer {
     interval: f64,
@@ -7,6 +7,7 @@ use std::convert::TryFrom;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, SystemTime};
+use tokio::sync::watch;
 use tokio_stream::wrappers::WatchStream;
 
 use ya_agreement_utils::agreement::TypedArrayPointer;
@@ -16,6 +17,9 @@ use ya_core_model::payment::local::NetworkName;
 use ya_file_logging::{start_logger, LoggerHandle};
 use ya_manifest_utils::{manifest, Feature};
 
+use crate::alerts::Alerts;
+use crate::attestation;
+use crate::benchmark;
 use crate::config::globals::GlobalsState;
 use crate::dir::clean_provider_dir;
 use crate::events::Event;
@@ -25,15 +29,21 @@ use crate::execution::{
 };
 use crate::hardware;
 use crate::market::provider_market::{OfferKind, Shutdown as MarketShutdown, Unsubscribe};
-use crate::market::{CreateOffer, Preset, PresetManager, ProviderMarket};
+use crate::market::reputation::ReputationApi;
+use crate::market::termination_reason::BreakReason;
+use crate::market::{
+    CreateOffer, Preset, PresetManager, ProviderMarket, SECURITY_PROFILE_PROPERTY,
+};
 use crate::payments::{AccountView, LinearPricingOffer, Payments, PricingOffer};
 use crate::rules::RulesManager;
 use crate::startup_config::{FileMonitor, NodeConfig, ProviderConfig, RunConfig};
-use crate::tasks::task_manager::{InitializeTaskManager, TaskManager};
+use crate::tasks::task_manager::{BreakAllAgreements, InitializeTaskManager, TaskManager};
 
 struct GlobalsManager {
     state: Arc<Mutex<GlobalsState>>,
     monitor: Option<FileMonitor>,
+    sender: Option<watch::Sender<Event>>,
+    receiver: watch::Receiver<Event>,
 }
 
 impl GlobalsManager {
@@ -41,17 +51,30 @@ impl GlobalsManager {
         let mut state = GlobalsState::load_or_create(globals_file)?;
         state.update_and_save(node_config, globals_file)?;
 
+        let (sender, receiver) = watch::channel(Event::Initialized);
         Ok(Self {
             state: Arc::new(Mutex::new(state)),
             monitor: None,
+            sender: Some(sender),
+            receiver,
         })
     }
 
     fn spawn_monitor(&mut self, globals_file: &Path) -> anyhow::Result<()> {
+        let tx = self.sender.take().unwrap();
         let state = self.state.clone();
         let handler = move |p: PathBuf| match GlobalsState::load(&p) {
             Ok(new_state) => {
-                *state.lock().unwrap() = new_state;
+                let changed = {
+                    let mut state = state.lock().unwrap();
+                    let changed = *state != new_state;
+                    *state = new_state;
+                    changed
+                };
+                if changed {
+                    log::info!("Global configuration updated from {:?}", p);
+                    tx.send(Event::GlobalsChanged).unwrap_or_default();
+                }
             }
             Err(e) => log::warn!("Error updating global configuration from {:?}: {:?}", p, e),
         };
@@ -60,6 +83,16 @@ impl GlobalsManager {
         Ok(())
     }
 
+    fn event_receiver(&self) -> watch::Receiver<Event> {
+        self.receiver.clone()
+    }
+
+    fn reload(&self) {
+        if let Some(monitor) = &self.monitor {
+            monitor.reload();
+        }
+    }
+
     fn get_state(&self) -> GlobalsState {
         self.state.lock().unwrap().clone()
     }
@@ -84,6 +117,8 @@ pub struct ProviderAgent {
     keystore_monitor: FileMonitor,
     whitelist_monitor: FileMonitor,
     net_api: NetApi,
+    attestation_command: Option<PathBuf>,
+    benchmark: benchmark::Manager,
 }
 
 impl ProviderAgent {
@@ -95,7 +130,7 @@ impl ProviderAgent {
 
         //start_logger is using env var RUST_LOG internally.
         //args.debug options sets default logger to debug
-        let log_handler = start_logger("info", Some(&log_dir), &[], args.debug)?;
+        let log_handler = start_logger("info", Some(&log_dir), &[], args.debug, config.log_format)?;
 
         let app_name = structopt::clap::crate_name!();
         log::info!(
@@ -166,17 +201,28 @@ impl ProviderAgent {
         presets.spawn_monitor(&config.presets_file)?;
         let mut hardware = hardware::Manager::try_new(&config)?;
         hardware.spawn_monitor(&config.hardware_file)?;
+        let mut benchmark = benchmark::Manager::try_new(&config.benchmark_file)?;
+        benchmark.spawn_monitor(&config.benchmark_file)?;
         let (rulestore_monitor, keystore_monitor, whitelist_monitor) =
             rules_manager.spawn_file_monitors()?;
 
         let agent_negotiators_cfg = AgentNegotiatorsConfig { rules_manager };
 
-        let market = ProviderMarket::new(api.market, args.market, agent_negotiators_cfg).start();
-        let payments = Payments::new(api.activity.clone(), api.payment, args.payment).start();
+        let reputation = ReputationApi::new(&args.api)?;
+        let market =
+            ProviderMarket::new(api.market, reputation, args.market, agent_negotiators_cfg).start();
+        let payments = Payments::new(
+            api.activity.clone(),
+            api.payment,
+            market.clone(),
+            args.payment,
+        )
+        .start();
         let runner = TaskRunner::new(api.activity, args.runner, registry, data_dir)?.start();
         let task_manager =
             TaskManager::new(market.clone(), runner.clone(), payments, args.tasks)?.start();
         let net_api = api.net;
+        Alerts::new(args.alerts, market.clone(), net_api.clone())?.start();
 
         Ok(ProviderAgent {
             globals,
@@ -192,6 +238,8 @@ impl ProviderAgent {
             keystore_monitor,
             whitelist_monitor,
             net_api,
+            attestation_command: args.attestation.attestation_command,
+            benchmark,
         })
     }
 
@@ -256,6 +304,12 @@ impl ProviderAgent {
         };
         let (initial_price, prices) = get_prices(pricing_model.as_ref(), &preset, &offer)?;
         offer.set_property("golem.com.usage.vector", get_usage_vector_value(&prices));
+        for (key, value) in preset.outbound.properties() {
+            offer.set_property(key, value);
+        }
+        if let Some(profile) = &preset.security_profile {
+            offer.set_property(SECURITY_PROFILE_PROPERTY, serde_json::json!(profile));
+        }
         offer.add_constraints(Self::build_constraints(node_info.subnet.clone())?);
         let com_info = pricing_model.build(accounts, initial_price, prices)?;
         let srv_info = Self::build_service_info(inf_node_info, exeunit_desc, &offer)?;
@@ -440,14 +494,17 @@ impl Handler<Initialize> for ProviderAgent {
         let preset_state = self.presets.state.clone();
 
         let rx = futures::stream::select_all(vec![
+            WatchStream::new(self.globals.event_receiver()),
             WatchStream::new(self.hardware.event_receiver()),
+            WatchStream::new(self.benchmark.event_receiver()),
             WatchStream::new(self.presets.event_receiver()),
         ]);
 
         tokio::task::spawn_local(async move {
             rx.for_each(|e| async {
                 match e {
-                    Event::HardwareChanged => {
+                    // Node name and subnet are part of every Offer.
+                    Event::HardwareChanged | Event::GlobalsChanged => {
                         let _ = market
                             .send(Unsubscribe(OfferKind::Any))
                             .map_err(|e| log::error!("Cannot unsubscribe offers: {}", e))
@@ -513,12 +570,19 @@ impl Handler<Shutdown> for ProviderAgent {
     fn handle(&mut self, _: Shutdown, _: &mut Context<Self>) -> Self::Result {
         let market = self.market.clone();
         let runner = self.runner.clone();
+        let task_manager = self.task_manager.clone();
         let log_handler = self.log_handler.clone();
         self.keystore_monitor.stop();
         self.rulestore_monitor.stop();
         self.whitelist_monitor.stop();
 
         async move {
+            let break_all = BreakAllAgreements {
+                reason: BreakReason::Shutdown,
+            };
+            if let Err(e) = task_manager.send(break_all).await? {
+                log::warn!("Failed to break agreements on shutdown: {}", e);
+            }
             market.send(MarketShutdown).await??;
             runner.send(ShutdownExecution).await??;
             log_handler.shutdown();
@@ -528,6 +592,21 @@ impl Handler<Shutdown> for ProviderAgent {
     }
 }
 
+impl Handler<Reload> for ProviderAgent {
+    type Result = ();
+
+    fn handle(&mut self, _: Reload, _: &mut Context<Self>) -> Self::Result {
+        log::info!("Reloading configuration files");
+        self.globals.reload();
+        self.presets.reload();
+        self.hardware.reload();
+        self.benchmark.reload();
+        self.rulestore_monitor.reload();
+        self.keystore_monitor.reload();
+        self.whitelist_monitor.reload();
+    }
+}
+
 impl Handler<CreateOffers> for ProviderAgent {
     type Result = ResponseFuture<Result<(), Error>>;
 
@@ -539,7 +618,20 @@ impl Handler<CreateOffers> for ProviderAgent {
             Ok(acc) => acc,
             Err(e) => return Box::pin(async { Err(e) }),
         };
-        let inf_node_info = InfNodeInfo::from(self.hardware.capped());
+        let inf_node_info = {
+            // Offers describe resources of a single slot. Slot count is advertised
+            // only if it differs from the default, so running Activities aren't limited otherwise.
+            let slots = self.hardware.slots();
+            let inf_node_info = InfNodeInfo::from(self.hardware.slot_resources());
+            let inf_node_info = match slots {
+                1 => inf_node_info,
+                slots => inf_node_info.with_slots(slots),
+            };
+            match self.benchmark.scores() {
+                scores if scores.is_empty() => inf_node_info,
+                scores => inf_node_info.with_benchmark(scores),
+            }
+        };
         let preset_names = match msg.0 {
             OfferKind::Any => self.presets.active(),
             OfferKind::WithPresets(names) => names,
@@ -551,8 +643,22 @@ impl Handler<CreateOffers> for ProviderAgent {
         let presets = self.presets.list_matching(&preset_names);
         let globals = self.globals.get_state();
         let net_api = self.net_api.clone();
+        let attestation_command = self.attestation_command.clone();
 
         async move {
+            let inf_node_info = match attestation_command {
+                Some(command) => {
+                    let node_id = net_api.get_status().await?.node_id;
+                    match attestation::quote(&command, node_id).await {
+                        Ok(attestation) => inf_node_info.with_attestation(attestation),
+                        Err(e) => {
+                            log::warn!("Offers created without hardware attestation: {e}");
+                            inf_node_info
+                        }
+                    }
+                }
+                None => inf_node_info,
+            };
             let node_info = Self::build_node_info(globals, net_api).await?;
             Self::create_offers(presets?, node_info, inf_node_info, runner, market, accounts).await
         }
@@ -568,6 +674,12 @@ pub struct Initialize;
 #[rtype(result = "Result<(), Error>")]
 pub struct Shutdown;
 
+/// Applies changes of configuration files to future Offers,
+/// without waiting for file monitors and breaking current Agreements.
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct Reload;
+
 #[derive(Message)]
 #[rtype(result = "Result<(), Error>")]
 struct CreateOffers(pub OfferKind);
@@ -22,7 +22,7 @@ use ya_manifest_utils::{
         domain::{DomainPatterns, DomainWhitelistState, DomainsMatcher},
         Matcher,
     },
-    AppManifest, CompositeKeystore,
+    AppManifest, CompositeKeystore, OutboundPolicy,
 };
 
 #[derive(Clone)]
@@ -271,10 +271,10 @@ impl RulesManager {
         }
     }
 
-    fn check_everyone_rule(&self, manifest: &AppManifest) -> Result<()> {
+    fn check_everyone_rule(&self, manifest: &AppManifest, policy: &OutboundPolicy) -> Result<()> {
         let mode = &self.rulestore.config.read().unwrap().outbound.everyone;
 
-        self.check_mode(mode, manifest)
+        self.check_mode(mode, manifest, policy)
             .map_err(|e| anyhow!("Everyone {e}"))
     }
 
@@ -282,6 +282,7 @@ impl RulesManager {
         &self,
         manifest: &AppManifest,
         manifest_sig: Option<ManifestSignatureProps>,
+        policy: &OutboundPolicy,
     ) -> Result<()> {
         if let Some(props) = manifest_sig {
             let cert_chain_ids = self
@@ -299,7 +300,7 @@ impl RulesManager {
             for cert_id in cert_chain_ids.iter().rev() {
                 if let Some(rule) = rulestore_config.outbound.audited_payload.get(cert_id) {
                     return self
-                        .check_mode(&rule.mode, manifest)
+                        .check_mode(&rule.mode, manifest, policy)
                         .map_err(|e| anyhow!("Audited-Payload {e}"));
                 }
             }
@@ -318,6 +319,7 @@ impl RulesManager {
         manifest: &AppManifest,
         node_descriptor: Option<serde_json::Value>,
         requestor_id: NodeId,
+        policy: &OutboundPolicy,
     ) -> Result<()> {
         let node_descriptor =
             node_descriptor.ok_or_else(|| anyhow!("Partner rule requires node descriptor"))?;
@@ -351,7 +353,7 @@ impl RulesManager {
                 .get(cert_id)
             {
                 return self
-                    .check_mode(&rule.mode, manifest)
+                    .check_mode(&rule.mode, manifest, policy)
                     .map_err(|e| anyhow!("Partner {e}"));
             }
         }
@@ -361,13 +363,18 @@ impl RulesManager {
         ))
     }
 
-    fn check_mode(&self, mode: &Mode, manifest: &AppManifest) -> Result<()> {
+    fn check_mode(
+        &self,
+        mode: &Mode,
+        manifest: &AppManifest,
+        policy: &OutboundPolicy,
+    ) -> Result<()> {
         log::trace!("Checking mode: {mode}");
 
         match mode {
             Mode::All => Ok(()),
             Mode::Whitelist => {
-                if self.whitelist_matching(manifest) {
+                if self.whitelist_matching(manifest, policy)? {
                     log::trace!("Whitelist matched");
 
                     Ok(())
@@ -385,6 +392,7 @@ impl RulesManager {
         requestor_id: NodeId,
         manifest_sig: Option<ManifestSignatureProps>,
         node_descriptor: Option<serde_json::Value>,
+        policy: &OutboundPolicy,
     ) -> CheckRulesResult {
         if self.rulestore.is_outbound_disabled() {
             log::trace!("Checking rules: outbound is disabled.");
@@ -392,10 +400,19 @@ impl RulesManager {
             return CheckRulesResult::Reject("outbound is disabled".into());
         }
 
+        let urls = manifest.get_outbound_requested_urls();
+        let forbidden = policy.forbidden_ports(&urls);
+        if forbidden.is_empty().not() {
+            let forbidden = forbidden.iter().map(|url| url.as_str()).join(", ");
+            return CheckRulesResult::Reject(format!(
+                "Outbound rejected because: ports of {forbidden} are not allowed by preset"
+            ));
+        }
+
         let (accepts, rejects): (Vec<_>, Vec<_>) = vec![
-            self.check_everyone_rule(&manifest),
-            self.check_audited_payload_rule(&manifest, manifest_sig),
-            self.check_partner_rule(&manifest, node_descriptor, requestor_id),
+            self.check_everyone_rule(&manifest, policy),
+            self.check_audited_payload_rule(&manifest, manifest_sig, policy),
+            self.check_partner_rule(&manifest, node_descriptor, requestor_id, policy),
         ]
         .into_iter()
         .partition_result();
@@ -411,9 +428,17 @@ impl RulesManager {
         }
     }
 
-    fn whitelist_matching(&self, manifest: &AppManifest) -> bool {
+    /// Preset whitelist replaces the node-global one, when it's set.
+    fn whitelist_matching(&self, manifest: &AppManifest, policy: &OutboundPolicy) -> Result<bool> {
         let urls = manifest.get_outbound_requested_urls();
-        let matcher = self.whitelist.matchers.read().unwrap();
+        let preset_matcher = match policy.whitelist.is_empty() {
+            true => None,
+            false => Some(DomainsMatcher::try_from(&DomainPatterns {
+                patterns: policy.whitelist.clone(),
+            })?),
+        };
+        let global_matcher = self.whitelist.matchers.read().unwrap();
+        let matcher = preset_matcher.as_ref().unwrap_or(&*global_matcher);
         let non_whitelisted_urls: Vec<&str> = urls
             .iter()
             .flat_map(Url::host_str)
@@ -421,13 +446,13 @@ impl RulesManager {
             .collect();
 
         if non_whitelisted_urls.is_empty() {
-            true
+            Ok(true)
         } else {
             log::debug!(
                 "Whitelist. Non whitelisted URLs: {:?}",
                 non_whitelisted_urls
             );
-            false
+            Ok(false)
         }
     }
 }
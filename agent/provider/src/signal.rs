@@ -26,6 +26,17 @@ impl SignalMonitor {
 
         SignalMonitor { rx, hooks }
     }
+
+    /// SIGHUP asks to reload configuration instead of shutting down.
+    #[cfg(not(windows))]
+    pub fn is_reload(signal: i32) -> bool {
+        signal == SIGHUP
+    }
+
+    #[cfg(windows)]
+    pub fn is_reload(_signal: i32) -> bool {
+        false
+    }
 }
 
 impl Default for SignalMonitor {
@@ -34,7 +45,7 @@ impl Default for SignalMonitor {
         let mut signals = vec![SIGABRT, SIGINT, SIGTERM];
 
         #[cfg(not(windows))]
-        signals.push(SIGQUIT);
+        signals.extend([SIGQUIT, SIGHUP]);
 
         Self::new(signals)
     }
@@ -13,8 +13,12 @@ use strum::VariantNames;
 use ya_client::{cli::ApiOpts, model::node_id::NodeId};
 
 use ya_core_model::payment::local::NetworkName;
+use ya_file_logging::{LogFormat, LOG_FORMAT_ENV_VAR};
 use ya_utils_path::data_dir::DataDir;
 
+use crate::alerts::AlertsConfig;
+use crate::attestation::AttestationConfig;
+use crate::cli::benchmark::BenchmarkConfig;
 use crate::cli::clean::CleanConfig;
 use crate::cli::config::ConfigConfig;
 use crate::cli::exe_unit::ExeUnitsConfig;
@@ -38,6 +42,8 @@ pub(crate) const DOMAIN_WHITELIST_JSON: &str = "domain_whitelist.json";
 pub(crate) const RULES_JSON: &str = "rules.json";
 pub(crate) const PRESETS_JSON: &str = "presets.json";
 pub(crate) const HARDWARE_JSON: &str = "hardware.json";
+pub(crate) const SECURITY_PROFILES_JSON: &str = "security_profiles.json";
+pub(crate) const BENCHMARK_JSON: &str = "benchmark.json";
 pub(crate) const CERT_DIR: &str = "cert-dir";
 
 const DATA_DIR_ENV: &str = "DATA_DIR";
@@ -77,6 +83,15 @@ pub struct ProviderConfig {
         env = "PROVIDER_CERT_DIR",
     )]
     cert_dir: Option<DataDir>,
+    /// Log output format
+    #[structopt(
+        long,
+        set = clap::ArgSettings::Global,
+        env = LOG_FORMAT_ENV_VAR,
+        default_value = "text",
+        possible_values = LogFormat::VARIANTS,
+    )]
+    pub log_format: LogFormat,
     #[structopt(skip = DOMAIN_WHITELIST_JSON)]
     pub domain_whitelist_file: PathBuf,
     #[structopt(skip = GLOBALS_JSON)]
@@ -87,6 +102,10 @@ pub struct ProviderConfig {
     pub hardware_file: PathBuf,
     #[structopt(skip = RULES_JSON)]
     pub rules_file: PathBuf,
+    #[structopt(skip = SECURITY_PROFILES_JSON)]
+    pub security_profiles_file: PathBuf,
+    #[structopt(skip = BENCHMARK_JSON)]
+    pub benchmark_file: PathBuf,
     /// Max number of available CPU cores
     #[structopt(
         long,
@@ -185,6 +204,10 @@ pub struct RunConfig {
     pub payment: PaymentsConfig,
     #[structopt(flatten)]
     pub tasks: TaskConfig,
+    #[structopt(flatten)]
+    pub attestation: AttestationConfig,
+    #[structopt(flatten)]
+    pub alerts: AlertsConfig,
     ///changes log level from info to debug
     #[structopt(long)]
     pub debug: bool,
@@ -200,6 +223,9 @@ pub struct PresetNoInteractive {
     pub pricing: Option<String>,
     #[structopt(long, parse(try_from_str = parse_key_val))]
     pub price: Vec<(String, f64)>,
+    /// Security profile applied to runtimes, empty to remove it
+    #[structopt(long)]
+    pub security_profile: Option<String>,
 }
 
 #[derive(StructOpt, Clone, Debug)]
@@ -247,6 +273,8 @@ pub enum Commands {
     Clean(CleanConfig),
     /// Manage Rule config
     Rule(RuleCommand),
+    /// Benchmark this machine, scores are published in Offers
+    Benchmark(BenchmarkConfig),
 }
 
 #[derive(Debug)]
@@ -254,6 +282,7 @@ pub struct FileMonitor {
     #[allow(dead_code)]
     pub(crate) path: PathBuf,
     pub(crate) thread_ctl: Option<oneshot::Sender<()>>,
+    events: mpsc::Sender<DebouncedEvent>,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -305,6 +334,7 @@ impl FileMonitor {
         let (tx, rx) = mpsc::channel();
         let (tx_ctl, mut rx_ctl) = oneshot::channel();
 
+        let events = tx.clone();
         let mut watcher: RecommendedWatcher = Watcher::new(tx, config.watch_delay)?;
 
         std::thread::spawn(move || {
@@ -346,9 +376,15 @@ impl FileMonitor {
         Ok(Self {
             path,
             thread_ctl: Some(tx_ctl),
+            events,
         })
     }
 
+    /// Handles the monitored path as if it was modified, e.g. on SIGHUP.
+    pub fn reload(&self) {
+        let _ = self.events.send(DebouncedEvent::Write(self.path.clone()));
+    }
+
     pub fn stop(&mut self) {
         if let Some(sender) = self.thread_ctl.take() {
             let _ = sender.send(());
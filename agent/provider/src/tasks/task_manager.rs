@@ -2,7 +2,7 @@
 
 use actix::prelude::*;
 use anyhow::{anyhow, bail, Error, Result};
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use futures::future::TryFutureExt;
 use std::collections::HashMap;
 
@@ -14,7 +14,7 @@ use ya_utils_actix::forward_actix_handler;
 use super::task_info::TaskInfo;
 use super::task_state::{AgreementState, TasksStates};
 use crate::execution::{ActivityDestroyed, CreateActivity, TaskRunner, TerminateActivity};
-use crate::market::provider_market::{NewAgreement, ProviderMarket};
+use crate::market::provider_market::{GetAgreement, NewAgreement, ProviderMarket};
 use crate::market::termination_reason::BreakReason;
 use crate::payments::Payments;
 use crate::tasks::config::TaskConfig;
@@ -47,6 +47,13 @@ pub enum ClosingCause {
     SingleActivity,
 }
 
+/// Breaks all Agreements, which aren't finalized yet, e.g. on Provider shutdown.
+#[derive(Message, Clone)]
+#[rtype(result = "Result<()>")]
+pub struct BreakAllAgreements {
+    pub reason: BreakReason,
+}
+
 /// Notifies TaskManager that Requestor close agreement.
 #[derive(Message, Clone)]
 #[rtype(result = "Result<()>")]
@@ -101,6 +108,14 @@ struct ScheduleExpiration(TaskInfo);
 #[rtype(result = "Result<()>")]
 struct ScheduleIdleExpiration(TaskInfo);
 
+/// Agreement reached its expiration, unless it was extended by an Amendment in the meantime.
+#[derive(Message)]
+#[rtype(result = "Result<()>")]
+struct AgreementExpired {
+    pub agreement_id: String,
+    pub expiration: DateTime<Utc>,
+}
+
 #[derive(Message)]
 #[rtype(result = "Result<()>")]
 struct StartUpdateState {
@@ -168,19 +183,27 @@ impl TaskManager {
             );
         }
 
-        // Schedule agreement termination after expiration time.
+        self.schedule_agreement_expiration(agreement_id, expiration, ctx)?;
+        self.schedule_idle_expiration(ScheduleIdleExpiration(msg.0), ctx)
+    }
+
+    /// Schedule agreement termination after expiration time.
+    fn schedule_agreement_expiration(
+        &mut self,
+        agreement_id: String,
+        expiration: DateTime<Utc>,
+        ctx: &mut Context<Self>,
+    ) -> Result<()> {
         let duration = (expiration - Utc::now()).to_std()?;
-        let agr_id = agreement_id;
         ctx.run_later(duration, move |myself, ctx| {
-            if !myself.tasks.is_agreement_finalized(&agr_id) {
-                ctx.address().do_send(BreakAgreement {
-                    agreement_id: agr_id,
-                    reason: BreakReason::Expired(expiration),
+            if !myself.tasks.is_agreement_finalized(&agreement_id) {
+                ctx.address().do_send(AgreementExpired {
+                    agreement_id,
+                    expiration,
                 });
             }
         });
-
-        self.schedule_idle_expiration(ScheduleIdleExpiration(msg.0), ctx)
+        Ok(())
     }
 
     fn schedule_idle_expiration(
@@ -479,6 +502,57 @@ impl Handler<ActivityDestroyed> for TaskManager {
     }
 }
 
+impl Handler<AgreementExpired> for TaskManager {
+    type Result = ActorResponse<Self, Result<(), Error>>;
+
+    fn handle(&mut self, msg: AgreementExpired, _ctx: &mut Context<Self>) -> Self::Result {
+        let market = self.market.clone();
+        let agreement_id = msg.agreement_id.clone();
+
+        // Requestor could extend the Agreement with an Amendment, so we check
+        // current expiration on the market, before breaking it.
+        let future = async move { market.send(GetAgreement { agreement_id }).await? }
+            .into_actor(self)
+            .map(move |result, myself, ctx| {
+                let extended = match result.and_then(|agreement| TaskInfo::from(&agreement)) {
+                    Ok(info) if info.expiration > msg.expiration => Some(info.expiration),
+                    Ok(_) => None,
+                    Err(e) => {
+                        log::warn!(
+                            "Can't check expiration of agreement [{}]. {}",
+                            msg.agreement_id,
+                            e
+                        );
+                        None
+                    }
+                };
+
+                match extended {
+                    Some(expiration) if expiration > Utc::now() => {
+                        log::info!(
+                            "Agreement [{}] was amended to expire at {}.",
+                            msg.agreement_id,
+                            expiration
+                        );
+                        if let Some(props) = myself.tasks_props.get_mut(&msg.agreement_id) {
+                            props.expiration = expiration;
+                        }
+                        myself.schedule_agreement_expiration(msg.agreement_id, expiration, ctx)
+                    }
+                    _ => {
+                        ctx.address().do_send(BreakAgreement {
+                            agreement_id: msg.agreement_id,
+                            reason: BreakReason::Expired(msg.expiration),
+                        });
+                        Ok(())
+                    }
+                }
+            });
+
+        ActorResponse::r#async(future)
+    }
+}
+
 impl Handler<BreakAgreement> for TaskManager {
     type Result = ActorResponse<Self, Result<(), Error>>;
 
@@ -531,6 +605,38 @@ impl Handler<BreakAgreement> for TaskManager {
     }
 }
 
+impl Handler<BreakAllAgreements> for TaskManager {
+    type Result = ResponseFuture<Result<(), Error>>;
+
+    fn handle(&mut self, msg: BreakAllAgreements, ctx: &mut Context<Self>) -> Self::Result {
+        let myself = ctx.address();
+        let agreements = self
+            .tasks_props
+            .keys()
+            .filter(|agreement_id| !self.tasks.is_agreement_finalized(agreement_id))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        Box::pin(async move {
+            log::info!(
+                "Breaking {} agreement(s), reason: {}.",
+                agreements.len(),
+                msg.reason
+            );
+            let breaks = agreements.into_iter().map(|agreement_id| {
+                myself.send(BreakAgreement {
+                    agreement_id,
+                    reason: msg.reason.clone(),
+                })
+            });
+            for result in futures::future::join_all(breaks).await {
+                result??;
+            }
+            Ok(())
+        })
+    }
+}
+
 impl Handler<CloseAgreement> for TaskManager {
     type Result = ActorResponse<Self, Result<(), Error>>;
 
@@ -452,6 +452,77 @@ fn manifest_negotiator_test_whitelist(whitelist: &str, urls: &str, error_msg: Op
     )
 }
 
+#[test_case(
+    r#"{ "golem.srv.caps.inet.out.whitelist": [{ "domain": "preset.com", "match": "strict" }] }"#, // offer of preset
+    r#"["https://preset.com"]"#, // compManifest.net.inet.out.urls
+    None; // error msg
+    "Accepted because domain is on preset whitelist"
+)]
+#[test_case(
+    r#"{ "golem.srv.caps.inet.out.whitelist": [{ "domain": "preset.com", "match": "strict" }] }"#, // offer of preset
+    r#"["https://domain.com"]"#, // compManifest.net.inet.out.urls
+    Some("Everyone rule didn't match whitelist"); // error msg
+    "Rejected because preset whitelist replaces node whitelist"
+)]
+#[test_case(
+    r#"{ "golem.srv.caps.inet.out.ports": [443] }"#, // offer of preset
+    r#"["http://domain.com"]"#, // compManifest.net.inet.out.urls
+    Some("not allowed by preset"); // error msg
+    "Rejected because port is not allowed by preset"
+)]
+#[test_case(
+    r#"{ "golem.srv.caps.inet.out.ports": [443] }"#, // offer of preset
+    r#"["https://domain.com"]"#, // compManifest.net.inet.out.urls
+    None; // error msg
+    "Accepted because port is allowed by preset"
+)]
+#[serial]
+fn manifest_negotiator_test_preset_outbound_policy(
+    offer_properties: &str,
+    urls: &str,
+    error_msg: Option<&str>,
+) {
+    let rulestore = r#"{"outbound": {"enabled": true, "everyone": "whitelist"}}"#;
+    let whitelist = r#"{ "patterns": [{ "domain": "domain.com", "match": "strict" }] }"#;
+
+    let (_, test_cert_dir) = MANIFEST_TEST_RESOURCES.init_cert_dirs();
+    let whitelist_file = create_whitelist_file(whitelist);
+    let rules_file_name = test_cert_dir.join("rules.json");
+    let mut rules_file = std::fs::File::create(&rules_file_name).unwrap();
+    rules_file.write_all(rulestore.as_bytes()).unwrap();
+
+    let rules_manager =
+        RulesManager::load_or_create(&rules_file_name, &whitelist_file, &test_cert_dir)
+            .expect("Can't load RulesManager");
+
+    let config = create_manifest_signature_validating_policy_config();
+    let negotiator_cfg = AgentNegotiatorsConfig { rules_manager };
+    let mut manifest_negotiator = ManifestSignature::new(&config, negotiator_cfg);
+
+    let demand = create_demand_json(Some(Payload {
+        comp_manifest_b64: create_comp_manifest_b64(urls),
+        signature_b64: None,
+        signature_alg_b64: None,
+        cert_b64: None,
+        node_descriptor: None,
+    }));
+    let demand = create_demand(demand);
+    let mut offer = create_offer();
+    offer.content.properties = expand(serde_json::from_str(offer_properties).unwrap());
+
+    let negotiation_result = manifest_negotiator
+        .negotiate_step(&demand, offer.clone())
+        .expect("Negotiator had not failed");
+
+    match (error_msg, negotiation_result) {
+        (None, result) => assert_eq!(result, NegotiationResult::Ready { offer }),
+        (Some(expected_error), NegotiationResult::Reject { message, .. }) => {
+            assert!(message.contains(expected_error), "{message}")
+        }
+        (Some(_), _) => panic!("Expected negotiations rejected"),
+    }
+}
+
 fn manifest_negotiator_test_encoded_manifest_without_signature(
     rulestore: &str,
     whitelist: &str,
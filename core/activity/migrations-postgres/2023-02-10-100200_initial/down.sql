@@ -0,0 +1,6 @@
+DROP TABLE activity_credentials;
+DROP TABLE activity_event;
+DROP TABLE activity_event_type;
+DROP TABLE activity;
+DROP TABLE activity_usage;
+DROP TABLE activity_state;
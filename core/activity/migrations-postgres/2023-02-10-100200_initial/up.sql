@@ -0,0 +1,54 @@
+-- Schema equivalent to SQLite migrations up to 2021-07-21-201031_drop_runtime_event.
+
+CREATE TABLE activity_state(
+    id SERIAL PRIMARY KEY,
+    name TEXT NOT NULL,
+    reason TEXT,
+    error_message TEXT,
+    updated_date TIMESTAMP NOT NULL
+);
+
+CREATE TABLE activity_usage(
+    id SERIAL PRIMARY KEY,
+    vector_json TEXT,
+    updated_date TIMESTAMP NOT NULL
+);
+
+CREATE TABLE activity(
+    id SERIAL PRIMARY KEY,
+    natural_id TEXT NOT NULL UNIQUE,
+    agreement_id TEXT NOT NULL,
+    state_id INTEGER NOT NULL,
+    usage_id INTEGER NOT NULL,
+    FOREIGN KEY (state_id) REFERENCES activity_state(id),
+    FOREIGN KEY (usage_id) REFERENCES activity_usage(id)
+);
+
+CREATE TABLE activity_event_type(
+    id SERIAL PRIMARY KEY,
+    name TEXT NOT NULL
+);
+
+CREATE TABLE activity_event(
+    id SERIAL PRIMARY KEY,
+    activity_id INTEGER NOT NULL,
+    identity_id TEXT NOT NULL,
+    event_date TIMESTAMP NOT NULL,
+    event_type_id INTEGER NOT NULL,
+    requestor_pub_key BYTEA,
+    app_session_id TEXT,
+    FOREIGN KEY (activity_id) REFERENCES activity(id),
+    FOREIGN KEY (event_type_id) REFERENCES activity_event_type(id)
+);
+
+CREATE TABLE activity_credentials(
+    activity_id TEXT NOT NULL PRIMARY KEY,
+    credentials TEXT NOT NULL
+);
+
+INSERT INTO activity_event_type(name) VALUES
+    ('CreateActivity'),
+    ('DestroyActivity');
+
+CREATE INDEX idx_app_session_id ON activity_event(app_session_id);
+CREATE INDEX activity_natural_id_agreement_id_idx ON activity(natural_id, agreement_id);
@@ -0,0 +1 @@
+ALTER TABLE activity_usage DROP COLUMN signed_json;
@@ -0,0 +1 @@
+ALTER TABLE activity_usage ADD COLUMN signed_json TEXT NULL;
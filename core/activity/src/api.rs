@@ -21,6 +21,7 @@ mod common {
     use actix_web::{web, HttpResponse, Responder};
     use futures::prelude::*;
 
+    use ya_client_model::activity::ActivityState;
     use ya_client_model::market::Role;
     use ya_core_model::{activity, NodeId};
     use ya_persistence::executor::DbExecutor;
@@ -28,9 +29,13 @@ mod common {
     use ya_service_bus::{timeout::IntoTimeoutFuture, RpcEndpoint};
 
     use crate::common::*;
+    use crate::error::Error;
     use crate::tracker::TrackingEvent;
     use crate::TrackerRef;
     use actix_web::http::header;
+    use std::time::Duration;
+
+    const STATE_POLL_INTERVAL: Duration = Duration::from_secs(1);
 
     pub fn extend_web_scope(scope: actix_web::Scope) -> actix_web::Scope {
         scope
@@ -38,7 +43,9 @@ mod common {
             .service(get_events)
             .service(get_activity_agreement_web)
             .service(get_activity_state_web)
+            .service(stream_activity_state_web)
             .service(get_activity_usage_web)
+            .service(get_activity_usage_history_web)
     }
 
     // TODO this endpoint needs authorization via Identity, otherwise is vulnerable for attacks.
@@ -106,28 +113,104 @@ mod common {
         authorize_activity_initiator(&db, id.identity, &path.activity_id, Role::Requestor).await?;
 
         log::trace!("get_activity_state_web: I'm the requestor");
+        get_requestor_state(&db, &path.activity_id, &id, query.timeout)
+            .await
+            .map(web::Json)
+    }
 
-        // Return locally persisted usage if activity has been already terminated or terminating
-        let state = get_persisted_state(&db, &path.activity_id).await?;
+    /// Streams Activity state as server-sent events, whenever it changes, until the Activity
+    /// is terminated. Requestor's stream polls the Provider like `/activity/{id}/state` does.
+    #[actix_web::get("/activity/{activity_id}/state/stream")]
+    async fn stream_activity_state_web(
+        db: web::Data<DbExecutor>,
+        path: web::Path<PathActivity>,
+        query: web::Query<QueryTimeout>,
+        id: Identity,
+    ) -> Result<HttpResponse, Error> {
+        let is_provider =
+            authorize_activity_executor(&db, id.identity, &path.activity_id, Role::Provider)
+                .await
+                .is_ok();
+        if !is_provider {
+            authorize_activity_initiator(&db, id.identity, &path.activity_id, Role::Requestor)
+                .await?;
+        }
+
+        let db = db.get_ref().clone();
+        let activity_id = path.activity_id.clone();
+        let timeout = query.timeout;
+        let stream = futures::stream::unfold(
+            (None, false),
+            move |(last, finished): (Option<String>, bool)| {
+                let db = db.clone();
+                let activity_id = activity_id.clone();
+                let id = id.clone();
+                async move {
+                    if finished {
+                        return None;
+                    }
+                    loop {
+                        let state = match is_provider {
+                            true => get_persisted_state(&db, &activity_id).await,
+                            false => get_requestor_state(&db, &activity_id, &id, timeout).await,
+                        };
+                        let state = match state {
+                            Ok(state) => state,
+                            Err(e) => {
+                                log::debug!(
+                                    "Activity [{}] state stream closed: {}",
+                                    activity_id,
+                                    e
+                                );
+                                return None;
+                            }
+                        };
+                        let json = serde_json::to_string(&state).unwrap();
+                        if last.as_ref() != Some(&json) {
+                            let line = format!("data: {}\r\n\r\n", json);
+                            return Some((
+                                Ok::<_, actix_web::Error>(web::Bytes::from(line)),
+                                (Some(json), !state.alive()),
+                            ));
+                        }
+                        tokio::time::sleep(STATE_POLL_INTERVAL).await;
+                    }
+                }
+            },
+        );
+
+        Ok(HttpResponse::Ok()
+            .append_header((header::CONTENT_TYPE, "text/event-stream"))
+            .append_header((header::CACHE_CONTROL, "no-cache"))
+            .streaming(Box::pin(stream)))
+    }
+
+    /// Returns locally persisted state if Activity has been already terminated or terminating,
+    /// otherwise retrieves it from the Provider and persists it.
+    async fn get_requestor_state(
+        db: &DbExecutor,
+        activity_id: &str,
+        id: &Identity,
+        timeout: Option<f32>,
+    ) -> Result<ActivityState, Error> {
+        let state = get_persisted_state(db, activity_id).await?;
         if !state.alive() {
             log::trace!("get_activity_state_web: got persisted state");
-            return Ok(web::Json(state));
+            return Ok(state);
         }
 
-        // Retrieve and persist activity state
-        let agreement = get_activity_agreement(&db, &path.activity_id, Role::Requestor).await?;
-        let provider_service = agreement_provider_service(&id, &agreement)?;
+        let agreement = get_activity_agreement(db, activity_id, Role::Requestor).await?;
+        let provider_service = agreement_provider_service(id, &agreement)?;
         let state = provider_service
             .send(activity::GetState {
-                activity_id: path.activity_id.to_string(),
-                timeout: query.timeout,
+                activity_id: activity_id.to_string(),
+                timeout,
             })
-            .timeout(timeout_margin(query.timeout))
+            .timeout(timeout_margin(timeout))
             .await???;
+        record_activity_failure(&agreement, activity_id, &state).await;
 
-        set_persisted_state(&db, &path.activity_id, state)
-            .await
-            .map(web::Json)
+        set_persisted_state(db, activity_id, state).await
     }
 
     #[actix_web::get("/activity/{activity_id}/usage")]
@@ -169,11 +252,45 @@ mod common {
             .timeout(timeout_margin(query.timeout))
             .await???;
 
-        set_persisted_usage(&db, &path.activity_id, usage)
+        set_persisted_usage(&db, &path.activity_id, usage, None)
             .await
             .map(web::Json)
     }
 
+    /// Usage samples reported periodically by the ExeUnit, oldest first.
+    #[actix_web::get("/activity/{activity_id}/usage/history")]
+    async fn get_activity_usage_history_web(
+        db: web::Data<DbExecutor>,
+        path: web::Path<PathActivity>,
+        query: web::Query<QueryTimeout>,
+        id: Identity,
+    ) -> impl Responder {
+        // check if caller is the Provider
+        if authorize_activity_executor(&db, id.identity, &path.activity_id, Role::Provider)
+            .await
+            .is_ok()
+        {
+            return Ok(web::Json(crate::provider::usage_history::get(
+                &path.activity_id,
+            )));
+        }
+
+        // check if caller is the Requestor
+        authorize_activity_initiator(&db, id.identity, &path.activity_id, Role::Requestor).await?;
+
+        let agreement = get_activity_agreement(&db, &path.activity_id, Role::Requestor).await?;
+        let provider_service = agreement_provider_service(&id, &agreement)?;
+        let history = provider_service
+            .send(activity::GetUsageHistory {
+                activity_id: path.activity_id.to_string(),
+                timeout: query.timeout,
+            })
+            .timeout(timeout_margin(query.timeout))
+            .await???;
+
+        Ok::<_, Error>(web::Json(history))
+    }
+
     fn event_stream(
         stream: tokio::sync::broadcast::Receiver<TrackingEvent>,
         provider_id: NodeId,
@@ -1,5 +1,6 @@
 use crate::dao::{ActivityDao, ActivityStateDao, ActivityUsageDao};
 use crate::error::Error;
+use bigdecimal::BigDecimal;
 use chrono::{DateTime, Utc};
 use serde::Deserialize;
 use std::time::Duration;
@@ -32,6 +33,15 @@ pub struct QueryTimeout {
     pub timeout: Option<f32>,
 }
 
+#[derive(Deserialize)]
+pub struct QueryCreateActivity {
+    #[serde(rename = "timeout", default = "default_query_timeout")]
+    pub timeout: Option<f32>,
+    /// Activity is destroyed when its Debit Notes reach this amount.
+    #[serde(rename = "maxCost")]
+    pub max_cost: Option<BigDecimal>,
+}
+
 #[derive(Deserialize)]
 pub struct QueryTimeoutCommandIndex {
     #[serde(rename = "timeout")]
@@ -89,6 +99,36 @@ pub(crate) async fn set_persisted_state(
         .await?)
 }
 
+/// Reports Activity terminated with error to market, which keeps local
+/// reputation of Providers.
+pub(crate) async fn record_activity_failure(
+    agreement: &Agreement,
+    activity_id: &str,
+    activity_state: &ActivityState,
+) {
+    if activity_state.alive() || activity_state.error_message.is_none() {
+        return;
+    }
+
+    let msg = market::RecordOutcome {
+        node_id: *agreement.provider_id(),
+        subject_id: activity_id.to_string(),
+        outcome: market::Outcome::ActivityFailed,
+    };
+    if let Err(e) = bus::service(market::local::BUS_ID)
+        .send(msg)
+        .await
+        .map_err(|e| e.to_string())
+        .and_then(|r| r.map_err(|e| e.to_string()))
+    {
+        log::warn!(
+            "Failed to record failure of Activity [{}]: {}",
+            activity_id,
+            e
+        );
+    }
+}
+
 pub(crate) fn agreement_provider_service(
     id: &Identity,
     agreement: &Agreement,
@@ -109,10 +149,11 @@ pub(crate) async fn set_persisted_usage(
     db: &DbExecutor,
     activity_id: &str,
     activity_usage: ActivityUsage,
+    signature: Option<Vec<u8>>,
 ) -> Result<ActivityUsage, Error> {
     Ok(db
         .as_dao::<ActivityUsageDao>()
-        .set(activity_id, activity_usage)
+        .set(activity_id, activity_usage, signature)
         .await?)
 }
 
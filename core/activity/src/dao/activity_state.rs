@@ -8,6 +8,7 @@ use tokio::time::sleep;
 
 use ya_client_model::activity::activity_state::{ActivityState, StatePair};
 use ya_persistence::executor::{do_with_transaction, readonly_transaction, AsDao, PoolType};
+use ya_persistence::types::AdaptTimestamp;
 
 use crate::dao::{DaoError, Result};
 use crate::db::{models::ActivityState as DbActivityState, schema};
@@ -48,6 +49,30 @@ impl<'c> ActivityStateDao<'c> {
         .await
     }
 
+    /// Activities not terminated yet, with their states.
+    pub async fn list_alive(&self) -> Result<Vec<(String, ActivityState)>> {
+        use schema::activity::dsl;
+        use schema::activity_state::dsl as dsl_state;
+
+        readonly_transaction(self.pool, move |conn| {
+            let activities: Vec<(String, DbActivityState)> = dsl::activity
+                .inner_join(schema::activity_state::table)
+                .select((dsl::natural_id, schema::activity_state::all_columns))
+                .filter(dsl_state::name.not_like("%Terminated%"))
+                .load(conn)?;
+
+            let mut alive = Vec::new();
+            for (activity_id, state) in activities {
+                let state: ActivityState = state.try_into()?;
+                if state.state.alive() {
+                    alive.push((activity_id, state));
+                }
+            }
+            Ok(alive)
+        })
+        .await
+    }
+
     pub async fn stats(&self) -> Result<BTreeMap<State, u64>> {
         readonly_transaction(self.pool, move |conn| {
             use diesel::sql_types::{Integer, Text};
@@ -61,7 +86,7 @@ impl<'c> ActivityStateDao<'c> {
 
             let stats: Vec<StatRecord> = diesel::sql_query(
                 r#"
-            select b.name state, count(a.natural_id) n
+            select b.name state, cast(count(a.natural_id) as integer) n
             from activity a join activity_state b on (a.state_id = b.id) group by b.name
             "#,
             )
@@ -90,14 +115,17 @@ impl<'c> ActivityStateDao<'c> {
                 n: i32,
             }
 
-            let stats: Vec<StatRecord> = diesel::sql_query(
+            // Placeholder syntax differs between backends, so the timestamp is inlined.
+            let since = (Utc::now() - chrono::Duration::hours(1)).adapt();
+            let stats: Vec<StatRecord> = diesel::sql_query(format!(
                 r#"
-            select b.name state, count(a.natural_id) n
+            select b.name state, cast(count(a.natural_id) as integer) n
             from activity a join activity_state b on (a.state_id = b.id)
-            where b.updated_date >= datetime('now', '-1 hour')
+            where b.updated_date >= '{}'
             group by b.name
             "#,
-            )
+                since.format()
+            ))
             .load(conn)
             .map_err(DaoError::from)?;
             let mut m = BTreeMap::new();
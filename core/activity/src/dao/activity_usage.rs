@@ -5,7 +5,8 @@ use diesel::prelude::*;
 use std::convert::TryInto;
 
 use ya_client_model::activity::activity_usage::ActivityUsage;
-use ya_persistence::executor::{do_with_transaction, AsDao, PoolType};
+use ya_core_model::activity::SignedUsage;
+use ya_persistence::executor::{do_with_transaction, readonly_transaction, AsDao, PoolType};
 
 use crate::dao::{DaoError, Result};
 use crate::db::{models::ActivityUsage as DbActivityUsage, schema};
@@ -44,28 +45,77 @@ impl<'c> ActivityUsageDao<'c> {
         .await
     }
 
-    pub async fn set(&self, activity_id: &str, usage: ActivityUsage) -> Result<ActivityUsage> {
+    /// Get the last usage signed by the ExeUnit.
+    pub async fn get_signed(&self, activity_id: &str) -> Result<Option<SignedUsage>> {
+        use schema::activity::dsl;
+        use schema::activity_usage::dsl as dsl_usage;
+
+        let activity_id = activity_id.to_owned();
+
+        readonly_transaction(self.pool, move |conn| {
+            let signed_json = dsl::activity
+                .inner_join(dsl_usage::activity_usage)
+                .select(dsl_usage::signed_json)
+                .filter(dsl::natural_id.eq(&activity_id))
+                .first::<Option<String>>(conn)
+                .map_err(|e| match e {
+                    diesel::NotFound => {
+                        DaoError::NotFound(format!("activity usage: {}", activity_id))
+                    }
+                    e => e.into(),
+                })?;
+            Ok(signed_json
+                .map(|json| serde_json::from_str(&json))
+                .transpose()?)
+        })
+        .await
+    }
+
+    /// Sets usage of the activity. Usage with `signature` is also kept as the last signed usage.
+    pub async fn set(
+        &self,
+        activity_id: &str,
+        usage: ActivityUsage,
+        signature: Option<Vec<u8>>,
+    ) -> Result<ActivityUsage> {
         use schema::activity::dsl;
         use schema::activity_usage::dsl as dsl_usage;
 
         let vector = serde_json::to_string(&usage.current_usage)?;
+        let signed = signature
+            .map(|signature| {
+                serde_json::to_string(&SignedUsage {
+                    activity_id: activity_id.to_owned(),
+                    usage: usage.clone(),
+                    signature,
+                })
+            })
+            .transpose()?;
         let now = Utc::now().naive_utc();
 
         let activity_id = activity_id.to_owned();
 
         do_with_transaction(self.pool, move |conn| {
-            diesel::update(
-                dsl_usage::activity_usage.filter(exists(
-                    dsl::activity
-                        .filter(dsl::natural_id.eq(activity_id))
-                        .filter(dsl::usage_id.eq(dsl_usage::id)),
-                )),
-            )
-            .set((
-                dsl_usage::vector_json.eq(&vector),
-                dsl_usage::updated_date.eq(now),
-            ))
-            .execute(conn)?;
+            let filter = dsl_usage::activity_usage.filter(exists(
+                dsl::activity
+                    .filter(dsl::natural_id.eq(activity_id))
+                    .filter(dsl::usage_id.eq(dsl_usage::id)),
+            ));
+            match signed {
+                Some(signed) => diesel::update(filter)
+                    .set((
+                        dsl_usage::vector_json.eq(&vector),
+                        dsl_usage::updated_date.eq(now),
+                        dsl_usage::signed_json.eq(signed),
+                    ))
+                    .execute(conn)?,
+                None => diesel::update(filter)
+                    .set((
+                        dsl_usage::vector_json.eq(&vector),
+                        dsl_usage::updated_date.eq(now),
+                    ))
+                    .execute(conn)?,
+            };
 
             Ok(usage)
         })
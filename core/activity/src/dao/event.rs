@@ -97,7 +97,7 @@ impl<'c> EventDao<'c> {
                 ))
                 .execute(conn)?;
 
-            let event_id = diesel::select(super::last_insert_rowid).first(conn)?;
+            let event_id = diesel::select(super::last_insert_rowid()).first(conn)?;
             log::trace!("event inserted: {}", event_id);
 
             Ok(event_id)
@@ -13,7 +13,14 @@ use thiserror::Error;
 
 type Result<T> = std::result::Result<T, DaoError>;
 
-no_arg_sql_function!(last_insert_rowid, diesel::sql_types::Integer);
+/// Id of the row inserted last on this connection.
+fn last_insert_rowid() -> diesel::expression::SqlLiteral<diesel::sql_types::Integer> {
+    #[cfg(not(feature = "postgres"))]
+    let query = "last_insert_rowid()";
+    #[cfg(feature = "postgres")]
+    let query = "CAST(lastval() AS INTEGER)";
+    diesel::dsl::sql(query)
+}
 
 #[derive(Error, Debug)]
 pub enum DaoError {
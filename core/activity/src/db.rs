@@ -4,5 +4,9 @@ pub(crate) mod schema;
 #[allow(dead_code)]
 pub mod migrations {
     #[derive(EmbedMigrations)]
+    #[cfg_attr(
+        feature = "postgres",
+        embed_migrations_options(migrations_path = "migrations-postgres")
+    )]
     struct _Dummy;
 }
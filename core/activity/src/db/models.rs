@@ -87,6 +87,7 @@ pub struct ActivityUsage {
     pub id: i32,
     pub vector_json: Option<String>,
     pub updated_date: NaiveDateTime,
+    pub signed_json: Option<String>,
 }
 
 impl TryFrom<ActivityUsage> for ya_client_model::activity::ActivityUsage {
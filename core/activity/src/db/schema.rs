@@ -49,6 +49,7 @@ table! {
         id -> Integer,
         vector_json -> Nullable<Text>,
         updated_date -> Timestamp,
+        signed_json -> Nullable<Text>,
     }
 }
 
@@ -14,6 +14,7 @@ pub mod db;
 mod api;
 mod cli;
 mod error;
+mod metric_keys;
 mod provider;
 mod requestor;
 pub mod service;
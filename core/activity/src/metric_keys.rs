@@ -0,0 +1,28 @@
+//! Names of the metrics reported by the Activity service.
+
+use metrics::counter;
+
+pub(crate) const PROVIDER_CREATE_AGREEMENT_NOT_APPROVED: &str =
+    "activity.provider.create.agreement.not-approved";
+pub(crate) const PROVIDER_CREATED: &str = "activity.provider.created";
+pub(crate) const PROVIDER_DESTROYED: &str = "activity.provider.destroyed";
+pub(crate) const PROVIDER_DESTROYED_BY_REQUESTOR: &str = "activity.provider.destroyed.by_requestor";
+pub(crate) const PROVIDER_DESTROYED_UNRESPONSIVE: &str = "activity.provider.destroyed.unresponsive";
+pub(crate) const REQUESTOR_CREATED: &str = "activity.requestor.created";
+pub(crate) const REQUESTOR_DESTROYED: &str = "activity.requestor.destroyed";
+pub(crate) const REQUESTOR_READ_OUTPUT: &str = "activity.requestor.read-output";
+pub(crate) const REQUESTOR_RUN_EXESCRIPT: &str = "activity.requestor.run-exescript";
+
+/// Initializes counters to 0 value. Otherwise they won't appear on metrics endpoint
+/// until first change to value will be made.
+pub(crate) fn init() {
+    counter!(PROVIDER_CREATE_AGREEMENT_NOT_APPROVED, 0);
+    counter!(PROVIDER_CREATED, 0);
+    counter!(PROVIDER_DESTROYED, 0);
+    counter!(PROVIDER_DESTROYED_BY_REQUESTOR, 0);
+    counter!(PROVIDER_DESTROYED_UNRESPONSIVE, 0);
+    counter!(REQUESTOR_CREATED, 0);
+    counter!(REQUESTOR_DESTROYED, 0);
+    counter!(REQUESTOR_READ_OUTPUT, 0);
+    counter!(REQUESTOR_RUN_EXESCRIPT, 0);
+}
@@ -13,6 +13,7 @@ use ya_client_model::NodeId;
 use ya_core_model::activity;
 use ya_core_model::activity::local::Credentials;
 use ya_core_model::activity::RpcMessageError;
+use ya_core_model::journal;
 use ya_persistence::executor::DbExecutor;
 use ya_service_bus::{timeout::*, typed::ServiceBinder};
 
@@ -24,6 +25,8 @@ use crate::common::{
 use crate::dao::*;
 use crate::db::models::ActivityEventType;
 use crate::error::Error;
+use crate::metric_keys;
+use crate::provider::usage_history;
 use crate::TrackerRef;
 
 const INACTIVITY_LIMIT_SECONDS_ENV_VAR: &str = "INACTIVITY_LIMIT_SECONDS";
@@ -64,15 +67,8 @@ pub fn bind_gsb(db: &DbExecutor, tracker: TrackerRef) {
         .bind_with_processor(create_activity_gsb)
         .bind(destroy_activity_gsb)
         .bind(get_activity_state_gsb)
-        .bind(get_activity_usage_gsb);
-
-    // Initialize counters to 0 value. Otherwise they won't appear on metrics endpoint
-    // until first change to value will be made.
-    counter!("activity.provider.created", 0);
-    counter!("activity.provider.create.agreement.not-approved", 0);
-    counter!("activity.provider.destroyed", 0);
-    counter!("activity.provider.destroyed.by_requestor", 0);
-    counter!("activity.provider.destroyed.unresponsive", 0);
+        .bind(get_activity_usage_gsb)
+        .bind(get_activity_usage_history_gsb);
 
     local::bind_gsb(db, tracker);
 }
@@ -93,7 +89,7 @@ async fn create_activity_gsb(
 
     if agreement.state != AgreementState::Approved {
         // to track inconsistencies between this and remote market service
-        counter!("activity.provider.create.agreement.not-approved", 1);
+        counter!(metric_keys::PROVIDER_CREATE_AGREEMENT_NOT_APPROVED, 1);
 
         let msg = format!(
             "Agreement {} is not Approved. Current state: {:?}",
@@ -150,7 +146,17 @@ async fn create_activity_gsb(
     log::info!(
         "Requestor [{caller}] created Activity [{activity_id}] for Agreement [{agreement_id}]"
     );
-    counter!("activity.provider.created", 1);
+    counter!(metric_keys::PROVIDER_CREATED, 1);
+
+    journal::Event::new(journal::Category::Activity, "activity-created")
+        .subject(&activity_id)
+        .node_id(*agreement.provider_id())
+        .details(serde_json::json!({
+            "agreementId": agreement_id,
+            "requestorId": caller,
+        }))
+        .record()
+        .await;
 
     Ok(if credentials.is_none() {
         activity::CreateResponseCompat::ActivityId(activity_id)
@@ -254,7 +260,17 @@ async fn destroy_activity_gsb(
         agreement.agreement_id
     );
 
-    counter!("activity.provider.destroyed.by_requestor", 1);
+    counter!(metric_keys::PROVIDER_DESTROYED_BY_REQUESTOR, 1);
+
+    journal::Event::new(journal::Category::Activity, "activity-destroyed")
+        .subject(&msg.activity_id)
+        .node_id(*agreement.provider_id())
+        .details(serde_json::json!({
+            "agreementId": agreement.agreement_id,
+            "reason": "requestor",
+        }))
+        .record()
+        .await;
     Ok(result)
 }
 
@@ -278,6 +294,16 @@ async fn get_activity_usage_gsb(
     Ok(get_persisted_usage(&db, &msg.activity_id).await?)
 }
 
+async fn get_activity_usage_history_gsb(
+    db: DbExecutor,
+    caller: String,
+    msg: activity::GetUsageHistory,
+) -> RpcMessageResult<activity::GetUsageHistory> {
+    authorize_activity_initiator(&db, caller, &msg.activity_id, Role::Provider).await?;
+
+    Ok(usage_history::get(&msg.activity_id))
+}
+
 async fn get_activity_progress(
     db: &DbExecutor,
     activity_id: &str,
@@ -362,7 +388,17 @@ async fn monitor_activity(
                 )
                 .await;
 
-                counter!("activity.provider.destroyed.unresponsive", 1);
+                counter!(metric_keys::PROVIDER_DESTROYED_UNRESPONSIVE, 1);
+
+                journal::Event::new(journal::Category::Activity, "activity-destroyed")
+                    .subject(&activity_id)
+                    .node_id(provider_id)
+                    .details(serde_json::json!({
+                        "reason": "inactive",
+                        "inactiveSeconds": dt,
+                    }))
+                    .record()
+                    .await;
                 break;
             } else if state.state.0 != State::Unresponsive && dt >= unresp_s {
                 log::warn!("activity {} unresponsive after {}s", activity_id, dt);
@@ -395,7 +431,8 @@ async fn monitor_activity(
     // If we got here, we can be sure, that activity was already destroyed.
     // Counting activities in all other places can result with duplicated
     // DestroyActivity events.
-    counter!("activity.provider.destroyed", 1);
+    counter!(metric_keys::PROVIDER_DESTROYED, 1);
+    usage_history::remove(&activity_id);
     log::debug!("Stopping activity monitor: {}", activity_id);
 }
 
@@ -410,6 +447,7 @@ mod local {
             .bind_with_processor(set_activity_state_gsb)
             .bind_with_processor(set_activity_usage_gsb)
             .bind(get_agreement_id_gsb)
+            .bind(get_signed_usage_gsb)
             .bind(activity_status);
     }
 
@@ -457,7 +495,15 @@ mod local {
         let _ = tracker
             .update_state(msg.activity_id.clone(), msg.state.state.0)
             .await;
+        let event = journal::Event::new(journal::Category::Activity, "activity-state-changed")
+            .subject(&msg.activity_id)
+            .details(serde_json::json!({
+                "state": msg.state.state,
+                "reason": msg.state.reason,
+                "errorMessage": msg.state.error_message,
+            }));
         set_persisted_state(&db, &msg.activity_id, msg.state).await?;
+        event.record().await;
         Ok(())
     }
 
@@ -483,10 +529,26 @@ mod local {
                 .await;
         }
 
-        set_persisted_usage(&db, &msg.activity_id, msg.usage).await?;
+        usage_history::record(&msg.activity_id, &msg.usage);
+
+        set_persisted_usage(&db, &msg.activity_id, msg.usage, msg.signature).await?;
         Ok(())
     }
 
+    /// Get the last usage signed by the ExeUnit.
+    /// Called by payment module, to attach it to Debit Notes.
+    async fn get_signed_usage_gsb(
+        db: DbExecutor,
+        _caller: String,
+        msg: activity::local::GetSignedUsage,
+    ) -> RpcMessageResult<activity::local::GetSignedUsage> {
+        Ok(db
+            .as_dao::<ActivityUsageDao>()
+            .get_signed(&msg.activity_id)
+            .await
+            .map_err(Error::from)?)
+    }
+
     /// Get agreement ID for a given activity ID
     /// Called e.g. by payment module
     async fn get_agreement_id_gsb(
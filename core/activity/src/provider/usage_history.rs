@@ -0,0 +1,80 @@
+//! Usage samples periodically reported by ExeUnits, retained in memory for
+//! Requestors to query. Each activity keeps at most `ACTIVITY_USAGE_HISTORY_SIZE`
+//! latest samples, history is dropped when the activity is no longer monitored.
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+use ya_client_model::activity::ActivityUsage;
+
+const USAGE_HISTORY_SIZE_ENV_VAR: &str = "ACTIVITY_USAGE_HISTORY_SIZE";
+const DEFAULT_USAGE_HISTORY_SIZE: usize = 360;
+
+lazy_static::lazy_static! {
+    static ref HISTORY_SIZE: usize = std::env::var(USAGE_HISTORY_SIZE_ENV_VAR)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_USAGE_HISTORY_SIZE);
+    static ref HISTORY: Mutex<HashMap<String, VecDeque<ActivityUsage>>> = Default::default();
+}
+
+pub(crate) fn record(activity_id: &str, usage: &ActivityUsage) {
+    if *HISTORY_SIZE == 0 || usage.current_usage.is_none() {
+        return;
+    }
+    let mut history = HISTORY.lock().unwrap();
+    let samples = history.entry(activity_id.to_string()).or_default();
+    push(samples, usage.clone(), *HISTORY_SIZE);
+}
+
+/// Retained samples of the activity, oldest first.
+pub(crate) fn get(activity_id: &str) -> Vec<ActivityUsage> {
+    HISTORY
+        .lock()
+        .unwrap()
+        .get(activity_id)
+        .map(|samples| samples.iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+pub(crate) fn remove(activity_id: &str) {
+    HISTORY.lock().unwrap().remove(activity_id);
+}
+
+fn push(samples: &mut VecDeque<ActivityUsage>, usage: ActivityUsage, size: usize) {
+    // ExeUnit may report the same sample more than once within a second.
+    if let Some(last) = samples.back_mut() {
+        if last.timestamp == usage.timestamp {
+            *last = usage;
+            return;
+        }
+    }
+    while samples.len() >= size {
+        samples.pop_front();
+    }
+    samples.push_back(usage);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage(timestamp: i64, cpu: f64) -> ActivityUsage {
+        ActivityUsage {
+            current_usage: Some(vec![cpu]),
+            timestamp,
+        }
+    }
+
+    #[test]
+    fn test_push() {
+        let mut samples = VecDeque::new();
+        for t in 0..5 {
+            push(&mut samples, usage(t, t as f64), 3);
+        }
+        push(&mut samples, usage(4, 10.0), 3);
+
+        let timestamps: Vec<_> = samples.iter().map(|u| u.timestamp).collect();
+        assert_eq!(timestamps, vec![2, 3, 4]);
+        assert_eq!(samples.back().unwrap().current_usage, Some(vec![10.0]));
+    }
+}
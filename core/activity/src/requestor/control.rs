@@ -10,17 +10,20 @@ use tokio_stream::wrappers::IntervalStream;
 
 use ya_client_model::activity::{
     ActivityState, CreateActivityRequest, CreateActivityResult, Credentials, ExeScriptCommand,
-    ExeScriptRequest, SgxCredentials, State,
+    SgxCredentials, State,
 };
 use ya_client_model::market::{Agreement, Role};
 use ya_core_model::activity;
+use ya_core_model::journal;
+use ya_core_model::payment;
 use ya_net::{self as net, RemoteEndpoint};
 use ya_persistence::executor::DbExecutor;
 use ya_service_api_web::middleware::Identity;
-use ya_service_bus::{timeout::IntoTimeoutFuture, RpcEndpoint};
+use ya_service_bus::{timeout::IntoTimeoutFuture, typed as bus, RpcEndpoint};
 
 use crate::common::*;
 use crate::dao::ActivityDao;
+use crate::metric_keys;
 use crate::{error::Error, Result};
 
 pub fn extend_web_scope(scope: actix_web::Scope) -> actix_web::Scope {
@@ -29,6 +32,7 @@ pub fn extend_web_scope(scope: actix_web::Scope) -> actix_web::Scope {
         .service(destroy_activity)
         .service(exec)
         .service(get_batch_results)
+        .service(get_output_file)
         .service(encrypted)
 }
 
@@ -74,7 +78,7 @@ impl CreateActivityJson {
 #[actix_web::post("/activity")]
 async fn create_activity(
     db: web::Data<DbExecutor>,
-    query: web::Query<QueryTimeout>,
+    query: web::Query<QueryCreateActivity>,
     body: web::Json<CreateActivityJson>,
     id: Identity,
 ) -> impl Responder {
@@ -103,6 +107,36 @@ async fn create_activity(
         .create_if_not_exists(create_resp.activity_id(), agreement_id)
         .await?;
 
+    if let Some(max_cost) = query.max_cost.clone() {
+        let msg = payment::local::SetActivityBudget {
+            activity_id: create_resp.activity_id().to_string(),
+            agreement_id: agreement_id.to_string(),
+            owner_id: id.identity,
+            max_amount: max_cost,
+        };
+        let result = match bus::service(payment::local::BUS_ID).send(msg).await {
+            Ok(result) => result.map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+        if let Err(e) = result {
+            // Activity without its cost ceiling is not what the Requestor asked for.
+            let msg = activity::Destroy {
+                activity_id: create_resp.activity_id().to_string(),
+                agreement_id: agreement_id.to_string(),
+                timeout: query.timeout,
+            };
+            let _ = agreement_provider_service(&id, &agreement)?
+                .send(msg)
+                .timeout(timeout_margin(query.timeout))
+                .await;
+            return Err(Error::Service(format!(
+                "Unable to set cost ceiling of Activity {}: {}",
+                create_resp.activity_id(),
+                e
+            )));
+        }
+    }
+
     let create_result = CreateActivityResult {
         activity_id: create_resp.activity_id().into(),
         credentials: create_resp
@@ -111,13 +145,23 @@ async fn create_activity(
             .transpose()?,
     };
 
-    counter!("activity.requestor.created", 1);
+    counter!(metric_keys::REQUESTOR_CREATED, 1);
     log::info!(
         "Created Activity [{}] for Agreement [{}]",
         create_resp.activity_id(),
         agreement_id
     );
 
+    journal::Event::new(journal::Category::Activity, "activity-created")
+        .subject(create_resp.activity_id())
+        .node_id(id.identity)
+        .details(serde_json::json!({
+            "agreementId": agreement_id,
+            "providerId": agreement.provider_id(),
+        }))
+        .record()
+        .await;
+
     Ok::<_, Error>(web::Json(body.to_response(create_result)))
 }
 
@@ -151,31 +195,71 @@ async fn destroy_activity(
             error_message: None,
         },
     )
-    .await
-    .map(|_| {
-        counter!("activity.requestor.destroyed", 1);
-        log::info!(
-            "Requestor destroyed Activity [{}] for Agreement [{}]",
-            path.activity_id,
-            agreement.agreement_id
-        );
-        web::Json(())
-    })
+    .await?;
+
+    counter!(metric_keys::REQUESTOR_DESTROYED, 1);
+    log::info!(
+        "Requestor destroyed Activity [{}] for Agreement [{}]",
+        path.activity_id,
+        agreement.agreement_id
+    );
+
+    journal::Event::new(journal::Category::Activity, "activity-destroyed")
+        .subject(&path.activity_id)
+        .node_id(id.identity)
+        .details(serde_json::json!({
+            "agreementId": agreement.agreement_id,
+            "reason": "requestor",
+        }))
+        .record()
+        .await;
+    Ok::<_, Error>(web::Json(()))
+}
+
+/// `ExeScriptRequest` extended with optional dependencies between its commands.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ExecJson {
+    text: String,
+    /// Indices of earlier commands each command depends on.
+    depends_on: Option<Vec<Vec<usize>>>,
+    on_failure: Option<activity::FailurePolicy>,
+}
+
+impl ExecJson {
+    fn pipeline(&self) -> Option<activity::ExecPipeline> {
+        if self.depends_on.is_none() && self.on_failure.is_none() {
+            return None;
+        }
+        Some(activity::ExecPipeline {
+            depends_on: self.depends_on.clone().unwrap_or_default(),
+            on_failure: self.on_failure.unwrap_or_default(),
+        })
+    }
 }
 
 /// Executes an ExeScript batch within a given Activity.
+///
+/// Commands run one after another on the Provider. With `onFailure: "continue"`
+/// a failed command only skips commands depending on it (`dependsOn`).
 #[actix_web::post("/activity/{activity_id}/exec")]
 async fn exec(
     db: web::Data<DbExecutor>,
     path: web::Path<PathActivity>,
     query: web::Query<QueryTimeout>,
-    body: web::Json<ExeScriptRequest>,
+    body: web::Json<ExecJson>,
     id: Identity,
 ) -> impl Responder {
     authorize_activity_initiator(&db, id.identity, &path.activity_id, Role::Requestor).await?;
 
     let commands: Vec<ExeScriptCommand> =
         serde_json::from_str(&body.text).map_err(|e| Error::BadRequest(format!("{:?}", e)))?;
+    let pipeline = body.pipeline();
+    if let Some(pipeline) = &pipeline {
+        pipeline
+            .validate(commands.len())
+            .map_err(Error::BadRequest)?;
+    }
     let agreement = get_activity_agreement(&db, &path.activity_id, Role::Requestor).await?;
     let batch_id = generate_id();
     let msg = activity::Exec {
@@ -183,6 +267,7 @@ async fn exec(
         batch_id: batch_id.clone(),
         exe_script: commands,
         timeout: query.timeout,
+        pipeline,
     };
 
     ya_net::from(id.identity)
@@ -192,7 +277,7 @@ async fn exec(
         .timeout(timeout_margin(query.timeout))
         .await???;
 
-    counter!("activity.requestor.run-exescript", 1);
+    counter!(metric_keys::REQUESTOR_RUN_EXESCRIPT, 1);
     Ok::<_, Error>(web::Json(batch_id))
 }
 
@@ -291,6 +376,74 @@ fn map_event_result<T: Serialize>(
     Ok(bytes.freeze())
 }
 
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct QueryOutputFile {
+    /// Path within one of the container volumes.
+    path: String,
+    #[serde(default)]
+    offset: u64,
+    max_bytes: Option<u64>,
+    /// Keep the response open and send data appended to the file.
+    #[serde(default)]
+    follow: bool,
+    timeout: Option<f32>,
+}
+
+/// Reads a file written by a running Activity, e.g. its intermediate results.
+///
+/// Responds with file contents starting at `offset`, capped by the Provider.
+/// Size of the whole file is returned in the `X-File-Size` header.
+/// With `follow=true` data is streamed as it's appended to the file.
+#[actix_web::get("/activity/{activity_id}/output")]
+async fn get_output_file(
+    db: web::Data<DbExecutor>,
+    path: web::Path<PathActivity>,
+    query: web::Query<QueryOutputFile>,
+    id: Identity,
+) -> Result<HttpResponse> {
+    authorize_activity_initiator(&db, id.identity, &path.activity_id, Role::Requestor).await?;
+    let agreement = get_activity_agreement(&db, &path.activity_id, Role::Requestor).await?;
+    let query = query.into_inner();
+    let endpoint = ya_net::from(id.identity)
+        .to(*agreement.provider_id())
+        .service_transfer(&activity::exeunit::bus_id(&path.activity_id));
+
+    if query.follow {
+        let msg = activity::StreamOutputFile {
+            activity_id: path.activity_id.to_string(),
+            path: query.path,
+            offset: query.offset,
+        };
+        let stream = endpoint.call_streaming(msg).map(|item| match item {
+            Ok(Ok(chunk)) => Ok(Bytes::from(chunk.data)),
+            Ok(Err(e)) => Err(actix_web::Error::from(Error::from(e))),
+            Err(e) => Err(actix_web::Error::from(Error::from(e))),
+        });
+        return Ok(HttpResponse::Ok()
+            .content_type(mime::APPLICATION_OCTET_STREAM.essence_str())
+            .streaming(stream));
+    }
+
+    let msg = activity::ReadOutputFile {
+        activity_id: path.activity_id.to_string(),
+        path: query.path,
+        offset: query.offset,
+        max_bytes: query.max_bytes,
+        timeout: query.timeout,
+    };
+    let chunk = endpoint
+        .send(msg)
+        .timeout(timeout_margin(query.timeout))
+        .await???;
+
+    counter!(metric_keys::REQUESTOR_READ_OUTPUT, 1);
+    Ok(HttpResponse::Ok()
+        .content_type(mime::APPLICATION_OCTET_STREAM.essence_str())
+        .insert_header(("X-File-Size", chunk.size.to_string()))
+        .body(chunk.data))
+}
+
 /// Forwards an encrypted ExeUnit call.
 #[actix_web::post("/activity/{activity_id}/encrypted")]
 async fn encrypted(
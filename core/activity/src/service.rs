@@ -1,6 +1,9 @@
+use ya_client_model::activity::{State, StatePair};
 use ya_persistence::executor::DbExecutor;
+use ya_service_api::recovery::{RecoveryAction, RecoveryItem};
 use ya_service_api_interfaces::{Provider, Service};
 
+use crate::dao::ActivityStateDao;
 use crate::{api, db::migrations, provider, TrackerRef};
 
 pub struct Activity;
@@ -16,6 +19,7 @@ impl Activity {
         let db: DbExecutor = ctx.component();
         let tracker_ref: TrackerRef = ctx.component();
         db.apply_migration(migrations::run_with_output)?;
+        crate::metric_keys::init();
         provider::service::bind_gsb(&db, tracker_ref);
         Ok(())
     }
@@ -25,4 +29,35 @@ impl Activity {
     ) -> actix_web::Scope {
         api::web_scope(&ctx.component(), ctx.component())
     }
+
+    /// Reports activities which were not terminated before the previous shutdown.
+    /// ExeUnits run independently of the daemon, so their states are kept.
+    pub async fn recover<Context: Provider<Self, DbExecutor>>(
+        ctx: &Context,
+    ) -> anyhow::Result<Vec<RecoveryItem>> {
+        let db: DbExecutor = ctx.component();
+        let activities = db.as_dao::<ActivityStateDao>().list_alive().await?;
+
+        Ok(activities
+            .into_iter()
+            .map(|(activity_id, state)| {
+                let item = |action| {
+                    RecoveryItem::new(
+                        "activity",
+                        &activity_id,
+                        format!("{:?}", state.state.0),
+                        action,
+                    )
+                };
+                match &state.state {
+                    StatePair(State::Unresponsive, _) => item(RecoveryAction::NeedsAttention)
+                        .details("ExeUnit is unresponsive. Destroy the activity if it's gone"),
+                    StatePair(_, Some(to)) => item(RecoveryAction::NeedsAttention)
+                        .details(format!("Interrupted while changing state to {:?}", to)),
+                    _ => item(RecoveryAction::Resumed)
+                        .details("Tracked until ExeUnit reports termination"),
+                }
+            })
+            .collect())
+    }
 }
@@ -0,0 +1,5 @@
+DROP TABLE identity_role;
+DROP TABLE app_key;
+DROP TABLE role;
+DROP TABLE identity_data;
+DROP TABLE identity;
@@ -0,0 +1,46 @@
+-- Schema equivalent to SQLite migrations up to 2023-02-02-100000_identity_roles.
+
+CREATE TABLE identity(
+    identity_id TEXT NOT NULL PRIMARY KEY,
+    key_file_json TEXT NOT NULL,
+    is_default BOOLEAN NOT NULL DEFAULT FALSE,
+    is_deleted BOOLEAN NOT NULL DEFAULT FALSE,
+    alias TEXT UNIQUE,
+    note TEXT,
+    created_date TIMESTAMP NOT NULL
+);
+
+CREATE TABLE identity_data(
+    identity_id TEXT,
+    module_id TEXT,
+    configuration TEXT,
+    version INTEGER DEFAULT 0,
+    PRIMARY KEY (identity_id, module_id),
+    FOREIGN KEY (identity_id) REFERENCES identity(identity_id)
+);
+
+CREATE TABLE role(
+    id SERIAL PRIMARY KEY,
+    name TEXT NOT NULL
+);
+
+CREATE TABLE app_key(
+    id SERIAL PRIMARY KEY,
+    role_id INTEGER NOT NULL,
+    name TEXT NOT NULL UNIQUE,
+    key TEXT NOT NULL,
+    identity_id TEXT NOT NULL,
+    created_date TIMESTAMP NOT NULL,
+    allow_origins TEXT,
+    scopes TEXT,
+    FOREIGN KEY (role_id) REFERENCES role(id),
+    FOREIGN KEY (identity_id) REFERENCES identity(identity_id)
+);
+
+CREATE TABLE identity_role(
+    role TEXT NOT NULL PRIMARY KEY,
+    identity_id TEXT NOT NULL,
+    FOREIGN KEY (identity_id) REFERENCES identity(identity_id)
+);
+
+INSERT INTO role(name) VALUES ('manager');
@@ -0,0 +1,3 @@
+-- Your SQL goes here
+
+ALTER TABLE app_key ADD COLUMN "scopes" TEXT NULL;
@@ -0,0 +1,7 @@
+-- Your SQL goes here
+
+CREATE TABLE "identity_role"(
+	"role" VARCHAR(50) NOT NULL PRIMARY KEY,
+	"identity_id" VARCHAR(255) NOT NULL,
+    FOREIGN KEY (identity_id) REFERENCES identity(identity_id)
+);
@@ -20,6 +20,10 @@ pub enum AppKeyCommand {
         /// Set cors policy for request made using this app-key.
         #[structopt(long)]
         allow_origins: Vec<String>,
+        /// Restrict app-key to given REST API scope, e.g. `market` or `payment:read`.
+        /// Can be repeated. Without scopes the key has full access.
+        #[structopt(long = "scope", parse(try_from_str = parse_scope))]
+        scopes: Vec<String>,
     },
     Drop {
         name: String,
@@ -56,6 +60,7 @@ impl AppKeyCommand {
                 role,
                 id,
                 allow_origins: allow_origin,
+                scopes,
             } => {
                 let identity = match id {
                     Some(id) => {
@@ -74,6 +79,7 @@ impl AppKeyCommand {
                     role: role.clone(),
                     identity,
                     allow_origins: allow_origin.clone(),
+                    scopes: scopes.clone(),
                 };
                 let key = bus::service(model::BUS_ID).send(create).await??;
                 Ok(CommandOutput::Object(serde_json::to_value(key)?))
@@ -116,6 +122,7 @@ impl AppKeyCommand {
                         "key".into(),
                         "id".into(),
                         "role".into(),
+                        "scopes".into(),
                         "created".into(),
                     ],
                     values: result
@@ -124,7 +131,7 @@ impl AppKeyCommand {
                         .map(|app_key| {
                             serde_json::json! {[
                                 app_key.name, app_key.key, app_key.identity,
-                                app_key.role, app_key.created_date,
+                                app_key.role, app_key.scopes.join(","), app_key.created_date,
                             ]}
                         })
                         .collect(),
@@ -134,3 +141,8 @@ impl AppKeyCommand {
         }
     }
 }
+
+fn parse_scope(s: &str) -> Result<String> {
+    s.parse::<model::Scope>()?;
+    Ok(s.to_string())
+}
@@ -7,6 +7,7 @@ use ethsign::Protected;
 use rustc_hex::ToHex;
 use sha2::Digest;
 use structopt::*;
+use strum::VariantNames;
 use tokio::io::{AsyncReadExt, BufReader};
 
 use ya_client_model::NodeId;
@@ -128,6 +129,14 @@ pub enum IdentityCommand {
         /// password for keystore
         #[structopt(long = "no-password")]
         no_password: bool,
+
+        /// JSON-RPC endpoint of external signing service holding the key
+        #[structopt(long, requires = "address", conflicts_with = "from-keystore")]
+        remote_signer: Option<String>,
+
+        /// Address of the key held by remote signer
+        #[structopt(long, requires = "remote-signer")]
+        address: Option<NodeId>,
     },
     /// Update given identity
     Update {
@@ -140,6 +149,20 @@ pub enum IdentityCommand {
         set_default: bool,
     },
 
+    /// Use given identity for a role (payment, node-id, signing)
+    SetRole {
+        #[structopt(possible_values = identity::IdentityRole::VARIANTS)]
+        role: identity::IdentityRole,
+        /// Identity to assign. Defaults to the default identity
+        node_or_alias: Option<NodeOrAlias>,
+    },
+
+    /// Restore default identity for a role
+    UnsetRole {
+        #[structopt(possible_values = identity::IdentityRole::VARIANTS)]
+        role: identity::IdentityRole,
+    },
+
     /// Drop given identity
     Drop {
         /// Identity alias to drop
@@ -164,7 +187,7 @@ pub struct SignCommand {
     /// Input file path
     file_path: PathBuf,
 
-    /// NodeId or key
+    /// NodeId or key, identity with signing role by default
     node_or_alias: Option<NodeOrAlias>,
 }
 
@@ -185,6 +208,7 @@ impl IdentityCommand {
                         "locked".into(),
                         "alias".into(),
                         "address".into(),
+                        "roles".into(),
                     ],
                     values: identities
                         .into_iter()
@@ -193,7 +217,8 @@ impl IdentityCommand {
                                 if identity.is_default { "X" } else { "" },
                                 if identity.is_locked { "X" } else { "" },
                                 identity.alias,
-                                identity.node_id
+                                identity.node_id,
+                                identity.roles.iter().map(ToString::to_string).collect::<Vec<_>>().join(","),
                             ]}
                         })
                         .collect(),
@@ -226,7 +251,20 @@ impl IdentityCommand {
                 node_or_alias,
                 file_path,
             }) => {
-                let node_id = node_or_alias.clone().unwrap_or_default().resolve().await?;
+                let node_id = match node_or_alias {
+                    Some(node_or_alias) => node_or_alias.resolve().await?,
+                    // Signed by the identity with signing role, if assigned.
+                    None => {
+                        let id = bus::service(identity::BUS_ID)
+                            .send(identity::Get::ByRole(identity::IdentityRole::Signing))
+                            .await
+                            .map_err(|e| anyhow::anyhow!(e))?;
+                        match id? {
+                            Some(id) => id.node_id,
+                            None => anyhow::bail!("signing identity not found"),
+                        }
+                    }
+                };
 
                 let file = tokio::fs::File::open(file_path)
                     .await
@@ -281,10 +319,30 @@ impl IdentityCommand {
                     .map_err(anyhow::Error::msg)?;
                 CommandOutput::object(id)
             }
+            IdentityCommand::Create {
+                alias,
+                remote_signer: Some(url),
+                address: Some(address),
+                ..
+            } => {
+                let id = bus::service(identity::BUS_ID)
+                    .send(identity::CreateGenerated {
+                        alias: alias.clone(),
+                        from_keystore: None,
+                        remote_signer: Some(identity::RemoteSigner {
+                            url: url.clone(),
+                            address: *address,
+                        }),
+                    })
+                    .await
+                    .map_err(anyhow::Error::msg)?;
+                CommandOutput::object(id)
+            }
             IdentityCommand::Create {
                 alias,
                 from_keystore,
                 no_password,
+                ..
             } => {
                 let key_file = if let Some(keystore) = from_keystore {
                     std::fs::read_to_string(keystore)?
@@ -308,6 +366,7 @@ impl IdentityCommand {
                     .send(identity::CreateGenerated {
                         alias: alias.clone(),
                         from_keystore: Some(key_file),
+                        remote_signer: None,
                     })
                     .await
                     .map_err(anyhow::Error::msg)?;
@@ -346,6 +405,30 @@ impl IdentityCommand {
                         .map_err(anyhow::Error::msg)?,
                 )
             }
+            IdentityCommand::SetRole {
+                role,
+                node_or_alias,
+            } => {
+                let node_id = node_or_alias.clone().unwrap_or_default().resolve().await?;
+                CommandOutput::object(
+                    bus::service(identity::BUS_ID)
+                        .send(identity::SetRole {
+                            role: *role,
+                            node_id: Some(node_id),
+                        })
+                        .await
+                        .map_err(anyhow::Error::msg)?,
+                )
+            }
+            IdentityCommand::UnsetRole { role } => CommandOutput::object(
+                bus::service(identity::BUS_ID)
+                    .send(identity::SetRole {
+                        role: *role,
+                        node_id: None,
+                    })
+                    .await
+                    .map_err(anyhow::Error::msg)?,
+            ),
             IdentityCommand::Drop { node_or_alias } => {
                 let command: identity::Get = node_or_alias.clone().into();
                 let id = bus::service(identity::BUS_ID)
@@ -48,12 +48,14 @@ impl<'c> AppKeyDao<'c> {
         role: String,
         identity: NodeId,
         cors_allow_origin: Vec<String>,
+        scopes: Vec<String>,
     ) -> Result<()> {
         use crate::db::schema::app_key as app_key_dsl;
         use crate::db::schema::role as role_dsl;
 
         let cors_allow_origin =
             Some(serde_json::to_string(&cors_allow_origin).unwrap_or_else(|_| "[]".to_string()));
+        let scopes = Some(serde_json::to_string(&scopes).unwrap_or_else(|_| "[]".to_string()));
 
         do_with_transaction(self.pool, move |conn| {
             let role: Role = role_dsl::table
@@ -68,6 +70,7 @@ impl<'c> AppKeyDao<'c> {
                     app_key_dsl::identity_id.eq(identity),
                     app_key_dsl::created_date.eq(Utc::now().naive_utc()),
                     app_key_dsl::allow_origins.eq(cors_allow_origin),
+                    app_key_dsl::scopes.eq(scopes),
                 ))
                 .execute(conn)?;
 
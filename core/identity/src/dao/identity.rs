@@ -1,10 +1,12 @@
 use diesel::prelude::*;
 
+use ya_client_model::NodeId;
+
 use ya_persistence::executor::{
     do_with_transaction, readonly_transaction, AsDao, ConnType, PoolType,
 };
 
-pub use crate::db::models::Identity;
+pub use crate::db::models::{Identity, RoleAssignment};
 use crate::db::schema as s;
 
 type Result<T> = std::result::Result<T, super::Error>;
@@ -111,4 +113,25 @@ impl<'c> IdentityDao<'c> {
         })
         .await
     }
+
+    pub async fn list_roles(&self) -> Result<Vec<RoleAssignment>> {
+        readonly_transaction(self.pool, |conn| {
+            Ok(s::identity_role::table.load::<RoleAssignment>(conn)?)
+        })
+        .await
+    }
+
+    pub async fn set_role(&self, role: String, identity_id: Option<NodeId>) -> Result<()> {
+        self.with_transaction(move |conn| {
+            diesel::delete(s::identity_role::table.filter(s::identity_role::role.eq(&role)))
+                .execute(conn)?;
+            if let Some(identity_id) = identity_id {
+                diesel::insert_into(s::identity_role::table)
+                    .values(RoleAssignment { role, identity_id })
+                    .execute(conn)?;
+            }
+            Ok(())
+        })
+        .await
+    }
 }
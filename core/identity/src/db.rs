@@ -5,5 +5,9 @@ pub(crate) mod schema;
 pub mod migrations {
     use diesel_migrations::*;
     #[derive(EmbedMigrations)]
+    #[cfg_attr(
+        feature = "postgres",
+        embed_migrations_options(migrations_path = "migrations-postgres")
+    )]
     struct _Dummy;
 }
@@ -1,7 +1,7 @@
 #![allow(unused)]
 #![allow(clippy::all)]
 
-use crate::db::schema::{app_key, identity, role};
+use crate::db::schema::{app_key, identity, identity_role, role};
 use chrono::NaiveDateTime;
 use diesel::{Associations, Identifiable, Insertable, Queryable};
 use ya_client_model::NodeId;
@@ -19,6 +19,14 @@ pub struct Identity {
     pub created_date: NaiveDateTime,
 }
 
+#[derive(Queryable, Debug, Identifiable, Insertable, Clone)]
+#[table_name = "identity_role"]
+#[primary_key(role)]
+pub struct RoleAssignment {
+    pub role: String,
+    pub identity_id: NodeId,
+}
+
 #[derive(Queryable, Debug, Associations, Identifiable)]
 #[belongs_to(Identity)]
 #[table_name = "app_key"]
@@ -30,6 +38,7 @@ pub struct AppKey {
     pub identity_id: NodeId,
     pub created_date: NaiveDateTime,
     pub allow_origins: Option<String>,
+    pub scopes: Option<String>,
 }
 
 #[derive(Queryable, Debug, Identifiable)]
@@ -51,6 +60,10 @@ impl AppKey {
                 .allow_origins
                 .map(|allowed| serde_json::from_str(&allowed).unwrap_or(vec![]))
                 .unwrap_or(vec![]),
+            scopes: self
+                .scopes
+                .map(|scopes| serde_json::from_str(&scopes).unwrap_or(vec![]))
+                .unwrap_or(vec![]),
         }
     }
 }
@@ -9,6 +9,7 @@ diesel::table! {
         identity_id -> Text,
         created_date -> Timestamp,
         allow_origins -> Nullable<Text>,
+        scopes -> Nullable<Text>,
     }
 }
 
@@ -33,6 +34,13 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    identity_role (role) {
+        role -> Text,
+        identity_id -> Text,
+    }
+}
+
 diesel::table! {
     role (id) {
         id -> Integer,
@@ -54,11 +62,13 @@ diesel::table! {
 diesel::joinable!(app_key -> identity (identity_id));
 diesel::joinable!(app_key -> role (role_id));
 diesel::joinable!(identity_data -> identity (identity_id));
+diesel::joinable!(identity_role -> identity (identity_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
     app_key,
     identity,
     identity_data,
+    identity_role,
     role,
     version_release,
 );
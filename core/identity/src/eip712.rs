@@ -0,0 +1,90 @@
+use tiny_keccak::{Hasher, Keccak};
+
+use ya_core_model::identity::Eip712Domain;
+
+fn keccak256(parts: &[&[u8]]) -> [u8; 32] {
+    let mut hasher = Keccak::v256();
+    for part in parts {
+        hasher.update(part);
+    }
+    let mut hash = [0u8; 32];
+    hasher.finalize(&mut hash);
+    hash
+}
+
+/// `hashStruct(domain)` for the fields present in `domain`.
+pub fn domain_separator(domain: &Eip712Domain) -> [u8; 32] {
+    let mut members = Vec::new();
+    let mut encoded: Vec<[u8; 32]> = Vec::new();
+
+    if let Some(name) = &domain.name {
+        members.push("string name");
+        encoded.push(keccak256(&[name.as_bytes()]));
+    }
+    if let Some(version) = &domain.version {
+        members.push("string version");
+        encoded.push(keccak256(&[version.as_bytes()]));
+    }
+    if let Some(chain_id) = domain.chain_id {
+        members.push("uint256 chainId");
+        let mut word = [0u8; 32];
+        word[24..].copy_from_slice(&chain_id.to_be_bytes());
+        encoded.push(word);
+    }
+    if let Some(verifying_contract) = &domain.verifying_contract {
+        members.push("address verifyingContract");
+        let mut word = [0u8; 32];
+        word[12..].copy_from_slice(verifying_contract);
+        encoded.push(word);
+    }
+    if let Some(salt) = domain.salt {
+        members.push("bytes32 salt");
+        encoded.push(salt);
+    }
+
+    let type_hash = keccak256(&[format!("EIP712Domain({})", members.join(",")).as_bytes()]);
+    let mut parts: Vec<&[u8]> = vec![&type_hash[..]];
+    parts.extend(encoded.iter().map(|word| &word[..]));
+    keccak256(&parts)
+}
+
+/// Digest to be signed for a message with the given `struct_hash`.
+pub fn typed_data_hash(domain: &Eip712Domain, struct_hash: &[u8]) -> [u8; 32] {
+    keccak256(&[&[0x19, 0x01], &domain_separator(domain), struct_hash])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rustc_hex::{FromHex, ToHex};
+
+    // Example from the EIP-712 specification.
+    #[test]
+    fn test_typed_data_hash() {
+        let mut verifying_contract = [0u8; 20];
+        verifying_contract.copy_from_slice(
+            &"CcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+                .from_hex::<Vec<u8>>()
+                .unwrap(),
+        );
+        let domain = Eip712Domain {
+            name: Some("Ether Mail".to_string()),
+            version: Some("1".to_string()),
+            chain_id: Some(1),
+            verifying_contract: Some(verifying_contract),
+            salt: None,
+        };
+        let mail: Vec<u8> = "c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e"
+            .from_hex()
+            .unwrap();
+
+        assert_eq!(
+            domain_separator(&domain).to_hex::<String>(),
+            "f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f"
+        );
+        assert_eq!(
+            typed_data_hash(&domain, &mail).to_hex::<String>(),
+            "be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2"
+        );
+    }
+}
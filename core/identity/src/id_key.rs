@@ -1,6 +1,7 @@
 #![allow(unused)]
 
 use std::convert::TryFrom;
+use std::time::Instant;
 
 use anyhow::Context;
 use ethsign::keyfile::Bytes;
@@ -10,12 +11,25 @@ use ya_client_model::NodeId;
 
 use crate::dao::identity::Identity;
 use crate::dao::Error;
+use crate::lock_policy::LockPolicy;
+use crate::remote_signer::RemoteKey;
 
 pub struct IdentityKey {
     id: NodeId,
     alias: Option<String>,
-    key_file: KeyFile,
-    secret: Option<SecretKey>,
+    store: KeyStore,
+    /// Key can't be decrypted with empty password.
+    protected: bool,
+    unlocked_at: Instant,
+    last_used: Instant,
+}
+
+enum KeyStore {
+    Local {
+        key_file: KeyFile,
+        secret: Option<SecretKey>,
+    },
+    Remote(RemoteKey),
 }
 
 impl IdentityKey {
@@ -33,36 +47,68 @@ impl IdentityKey {
         std::mem::replace(&mut self.alias, new_alias)
     }
 
+    /// Key stored by external signing service, if any.
+    pub fn remote(&self) -> Option<&RemoteKey> {
+        match &self.store {
+            KeyStore::Remote(remote) => Some(remote),
+            KeyStore::Local { .. } => None,
+        }
+    }
+
     pub fn to_pub_key(&self) -> Result<PublicKey, Error> {
-        match &self.secret {
-            Some(secret) => Ok(secret.public()),
-            None => Err(Error::internal("key locked")),
+        match &self.store {
+            KeyStore::Local {
+                secret: Some(secret),
+                ..
+            } => Ok(secret.public()),
+            KeyStore::Local { secret: None, .. } => Err(Error::internal("key locked")),
+            KeyStore::Remote(remote) => remote.public_key().map_err(Error::internal),
         }
     }
 
     pub fn to_key_file(&self) -> Result<String, serde_json::Error> {
-        serde_json::to_string_pretty(&self.key_file)
+        match &self.store {
+            KeyStore::Local { key_file, .. } => serde_json::to_string_pretty(key_file),
+            KeyStore::Remote(remote) => serde_json::to_string_pretty(remote),
+        }
     }
 
     pub fn is_locked(&self) -> bool {
-        self.secret.is_none()
+        matches!(self.store, KeyStore::Local { secret: None, .. })
     }
 
     pub fn unlock(&mut self, password: Protected) -> Result<bool, Error> {
-        let secret = match self.key_file.to_secret_key(&password) {
-            Ok(secret) => secret,
+        let (key_file, secret) = match &mut self.store {
+            KeyStore::Local { key_file, secret } => (key_file, secret),
+            KeyStore::Remote(_) => return Ok(true),
+        };
+        *secret = match key_file.to_secret_key(&password) {
+            Ok(secret) => Some(secret),
             Err(ethsign::Error::InvalidPassword) => return Ok(false),
             Err(e) => return Err(Error::internal(e)),
         };
-        self.secret = Some(secret);
+        self.unlocked_at = Instant::now();
+        self.last_used = self.unlocked_at;
         Ok(true)
     }
 
+    /// Checks whether unlocked, password protected key should be locked according to `policy`.
+    pub fn should_lock(&self, policy: &LockPolicy, now: Instant) -> bool {
+        self.protected
+            && !self.is_locked()
+            && policy.should_lock(self.unlocked_at, self.last_used, now)
+    }
+
     /// Sign given 32-byte message with the key.
-    pub fn sign(&self, data: &[u8]) -> Option<Vec<u8>> {
-        let s = match &self.secret {
-            Some(secret) => secret,
-            None => return None,
+    /// Remote keys have to be signed with [`RemoteKey::sign`].
+    pub fn sign(&mut self, data: &[u8]) -> Option<Vec<u8>> {
+        self.last_used = Instant::now();
+        let s = match &self.store {
+            KeyStore::Local {
+                secret: Some(secret),
+                ..
+            } => secret,
+            _ => return None,
         };
         s.sign(data).ok().map(|s| {
             let mut v = Vec::with_capacity(33);
@@ -76,27 +122,38 @@ impl IdentityKey {
     }
 
     pub fn lock(&mut self, new_password: Option<String>) -> anyhow::Result<()> {
+        let (key_file, secret) = match &mut self.store {
+            KeyStore::Local { key_file, secret } => (key_file, secret),
+            KeyStore::Remote(_) => anyhow::bail!("remote key can't be locked"),
+        };
         if let Some(new_password) = new_password {
-            if let Some(secret) = self.secret.take() {
+            if let Some(secret) = secret.take() {
+                self.protected = !new_password.is_empty();
                 let crypto = secret.to_crypto(&Protected::new(new_password), KEY_ITERATIONS)?;
-                self.key_file.crypto = crypto;
+                key_file.crypto = crypto;
             } else {
                 anyhow::bail!("key already locked")
             }
         } else {
-            self.secret = None;
+            *secret = None;
         }
         Ok(())
     }
 
     pub fn from_secret(alias: Option<String>, secret: SecretKey, password: Protected) -> Self {
+        let protected = !password.as_ref().is_empty();
         let key_file = key_file_from_secret(&secret, password);
         let id = NodeId::from(secret.public().address().as_ref());
         IdentityKey {
             id,
             alias,
-            key_file,
-            secret: Some(secret),
+            store: KeyStore::Local {
+                key_file,
+                secret: Some(secret),
+            },
+            protected,
+            unlocked_at: Instant::now(),
+            last_used: Instant::now(),
         }
     }
 }
@@ -105,15 +162,24 @@ impl TryFrom<Identity> for IdentityKey {
     type Error = serde_json::Error;
 
     fn try_from(value: Identity) -> Result<Self, Self::Error> {
-        let key_file: KeyFile = serde_json::from_str(&value.key_file_json)?;
         let id = value.identity_id;
         let alias = value.alias;
-        let secret = key_file.to_secret_key(&Protected::new("")).ok();
+        let (store, protected) = if RemoteKey::is_remote(&value.key_file_json) {
+            let remote: RemoteKey = serde_json::from_str(&value.key_file_json)?;
+            (KeyStore::Remote(remote), false)
+        } else {
+            let key_file: KeyFile = serde_json::from_str(&value.key_file_json)?;
+            let secret = key_file.to_secret_key(&Protected::new("")).ok();
+            let protected = secret.is_none();
+            (KeyStore::Local { key_file, secret }, protected)
+        };
         Ok(IdentityKey {
             id,
             alias,
-            key_file,
-            secret,
+            store,
+            protected,
+            unlocked_at: Instant::now(),
+            last_used: Instant::now(),
         })
     }
 }
@@ -126,13 +192,19 @@ pub fn default_password() -> Protected {
 }
 
 pub fn generate_new(alias: Option<String>, password: Protected) -> IdentityKey {
+    let protected = !password.as_ref().is_empty();
     let (key_file, secret) = generate_new_secret(password);
     let id = NodeId::from(secret.public().address().as_ref());
     IdentityKey {
         id,
         alias,
-        key_file,
-        secret: Some(secret),
+        store: KeyStore::Local {
+            key_file,
+            secret: Some(secret),
+        },
+        protected,
+        unlocked_at: Instant::now(),
+        last_used: Instant::now(),
     }
 }
 
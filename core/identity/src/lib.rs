@@ -9,4 +9,7 @@ pub mod service;
 mod autoconf;
 pub mod dao;
 mod db;
+mod eip712;
 mod id_key;
+mod lock_policy;
+mod remote_signer;
@@ -0,0 +1,129 @@
+/// Policy of keeping password protected identities unlocked in memory.
+use std::env;
+use std::time::{Duration, Instant};
+
+use anyhow::Context;
+
+const ENV_IDLE_LOCK_TIMEOUT: &str = "YAGNA_IDENTITY_IDLE_LOCK_TIMEOUT";
+const ENV_PASSPHRASE_TTL: &str = "YAGNA_IDENTITY_PASSPHRASE_TTL";
+
+/// Unlock attempts allowed before further attempts are rejected.
+pub const MAX_UNLOCK_ATTEMPTS: u32 = 5;
+/// Time after the last failed attempt when unlocking is allowed again.
+pub const UNLOCK_BACKOFF: Duration = Duration::from_secs(60);
+
+#[derive(Clone, Debug, Default)]
+pub struct LockPolicy {
+    /// Lock identity which wasn't used for signing for this long.
+    pub idle_timeout: Option<Duration>,
+    /// Lock identity this long after it was unlocked, regardless of its usage.
+    pub passphrase_ttl: Option<Duration>,
+}
+
+impl LockPolicy {
+    pub fn from_env() -> anyhow::Result<Self> {
+        Ok(LockPolicy {
+            idle_timeout: duration_from_env(ENV_IDLE_LOCK_TIMEOUT)?,
+            passphrase_ttl: duration_from_env(ENV_PASSPHRASE_TTL)?,
+        })
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.idle_timeout.is_some() || self.passphrase_ttl.is_some()
+    }
+
+    pub fn should_lock(&self, unlocked_at: Instant, last_used: Instant, now: Instant) -> bool {
+        let idle = self
+            .idle_timeout
+            .map(|timeout| now.saturating_duration_since(last_used) >= timeout)
+            .unwrap_or(false);
+        let expired = self
+            .passphrase_ttl
+            .map(|ttl| now.saturating_duration_since(unlocked_at) >= ttl)
+            .unwrap_or(false);
+        idle || expired
+    }
+}
+
+fn duration_from_env(var: &str) -> anyhow::Result<Option<Duration>> {
+    match env::var(var) {
+        Ok(v) => humantime::parse_duration(&v)
+            .map(Some)
+            .with_context(|| format!("Failed to parse duration from {}", var)),
+        Err(_) => Ok(None),
+    }
+}
+
+/// Tracks failed unlock attempts of a single identity.
+#[derive(Clone, Debug, Default)]
+pub struct UnlockAttempts {
+    failed: u32,
+    last_failure: Option<Instant>,
+}
+
+impl UnlockAttempts {
+    pub fn is_blocked(&self, now: Instant) -> bool {
+        match self.last_failure {
+            Some(last_failure) if self.failed >= MAX_UNLOCK_ATTEMPTS => {
+                now.saturating_duration_since(last_failure) < UNLOCK_BACKOFF
+            }
+            _ => false,
+        }
+    }
+
+    pub fn failure(&mut self, now: Instant) {
+        if !self.is_blocked(now) && self.failed >= MAX_UNLOCK_ATTEMPTS {
+            self.failed = 0;
+        }
+        self.failed += 1;
+        self.last_failure = Some(now);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_disabled_policy_never_locks() {
+        let now = Instant::now();
+        let policy = LockPolicy::default();
+        assert!(!policy.is_enabled());
+        assert!(!policy.should_lock(now, now, now + Duration::from_secs(3600)));
+    }
+
+    #[test]
+    fn test_idle_timeout() {
+        let start = Instant::now();
+        let policy = LockPolicy {
+            idle_timeout: Some(Duration::from_secs(60)),
+            passphrase_ttl: None,
+        };
+        let used = start + Duration::from_secs(50);
+        assert!(!policy.should_lock(start, used, start + Duration::from_secs(100)));
+        assert!(policy.should_lock(start, used, start + Duration::from_secs(110)));
+    }
+
+    #[test]
+    fn test_passphrase_ttl() {
+        let start = Instant::now();
+        let policy = LockPolicy {
+            idle_timeout: None,
+            passphrase_ttl: Some(Duration::from_secs(60)),
+        };
+        let now = start + Duration::from_secs(60);
+        assert!(policy.should_lock(start, now, now));
+    }
+
+    #[test]
+    fn test_unlock_attempts() {
+        let start = Instant::now();
+        let mut attempts = UnlockAttempts::default();
+        for _ in 0..MAX_UNLOCK_ATTEMPTS {
+            assert!(!attempts.is_blocked(start));
+            attempts.failure(start);
+        }
+        assert!(attempts.is_blocked(start));
+        assert!(!attempts.is_blocked(start + UNLOCK_BACKOFF));
+    }
+}
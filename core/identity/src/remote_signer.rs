@@ -0,0 +1,138 @@
+/// Client of an external signing service (e.g. KMS/HSM gateway) speaking JSON-RPC 2.0.
+///
+/// The service is expected to implement two methods:
+///  * `publicKey(address)` returning hex encoded, uncompressed 64-byte public key,
+///  * `sign(address, payload)` returning hex encoded 65-byte `v || r || s` signature
+///    of the 32-byte payload.
+use std::time::Duration;
+
+use anyhow::{anyhow, Context};
+use ethsign::PublicKey;
+use rustc_hex::{FromHex, ToHex};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use ya_client_model::NodeId;
+
+const REMOTE_SIGNER_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Identity key held by remote signer. Stored in place of the key file.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteKey {
+    pub remote_signer: String,
+    pub address: NodeId,
+    pub public_key: String,
+}
+
+#[derive(Deserialize)]
+struct RpcResponse<T> {
+    result: Option<T>,
+    error: Option<RpcError>,
+}
+
+#[derive(Deserialize)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RemoteKey {
+    pub fn is_remote(key_file_json: &str) -> bool {
+        serde_json::from_str::<serde_json::Value>(key_file_json)
+            .map(|v| v.get("remoteSigner").is_some())
+            .unwrap_or(false)
+    }
+
+    /// Retrieves public key of `address` from remote signer and checks it matches the address.
+    pub async fn fetch(remote_signer: String, address: NodeId) -> anyhow::Result<Self> {
+        let public_key: String = call(
+            &remote_signer,
+            "publicKey",
+            json!({ "address": address.to_string() }),
+        )
+        .await?;
+        let key = RemoteKey {
+            remote_signer,
+            address,
+            public_key: public_key.trim_start_matches("0x").to_string(),
+        };
+
+        let derived = NodeId::from(key.public_key()?.address().as_ref());
+        if derived != address {
+            anyhow::bail!(
+                "remote signer returned public key of {} instead of {}",
+                derived,
+                address
+            );
+        }
+        Ok(key)
+    }
+
+    pub fn public_key(&self) -> anyhow::Result<PublicKey> {
+        let bytes: Vec<u8> = self
+            .public_key
+            .from_hex()
+            .context("invalid public key hex")?;
+        PublicKey::from_slice(&bytes).map_err(|e| anyhow!("invalid public key: {:?}", e))
+    }
+
+    pub async fn sign(&self, payload: &[u8]) -> anyhow::Result<Vec<u8>> {
+        let signature: String = call(
+            &self.remote_signer,
+            "sign",
+            json!({
+                "address": self.address.to_string(),
+                "payload": format!("0x{}", payload.to_hex::<String>()),
+            }),
+        )
+        .await?;
+        let signature: Vec<u8> = signature
+            .trim_start_matches("0x")
+            .from_hex()
+            .context("invalid signature hex")?;
+        if signature.len() != 65 {
+            anyhow::bail!("invalid signature length: {}", signature.len());
+        }
+        Ok(signature)
+    }
+}
+
+async fn call<T: DeserializeOwned>(
+    url: &str,
+    method: &str,
+    params: serde_json::Value,
+) -> anyhow::Result<T> {
+    let request = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": method,
+        "params": params,
+    });
+    let response: RpcResponse<T> = awc::Client::builder()
+        .timeout(REMOTE_SIGNER_TIMEOUT)
+        .finish()
+        .post(url)
+        .send_json(&request)
+        .await
+        .map_err(|e| anyhow!("remote signer {} unreachable: {}", url, e))?
+        .json()
+        .await
+        .map_err(|e| anyhow!("invalid remote signer response: {}", e))?;
+
+    match response {
+        RpcResponse {
+            error: Some(error), ..
+        } => Err(anyhow!(
+            "remote signer error [{}]: {}",
+            error.code,
+            error.message
+        )),
+        RpcResponse {
+            result: Some(result),
+            ..
+        } => Ok(result),
+        _ => Err(anyhow!("empty remote signer response")),
+    }
+}
@@ -9,6 +9,7 @@ use crate::cli::Command;
 
 mod appkey;
 mod identity;
+mod rest;
 
 pub struct Identity;
 
@@ -30,4 +31,8 @@ impl Identity {
         appkey::activate(&db).await?;
         Ok(())
     }
+
+    pub fn rest<C: Provider<Self, ()>>(_ctx: &C) -> actix_web::Scope {
+        rest::web_scope()
+    }
 }
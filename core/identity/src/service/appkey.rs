@@ -68,6 +68,7 @@ pub async fn preconfigured_to_appkey_model(
         identity: node_id,
         created_date,
         allow_origins: vec![],
+        scopes: vec![],
     })
 }
 
@@ -114,6 +115,10 @@ pub async fn activate(db: &DbExecutor) -> anyhow::Result<()> {
                     }
                 }
 
+                for scope in &create.scopes {
+                    scope.parse::<model::Scope>()?;
+                }
+
                 let result = match dao.get_for_name(create.name.clone()).await {
                     Ok((app_key, _)) => {
                         if app_key.identity_id == create.identity {
@@ -132,6 +137,7 @@ pub async fn activate(db: &DbExecutor) -> anyhow::Result<()> {
                             create.role,
                             create.identity,
                             create.allow_origins,
+                            create.scopes,
                         )
                         .await
                         .map_err(model::Error::internal)
@@ -3,6 +3,7 @@ use std::collections::HashMap;
 use std::convert::{TryFrom, TryInto};
 use std::rc::Rc;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use anyhow::bail;
 use chrono::Utc;
@@ -14,11 +15,17 @@ use ya_client_model::NodeId;
 use ya_service_bus::{typed as bus, RpcEndpoint, RpcMessage};
 
 use ya_core_model::identity as model;
+use ya_core_model::journal;
 use ya_persistence::executor::DbExecutor;
 
 use crate::dao::identity::Identity;
 use crate::dao::{Error as DaoError, IdentityDao};
+use crate::eip712;
 use crate::id_key::{default_password, generate_new, IdentityKey};
+use crate::lock_policy::{LockPolicy, UnlockAttempts};
+use crate::remote_signer::RemoteKey;
+
+const LOCK_CHECK_INTERVAL: Duration = Duration::from_secs(10);
 
 #[derive(Default)]
 struct Subscription {
@@ -39,12 +46,19 @@ pub struct IdentityService {
     default_key: NodeId,
     ids: HashMap<NodeId, IdentityKey>,
     alias_to_id: HashMap<String, NodeId>,
+    roles: HashMap<model::IdentityRole, NodeId>,
+    lock_policy: LockPolicy,
+    unlock_attempts: HashMap<NodeId, UnlockAttempts>,
     sender: futures::channel::mpsc::UnboundedSender<model::event::Event>,
     subscription: Rc<RefCell<Subscription>>,
     db: DbExecutor,
 }
 
-fn to_info(default_key: &NodeId, key: &IdentityKey) -> model::IdentityInfo {
+fn to_info(
+    default_key: &NodeId,
+    roles: &HashMap<model::IdentityRole, NodeId>,
+    key: &IdentityKey,
+) -> model::IdentityInfo {
     let node_id = key.id();
     let is_default = *default_key == node_id;
     model::IdentityInfo {
@@ -52,14 +66,38 @@ fn to_info(default_key: &NodeId, key: &IdentityKey) -> model::IdentityInfo {
         node_id,
         is_locked: key.is_locked(),
         is_default,
+        roles: roles_of(roles, &node_id),
     }
 }
 
+fn roles_of(
+    roles: &HashMap<model::IdentityRole, NodeId>,
+    node_id: &NodeId,
+) -> Vec<model::IdentityRole> {
+    let mut roles: Vec<_> = roles
+        .iter()
+        .filter(|(_, id)| *id == node_id)
+        .map(|(role, _)| *role)
+        .collect();
+    roles.sort_by_key(ToString::to_string);
+    roles
+}
+
 fn send_event(s: Ref<Subscription>, event: model::event::Event) -> impl Future<Output = ()> {
     let subscriptions: Vec<String> = s.subscriptions.clone();
     log::debug!("sending event: {:?} to {:?}", event, subscriptions);
 
+    let journal_event = match &event {
+        model::event::Event::AccountLocked { identity } => {
+            journal::Event::new(journal::Category::Identity, "identity-locked").node_id(*identity)
+        }
+        model::event::Event::AccountUnlocked { identity } => {
+            journal::Event::new(journal::Category::Identity, "identity-unlocked").node_id(*identity)
+        }
+    };
+
     async move {
+        journal_event.record().await;
         for endpoint in subscriptions {
             let msg = event.clone();
             tokio::task::spawn_local(async move {
@@ -136,6 +174,25 @@ impl IdentityService {
             let _ = ids.insert(key.id(), key);
         }
 
+        let mut roles: HashMap<model::IdentityRole, _> = Default::default();
+        for assignment in db.as_dao::<IdentityDao>().list_roles().await? {
+            match assignment.role.parse() {
+                Ok(role) if ids.contains_key(&assignment.identity_id) => {
+                    let _ = roles.insert(role, assignment.identity_id);
+                }
+                _ => log::warn!(
+                    "ignoring invalid identity role assignment: {} -> {}",
+                    assignment.role,
+                    assignment.identity_id
+                ),
+            }
+        }
+
+        let lock_policy = LockPolicy::from_env()?;
+        if lock_policy.is_enabled() {
+            log::info!("identity lock policy: {:?}", lock_policy);
+        }
+
         Ok(IdentityService {
             default_key,
             db,
@@ -143,6 +200,9 @@ impl IdentityService {
             sender,
             subscription,
             alias_to_id,
+            roles,
+            lock_policy,
+            unlock_attempts: Default::default(),
         })
     }
 
@@ -159,7 +219,7 @@ impl IdentityService {
             None => return Ok(None),
             Some(id) => id,
         };
-        Ok(Some(to_info(&self.default_key, id)))
+        Ok(Some(to_info(&self.default_key, &self.roles, id)))
     }
 
     pub fn get_by_id(&self, node_id: &NodeId) -> Result<Option<model::IdentityInfo>, model::Error> {
@@ -167,7 +227,7 @@ impl IdentityService {
             None => return Ok(None),
             Some(id) => id,
         };
-        Ok(Some(to_info(&self.default_key, id)))
+        Ok(Some(to_info(&self.default_key, &self.roles, id)))
     }
 
     pub fn get_default_id(&self) -> Result<Option<model::IdentityInfo>, model::Error> {
@@ -175,14 +235,46 @@ impl IdentityService {
             None => return Ok(None),
             Some(id) => id,
         };
-        Ok(Some(to_info(&self.default_key, id)))
+        Ok(Some(to_info(&self.default_key, &self.roles, id)))
+    }
+
+    pub fn get_by_role(
+        &self,
+        role: model::IdentityRole,
+    ) -> Result<Option<model::IdentityInfo>, model::Error> {
+        let node_id = self.roles.get(&role).unwrap_or(&self.default_key);
+        self.get_by_id(node_id)
+    }
+
+    pub async fn set_role(
+        &mut self,
+        role: model::IdentityRole,
+        node_id: Option<NodeId>,
+    ) -> Result<model::Ack, model::Error> {
+        if let Some(node_id) = &node_id {
+            if !self.ids.contains_key(node_id) {
+                return Err(model::Error::NodeNotFound(Box::new(*node_id)));
+            }
+        }
+
+        self.db
+            .as_dao::<IdentityDao>()
+            .set_role(role.to_string(), node_id)
+            .await
+            .map_err(model::Error::new_err_msg)?;
+
+        match node_id {
+            Some(node_id) => self.roles.insert(role, node_id),
+            None => self.roles.remove(&role),
+        };
+        Ok(model::Ack {})
     }
 
     pub fn list_ids(&self) -> Result<Vec<model::IdentityInfo>, model::Error> {
         Ok(self
             .ids
             .values()
-            .map(|id_key| to_info(&self.default_key, id_key))
+            .map(|id_key| to_info(&self.default_key, &self.roles, id_key))
             .collect())
     }
 
@@ -210,7 +302,7 @@ impl IdentityService {
             .await
             .map_err(|e| model::Error::InternalErr(e.to_string()))?;
 
-        let output = to_info(&self.default_key, &key);
+        let output = to_info(&self.default_key, &self.roles, &key);
 
         if let Some(alias) = alias {
             let _ = self.alias_to_id.insert(alias, key.id());
@@ -245,7 +337,7 @@ impl IdentityService {
             .map_err(|e| model::Error::InternalErr(e.to_string()))?;
 
         let key = IdentityKey::try_from(new_identity).map_err(model::Error::new_err_msg)?;
-        let output = to_info(&self.default_key, &key);
+        let output = to_info(&self.default_key, &self.roles, &key);
 
         if let Some(alias) = alias {
             let _ = self.alias_to_id.insert(alias, key.id());
@@ -254,6 +346,51 @@ impl IdentityService {
         Ok(output)
     }
 
+    /// Remote key is fetched with the service released, like in `sign`.
+    pub async fn create_remote(
+        me: Arc<Mutex<Self>>,
+        alias: Option<String>,
+        remote_signer: model::RemoteSigner,
+    ) -> Result<model::IdentityInfo, model::Error> {
+        if me.lock().await.ids.contains_key(&remote_signer.address) {
+            return Err(model::Error::AlreadyExists);
+        }
+        let remote = RemoteKey::fetch(remote_signer.url, remote_signer.address)
+            .await
+            .map_err(model::Error::new_err_msg)?;
+
+        let mut this = me.lock().await;
+        // Could be created by a concurrent call, while the key was fetched.
+        if this.ids.contains_key(&remote.address) {
+            return Err(model::Error::AlreadyExists);
+        }
+
+        let new_identity = Identity {
+            identity_id: remote.address,
+            key_file_json: serde_json::to_string(&remote).map_err(model::Error::new_err_msg)?,
+            is_default: false,
+            is_deleted: false,
+            alias: alias.clone(),
+            note: None,
+            created_date: Utc::now().naive_utc(),
+        };
+
+        this.db
+            .as_dao::<IdentityDao>()
+            .create_identity(new_identity.clone())
+            .await
+            .map_err(|e| model::Error::InternalErr(e.to_string()))?;
+
+        let key = IdentityKey::try_from(new_identity).map_err(model::Error::new_err_msg)?;
+        let output = to_info(&this.default_key, &this.roles, &key);
+
+        if let Some(alias) = alias {
+            let _ = this.alias_to_id.insert(alias, key.id());
+        }
+        let _ = this.ids.insert(key.id(), key);
+        Ok(output)
+    }
+
     fn get_key_by_id(&mut self, node_id: &NodeId) -> Result<&mut IdentityKey, model::Error> {
         Ok(match self.ids.get_mut(node_id) {
             Some(v) => v,
@@ -267,11 +404,12 @@ impl IdentityService {
         new_password: Option<String>,
     ) -> Result<model::IdentityInfo, model::Error> {
         let default_key = self.default_key;
+        let roles = self.roles.clone();
         let key = self.get_key_by_id(&node_id)?;
         let new_key = new_password.is_some();
         key.lock(new_password)
             .map_err(|e| model::Error::InternalErr(e.to_string()))?;
-        let output = to_info(&default_key, key);
+        let output = to_info(&default_key, &roles, key);
         if new_key {
             let key_file = key
                 .to_key_file()
@@ -293,21 +431,83 @@ impl IdentityService {
         password: Protected,
     ) -> Result<model::IdentityInfo, model::Error> {
         let default_key = self.default_key;
+        let roles = self.roles.clone();
+        let now = Instant::now();
+        if let Some(attempts) = self.unlock_attempts.get(&node_id) {
+            if attempts.is_blocked(now) {
+                log::warn!("rejected unlock of {}: too many failed attempts", node_id);
+                return Err(model::Error::UnlockBlocked);
+            }
+        }
+
         let key = self.get_key_by_id(&node_id)?;
         if key.unlock(password).map_err(model::Error::new_err_msg)? {
-            Ok(to_info(&default_key, key))
+            let info = to_info(&default_key, &roles, key);
+            self.unlock_attempts.remove(&node_id);
+            Ok(info)
         } else {
+            self.unlock_attempts
+                .entry(node_id)
+                .or_default()
+                .failure(now);
             Err(model::Error::InvalidPassword)
         }
     }
 
-    pub async fn sign(&mut self, node_id: NodeId, data: Vec<u8>) -> Result<Vec<u8>, model::Error> {
-        let key = self.get_key_by_id(&node_id)?;
-        if let Some(signature) = key.sign(data.as_slice()) {
-            Ok(signature)
-        } else {
-            Err(model::Error::new_err_msg("sign error"))
+    /// Locks identities which should not be kept unlocked any longer according to lock policy.
+    pub fn lock_expired(&mut self) -> Vec<NodeId> {
+        let now = Instant::now();
+        let policy = &self.lock_policy;
+        self.ids
+            .values_mut()
+            .filter(|key| key.should_lock(policy, now))
+            .filter_map(|key| match key.lock(None) {
+                Ok(_) => Some(key.id()),
+                Err(e) => {
+                    log::error!("failed to lock identity {}: {}", key.id(), e);
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Remote signers are called with the service released, so a slow one doesn't block
+    /// other identity calls.
+    pub async fn sign(
+        me: Arc<Mutex<Self>>,
+        node_id: NodeId,
+        data: Vec<u8>,
+    ) -> Result<Vec<u8>, model::Error> {
+        let remote = {
+            let mut this = me.lock().await;
+            let key = this.get_key_by_id(&node_id)?;
+            match key.remote().cloned() {
+                Some(remote) => remote,
+                None => {
+                    return key
+                        .sign(data.as_slice())
+                        .ok_or_else(|| model::Error::new_err_msg("sign error"))
+                }
+            }
+        };
+        remote
+            .sign(data.as_slice())
+            .await
+            .map_err(model::Error::new_err_msg)
+    }
+
+    pub async fn sign_typed_data(
+        me: Arc<Mutex<Self>>,
+        sign: model::SignTypedData,
+    ) -> Result<Vec<u8>, model::Error> {
+        if sign.struct_hash.len() != 32 {
+            return Err(model::Error::new_err_msg(format!(
+                "invalid struct hash length: {}",
+                sign.struct_hash.len()
+            )));
         }
+        let digest = eip712::typed_data_hash(&sign.domain, &sign.struct_hash);
+        Self::sign(me, sign.node_id, digest.to_vec()).await
     }
 
     pub async fn update_identity(
@@ -363,6 +563,7 @@ impl IdentityService {
             node_id,
             is_locked: key.is_locked(),
             is_default: self.default_key == node_id,
+            roles: roles_of(&self.roles, &node_id),
         })
     }
 
@@ -400,7 +601,31 @@ impl IdentityService {
         key.to_key_file().map_err(model::Error::new_err_msg)
     }
 
+    fn spawn_lock_policy(me: Arc<Mutex<Self>>) {
+        tokio::task::spawn_local(async move {
+            if !me.lock().await.lock_policy.is_enabled() {
+                return;
+            }
+            let mut interval = tokio::time::interval(LOCK_CHECK_INTERVAL);
+            loop {
+                interval.tick().await;
+                let (locked, mut sender) = {
+                    let mut this = me.lock().await;
+                    (this.lock_expired(), this.sender().clone())
+                };
+                for identity in locked {
+                    log::info!("identity {} locked by lock policy", identity);
+                    let _ = sender
+                        .send(model::event::Event::AccountLocked { identity })
+                        .await;
+                }
+            }
+        });
+    }
+
     pub fn bind_service(me: Arc<Mutex<Self>>) {
+        Self::spawn_lock_policy(me.clone());
+
         let this = me.clone();
         let _ = bus::bind(model::BUS_ID, move |_list: model::List| {
             let this = this.clone();
@@ -414,6 +639,7 @@ impl IdentityService {
                     model::Get::ByAlias(alias) => this.lock().await.get_by_alias(&alias),
                     model::Get::ByNodeId(node_id) => this.lock().await.get_by_id(&node_id),
                     model::Get::ByDefault => this.lock().await.get_default_id(),
+                    model::Get::ByRole(role) => this.lock().await.get_by_role(role),
                     _ => Err(model::Error::InternalErr("unsupported query".to_string())),
                 }
             }
@@ -422,7 +648,9 @@ impl IdentityService {
         let _ = bus::bind(model::BUS_ID, move |create: model::CreateGenerated| {
             let this = this.clone();
             async move {
-                if let Some(key_store) = create.from_keystore {
+                let result = if let Some(remote_signer) = create.remote_signer {
+                    Self::create_remote(this.clone(), create.alias, remote_signer).await
+                } else if let Some(key_store) = create.from_keystore {
                     let key: KeyFile = serde_json::from_str(key_store.as_str())
                         .map_err(model::Error::keystore_format)?;
                     let addr_bytes = match &key.address {
@@ -441,7 +669,16 @@ impl IdentityService {
                         .await
                 } else {
                     this.lock().await.create_identity(create.alias).await
+                };
+
+                if let Ok(info) = &result {
+                    journal::Event::new(journal::Category::Identity, "identity-created")
+                        .node_id(info.node_id)
+                        .details(serde_json::json!({ "alias": info.alias }))
+                        .record()
+                        .await;
                 }
+                result
             }
         });
 
@@ -451,6 +688,16 @@ impl IdentityService {
             async move { this.lock().await.update_identity(update).await }
         });
         let this = me.clone();
+        let _ = bus::bind(model::BUS_ID, move |set_role: model::SetRole| {
+            let this = this.clone();
+            async move {
+                this.lock()
+                    .await
+                    .set_role(set_role.role, set_role.node_id)
+                    .await
+            }
+        });
+        let this = me.clone();
         let _ = bus::bind(model::BUS_ID, move |lock: model::Lock| {
             let this = this.clone();
             async move {
@@ -496,7 +743,12 @@ impl IdentityService {
         let this = me.clone();
         let _ = bus::bind(model::BUS_ID, move |sign: model::Sign| {
             let this = this.clone();
-            async move { this.lock().await.sign(sign.node_id, sign.payload).await }
+            async move { Self::sign(this, sign.node_id, sign.payload).await }
+        });
+        let this = me.clone();
+        let _ = bus::bind(model::BUS_ID, move |sign: model::SignTypedData| {
+            let this = this.clone();
+            async move { Self::sign_typed_data(this, sign).await }
         });
         let this = me.clone();
         let _ = bus::bind(model::BUS_ID, move |subscribe: model::Subscribe| {
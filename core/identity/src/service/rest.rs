@@ -0,0 +1,35 @@
+use actix_web::{web, HttpResponse, Responder};
+use serde::Deserialize;
+
+use ya_client_model::ErrorMessage;
+use ya_core_model::identity as model;
+use ya_service_api_web::middleware::Identity;
+use ya_service_bus::{typed as bus, RpcEndpoint};
+
+pub const IDENTITY_API_PATH: &str = "identity-api/v1";
+
+pub fn web_scope() -> actix_web::Scope {
+    actix_web::web::scope(IDENTITY_API_PATH).service(unlock)
+}
+
+#[derive(Deserialize)]
+struct UnlockRequest {
+    password: String,
+}
+
+/// Unlocks identity bound to the application key used for the request.
+#[actix_web::post("/unlock")]
+async fn unlock(body: web::Json<UnlockRequest>, id: Identity) -> impl Responder {
+    let unlock = model::Unlock::with_id(id.identity, body.into_inner().password);
+    match bus::service(model::BUS_ID).send(unlock).await {
+        Ok(Ok(info)) => HttpResponse::Ok().json(info),
+        Ok(Err(e @ model::Error::InvalidPassword)) => {
+            HttpResponse::Unauthorized().json(ErrorMessage::new(e.to_string()))
+        }
+        Ok(Err(e @ model::Error::UnlockBlocked)) => {
+            HttpResponse::TooManyRequests().json(ErrorMessage::new(e.to_string()))
+        }
+        Ok(Err(e)) => HttpResponse::InternalServerError().json(ErrorMessage::new(e.to_string())),
+        Err(e) => HttpResponse::InternalServerError().json(ErrorMessage::new(e.to_string())),
+    }
+}
@@ -0,0 +1,14 @@
+-- Schema equivalent to SQLite migrations up to 2023-01-16-120000_initial.
+
+CREATE TABLE journal_event (
+    id BIGSERIAL PRIMARY KEY,
+    timestamp TIMESTAMP NOT NULL,
+    category TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    subject TEXT,
+    node_id TEXT,
+    details TEXT
+);
+
+CREATE INDEX journal_event_timestamp_idx ON journal_event(timestamp);
+CREATE INDEX journal_event_subject_idx ON journal_event(subject);
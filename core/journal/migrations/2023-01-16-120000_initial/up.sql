@@ -0,0 +1,12 @@
+CREATE TABLE journal_event (
+	id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+	timestamp TIMESTAMP NOT NULL,
+	category VARCHAR(16) NOT NULL,
+	kind VARCHAR(64) NOT NULL,
+	subject VARCHAR(128),
+	node_id VARCHAR(42),
+	details TEXT
+);
+
+CREATE INDEX journal_event_timestamp_idx ON journal_event(timestamp);
+CREATE INDEX journal_event_subject_idx ON journal_event(subject);
@@ -0,0 +1,8 @@
+CREATE TABLE availability_sample (
+	id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+	timestamp TIMESTAMP NOT NULL,
+	offers INTEGER NOT NULL,
+	interval_secs INTEGER NOT NULL
+);
+
+CREATE INDEX availability_sample_timestamp_idx ON availability_sample(timestamp);
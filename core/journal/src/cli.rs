@@ -0,0 +1,130 @@
+use chrono::Utc;
+use structopt::StructOpt;
+
+use ya_client::model::NodeId;
+use ya_core_model::journal;
+use ya_service_api::{CliCtx, CommandOutput, ResponseTable};
+use ya_service_bus::{typed as bus, RpcEndpoint};
+
+/// Journal of daemon events
+#[derive(StructOpt, Debug)]
+pub enum JournalCli {
+    /// Show Provider uptime: Offer availability, served Agreements and Activity success rate
+    Uptime {
+        /// Rolling windows in hours
+        #[structopt(long, use_delimiter = true, default_value = "1,24,168,720")]
+        window: Vec<u32>,
+    },
+    /// List recorded events, oldest first
+    Events {
+        /// One of market, activity, payment, identity
+        #[structopt(long)]
+        category: Option<journal::Category>,
+        /// Event name, e.g. `payment-rejected`
+        #[structopt(long)]
+        kind: Option<String>,
+        #[structopt(long)]
+        node_id: Option<NodeId>,
+        /// Show events from the given period of time
+        #[structopt(long)]
+        last: Option<humantime::Duration>,
+        #[structopt(long, default_value = "100")]
+        limit: u32,
+    },
+}
+
+impl JournalCli {
+    pub async fn run_command(self, ctx: &CliCtx) -> anyhow::Result<CommandOutput> {
+        match self {
+            JournalCli::Events {
+                category,
+                kind,
+                node_id,
+                last,
+                limit,
+            } => {
+                let after_timestamp = match last {
+                    Some(last) => Some(Utc::now() - chrono::Duration::from_std(*last)?),
+                    None => None,
+                };
+                let entries = bus::service(journal::BUS_ID)
+                    .send(journal::ListEvents {
+                        category,
+                        kind,
+                        node_id,
+                        after_timestamp,
+                        max_items: Some(limit),
+                        ..Default::default()
+                    })
+                    .await??;
+                if ctx.json_output {
+                    return CommandOutput::object(entries);
+                }
+
+                let values = entries
+                    .into_iter()
+                    .map(|entry| {
+                        serde_json::json!([
+                            entry.timestamp.format("%Y-%m-%d %H:%M:%S").to_string(),
+                            entry.category.to_string(),
+                            entry.kind,
+                            entry.subject.unwrap_or_default(),
+                            entry.details.map(|d| d.to_string()).unwrap_or_default(),
+                        ])
+                    })
+                    .collect();
+                Ok(ResponseTable {
+                    columns: vec![
+                        "timestamp".to_owned(),
+                        "category".to_owned(),
+                        "kind".to_owned(),
+                        "subject".to_owned(),
+                        "details".to_owned(),
+                    ],
+                    values,
+                }
+                .into())
+            }
+            JournalCli::Uptime { window } => {
+                let windows = bus::service(journal::BUS_ID)
+                    .send(journal::GetUptime {
+                        window_hours: window,
+                    })
+                    .await??;
+                if ctx.json_output {
+                    return CommandOutput::object(windows);
+                }
+
+                let values = windows
+                    .iter()
+                    .map(|w| {
+                        serde_json::json!([
+                            format!("{}h", w.window_hours),
+                            percent(w.offer_published),
+                            w.agreements_served,
+                            w.activities_started,
+                            percent(w.activity_success_rate()),
+                        ])
+                    })
+                    .collect();
+                Ok(ResponseTable {
+                    columns: vec![
+                        "window".to_owned(),
+                        "offer published".to_owned(),
+                        "agreements".to_owned(),
+                        "activities".to_owned(),
+                        "activity success".to_owned(),
+                    ],
+                    values,
+                }
+                .into())
+            }
+        }
+    }
+}
+
+fn percent(fraction: Option<f64>) -> String {
+    fraction
+        .map(|f| format!("{:.1}%", f * 100.0))
+        .unwrap_or_else(|| "N/A".to_owned())
+}
@@ -0,0 +1,36 @@
+use std::time::Duration;
+use structopt::StructOpt;
+
+#[derive(StructOpt, Clone, Debug)]
+pub struct Config {
+    /// Interval in which Journal cleaner will be invoked
+    #[structopt(env = "JOURNAL_CLEANUP_INTERVAL", parse(try_from_str = humantime::parse_duration), default_value = "1h")]
+    pub cleanup_interval: Duration,
+    /// Number of days to persist journal events
+    #[structopt(env = "JOURNAL_STORE_DAYS", default_value = "30")]
+    pub store_days: i32,
+    /// Maximum number of persisted journal events. Oldest events are removed first
+    #[structopt(env = "JOURNAL_MAX_EVENTS", default_value = "100000")]
+    pub max_events: i64,
+}
+
+impl Config {
+    pub fn from_env() -> Result<Config, structopt::clap::Error> {
+        // Empty command line arguments, because we want to use ENV fallback
+        // or default values if ENV variables are not set.
+        Config::from_iter_safe(&[""])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_structopt() {
+        let c = Config::from_env().unwrap();
+        assert_eq!(Duration::from_secs(3600), c.cleanup_interval);
+        assert_eq!(30, c.store_days);
+        assert_eq!(100_000, c.max_events);
+    }
+}
@@ -0,0 +1,165 @@
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+use diesel::prelude::*;
+use serde::Deserialize;
+use std::convert::TryFrom;
+
+use ya_client::model::NodeId;
+use ya_core_model::journal::{Category, Entry, Event};
+use ya_persistence::executor::{do_with_transaction, readonly_transaction, AsDao, PoolType};
+
+use crate::config::Config;
+use crate::db::model::{AvailabilitySample, DbEvent, NewAvailabilitySample, NewDbEvent};
+use crate::db::schema::availability_sample::dsl as sample_dsl;
+use crate::db::schema::journal_event::dsl;
+
+pub const DEFAULT_MAX_ITEMS: u32 = 100;
+pub const MAX_ITEMS_LIMIT: u32 = 1000;
+
+/// Journal query filters. Events are returned in the order they were recorded;
+/// the next page starts after the `id` of the last returned entry.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventFilter {
+    pub category: Option<Category>,
+    pub kind: Option<String>,
+    pub subject: Option<String>,
+    pub node_id: Option<NodeId>,
+    pub after_timestamp: Option<DateTime<Utc>>,
+    pub before_timestamp: Option<DateTime<Utc>>,
+    pub after_id: Option<i64>,
+    pub max_items: Option<u32>,
+}
+
+pub struct JournalDao<'c> {
+    pool: &'c PoolType,
+}
+
+impl<'a> AsDao<'a> for JournalDao<'a> {
+    fn as_dao(pool: &'a PoolType) -> Self {
+        Self { pool }
+    }
+}
+
+impl<'c> JournalDao<'c> {
+    pub async fn insert(&self, events: Vec<Event>) -> anyhow::Result<()> {
+        let events = events.into_iter().map(NewDbEvent::from).collect::<Vec<_>>();
+        do_with_transaction(self.pool, move |conn| {
+            diesel::insert_into(dsl::journal_event)
+                .values(&events)
+                .execute(conn)?;
+            Ok(())
+        })
+        .await
+    }
+
+    pub async fn list(&self, filter: EventFilter) -> anyhow::Result<Vec<Entry>> {
+        readonly_transaction(self.pool, move |conn| {
+            let mut query = dsl::journal_event.order(dsl::id.asc()).into_boxed();
+            if let Some(category) = filter.category {
+                query = query.filter(dsl::category.eq(category.to_string()));
+            }
+            if let Some(kind) = filter.kind {
+                query = query.filter(dsl::kind.eq(kind));
+            }
+            if let Some(subject) = filter.subject {
+                query = query.filter(dsl::subject.eq(subject));
+            }
+            if let Some(node_id) = filter.node_id {
+                query = query.filter(dsl::node_id.eq(node_id.to_string()));
+            }
+            if let Some(ts) = filter.after_timestamp {
+                query = query.filter(dsl::timestamp.ge(ts.naive_utc()));
+            }
+            if let Some(ts) = filter.before_timestamp {
+                query = query.filter(dsl::timestamp.lt(ts.naive_utc()));
+            }
+            if let Some(id) = filter.after_id {
+                query = query.filter(dsl::id.gt(id));
+            }
+            let limit = filter
+                .max_items
+                .unwrap_or(DEFAULT_MAX_ITEMS)
+                .min(MAX_ITEMS_LIMIT);
+
+            query
+                .limit(limit as i64)
+                .load::<DbEvent>(conn)?
+                .into_iter()
+                .map(Entry::try_from)
+                .collect()
+        })
+        .await
+    }
+
+    /// Returns all events of given `kinds` recorded since `since`, in the order they were recorded.
+    pub async fn list_kinds_since(
+        &self,
+        kinds: Vec<String>,
+        since: NaiveDateTime,
+    ) -> anyhow::Result<Vec<Entry>> {
+        readonly_transaction(self.pool, move |conn| {
+            dsl::journal_event
+                .filter(dsl::kind.eq_any(kinds))
+                .filter(dsl::timestamp.ge(since))
+                .order(dsl::id.asc())
+                .load::<DbEvent>(conn)?
+                .into_iter()
+                .map(Entry::try_from)
+                .collect()
+        })
+        .await
+    }
+
+    pub async fn insert_availability(&self, sample: NewAvailabilitySample) -> anyhow::Result<()> {
+        do_with_transaction(self.pool, move |conn| {
+            diesel::insert_into(sample_dsl::availability_sample)
+                .values(&sample)
+                .execute(conn)?;
+            Ok(())
+        })
+        .await
+    }
+
+    pub async fn list_availability_since(
+        &self,
+        since: NaiveDateTime,
+    ) -> anyhow::Result<Vec<AvailabilitySample>> {
+        readonly_transaction(self.pool, move |conn| {
+            Ok(sample_dsl::availability_sample
+                .filter(sample_dsl::timestamp.ge(since))
+                .order(sample_dsl::timestamp.asc())
+                .load::<AvailabilitySample>(conn)?)
+        })
+        .await
+    }
+
+    /// Removes events older than `store_days` and the oldest ones above `max_events`.
+    /// Availability samples older than `store_days` are removed as well.
+    pub async fn clean(&self, config: &Config) -> anyhow::Result<usize> {
+        let cutoff = (Utc::now() - Duration::days(config.store_days as i64)).naive_utc();
+        let max_events = config.max_events;
+        do_with_transaction(self.pool, move |conn| {
+            let mut num_deleted =
+                diesel::delete(dsl::journal_event.filter(dsl::timestamp.lt(cutoff)))
+                    .execute(conn)?;
+
+            let last_removed = dsl::journal_event
+                .select(dsl::id)
+                .order(dsl::id.desc())
+                .offset(max_events)
+                .first::<i64>(conn)
+                .optional()?;
+            if let Some(id) = last_removed {
+                num_deleted +=
+                    diesel::delete(dsl::journal_event.filter(dsl::id.le(id))).execute(conn)?;
+            }
+
+            diesel::delete(
+                sample_dsl::availability_sample.filter(sample_dsl::timestamp.lt(cutoff)),
+            )
+            .execute(conn)?;
+            Ok(num_deleted)
+        })
+        .await
+    }
+}
@@ -0,0 +1,119 @@
+use chrono::NaiveDateTime;
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+use ya_core_model::journal::{Entry, Event};
+
+use crate::db::schema::{availability_sample, journal_event};
+
+#[derive(Clone, Debug, Insertable)]
+#[table_name = "journal_event"]
+pub struct NewDbEvent {
+    pub timestamp: NaiveDateTime,
+    pub category: String,
+    pub kind: String,
+    pub subject: Option<String>,
+    pub node_id: Option<String>,
+    pub details: Option<String>,
+}
+
+#[derive(Clone, Debug, Queryable)]
+pub struct DbEvent {
+    pub id: i64,
+    pub timestamp: NaiveDateTime,
+    pub category: String,
+    pub kind: String,
+    pub subject: Option<String>,
+    pub node_id: Option<String>,
+    pub details: Option<String>,
+}
+
+impl From<Event> for NewDbEvent {
+    fn from(event: Event) -> Self {
+        NewDbEvent {
+            timestamp: event.timestamp,
+            category: event.category.to_string(),
+            kind: event.kind,
+            subject: event.subject,
+            node_id: event.node_id.map(|id| id.to_string()),
+            details: event.details.map(|details| details.to_string()),
+        }
+    }
+}
+
+impl TryFrom<DbEvent> for Entry {
+    type Error = anyhow::Error;
+
+    fn try_from(event: DbEvent) -> Result<Self, Self::Error> {
+        Ok(Entry {
+            id: event.id,
+            timestamp: event.timestamp,
+            category: FromStr::from_str(&event.category)?,
+            kind: event.kind,
+            subject: event.subject,
+            node_id: event
+                .node_id
+                .as_deref()
+                .map(FromStr::from_str)
+                .transpose()?,
+            details: event
+                .details
+                .as_deref()
+                .map(serde_json::from_str)
+                .transpose()?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Insertable)]
+#[table_name = "availability_sample"]
+pub struct NewAvailabilitySample {
+    pub timestamp: NaiveDateTime,
+    pub offers: i32,
+    pub interval_secs: i32,
+}
+
+#[derive(Clone, Debug, Queryable)]
+pub struct AvailabilitySample {
+    pub id: i64,
+    pub timestamp: NaiveDateTime,
+    pub offers: i32,
+    pub interval_secs: i32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ya_core_model::journal::Category;
+
+    #[test]
+    fn test_round_trip() {
+        let event = Event::new(Category::Payment, "invoice-accepted")
+            .subject("a4d5fa1c")
+            .node_id(
+                "0x8b42e4b0c1e5b1d9b9cef3e4e2a1bff8b6b3e8d2"
+                    .parse()
+                    .unwrap(),
+            )
+            .details(serde_json::json!({ "amount": "1.5" }));
+
+        let new = NewDbEvent::from(event.clone());
+        let db = DbEvent {
+            id: 7,
+            timestamp: new.timestamp,
+            category: new.category,
+            kind: new.kind,
+            subject: new.subject,
+            node_id: new.node_id,
+            details: new.details,
+        };
+        let entry = Entry::try_from(db).unwrap();
+
+        assert_eq!(entry.id, 7);
+        assert_eq!(entry.category, event.category);
+        assert_eq!(entry.kind, event.kind);
+        assert_eq!(entry.subject, event.subject);
+        assert_eq!(entry.node_id, event.node_id);
+        assert_eq!(entry.details, event.details);
+    }
+}
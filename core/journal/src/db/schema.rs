@@ -0,0 +1,20 @@
+table! {
+    journal_event (id) {
+        id -> BigInt,
+        timestamp -> Timestamp,
+        category -> Text,
+        kind -> Text,
+        subject -> Nullable<Text>,
+        node_id -> Nullable<Text>,
+        details -> Nullable<Text>,
+    }
+}
+
+table! {
+    availability_sample (id) {
+        id -> BigInt,
+        timestamp -> Timestamp,
+        offers -> Integer,
+        interval_secs -> Integer,
+    }
+}
@@ -0,0 +1,11 @@
+#[macro_use]
+extern crate diesel;
+#[macro_use]
+extern crate diesel_migrations;
+
+mod cli;
+mod config;
+mod db;
+mod service;
+
+pub use service::JournalService;
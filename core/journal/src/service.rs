@@ -0,0 +1,33 @@
+use ya_persistence::executor::DbExecutor;
+use ya_service_api_interfaces::{Provider, Service};
+
+use crate::config::Config;
+use crate::db::migrations;
+
+mod cleaner;
+mod gsb;
+mod rest;
+mod uptime;
+
+pub struct JournalService;
+
+impl Service for JournalService {
+    type Cli = crate::cli::JournalCli;
+}
+
+impl JournalService {
+    pub async fn gsb<C: Provider<Self, DbExecutor>>(ctx: &C) -> anyhow::Result<()> {
+        let db = ctx.component();
+        db.apply_migration(migrations::run_with_output)?;
+
+        let config = Config::from_env()?;
+        gsb::bind_gsb(&db);
+        tokio::task::spawn_local(cleaner::clean_forever(db, config));
+
+        Ok(())
+    }
+
+    pub fn rest<C: Provider<Self, DbExecutor>>(ctx: &C) -> actix_web::Scope {
+        rest::web_scope(ctx.component())
+    }
+}
@@ -0,0 +1,20 @@
+use tokio::time;
+
+use ya_persistence::executor::DbExecutor;
+
+use crate::config::Config;
+use crate::db::dao::JournalDao;
+
+pub async fn clean_forever(db: DbExecutor, config: Config) {
+    let mut interval = time::interval(config.cleanup_interval);
+    loop {
+        interval.tick().await;
+        log::debug!("Journal cleaner job started");
+        match db.as_dao::<JournalDao>().clean(&config).await {
+            Ok(0) => (),
+            Ok(num_deleted) => log::info!("Journal cleaner: {} events removed", num_deleted),
+            Err(e) => log::error!("Journal cleaner error: {}", e),
+        }
+        log::debug!("Journal cleaner job done");
+    }
+}
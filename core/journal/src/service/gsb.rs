@@ -0,0 +1,89 @@
+use chrono::Utc;
+use tokio::sync::mpsc;
+
+use ya_client::model::ErrorMessage;
+use ya_core_model::journal;
+use ya_persistence::executor::DbExecutor;
+use ya_service_bus::typed as bus;
+
+use crate::db::dao::{EventFilter, JournalDao};
+use crate::db::model::NewAvailabilitySample;
+use crate::service::uptime;
+
+/// Events are written in batches, so recording services don't wait for the database.
+const MAX_BATCH_SIZE: usize = 256;
+
+pub fn bind_gsb(db: &DbExecutor) {
+    let (tx, rx) = mpsc::unbounded_channel();
+    tokio::task::spawn_local(writer(db.clone(), rx));
+
+    bus::bind(journal::BUS_ID, move |msg: journal::Record| {
+        let result = tx
+            .send(msg.event)
+            .map_err(|_| ErrorMessage::new("Journal writer stopped".to_string()));
+        async move { result }
+    });
+
+    let sample_db = db.clone();
+    bus::bind(journal::BUS_ID, move |msg: journal::RecordAvailability| {
+        let db = sample_db.clone();
+        async move {
+            let sample = NewAvailabilitySample {
+                timestamp: Utc::now().naive_utc(),
+                offers: msg.offers as i32,
+                interval_secs: msg.interval_secs as i32,
+            };
+            db.as_dao::<JournalDao>()
+                .insert_availability(sample)
+                .await
+                .map_err(|e| ErrorMessage::new(e.to_string()))
+        }
+    });
+
+    let list_db = db.clone();
+    bus::bind(journal::BUS_ID, move |msg: journal::ListEvents| {
+        let db = list_db.clone();
+        async move {
+            let filter = EventFilter {
+                category: msg.category,
+                kind: msg.kind,
+                subject: msg.subject,
+                node_id: msg.node_id,
+                after_timestamp: msg.after_timestamp,
+                max_items: msg.max_items,
+                ..Default::default()
+            };
+            db.as_dao::<JournalDao>()
+                .list(filter)
+                .await
+                .map_err(|e| ErrorMessage::new(e.to_string()))
+        }
+    });
+
+    let uptime_db = db.clone();
+    bus::bind(journal::BUS_ID, move |msg: journal::GetUptime| {
+        let db = uptime_db.clone();
+        async move {
+            uptime::get_uptime(&db, msg.window_hours)
+                .await
+                .map_err(|e| ErrorMessage::new(e.to_string()))
+        }
+    });
+}
+
+async fn writer(db: DbExecutor, mut rx: mpsc::UnboundedReceiver<journal::Event>) {
+    while let Some(event) = rx.recv().await {
+        let mut events = vec![event];
+        while events.len() < MAX_BATCH_SIZE {
+            match rx.try_recv() {
+                Ok(event) => events.push(event),
+                Err(_) => break,
+            }
+        }
+
+        let count = events.len();
+        if let Err(e) = db.as_dao::<JournalDao>().insert(events).await {
+            log::warn!("Failed to write {} journal events: {}", count, e);
+        }
+    }
+}
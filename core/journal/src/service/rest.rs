@@ -0,0 +1,23 @@
+use actix_web::web::{Data, Query};
+use actix_web::{HttpResponse, Responder};
+
+use ya_client::model::ErrorMessage;
+use ya_persistence::executor::DbExecutor;
+
+use crate::db::dao::{EventFilter, JournalDao};
+
+pub const JOURNAL_API_PATH: &str = "/journal-api/v1";
+
+pub fn web_scope(db: DbExecutor) -> actix_web::Scope {
+    actix_web::web::scope(JOURNAL_API_PATH)
+        .app_data(Data::new(db))
+        .service(get_events)
+}
+
+#[actix_web::get("/events")]
+async fn get_events(db: Data<DbExecutor>, query: Query<EventFilter>) -> impl Responder {
+    match db.as_dao::<JournalDao>().list(query.into_inner()).await {
+        Ok(entries) => HttpResponse::Ok().json(entries),
+        Err(e) => HttpResponse::InternalServerError().json(ErrorMessage::new(e.to_string())),
+    }
+}
@@ -0,0 +1,251 @@
+//! Provider uptime over rolling windows, computed from availability samples
+//! recorded by the market and from market and activity events.
+use chrono::{Duration, NaiveDateTime, Utc};
+use std::collections::HashSet;
+
+use ya_client::model::NodeId;
+use ya_core_model::journal::{Entry, UptimeWindow};
+use ya_persistence::executor::DbExecutor;
+
+use crate::db::dao::JournalDao;
+use crate::db::model::AvailabilitySample;
+
+const AGREEMENT_APPROVED: &str = "agreement-approved";
+const ACTIVITY_CREATED: &str = "activity-created";
+const ACTIVITY_DESTROYED: &str = "activity-destroyed";
+const ACTIVITY_STATE_CHANGED: &str = "activity-state-changed";
+
+pub async fn get_uptime(
+    db: &DbExecutor,
+    window_hours: Vec<u32>,
+) -> anyhow::Result<Vec<UptimeWindow>> {
+    let now = Utc::now().naive_utc();
+    let since = now - Duration::hours(window_hours.iter().copied().max().unwrap_or(0) as i64);
+
+    let dao = db.as_dao::<JournalDao>();
+    let samples = dao.list_availability_since(since).await?;
+    let kinds = [
+        AGREEMENT_APPROVED,
+        ACTIVITY_CREATED,
+        ACTIVITY_DESTROYED,
+        ACTIVITY_STATE_CHANGED,
+    ];
+    let events = dao
+        .list_kinds_since(kinds.iter().map(ToString::to_string).collect(), since)
+        .await?;
+
+    Ok(window_hours
+        .into_iter()
+        .map(|hours| compute_window(hours, &samples, &events, now))
+        .collect())
+}
+
+fn compute_window(
+    window_hours: u32,
+    samples: &[AvailabilitySample],
+    events: &[Entry],
+    now: NaiveDateTime,
+) -> UptimeWindow {
+    let since = now - Duration::hours(window_hours as i64);
+    let samples = samples
+        .iter()
+        .filter(|s| s.timestamp >= since && s.timestamp <= now)
+        .collect::<Vec<_>>();
+    let events = events
+        .iter()
+        .filter(|e| e.timestamp >= since && e.timestamp <= now)
+        .collect::<Vec<_>>();
+
+    let agreements_served = events
+        .iter()
+        .filter(|e| e.kind == AGREEMENT_APPROVED && is_provider(e))
+        .count() as u64;
+
+    // Only Provider side `activity-created` events carry Requestor id.
+    let started = events
+        .iter()
+        .filter(|e| e.kind == ACTIVITY_CREATED && detail(e, "requestorId").is_some())
+        .filter_map(|e| e.subject.as_deref())
+        .collect::<HashSet<_>>();
+    let failed = events
+        .iter()
+        .filter(|e| is_failure(e))
+        .filter_map(|e| e.subject.as_deref())
+        .filter(|subject| started.contains(subject))
+        .collect::<HashSet<_>>();
+
+    UptimeWindow {
+        window_hours,
+        offer_published: offer_published(&samples, now),
+        agreements_served,
+        activities_started: started.len() as u64,
+        activities_failed: failed.len() as u64,
+    }
+}
+
+/// Each sample with Offers counts as published until the next sample, but no longer
+/// than the sampling interval. Periods without samples (daemon down) count as not published.
+fn offer_published(samples: &[&AvailabilitySample], now: NaiveDateTime) -> Option<f64> {
+    let first = samples.first()?;
+    let covered = (now - first.timestamp).num_seconds();
+    if covered <= 0 {
+        return Some(if first.offers > 0 { 1.0 } else { 0.0 });
+    }
+
+    let published: i64 = samples
+        .iter()
+        .enumerate()
+        .filter(|(_, sample)| sample.offers > 0)
+        .map(|(i, sample)| {
+            let end = samples.get(i + 1).map(|next| next.timestamp).unwrap_or(now);
+            (end - sample.timestamp)
+                .num_seconds()
+                .min(sample.interval_secs as i64)
+        })
+        .sum();
+    Some((published as f64 / covered as f64).min(1.0))
+}
+
+fn detail<'a>(event: &'a Entry, name: &str) -> Option<&'a serde_json::Value> {
+    event
+        .details
+        .as_ref()
+        .and_then(|details| details.get(name))
+        .filter(|value| !value.is_null())
+}
+
+fn is_provider(event: &Entry) -> bool {
+    let provider_id = detail(event, "providerId")
+        .and_then(|id| serde_json::from_value::<NodeId>(id.clone()).ok());
+    provider_id.is_some() && provider_id == event.node_id
+}
+
+fn is_failure(event: &Entry) -> bool {
+    match event.kind.as_str() {
+        ACTIVITY_DESTROYED => {
+            detail(event, "reason").and_then(|reason| reason.as_str()) == Some("inactive")
+        }
+        ACTIVITY_STATE_CHANGED => detail(event, "errorMessage").is_some(),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ya_core_model::journal::Category;
+
+    const PROVIDER: &str = "0x8b42e4b0c1e5b1d9b9cef3e4e2a1bff8b6b3e8d2";
+    const REQUESTOR: &str = "0x1e9a2c2d3d2f5e1b5a0e6c4b1d5f6a7b8c9d0e1f";
+
+    fn sample(now: NaiveDateTime, minutes_ago: i64, offers: i32) -> AvailabilitySample {
+        AvailabilitySample {
+            id: 0,
+            timestamp: now - Duration::minutes(minutes_ago),
+            offers,
+            interval_secs: 300,
+        }
+    }
+
+    fn event(
+        now: NaiveDateTime,
+        minutes_ago: i64,
+        category: Category,
+        kind: &str,
+        subject: &str,
+        details: serde_json::Value,
+    ) -> Entry {
+        Entry {
+            id: 0,
+            timestamp: now - Duration::minutes(minutes_ago),
+            category,
+            kind: kind.to_string(),
+            subject: Some(subject.to_string()),
+            node_id: Some(PROVIDER.parse().unwrap()),
+            details: Some(details),
+        }
+    }
+
+    #[test]
+    fn test_compute_window() {
+        let now = Utc::now().naive_utc();
+        // Published for 10 minutes, then nothing for 5 and daemon down for the last 15.
+        let samples = vec![sample(now, 30, 1), sample(now, 25, 2), sample(now, 20, 0)];
+        let events = vec![
+            event(
+                now,
+                28,
+                Category::Market,
+                AGREEMENT_APPROVED,
+                "a1",
+                serde_json::json!({ "providerId": PROVIDER, "requestorId": REQUESTOR }),
+            ),
+            event(
+                now,
+                27,
+                Category::Market,
+                AGREEMENT_APPROVED,
+                "a2",
+                serde_json::json!({ "providerId": REQUESTOR, "requestorId": PROVIDER }),
+            ),
+            event(
+                now,
+                26,
+                Category::Activity,
+                ACTIVITY_CREATED,
+                "act1",
+                serde_json::json!({ "agreementId": "a1", "requestorId": REQUESTOR }),
+            ),
+            event(
+                now,
+                26,
+                Category::Activity,
+                ACTIVITY_CREATED,
+                "act2",
+                serde_json::json!({ "agreementId": "a1", "requestorId": REQUESTOR }),
+            ),
+            event(
+                now,
+                24,
+                Category::Activity,
+                ACTIVITY_STATE_CHANGED,
+                "act1",
+                serde_json::json!({ "state": ["Ready", null], "errorMessage": null }),
+            ),
+            event(
+                now,
+                22,
+                Category::Activity,
+                ACTIVITY_STATE_CHANGED,
+                "act2",
+                serde_json::json!({ "state": ["Terminated", null], "errorMessage": "crashed" }),
+            ),
+            event(
+                now,
+                21,
+                Category::Activity,
+                ACTIVITY_DESTROYED,
+                "act2",
+                serde_json::json!({ "reason": "inactive" }),
+            ),
+        ];
+
+        let window = compute_window(1, &samples, &events, now);
+        assert_eq!(window.offer_published, Some(600.0 / 1800.0));
+        assert_eq!(window.agreements_served, 1);
+        assert_eq!(window.activities_started, 2);
+        assert_eq!(window.activities_failed, 1);
+        assert_eq!(window.activity_success_rate(), Some(0.5));
+    }
+
+    #[test]
+    fn test_compute_window_empty() {
+        let now = Utc::now().naive_utc();
+        let samples = vec![sample(now, 120, 1)];
+
+        let window = compute_window(1, &samples, &[], now);
+        assert_eq!(window.offer_published, None);
+        assert_eq!(window.agreements_served, 0);
+        assert_eq!(window.activity_success_rate(), None);
+    }
+}
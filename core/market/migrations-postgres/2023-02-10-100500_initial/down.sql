@@ -0,0 +1,12 @@
+DROP TABLE market_reputation_outcome;
+DROP TABLE market_agreement_amendment;
+DROP TABLE market_price_stats;
+DROP TABLE market_negotiation_history;
+DROP TABLE market_negotiation_event;
+DROP TABLE market_agreement_event;
+DROP TABLE market_agreement;
+DROP TABLE market_proposal;
+DROP TABLE market_negotiation;
+DROP TABLE market_demand;
+DROP TABLE market_offer_unsubscribed;
+DROP TABLE market_offer;
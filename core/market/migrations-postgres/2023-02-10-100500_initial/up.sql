@@ -0,0 +1,198 @@
+-- Schema equivalent to SQLite migrations up to 2023-02-06-100000_reputation.
+
+CREATE TABLE market_offer(
+    id TEXT NOT NULL PRIMARY KEY,
+    properties TEXT NOT NULL,
+    constraints TEXT NOT NULL,
+    node_id TEXT NOT NULL,
+
+    creation_ts TIMESTAMP NOT NULL,
+    insertion_ts TIMESTAMP NOT NULL DEFAULT timezone('utc', now()),
+    expiration_ts TIMESTAMP NOT NULL
+);
+
+CREATE TABLE market_offer_unsubscribed(
+    id TEXT NOT NULL PRIMARY KEY,
+    node_id TEXT NOT NULL,
+
+    insertion_ts TIMESTAMP NOT NULL DEFAULT timezone('utc', now()),
+    expiration_ts TIMESTAMP NOT NULL
+);
+
+CREATE TABLE market_demand(
+    id TEXT NOT NULL PRIMARY KEY,
+    properties TEXT NOT NULL,
+    constraints TEXT NOT NULL,
+    node_id TEXT NOT NULL,
+
+    creation_ts TIMESTAMP NOT NULL,
+    insertion_ts TIMESTAMP NOT NULL DEFAULT timezone('utc', now()),
+    expiration_ts TIMESTAMP NOT NULL
+);
+
+CREATE TABLE market_negotiation(
+    id TEXT NOT NULL PRIMARY KEY,
+    subscription_id TEXT NOT NULL,
+
+    offer_id TEXT NOT NULL,
+    demand_id TEXT NOT NULL,
+
+    requestor_id TEXT NOT NULL,
+    provider_id TEXT NOT NULL,
+
+    agreement_id TEXT
+);
+
+CREATE TABLE market_proposal(
+    id TEXT NOT NULL PRIMARY KEY,
+    prev_proposal_id TEXT,
+    issuer TEXT NOT NULL,
+    negotiation_id TEXT NOT NULL,
+
+    properties TEXT NOT NULL,
+    constraints TEXT NOT NULL,
+
+    state TEXT NOT NULL,
+    creation_ts TIMESTAMP NOT NULL,
+    expiration_ts TIMESTAMP NOT NULL,
+
+    FOREIGN KEY (negotiation_id) REFERENCES market_negotiation (id),
+    CHECK (state in ('Initial', 'Draft', 'Rejected', 'Accepted', 'Expired')),
+    CHECK (issuer in ('Us', 'Them'))
+);
+
+CREATE TABLE market_agreement(
+    id TEXT NOT NULL PRIMARY KEY,
+
+    demand_properties TEXT NOT NULL,
+    demand_constraints TEXT NOT NULL,
+
+    offer_properties TEXT NOT NULL,
+    offer_constraints TEXT NOT NULL,
+
+    offer_id TEXT NOT NULL,
+    demand_id TEXT NOT NULL,
+
+    offer_proposal_id TEXT NOT NULL,
+    demand_proposal_id TEXT NOT NULL,
+
+    provider_id TEXT NOT NULL,
+    requestor_id TEXT NOT NULL,
+
+    session_id TEXT,
+
+    creation_ts TIMESTAMP NOT NULL,
+    valid_to TIMESTAMP NOT NULL,
+    state TEXT NOT NULL,
+    approved_ts TIMESTAMP,
+
+    proposed_signature TEXT,
+    approved_signature TEXT,
+    committed_signature TEXT,
+
+    CHECK (state in ('Proposal','Pending','Cancelled','Rejected','Approved','Expired','Terminated', 'Approving'))
+);
+
+CREATE TABLE market_agreement_event(
+    id SERIAL PRIMARY KEY,
+    agreement_id TEXT NOT NULL,
+    event_type TEXT NOT NULL,
+    timestamp TIMESTAMP NOT NULL DEFAULT timezone('utc', now()),
+    issuer TEXT NOT NULL,
+    reason TEXT,
+    signature TEXT,
+
+    FOREIGN KEY (agreement_id) REFERENCES market_agreement (id),
+    UNIQUE (agreement_id, event_type),
+    CHECK (event_type in ('Terminated', 'Approved', 'Cancelled', 'Rejected')),
+    CHECK (issuer in ('P', 'R'))
+);
+
+CREATE TABLE market_negotiation_event(
+    id SERIAL PRIMARY KEY,
+    subscription_id TEXT NOT NULL,
+    timestamp TIMESTAMP NOT NULL DEFAULT timezone('utc', now()),
+    event_type TEXT NOT NULL,
+    artifact_id TEXT NOT NULL,
+    reason TEXT,
+
+    CHECK (event_type in ('P-NewProposal', 'P-ProposalRejected', 'P-Agreement', 'P-PropertyQuery', 'R-NewProposal', 'R-ProposalRejected', 'R-PropertyQuery'))
+);
+
+CREATE TABLE market_negotiation_history(
+    id SERIAL PRIMARY KEY,
+    agreement_id TEXT NOT NULL,
+    proposal_id TEXT NOT NULL,
+    prev_proposal_id TEXT,
+    issuer_id TEXT NOT NULL,
+
+    properties TEXT NOT NULL,
+    constraints TEXT NOT NULL,
+
+    state TEXT NOT NULL,
+    creation_ts TIMESTAMP NOT NULL,
+
+    FOREIGN KEY (agreement_id) REFERENCES market_agreement (id),
+    CHECK (state in ('Initial', 'Draft', 'Rejected', 'Accepted', 'Expired'))
+);
+
+CREATE TABLE market_price_stats(
+    id SERIAL PRIMARY KEY,
+    runtime TEXT NOT NULL,
+    usage_counter TEXT NOT NULL,
+    sample_ts TIMESTAMP NOT NULL,
+    offers_count INTEGER NOT NULL,
+
+    p10 DOUBLE PRECISION NOT NULL,
+    p25 DOUBLE PRECISION NOT NULL,
+    p50 DOUBLE PRECISION NOT NULL,
+    p75 DOUBLE PRECISION NOT NULL,
+    p90 DOUBLE PRECISION NOT NULL
+);
+
+CREATE TABLE market_agreement_amendment(
+    amendment_id TEXT NOT NULL,
+    agreement_id TEXT NOT NULL,
+    issuer TEXT NOT NULL,
+
+    demand_properties TEXT,
+    offer_properties TEXT,
+    reason TEXT,
+
+    state TEXT NOT NULL,
+    creation_ts TIMESTAMP NOT NULL,
+    update_ts TIMESTAMP NOT NULL,
+
+    PRIMARY KEY (amendment_id, agreement_id),
+    FOREIGN KEY (agreement_id) REFERENCES market_agreement (id),
+    CHECK (state in ('Pending', 'Accepted', 'Rejected')),
+    CHECK (issuer in ('P', 'R'))
+);
+
+CREATE TABLE market_reputation_outcome(
+    node_id TEXT NOT NULL,
+    subject_id TEXT NOT NULL,
+    outcome TEXT NOT NULL,
+    timestamp TIMESTAMP NOT NULL,
+
+    PRIMARY KEY (node_id, subject_id, outcome),
+    CHECK (outcome in ('AgreementCompleted', 'PaymentOnTime', 'PaymentLate', 'ActivityFailed'))
+);
+
+CREATE INDEX market_proposal_expiration_idx ON market_proposal (expiration_ts);
+CREATE INDEX market_proposal_negotiation_idx ON market_proposal (expiration_ts, negotiation_id);
+CREATE INDEX market_proposal_prev_proposal_idx ON market_proposal (prev_proposal_id);
+CREATE INDEX market_agreement_offer_proposal_idx ON market_agreement (offer_proposal_id);
+CREATE INDEX market_agreement_provider_idx ON market_agreement (provider_id);
+CREATE INDEX market_agreement_requestor_idx ON market_agreement (requestor_id);
+CREATE INDEX market_agreement_session_idx ON market_agreement (session_id);
+CREATE INDEX market_demand_expiration_idx ON market_demand (expiration_ts);
+CREATE INDEX market_demand_insertion_idx ON market_demand (insertion_ts);
+CREATE INDEX market_offer_expiration_idx ON market_offer (expiration_ts);
+CREATE INDEX market_offer_insertion_idx ON market_offer (insertion_ts);
+CREATE INDEX market_offer_unsubscribed_expiration_idx ON market_offer_unsubscribed (expiration_ts);
+CREATE INDEX market_negotiation_event_subscription_idx ON market_negotiation_event (subscription_id);
+CREATE INDEX market_negotiation_event_timestamp_idx ON market_negotiation_event (timestamp);
+CREATE INDEX market_negotiation_history_agreement_idx ON market_negotiation_history (agreement_id);
+CREATE INDEX market_price_stats_sample_idx ON market_price_stats (sample_ts);
+CREATE INDEX market_agreement_amendment_update_idx ON market_agreement_amendment (update_ts);
@@ -0,0 +1,14 @@
+-- Requestor's saved negotiation profiles, from which Demands are subscribed.
+CREATE TABLE market_demand_template(
+    owner_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    properties TEXT NOT NULL,
+    constraints TEXT NOT NULL,
+    payment_platform TEXT NULL,
+    expiration_secs BIGINT NULL,
+    debit_note_accept_timeout_secs BIGINT NULL,
+    budget TEXT NULL,
+    updated_ts TIMESTAMP NOT NULL,
+
+    PRIMARY KEY (owner_id, name)
+);
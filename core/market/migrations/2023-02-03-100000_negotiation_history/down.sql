@@ -0,0 +1,2 @@
+drop index market_negotiation_history_agreement_idx;
+DROP TABLE market_negotiation_history;
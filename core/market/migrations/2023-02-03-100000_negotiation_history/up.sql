@@ -0,0 +1,20 @@
+-- Proposals chain that ended with Agreement. Proposals themselves are kept
+-- in memory database and are removed after expiration.
+CREATE TABLE market_negotiation_history(
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    agreement_id VARCHAR(100) NOT NULL,
+    proposal_id VARCHAR(100) NOT NULL,
+    prev_proposal_id VARCHAR(100),
+    issuer_id VARCHAR(20) NOT NULL,
+
+    properties TEXT NOT NULL,
+    constraints TEXT NOT NULL,
+
+    state VARCHAR(10) NOT NULL,
+    creation_ts DATETIME NOT NULL,
+
+    FOREIGN KEY(agreement_id) REFERENCES market_agreement (id)
+    CHECK (state in ('Initial', 'Draft', 'Rejected', 'Accepted', 'Expired'))
+);
+
+create index if not exists market_negotiation_history_agreement_idx on market_negotiation_history (agreement_id);
@@ -0,0 +1,2 @@
+drop index market_price_stats_sample_idx;
+DROP TABLE market_price_stats;
@@ -0,0 +1,16 @@
+-- Percentiles of prices observed in Offers, sampled periodically.
+CREATE TABLE market_price_stats(
+    id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    runtime VARCHAR(50) NOT NULL,
+    usage_counter VARCHAR(50) NOT NULL,
+    sample_ts DATETIME NOT NULL,
+    offers_count INTEGER NOT NULL,
+
+    p10 DOUBLE NOT NULL,
+    p25 DOUBLE NOT NULL,
+    p50 DOUBLE NOT NULL,
+    p75 DOUBLE NOT NULL,
+    p90 DOUBLE NOT NULL
+);
+
+create index if not exists market_price_stats_sample_idx on market_price_stats (sample_ts);
@@ -0,0 +1,2 @@
+drop index market_agreement_amendment_update_idx;
+DROP TABLE market_agreement_amendment;
@@ -0,0 +1,22 @@
+-- Changes to properties of running Agreement proposed by one of the parties.
+-- Both sides store the same amendment_id under their own agreement_id.
+CREATE TABLE market_agreement_amendment(
+    amendment_id VARCHAR(100) NOT NULL,
+    agreement_id VARCHAR(100) NOT NULL,
+    issuer VARCHAR(1) NOT NULL,
+
+    demand_properties TEXT,
+    offer_properties TEXT,
+    reason TEXT,
+
+    state VARCHAR(10) NOT NULL,
+    creation_ts DATETIME NOT NULL,
+    update_ts DATETIME NOT NULL,
+
+    PRIMARY KEY(amendment_id, agreement_id),
+    FOREIGN KEY(agreement_id) REFERENCES market_agreement (id)
+    CHECK (state in ('Pending', 'Accepted', 'Rejected'))
+    CHECK (issuer in ('P', 'R'))
+);
+
+create index if not exists market_agreement_amendment_update_idx on market_agreement_amendment (update_ts);
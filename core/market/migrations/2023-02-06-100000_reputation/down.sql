@@ -0,0 +1 @@
+DROP TABLE market_reputation_outcome;
@@ -0,0 +1,11 @@
+-- Outcomes of cooperation with other nodes used to compute their local reputation.
+-- The same outcome is counted only once for given subject (Agreement or Activity).
+CREATE TABLE market_reputation_outcome(
+    node_id VARCHAR(20) NOT NULL,
+    subject_id VARCHAR(100) NOT NULL,
+    outcome VARCHAR(20) NOT NULL,
+    timestamp DATETIME NOT NULL,
+
+    PRIMARY KEY(node_id, subject_id, outcome),
+    CHECK (outcome in ('AgreementCompleted', 'PaymentOnTime', 'PaymentLate', 'ActivityFailed'))
+);
@@ -0,0 +1,14 @@
+-- Requestor's saved negotiation profiles, from which Demands are subscribed.
+CREATE TABLE market_demand_template(
+    owner_id VARCHAR(20) NOT NULL,
+    name VARCHAR(100) NOT NULL,
+    properties TEXT NOT NULL,
+    constraints TEXT NOT NULL,
+    payment_platform VARCHAR(100) NULL,
+    expiration_secs BIGINT NULL,
+    debit_note_accept_timeout_secs BIGINT NULL,
+    budget VARCHAR(32) NULL,
+    updated_ts DATETIME NOT NULL,
+
+    PRIMARY KEY(owner_id, name)
+);
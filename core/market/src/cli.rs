@@ -1,7 +1,14 @@
 use chrono::{DateTime, Utc};
+use std::path::PathBuf;
 use structopt::StructOpt;
 use ya_client::model::market::{agreement::State, Role};
-use ya_core_model::market::{GetAgreement, ListAgreements};
+use ya_client::model::NodeId;
+use ya_core_model::identity as idm;
+use ya_core_model::market::{
+    local, DeleteDemandTemplate, DemandTemplate, GetAgreement, GetNegotiationHistory,
+    GetReputation, GetTerminationStats, ListAgreements, ListDemandTemplates, ListReputation,
+    SaveDemandTemplate, SubscribeDemandTemplate,
+};
 use ya_service_api::{CliCtx, CommandOutput, ResponseTable};
 use ya_service_bus::{typed as bus, RpcEndpoint};
 
@@ -9,12 +16,93 @@ use ya_service_bus::{typed as bus, RpcEndpoint};
 #[derive(StructOpt, Debug)]
 pub enum Command {
     Agreements(AgreementsCommand),
+    /// Show chain of Proposals, that ended with the Agreement
+    Negotiations {
+        #[structopt(help = "Agreement ID, may be obtained via list-agreements")]
+        agreement_id: String,
+    },
+    /// Show local reputation of nodes we cooperated with
+    Reputation {
+        #[structopt(help = "Show only reputation of this node")]
+        node_id: Option<NodeId>,
+    },
+    Templates(TemplatesCommand),
 }
 
 impl Command {
     pub async fn run_command(self, ctx: &CliCtx) -> anyhow::Result<CommandOutput> {
         match self {
             Command::Agreements(agreements_cmd) => agreements_cmd.run_command(ctx).await,
+            Command::Templates(templates_cmd) => templates_cmd.run_command(ctx).await,
+            Command::Negotiations { agreement_id } => {
+                let history = bus::service(local::BUS_ID)
+                    .send(GetNegotiationHistory { agreement_id })
+                    .await??;
+
+                let mut values = Vec::new();
+                for proposal in history {
+                    values.push(serde_json::to_value([
+                        proposal.proposal_id,
+                        proposal.issuer_id.to_string(),
+                        format!("{:?}", proposal.state),
+                        proposal.timestamp.to_rfc3339(),
+                        proposal.properties.to_string(),
+                        proposal.constraints,
+                    ])?);
+                }
+
+                Ok(ResponseTable {
+                    columns: vec![
+                        "proposal".to_owned(),
+                        "issuer".to_owned(),
+                        "state".to_owned(),
+                        "created".to_owned(),
+                        "properties".to_owned(),
+                        "constraints".to_owned(),
+                    ],
+                    values,
+                }
+                .into())
+            }
+            Command::Reputation { node_id } => {
+                let reputation = match node_id {
+                    Some(node_id) => vec![
+                        bus::service(local::BUS_ID)
+                            .send(GetReputation { node_id })
+                            .await??,
+                    ],
+                    None => {
+                        bus::service(local::BUS_ID)
+                            .send(ListReputation {})
+                            .await??
+                    }
+                };
+
+                let mut values = Vec::new();
+                for entry in reputation {
+                    values.push(serde_json::json!([
+                        entry.node_id.to_string(),
+                        format!("{:.2}", entry.score),
+                        entry.agreements_completed,
+                        entry.payments_on_time,
+                        entry.payments_late,
+                        entry.activity_failures,
+                    ]));
+                }
+
+                Ok(ResponseTable {
+                    columns: vec![
+                        "node".to_owned(),
+                        "score".to_owned(),
+                        "agreements".to_owned(),
+                        "paid on time".to_owned(),
+                        "paid late".to_owned(),
+                        "failed activities".to_owned(),
+                    ],
+                    values,
+                }
+                .into())
+            }
         }
     }
 }
@@ -37,6 +125,11 @@ pub enum AgreementsCommand {
         #[structopt(long, help = "Your role in the agreement (Provider | Requestor)")]
         role: Role,
     },
+    /// Show why agreements were terminated
+    TerminationStats {
+        #[structopt(long, help = "Only count terminations after this date, rfc3339")]
+        after: Option<DateTime<Utc>>,
+    },
 }
 
 impl AgreementsCommand {
@@ -95,6 +188,160 @@ impl AgreementsCommand {
 
                 CommandOutput::object(agreement)
             }
+            AgreementsCommand::TerminationStats { after } => {
+                let stats = bus::service(ya_core_model::market::BUS_ID)
+                    .send(GetTerminationStats { after_date: after })
+                    .await??;
+
+                let mut values = Vec::new();
+                for entry in stats {
+                    values.push(serde_json::json!([
+                        entry.role.to_string(),
+                        entry.terminator.to_string(),
+                        entry.reason,
+                        entry.count,
+                    ]));
+                }
+
+                Ok(ResponseTable {
+                    columns: vec![
+                        "role".to_owned(),
+                        "terminated by".to_owned(),
+                        "reason".to_owned(),
+                        "count".to_owned(),
+                    ],
+                    values,
+                }
+                .into())
+            }
+        }
+    }
+}
+
+/// Saved Demand templates of the Requestor
+#[derive(StructOpt, Debug)]
+pub enum TemplatesCommand {
+    List {
+        #[structopt(long, help = "Templates owner, defaults to the default identity")]
+        id: Option<NodeId>,
+    },
+    Show {
+        name: String,
+        #[structopt(long)]
+        id: Option<NodeId>,
+    },
+    /// Save template from json file, replacing the one with the same name
+    Save {
+        #[structopt(help = "Json file with the template, `name` and `ownerId` may be omitted")]
+        file: PathBuf,
+        #[structopt(long, help = "Overrides template name from the file")]
+        name: Option<String>,
+        #[structopt(long)]
+        id: Option<NodeId>,
+    },
+    Delete {
+        name: String,
+        #[structopt(long)]
+        id: Option<NodeId>,
+    },
+    /// Subscribe Demand built from the template
+    Subscribe {
+        name: String,
+        #[structopt(long)]
+        id: Option<NodeId>,
+    },
+}
+
+impl TemplatesCommand {
+    async fn owner_id(id: Option<NodeId>) -> anyhow::Result<NodeId> {
+        match id {
+            Some(id) => Ok(id),
+            None => Ok(bus::service(idm::BUS_ID)
+                .send(idm::Get::ByDefault)
+                .await
+                .map_err(anyhow::Error::msg)?
+                .map_err(anyhow::Error::msg)?
+                .ok_or_else(|| anyhow::anyhow!("Default identity not found"))?
+                .node_id),
+        }
+    }
+
+    async fn list(owner_id: NodeId) -> anyhow::Result<Vec<DemandTemplate>> {
+        Ok(bus::service(local::BUS_ID)
+            .send(ListDemandTemplates { owner_id })
+            .await??)
+    }
+
+    pub async fn run_command(self, _ctx: &CliCtx) -> anyhow::Result<CommandOutput> {
+        match self {
+            TemplatesCommand::List { id } => {
+                let templates = Self::list(Self::owner_id(id).await?).await?;
+
+                let mut values = Vec::new();
+                for template in templates {
+                    values.push(serde_json::json!([
+                        template.name,
+                        template.payment_platform,
+                        template.expiration_secs,
+                        template.budget.map(|budget| budget.to_string()),
+                        template.updated_at.to_rfc3339(),
+                    ]));
+                }
+
+                Ok(ResponseTable {
+                    columns: vec![
+                        "name".to_owned(),
+                        "platform".to_owned(),
+                        "expiration [s]".to_owned(),
+                        "budget".to_owned(),
+                        "updated".to_owned(),
+                    ],
+                    values,
+                }
+                .into())
+            }
+            TemplatesCommand::Show { name, id } => {
+                let template = Self::list(Self::owner_id(id).await?)
+                    .await?
+                    .into_iter()
+                    .find(|template| template.name == name)
+                    .ok_or_else(|| anyhow::anyhow!("Demand template [{}] not found", name))?;
+                CommandOutput::object(template)
+            }
+            TemplatesCommand::Save { file, name, id } => {
+                let mut template: serde_json::Value =
+                    serde_json::from_reader(std::fs::File::open(&file)?)?;
+                let object = template
+                    .as_object_mut()
+                    .ok_or_else(|| anyhow::anyhow!("Template should be a json object"))?;
+                if let Some(name) = name {
+                    object.insert("name".to_owned(), name.into());
+                }
+                object.insert(
+                    "ownerId".to_owned(),
+                    Self::owner_id(id).await?.to_string().into(),
+                );
+                let template: DemandTemplate = serde_json::from_value(template)?;
+
+                let template = bus::service(local::BUS_ID)
+                    .send(SaveDemandTemplate(template))
+                    .await??;
+                CommandOutput::object(template)
+            }
+            TemplatesCommand::Delete { name, id } => {
+                let owner_id = Self::owner_id(id).await?;
+                bus::service(local::BUS_ID)
+                    .send(DeleteDemandTemplate { owner_id, name })
+                    .await??;
+                Ok(CommandOutput::NoOutput)
+            }
+            TemplatesCommand::Subscribe { name, id } => {
+                let owner_id = Self::owner_id(id).await?;
+                let subscription_id = bus::service(local::BUS_ID)
+                    .send(SubscribeDemandTemplate { owner_id, name })
+                    .await??;
+                CommandOutput::object(subscription_id)
+            }
         }
     }
 }
@@ -11,6 +11,14 @@ pub struct Config {
     pub events: EventsConfig,
     #[structopt(flatten)]
     pub db: DbConfig,
+    #[structopt(flatten)]
+    pub price_stats: PriceStatsConfig,
+    #[structopt(flatten)]
+    pub amendment: AmendmentConfig,
+    #[structopt(flatten)]
+    pub reputation: ReputationConfig,
+    #[structopt(flatten)]
+    pub availability: AvailabilityConfig,
 }
 
 #[derive(StructOpt, Clone)]
@@ -37,6 +45,22 @@ pub struct DiscoveryConfig {
 pub struct SubscriptionConfig {
     #[structopt(env = "DEFAULT_SUBSCRIPTION_TTL", parse(try_from_str = parse_chrono_duration), default_value = "1h")]
     pub default_ttl: chrono::Duration,
+    /// Time before expiration of our Offers and Demands, when warning is recorded
+    /// in the journal. Zero disables warnings and renewals.
+    #[structopt(env = "MARKET_SUBSCRIPTION_EXPIRY_WARNING", parse(try_from_str = humantime::parse_duration), default_value = "5min")]
+    pub expiry_warning: Duration,
+    /// Prolong our Demands by `default_ttl` before they expire.
+    /// Offers are renewed by Provider agent, since renewing them changes their ids.
+    #[structopt(
+        env = "MARKET_SUBSCRIPTION_RENEW_DEMANDS",
+        parse(try_from_str),
+        default_value = "false"
+    )]
+    pub renew_demands: bool,
+    /// Demands are renewed randomly up to this time before the warning,
+    /// so they don't all expire at once again.
+    #[structopt(env = "MARKET_SUBSCRIPTION_RENEWAL_JITTER", parse(try_from_str = humantime::parse_duration), default_value = "1min")]
+    pub renewal_jitter: Duration,
 }
 
 #[derive(StructOpt, Clone)]
@@ -60,6 +84,40 @@ pub struct DbConfig {
     pub event_store_days: i32,
 }
 
+#[derive(StructOpt, Clone)]
+pub struct PriceStatsConfig {
+    /// Interval in which prices of Offers available on market are sampled
+    #[structopt(env = "MARKET_PRICE_SAMPLE_INTERVAL", parse(try_from_str = humantime::parse_duration), default_value = "10min")]
+    pub sample_interval: Duration,
+    /// Number of days to persist price statistics
+    #[structopt(env = "MARKET_PRICE_STATS_STORE_DAYS", default_value = "30")]
+    pub store_days: i32,
+}
+
+#[derive(StructOpt, Clone)]
+pub struct AmendmentConfig {
+    /// Maximal Agreement expiration extension accepted by Provider without asking.
+    /// Zero disables automatic acceptance.
+    #[structopt(env = "MARKET_AMENDMENT_MAX_AUTO_EXTENSION", parse(try_from_str = humantime::parse_duration), default_value = "0s")]
+    pub max_auto_extension: Duration,
+}
+
+#[derive(StructOpt, Clone)]
+pub struct ReputationConfig {
+    /// Offers and Proposals from nodes with lower local reputation score are rejected.
+    /// Zero disables filtering.
+    #[structopt(env = "MARKET_REPUTATION_MIN_SCORE", default_value = "0")]
+    pub min_score: f64,
+}
+
+#[derive(StructOpt, Clone)]
+pub struct AvailabilityConfig {
+    /// Interval in which number of our subscribed Offers is recorded in the journal
+    /// for uptime statistics. Zero disables sampling.
+    #[structopt(env = "MARKET_AVAILABILITY_SAMPLE_INTERVAL", parse(try_from_str = humantime::parse_duration), default_value = "5min")]
+    pub sample_interval: Duration,
+}
+
 impl Config {
     pub fn from_env() -> Result<Config, structopt::clap::Error> {
         // Empty command line arguments, because we want to use ENV fallback
@@ -80,6 +138,9 @@ mod test {
     fn test_default_structopt_subscription_ttl() {
         let c = Config::from_env().unwrap();
         assert_eq!(60, c.subscription.default_ttl.num_minutes());
+        assert_eq!(5 * 60, c.subscription.expiry_warning.as_secs());
+        assert!(!c.subscription.renew_demands);
+        assert_eq!(60, c.subscription.renewal_jitter.as_secs());
     }
 
     #[test]
@@ -96,4 +157,29 @@ mod test {
         assert_eq!(90, c.db.agreement_store_days);
         assert_eq!(1, c.db.event_store_days);
     }
+
+    #[test]
+    fn test_default_structopt_price_stats_config() {
+        let c = Config::from_env().unwrap();
+        assert_eq!(600, c.price_stats.sample_interval.as_secs());
+        assert_eq!(30, c.price_stats.store_days);
+    }
+
+    #[test]
+    fn test_default_structopt_amendment_config() {
+        let c = Config::from_env().unwrap();
+        assert_eq!(0, c.amendment.max_auto_extension.as_secs());
+    }
+
+    #[test]
+    fn test_default_structopt_reputation_config() {
+        let c = Config::from_env().unwrap();
+        assert_eq!(0.0, c.reputation.min_score);
+    }
+
+    #[test]
+    fn test_default_structopt_availability_config() {
+        let c = Config::from_env().unwrap();
+        assert_eq!(300, c.availability.sample_interval.as_secs());
+    }
 }
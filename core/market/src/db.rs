@@ -4,6 +4,10 @@ pub(crate) mod schema;
 
 pub(crate) mod migrations {
     #[derive(diesel_migrations::EmbedMigrations)]
+    #[cfg_attr(
+        feature = "postgres",
+        embed_migrations_options(migrations_path = "migrations-postgres")
+    )]
     struct _Dummy;
 }
 
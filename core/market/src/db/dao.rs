@@ -1,22 +1,38 @@
 mod agreement;
+mod agreement_amendment;
 mod agreement_events;
 pub mod cleaner;
 mod demand;
+mod demand_template;
 mod negotiation_events;
+mod negotiation_history;
 pub mod sql_functions {
     use diesel::sql_types;
-    diesel::sql_function!(fn datetime(timestring:sql_types::Text, modifier:sql_types::Text) -> sql_types::Timestamp);
     diesel::sql_function!(
         #[sql_name = "coalesce"]
         fn coalesce_id(column: sql_types::Nullable<sql_types::Text>, default: sql_types::Text) -> sql_types::Text
     );
 }
 mod offer;
+mod price_stats;
 mod proposal;
+mod reputation;
 
 pub use agreement::{AgreementDao, AgreementDaoError, SaveAgreementError};
+pub use agreement_amendment::{AmendmentDao, AmendmentDaoError};
 pub use agreement_events::AgreementEventsDao;
 pub use demand::{DemandDao, DemandState};
+pub use demand_template::DemandTemplateDao;
 pub use negotiation_events::{NegotiationEventsDao, TakeEventsError};
+pub use negotiation_history::NegotiationHistoryDao;
 pub use offer::{OfferDao, OfferState};
+pub use price_stats::PriceStatsDao;
 pub use proposal::{ChangeProposalStateError, ProposalDao, SaveProposalError};
+pub use reputation::ReputationDao;
+
+/// Cutoff for cleaning records older than `days`. Computed here instead of using
+/// database date functions, which differ between SQLite and PostgreSQL.
+pub(crate) fn days_ago(days: i32) -> ya_persistence::types::TimestampAdapter {
+    use ya_persistence::types::AdaptTimestamp;
+    (chrono::Utc::now() - chrono::Duration::days(days as i64)).adapt()
+}
@@ -7,16 +7,21 @@ use ya_persistence::executor::{do_with_transaction, readonly_transaction, ConnTy
 
 use crate::config::DbConfig;
 use crate::db::dao::agreement_events::create_event;
-use crate::db::dao::proposal::{has_counter_proposal, update_proposal_state};
-use crate::db::dao::sql_functions::datetime;
+use crate::db::dao::days_ago;
+use crate::db::dao::negotiation_history::create_history;
+use crate::db::dao::proposal::{has_counter_proposal, proposal_chain, update_proposal_state};
 use crate::db::model::{
-    check_transition, Agreement, AgreementId, AgreementState, AppSessionId, Owner, ProposalId,
-    ProposalIdParseError, ProposalState,
+    check_transition, Agreement, AgreementId, AgreementState, AppSessionId,
+    NegotiationHistoryEntry, Owner, ProposalId, ProposalIdParseError, ProposalState,
 };
 use crate::db::schema::market_agreement::dsl as agreement;
 use crate::db::schema::market_agreement::dsl::market_agreement;
+use crate::db::schema::market_agreement_amendment::dsl as amendment;
+use crate::db::schema::market_agreement_amendment::dsl::market_agreement_amendment;
 use crate::db::schema::market_agreement_event::dsl as event;
 use crate::db::schema::market_agreement_event::dsl::market_agreement_event;
+use crate::db::schema::market_negotiation_history::dsl as history;
+use crate::db::schema::market_negotiation_history::dsl::market_negotiation_history;
 use crate::db::{AsMixedDao, DbError, DbResult};
 
 #[derive(thiserror::Error, Debug)]
@@ -178,14 +183,22 @@ impl<'c> AgreementDao<'c> {
         // Agreement is always created for last Provider Proposal.
         // TODO: Accessing two databases can cause race conditions in some edge cases.
         let proposal_id = agreement.offer_proposal_id.clone();
-        readonly_transaction(self.ram_pool, move |conn| {
+        let chain = readonly_transaction(self.ram_pool, move |conn| {
             if has_counter_proposal(conn, &proposal_id)? {
                 return Err(SaveAgreementError::ProposalCountered(proposal_id));
             }
-            Ok(())
+            Ok(proposal_chain(conn, &proposal_id)?)
         })
         .await?;
 
+        let mut history = chain
+            .into_iter()
+            .map(|proposal| NegotiationHistoryEntry::new(&agreement.id, proposal))
+            .collect::<Vec<_>>();
+        if let Some(last) = history.last_mut() {
+            last.state = ProposalState::Accepted;
+        }
+
         let proposal_id = agreement.offer_proposal_id.clone();
         let agreement = do_with_transaction(self.pool, move |conn| {
             if let Some(agreement) = find_agreement_for_proposal(conn, &proposal_id)? {
@@ -198,6 +211,7 @@ impl<'c> AgreementDao<'c> {
             diesel::insert_into(market_agreement)
                 .values(&agreement)
                 .execute(conn)?;
+            create_history(conn, &history)?;
             Ok(agreement)
         })
         .await?;
@@ -386,19 +400,42 @@ impl<'c> AgreementDao<'c> {
         .await
     }
 
+    /// Reverts all Agreements left in `Approving` state, which happens when approval
+    /// was interrupted by shutdown. Returns ids of reverted Agreements.
+    pub async fn revert_all_approving(&self) -> DbResult<Vec<AgreementId>> {
+        do_with_transaction(self.pool, move |conn| {
+            let approving = market_agreement.filter(agreement::state.eq(AgreementState::Approving));
+            let ids: Vec<AgreementId> = approving.clone().select(agreement::id).load(conn)?;
+            diesel::update(approving)
+                .set(agreement::state.eq(AgreementState::Pending))
+                .execute(conn)?;
+            Ok(ids)
+        })
+        .await
+    }
+
     pub async fn clean(&self, db_config: &DbConfig) -> DbResult<()> {
         log::trace!("Clean market agreements: start");
         let interval_days = db_config.agreement_store_days;
         let (num_agreements, num_events) = do_with_transaction(self.pool, move |conn| {
-            let agreements_to_clean = market_agreement.filter(
-                agreement::valid_to.lt(datetime("NOW", format!("-{} days", interval_days))),
-            );
+            let agreements_to_clean =
+                market_agreement.filter(agreement::valid_to.lt(days_ago(interval_days)));
 
             let related_events = market_agreement_event.filter(
                 event::agreement_id.eq_any(agreements_to_clean.clone().select(agreement::id)),
             );
 
+            let related_history = market_negotiation_history.filter(
+                history::agreement_id.eq_any(agreements_to_clean.clone().select(agreement::id)),
+            );
+
+            let related_amendments = market_agreement_amendment.filter(
+                amendment::agreement_id.eq_any(agreements_to_clean.clone().select(agreement::id)),
+            );
+
             let num_events = diesel::delete(related_events).execute(conn)?;
+            diesel::delete(related_history).execute(conn)?;
+            diesel::delete(related_amendments).execute(conn)?;
             let num_agreements = diesel::delete(agreements_to_clean).execute(conn)?;
             Result::<(usize, usize), DbError>::Ok((num_agreements, num_events))
         })
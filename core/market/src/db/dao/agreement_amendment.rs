@@ -0,0 +1,324 @@
+use chrono::{NaiveDateTime, Utc};
+use diesel::prelude::*;
+
+use ya_client::model::NodeId;
+use ya_persistence::executor::{do_with_transaction, readonly_transaction, ConnType, PoolType};
+
+use crate::db::model::{
+    Agreement, AgreementId, AgreementState, Amendment, AmendmentState, DbReason, Owner,
+};
+use crate::db::schema::market_agreement::dsl as agreement;
+use crate::db::schema::market_agreement::dsl::market_agreement;
+use crate::db::schema::market_agreement_amendment::dsl as amendment;
+use crate::db::schema::market_agreement_amendment::dsl::market_agreement_amendment;
+use crate::db::{AsMixedDao, DbError, DbResult};
+
+#[derive(thiserror::Error, Debug)]
+pub enum AmendmentDaoError {
+    #[error("Amendment [{0}] not found.")]
+    NotFound(String),
+    #[error("Amendment [{0}] is already {1}.")]
+    NotPending(String, AmendmentState),
+    #[error("Can't amend Agreement [{0}] in state {1}.")]
+    AgreementNotApproved(AgreementId, AgreementState),
+    #[error("Agreement [{0}] changed after Amendment was accepted.")]
+    AgreementChanged(AgreementId),
+    #[error("Amendment database error: {0}")]
+    DbError(DbError),
+}
+
+pub struct AmendmentDao<'c> {
+    pool: &'c PoolType,
+}
+
+impl<'a> AsMixedDao<'a> for AmendmentDao<'a> {
+    fn as_dao(disk_pool: &'a PoolType, _ram_pool: &'a PoolType) -> Self {
+        Self { pool: disk_pool }
+    }
+}
+
+impl<'c> AmendmentDao<'c> {
+    /// Saves new Amendment. Agreement must be approved to be amended.
+    pub async fn insert(&self, new_amendment: Amendment) -> Result<(), AmendmentDaoError> {
+        do_with_transaction(self.pool, move |conn| {
+            let agreement: Agreement = market_agreement
+                .filter(agreement::id.eq(&new_amendment.agreement_id))
+                .first(conn)?;
+            check_approved(&agreement)?;
+
+            diesel::insert_into(market_agreement_amendment)
+                .values(&new_amendment)
+                .execute(conn)?;
+            Ok(())
+        })
+        .await
+    }
+
+    pub async fn select(&self, agreement_id: &AgreementId) -> DbResult<Vec<Amendment>> {
+        let agreement_id = agreement_id.clone();
+        readonly_transaction(self.pool, move |conn| {
+            Ok(market_agreement_amendment
+                .filter(amendment::agreement_id.eq(agreement_id))
+                .order_by(amendment::creation_ts.asc())
+                .load::<Amendment>(conn)?)
+        })
+        .await
+    }
+
+    /// Returns Amendments of Agreements, where `node_id` is one of the parties,
+    /// that changed after given timestamp.
+    pub async fn select_updated(
+        &self,
+        node_id: NodeId,
+        after_timestamp: NaiveDateTime,
+        max_amendments: i64,
+    ) -> DbResult<Vec<Amendment>> {
+        readonly_transaction(self.pool, move |conn| {
+            let my_agreements = market_agreement.select(agreement::id).filter(
+                agreement::provider_id
+                    .eq(node_id)
+                    .or(agreement::requestor_id.eq(node_id)),
+            );
+
+            Ok(market_agreement_amendment
+                .filter(amendment::agreement_id.eq_any(my_agreements))
+                .filter(amendment::update_ts.gt(after_timestamp))
+                .order_by(amendment::update_ts.asc())
+                .limit(max_amendments)
+                .load::<Amendment>(conn)?)
+        })
+        .await
+    }
+
+    pub async fn get(
+        &self,
+        amendment_id: &str,
+        agreement_id: &AgreementId,
+    ) -> DbResult<Option<Amendment>> {
+        let amendment_id = amendment_id.to_string();
+        let agreement_id = agreement_id.clone();
+        readonly_transaction(self.pool, move |conn| {
+            Ok(market_agreement_amendment
+                .filter(amendment::amendment_id.eq(amendment_id))
+                .filter(amendment::agreement_id.eq(agreement_id))
+                .first::<Amendment>(conn)
+                .optional()?)
+        })
+        .await
+    }
+
+    /// Merges Amendment properties into Agreement. Only pending Amendments
+    /// proposed by `issuer` can be accepted.
+    pub async fn accept(
+        &self,
+        amendment_id: &str,
+        agreement_id: &AgreementId,
+        issuer: Owner,
+    ) -> Result<Amendment, AmendmentDaoError> {
+        let amendment_id = amendment_id.to_string();
+        let agreement_id = agreement_id.clone();
+        do_with_transaction(self.pool, move |conn| {
+            let mut pending = get_pending(conn, &amendment_id, &agreement_id, issuer)?;
+            let mut agreement: Agreement = market_agreement
+                .filter(agreement::id.eq(&agreement_id))
+                .first(conn)?;
+            check_approved(&agreement)?;
+            merge_amendment(&mut agreement, &pending)?;
+
+            diesel::update(market_agreement.filter(agreement::id.eq(&agreement_id)))
+                .set((
+                    agreement::demand_properties.eq(&agreement.demand_properties),
+                    agreement::offer_properties.eq(&agreement.offer_properties),
+                ))
+                .execute(conn)?;
+
+            update_state(conn, &mut pending, AmendmentState::Accepted, None)?;
+            Ok(pending)
+        })
+        .await
+    }
+
+    pub async fn reject(
+        &self,
+        amendment_id: &str,
+        agreement_id: &AgreementId,
+        issuer: Owner,
+        reason: Option<DbReason>,
+    ) -> Result<Amendment, AmendmentDaoError> {
+        let amendment_id = amendment_id.to_string();
+        let agreement_id = agreement_id.clone();
+        do_with_transaction(self.pool, move |conn| {
+            let mut pending = get_pending(conn, &amendment_id, &agreement_id, issuer)?;
+            update_state(conn, &mut pending, AmendmentState::Rejected, reason)?;
+            Ok(pending)
+        })
+        .await
+    }
+
+    /// Returns Amendment to `pending` state, when the other party couldn't be notified
+    /// about our decision. Properties of accepted Amendment are removed from the Agreement
+    /// by restoring `agreement` from before the decision.
+    pub async fn revert(
+        &self,
+        pending: &Amendment,
+        agreement: &Agreement,
+    ) -> Result<(), AmendmentDaoError> {
+        let pending = pending.clone();
+        let agreement = agreement.clone();
+        do_with_transaction(self.pool, move |conn| {
+            let decided: Amendment = market_agreement_amendment
+                .filter(amendment::amendment_id.eq(&pending.amendment_id))
+                .filter(amendment::agreement_id.eq(&pending.agreement_id))
+                .first(conn)
+                .optional()?
+                .ok_or_else(|| AmendmentDaoError::NotFound(pending.amendment_id.clone()))?;
+
+            if decided.state == AmendmentState::Accepted {
+                let current: Agreement = market_agreement
+                    .filter(agreement::id.eq(&agreement.id))
+                    .first(conn)?;
+                let mut amended = agreement.clone();
+                merge_amendment(&mut amended, &pending)?;
+                if current.demand_properties != amended.demand_properties
+                    || current.offer_properties != amended.offer_properties
+                {
+                    return Err(AmendmentDaoError::AgreementChanged(agreement.id.clone()));
+                }
+
+                diesel::update(market_agreement.filter(agreement::id.eq(&agreement.id)))
+                    .set((
+                        agreement::demand_properties.eq(&agreement.demand_properties),
+                        agreement::offer_properties.eq(&agreement.offer_properties),
+                    ))
+                    .execute(conn)?;
+            }
+
+            let mut reverted = decided;
+            reverted.reason = pending.reason.clone();
+            update_state(conn, &mut reverted, AmendmentState::Pending, None)
+        })
+        .await
+    }
+
+    /// Removes Amendment, that couldn't be delivered to the other party.
+    pub async fn delete(&self, amendment_id: &str, agreement_id: &AgreementId) -> DbResult<()> {
+        let amendment_id = amendment_id.to_string();
+        let agreement_id = agreement_id.clone();
+        do_with_transaction(self.pool, move |conn| {
+            diesel::delete(
+                market_agreement_amendment
+                    .filter(amendment::amendment_id.eq(amendment_id))
+                    .filter(amendment::agreement_id.eq(agreement_id)),
+            )
+            .execute(conn)?;
+            Ok(())
+        })
+        .await
+    }
+}
+
+fn check_approved(agreement: &Agreement) -> Result<(), AmendmentDaoError> {
+    match agreement.state {
+        AgreementState::Approved => Ok(()),
+        state => Err(AmendmentDaoError::AgreementNotApproved(
+            agreement.id.clone(),
+            state,
+        )),
+    }
+}
+
+fn get_pending(
+    conn: &ConnType,
+    amendment_id: &str,
+    agreement_id: &AgreementId,
+    issuer: Owner,
+) -> Result<Amendment, AmendmentDaoError> {
+    let pending: Amendment = market_agreement_amendment
+        .filter(amendment::amendment_id.eq(amendment_id))
+        .filter(amendment::agreement_id.eq(agreement_id))
+        .filter(amendment::issuer.eq(issuer))
+        .first(conn)
+        .optional()?
+        .ok_or_else(|| AmendmentDaoError::NotFound(amendment_id.to_string()))?;
+
+    match pending.state {
+        AmendmentState::Pending => Ok(pending),
+        state => Err(AmendmentDaoError::NotPending(
+            amendment_id.to_string(),
+            state,
+        )),
+    }
+}
+
+fn update_state(
+    conn: &ConnType,
+    pending: &mut Amendment,
+    state: AmendmentState,
+    reason: Option<DbReason>,
+) -> Result<(), AmendmentDaoError> {
+    pending.state = state;
+    pending.update_ts = Utc::now().naive_utc();
+    if reason.is_some() {
+        pending.reason = reason;
+    }
+
+    diesel::update(
+        market_agreement_amendment
+            .filter(amendment::amendment_id.eq(&pending.amendment_id))
+            .filter(amendment::agreement_id.eq(&pending.agreement_id)),
+    )
+    .set((
+        amendment::state.eq(&pending.state),
+        amendment::update_ts.eq(&pending.update_ts),
+        amendment::reason.eq(&pending.reason),
+    ))
+    .execute(conn)?;
+    Ok(())
+}
+
+/// Merges Amendment properties into Agreement.
+fn merge_amendment(agreement: &mut Agreement, amendment: &Amendment) -> serde_json::Result<()> {
+    if let Some(patch) = &amendment.demand_properties {
+        agreement.demand_properties = merge_properties(&agreement.demand_properties, patch)?;
+    }
+    if let Some(patch) = &amendment.offer_properties {
+        agreement.offer_properties = merge_properties(&agreement.offer_properties, patch)?;
+    }
+    Ok(())
+}
+
+/// Overrides flattened `properties` with flattened values from `patch`.
+fn merge_properties(properties: &str, patch: &str) -> Result<String, serde_json::Error> {
+    let mut properties = ya_agreement_utils::agreement::flatten(serde_json::from_str(properties)?);
+    properties.extend(ya_agreement_utils::agreement::flatten(
+        serde_json::from_str(patch)?,
+    ));
+    serde_json::to_string(&properties)
+}
+
+impl<ErrorType: Into<DbError>> From<ErrorType> for AmendmentDaoError {
+    fn from(err: ErrorType) -> Self {
+        AmendmentDaoError::DbError(err.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::merge_properties;
+
+    #[test]
+    fn test_merge_properties() {
+        let properties = r#"{"golem.srv.comp.expiration": 100, "golem.node.id.name": "node"}"#;
+        let patch = r#"{"golem": {"srv.comp.expiration": 200}}"#;
+
+        let merged: serde_json::Value =
+            serde_json::from_str(&merge_properties(properties, patch).unwrap()).unwrap();
+        assert_eq!(
+            merged,
+            serde_json::json!({
+                "golem.srv.comp.expiration": 200,
+                "golem.node.id.name": "node",
+            })
+        );
+    }
+}
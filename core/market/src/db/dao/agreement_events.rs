@@ -8,7 +8,9 @@ use ya_persistence::executor::{readonly_transaction, ConnType};
 use ya_persistence::types::AdaptTimestamp;
 
 use crate::db::dao::AgreementDaoError;
-use crate::db::model::{Agreement, AgreementEvent, AgreementId, NewAgreementEvent};
+use crate::db::model::{
+    Agreement, AgreementEvent, AgreementEventType, AgreementId, NewAgreementEvent,
+};
 use crate::db::model::{AppSessionId, Owner};
 use crate::db::schema::market_agreement::dsl as agreement;
 use crate::db::schema::market_agreement::dsl::market_agreement;
@@ -65,6 +67,21 @@ impl<'c> AgreementEventsDao<'c> {
         .await
     }
 
+    /// Termination events of all Agreements since `after_timestamp`.
+    pub async fn select_terminations(
+        &self,
+        after_timestamp: NaiveDateTime,
+    ) -> DbResult<Vec<AgreementEvent>> {
+        readonly_transaction(self.pool, move |conn| {
+            Ok(market_agreement_event
+                .filter(event::event_type.eq(AgreementEventType::Terminated))
+                .filter(event::timestamp.gt(after_timestamp.adapt()))
+                .order_by(event::timestamp.asc())
+                .load::<AgreementEvent>(conn)?)
+        })
+        .await
+    }
+
     pub async fn select_for_agreement(
         &self,
         agreement_id: &AgreementId,
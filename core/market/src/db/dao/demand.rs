@@ -87,6 +87,24 @@ impl<'c> DemandDao<'c> {
         .await
     }
 
+    /// Moves expiration of the Demand. Demands never leave our node,
+    /// so nobody validates their id against the new expiration.
+    pub async fn prolong(
+        &self,
+        id: &SubscriptionId,
+        expiration_ts: NaiveDateTime,
+    ) -> DbResult<bool> {
+        let id = id.clone();
+
+        do_with_transaction(self.pool, move |conn| {
+            let num_updated = diesel::update(dsl::market_demand.filter(dsl::id.eq(id)))
+                .set(dsl::expiration_ts.eq(expiration_ts))
+                .execute(conn)?;
+            Ok(num_updated > 0)
+        })
+        .await
+    }
+
     pub async fn delete(&self, id: &SubscriptionId) -> DbResult<bool> {
         let id = id.clone();
 
@@ -0,0 +1,73 @@
+use diesel::{ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl};
+
+use ya_client::model::NodeId;
+use ya_persistence::executor::{do_with_transaction, readonly_transaction, PoolType};
+
+use crate::db::model::DbDemandTemplate;
+use crate::db::schema::market_demand_template::dsl as template;
+use crate::db::schema::market_demand_template::dsl::market_demand_template;
+use crate::db::{AsMixedDao, DbResult};
+
+pub struct DemandTemplateDao<'c> {
+    pool: &'c PoolType,
+}
+
+impl<'a> AsMixedDao<'a> for DemandTemplateDao<'a> {
+    fn as_dao(disk_pool: &'a PoolType, _ram_pool: &'a PoolType) -> Self {
+        Self { pool: disk_pool }
+    }
+}
+
+impl<'c> DemandTemplateDao<'c> {
+    /// Replaces template with the same name, if there is one.
+    pub async fn save(&self, new: DbDemandTemplate) -> DbResult<()> {
+        do_with_transaction(self.pool, move |conn| {
+            diesel::delete(
+                market_demand_template
+                    .filter(template::owner_id.eq(&new.owner_id))
+                    .filter(template::name.eq(&new.name)),
+            )
+            .execute(conn)?;
+            diesel::insert_into(market_demand_template)
+                .values(&new)
+                .execute(conn)?;
+            Ok(())
+        })
+        .await
+    }
+
+    pub async fn select(&self, owner_id: NodeId) -> DbResult<Vec<DbDemandTemplate>> {
+        readonly_transaction(self.pool, move |conn| {
+            Ok(market_demand_template
+                .filter(template::owner_id.eq(owner_id))
+                .order_by(template::name)
+                .load::<DbDemandTemplate>(conn)?)
+        })
+        .await
+    }
+
+    pub async fn get(&self, owner_id: NodeId, name: String) -> DbResult<Option<DbDemandTemplate>> {
+        readonly_transaction(self.pool, move |conn| {
+            Ok(market_demand_template
+                .filter(template::owner_id.eq(owner_id))
+                .filter(template::name.eq(name))
+                .first::<DbDemandTemplate>(conn)
+                .optional()?)
+        })
+        .await
+    }
+
+    /// Returns whether the template existed.
+    pub async fn delete(&self, owner_id: NodeId, name: String) -> DbResult<bool> {
+        do_with_transaction(self.pool, move |conn| {
+            let deleted = diesel::delete(
+                market_demand_template
+                    .filter(template::owner_id.eq(owner_id))
+                    .filter(template::name.eq(name)),
+            )
+            .execute(conn)?;
+            Ok(deleted > 0)
+        })
+        .await
+    }
+}
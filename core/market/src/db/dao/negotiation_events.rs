@@ -8,9 +8,9 @@ use ya_persistence::executor::ConnType;
 use ya_persistence::executor::{do_with_transaction, PoolType};
 
 use crate::config::DbConfig;
+use crate::db::dao::days_ago;
 use crate::db::dao::demand::{demand_status, DemandState};
 use crate::db::dao::offer::{query_state, OfferState};
-use crate::db::dao::sql_functions::datetime;
 use crate::db::model::{Agreement, EventType, MarketEvent, Owner, Proposal, SubscriptionId};
 use crate::db::schema::market_negotiation_event::dsl;
 use crate::db::{AsMixedDao, DbError, DbResult};
@@ -140,8 +140,7 @@ impl<'c> NegotiationEventsDao<'c> {
         let interval_days = db_config.event_store_days;
         let num_deleted = do_with_transaction(self.pool, move |conn| {
             let nd = diesel::delete(
-                dsl::market_negotiation_event
-                    .filter(dsl::timestamp.lt(datetime("NOW", format!("-{} days", interval_days)))),
+                dsl::market_negotiation_event.filter(dsl::timestamp.lt(days_ago(interval_days))),
             )
             .execute(conn)?;
             Result::<usize, DbError>::Ok(nd)
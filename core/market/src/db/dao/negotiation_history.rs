@@ -0,0 +1,52 @@
+use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl};
+
+use ya_persistence::executor::{readonly_transaction, ConnType, PoolType};
+
+use crate::db::model::{AgreementId, NegotiationHistoryEntry};
+use crate::db::schema::market_negotiation_history::dsl as history;
+use crate::db::schema::market_negotiation_history::dsl::market_negotiation_history;
+use crate::db::{AsMixedDao, DbResult};
+
+pub struct NegotiationHistoryDao<'c> {
+    pool: &'c PoolType,
+}
+
+impl<'a> AsMixedDao<'a> for NegotiationHistoryDao<'a> {
+    fn as_dao(disk_pool: &'a PoolType, _ram_pool: &'a PoolType) -> Self {
+        Self { pool: disk_pool }
+    }
+}
+
+impl<'c> NegotiationHistoryDao<'c> {
+    /// Returns Proposals chain ending with Agreement, starting from initial Proposal.
+    pub async fn select(
+        &self,
+        agreement_id: &AgreementId,
+    ) -> DbResult<Vec<NegotiationHistoryEntry>> {
+        let agreement_id = agreement_id.clone();
+        readonly_transaction(self.pool, move |conn| {
+            Ok(market_negotiation_history
+                .select((
+                    history::agreement_id,
+                    history::proposal_id,
+                    history::prev_proposal_id,
+                    history::issuer_id,
+                    history::properties,
+                    history::constraints,
+                    history::state,
+                    history::creation_ts,
+                ))
+                .filter(history::agreement_id.eq(agreement_id))
+                .order_by(history::id.asc())
+                .load::<NegotiationHistoryEntry>(conn)?)
+        })
+        .await
+    }
+}
+
+pub(crate) fn create_history(conn: &ConnType, entries: &[NegotiationHistoryEntry]) -> DbResult<()> {
+    diesel::insert_into(market_negotiation_history)
+        .values(entries)
+        .execute(conn)?;
+    Ok(())
+}
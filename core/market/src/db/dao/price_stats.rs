@@ -0,0 +1,82 @@
+use chrono::NaiveDateTime;
+use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl};
+
+use ya_persistence::executor::{do_with_transaction, readonly_transaction, PoolType};
+
+use crate::db::dao::days_ago;
+use crate::db::model::PriceStats;
+use crate::db::schema::market_price_stats::dsl as stats;
+use crate::db::schema::market_price_stats::dsl::market_price_stats;
+use crate::db::{AsMixedDao, DbError, DbResult};
+
+pub struct PriceStatsDao<'c> {
+    pool: &'c PoolType,
+}
+
+impl<'a> AsMixedDao<'a> for PriceStatsDao<'a> {
+    fn as_dao(disk_pool: &'a PoolType, _ram_pool: &'a PoolType) -> Self {
+        Self { pool: disk_pool }
+    }
+}
+
+impl<'c> PriceStatsDao<'c> {
+    pub async fn insert(&self, samples: Vec<PriceStats>) -> DbResult<()> {
+        do_with_transaction(self.pool, move |conn| {
+            diesel::insert_into(market_price_stats)
+                .values(&samples)
+                .execute(conn)?;
+            Ok(())
+        })
+        .await
+    }
+
+    pub async fn select(
+        &self,
+        runtime: Option<String>,
+        after_timestamp: NaiveDateTime,
+    ) -> DbResult<Vec<PriceStats>> {
+        readonly_transaction(self.pool, move |conn| {
+            let mut query = market_price_stats
+                .select((
+                    stats::runtime,
+                    stats::usage_counter,
+                    stats::sample_ts,
+                    stats::offers_count,
+                    stats::p10,
+                    stats::p25,
+                    stats::p50,
+                    stats::p75,
+                    stats::p90,
+                ))
+                .filter(stats::sample_ts.gt(after_timestamp))
+                .into_boxed();
+
+            if let Some(runtime) = runtime {
+                query = query.filter(stats::runtime.eq(runtime));
+            }
+
+            Ok(query
+                .order_by(stats::sample_ts.asc())
+                .load::<PriceStats>(conn)?)
+        })
+        .await
+    }
+
+    pub async fn clean(&self, store_days: i32) -> DbResult<()> {
+        log::trace!("Clean market price statistics: start");
+        let num_deleted = do_with_transaction(self.pool, move |conn| {
+            let nd = diesel::delete(
+                market_price_stats.filter(stats::sample_ts.lt(days_ago(store_days))),
+            )
+            .execute(conn)?;
+            Result::<usize, DbError>::Ok(nd)
+        })
+        .await?;
+
+        if num_deleted > 0 {
+            log::info!("Cleaned {} market price statistics", num_deleted);
+        }
+        log::trace!("Clean market price statistics: done");
+        Ok(())
+    }
+}
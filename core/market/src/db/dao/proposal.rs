@@ -190,6 +190,34 @@ pub(super) fn has_counter_proposal(conn: &ConnType, proposal_id: &ProposalId) ->
     Ok(proposal.is_some())
 }
 
+/// Follows `prev_proposal_id` links back to initial Proposal.
+/// Returns Proposals in order, in which they were negotiated. Chain is cut
+/// on first missing Proposal.
+pub(super) fn proposal_chain(conn: &ConnType, proposal_id: &ProposalId) -> DbResult<Vec<Proposal>> {
+    let mut chain = vec![];
+    let mut next_id = Some(proposal_id.clone());
+
+    while let Some(id) = next_id {
+        let body: DbProposal = match dsl::market_proposal
+            .filter(dsl::id.eq(&id))
+            .first(conn)
+            .optional()?
+        {
+            Some(body) => body,
+            None => break,
+        };
+        let negotiation: Negotiation = dsl_negotiation::market_negotiation
+            .filter(dsl_negotiation::id.eq(&body.negotiation_id))
+            .first(conn)?;
+
+        next_id = body.prev_proposal_id.clone();
+        chain.push(Proposal { negotiation, body });
+    }
+
+    chain.reverse();
+    Ok(chain)
+}
+
 pub(super) fn update_proposal_state(
     conn: &ConnType,
     proposal_id: &ProposalId,
@@ -0,0 +1,57 @@
+use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl};
+
+use ya_client::model::NodeId;
+use ya_persistence::executor::{do_with_transaction, readonly_transaction, PoolType};
+
+use crate::db::model::{DbOutcome, ReputationOutcome};
+use crate::db::schema::market_reputation_outcome::dsl as reputation;
+use crate::db::schema::market_reputation_outcome::dsl::market_reputation_outcome;
+use crate::db::{AsMixedDao, DbResult};
+
+pub struct ReputationDao<'c> {
+    pool: &'c PoolType,
+}
+
+impl<'a> AsMixedDao<'a> for ReputationDao<'a> {
+    fn as_dao(disk_pool: &'a PoolType, _ram_pool: &'a PoolType) -> Self {
+        Self { pool: disk_pool }
+    }
+}
+
+impl<'c> ReputationDao<'c> {
+    /// Outcomes already recorded for the same subject are ignored.
+    pub async fn record(&self, outcome: ReputationOutcome) -> DbResult<()> {
+        do_with_transaction(self.pool, move |conn| {
+            #[cfg(not(feature = "postgres"))]
+            diesel::insert_or_ignore_into(market_reputation_outcome)
+                .values(&outcome)
+                .execute(conn)?;
+            #[cfg(feature = "postgres")]
+            diesel::insert_into(market_reputation_outcome)
+                .values(&outcome)
+                .on_conflict_do_nothing()
+                .execute(conn)?;
+            Ok(())
+        })
+        .await
+    }
+
+    pub async fn select(&self, node_id: NodeId) -> DbResult<Vec<DbOutcome>> {
+        readonly_transaction(self.pool, move |conn| {
+            Ok(market_reputation_outcome
+                .select(reputation::outcome)
+                .filter(reputation::node_id.eq(node_id))
+                .load::<DbOutcome>(conn)?)
+        })
+        .await
+    }
+
+    pub async fn select_all(&self) -> DbResult<Vec<(NodeId, DbOutcome)>> {
+        readonly_transaction(self.pool, move |conn| {
+            Ok(market_reputation_outcome
+                .select((reputation::node_id, reputation::outcome))
+                .load::<(NodeId, DbOutcome)>(conn)?)
+        })
+        .await
+    }
+}
@@ -1,20 +1,30 @@
 mod agreement;
+mod agreement_amendment;
 mod agreement_events;
 mod demand;
+mod demand_template;
 mod negotiation_events;
+mod negotiation_history;
 mod offer;
+mod price_stats;
 mod proposal;
 mod proposal_id;
+mod reputation;
 mod subscription_id;
 
 pub use agreement::{check_transition, Agreement, AgreementId, AgreementState, AppSessionId};
-pub use agreement_events::{AgreementEvent, AgreementEventType, NewAgreementEvent};
+pub use agreement_amendment::{Amendment, AmendmentState, ClientAmendment};
+pub use agreement_events::{AgreementEvent, AgreementEventType, DbReason, NewAgreementEvent};
 pub use demand::Demand;
+pub use demand_template::DbDemandTemplate;
 pub use negotiation_events::{EventError, EventType, MarketEvent};
+pub use negotiation_history::NegotiationHistoryEntry;
 pub use offer::{Offer, OfferUnsubscribed};
+pub use price_stats::PriceStats;
 pub use proposal::{DbProposal, Issuer, Negotiation, Proposal, ProposalState};
 
 pub use proposal_id::{Owner, ProposalId, ProposalIdParseError, ProposalIdValidationError};
+pub use reputation::{DbOutcome, ReputationOutcome};
 pub use subscription_id::{
     generate_random_id, SubscriptionId, SubscriptionParseError, SubscriptionValidationError,
 };
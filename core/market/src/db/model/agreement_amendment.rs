@@ -0,0 +1,98 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use diesel::sql_types::Text;
+use serde::{Deserialize, Serialize};
+
+use ya_client::model::market::{Reason, Role};
+use ya_client::model::ErrorMessage;
+use ya_diesel_utils::DbTextField;
+
+use crate::db::model::{AgreementId, DbReason, Owner};
+use crate::db::schema::market_agreement_amendment;
+
+#[derive(
+    DbTextField,
+    strum_macros::EnumString,
+    derive_more::Display,
+    AsExpression,
+    FromSqlRow,
+    PartialEq,
+    Eq,
+    Debug,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+)]
+#[sql_type = "Text"]
+pub enum AmendmentState {
+    /// Waiting for decision of the other party.
+    Pending,
+    /// Changes were applied to the Agreement.
+    Accepted,
+    Rejected,
+}
+
+/// Change of running Agreement terms. Properties are stored as patches,
+/// which will be merged into Agreement properties after acceptance.
+#[derive(Clone, Debug, Insertable, Queryable)]
+#[table_name = "market_agreement_amendment"]
+pub struct Amendment {
+    pub amendment_id: String,
+    pub agreement_id: AgreementId,
+    pub issuer: Owner,
+
+    pub demand_properties: Option<String>,
+    pub offer_properties: Option<String>,
+    pub reason: Option<DbReason>,
+
+    pub state: AmendmentState,
+    pub creation_ts: NaiveDateTime,
+    pub update_ts: NaiveDateTime,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientAmendment {
+    pub amendment_id: String,
+    pub agreement_id: String,
+    /// Party, that proposed the amendment.
+    pub issuer: Role,
+    pub demand_properties: Option<serde_json::Value>,
+    pub offer_properties: Option<serde_json::Value>,
+    pub reason: Option<Reason>,
+    pub state: AmendmentState,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl Amendment {
+    pub fn into_client(self) -> Result<ClientAmendment, ErrorMessage> {
+        let parse = |properties: Option<String>| {
+            properties
+                .map(|properties| serde_json::from_str(&properties))
+                .transpose()
+                .map_err(|e| {
+                    format!(
+                        "Can't deserialize Amendment [{}] properties. Error: {}",
+                        self.amendment_id, e
+                    )
+                })
+        };
+
+        let demand_properties = parse(self.demand_properties.clone())?;
+        let offer_properties = parse(self.offer_properties.clone())?;
+
+        Ok(ClientAmendment {
+            demand_properties,
+            offer_properties,
+            agreement_id: self.agreement_id.into_client(),
+            amendment_id: self.amendment_id,
+            issuer: match self.issuer {
+                Owner::Provider => Role::Provider,
+                Owner::Requestor => Role::Requestor,
+            },
+            reason: self.reason.map(|reason| reason.0),
+            state: self.state,
+            timestamp: DateTime::<Utc>::from_utc(self.update_ts, Utc),
+        })
+    }
+}
@@ -0,0 +1,55 @@
+use chrono::{NaiveDateTime, TimeZone, Utc};
+
+use ya_client::model::NodeId;
+use ya_core_model::market::DemandTemplate;
+use ya_persistence::types::BigDecimalField;
+
+use crate::db::schema::market_demand_template;
+
+#[derive(Clone, Debug, Insertable, Queryable)]
+#[table_name = "market_demand_template"]
+pub struct DbDemandTemplate {
+    pub owner_id: NodeId,
+    pub name: String,
+    pub properties: String,
+    pub constraints: String,
+    pub payment_platform: Option<String>,
+    pub expiration_secs: Option<i64>,
+    pub debit_note_accept_timeout_secs: Option<i64>,
+    pub budget: Option<BigDecimalField>,
+    pub updated_ts: NaiveDateTime,
+}
+
+impl DbDemandTemplate {
+    pub fn from_client(template: DemandTemplate) -> Self {
+        DbDemandTemplate {
+            owner_id: template.owner_id,
+            name: template.name,
+            properties: template.properties.to_string(),
+            constraints: template.constraints,
+            payment_platform: template.payment_platform,
+            expiration_secs: template.expiration_secs.map(|secs| secs as i64),
+            debit_note_accept_timeout_secs: template
+                .debit_note_accept_timeout_secs
+                .map(|secs| secs as i64),
+            budget: template.budget.map(Into::into),
+            updated_ts: Utc::now().naive_utc(),
+        }
+    }
+
+    pub fn into_client(self) -> Result<DemandTemplate, serde_json::Error> {
+        Ok(DemandTemplate {
+            properties: serde_json::from_str(&self.properties)?,
+            name: self.name,
+            owner_id: self.owner_id,
+            constraints: self.constraints,
+            payment_platform: self.payment_platform,
+            expiration_secs: self.expiration_secs.map(|secs| secs as u64),
+            debit_note_accept_timeout_secs: self
+                .debit_note_accept_timeout_secs
+                .map(|secs| secs as u64),
+            budget: self.budget.map(Into::into),
+            updated_at: Utc.from_utc_datetime(&self.updated_ts),
+        })
+    }
+}
@@ -0,0 +1,59 @@
+use chrono::{NaiveDateTime, TimeZone, Utc};
+
+use ya_client::model::market::proposal::{Proposal as ClientProposal, State};
+use ya_client::model::{ErrorMessage, NodeId};
+
+use crate::db::model::{AgreementId, Proposal, ProposalId, ProposalState};
+use crate::db::schema::market_negotiation_history;
+
+/// Copy of single Proposal from negotiation chain, that ended with Agreement.
+/// Proposals are removed from database after expiration, so we must keep
+/// them separately to be able to explain final Agreement terms later.
+#[derive(Clone, Debug, Insertable, Queryable)]
+#[table_name = "market_negotiation_history"]
+pub struct NegotiationHistoryEntry {
+    pub agreement_id: AgreementId,
+    pub proposal_id: ProposalId,
+    pub prev_proposal_id: Option<ProposalId>,
+    pub issuer_id: NodeId,
+
+    pub properties: String,
+    pub constraints: String,
+
+    pub state: ProposalState,
+    pub creation_ts: NaiveDateTime,
+}
+
+impl NegotiationHistoryEntry {
+    pub fn new(agreement_id: &AgreementId, proposal: Proposal) -> NegotiationHistoryEntry {
+        NegotiationHistoryEntry {
+            agreement_id: agreement_id.clone(),
+            issuer_id: proposal.issuer(),
+            proposal_id: proposal.body.id,
+            prev_proposal_id: proposal.body.prev_proposal_id,
+            properties: proposal.body.properties,
+            constraints: proposal.body.constraints,
+            state: proposal.body.state,
+            creation_ts: proposal.body.creation_ts,
+        }
+    }
+
+    pub fn into_client(self) -> Result<ClientProposal, ErrorMessage> {
+        let properties = serde_json::from_str(&self.properties).map_err(|error| {
+            format!(
+                "Can't deserialize Proposal [{}] properties from negotiation history. Error: {}",
+                self.proposal_id, error
+            )
+        })?;
+
+        Ok(ClientProposal {
+            properties,
+            constraints: self.constraints,
+            proposal_id: self.proposal_id.to_string(),
+            issuer_id: self.issuer_id,
+            state: State::from(self.state),
+            timestamp: Utc.from_utc_datetime(&self.creation_ts),
+            prev_proposal_id: self.prev_proposal_id.map(|id| id.to_string()),
+        })
+    }
+}
@@ -0,0 +1,22 @@
+use chrono::NaiveDateTime;
+use serde::Serialize;
+
+use crate::db::schema::market_price_stats;
+
+/// Percentiles of price for single usage counter of given runtime,
+/// computed from Offers available on market at `sample_ts`.
+#[derive(Clone, Debug, PartialEq, Insertable, Queryable, Serialize)]
+#[table_name = "market_price_stats"]
+#[serde(rename_all = "camelCase")]
+pub struct PriceStats {
+    pub runtime: String,
+    pub usage_counter: String,
+    pub sample_ts: NaiveDateTime,
+    pub offers_count: i32,
+
+    pub p10: f64,
+    pub p25: f64,
+    pub p50: f64,
+    pub p75: f64,
+    pub p90: f64,
+}
@@ -0,0 +1,48 @@
+use chrono::NaiveDateTime;
+use diesel::sql_types::Text;
+
+use ya_client::model::NodeId;
+use ya_core_model::market::Outcome;
+use ya_diesel_utils::DbTextField;
+
+use crate::db::schema::market_reputation_outcome;
+
+#[derive(
+    DbTextField,
+    strum_macros::EnumString,
+    derive_more::Display,
+    AsExpression,
+    FromSqlRow,
+    PartialEq,
+    Eq,
+    Debug,
+    Clone,
+    Copy,
+)]
+#[sql_type = "Text"]
+pub enum DbOutcome {
+    AgreementCompleted,
+    PaymentOnTime,
+    PaymentLate,
+    ActivityFailed,
+}
+
+#[derive(Clone, Debug, Insertable, Queryable)]
+#[table_name = "market_reputation_outcome"]
+pub struct ReputationOutcome {
+    pub node_id: NodeId,
+    pub subject_id: String,
+    pub outcome: DbOutcome,
+    pub timestamp: NaiveDateTime,
+}
+
+impl From<Outcome> for DbOutcome {
+    fn from(outcome: Outcome) -> Self {
+        match outcome {
+            Outcome::AgreementCompleted => DbOutcome::AgreementCompleted,
+            Outcome::PaymentOnTime => DbOutcome::PaymentOnTime,
+            Outcome::PaymentLate => DbOutcome::PaymentLate,
+            Outcome::ActivityFailed => DbOutcome::ActivityFailed,
+        }
+    }
+}
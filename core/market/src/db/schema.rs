@@ -121,11 +121,86 @@ table! {
     }
 }
 
+table! {
+    market_negotiation_history (id) {
+        id -> Integer,
+        agreement_id -> Text,
+        proposal_id -> Text,
+        prev_proposal_id -> Nullable<Text>,
+        issuer_id -> Text,
+
+        properties -> Text,
+        constraints -> Text,
+
+        state -> Text,
+        creation_ts -> Timestamp,
+    }
+}
+
+table! {
+    market_agreement_amendment (amendment_id, agreement_id) {
+        amendment_id -> Text,
+        agreement_id -> Text,
+        issuer -> Text,
+
+        demand_properties -> Nullable<Text>,
+        offer_properties -> Nullable<Text>,
+        reason -> Nullable<Text>,
+
+        state -> Text,
+        creation_ts -> Timestamp,
+        update_ts -> Timestamp,
+    }
+}
+
+table! {
+    market_price_stats (id) {
+        id -> Integer,
+        runtime -> Text,
+        usage_counter -> Text,
+        sample_ts -> Timestamp,
+        offers_count -> Integer,
+
+        p10 -> Double,
+        p25 -> Double,
+        p50 -> Double,
+        p75 -> Double,
+        p90 -> Double,
+    }
+}
+
+table! {
+    market_reputation_outcome (node_id, subject_id, outcome) {
+        node_id -> Text,
+        subject_id -> Text,
+        outcome -> Text,
+        timestamp -> Timestamp,
+    }
+}
+
+table! {
+    market_demand_template (owner_id, name) {
+        owner_id -> Text,
+        name -> Text,
+        properties -> Text,
+        constraints -> Text,
+        payment_platform -> Nullable<Text>,
+        expiration_secs -> Nullable<BigInt>,
+        debit_note_accept_timeout_secs -> Nullable<BigInt>,
+        budget -> Nullable<Text>,
+        updated_ts -> Timestamp,
+    }
+}
+
 allow_tables_to_appear_in_same_query!(market_demand, market_offer, market_offer_unsubscribed);
 allow_tables_to_appear_in_same_query!(market_proposal, market_negotiation);
 allow_tables_to_appear_in_same_query!(market_agreement, market_agreement_event);
+allow_tables_to_appear_in_same_query!(market_agreement, market_negotiation_history);
+allow_tables_to_appear_in_same_query!(market_agreement, market_agreement_amendment);
 
 joinable!(market_agreement_event -> market_agreement (agreement_id));
+joinable!(market_negotiation_history -> market_agreement (agreement_id));
+joinable!(market_agreement_amendment -> market_agreement (agreement_id));
 joinable!(market_negotiation -> market_agreement (agreement_id));
 joinable!(market_offer -> market_offer_unsubscribed (id));
 joinable!(market_proposal -> market_negotiation (negotiation_id));
@@ -0,0 +1,255 @@
+//! Requestor's saved negotiation profiles.
+//!
+//! A template keeps Demand properties and constraints together with payment
+//! platform and timeouts, which are turned into properties each time a Demand
+//! is subscribed from it. Budget is only stored for the Requestor's own use.
+use chrono::{DateTime, Duration, Utc};
+use serde_json::{Map, Value};
+use std::sync::Arc;
+
+use ya_client::model::market::NewDemand;
+use ya_client::model::NodeId;
+use ya_core_model::market::{
+    DeleteDemandTemplate, DemandTemplate, ListDemandTemplates, RpcMessageError, SaveDemandTemplate,
+    SubscribeDemandTemplate,
+};
+use ya_service_api_web::middleware::Identity;
+use ya_service_bus::typed::ServiceBinder;
+
+use crate::db::dao::DemandTemplateDao;
+use crate::db::model::DbDemandTemplate;
+use crate::db::{DbError, DbMixedExecutor};
+use crate::market::{MarketError, MarketService};
+
+const EXPIRATION_PROPERTY: &str = "golem.srv.comp.expiration";
+const CHOSEN_PLATFORM_PROPERTY: &str = "golem.com.payment.chosen-platform";
+const DEBIT_NOTE_ACCEPT_TIMEOUT_PROPERTY: &str = "golem.com.payment.debit-notes.accept-timeout?";
+
+#[derive(thiserror::Error, Debug)]
+pub enum DemandTemplateError {
+    #[error("Demand template [{0}] not found.")]
+    NotFound(String),
+    #[error("Invalid Demand template: {0}.")]
+    Invalid(String),
+    #[error("Demand template database error: {0}.")]
+    Db(#[from] DbError),
+    #[error(transparent)]
+    Subscribe(#[from] Box<MarketError>),
+}
+
+impl From<DemandTemplateError> for RpcMessageError {
+    fn from(e: DemandTemplateError) -> Self {
+        match e {
+            DemandTemplateError::NotFound(name) => RpcMessageError::NotFound(name),
+            DemandTemplateError::Invalid(_) => RpcMessageError::BadRequest(e.to_string()),
+            e => RpcMessageError::Market(e.to_string()),
+        }
+    }
+}
+
+pub async fn bind_local_gsb(market: Arc<MarketService>, local_prefix: &str) {
+    log::trace!("Binding market Demand templates to service bus");
+    ServiceBinder::new(local_prefix, &market.db, market.clone())
+        .bind(save_template_gsb)
+        .bind(list_templates_gsb)
+        .bind(delete_template_gsb)
+        .bind_with_processor(subscribe_template_gsb);
+    log::debug!("Successfully bound market Demand templates to service bus");
+}
+
+pub async fn save(
+    db: &DbMixedExecutor,
+    template: DemandTemplate,
+) -> Result<DemandTemplate, DemandTemplateError> {
+    if template.name.is_empty() {
+        return Err(DemandTemplateError::Invalid("empty name".to_string()));
+    }
+    if !(template.properties.is_object() || template.properties.is_null()) {
+        return Err(DemandTemplateError::Invalid(
+            "properties should be an object".to_string(),
+        ));
+    }
+
+    let template = DbDemandTemplate::from_client(template);
+    db.as_dao::<DemandTemplateDao>()
+        .save(template.clone())
+        .await?;
+    template
+        .into_client()
+        .map_err(|e| DemandTemplateError::Invalid(e.to_string()))
+}
+
+pub async fn list(
+    db: &DbMixedExecutor,
+    owner_id: NodeId,
+) -> Result<Vec<DemandTemplate>, DemandTemplateError> {
+    db.as_dao::<DemandTemplateDao>()
+        .select(owner_id)
+        .await?
+        .into_iter()
+        .map(|template| {
+            template
+                .into_client()
+                .map_err(|e| DemandTemplateError::Invalid(e.to_string()))
+        })
+        .collect()
+}
+
+pub async fn get(
+    db: &DbMixedExecutor,
+    owner_id: NodeId,
+    name: String,
+) -> Result<DemandTemplate, DemandTemplateError> {
+    db.as_dao::<DemandTemplateDao>()
+        .get(owner_id, name.clone())
+        .await?
+        .ok_or(DemandTemplateError::NotFound(name))?
+        .into_client()
+        .map_err(|e| DemandTemplateError::Invalid(e.to_string()))
+}
+
+pub async fn delete(
+    db: &DbMixedExecutor,
+    owner_id: NodeId,
+    name: String,
+) -> Result<(), DemandTemplateError> {
+    match db
+        .as_dao::<DemandTemplateDao>()
+        .delete(owner_id, name.clone())
+        .await?
+    {
+        true => Ok(()),
+        false => Err(DemandTemplateError::NotFound(name)),
+    }
+}
+
+/// Subscribes Demand built from the template and returns its subscription id.
+pub async fn subscribe(
+    market: &MarketService,
+    name: String,
+    id: &Identity,
+) -> Result<String, DemandTemplateError> {
+    let template = get(&market.db, id.identity, name).await?;
+    let demand = to_new_demand(&template, Utc::now());
+    let subscription_id = market
+        .subscribe_demand(&demand, id)
+        .await
+        .map_err(Box::new)?;
+
+    log::info!(
+        "Subscribed Demand [{}] from template [{}].",
+        subscription_id,
+        template.name
+    );
+    Ok(subscription_id.to_string())
+}
+
+fn to_new_demand(template: &DemandTemplate, now: DateTime<Utc>) -> NewDemand {
+    let mut properties = match &template.properties {
+        Value::Object(properties) => properties.clone(),
+        _ => Map::new(),
+    };
+
+    if let Some(platform) = &template.payment_platform {
+        properties.insert(
+            CHOSEN_PLATFORM_PROPERTY.to_string(),
+            platform.clone().into(),
+        );
+        properties.insert(
+            format!("golem.com.payment.platform.{}.address", platform),
+            template.owner_id.to_string().into(),
+        );
+    }
+    if let Some(secs) = template.expiration_secs {
+        let expiration = now + Duration::seconds(secs as i64);
+        properties.insert(
+            EXPIRATION_PROPERTY.to_string(),
+            expiration.timestamp_millis().into(),
+        );
+    }
+    if let Some(secs) = template.debit_note_accept_timeout_secs {
+        properties.insert(DEBIT_NOTE_ACCEPT_TIMEOUT_PROPERTY.to_string(), secs.into());
+    }
+
+    NewDemand::new(Value::Object(properties), template.constraints.clone())
+}
+
+async fn save_template_gsb(
+    db: DbMixedExecutor,
+    _sender_id: String,
+    msg: SaveDemandTemplate,
+) -> Result<DemandTemplate, RpcMessageError> {
+    Ok(save(&db, msg.0).await?)
+}
+
+async fn list_templates_gsb(
+    db: DbMixedExecutor,
+    _sender_id: String,
+    msg: ListDemandTemplates,
+) -> Result<Vec<DemandTemplate>, RpcMessageError> {
+    Ok(list(&db, msg.owner_id).await?)
+}
+
+async fn delete_template_gsb(
+    db: DbMixedExecutor,
+    _sender_id: String,
+    msg: DeleteDemandTemplate,
+) -> Result<(), RpcMessageError> {
+    Ok(delete(&db, msg.owner_id, msg.name).await?)
+}
+
+async fn subscribe_template_gsb(
+    _db: DbMixedExecutor,
+    market: Arc<MarketService>,
+    _sender_id: String,
+    msg: SubscribeDemandTemplate,
+) -> Result<String, RpcMessageError> {
+    let id = Identity {
+        identity: msg.owner_id,
+        name: "local".to_string(),
+        role: "manager".to_string(),
+    };
+    Ok(subscribe(&market, msg.name, &id).await?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_new_demand() {
+        let owner_id: NodeId = "0xbabe000000000000000000000000000000000000"
+            .parse()
+            .unwrap();
+        let template = DemandTemplate {
+            name: "vm".to_string(),
+            owner_id,
+            properties: serde_json::json!({ "golem.srv.caps.multi-activity": true }),
+            constraints: "(golem.runtime.name=vm)".to_string(),
+            payment_platform: Some("erc20-polygon-glm".to_string()),
+            expiration_secs: Some(1800),
+            debit_note_accept_timeout_secs: Some(240),
+            budget: None,
+            updated_at: Utc::now(),
+        };
+        let now = Utc::now();
+        let demand = to_new_demand(&template, now);
+
+        assert_eq!(demand.constraints, template.constraints);
+        let properties = demand.properties.as_object().unwrap();
+        assert_eq!(properties["golem.srv.caps.multi-activity"], true);
+        assert_eq!(
+            properties[CHOSEN_PLATFORM_PROPERTY],
+            Value::from("erc20-polygon-glm")
+        );
+        assert_eq!(
+            properties["golem.com.payment.platform.erc20-polygon-glm.address"],
+            Value::from(owner_id.to_string())
+        );
+        assert_eq!(
+            properties[EXPIRATION_PROPERTY],
+            Value::from((now + Duration::seconds(1800)).timestamp_millis())
+        );
+        assert_eq!(properties[DEBIT_NOTE_ACCEPT_TIMEOUT_PROPERTY], 240);
+    }
+}
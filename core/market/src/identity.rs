@@ -31,7 +31,7 @@ pub struct IdentityGSB;
 impl IdentityApi for IdentityGSB {
     async fn default_identity(&self) -> Result<NodeId, IdentityError> {
         Ok(bus::service(identity::BUS_ID)
-            .send(identity::Get::ByDefault)
+            .send(identity::Get::ByRole(identity::IdentityRole::NodeId))
             .await
             .map_err(|e| IdentityError::GsbError(e.to_string()))?
             .map_err(|e| IdentityError::GetDefaultIdError(e.to_string()))?
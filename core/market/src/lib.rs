@@ -4,11 +4,15 @@ extern crate diesel;
 mod cli;
 mod config;
 mod db;
+mod demand_template;
 mod identity;
 mod market;
 mod matcher;
+mod metric_keys;
 mod negotiation;
+mod price_stats;
 mod protocol;
+mod reputation;
 mod rest_api;
 mod utils;
 
@@ -6,33 +6,41 @@ use std::sync::{Arc, Mutex};
 use thiserror::Error;
 
 use crate::config::Config;
-use crate::db::dao::AgreementDao;
-use crate::db::model::{AgreementId, AppSessionId, Owner, SubscriptionId};
+use crate::db::dao::{AgreementDao, AgreementDaoError, NegotiationHistoryDao, PriceStatsDao};
+use crate::db::model::{AgreementId, AppSessionId, Owner, PriceStats, SubscriptionId};
+use crate::demand_template;
 use crate::identity::{IdentityApi, IdentityGSB};
+use crate::market::amendment::Amendments;
 use crate::matcher::error::{
     DemandError, MatcherError, MatcherInitError, QueryDemandsError, QueryOfferError,
     QueryOffersError,
 };
+use crate::matcher::scan::{ScanRequest, ScanResult};
 use crate::matcher::{store::SubscriptionStore, Matcher};
 use crate::negotiation::error::{
     AgreementError, AgreementEventsError, NegotiationError, NegotiationInitError,
 };
 use crate::negotiation::{EventNotifier, ProviderBroker, RequestorBroker};
+use crate::reputation;
 use crate::rest_api;
 use crate::testing::AgreementState;
 
 use ya_client::model::market::{
     Agreement, AgreementListEntry, AgreementOperationEvent as ClientAgreementEvent, Demand,
-    NewDemand, NewOffer, Offer, Reason, Role,
+    NewDemand, NewOffer, Offer, Proposal, Reason, Role,
 };
-use ya_core_model::market::{local, BUS_ID};
+use ya_client::model::NodeId;
+use ya_core_model::market::{local, Reputation, BUS_ID};
+use ya_service_api::recovery::{RecoveryAction, RecoveryItem};
 use ya_service_api_interfaces::{Provider, Service};
 use ya_service_api_web::middleware::Identity;
 
-use crate::db::DbMixedExecutor;
+use crate::db::{DbError, DbMixedExecutor};
+use crate::metric_keys;
 use ya_service_api_web::scope::ExtendableScope;
 
 pub mod agreement;
+pub mod amendment;
 
 #[derive(Error, Debug)]
 pub enum MarketError {
@@ -48,6 +56,10 @@ pub enum MarketError {
     DemandError(#[from] DemandError),
     #[error(transparent)]
     Negotiation(#[from] NegotiationError),
+    #[error("Failed to get price statistics. Error: {0}.")]
+    PriceStats(#[from] DbError),
+    #[error("Failed to get reputation. Error: {0}.")]
+    Reputation(DbError),
 }
 
 #[derive(Error, Debug)]
@@ -70,6 +82,8 @@ pub struct MarketService {
     pub matcher: Matcher,
     pub provider_engine: ProviderBroker,
     pub requestor_engine: RequestorBroker,
+    pub amendments: Amendments,
+    background_tasks: Vec<tokio::task::JoinHandle<()>>,
 }
 
 impl MarketService {
@@ -78,12 +92,7 @@ impl MarketService {
         identity_api: Arc<dyn IdentityApi>,
         config: Arc<Config>,
     ) -> Result<Self, MarketInitError> {
-        counter!("market.offers.subscribed", 0);
-        counter!("market.offers.unsubscribed", 0);
-        counter!("market.offers.expired", 0);
-        counter!("market.demands.subscribed", 0);
-        counter!("market.demands.unsubscribed", 0);
-        counter!("market.demands.expired", 0);
+        metric_keys::init();
 
         db.ram_db
             .apply_migration(crate::db::migrations::run_with_output)?;
@@ -110,16 +119,26 @@ impl MarketService {
             agreement_notifier,
             config.clone(),
         )?;
+        let amendments = Amendments::new(db.clone(), config.clone());
         let cleaner_db = db.clone();
-        tokio::spawn(async move {
-            crate::db::dao::cleaner::clean_forever(cleaner_db, config.db.clone()).await;
-        });
+        let price_stats_db = db.clone();
+        let price_stats_config = config.price_stats.clone();
+        let background_tasks = vec![
+            tokio::spawn(async move {
+                crate::db::dao::cleaner::clean_forever(cleaner_db, config.db.clone()).await;
+            }),
+            tokio::spawn(async move {
+                crate::price_stats::sample_forever(price_stats_db, price_stats_config).await;
+            }),
+        ];
 
         Ok(MarketService {
             db: db.clone(),
             matcher,
             provider_engine,
             requestor_engine,
+            amendments,
+            background_tasks,
         })
     }
 
@@ -136,6 +155,8 @@ impl MarketService {
             .bind_gsb(public_prefix, local_prefix)
             .await?;
         agreement::bind_gsb(self.db.clone(), public_prefix, local_prefix).await;
+        self.amendments.bind_gsb(public_prefix, local_prefix).await;
+        reputation::bind_gsb(self.db.clone(), public_prefix, local_prefix).await;
         Ok(())
     }
 
@@ -143,7 +164,50 @@ impl MarketService {
         ctx: &Context,
     ) -> anyhow::Result<()> {
         let market = MARKET.get_or_init_market(&ctx.component())?;
-        Ok(market.bind_gsb(BUS_ID, local::BUS_ID).await?)
+        market.bind_gsb(BUS_ID, local::BUS_ID).await?;
+        agreement::bind_local_gsb(market.clone(), local::BUS_ID).await;
+        demand_template::bind_local_gsb(market, local::BUS_ID).await;
+        Ok(())
+    }
+
+    /// Stops periodic database cleanup and statistics, then flushes market database,
+    /// so Offers and Demands are restored from it after restart.
+    pub async fn shut_down() {
+        let market = MARKET.locked_market.lock().unwrap().clone();
+        let market = match market {
+            Some(market) => market,
+            None => return,
+        };
+        for task in &market.background_tasks {
+            task.abort();
+        }
+
+        #[cfg(not(feature = "postgres"))]
+        if let Err(e) = market.db.disk_db.checkpoint().await {
+            log::warn!("Failed to flush market database: {}", e);
+        }
+        log::info!("Market service stopped.");
+    }
+
+    /// Reverts Agreements which were being approved during previous shutdown.
+    /// Approval can be repeated afterwards, like after approval timeout.
+    pub async fn recover<Context: Provider<Self, DbMixedExecutor>>(
+        ctx: &Context,
+    ) -> anyhow::Result<Vec<RecoveryItem>> {
+        let db: DbMixedExecutor = ctx.component();
+        let reverted = db.as_dao::<AgreementDao>().revert_all_approving().await?;
+        Ok(reverted
+            .into_iter()
+            .map(|id| {
+                RecoveryItem::new(
+                    "market",
+                    id.into_client(),
+                    AgreementState::Approving,
+                    RecoveryAction::RolledBack,
+                )
+                .details("Approval interrupted. Agreement is Pending again")
+            })
+            .collect())
     }
 
     pub fn rest<Context: Provider<Self, DbMixedExecutor>>(ctx: &Context) -> actix_web::Scope {
@@ -175,6 +239,15 @@ impl MarketService {
             .await?)
     }
 
+    pub async fn scan_offers(
+        &self,
+        request: &ScanRequest,
+        offset: usize,
+        limit: usize,
+    ) -> Result<ScanResult, MarketError> {
+        Ok(self.matcher.scan_offers(request, offset, limit).await?)
+    }
+
     pub async fn get_demands(&self, id: Option<Identity>) -> Result<Vec<Demand>, MarketError> {
         Ok(self
             .matcher
@@ -191,7 +264,7 @@ impl MarketService {
         let offer = self.matcher.subscribe_offer(offer, id).await?;
         self.provider_engine.subscribe_offer(&offer).await?;
 
-        counter!("market.offers.subscribed", 1);
+        counter!(metric_keys::OFFERS_SUBSCRIBED, 1);
         Ok(offer.id)
     }
 
@@ -204,7 +277,7 @@ impl MarketService {
         self.provider_engine.unsubscribe_offer(offer_id).await?;
         self.matcher.unsubscribe_offer(offer_id, id).await?;
 
-        counter!("market.offers.unsubscribed", 1);
+        counter!(metric_keys::OFFERS_UNSUBSCRIBED, 1);
         Ok(())
     }
 
@@ -216,7 +289,7 @@ impl MarketService {
         let demand = self.matcher.subscribe_demand(demand, id).await?;
         self.requestor_engine.subscribe_demand(&demand).await?;
 
-        counter!("market.demands.subscribed", 1);
+        counter!(metric_keys::DEMANDS_SUBSCRIBED, 1);
         Ok(demand.id)
     }
 
@@ -231,7 +304,7 @@ impl MarketService {
         // TODO: shouldn't remove precede negotiation unsubscribe?
         self.matcher.unsubscribe_demand(demand_id, id).await?;
 
-        counter!("market.demands.unsubscribed", 1);
+        counter!(metric_keys::DEMANDS_UNSUBSCRIBED, 1);
         Ok(())
     }
 
@@ -289,6 +362,55 @@ impl MarketService {
         }
     }
 
+    pub async fn get_price_stats(
+        &self,
+        runtime: Option<String>,
+        after_timestamp: DateTime<Utc>,
+    ) -> Result<Vec<PriceStats>, MarketError> {
+        Ok(self
+            .db
+            .as_dao::<PriceStatsDao>()
+            .select(runtime, after_timestamp.naive_utc())
+            .await?)
+    }
+
+    pub async fn get_reputation(&self, node_id: NodeId) -> Result<Reputation, MarketError> {
+        reputation::get_reputation(&self.db, node_id)
+            .await
+            .map_err(MarketError::Reputation)
+    }
+
+    pub async fn list_reputation(&self) -> Result<Vec<Reputation>, MarketError> {
+        reputation::list_reputation(&self.db)
+            .await
+            .map_err(MarketError::Reputation)
+    }
+
+    /// Returns chain of Proposals, that ended with the Agreement.
+    pub async fn get_negotiation_history(
+        &self,
+        agreement_id: &AgreementId,
+        id: &Identity,
+    ) -> Result<Vec<Proposal>, AgreementError> {
+        // Checks if caller is owner of the Agreement.
+        self.get_agreement(agreement_id, id).await?;
+
+        self.db
+            .as_dao::<NegotiationHistoryDao>()
+            .select(agreement_id)
+            .await
+            .map_err(|e| {
+                AgreementError::Get(agreement_id.to_string(), AgreementDaoError::DbError(e))
+            })?
+            .into_iter()
+            .map(|entry| {
+                entry
+                    .into_client()
+                    .map_err(|e| AgreementError::Internal(e.to_string()))
+            })
+            .collect()
+    }
+
     pub async fn query_agreement_events(
         &self,
         session_id: &AppSessionId,
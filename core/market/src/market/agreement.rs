@@ -1,20 +1,59 @@
 use chrono::{DateTime, Utc};
-use ya_client::model::market::{Agreement as ClientAgreement, AgreementListEntry, Role};
-use ya_core_model::market::{GetAgreement, ListAgreements, RpcMessageError};
+use std::collections::HashMap;
+use std::sync::Arc;
+use ya_agreement_utils::TerminationReason;
+use ya_client::model::market::{
+    Agreement as ClientAgreement, AgreementListEntry, Proposal as ClientProposal, Role,
+};
+use ya_core_model::market::{
+    GetAgreement, GetNegotiationHistory, GetTerminationStats, ListAgreements, RpcMessageError,
+    TerminateAgreement, TerminationStats,
+};
+use ya_service_api_web::middleware::Identity;
 use ya_service_bus::typed::ServiceBinder;
 
-use crate::db::dao::AgreementDao;
+use crate::db::dao::{AgreementDao, AgreementEventsDao, NegotiationHistoryDao};
 use crate::db::model::{AgreementId, Owner};
 use crate::db::DbMixedExecutor;
+use crate::market::MarketService;
+use crate::negotiation::error::AgreementError;
 
-pub async fn bind_gsb(db: DbMixedExecutor, public_prefix: &str, _local_prefix: &str) {
+pub async fn bind_gsb(db: DbMixedExecutor, public_prefix: &str, local_prefix: &str) {
     log::trace!("Binding market agreement public service to service bus");
     ServiceBinder::new(public_prefix, &db, ())
         .bind(list_agreements)
-        .bind(get_agreement);
+        .bind(get_agreement)
+        .bind(get_termination_stats);
+    // Negotiation history reveals our Offers or Demands to other nodes.
+    ServiceBinder::new(local_prefix, &db, ()).bind(get_negotiation_history);
     log::debug!("Successfully bound market agreement public service to service bus");
 }
 
+pub async fn bind_local_gsb(market: Arc<MarketService>, local_prefix: &str) {
+    ServiceBinder::new(local_prefix, &market.db, market.clone())
+        .bind_with_processor(terminate_agreement);
+}
+
+async fn terminate_agreement(
+    _db: DbMixedExecutor,
+    market: Arc<MarketService>,
+    _sender_id: String,
+    msg: TerminateAgreement,
+) -> Result<(), RpcMessageError> {
+    let id = Identity {
+        identity: msg.node_id,
+        name: "local".to_string(),
+        role: "manager".to_string(),
+    };
+    market
+        .terminate_agreement(id, msg.agreement_id, msg.reason)
+        .await
+        .map_err(|e| match e {
+            AgreementError::NotFound(id) => RpcMessageError::NotFound(id),
+            e => RpcMessageError::Market(e.to_string()),
+        })
+}
+
 async fn list_agreements(
     db: DbMixedExecutor,
     _sender_id: String,
@@ -77,3 +116,84 @@ async fn get_agreement(
         .into_client()
         .map_err(|e| RpcMessageError::Market(e.to_string()))
 }
+
+async fn get_negotiation_history(
+    db: DbMixedExecutor,
+    _sender_id: String,
+    msg: GetNegotiationHistory,
+) -> Result<Vec<ClientProposal>, RpcMessageError> {
+    // We don't know which side of the Agreement we are, so we check both.
+    let r_agreement_id = AgreementId::from_client(&msg.agreement_id, Owner::Requestor)
+        .map_err(|e| RpcMessageError::BadRequest(e.to_string()))?;
+    let p_agreement_id = r_agreement_id.clone().swap_owner();
+
+    let dao = db.as_dao::<AgreementDao>();
+    let now = chrono::Utc::now().naive_utc();
+    let agreement = match dao
+        .select(&r_agreement_id, None, now)
+        .await
+        .map_err(|e| RpcMessageError::Market(e.to_string()))?
+    {
+        Some(agreement) => agreement,
+        None => dao
+            .select(&p_agreement_id, None, now)
+            .await
+            .map_err(|e| RpcMessageError::Market(e.to_string()))?
+            .ok_or_else(|| RpcMessageError::NotFound(msg.agreement_id.clone()))?,
+    };
+
+    db.as_dao::<NegotiationHistoryDao>()
+        .select(&agreement.id)
+        .await
+        .map_err(|e| RpcMessageError::Market(e.to_string()))?
+        .into_iter()
+        .map(|entry| {
+            entry
+                .into_client()
+                .map_err(|e| RpcMessageError::Market(e.to_string()))
+        })
+        .collect()
+}
+
+async fn get_termination_stats(
+    db: DbMixedExecutor,
+    _sender_id: String,
+    msg: GetTerminationStats,
+) -> Result<Vec<TerminationStats>, RpcMessageError> {
+    let after = msg
+        .after_date
+        .map(|date| date.naive_utc())
+        .unwrap_or_else(|| chrono::NaiveDateTime::from_timestamp_opt(0, 0).unwrap());
+    let events = db
+        .as_dao::<AgreementEventsDao>()
+        .select_terminations(after)
+        .await
+        .map_err(|e| RpcMessageError::Market(e.to_string()))?;
+
+    let mut counts = HashMap::<_, u32>::new();
+    for event in events {
+        let reason = TerminationReason::from_reason(
+            event.reason.as_ref().map(|reason| &reason.0),
+            event.issuer == Owner::Requestor,
+        );
+        *counts
+            .entry((event.agreement_id.owner(), event.issuer, reason))
+            .or_default() += 1;
+    }
+
+    let role = |owner: Owner| match owner {
+        Owner::Provider => Role::Provider,
+        Owner::Requestor => Role::Requestor,
+    };
+    let mut stats = counts
+        .into_iter()
+        .map(|((owner, issuer, reason), count)| TerminationStats {
+            role: role(owner),
+            terminator: role(issuer),
+            reason: reason.to_string(),
+            count,
+        })
+        .collect::<Vec<_>>();
+    stats.sort_by(|a, b| b.count.cmp(&a.count));
+    Ok(stats)
+}
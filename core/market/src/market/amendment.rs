@@ -0,0 +1,657 @@
+//! Renegotiation of running Agreements.
+//!
+//! Either party can propose an Amendment with patches of Demand and Offer properties.
+//! The other party decides about it either manually through REST api or automatically
+//! using `AmendmentPolicy`. Accepted Amendments are merged into Agreement properties,
+//! Provider agent follows amended expiration and pricing of running Agreements.
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+
+use ya_client::model::market::Reason;
+use ya_client::model::NodeId;
+use ya_core_model::journal;
+use ya_service_api_web::middleware::Identity;
+
+use crate::config::Config;
+use crate::db::dao::{AgreementDao, AgreementDaoError, AmendmentDao, AmendmentDaoError};
+use crate::db::model::{
+    Agreement, AgreementId, AgreementState, Amendment, AmendmentState, ClientAmendment, DbReason,
+    Owner,
+};
+use crate::db::DbMixedExecutor;
+use crate::protocol::amendment::{
+    AmendmentAccepted, AmendmentApi, AmendmentProposed, AmendmentProtocolError, AmendmentRejected,
+};
+
+const EXPIRATION_PROPERTY: &str = "golem.srv.comp.expiration";
+
+#[derive(Error, Debug)]
+pub enum AmendmentError {
+    #[error("Agreement [{0}] not found.")]
+    NotFound(String),
+    #[error("Invalid Agreement id. {0}")]
+    InvalidId(String),
+    #[error("Amendment [{0}] not found.")]
+    AmendmentNotFound(String),
+    #[error("Amendment of Agreement [{0}] doesn't change any properties.")]
+    NoChanges(AgreementId),
+    #[error("Amendment properties must be JSON object.")]
+    InvalidProperties,
+    #[error("Can't accept or reject own Amendment [{0}].")]
+    OwnAmendment(String),
+    #[error("Failed to get Agreement [{0}]. Error: {1}")]
+    Get(AgreementId, AgreementDaoError),
+    #[error(transparent)]
+    Dao(#[from] AmendmentDaoError),
+    #[error("Protocol error: {0}")]
+    Protocol(#[from] AmendmentProtocolError),
+    #[error("Internal error: {0}")]
+    Internal(String),
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewAmendment {
+    #[serde(default)]
+    pub demand_properties: Option<serde_json::Value>,
+    #[serde(default)]
+    pub offer_properties: Option<serde_json::Value>,
+    #[serde(default)]
+    pub reason: Option<Reason>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum AmendmentDecision {
+    Accept,
+    Reject(Option<Reason>),
+    /// Leave Amendment pending until user decides.
+    Ask,
+}
+
+/// Hook deciding about Amendments proposed by the other party.
+pub trait AmendmentPolicy: Send + Sync {
+    fn decide(&self, agreement: &Agreement, amendment: &Amendment) -> AmendmentDecision;
+}
+
+/// Provider side policy accepting Agreement expiration extensions
+/// not longer than configured limit. Other changes need manual decision.
+pub struct ExtensionPolicy {
+    pub max_extension: Duration,
+}
+
+impl AmendmentPolicy for ExtensionPolicy {
+    fn decide(&self, agreement: &Agreement, amendment: &Amendment) -> AmendmentDecision {
+        if agreement.id.owner() != Owner::Provider
+            || amendment.offer_properties.is_some()
+            || self.max_extension == Duration::from_secs(0)
+        {
+            return AmendmentDecision::Ask;
+        }
+
+        let patch = match amendment
+            .demand_properties
+            .as_deref()
+            .and_then(|patch| serde_json::from_str(patch).ok())
+        {
+            Some(patch) => ya_agreement_utils::agreement::flatten(patch),
+            None => return AmendmentDecision::Ask,
+        };
+        let current = serde_json::from_str(&agreement.demand_properties)
+            .map(ya_agreement_utils::agreement::flatten)
+            .ok()
+            .and_then(|properties| properties.get(EXPIRATION_PROPERTY)?.as_i64());
+        let proposed = patch
+            .get(EXPIRATION_PROPERTY)
+            .and_then(|value| value.as_i64());
+
+        match (patch.len(), current, proposed) {
+            (1, Some(current), Some(proposed))
+                if proposed > current
+                    && proposed - current <= self.max_extension.as_millis() as i64 =>
+            {
+                AmendmentDecision::Accept
+            }
+            _ => AmendmentDecision::Ask,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Amendments {
+    db: DbMixedExecutor,
+    api: AmendmentApi,
+    config: Arc<Config>,
+}
+
+impl Amendments {
+    pub fn new(db: DbMixedExecutor, config: Arc<Config>) -> Amendments {
+        let policy: Arc<dyn AmendmentPolicy> = Arc::new(ExtensionPolicy {
+            max_extension: config.amendment.max_auto_extension,
+        });
+        Amendments::with_policy(db, policy, config)
+    }
+
+    pub fn with_policy(
+        db: DbMixedExecutor,
+        policy: Arc<dyn AmendmentPolicy>,
+        config: Arc<Config>,
+    ) -> Amendments {
+        let db1 = db.clone();
+        let db2 = db.clone();
+        let db3 = db.clone();
+        let api = AmendmentApi::new(
+            move |caller: String, msg: AmendmentProposed| {
+                on_amendment_proposed(db1.clone(), policy.clone(), caller, msg)
+            },
+            move |caller: String, msg: AmendmentAccepted| {
+                on_amendment_accepted(db2.clone(), caller, msg)
+            },
+            move |caller: String, msg: AmendmentRejected| {
+                on_amendment_rejected(db3.clone(), caller, msg)
+            },
+        );
+        Amendments { db, api, config }
+    }
+
+    pub async fn bind_gsb(&self, public_prefix: &str, local_prefix: &str) {
+        self.api.bind_gsb(public_prefix, local_prefix).await
+    }
+
+    pub async fn propose(
+        &self,
+        agreement_id: &str,
+        amendment: NewAmendment,
+        id: &Identity,
+    ) -> Result<ClientAmendment, AmendmentError> {
+        let agreement = self.get_agreement(agreement_id, id.identity).await?;
+        if amendment.demand_properties.is_none() && amendment.offer_properties.is_none() {
+            return Err(AmendmentError::NoChanges(agreement.id));
+        }
+
+        let serialize = |properties: Option<serde_json::Value>| match properties {
+            Some(properties) if properties.is_object() => Ok(Some(properties.to_string())),
+            Some(_) => Err(AmendmentError::InvalidProperties),
+            None => Ok(None),
+        };
+
+        let now = Utc::now().naive_utc();
+        let new_amendment = Amendment {
+            amendment_id: uuid::Uuid::new_v4().to_simple().to_string(),
+            agreement_id: agreement.id.clone(),
+            issuer: agreement.id.owner(),
+            demand_properties: serialize(amendment.demand_properties)?,
+            offer_properties: serialize(amendment.offer_properties)?,
+            reason: amendment.reason.clone().map(DbReason),
+            state: AmendmentState::Pending,
+            creation_ts: now,
+            update_ts: now,
+        };
+
+        let dao = self.db.as_dao::<AmendmentDao>();
+        dao.insert(new_amendment.clone()).await?;
+
+        let msg = AmendmentProposed {
+            agreement_id: agreement.id.clone(),
+            amendment_id: new_amendment.amendment_id.clone(),
+            demand_properties: new_amendment.demand_properties.clone(),
+            offer_properties: new_amendment.offer_properties.clone(),
+            reason: amendment.reason,
+            creation_ts: now,
+        };
+
+        let amendment_id = new_amendment.amendment_id.clone();
+        let state = match self.api.propose(&agreement, msg).await {
+            Ok(state) => state,
+            Err(e) => {
+                // Other party doesn't know about this Amendment, so we can forget it.
+                dao.delete(&amendment_id, &agreement.id)
+                    .await
+                    .map_err(|e| AmendmentError::Internal(e.to_string()))?;
+                return Err(e.into());
+            }
+        };
+
+        log::info!(
+            "Amendment [{}] of Agreement [{}] proposed to [{}]. Remote decision: {}.",
+            amendment_id,
+            agreement.id,
+            counterparty(&agreement),
+            state
+        );
+
+        let amendment = match state {
+            AmendmentState::Pending => new_amendment,
+            AmendmentState::Accepted => {
+                let amendment = dao
+                    .accept(&amendment_id, &agreement.id, agreement.id.owner())
+                    .await?;
+                record_accepted(&agreement, &amendment).await;
+                amendment
+            }
+            AmendmentState::Rejected => {
+                dao.reject(&amendment_id, &agreement.id, agreement.id.owner(), None)
+                    .await?
+            }
+        };
+        amendment
+            .into_client()
+            .map_err(|e| AmendmentError::Internal(e.to_string()))
+    }
+
+    pub async fn accept(
+        &self,
+        agreement_id: &str,
+        amendment_id: &str,
+        id: &Identity,
+    ) -> Result<ClientAmendment, AmendmentError> {
+        let agreement = self.get_agreement(agreement_id, id.identity).await?;
+        let pending = self.get_remote_amendment(&agreement, amendment_id).await?;
+
+        let dao = self.db.as_dao::<AmendmentDao>();
+        let amendment = dao
+            .accept(amendment_id, &agreement.id, agreement.id.owner().swap())
+            .await?;
+
+        let msg = AmendmentAccepted {
+            agreement_id: agreement.id.clone(),
+            amendment_id: amendment_id.to_string(),
+        };
+        if let Err(e) = self.api.accept(&agreement, msg).await {
+            self.revert(&pending, &agreement).await?;
+            return Err(e.into());
+        }
+
+        log::info!(
+            "Amendment [{}] of Agreement [{}] accepted.",
+            amendment_id,
+            agreement.id
+        );
+        record_accepted(&agreement, &amendment).await;
+        amendment
+            .into_client()
+            .map_err(|e| AmendmentError::Internal(e.to_string()))
+    }
+
+    pub async fn reject(
+        &self,
+        agreement_id: &str,
+        amendment_id: &str,
+        reason: Option<Reason>,
+        id: &Identity,
+    ) -> Result<ClientAmendment, AmendmentError> {
+        let agreement = self.get_agreement(agreement_id, id.identity).await?;
+        let pending = self.get_remote_amendment(&agreement, amendment_id).await?;
+
+        let amendment = self
+            .db
+            .as_dao::<AmendmentDao>()
+            .reject(
+                amendment_id,
+                &agreement.id,
+                agreement.id.owner().swap(),
+                reason.clone().map(DbReason),
+            )
+            .await?;
+
+        let msg = AmendmentRejected {
+            agreement_id: agreement.id.clone(),
+            amendment_id: amendment_id.to_string(),
+            reason,
+        };
+        if let Err(e) = self.api.reject(&agreement, msg).await {
+            self.revert(&pending, &agreement).await?;
+            return Err(e.into());
+        }
+
+        log::info!(
+            "Amendment [{}] of Agreement [{}] rejected.",
+            amendment_id,
+            agreement.id
+        );
+        amendment
+            .into_client()
+            .map_err(|e| AmendmentError::Internal(e.to_string()))
+    }
+
+    /// Other party doesn't know about our decision, so we can't keep it.
+    async fn revert(
+        &self,
+        pending: &Amendment,
+        agreement: &Agreement,
+    ) -> Result<(), AmendmentError> {
+        self.db
+            .as_dao::<AmendmentDao>()
+            .revert(pending, agreement)
+            .await
+            .map_err(|e| {
+                log::error!(
+                    "Failed to revert decision about Amendment [{}] of Agreement [{}]. {}",
+                    pending.amendment_id,
+                    agreement.id,
+                    e
+                );
+                AmendmentError::Internal(e.to_string())
+            })
+    }
+
+    pub async fn list(
+        &self,
+        agreement_id: &str,
+        id: &Identity,
+    ) -> Result<Vec<ClientAmendment>, AmendmentError> {
+        let agreement = self.get_agreement(agreement_id, id.identity).await?;
+        self.db
+            .as_dao::<AmendmentDao>()
+            .select(&agreement.id)
+            .await
+            .map_err(AmendmentDaoError::from)?
+            .into_iter()
+            .map(|amendment| {
+                amendment
+                    .into_client()
+                    .map_err(|e| AmendmentError::Internal(e.to_string()))
+            })
+            .collect()
+    }
+
+    /// Returns Amendments proposed or decided after given timestamp.
+    /// Serves as event stream for both parties of Agreements.
+    pub async fn query_events(
+        &self,
+        after_timestamp: DateTime<Utc>,
+        max_events: Option<i32>,
+        id: &Identity,
+    ) -> Result<Vec<ClientAmendment>, AmendmentError> {
+        let max_events = max_events
+            .unwrap_or(self.config.events.max_events_default)
+            .min(self.config.events.max_events_max)
+            .max(0);
+        self.db
+            .as_dao::<AmendmentDao>()
+            .select_updated(id.identity, after_timestamp.naive_utc(), max_events as i64)
+            .await
+            .map_err(AmendmentDaoError::from)?
+            .into_iter()
+            .map(|amendment| {
+                amendment
+                    .into_client()
+                    .map_err(|e| AmendmentError::Internal(e.to_string()))
+            })
+            .collect()
+    }
+
+    /// Finds Agreement owned by `node_id`. We don't know which side of
+    /// the Agreement we are, so we try both.
+    async fn get_agreement(
+        &self,
+        agreement_id: &str,
+        node_id: NodeId,
+    ) -> Result<Agreement, AmendmentError> {
+        let r_agreement_id = AgreementId::from_client(agreement_id, Owner::Requestor)
+            .map_err(|e| AmendmentError::InvalidId(e.to_string()))?;
+        let p_agreement_id = r_agreement_id.clone().swap_owner();
+
+        let dao = self.db.as_dao::<AgreementDao>();
+        let now = Utc::now().naive_utc();
+        for id in vec![r_agreement_id, p_agreement_id] {
+            if let Some(agreement) = dao
+                .select(&id, Some(node_id), now)
+                .await
+                .map_err(|e| AmendmentError::Get(id.clone(), e))?
+            {
+                return Ok(agreement);
+            }
+        }
+        Err(AmendmentError::NotFound(agreement_id.to_string()))
+    }
+
+    /// Only Amendments proposed by the other party can be accepted or rejected.
+    async fn get_remote_amendment(
+        &self,
+        agreement: &Agreement,
+        amendment_id: &str,
+    ) -> Result<Amendment, AmendmentError> {
+        let amendment = self
+            .db
+            .as_dao::<AmendmentDao>()
+            .get(amendment_id, &agreement.id)
+            .await
+            .map_err(AmendmentDaoError::from)?
+            .ok_or_else(|| AmendmentError::AmendmentNotFound(amendment_id.to_string()))?;
+
+        if amendment.issuer == agreement.id.owner() {
+            return Err(AmendmentError::OwnAmendment(amendment_id.to_string()));
+        }
+        match amendment.state {
+            AmendmentState::Pending => Ok(amendment),
+            state => Err(AmendmentDaoError::NotPending(amendment_id.to_string(), state).into()),
+        }
+    }
+}
+
+fn counterparty(agreement: &Agreement) -> NodeId {
+    match agreement.id.owner() {
+        Owner::Provider => agreement.requestor_id,
+        Owner::Requestor => agreement.provider_id,
+    }
+}
+
+/// Records Amendment merged into the Agreement in the journal, so changes of running
+/// Agreements, like extended expiration, can be followed by both parties.
+async fn record_accepted(agreement: &Agreement, amendment: &Amendment) {
+    let node_id = match agreement.id.owner() {
+        Owner::Provider => agreement.provider_id,
+        Owner::Requestor => agreement.requestor_id,
+    };
+    let mut event = journal::Event::new(journal::Category::Market, "agreement-amended")
+        .subject(agreement.id.into_client())
+        .node_id(node_id);
+    match amendment
+        .clone()
+        .into_client()
+        .map_err(|e| e.to_string())
+        .and_then(|amendment| serde_json::to_value(amendment).map_err(|e| e.to_string()))
+    {
+        Ok(details) => event = event.details(details),
+        Err(e) => log::warn!("Can't record Amendment [{}]. {}", amendment.amendment_id, e),
+    }
+    event.record().await;
+}
+
+/// Finds Agreement and checks if caller is the other party.
+async fn remote_agreement(
+    db: &DbMixedExecutor,
+    caller: String,
+    agreement_id: &AgreementId,
+) -> Result<Agreement, AmendmentProtocolError> {
+    let caller =
+        NodeId::from_str(&caller).map_err(|_| AmendmentProtocolError::CallerParse(caller))?;
+    let agreement = db
+        .as_dao::<AgreementDao>()
+        .select(agreement_id, None, Utc::now().naive_utc())
+        .await
+        .map_err(|_| AmendmentProtocolError::AgreementNotFound(agreement_id.clone()))?
+        .ok_or_else(|| AmendmentProtocolError::AgreementNotFound(agreement_id.clone()))?;
+
+    if counterparty(&agreement) != caller {
+        return Err(AmendmentProtocolError::NotParty(
+            caller,
+            agreement_id.clone(),
+        ));
+    }
+    Ok(agreement)
+}
+
+async fn on_amendment_proposed(
+    db: DbMixedExecutor,
+    policy: Arc<dyn AmendmentPolicy>,
+    caller: String,
+    msg: AmendmentProposed,
+) -> Result<AmendmentState, AmendmentProtocolError> {
+    let agreement = remote_agreement(&db, caller, &msg.agreement_id).await?;
+    let amendment_id = msg.amendment_id.clone();
+    let remote_error = |e: AmendmentDaoError| match e {
+        // Don't expose database errors to other nodes.
+        AmendmentDaoError::DbError(e) => {
+            log::warn!("Failed to save Amendment [{}]: {}", amendment_id, e);
+            AmendmentProtocolError::Remote(amendment_id.clone(), "internal error".to_string())
+        }
+        e => AmendmentProtocolError::Remote(amendment_id.clone(), e.to_string()),
+    };
+
+    let amendment = Amendment {
+        amendment_id: msg.amendment_id.clone(),
+        agreement_id: agreement.id.clone(),
+        issuer: agreement.id.owner().swap(),
+        demand_properties: msg.demand_properties,
+        offer_properties: msg.offer_properties,
+        reason: msg.reason.map(DbReason),
+        state: AmendmentState::Pending,
+        creation_ts: msg.creation_ts,
+        update_ts: Utc::now().naive_utc(),
+    };
+
+    let dao = db.as_dao::<AmendmentDao>();
+    dao.insert(amendment.clone()).await.map_err(remote_error)?;
+
+    let issuer = amendment.issuer;
+    let decision = match agreement.state {
+        AgreementState::Approved => policy.decide(&agreement, &amendment),
+        _ => AmendmentDecision::Ask,
+    };
+    log::info!(
+        "Amendment [{}] of Agreement [{}] received. Decision: {:?}.",
+        msg.amendment_id,
+        agreement.id,
+        decision
+    );
+
+    Ok(match decision {
+        AmendmentDecision::Accept => {
+            let amendment = dao
+                .accept(&msg.amendment_id, &agreement.id, issuer)
+                .await
+                .map_err(remote_error)?;
+            record_accepted(&agreement, &amendment).await;
+            amendment.state
+        }
+        AmendmentDecision::Reject(reason) => {
+            dao.reject(
+                &msg.amendment_id,
+                &agreement.id,
+                issuer,
+                reason.map(DbReason),
+            )
+            .await
+            .map_err(remote_error)?
+            .state
+        }
+        AmendmentDecision::Ask => AmendmentState::Pending,
+    })
+}
+
+async fn on_amendment_accepted(
+    db: DbMixedExecutor,
+    caller: String,
+    msg: AmendmentAccepted,
+) -> Result<(), AmendmentProtocolError> {
+    let agreement = remote_agreement(&db, caller, &msg.agreement_id).await?;
+    let amendment = db
+        .as_dao::<AmendmentDao>()
+        .accept(&msg.amendment_id, &agreement.id, agreement.id.owner())
+        .await
+        .map_err(|e| AmendmentProtocolError::Remote(msg.amendment_id.clone(), e.to_string()))?;
+    record_accepted(&agreement, &amendment).await;
+
+    log::info!(
+        "Amendment [{}] of Agreement [{}] accepted by [{}].",
+        msg.amendment_id,
+        agreement.id,
+        counterparty(&agreement)
+    );
+    Ok(())
+}
+
+async fn on_amendment_rejected(
+    db: DbMixedExecutor,
+    caller: String,
+    msg: AmendmentRejected,
+) -> Result<(), AmendmentProtocolError> {
+    let agreement = remote_agreement(&db, caller, &msg.agreement_id).await?;
+    db.as_dao::<AmendmentDao>()
+        .reject(
+            &msg.amendment_id,
+            &agreement.id,
+            agreement.id.owner(),
+            msg.reason.map(DbReason),
+        )
+        .await
+        .map_err(|e| AmendmentProtocolError::Remote(msg.amendment_id.clone(), e.to_string()))?;
+
+    log::info!(
+        "Amendment [{}] of Agreement [{}] rejected by [{}].",
+        msg.amendment_id,
+        agreement.id,
+        counterparty(&agreement)
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::mock_agreement::generate_agreement;
+
+    fn extension(agreement: &Agreement, patch: serde_json::Value) -> Amendment {
+        let now = Utc::now().naive_utc();
+        Amendment {
+            amendment_id: "amendment".to_string(),
+            agreement_id: agreement.id.clone(),
+            issuer: Owner::Requestor,
+            demand_properties: Some(patch.to_string()),
+            offer_properties: None,
+            reason: None,
+            state: AmendmentState::Pending,
+            creation_ts: now,
+            update_ts: now,
+        }
+    }
+
+    #[test]
+    fn test_extension_policy() {
+        let policy = ExtensionPolicy {
+            max_extension: Duration::from_secs(3600),
+        };
+        let mut agreement = generate_agreement(1, Utc::now().naive_utc());
+        agreement.id = agreement.id.translate(Owner::Provider);
+        agreement.demand_properties =
+            serde_json::json!({ EXPIRATION_PROPERTY: 1_000_000 }).to_string();
+
+        let within = extension(
+            &agreement,
+            serde_json::json!({ EXPIRATION_PROPERTY: 1_000_000 + 3_600_000 }),
+        );
+        let too_long = extension(
+            &agreement,
+            serde_json::json!({ EXPIRATION_PROPERTY: 1_000_000 + 3_600_001 }),
+        );
+        let other = extension(
+            &agreement,
+            serde_json::json!({ EXPIRATION_PROPERTY: 1_000_001, "golem.inf.mem.gib": 1 }),
+        );
+
+        assert_eq!(
+            policy.decide(&agreement, &within),
+            AmendmentDecision::Accept
+        );
+        assert_eq!(policy.decide(&agreement, &too_long), AmendmentDecision::Ask);
+        assert_eq!(policy.decide(&agreement, &other), AmendmentDecision::Ask);
+
+        agreement.id = agreement.id.translate(Owner::Requestor);
+        assert_eq!(policy.decide(&agreement, &within), AmendmentDecision::Ask);
+    }
+}
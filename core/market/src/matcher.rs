@@ -20,13 +20,17 @@ pub(crate) mod cyclic;
 pub mod error;
 pub(crate) mod handlers;
 pub(crate) mod resolver;
+pub(crate) mod scan;
+pub(crate) mod schema;
 pub(crate) mod store;
 
 use crate::db::dao::{DemandDao, DemandState};
-use error::{MatcherError, MatcherInitError, QueryOfferError, QueryOffersError};
+use crate::metric_keys;
+use error::{MatcherError, MatcherInitError, QueryOfferError, QueryOffersError, ScanError};
 use futures::FutureExt;
 use log::debug;
 use resolver::Resolver;
+use scan::{ScanRequest, ScanResult};
 use store::SubscriptionStore;
 use ya_core_model::net::local::{
     BindBroadcastError, BroadcastMessage, NewNeighbour, SendBroadcastMessage,
@@ -87,18 +91,6 @@ impl Matcher {
 
         let listeners = EventsListeners { proposal_receiver };
 
-        // Initialize counters to 0 value. Otherwise they won't appear on metrics endpoint
-        // until first change to value will be made.
-        counter!("market.offers.incoming", 0);
-        counter!("market.offers.broadcasts", 0);
-        counter!("market.offers.broadcasts.skip", 0);
-        counter!("market.offers.broadcasts.net", 0);
-        counter!("market.offers.broadcasts.net_errors", 0);
-        counter!("market.offers.unsubscribes.incoming", 0);
-        counter!("market.offers.unsubscribes.broadcasts", 0);
-        counter!("market.offers.unsubscribes.broadcasts.net", 0);
-        counter!("market.offers.unsubscribes.broadcasts.net_errors", 0);
-
         Ok((matcher, listeners))
     }
 
@@ -113,6 +105,8 @@ impl Matcher {
         // That's why we don't spawn this in Matcher::new.
         tokio::task::spawn_local(cyclic::bcast_offers(self.clone()));
         tokio::task::spawn_local(cyclic::bcast_unsubscribes(self.clone()));
+        tokio::task::spawn_local(cyclic::sample_availability(self.clone()));
+        tokio::task::spawn_local(cyclic::keep_alive_subscriptions(self.clone()));
 
         self.bind_neighbourhood_bcast(local_prefix).await.ok();
 
@@ -150,7 +144,7 @@ impl Matcher {
                     ("Offer", Ok(id)) => {
                         if let Err(QueryOfferError::Expired(_)) = store.get_offer(id).await {
                             log::info!("Offer [{}] expired.", id);
-                            counter!("market.offers.expired", 1)
+                            counter!(metric_keys::OFFERS_EXPIRED, 1)
                         }
                     }
                     ("Demand", Ok(id)) => {
@@ -158,7 +152,7 @@ impl Matcher {
                             store.db.as_dao::<DemandDao>().demand_state(id).await
                         {
                             log::info!("Demand [{}] expired.", id);
-                            counter!("market.demands.expired", 1)
+                            counter!(metric_keys::DEMANDS_EXPIRED, 1)
                         }
                     }
                     _ => {}
@@ -170,6 +164,21 @@ impl Matcher {
         Ok(())
     }
 
+    /// Returns known Offers matching given constraints without subscribing Demand.
+    pub async fn scan_offers(
+        &self,
+        request: &ScanRequest,
+        offset: usize,
+        limit: usize,
+    ) -> Result<ScanResult, MatcherError> {
+        let offers = self
+            .store
+            .get_client_offers(None)
+            .await
+            .map_err(ScanError::from)?;
+        Ok(scan::scan_offers(offers, request, offset, limit)?)
+    }
+
     // =========================================== //
     // Offer/Demand subscription
     // =========================================== //
@@ -1,12 +1,181 @@
 //! Cyclic methods for Matcher spawned after binding to GSB
+use chrono::{NaiveDateTime, Utc};
 use metrics::{counter, timing};
 use rand::seq::IteratorRandom;
 use rand::Rng;
 use std::collections::HashSet;
 use std::hash::Hash;
+use tokio::time;
+
+use ya_client::model::NodeId;
+use ya_core_model::journal;
+use ya_service_bus::{typed as bus, RpcEndpoint};
 
 use super::Matcher;
-use std::time::Instant;
+use crate::db::dao::{DemandDao, OfferDao};
+use crate::db::model::SubscriptionId;
+use crate::metric_keys;
+use std::time::{Duration, Instant};
+
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Infinitely record number of our active Offers in the journal,
+/// which computes Provider uptime from these samples.
+pub(super) async fn sample_availability(matcher: Matcher) {
+    let sample_interval = matcher.config.availability.sample_interval;
+    if sample_interval.as_secs() == 0 {
+        return;
+    }
+
+    let mut interval = time::interval(sample_interval);
+    loop {
+        interval.tick().await;
+        let offers = match matcher.get_our_active_offer_ids().await {
+            Ok(ids) => ids.len() as u32,
+            Err(e) => {
+                log::warn!("Failed to sample our Offers availability. Error: {}", e);
+                continue;
+            }
+        };
+
+        let msg = journal::RecordAvailability {
+            offers,
+            interval_secs: sample_interval.as_secs() as u32,
+        };
+        match bus::service(journal::BUS_ID).send(msg).await {
+            Ok(Ok(())) => (),
+            Ok(Err(e)) => log::debug!("Failed to record Offers availability: {}", e),
+            Err(e) => log::debug!("Failed to record Offers availability: {}", e),
+        }
+    }
+}
+
+/// Infinitely check expiration of our Offers and Demands. Demands are prolonged
+/// if configured, for other expiring subscriptions a warning is recorded
+/// in the journal once.
+pub(super) async fn keep_alive_subscriptions(matcher: Matcher) {
+    let expiry_warning = matcher.config.subscription.expiry_warning;
+    if expiry_warning.as_secs() == 0 {
+        return;
+    }
+
+    let mut warned = HashSet::new();
+    let mut interval = time::interval(KEEP_ALIVE_INTERVAL.min(expiry_warning / 2));
+    loop {
+        interval.tick().await;
+        if let Err(e) = keep_alive_once(&matcher, &mut warned).await {
+            log::warn!(
+                "Failed to check expiration of our subscriptions. Error: {}",
+                e
+            );
+        }
+    }
+}
+
+async fn keep_alive_once(
+    matcher: &Matcher,
+    warned: &mut HashSet<SubscriptionId>,
+) -> anyhow::Result<()> {
+    let config = &matcher.config.subscription;
+    let now = Utc::now().naive_utc();
+    let warning_ts = now + chrono::Duration::from_std(config.expiry_warning)?;
+    let node_ids = matcher.identity.list().await?;
+
+    let mut expiring = matcher
+        .store
+        .db
+        .as_dao::<OfferDao>()
+        .get_offers(None, Some(node_ids.clone()), None, now)
+        .await?
+        .into_iter()
+        .filter(|offer| offer.expiration_ts < warning_ts)
+        .map(|offer| (offer.id, offer.node_id, offer.expiration_ts, "offer"))
+        .collect::<Vec<_>>();
+
+    let demands = matcher
+        .store
+        .db
+        .as_dao::<DemandDao>()
+        .get_demands(None, None, now)
+        .await?
+        .into_iter()
+        .filter(|demand| node_ids.contains(&demand.node_id));
+    for demand in demands {
+        let renewal_ts = warning_ts + chrono::Duration::from_std(jitter(config.renewal_jitter))?;
+        if config.renew_demands && demand.expiration_ts < renewal_ts {
+            let expiration_ts = now + config.default_ttl;
+            match matcher
+                .store
+                .db
+                .as_dao::<DemandDao>()
+                .prolong(&demand.id, expiration_ts)
+                .await
+            {
+                Ok(true) => {
+                    log::info!("Demand [{}] renewed until {}.", demand.id, expiration_ts);
+                    record_subscription_event(
+                        "subscription-renewed",
+                        &demand.id,
+                        demand.node_id,
+                        "demand",
+                        expiration_ts,
+                    )
+                    .await;
+                    continue;
+                }
+                // Unsubscribed meanwhile.
+                Ok(false) => continue,
+                Err(e) => {
+                    log::warn!("Failed to renew Demand [{}]. Error: {}", demand.id, e);
+                    record_subscription_event(
+                        "subscription-renewal-failed",
+                        &demand.id,
+                        demand.node_id,
+                        "demand",
+                        demand.expiration_ts,
+                    )
+                    .await;
+                }
+            }
+        }
+        if demand.expiration_ts < warning_ts {
+            expiring.push((demand.id, demand.node_id, demand.expiration_ts, "demand"));
+        }
+    }
+
+    warned.retain(|id| expiring.iter().any(|(expiring_id, ..)| expiring_id == id));
+    for (id, node_id, expiration_ts, kind) in expiring {
+        if warned.insert(id.clone()) {
+            log::warn!(
+                "Our {} [{}] expires at {} and won't produce Proposals afterwards.",
+                kind,
+                id,
+                expiration_ts
+            );
+            record_subscription_event("subscription-expiring", &id, node_id, kind, expiration_ts)
+                .await;
+        }
+    }
+    Ok(())
+}
+
+async fn record_subscription_event(
+    name: &str,
+    id: &SubscriptionId,
+    node_id: NodeId,
+    kind: &str,
+    expiration_ts: NaiveDateTime,
+) {
+    journal::Event::new(journal::Category::Market, name)
+        .subject(id)
+        .node_id(node_id)
+        .details(serde_json::json!({
+            "subscriptionType": kind,
+            "expirationTs": expiration_ts,
+        }))
+        .record()
+        .await;
+}
 
 /// Infinitely broadcast set of Offers according to the configured interval.
 /// The set always includes our own Offers plus some random subset.
@@ -50,8 +219,8 @@ pub async fn bcast_offers_once(matcher: Matcher) {
         matcher.discovery.bcast_offers(offers_to_broadcast).await?;
 
         let end = Instant::now();
-        counter!("market.offers.broadcasts", 1);
-        timing!("market.offers.broadcasts.time", start, end);
+        counter!(metric_keys::OFFERS_BROADCASTS, 1);
+        timing!(metric_keys::OFFERS_BROADCASTS_TIME, start, end);
 
         Result::<(), anyhow::Error>::Ok(())
     }
@@ -99,8 +268,8 @@ pub(super) async fn bcast_unsubscribes(matcher: Matcher) {
                 .await?;
 
             let end = Instant::now();
-            counter!("market.offers.unsubscribes.broadcasts", 1);
-            timing!("market.offers.unsubscribes.broadcasts.time", start, end);
+            counter!(metric_keys::OFFERS_UNSUBSCRIBES_BROADCASTS, 1);
+            timing!(metric_keys::OFFERS_UNSUBSCRIBES_BROADCASTS_TIME, start, end);
 
             Result::<(), anyhow::Error>::Ok(())
         }
@@ -138,6 +307,11 @@ fn randomize_ids<T: Eq + Hash + Clone>(
     randomized_ids
 }
 
+/// Random duration between zero and `max`.
+fn jitter(max: Duration) -> Duration {
+    max.mul_f64(rand::thread_rng().gen::<f64>())
+}
+
 fn randomize_interval(mean_interval: std::time::Duration) -> std::time::Duration {
     let mut rng = rand::thread_rng();
     // randomize interval between 0.5 and 1.5 times the mean_interval
@@ -155,6 +329,14 @@ mod test {
     use crate::db::model::SubscriptionId;
     use std::str::FromStr;
 
+    #[test]
+    fn test_jitter() {
+        assert_eq!(jitter(Duration::from_secs(0)), Duration::from_secs(0));
+        for _ in 0..100 {
+            assert!(jitter(Duration::from_secs(60)) <= Duration::from_secs(60));
+        }
+    }
+
     #[test]
     fn test_randomize_cap_0() {
         let offers = randomize_ids(vec![1], vec![], 0);
@@ -3,6 +3,8 @@ use crate::db::DbError;
 use crate::identity::IdentityError;
 use crate::protocol::discovery::error::DiscoveryInitError;
 
+pub use super::schema::PropertySchemaError;
+
 #[derive(thiserror::Error, Debug)]
 pub enum DemandError {
     #[error("Failed to get Demands. Error: {0}.")]
@@ -15,6 +17,8 @@ pub enum DemandError {
     Remove(DbError, SubscriptionId),
     #[error("Demand [{0}] not found.")]
     NotFound(SubscriptionId),
+    #[error("Invalid Demand properties. {0}")]
+    InvalidProperties(#[from] PropertySchemaError),
     #[error(transparent)]
     JsonObjectExpected(#[from] serde_json::error::Error),
 }
@@ -27,6 +31,14 @@ pub enum QueryOffersError {
     IdentityError(#[from] IdentityError),
 }
 
+#[derive(thiserror::Error, Debug)]
+pub enum ScanError {
+    #[error("Invalid scan constraints. {0}")]
+    InvalidConstraints(String),
+    #[error(transparent)]
+    QueryOffers(#[from] QueryOffersError),
+}
+
 #[derive(thiserror::Error, Debug)]
 pub enum QueryDemandsError {
     #[error("Failed to get Demands. Error: {0}.")]
@@ -57,6 +69,8 @@ pub enum SaveOfferError {
     Expired(SubscriptionId),
     #[error(transparent)]
     SubscriptionValidation(#[from] SubscriptionValidationError),
+    #[error("Invalid Offer properties. {0}")]
+    InvalidProperties(#[from] PropertySchemaError),
     #[error(transparent)]
     JsonObjectExpected(#[from] serde_json::error::Error),
     #[error("Wrong Offer [{id}] state {state:?} after inserted: {inserted}.")]
@@ -107,6 +121,8 @@ pub enum MatcherError {
     SaveOffer(#[from] SaveOfferError),
     #[error(transparent)]
     ModifyOffer(#[from] ModifyOfferError),
+    #[error(transparent)]
+    Scan(#[from] ScanError),
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -10,6 +10,7 @@ use crate::protocol::discovery::{
 };
 
 use super::{resolver::Resolver, store::SubscriptionStore};
+use crate::metric_keys;
 
 /// Returns only those of input offers ids, that were not yet known.
 pub(super) async fn filter_out_known_offer_ids(
@@ -52,7 +53,7 @@ pub(super) async fn receive_remote_offers(
         .collect::<Vec<SubscriptionId>>()
         .await;
 
-    counter!("market.offers.incoming", added_offers_ids.len() as u64);
+    counter!(metric_keys::OFFERS_INCOMING, added_offers_ids.len() as u64);
     log::trace!(
         "Received {} new Offers from [{}]",
         added_offers_ids.len(),
@@ -67,7 +68,7 @@ pub(super) async fn get_local_offers(
     msg: RetrieveOffers,
 ) -> Result<Vec<Offer>, DiscoveryRemoteError> {
     value!(
-        "market.offers.retrieved_by_remotes",
+        metric_keys::OFFERS_RETRIEVED_BY_REMOTES,
         msg.offer_ids.len() as u64
     );
 
@@ -125,7 +126,7 @@ pub(super) async fn receive_remote_offer_unsubscribes(
 
     if !new_unsubscribes.is_empty() {
         counter!(
-            "market.offers.unsubscribes.incoming",
+            metric_keys::OFFERS_UNSUBSCRIBES_INCOMING,
             new_unsubscribes.len() as u64
         );
         log::trace!(
@@ -0,0 +1,176 @@
+//! Matching known Offers against constraints without creating Demand subscription.
+use serde::{Deserialize, Serialize};
+
+use ya_client::model::market::Offer as ClientOffer;
+use ya_market_resolver::{match_weak, MatchResult, PreparedDemand, PreparedOffer};
+
+use crate::matcher::error::ScanError;
+
+/// Upper bound for number of Offers returned in single page.
+pub const MAX_SCAN_LIMIT: usize = 1000;
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanRequest {
+    /// Constraints, that Offers must fulfill.
+    pub constraints: String,
+    /// Properties of requestor, that will be matched against Offers constraints.
+    /// If not set, Offers constraints are ignored.
+    #[serde(default)]
+    pub properties: Option<serde_json::Value>,
+    /// Return only Offer properties starting with one of these prefixes.
+    #[serde(default)]
+    pub include_properties: Option<Vec<String>>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanResult {
+    pub offers: Vec<ClientOffer>,
+    /// Number of all matching Offers.
+    pub total: usize,
+    /// Offset of next page. None if this is the last page.
+    pub next_offset: Option<usize>,
+}
+
+pub fn scan_offers(
+    offers: Vec<ClientOffer>,
+    request: &ScanRequest,
+    offset: usize,
+    limit: usize,
+) -> Result<ScanResult, ScanError> {
+    let properties = request
+        .properties
+        .clone()
+        .unwrap_or_else(|| serde_json::json!({}));
+    let demand = ya_market_resolver::Demand::from(&properties.to_string(), &request.constraints)
+        .map_err(|e| ScanError::InvalidConstraints(e.to_string()))?;
+    let demand =
+        PreparedDemand::from(&demand).map_err(|e| ScanError::InvalidConstraints(e.to_string()))?;
+
+    let mut matching = offers
+        .into_iter()
+        .filter(|offer| {
+            // Without requestor properties we can't evaluate Offer constraints.
+            let constraints = match request.properties {
+                Some(_) => offer.constraints.as_str(),
+                None => "",
+            };
+            matches(&demand, offer, constraints)
+        })
+        .collect::<Vec<_>>();
+    matching.sort_by(|a, b| (a.timestamp, &a.offer_id).cmp(&(b.timestamp, &b.offer_id)));
+
+    let total = matching.len();
+    let limit = limit.min(MAX_SCAN_LIMIT);
+    let offers = matching
+        .into_iter()
+        .skip(offset)
+        .take(limit)
+        .map(|mut offer| {
+            if let Some(prefixes) = &request.include_properties {
+                offer.properties = filter_properties(offer.properties, prefixes);
+            }
+            offer
+        })
+        .collect::<Vec<_>>();
+    let next_offset = Some(offset + offers.len()).filter(|next| *next < total);
+
+    Ok(ScanResult {
+        offers,
+        total,
+        next_offset,
+    })
+}
+
+fn matches(demand: &PreparedDemand, offer: &ClientOffer, constraints: &str) -> bool {
+    let offer = match ya_market_resolver::Offer::from(&offer.properties.to_string(), constraints) {
+        Ok(offer) => offer,
+        Err(e) => {
+            log::debug!("Skipping Offer [{}] in scan: {}", offer.offer_id, e);
+            return false;
+        }
+    };
+    let offer = match PreparedOffer::from(&offer) {
+        Ok(offer) => offer,
+        Err(_) => return false,
+    };
+    matches!(match_weak(demand, &offer), Ok(MatchResult::True))
+}
+
+fn filter_properties(properties: serde_json::Value, prefixes: &[String]) -> serde_json::Value {
+    let flat = ya_agreement_utils::agreement::flatten(properties);
+    serde_json::Value::Object(
+        flat.into_iter()
+            .filter(|(name, _)| prefixes.iter().any(|prefix| name.starts_with(prefix)))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn offer(id: &str, mem: f64) -> ClientOffer {
+        ClientOffer {
+            offer_id: id.to_string(),
+            provider_id: "0xbabe000000000000000000000000000000000000"
+                .parse()
+                .unwrap(),
+            constraints: "(golem.srv.comp.expiration>0)".to_string(),
+            properties: serde_json::json!({
+                "golem.inf.mem.gib": mem,
+                "golem.node.id.name": id,
+            }),
+            timestamp: Utc::now(),
+        }
+    }
+
+    fn request(constraints: &str) -> ScanRequest {
+        ScanRequest {
+            constraints: constraints.to_string(),
+            properties: None,
+            include_properties: None,
+        }
+    }
+
+    #[test]
+    fn test_scan_filters_and_paginates() {
+        let offers = vec![offer("a", 1.0), offer("b", 4.0), offer("c", 8.0)];
+        let result = scan_offers(offers, &request("(golem.inf.mem.gib>=2)"), 0, 1).unwrap();
+
+        assert_eq!(result.total, 2);
+        assert_eq!(result.offers.len(), 1);
+        assert_eq!(result.next_offset, Some(1));
+    }
+
+    #[test]
+    fn test_scan_respects_offer_constraints_with_properties() {
+        let offers = vec![offer("a", 1.0)];
+        let mut request = request("(golem.inf.mem.gib>=1)");
+        request.properties = Some(serde_json::json!({"golem.srv.comp.expiration": 0}));
+
+        let result = scan_offers(offers, &request, 0, 10).unwrap();
+        assert_eq!(result.total, 0);
+        assert_eq!(result.next_offset, None);
+    }
+
+    #[test]
+    fn test_scan_include_properties() {
+        let mut request = request("");
+        request.include_properties = Some(vec!["golem.inf".to_string()]);
+
+        let result = scan_offers(vec![offer("a", 1.0)], &request, 0, 10).unwrap();
+        assert_eq!(
+            result.offers[0].properties,
+            serde_json::json!({"golem.inf.mem.gib": 1.0})
+        );
+    }
+
+    #[test]
+    fn test_scan_invalid_constraints() {
+        let result = scan_offers(vec![], &request("(golem.inf.mem.gib>="), 0, 10);
+        assert!(matches!(result, Err(ScanError::InvalidConstraints(_))));
+    }
+}
@@ -0,0 +1,192 @@
+//! Validation of well-known `golem.*` properties of published Offers and Demands.
+//!
+//! Constraints referencing a property with wrong type or misspelled name never match,
+//! without any hint for the user, so we reject such subscriptions early.
+use serde_json::Value;
+
+use ya_agreement_utils::agreement::flatten;
+
+#[derive(Clone, Copy, Debug)]
+enum PropertyType {
+    String,
+    Bool,
+    /// Non-negative integer.
+    Count,
+    /// Non-negative number.
+    Amount,
+    StringList,
+    AmountList,
+    OneOf(&'static [&'static str]),
+}
+
+impl PropertyType {
+    fn check(&self, value: &Value) -> bool {
+        let non_negative = |v: &Value| v.as_f64().map(|n| n >= 0.0).unwrap_or(false);
+        match self {
+            PropertyType::String => value.is_string(),
+            PropertyType::Bool => value.is_boolean(),
+            PropertyType::Count => value.as_u64().is_some(),
+            PropertyType::Amount => non_negative(value),
+            PropertyType::StringList => value
+                .as_array()
+                .map(|list| list.iter().all(Value::is_string))
+                .unwrap_or(false),
+            PropertyType::AmountList => value
+                .as_array()
+                .map(|list| list.iter().all(non_negative))
+                .unwrap_or(false),
+            PropertyType::OneOf(allowed) => value
+                .as_str()
+                .map(|v| allowed.contains(&v))
+                .unwrap_or(false),
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            PropertyType::String => "string".to_string(),
+            PropertyType::Bool => "boolean".to_string(),
+            PropertyType::Count => "non-negative integer".to_string(),
+            PropertyType::Amount => "non-negative number".to_string(),
+            PropertyType::StringList => "list of strings".to_string(),
+            PropertyType::AmountList => "list of non-negative numbers".to_string(),
+            PropertyType::OneOf(allowed) => format!("one of [{}]", allowed.join(", ")),
+        }
+    }
+}
+
+const WELL_KNOWN: &[(&str, PropertyType)] = &[
+    (
+        "golem.activity.caps.transfer.protocol",
+        PropertyType::StringList,
+    ),
+    ("golem.com.pricing.model", PropertyType::OneOf(&["linear"])),
+    (
+        "golem.com.pricing.model.linear.coeffs",
+        PropertyType::AmountList,
+    ),
+    ("golem.com.scheme", PropertyType::OneOf(&["payu"])),
+    ("golem.com.usage.vector", PropertyType::StringList),
+    ("golem.inf.cpu.architecture", PropertyType::String),
+    ("golem.inf.cpu.brand", PropertyType::String),
+    ("golem.inf.cpu.capabilities", PropertyType::StringList),
+    ("golem.inf.cpu.cores", PropertyType::Count),
+    ("golem.inf.cpu.model", PropertyType::String),
+    ("golem.inf.cpu.threads", PropertyType::Count),
+    ("golem.inf.cpu.vendor", PropertyType::String),
+    ("golem.inf.mem.gib", PropertyType::Amount),
+    ("golem.inf.storage.gib", PropertyType::Amount),
+    ("golem.node.debug.subnet", PropertyType::String),
+    ("golem.node.id.name", PropertyType::String),
+    ("golem.node.net.is-public", PropertyType::Bool),
+    ("golem.runtime.capabilities", PropertyType::StringList),
+    ("golem.runtime.name", PropertyType::String),
+    ("golem.runtime.version", PropertyType::String),
+    ("golem.srv.caps.multi-activity", PropertyType::Bool),
+    ("golem.srv.comp.expiration", PropertyType::Count),
+    ("golem.srv.comp.task_package", PropertyType::String),
+];
+
+/// Namespaces fully described by `WELL_KNOWN` table. Any other property
+/// inside them is most probably a typo.
+const CLOSED_NAMESPACES: &[&str] = &["golem.inf.cpu.", "golem.inf.mem.", "golem.inf.storage."];
+
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum PropertySchemaError {
+    #[error("Property [{property}] should be {expected}, but got: {value}.")]
+    InvalidValue {
+        property: String,
+        expected: String,
+        value: Value,
+    },
+    #[error("Unknown property [{property}] in well-known namespace [{namespace}*].")]
+    Unknown { property: String, namespace: String },
+}
+
+/// Checks well-known properties of Offer or Demand before publishing.
+pub fn validate_properties(properties: &Value) -> Result<(), PropertySchemaError> {
+    for (property, value) in flatten(properties.clone()) {
+        match WELL_KNOWN.iter().find(|(name, _)| *name == property) {
+            Some((_, expected)) if !expected.check(&value) => {
+                return Err(PropertySchemaError::InvalidValue {
+                    property,
+                    expected: expected.describe(),
+                    value,
+                })
+            }
+            Some(_) => continue,
+            None => (),
+        }
+
+        if let Some(namespace) = CLOSED_NAMESPACES
+            .iter()
+            .find(|namespace| property.starts_with(*namespace))
+        {
+            return Err(PropertySchemaError::Unknown {
+                property,
+                namespace: namespace.to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_valid_properties() {
+        let properties = json!({
+            "golem": {
+                "inf.cpu.cores": 4,
+                "inf.mem.gib": 0.5,
+                "node.id.name": "provider",
+                "com": {
+                    "pricing": {
+                        "model": { "@tag": "linear", "linear": { "coeffs": [0, 0.01, 0.0016] } }
+                    },
+                    "usage.vector": ["golem.usage.duration_sec", "golem.usage.cpu_sec"]
+                },
+                "srv.comp.wasm.task_package": "custom-property"
+            },
+            "custom.property": -1
+        });
+        assert_eq!(validate_properties(&properties), Ok(()));
+    }
+
+    #[test]
+    fn test_invalid_type() {
+        let properties = json!({ "golem.inf.mem.gib": "2" });
+        assert_eq!(
+            validate_properties(&properties),
+            Err(PropertySchemaError::InvalidValue {
+                property: "golem.inf.mem.gib".to_string(),
+                expected: "non-negative number".to_string(),
+                value: json!("2"),
+            })
+        );
+    }
+
+    #[test]
+    fn test_not_allowed_value() {
+        let properties = json!({ "golem.com.pricing.model": "lineer" });
+        assert!(matches!(
+            validate_properties(&properties),
+            Err(PropertySchemaError::InvalidValue { property, .. }) if property == "golem.com.pricing.model"
+        ));
+    }
+
+    #[test]
+    fn test_typo_in_closed_namespace() {
+        let properties = json!({ "golem.inf.cpu.coers": 4 });
+        assert_eq!(
+            validate_properties(&properties),
+            Err(PropertySchemaError::Unknown {
+                property: "golem.inf.cpu.coers".to_string(),
+                namespace: "golem.inf.cpu.".to_string(),
+            })
+        );
+    }
+}
@@ -14,6 +14,7 @@ use crate::matcher::error::{
     DemandError, ModifyOfferError, QueryDemandsError, QueryOfferError, QueryOffersError,
     SaveOfferError,
 };
+use crate::matcher::schema::validate_properties;
 
 #[derive(Clone)]
 pub struct SubscriptionStore {
@@ -32,6 +33,8 @@ impl SubscriptionStore {
         id: &Identity,
         offer: &NewOffer,
     ) -> Result<Offer, SaveOfferError> {
+        validate_properties(&offer.properties)?;
+
         let creation_ts = Utc::now().naive_utc();
         // TODO: provider agent should set expiration.
         let expiration_ts = creation_ts + self.config.subscription.default_ttl;
@@ -235,6 +238,8 @@ impl SubscriptionStore {
         id: &Identity,
         demand: &NewDemand,
     ) -> Result<Demand, DemandError> {
+        validate_properties(&demand.properties)?;
+
         let creation_ts = Utc::now().naive_utc();
         // TODO: requestor agent should set expiration.
         let expiration_ts = creation_ts + self.config.subscription.default_ttl;
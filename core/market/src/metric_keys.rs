@@ -0,0 +1,129 @@
+//! Names of the metrics reported by the Market service.
+
+use metrics::counter;
+
+pub(crate) const AGREEMENTS_EVENTS_QUERIED: &str = "market.agreements.events.queried";
+pub(crate) const AGREEMENTS_PROVIDER_APPROVED: &str = "market.agreements.provider.approved";
+pub(crate) const AGREEMENTS_PROVIDER_APPROVING: &str = "market.agreements.provider.approving";
+pub(crate) const AGREEMENTS_PROVIDER_CANCELLED: &str = "market.agreements.provider.cancelled";
+pub(crate) const AGREEMENTS_PROVIDER_COMMITTING: &str = "market.agreements.provider.committing";
+pub(crate) const AGREEMENTS_PROVIDER_PROPOSED: &str = "market.agreements.provider.proposed";
+pub(crate) const AGREEMENTS_PROVIDER_REJECTED: &str = "market.agreements.provider.rejected";
+pub(crate) const AGREEMENTS_PROVIDER_TERMINATED: &str = "market.agreements.provider.terminated";
+pub(crate) const AGREEMENTS_PROVIDER_TERMINATED_REASON: &str =
+    "market.agreements.provider.terminated.reason";
+pub(crate) const AGREEMENTS_REQUESTOR_APPROVED: &str = "market.agreements.requestor.approved";
+pub(crate) const AGREEMENTS_REQUESTOR_CANCELLED: &str = "market.agreements.requestor.cancelled";
+pub(crate) const AGREEMENTS_REQUESTOR_COMMITTING: &str = "market.agreements.requestor.committing";
+pub(crate) const AGREEMENTS_REQUESTOR_CONFIRMED: &str = "market.agreements.requestor.confirmed";
+pub(crate) const AGREEMENTS_REQUESTOR_CREATED: &str = "market.agreements.requestor.created";
+pub(crate) const AGREEMENTS_REQUESTOR_REJECTED: &str = "market.agreements.requestor.rejected";
+pub(crate) const AGREEMENTS_REQUESTOR_TERMINATED: &str = "market.agreements.requestor.terminated";
+pub(crate) const AGREEMENTS_REQUESTOR_TERMINATED_REASON: &str =
+    "market.agreements.requestor.terminated.reason";
+pub(crate) const DEMANDS_EXPIRED: &str = "market.demands.expired";
+pub(crate) const DEMANDS_SUBSCRIBED: &str = "market.demands.subscribed";
+pub(crate) const DEMANDS_UNSUBSCRIBED: &str = "market.demands.unsubscribed";
+pub(crate) const EVENTS_PROVIDER_QUERIED: &str = "market.events.provider.queried";
+pub(crate) const EVENTS_REQUESTOR_QUERIED: &str = "market.events.requestor.queried";
+pub(crate) const OFFERS_BROADCASTS: &str = "market.offers.broadcasts";
+pub(crate) const OFFERS_BROADCASTS_LEN: &str = "market.offers.broadcasts.len";
+pub(crate) const OFFERS_BROADCASTS_NET: &str = "market.offers.broadcasts.net";
+pub(crate) const OFFERS_BROADCASTS_NET_ERRORS: &str = "market.offers.broadcasts.net_errors";
+pub(crate) const OFFERS_BROADCASTS_SKIP: &str = "market.offers.broadcasts.skip";
+pub(crate) const OFFERS_BROADCASTS_TIME: &str = "market.offers.broadcasts.time";
+pub(crate) const OFFERS_EXPIRED: &str = "market.offers.expired";
+pub(crate) const OFFERS_INCOMING: &str = "market.offers.incoming";
+pub(crate) const OFFERS_INCOMING_GET_REMOTE_TIME: &str = "market.offers.incoming.get_remote.time";
+pub(crate) const OFFERS_INCOMING_TIME: &str = "market.offers.incoming.time";
+pub(crate) const OFFERS_RETRIEVED_BY_REMOTES: &str = "market.offers.retrieved_by_remotes";
+pub(crate) const OFFERS_SUBSCRIBED: &str = "market.offers.subscribed";
+pub(crate) const OFFERS_UNSUBSCRIBED: &str = "market.offers.unsubscribed";
+pub(crate) const OFFERS_UNSUBSCRIBES_BROADCASTS: &str = "market.offers.unsubscribes.broadcasts";
+pub(crate) const OFFERS_UNSUBSCRIBES_BROADCASTS_LEN: &str =
+    "market.offers.unsubscribes.broadcasts.len";
+pub(crate) const OFFERS_UNSUBSCRIBES_BROADCASTS_NET: &str =
+    "market.offers.unsubscribes.broadcasts.net";
+pub(crate) const OFFERS_UNSUBSCRIBES_BROADCASTS_NET_ERRORS: &str =
+    "market.offers.unsubscribes.broadcasts.net_errors";
+pub(crate) const OFFERS_UNSUBSCRIBES_BROADCASTS_TIME: &str =
+    "market.offers.unsubscribes.broadcasts.time";
+pub(crate) const OFFERS_UNSUBSCRIBES_INCOMING: &str = "market.offers.unsubscribes.incoming";
+pub(crate) const OFFERS_UNSUBSCRIBES_INCOMING_TIME: &str =
+    "market.offers.unsubscribes.incoming.time";
+pub(crate) const PROPOSALS_LOW_REPUTATION: &str = "market.proposals.low-reputation";
+pub(crate) const PROPOSALS_PROVIDER_COUNTERED: &str = "market.proposals.provider.countered";
+pub(crate) const PROPOSALS_PROVIDER_INIT_NEGOTIATION: &str =
+    "market.proposals.provider.init-negotiation";
+pub(crate) const PROPOSALS_PROVIDER_RECEIVED: &str = "market.proposals.provider.received";
+pub(crate) const PROPOSALS_PROVIDER_REJECTED_BY_THEM: &str =
+    "market.proposals.provider.rejected.by-them";
+pub(crate) const PROPOSALS_PROVIDER_REJECTED_BY_US: &str =
+    "market.proposals.provider.rejected.by-us";
+pub(crate) const PROPOSALS_PROVIDER_REJECTED_INITIAL: &str =
+    "market.proposals.provider.rejected.initial";
+pub(crate) const PROPOSALS_REQUESTOR_COUNTERED: &str = "market.proposals.requestor.countered";
+pub(crate) const PROPOSALS_REQUESTOR_GENERATED: &str = "market.proposals.requestor.generated";
+pub(crate) const PROPOSALS_REQUESTOR_RECEIVED: &str = "market.proposals.requestor.received";
+pub(crate) const PROPOSALS_REQUESTOR_REJECTED_BY_THEM: &str =
+    "market.proposals.requestor.rejected.by-them";
+pub(crate) const PROPOSALS_REQUESTOR_REJECTED_BY_US: &str =
+    "market.proposals.requestor.rejected.by-us";
+pub(crate) const PROPOSALS_REQUESTOR_REJECTED_INITIAL: &str =
+    "market.proposals.requestor.rejected.initial";
+pub(crate) const PROPOSALS_SELF_REACTION_ATTEMPT: &str = "market.proposals.self-reaction-attempt";
+
+/// Initializes counters to 0 value. Otherwise they won't appear on metrics endpoint
+/// until first change to value will be made.
+pub(crate) fn init() {
+    counter!(AGREEMENTS_EVENTS_QUERIED, 0);
+    counter!(AGREEMENTS_PROVIDER_APPROVED, 0);
+    counter!(AGREEMENTS_PROVIDER_APPROVING, 0);
+    counter!(AGREEMENTS_PROVIDER_CANCELLED, 0);
+    counter!(AGREEMENTS_PROVIDER_COMMITTING, 0);
+    counter!(AGREEMENTS_PROVIDER_PROPOSED, 0);
+    counter!(AGREEMENTS_PROVIDER_REJECTED, 0);
+    counter!(AGREEMENTS_PROVIDER_TERMINATED, 0);
+    counter!(AGREEMENTS_REQUESTOR_APPROVED, 0);
+    counter!(AGREEMENTS_REQUESTOR_CANCELLED, 0);
+    counter!(AGREEMENTS_REQUESTOR_COMMITTING, 0);
+    counter!(AGREEMENTS_REQUESTOR_CONFIRMED, 0);
+    counter!(AGREEMENTS_REQUESTOR_CREATED, 0);
+    counter!(AGREEMENTS_REQUESTOR_REJECTED, 0);
+    counter!(AGREEMENTS_REQUESTOR_TERMINATED, 0);
+    counter!(DEMANDS_EXPIRED, 0);
+    counter!(DEMANDS_SUBSCRIBED, 0);
+    counter!(DEMANDS_UNSUBSCRIBED, 0);
+    counter!(EVENTS_PROVIDER_QUERIED, 0);
+    counter!(EVENTS_REQUESTOR_QUERIED, 0);
+    counter!(OFFERS_BROADCASTS, 0);
+    counter!(OFFERS_BROADCASTS_NET, 0);
+    counter!(OFFERS_BROADCASTS_NET_ERRORS, 0);
+    counter!(OFFERS_BROADCASTS_SKIP, 0);
+    counter!(OFFERS_EXPIRED, 0);
+    counter!(OFFERS_INCOMING, 0);
+    counter!(OFFERS_SUBSCRIBED, 0);
+    counter!(OFFERS_UNSUBSCRIBED, 0);
+    counter!(OFFERS_UNSUBSCRIBES_BROADCASTS, 0);
+    counter!(OFFERS_UNSUBSCRIBES_BROADCASTS_NET, 0);
+    counter!(OFFERS_UNSUBSCRIBES_BROADCASTS_NET_ERRORS, 0);
+    counter!(OFFERS_UNSUBSCRIBES_INCOMING, 0);
+    counter!(PROPOSALS_LOW_REPUTATION, 0);
+    counter!(PROPOSALS_PROVIDER_COUNTERED, 0);
+    counter!(PROPOSALS_PROVIDER_INIT_NEGOTIATION, 0);
+    counter!(PROPOSALS_PROVIDER_RECEIVED, 0);
+    counter!(PROPOSALS_PROVIDER_REJECTED_BY_THEM, 0);
+    counter!(PROPOSALS_PROVIDER_REJECTED_BY_US, 0);
+    counter!(PROPOSALS_PROVIDER_REJECTED_INITIAL, 0);
+    counter!(PROPOSALS_REQUESTOR_COUNTERED, 0);
+    counter!(PROPOSALS_REQUESTOR_GENERATED, 0);
+    counter!(PROPOSALS_REQUESTOR_RECEIVED, 0);
+    counter!(PROPOSALS_REQUESTOR_REJECTED_BY_THEM, 0);
+    counter!(PROPOSALS_REQUESTOR_REJECTED_BY_US, 0);
+    counter!(PROPOSALS_REQUESTOR_REJECTED_INITIAL, 0);
+    counter!(PROPOSALS_SELF_REACTION_ATTEMPT, 0);
+    counter!(AGREEMENTS_PROVIDER_TERMINATED_REASON, 0, "reason" => "NotSpecified");
+    counter!(AGREEMENTS_PROVIDER_TERMINATED_REASON, 0, "reason" => "Success");
+    counter!(AGREEMENTS_REQUESTOR_TERMINATED_REASON, 0, "reason" => "NotSpecified");
+    counter!(AGREEMENTS_REQUESTOR_TERMINATED_REASON, 0, "reason" => "Success");
+}
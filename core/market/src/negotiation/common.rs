@@ -4,8 +4,10 @@ use std::str::FromStr;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+use ya_agreement_utils::TerminationReason;
 use ya_client::model::market::{proposal::Proposal as ClientProposal, reason::Reason, NewProposal};
 use ya_client::model::NodeId;
+use ya_core_model::journal;
 use ya_market_resolver::{match_demand_offer, Match};
 use ya_service_api_web::middleware::Identity;
 
@@ -27,6 +29,7 @@ use crate::matcher::{
     store::SubscriptionStore,
     RawProposal,
 };
+use crate::metric_keys;
 use crate::negotiation::error::RegenerateProposalError;
 use crate::negotiation::error::{NegotiationError, ProposalValidationError};
 use crate::negotiation::{
@@ -46,6 +49,7 @@ use crate::protocol::negotiation::{
     },
     messages::{AgreementTerminated, ProposalReceived},
 };
+use crate::reputation;
 use crate::utils::display::EnableDisplay;
 use crate::utils::AgreementLock;
 
@@ -256,7 +260,7 @@ impl CommonBroker {
                 .map_err(|e| AgreementEventsError::Internal(e.to_string()))?;
 
             if !events.is_empty() {
-                counter!("market.agreements.events.queried", events.len() as u64);
+                counter!(metric_keys::AGREEMENTS_EVENTS_QUERIED, events.len() as u64);
                 return Ok(events);
             }
             // Solves panic 'supplied instant is later than self'.
@@ -380,6 +384,8 @@ impl CommonBroker {
 
         self.notify_agreement(&agreement).await;
         self.agreement_lock.clear_locks(&agreement.id).await;
+        self.record_terminated(&agreement, &reason, agreement.id.owner())
+            .await;
 
         inc_terminate_metrics(&reason, agreement.id.owner());
         log::info!(
@@ -483,6 +489,8 @@ impl CommonBroker {
 
         self.notify_agreement(&agreement).await;
         self.agreement_lock.clear_locks(&agreement_id).await;
+        self.record_terminated(&agreement, &msg.reason, caller_role)
+            .await;
 
         inc_terminate_metrics(&msg.reason, agreement.id.owner());
         log::info!(
@@ -550,6 +558,7 @@ impl CommonBroker {
 
         self.validate_proposal(&prev_proposal, &caller_id, caller_role)
             .await?;
+        self.validate_reputation(&caller_id).await?;
         validate_match(&proposal, &prev_proposal)?;
 
         self.db
@@ -587,8 +596,8 @@ impl CommonBroker {
         self.negotiation_notifier.notify(&subscription_id).await;
 
         match caller_role {
-            Owner::Requestor => counter!("market.proposals.requestor.received", 1),
-            Owner::Provider => counter!("market.proposals.provider.received", 1),
+            Owner::Requestor => counter!(metric_keys::PROPOSALS_REQUESTOR_RECEIVED, 1),
+            Owner::Provider => counter!(metric_keys::PROPOSALS_PROVIDER_RECEIVED, 1),
         };
         log::info!(
             "Received counter Proposal [{}] for Proposal [{}] from [{}].",
@@ -625,9 +634,9 @@ impl CommonBroker {
             match caller_role {
                 Owner::Provider => {
                     log::error!("Provider rejected Initial Proposal, but should not see it.");
-                    counter!("market.proposals.provider.rejected.initial", 1);
+                    counter!(metric_keys::PROPOSALS_PROVIDER_REJECTED_INITIAL, 1);
                 }
-                Owner::Requestor => counter!("market.proposals.requestor.rejected.initial", 1),
+                Owner::Requestor => counter!(metric_keys::PROPOSALS_REQUESTOR_REJECTED_INITIAL, 1),
             };
             return Ok(());
         }
@@ -664,8 +673,8 @@ impl CommonBroker {
         self.negotiation_notifier.notify(&subscription_id).await;
 
         match caller_role {
-            Owner::Provider => counter!("market.proposals.requestor.rejected.by-them", 1),
-            Owner::Requestor => counter!("market.proposals.provider.rejected.by-them", 1),
+            Owner::Provider => counter!(metric_keys::PROPOSALS_REQUESTOR_REJECTED_BY_THEM, 1),
+            Owner::Requestor => counter!(metric_keys::PROPOSALS_PROVIDER_REJECTED_BY_THEM, 1),
         };
 
         Ok(())
@@ -696,7 +705,7 @@ impl CommonBroker {
         if &proposal.issuer() == caller_id {
             let e = ProposalValidationError::OwnProposal(proposal.body.id.clone());
             log::warn!("{}", e);
-            counter!("market.proposals.self-reaction-attempt", 1);
+            counter!(metric_keys::PROPOSALS_SELF_REACTION_ATTEMPT, 1);
             Err(e)?;
         }
 
@@ -713,6 +722,63 @@ impl CommonBroker {
         Ok(())
     }
 
+    /// Rejects Proposals and matched Offers from nodes, that behaved badly
+    /// in previous Agreements.
+    pub async fn validate_reputation(
+        &self,
+        caller_id: &NodeId,
+    ) -> Result<(), ProposalValidationError> {
+        let min_score = self.config.reputation.min_score;
+        if min_score <= 0.0 {
+            return Ok(());
+        }
+
+        let reputation = reputation::get_reputation(&self.db, *caller_id)
+            .await
+            .map_err(|e| ProposalValidationError::Internal(e.to_string()))?;
+        if reputation.score < min_score {
+            log::info!(
+                "Rejecting Proposal from [{}]. Reputation score {:.2} is below {:.2}.",
+                caller_id,
+                reputation.score,
+                min_score
+            );
+            counter!(metric_keys::PROPOSALS_LOW_REPUTATION, 1);
+            Err(ProposalValidationError::LowReputation(*caller_id))?;
+        }
+        Ok(())
+    }
+
+    /// Records outcome of terminated Agreement for our counterparty according
+    /// to termination `reason` given by `terminator`.
+    pub async fn record_terminated(
+        &self,
+        agreement: &Agreement,
+        reason: &Option<Reason>,
+        terminator: Owner,
+    ) {
+        let reason =
+            TerminationReason::from_reason(reason.as_ref(), terminator == Owner::Requestor);
+        let outcome = match reputation::termination_outcome(reason) {
+            Some(outcome) => outcome,
+            None => return,
+        };
+        let counterparty = match agreement.id.owner() {
+            Owner::Provider => agreement.requestor_id,
+            Owner::Requestor => agreement.provider_id,
+        };
+        reputation::record(&self.db, counterparty, agreement.id.into_client(), outcome)
+            .await
+            .map_err(|e| {
+                log::warn!(
+                    "Failed to record Agreement [{}] outcome. {}",
+                    agreement.id,
+                    e
+                )
+            })
+            .ok();
+    }
+
     pub async fn notify_agreement(&self, agreement: &Agreement) {
         let session_notifier = &self.session_notifier;
 
@@ -726,6 +792,23 @@ impl CommonBroker {
 
         // This notifies wait_for_agreement endpoint.
         self.agreement_notifier.notify(&agreement.id).await;
+
+        let node_id = match agreement.id.owner() {
+            Owner::Provider => agreement.provider_id,
+            Owner::Requestor => agreement.requestor_id,
+        };
+        journal::Event::new(
+            journal::Category::Market,
+            format!("agreement-{}", agreement.state).to_lowercase(),
+        )
+        .subject(agreement.id.into_client())
+        .node_id(node_id)
+        .details(serde_json::json!({
+            "providerId": agreement.provider_id,
+            "requestorId": agreement.requestor_id,
+        }))
+        .record()
+        .await;
     }
 
     pub async fn generate_proposal(&self, proposal: RawProposal) -> Result<(), SaveProposalError> {
@@ -753,7 +836,7 @@ impl CommonBroker {
             .await?;
 
         // Send channel message to wake all query_events waiting for proposals.
-        counter!("market.proposals.requestor.generated", 1);
+        counter!(metric_keys::PROPOSALS_REQUESTOR_GENERATED, 1);
         notifier.notify(&subscription_id).await;
         Ok(())
     }
@@ -830,8 +913,8 @@ fn get_reason_code(reason: &Option<Reason>, key: &str) -> Option<String> {
 /// despite 'message'.
 pub fn inc_terminate_metrics(reason: &Option<Reason>, owner: Owner) {
     match owner {
-        Owner::Provider => counter!("market.agreements.provider.terminated", 1),
-        Owner::Requestor => counter!("market.agreements.requestor.terminated", 1),
+        Owner::Provider => counter!(metric_keys::AGREEMENTS_PROVIDER_TERMINATED, 1),
+        Owner::Requestor => counter!(metric_keys::AGREEMENTS_REQUESTOR_TERMINATED, 1),
     };
 
     let p_code = get_reason_code(reason, "golem.provider.code");
@@ -842,10 +925,10 @@ pub fn inc_terminate_metrics(reason: &Option<Reason>, owner: Owner) {
         .unwrap_or_else(|| "NotSpecified".to_string());
     match owner {
         Owner::Provider => {
-            counter!("market.agreements.provider.terminated.reason", 1, "reason" => reason_code)
+            counter!(metric_keys::AGREEMENTS_PROVIDER_TERMINATED_REASON, 1, "reason" => reason_code)
         }
         Owner::Requestor => {
-            counter!("market.agreements.requestor.terminated.reason", 1, "reason" => reason_code)
+            counter!(metric_keys::AGREEMENTS_REQUESTOR_TERMINATED_REASON, 1, "reason" => reason_code)
         }
     };
 }
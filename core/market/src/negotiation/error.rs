@@ -150,6 +150,8 @@ pub enum ProposalValidationError {
     OwnProposal(ProposalId),
     #[error("Unauthorized operation attempt on Proposal [{0}] from [{1}].")]
     Unauthorized(ProposalId, NodeId),
+    #[error("Reputation of node [{0}] is too low to negotiate.")]
+    LowReputation(NodeId),
     #[error("Internal error processing Proposal: {0}.")]
     Internal(String),
 }
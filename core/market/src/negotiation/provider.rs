@@ -23,6 +23,7 @@ use super::error::*;
 use super::notifier::EventNotifier;
 use crate::config::Config;
 use crate::db::dao::AgreementDaoError;
+use crate::metric_keys;
 use crate::negotiation::common::validate_transition;
 use crate::negotiation::notifier::NotifierError;
 use crate::utils::display::EnableDisplay;
@@ -89,25 +90,6 @@ impl ProviderBroker {
             },
         );
 
-        // Initialize counters to 0 value. Otherwise they won't appear on metrics endpoint
-        // until first change to value will be made.
-        counter!("market.agreements.provider.approved", 0);
-        counter!("market.agreements.provider.proposed", 0);
-        counter!("market.agreements.provider.terminated", 0);
-        counter!("market.agreements.provider.terminated.reason", 0, "reason" => "NotSpecified");
-        counter!("market.agreements.provider.terminated.reason", 0, "reason" => "Success");
-        counter!("market.agreements.provider.approving", 0);
-        counter!("market.agreements.provider.committing", 0);
-        counter!("market.agreements.provider.rejected", 0);
-        counter!("market.agreements.provider.cancelled", 0);
-        counter!("market.events.provider.queried", 0);
-        counter!("market.proposals.provider.countered", 0);
-        counter!("market.proposals.provider.init-negotiation", 0);
-        counter!("market.proposals.provider.received", 0);
-        counter!("market.proposals.provider.rejected.initial", 0);
-        counter!("market.proposals.provider.rejected.by-them", 0);
-        counter!("market.proposals.provider.rejected.by-us", 0);
-
         Ok(ProviderBroker {
             api,
             common: broker,
@@ -155,7 +137,7 @@ impl ProviderBroker {
             .await
             .map_err(|e| ProposalError::Send(prev_proposal_id.clone(), e))?;
 
-        counter!("market.proposals.provider.countered", 1);
+        counter!(metric_keys::PROPOSALS_PROVIDER_COUNTERED, 1);
         log::info!(
             "Provider {} countered Proposal [{}] with [{}]",
             id.display(),
@@ -187,7 +169,7 @@ impl ProviderBroker {
             .reject_proposal(id.identity, &proposal, reason.clone())
             .await?;
 
-        counter!("market.proposals.provider.rejected.by-us", 1);
+        counter!(metric_keys::PROPOSALS_PROVIDER_REJECTED_BY_US, 1);
         Ok(())
     }
 
@@ -214,7 +196,7 @@ impl ProviderBroker {
             .collect::<Vec<ProviderEvent>>()
             .await;
 
-        counter!("market.events.provider.queried", events.len() as u64);
+        counter!(metric_keys::EVENTS_PROVIDER_QUERIED, events.len() as u64);
         Ok(events)
     }
 
@@ -252,7 +234,7 @@ impl ProviderBroker {
                 .await
                 .map_err(|e| AgreementError::UpdateState(agreement.id.clone(), e))?;
 
-            counter!("market.agreements.provider.approving", 1);
+            counter!(metric_keys::AGREEMENTS_PROVIDER_APPROVING, 1);
             if let Some(session) = app_session_id {
                 log::info!(
                     "AppSession id [{}] set for Agreement [{}].",
@@ -296,7 +278,7 @@ impl ProviderBroker {
             }
         }
 
-        counter!("market.agreements.provider.committing", 1);
+        counter!(metric_keys::AGREEMENTS_PROVIDER_COMMITTING, 1);
         log::info!(
             "Provider {} approved Agreement [{}]. Waiting for commit from Requestor [{}].",
             id.display(),
@@ -375,7 +357,7 @@ impl ProviderBroker {
                 .map_err(|e| AgreementError::UpdateState((agreement.id).clone(), e))?
         };
 
-        counter!("market.agreements.provider.rejected", 1);
+        counter!(metric_keys::AGREEMENTS_PROVIDER_REJECTED, 1);
         log::info!(
             "Provider {} rejected Agreement [{}]. Reason: {}",
             id.display(),
@@ -457,7 +439,7 @@ async fn agreement_committed(
 
     broker.notify_agreement(&agreement).await;
 
-    counter!("market.agreements.provider.approved", 1);
+    counter!(metric_keys::AGREEMENTS_PROVIDER_APPROVED, 1);
     log::info!(
         "Agreement [{}] approved (committed) by [{}].",
         &agreement.id,
@@ -521,7 +503,7 @@ async fn initial_proposal(
         .proposal_received(msg, caller_id, Owner::Requestor)
         .await?;
 
-    counter!("market.proposals.provider.init-negotiation", 1);
+    counter!(metric_keys::PROPOSALS_PROVIDER_INIT_NEGOTIATION, 1);
     Ok(())
 }
 
@@ -604,7 +586,7 @@ async fn agreement_received(
     // Send channel message to wake all query_events waiting for proposals.
     broker.negotiation_notifier.notify(offer_id).await;
 
-    counter!("market.agreements.provider.proposed", 1);
+    counter!(metric_keys::AGREEMENTS_PROVIDER_PROPOSED, 1);
     log::info!(
         "Agreement proposal [{}] received from [{}].",
         &msg.agreement_id,
@@ -662,7 +644,7 @@ async fn agreement_cancelled(
 
     broker.notify_agreement(&agreement).await;
 
-    counter!("market.agreements.provider.cancelled", 1);
+    counter!(metric_keys::AGREEMENTS_PROVIDER_CANCELLED, 1);
     log::info!(
         "Agreement [{}] cancelled by [{}]. Reason: {}",
         &agreement.id,
@@ -23,6 +23,7 @@ use super::{common::*, error::*, notifier::NotifierError, EventNotifier};
 use crate::config::Config;
 use crate::db::dao::AgreementEventsDao;
 use crate::db::model::ProposalState;
+use crate::metric_keys;
 use crate::utils::display::EnableDisplay;
 
 #[derive(Clone, derive_more::Display, Debug, PartialEq)]
@@ -86,27 +87,6 @@ impl RequestorBroker {
             common: broker.clone(),
         };
 
-        // Initialize counters to 0 value. Otherwise they won't appear on metrics endpoint
-        // until first change to value will be made.
-        counter!("market.agreements.events.queried", 0);
-        counter!("market.agreements.requestor.approved", 0);
-        counter!("market.agreements.requestor.cancelled", 0);
-        counter!("market.agreements.requestor.confirmed", 0);
-        counter!("market.agreements.requestor.created", 0);
-        counter!("market.agreements.requestor.rejected", 0);
-        counter!("market.agreements.requestor.terminated", 0);
-        counter!("market.agreements.requestor.terminated.reason", 0, "reason" => "NotSpecified");
-        counter!("market.agreements.requestor.terminated.reason", 0, "reason" => "Success");
-        counter!("market.agreements.requestor.committing", 0);
-        counter!("market.events.requestor.queried", 0);
-        counter!("market.proposals.requestor.countered", 0);
-        counter!("market.proposals.requestor.generated", 0);
-        counter!("market.proposals.requestor.received", 0);
-        counter!("market.proposals.requestor.rejected.initial", 0);
-        counter!("market.proposals.requestor.rejected.by-them", 0);
-        counter!("market.proposals.requestor.rejected.by-us", 0);
-        counter!("market.proposals.self-reaction-attempt", 0);
-
         tokio::spawn(proposal_receiver_thread(broker, proposal_receiver));
         Ok(engine)
     }
@@ -157,7 +137,7 @@ impl RequestorBroker {
         }
         .map_err(|e| ProposalError::Send(prev_proposal_id.clone(), e))?;
 
-        counter!("market.proposals.requestor.countered", 1);
+        counter!(metric_keys::PROPOSALS_REQUESTOR_COUNTERED, 1);
         log::info!(
             "Requestor {} countered Proposal [{}] with [{}]",
             id.display(),
@@ -189,7 +169,7 @@ impl RequestorBroker {
             .reject_proposal(id.identity, &proposal, reason.clone())
             .await?;
 
-        counter!("market.proposals.requestor.rejected.by-us", 1);
+        counter!(metric_keys::PROPOSALS_REQUESTOR_REJECTED_BY_US, 1);
 
         Ok(())
     }
@@ -217,7 +197,7 @@ impl RequestorBroker {
             .collect::<Vec<RequestorEvent>>()
             .await;
 
-        counter!("market.events.requestor.queried", events.len() as u64);
+        counter!(metric_keys::EVENTS_REQUESTOR_QUERIED, events.len() as u64);
         Ok(events)
     }
 
@@ -299,7 +279,7 @@ impl RequestorBroker {
                 }
             })?;
 
-        counter!("market.agreements.requestor.created", 1);
+        counter!(metric_keys::AGREEMENTS_REQUESTOR_CREATED, 1);
         log::info!(
             "Requestor {} created Agreement [{}] from Proposal [{}].",
             id.display(),
@@ -339,7 +319,7 @@ impl RequestorBroker {
 
         self.common.notify_agreement(&agreement).await;
 
-        counter!("market.agreements.requestor.cancelled", 1);
+        counter!(metric_keys::AGREEMENTS_REQUESTOR_CANCELLED, 1);
         log::info!(
             "Provider {} cancelled Agreement [{}]. Reason: {}",
             id.display(),
@@ -441,7 +421,7 @@ impl RequestorBroker {
                 .map_err(|e| AgreementError::UpdateState(agreement_id.clone(), e))?;
         }
 
-        counter!("market.agreements.requestor.confirmed", 1);
+        counter!(metric_keys::AGREEMENTS_REQUESTOR_CONFIRMED, 1);
         log::info!(
             "Requestor {} confirmed Agreement [{}] and sent to Provider.",
             id.display(),
@@ -552,7 +532,7 @@ async fn agreement_approved(
             })?
     };
 
-    counter!("market.agreements.requestor.committing", 1);
+    counter!(metric_keys::AGREEMENTS_REQUESTOR_COMMITTING, 1);
 
     // Commit Agreement. We must spawn committing later, because we need to
     // return from this function to provider.
@@ -626,7 +606,7 @@ async fn commit_agreement(broker: CommonBroker, agreement_id: AgreementId) {
 
     broker.notify_agreement(&agreement).await;
 
-    counter!("market.agreements.requestor.approved", 1);
+    counter!(metric_keys::AGREEMENTS_REQUESTOR_APPROVED, 1);
     log::info!(
         "Agreement [{}] committed (approved) by [{}].",
         &agreement.id,
@@ -682,7 +662,7 @@ async fn agreement_rejected(
 
     broker.notify_agreement(&agreement).await;
 
-    counter!("market.agreements.requestor.rejected", 1);
+    counter!(metric_keys::AGREEMENTS_REQUESTOR_REJECTED, 1);
     log::info!(
         "Agreement [{}] rejected by [{}]. Reason: {}",
         &agreement.id,
@@ -699,6 +679,11 @@ pub async fn proposal_receiver_thread(
     while let Some(proposal) = proposal_receiver.recv().await {
         let broker = broker.clone();
         if let Err(error) = async move {
+            // Offers of nodes with low reputation don't become Proposals at all.
+            if let Err(e) = broker.validate_reputation(&proposal.offer.node_id).await {
+                log::debug!("Skipping matching Offer [{}]. {}", proposal.offer.id, e);
+                return Ok(());
+            }
             log::debug!("Got matching Offer-Demand pair; emitting as Proposal to Requestor.");
             broker.generate_proposal(proposal).await
         }
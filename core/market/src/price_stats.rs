@@ -0,0 +1,166 @@
+//! Periodic sampling of prices from Offers available on market.
+//!
+//! Statistics are meant for Provider agents to price their Offers and for Requestors
+//! to estimate budget, so we store only percentiles per runtime and usage counter.
+use chrono::Utc;
+use std::collections::HashMap;
+use tokio::time;
+
+use crate::config::PriceStatsConfig;
+use crate::db::dao::{OfferDao, PriceStatsDao};
+use crate::db::model::{Offer, PriceStats};
+use crate::db::{DbMixedExecutor, DbResult};
+
+/// Name used for constant component of linear pricing model.
+pub const FIXED_PRICE_COUNTER: &str = "fixed";
+
+const RUNTIME_PROPERTY: &str = "golem.runtime.name";
+const USAGE_VECTOR_PROPERTY: &str = "golem.com.usage.vector";
+const LINEAR_COEFFS_PROPERTY: &str = "golem.com.pricing.model.linear.coeffs";
+
+pub async fn sample_forever(db: DbMixedExecutor, config: PriceStatsConfig) {
+    let mut interval = time::interval(config.sample_interval);
+    loop {
+        interval.tick().await;
+        if let Err(e) = sample(&db).await {
+            log::warn!("Failed to sample market prices: {}", e);
+        }
+        if let Err(e) = db.as_dao::<PriceStatsDao>().clean(config.store_days).await {
+            log::error!("Market price statistics cleaner error: {}", e);
+        }
+    }
+}
+
+async fn sample(db: &DbMixedExecutor) -> DbResult<()> {
+    let now = Utc::now().naive_utc();
+    let offers = db
+        .as_dao::<OfferDao>()
+        .get_offers(None, None, None, now)
+        .await?;
+
+    let samples = compute_stats(&offers, now);
+    log::debug!(
+        "Sampled prices of {} Offers into {} statistics",
+        offers.len(),
+        samples.len()
+    );
+    if !samples.is_empty() {
+        db.as_dao::<PriceStatsDao>().insert(samples).await?;
+    }
+    Ok(())
+}
+
+fn compute_stats(offers: &[Offer], sample_ts: chrono::NaiveDateTime) -> Vec<PriceStats> {
+    let mut prices = HashMap::<(String, String), Vec<f64>>::new();
+    for (runtime, counter, price) in offers.iter().flat_map(offer_prices) {
+        prices.entry((runtime, counter)).or_default().push(price);
+    }
+
+    let mut stats = prices
+        .into_iter()
+        .map(|((runtime, usage_counter), mut prices)| {
+            prices.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+            PriceStats {
+                runtime,
+                usage_counter,
+                sample_ts,
+                offers_count: prices.len() as i32,
+                p10: percentile(&prices, 10),
+                p25: percentile(&prices, 25),
+                p50: percentile(&prices, 50),
+                p75: percentile(&prices, 75),
+                p90: percentile(&prices, 90),
+            }
+        })
+        .collect::<Vec<_>>();
+    stats.sort_by(|a, b| (&a.runtime, &a.usage_counter).cmp(&(&b.runtime, &b.usage_counter)));
+    stats
+}
+
+/// Returns (runtime, usage counter, price) for every priced counter in Offer.
+/// Offers without linear pricing model are skipped.
+fn offer_prices(offer: &Offer) -> Vec<(String, String, f64)> {
+    let properties: serde_json::Value = match serde_json::from_str(&offer.properties) {
+        Ok(properties) => properties,
+        Err(_) => return vec![],
+    };
+
+    let runtime = properties[RUNTIME_PROPERTY].as_str().unwrap_or("unknown");
+    let coeffs = match properties[LINEAR_COEFFS_PROPERTY].as_array() {
+        Some(coeffs) => coeffs,
+        None => return vec![],
+    };
+    let counters = match properties[USAGE_VECTOR_PROPERTY].as_array() {
+        Some(counters) => counters,
+        None => return vec![],
+    };
+
+    // Linear model has one coefficient for each usage counter and fixed price at the end.
+    if coeffs.len() != counters.len() + 1 {
+        return vec![];
+    }
+
+    counters
+        .iter()
+        .map(|counter| counter.as_str())
+        .chain(std::iter::once(Some(FIXED_PRICE_COUNTER)))
+        .zip(coeffs.iter().map(|coeff| coeff.as_f64()))
+        .filter_map(|(counter, price)| Some((runtime.to_string(), counter?.to_string(), price?)))
+        .collect()
+}
+
+/// Nearest-rank percentile of sorted, non-empty list.
+fn percentile(sorted: &[f64], p: usize) -> f64 {
+    let rank = (p * sorted.len() + 99) / 100;
+    sorted[rank.max(1) - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::mock_offer::sample_offer;
+
+    fn priced_offer(runtime: &str, coeffs: serde_json::Value) -> Offer {
+        let mut offer = sample_offer();
+        offer.properties = serde_json::json!({
+            RUNTIME_PROPERTY: runtime,
+            USAGE_VECTOR_PROPERTY: ["golem.usage.duration_sec", "golem.usage.cpu_sec"],
+            LINEAR_COEFFS_PROPERTY: coeffs,
+        })
+        .to_string();
+        offer
+    }
+
+    #[test]
+    fn test_percentile() {
+        let prices = (1..=10).map(|p| p as f64).collect::<Vec<_>>();
+        assert_eq!(percentile(&prices, 10), 1.0);
+        assert_eq!(percentile(&prices, 50), 5.0);
+        assert_eq!(percentile(&prices, 90), 9.0);
+        assert_eq!(percentile(&[3.0], 25), 3.0);
+    }
+
+    #[test]
+    fn test_compute_stats() {
+        let offers = vec![
+            priced_offer("vm", serde_json::json!([0.1, 0.2, 0.0])),
+            priced_offer("vm", serde_json::json!([0.3, 0.4, 0.0])),
+            priced_offer("wasmtime", serde_json::json!([0.5, 0.6, 1.0])),
+            // Malformed pricing is ignored.
+            priced_offer("vm", serde_json::json!([0.5])),
+            sample_offer(),
+        ];
+        let now = Utc::now().naive_utc();
+        let stats = compute_stats(&offers, now);
+
+        assert_eq!(stats.len(), 6);
+        assert_eq!(stats[0].runtime, "vm");
+        assert_eq!(stats[0].usage_counter, FIXED_PRICE_COUNTER);
+        assert_eq!(stats[1].usage_counter, "golem.usage.cpu_sec");
+        assert_eq!(stats[1].offers_count, 2);
+        assert_eq!(stats[1].p10, 0.2);
+        assert_eq!(stats[1].p90, 0.4);
+        assert_eq!(stats[5].runtime, "wasmtime");
+        assert_eq!(stats[5].p50, 0.5);
+    }
+}
@@ -7,6 +7,7 @@ macro_rules! PROTOCOL_VERSION {
     };
 }
 
+pub mod amendment;
 pub mod callback;
 pub mod discovery;
 pub mod negotiation;
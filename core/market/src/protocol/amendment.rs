@@ -0,0 +1,187 @@
+//! Protocol for changing terms of running Agreements.
+//!
+//! Amendment is proposed by one of the parties and has to be accepted
+//! or rejected by the other one. Accepted Amendment properties are merged into
+//! Agreement on both nodes.
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use ya_client::model::market::Reason;
+use ya_client::model::NodeId;
+use ya_core_model::market::BUS_ID;
+use ya_net::{self as net, RemoteEndpoint};
+use ya_service_bus::{typed::ServiceBinder, RpcEndpoint, RpcMessage};
+
+use crate::db::model::{Agreement, AgreementId, AmendmentState, Owner};
+
+use super::callback::{CallbackHandler, HandlerSlot};
+
+pub fn amendment_addr(prefix: &str) -> String {
+    format!("{}/protocol/{}/amendment", prefix, PROTOCOL_VERSION!())
+}
+
+#[derive(Error, Debug, Serialize, Deserialize)]
+pub enum AmendmentProtocolError {
+    #[error("Amendment [{1}] GSB error: {0}.")]
+    Gsb(String, String),
+    #[error("Agreement [{0}] not found.")]
+    AgreementNotFound(AgreementId),
+    #[error("Caller [{0}] is not a party of Agreement [{1}].")]
+    NotParty(NodeId, AgreementId),
+    #[error("Amendment [{0}] rejected by remote node: {1}")]
+    Remote(String, String),
+    #[error("Can't parse caller [{0}].")]
+    CallerParse(String),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AmendmentProposed {
+    pub agreement_id: AgreementId,
+    pub amendment_id: String,
+    pub demand_properties: Option<String>,
+    pub offer_properties: Option<String>,
+    pub reason: Option<Reason>,
+    pub creation_ts: NaiveDateTime,
+}
+
+/// Receiver responds with Amendment state after applying its policy,
+/// so Amendments accepted automatically don't need additional message.
+impl RpcMessage for AmendmentProposed {
+    const ID: &'static str = "AmendmentProposed";
+    type Item = AmendmentState;
+    type Error = AmendmentProtocolError;
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AmendmentAccepted {
+    pub agreement_id: AgreementId,
+    pub amendment_id: String,
+}
+
+impl RpcMessage for AmendmentAccepted {
+    const ID: &'static str = "AmendmentAccepted";
+    type Item = ();
+    type Error = AmendmentProtocolError;
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AmendmentRejected {
+    pub agreement_id: AgreementId,
+    pub amendment_id: String,
+    pub reason: Option<Reason>,
+}
+
+impl RpcMessage for AmendmentRejected {
+    const ID: &'static str = "AmendmentRejected";
+    type Item = ();
+    type Error = AmendmentProtocolError;
+}
+
+/// Responsible for communication with other nodes about Agreement Amendments.
+#[derive(Clone)]
+pub struct AmendmentApi {
+    amendment_proposed: HandlerSlot<AmendmentProposed>,
+    amendment_accepted: HandlerSlot<AmendmentAccepted>,
+    amendment_rejected: HandlerSlot<AmendmentRejected>,
+}
+
+impl AmendmentApi {
+    pub fn new(
+        amendment_proposed: impl CallbackHandler<AmendmentProposed>,
+        amendment_accepted: impl CallbackHandler<AmendmentAccepted>,
+        amendment_rejected: impl CallbackHandler<AmendmentRejected>,
+    ) -> AmendmentApi {
+        AmendmentApi {
+            amendment_proposed: HandlerSlot::new(amendment_proposed),
+            amendment_accepted: HandlerSlot::new(amendment_accepted),
+            amendment_rejected: HandlerSlot::new(amendment_rejected),
+        }
+    }
+
+    pub async fn propose(
+        &self,
+        agreement: &Agreement,
+        msg: AmendmentProposed,
+    ) -> Result<AmendmentState, AmendmentProtocolError> {
+        send(agreement, msg.amendment_id.clone(), msg).await
+    }
+
+    pub async fn accept(
+        &self,
+        agreement: &Agreement,
+        msg: AmendmentAccepted,
+    ) -> Result<(), AmendmentProtocolError> {
+        send(agreement, msg.amendment_id.clone(), msg).await
+    }
+
+    pub async fn reject(
+        &self,
+        agreement: &Agreement,
+        msg: AmendmentRejected,
+    ) -> Result<(), AmendmentProtocolError> {
+        send(agreement, msg.amendment_id.clone(), msg).await
+    }
+
+    pub async fn bind_gsb(&self, public_prefix: &str, _local_prefix: &str) {
+        log::info!("Amendment protocol version: mk1");
+
+        ServiceBinder::new(&amendment_addr(public_prefix), &(), self.clone())
+            .bind_with_processor(move |_, myself, caller: String, msg: AmendmentProposed| {
+                let myself = myself;
+                async move {
+                    let msg = AmendmentProposed {
+                        agreement_id: msg.agreement_id.swap_owner(),
+                        ..msg
+                    };
+                    myself.amendment_proposed.call(caller, msg).await
+                }
+            })
+            .bind_with_processor(move |_, myself, caller: String, msg: AmendmentAccepted| {
+                let myself = myself;
+                async move {
+                    let msg = AmendmentAccepted {
+                        agreement_id: msg.agreement_id.swap_owner(),
+                        ..msg
+                    };
+                    myself.amendment_accepted.call(caller, msg).await
+                }
+            })
+            .bind_with_processor(move |_, myself, caller: String, msg: AmendmentRejected| {
+                let myself = myself;
+                async move {
+                    let msg = AmendmentRejected {
+                        agreement_id: msg.agreement_id.swap_owner(),
+                        ..msg
+                    };
+                    myself.amendment_rejected.call(caller, msg).await
+                }
+            });
+    }
+}
+
+/// Sends message to the other party of the Agreement. Messages contain
+/// Agreement id from sender perspective; receiver swaps owner on arrival.
+async fn send<M>(
+    agreement: &Agreement,
+    amendment_id: String,
+    msg: M,
+) -> Result<M::Item, AmendmentProtocolError>
+where
+    M: RpcMessage<Error = AmendmentProtocolError> + Unpin,
+{
+    let (sender, receiver) = match agreement.id.owner() {
+        Owner::Requestor => (agreement.requestor_id, agreement.provider_id),
+        Owner::Provider => (agreement.provider_id, agreement.requestor_id),
+    };
+
+    net::from(sender)
+        .to(receiver)
+        .service(&amendment_addr(BUS_ID))
+        .send(msg)
+        .await
+        .map_err(|e| AmendmentProtocolError::Gsb(e.to_string(), amendment_id))?
+}
@@ -24,6 +24,7 @@ pub mod builder;
 pub mod error;
 pub mod message;
 
+use crate::metric_keys;
 use crate::PROTOCOL_VERSION;
 use error::*;
 use message::*;
@@ -126,8 +127,8 @@ impl Discovery {
         let size = offer_ids.len();
         log::debug!("Broadcasting offers. count={}", size);
 
-        counter!("market.offers.broadcasts.net", 1);
-        value!("market.offers.broadcasts.len", size as u64);
+        counter!(metric_keys::OFFERS_BROADCASTS_NET, 1);
+        value!(metric_keys::OFFERS_BROADCASTS_LEN, size as u64);
 
         if self.is_hybrid_net() {
             let mut iter = offer_ids.into_iter().peekable();
@@ -229,8 +230,8 @@ impl Discovery {
 
         let size = offer_ids.len();
         log::debug!("Broadcasting unsubscribes. count={}", size);
-        counter!("market.offers.unsubscribes.broadcasts.net", 1);
-        value!("market.offers.unsubscribes.broadcasts.len", size as u64);
+        counter!(metric_keys::OFFERS_UNSUBSCRIBES_BROADCASTS_NET, 1);
+        value!(metric_keys::OFFERS_UNSUBSCRIBES_BROADCASTS_LEN, size as u64);
 
         if self.is_hybrid_net() {
             let mut iter = offer_ids.into_iter().peekable();
@@ -346,7 +347,7 @@ impl Discovery {
 
             let end_remote = Instant::now();
             timing!(
-                "market.offers.incoming.get_remote.time",
+                metric_keys::OFFERS_INCOMING_GET_REMOTE_TIME,
                 start_remote,
                 end_remote
             );
@@ -372,7 +373,7 @@ impl Discovery {
         }
 
         let end = Instant::now();
-        timing!("market.offers.incoming.time", start, end);
+        timing!(metric_keys::OFFERS_INCOMING_TIME, start, end);
         Ok(())
     }
 
@@ -390,7 +391,7 @@ impl Discovery {
             Ok(_) => Ok(()),
             Err(_) => {
                 log::trace!("Already handling to many broadcasts, skipping...");
-                counter!("market.offers.broadcasts.skip", 1);
+                counter!(metric_keys::OFFERS_BROADCASTS_SKIP, 1);
                 Ok(())
             }
         }
@@ -434,7 +435,7 @@ impl Discovery {
             }
         }
         let end = Instant::now();
-        timing!("market.offers.unsubscribes.incoming.time", start, end);
+        timing!(metric_keys::OFFERS_UNSUBSCRIBES_INCOMING_TIME, start, end);
         Ok(())
     }
 
@@ -446,13 +447,13 @@ impl Discovery {
 async fn broadcast_offers(node_id: NodeId, offer_ids: Vec<SubscriptionId>) {
     if let Err(e) = net::broadcast(node_id, OffersBcast { offer_ids }).await {
         log::error!("Error broadcasting offers: {e}");
-        counter!("market.offers.broadcasts.net_errors", 1);
+        counter!(metric_keys::OFFERS_BROADCASTS_NET_ERRORS, 1);
     };
 }
 
 async fn broadcast_unsubscribed(node_id: NodeId, offer_ids: Vec<SubscriptionId>) {
     if let Err(e) = net::broadcast(node_id, UnsubscribedOffersBcast { offer_ids }).await {
         log::error!("Error broadcasting unsubscribed offers: {e}");
-        counter!("market.offers.unsubscribes.broadcasts.net_errors", 1);
+        counter!(metric_keys::OFFERS_UNSUBSCRIBES_BROADCASTS_NET_ERRORS, 1);
     };
 }
@@ -0,0 +1,189 @@
+//! Local reputation of other nodes.
+//!
+//! Every node scores its counterparties using only outcomes it observed itself:
+//! completed Agreements, timeliness of payments and failed Activities.
+//! Scores aren't shared with other nodes.
+use chrono::Utc;
+use std::collections::HashMap;
+
+use ya_agreement_utils::TerminationReason;
+use ya_client::model::NodeId;
+use ya_core_model::market::{
+    GetReputation, ListReputation, RecordOutcome, Reputation, RpcMessageError,
+};
+use ya_service_bus::typed::ServiceBinder;
+
+use crate::db::dao::ReputationDao;
+use crate::db::model::{DbOutcome, ReputationOutcome};
+use crate::db::{DbMixedExecutor, DbResult};
+
+pub async fn bind_gsb(db: DbMixedExecutor, _public_prefix: &str, local_prefix: &str) {
+    log::trace!("Binding market reputation service to service bus");
+    ServiceBinder::new(local_prefix, &db, ())
+        .bind(record_outcome_gsb)
+        .bind(get_reputation_gsb)
+        .bind(list_reputation_gsb);
+    log::debug!("Successfully bound market reputation service to service bus");
+}
+
+pub async fn record(
+    db: &DbMixedExecutor,
+    node_id: NodeId,
+    subject_id: String,
+    outcome: DbOutcome,
+) -> DbResult<()> {
+    log::debug!(
+        "Recording outcome {} of [{}] for node [{}].",
+        outcome,
+        subject_id,
+        node_id
+    );
+    db.as_dao::<ReputationDao>()
+        .record(ReputationOutcome {
+            node_id,
+            subject_id,
+            outcome,
+            timestamp: Utc::now().naive_utc(),
+        })
+        .await
+}
+
+/// Outcome of terminated Agreement for the counterparty. Only Agreements that ran
+/// to their end count as completed. Failures are left to outcomes recorded by payment
+/// and activity, so they aren't counted twice.
+pub fn termination_outcome(reason: TerminationReason) -> Option<DbOutcome> {
+    match reason {
+        TerminationReason::Success | TerminationReason::Expired => {
+            Some(DbOutcome::AgreementCompleted)
+        }
+        _ => None,
+    }
+}
+
+pub async fn get_reputation(db: &DbMixedExecutor, node_id: NodeId) -> DbResult<Reputation> {
+    let outcomes = db.as_dao::<ReputationDao>().select(node_id).await?;
+    Ok(compute_reputation(node_id, &outcomes))
+}
+
+pub async fn list_reputation(db: &DbMixedExecutor) -> DbResult<Vec<Reputation>> {
+    let mut outcomes = HashMap::<NodeId, Vec<DbOutcome>>::new();
+    for (node_id, outcome) in db.as_dao::<ReputationDao>().select_all().await? {
+        outcomes.entry(node_id).or_default().push(outcome);
+    }
+
+    let mut reputation = outcomes
+        .into_iter()
+        .map(|(node_id, outcomes)| compute_reputation(node_id, &outcomes))
+        .collect::<Vec<_>>();
+    reputation.sort_by(|a, b| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    Ok(reputation)
+}
+
+/// Laplace smoothed ratio of good outcomes to all outcomes.
+/// Nodes without history get neutral score 0.5.
+fn compute_reputation(node_id: NodeId, outcomes: &[DbOutcome]) -> Reputation {
+    let count =
+        |kind: DbOutcome| outcomes.iter().filter(|outcome| **outcome == kind).count() as u32;
+
+    let agreements_completed = count(DbOutcome::AgreementCompleted);
+    let payments_on_time = count(DbOutcome::PaymentOnTime);
+    let payments_late = count(DbOutcome::PaymentLate);
+    let activity_failures = count(DbOutcome::ActivityFailed);
+
+    let good = (agreements_completed + payments_on_time) as f64;
+    let bad = (payments_late + activity_failures) as f64;
+
+    Reputation {
+        node_id,
+        score: (good + 1.0) / (good + bad + 2.0),
+        agreements_completed,
+        payments_on_time,
+        payments_late,
+        activity_failures,
+    }
+}
+
+async fn record_outcome_gsb(
+    db: DbMixedExecutor,
+    _sender_id: String,
+    msg: RecordOutcome,
+) -> Result<(), RpcMessageError> {
+    record(&db, msg.node_id, msg.subject_id, msg.outcome.into())
+        .await
+        .map_err(|e| RpcMessageError::Market(e.to_string()))
+}
+
+async fn get_reputation_gsb(
+    db: DbMixedExecutor,
+    _sender_id: String,
+    msg: GetReputation,
+) -> Result<Reputation, RpcMessageError> {
+    get_reputation(&db, msg.node_id)
+        .await
+        .map_err(|e| RpcMessageError::Market(e.to_string()))
+}
+
+async fn list_reputation_gsb(
+    db: DbMixedExecutor,
+    _sender_id: String,
+    _msg: ListReputation,
+) -> Result<Vec<Reputation>, RpcMessageError> {
+    list_reputation(&db)
+        .await
+        .map_err(|e| RpcMessageError::Market(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compute_reputation() {
+        let node_id = "0xbabe000000000000000000000000000000000000"
+            .parse()
+            .unwrap();
+
+        let unknown = compute_reputation(node_id, &[]);
+        assert_eq!(unknown.score, 0.5);
+
+        let reliable = compute_reputation(
+            node_id,
+            &[
+                DbOutcome::AgreementCompleted,
+                DbOutcome::AgreementCompleted,
+                DbOutcome::PaymentOnTime,
+            ],
+        );
+        assert_eq!(reliable.agreements_completed, 2);
+        assert_eq!(reliable.score, 0.8);
+
+        let unreliable = compute_reputation(
+            node_id,
+            &[DbOutcome::ActivityFailed, DbOutcome::PaymentLate],
+        );
+        assert_eq!(unreliable.score, 0.25);
+        assert_eq!(unreliable.activity_failures, 1);
+    }
+
+    #[test]
+    fn test_termination_outcome() {
+        assert_eq!(
+            termination_outcome(TerminationReason::Success),
+            Some(DbOutcome::AgreementCompleted)
+        );
+        assert_eq!(
+            termination_outcome(TerminationReason::Expired),
+            Some(DbOutcome::AgreementCompleted)
+        );
+        assert_eq!(termination_outcome(TerminationReason::PaymentTimeout), None);
+        assert_eq!(
+            termination_outcome(TerminationReason::RequestorCancelled),
+            None
+        );
+        assert_eq!(termination_outcome(TerminationReason::Unspecified), None);
+    }
+}
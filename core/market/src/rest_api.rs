@@ -9,7 +9,7 @@ use actix_web::{error::InternalError, http::StatusCode, web::PathConfig};
 use chrono::{DateTime, Utc};
 use serde::Deserialize;
 
-use ya_client::model::{market::agreement::State, ErrorMessage};
+use ya_client::model::{market::agreement::State, ErrorMessage, NodeId};
 
 use crate::db::model::{
     AgreementId, AppSessionId, Owner, ProposalId, ProposalIdParseError, SubscriptionId,
@@ -22,6 +22,7 @@ pub(crate) mod requestor;
 
 const DEFAULT_EVENT_TIMEOUT: f32 = 5.0; // seconds
 const DEFAULT_QUERY_TIMEOUT: f32 = 5.0;
+const DEFAULT_SCAN_LIMIT: usize = 100;
 
 pub fn path_config() -> PathConfig {
     PathConfig::default().error_handler(|err, _req| {
@@ -48,6 +49,22 @@ pub struct PathAgreement {
     pub agreement_id: String,
 }
 
+#[derive(Deserialize)]
+pub struct PathDemandTemplate {
+    pub name: String,
+}
+
+#[derive(Deserialize)]
+pub struct PathAmendment {
+    pub agreement_id: String,
+    pub amendment_id: String,
+}
+
+#[derive(Deserialize)]
+pub struct PathNode {
+    pub node_id: NodeId,
+}
+
 #[derive(Deserialize)]
 pub struct PathSubscription {
     pub subscription_id: SubscriptionId,
@@ -106,6 +123,31 @@ pub struct QueryTimeoutMaxEvents {
     pub max_events: Option<i32>,
 }
 
+#[derive(Deserialize, Debug)]
+pub struct QueryScan {
+    #[serde(default)]
+    pub offset: usize,
+    #[serde(default = "default_scan_limit")]
+    pub limit: usize,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct QueryPriceStats {
+    /// Only statistics of Offers with this `golem.runtime.name`
+    pub runtime: Option<String>,
+    #[serde(rename = "afterTimestamp")]
+    pub after_timestamp: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct QueryAmendmentEvents {
+    /// maximum count of events to return
+    #[serde(rename = "maxEvents")]
+    pub max_events: Option<i32>,
+    #[serde(rename = "afterTimestamp")]
+    pub after_timestamp: Option<DateTime<Utc>>,
+}
+
 #[derive(Deserialize, Debug)]
 pub struct QueryAgreementEvents {
     /// number of seconds to wait
@@ -135,6 +177,11 @@ pub(crate) fn default_event_timeout() -> f32 {
     DEFAULT_EVENT_TIMEOUT
 }
 
+#[inline(always)]
+pub(crate) fn default_scan_limit() -> usize {
+    DEFAULT_SCAN_LIMIT
+}
+
 impl PathAgreement {
     pub fn to_id(&self, owner: Owner) -> Result<AgreementId, ProposalIdParseError> {
         AgreementId::from_client(&self.agreement_id, owner)
@@ -7,18 +7,30 @@ use ya_client::model::market::Reason;
 use ya_service_api_web::middleware::Identity;
 use ya_std_utils::LogErr;
 
-use super::PathAgreement;
+use super::{PathAgreement, PathAmendment, PathNode};
 use crate::db::model::Owner;
+use crate::market::amendment::NewAmendment;
 use crate::market::MarketService;
 use crate::negotiation::error::AgreementError;
-use crate::rest_api::{QueryAgreementEvents, QueryAgreementList};
+use crate::rest_api::{
+    QueryAgreementEvents, QueryAgreementList, QueryAmendmentEvents, QueryPriceStats,
+};
 
 pub fn register_endpoints(scope: Scope) -> Scope {
     scope
         .service(list_agreements)
         .service(collect_agreement_events)
         .service(get_agreement)
+        .service(get_negotiation_history)
+        .service(get_price_stats)
+        .service(list_reputation)
+        .service(get_reputation)
         .service(terminate_agreement)
+        .service(propose_amendment)
+        .service(list_amendments)
+        .service(accept_amendment)
+        .service(reject_amendment)
+        .service(collect_amendment_events)
 }
 
 #[actix_web::get("/agreements")]
@@ -69,6 +81,64 @@ async fn get_agreement(
     }
 }
 
+#[actix_web::get("/agreements/{agreement_id}/negotiations")]
+async fn get_negotiation_history(
+    market: Data<Arc<MarketService>>,
+    path: Path<PathAgreement>,
+    id: Identity,
+) -> impl Responder {
+    // Like in `get_agreement`, we don't know which side of the Agreement we are.
+    let path = path.into_inner();
+    let r_agreement_id = path.to_id(Owner::Requestor)?;
+    let p_agreement_id = r_agreement_id.clone().swap_owner();
+
+    match market.get_negotiation_history(&r_agreement_id, &id).await {
+        Err(AgreementError::NotFound(_)) => market
+            .get_negotiation_history(&p_agreement_id, &id)
+            .await
+            .map(|history| HttpResponse::Ok().json(history)),
+        result => result.map(|history| HttpResponse::Ok().json(history)),
+    }
+    .log_err()
+}
+
+#[actix_web::get("/priceStatistics")]
+async fn get_price_stats(
+    market: Data<Arc<MarketService>>,
+    query: Query<QueryPriceStats>,
+    _id: Identity,
+) -> impl Responder {
+    let query = query.into_inner();
+    let after_timestamp = query
+        .after_timestamp
+        .unwrap_or_else(|| Utc::now() - chrono::Duration::days(1));
+
+    market
+        .get_price_stats(query.runtime, after_timestamp)
+        .await
+        .map(|stats| HttpResponse::Ok().json(stats))
+}
+
+#[actix_web::get("/reputation")]
+async fn list_reputation(market: Data<Arc<MarketService>>, _id: Identity) -> impl Responder {
+    market
+        .list_reputation()
+        .await
+        .map(|reputation| HttpResponse::Ok().json(reputation))
+}
+
+#[actix_web::get("/reputation/{node_id}")]
+async fn get_reputation(
+    market: Data<Arc<MarketService>>,
+    path: Path<PathNode>,
+    _id: Identity,
+) -> impl Responder {
+    market
+        .get_reputation(path.node_id)
+        .await
+        .map(|reputation| HttpResponse::Ok().json(reputation))
+}
+
 #[actix_web::get("/agreementEvents")]
 async fn collect_agreement_events(
     market: Data<Arc<MarketService>>,
@@ -107,3 +177,84 @@ async fn terminate_agreement(
         .log_err()
         .map(|_| HttpResponse::Ok().finish())
 }
+
+#[actix_web::post("/agreements/{agreement_id}/amendments")]
+async fn propose_amendment(
+    market: Data<Arc<MarketService>>,
+    path: Path<PathAgreement>,
+    id: Identity,
+    body: Json<NewAmendment>,
+) -> impl Responder {
+    market
+        .amendments
+        .propose(&path.agreement_id, body.into_inner(), &id)
+        .await
+        .log_err()
+        .map(|amendment| HttpResponse::Created().json(amendment))
+}
+
+#[actix_web::get("/agreements/{agreement_id}/amendments")]
+async fn list_amendments(
+    market: Data<Arc<MarketService>>,
+    path: Path<PathAgreement>,
+    id: Identity,
+) -> impl Responder {
+    market
+        .amendments
+        .list(&path.agreement_id, &id)
+        .await
+        .log_err()
+        .map(|amendments| HttpResponse::Ok().json(amendments))
+}
+
+#[actix_web::post("/agreements/{agreement_id}/amendments/{amendment_id}/accept")]
+async fn accept_amendment(
+    market: Data<Arc<MarketService>>,
+    path: Path<PathAmendment>,
+    id: Identity,
+) -> impl Responder {
+    market
+        .amendments
+        .accept(&path.agreement_id, &path.amendment_id, &id)
+        .await
+        .log_err()
+        .map(|amendment| HttpResponse::Ok().json(amendment))
+}
+
+#[actix_web::post("/agreements/{agreement_id}/amendments/{amendment_id}/reject")]
+async fn reject_amendment(
+    market: Data<Arc<MarketService>>,
+    path: Path<PathAmendment>,
+    id: Identity,
+    body: Json<Option<Reason>>,
+) -> impl Responder {
+    market
+        .amendments
+        .reject(
+            &path.agreement_id,
+            &path.amendment_id,
+            body.into_inner(),
+            &id,
+        )
+        .await
+        .log_err()
+        .map(|amendment| HttpResponse::Ok().json(amendment))
+}
+
+#[actix_web::get("/amendmentEvents")]
+async fn collect_amendment_events(
+    market: Data<Arc<MarketService>>,
+    query: Query<QueryAmendmentEvents>,
+    id: Identity,
+) -> impl Responder {
+    let after_timestamp = query
+        .after_timestamp
+        .unwrap_or_else(|| Utc.with_ymd_and_hms(2016, 11, 11, 15, 12, 0).unwrap());
+
+    market
+        .amendments
+        .query_events(after_timestamp, query.max_events, &id)
+        .await
+        .log_err()
+        .map(|events| HttpResponse::Ok().json(events))
+}
@@ -2,16 +2,19 @@ use actix_web::{HttpResponse, ResponseError};
 
 use ya_client::model::ErrorMessage;
 
-use crate::db::dao::{AgreementDaoError, SaveProposalError};
+use crate::db::dao::{AgreementDaoError, AmendmentDaoError, SaveProposalError};
 use crate::db::model::AgreementState;
+use crate::demand_template::DemandTemplateError;
+use crate::market::amendment::AmendmentError;
 use crate::negotiation::error::{AgreementEventsError, ProposalValidationError};
+use crate::protocol::amendment::AmendmentProtocolError;
 use crate::protocol::negotiation::error::RejectProposalError;
 use crate::{
     db::dao::TakeEventsError,
     market::MarketError,
     matcher::error::{
         DemandError, MatcherError, ModifyOfferError, QueryDemandsError, QueryOfferError,
-        QueryOffersError, ResolverError, SaveOfferError,
+        QueryOffersError, ResolverError, SaveOfferError, ScanError,
     },
     negotiation::error::{
         AgreementError, GetProposalError, NegotiationError, ProposalError, QueryEventsError,
@@ -34,6 +37,9 @@ impl ResponseError for MarketError {
             MarketError::QueryOffersError(e) => e.error_response(),
             MarketError::DemandError(e) => e.error_response(),
             MarketError::Negotiation(e) => e.error_response(),
+            MarketError::PriceStats(_) | MarketError::Reputation(_) => {
+                HttpResponse::InternalServerError().json(ErrorMessage::new(self.to_string()))
+            }
         }
     }
 }
@@ -46,6 +52,7 @@ impl ResponseError for MatcherError {
             MatcherError::QueryOffer(e) => e.error_response(),
             MatcherError::SaveOffer(e) => e.error_response(),
             MatcherError::ModifyOffer(e) => e.error_response(),
+            MatcherError::Scan(e) => e.error_response(),
         }
     }
 }
@@ -64,6 +71,9 @@ impl ResponseError for DemandError {
             DemandError::NotFound(_) => {
                 HttpResponse::NotFound().json(ErrorMessage::new(self.to_string()))
             }
+            DemandError::InvalidProperties(_) => {
+                HttpResponse::BadRequest().json(ErrorMessage::new(self.to_string()))
+            }
             _ => HttpResponse::InternalServerError().json(ErrorMessage::new(self.to_string())),
         }
     }
@@ -75,6 +85,17 @@ impl ResponseError for QueryDemandsError {
     }
 }
 
+impl ResponseError for ScanError {
+    fn error_response(&self) -> HttpResponse {
+        match self {
+            ScanError::InvalidConstraints(_) => {
+                HttpResponse::BadRequest().json(ErrorMessage::new(self.to_string()))
+            }
+            ScanError::QueryOffers(e) => e.error_response(),
+        }
+    }
+}
+
 impl ResponseError for QueryOffersError {
     fn error_response(&self) -> HttpResponse {
         HttpResponse::InternalServerError().json(ErrorMessage::new(self.to_string()))
@@ -91,6 +112,18 @@ impl ResponseError for QueryOfferError {
     }
 }
 
+impl ResponseError for DemandTemplateError {
+    fn error_response(&self) -> HttpResponse {
+        let msg = ErrorMessage::new(self.to_string());
+        match self {
+            DemandTemplateError::NotFound(_) => HttpResponse::NotFound().json(msg),
+            DemandTemplateError::Invalid(_) => HttpResponse::BadRequest().json(msg),
+            DemandTemplateError::Subscribe(e) => e.error_response(),
+            DemandTemplateError::Db(_) => HttpResponse::InternalServerError().json(msg),
+        }
+    }
+}
+
 impl ResponseError for SaveOfferError {
     fn error_response(&self) -> HttpResponse {
         let msg = ErrorMessage::new(self.to_string());
@@ -98,6 +131,7 @@ impl ResponseError for SaveOfferError {
             SaveOfferError::Unsubscribed(_) | SaveOfferError::Expired(_) => {
                 HttpResponse::Gone().json(msg)
             }
+            SaveOfferError::InvalidProperties(_) => HttpResponse::BadRequest().json(msg),
             _ => HttpResponse::InternalServerError().json(msg),
         }
     }
@@ -171,6 +205,7 @@ impl ResponseError for ProposalValidationError {
             | ProposalValidationError::OwnProposal(_) => HttpResponse::BadRequest().json(msg),
             ProposalValidationError::SubscriptionExpired(_) => HttpResponse::Gone().json(msg),
             ProposalValidationError::Unauthorized(_, _) => HttpResponse::Unauthorized().json(msg),
+            ProposalValidationError::LowReputation(_) => HttpResponse::Forbidden().json(msg),
             ProposalValidationError::Internal(_) => HttpResponse::InternalServerError().json(msg),
         }
     }
@@ -263,3 +298,32 @@ impl ResponseError for AgreementEventsError {
         }
     }
 }
+
+impl ResponseError for AmendmentError {
+    fn error_response(&self) -> HttpResponse {
+        let msg = ErrorMessage::new(self.to_string());
+        match self {
+            AmendmentError::NotFound(_) | AmendmentError::AmendmentNotFound(_) => {
+                HttpResponse::NotFound().json(msg)
+            }
+            AmendmentError::NoChanges(_)
+            | AmendmentError::InvalidProperties
+            | AmendmentError::InvalidId(_)
+            | AmendmentError::OwnAmendment(_) => HttpResponse::BadRequest().json(msg),
+            AmendmentError::Dao(e) => match e {
+                AmendmentDaoError::NotFound(_) => HttpResponse::NotFound().json(msg),
+                AmendmentDaoError::NotPending(..)
+                | AmendmentDaoError::AgreementNotApproved(..)
+                | AmendmentDaoError::AgreementChanged(..) => HttpResponse::Conflict().json(msg),
+                AmendmentDaoError::DbError(_) => HttpResponse::InternalServerError().json(msg),
+            },
+            AmendmentError::Protocol(e) => match e {
+                AmendmentProtocolError::Remote(..) => HttpResponse::Conflict().json(msg),
+                _ => HttpResponse::InternalServerError().json(msg),
+            },
+            AmendmentError::Get(..) | AmendmentError::Internal(_) => {
+                HttpResponse::InternalServerError().json(msg)
+            }
+        }
+    }
+}
@@ -1,22 +1,28 @@
 use actix_web::web::{Data, Json, Path, Query};
 use actix_web::{HttpResponse, Responder, Scope};
+use bigdecimal::BigDecimal;
+use chrono::Utc;
+use serde::Deserialize;
 use std::str::FromStr;
 use std::sync::Arc;
 
 use ya_client::model::market::{AgreementProposal, NewDemand, NewProposal, Reason};
 use ya_client::model::ErrorMessage;
+use ya_core_model::market::DemandTemplate;
 use ya_service_api_web::middleware::Identity;
 use ya_std_utils::LogErr;
 
 use crate::db::model::Owner;
+use crate::demand_template;
 use crate::market::MarketService;
 
 use super::{
-    PathAgreement, PathSubscription, PathSubscriptionProposal, ProposalId, QueryTimeout,
-    QueryTimeoutMaxEvents,
+    PathAgreement, PathDemandTemplate, PathSubscription, PathSubscriptionProposal, ProposalId,
+    QueryTimeout, QueryTimeoutMaxEvents,
 };
+use crate::matcher::scan::ScanRequest;
 use crate::negotiation::ApprovalStatus;
-use crate::rest_api::QueryAppSessionId;
+use crate::rest_api::{QueryAppSessionId, QueryScan};
 
 pub fn register_endpoints(scope: Scope) -> Scope {
     scope
@@ -31,6 +37,26 @@ pub fn register_endpoints(scope: Scope) -> Scope {
         .service(confirm_agreement)
         .service(wait_for_approval)
         .service(cancel_agreement)
+        .service(scan)
+        .service(list_demand_templates)
+        .service(get_demand_template)
+        .service(save_demand_template)
+        .service(delete_demand_template)
+        .service(subscribe_demand_template)
+}
+
+/// Demand template as sent in REST requests, named by the path.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DemandTemplateBody {
+    #[serde(default)]
+    properties: serde_json::Value,
+    #[serde(default)]
+    constraints: String,
+    payment_platform: Option<String>,
+    expiration_secs: Option<u64>,
+    debit_note_accept_timeout_secs: Option<u64>,
+    budget: Option<BigDecimal>,
 }
 
 #[actix_web::post("/demands")]
@@ -214,3 +240,84 @@ async fn cancel_agreement(
         .log_err()
         .map(|_| HttpResponse::Ok().finish())
 }
+
+#[actix_web::post("/scan")]
+async fn scan(
+    market: Data<Arc<MarketService>>,
+    body: Json<ScanRequest>,
+    query: Query<QueryScan>,
+    _id: Identity,
+) -> impl Responder {
+    market
+        .scan_offers(&body.into_inner(), query.offset, query.limit)
+        .await
+        .log_err()
+        .map(|result| HttpResponse::Ok().json(result))
+}
+
+#[actix_web::get("/demandTemplates")]
+async fn list_demand_templates(market: Data<Arc<MarketService>>, id: Identity) -> impl Responder {
+    demand_template::list(&market.db, id.identity)
+        .await
+        .map(|templates| HttpResponse::Ok().json(templates))
+}
+
+#[actix_web::get("/demandTemplates/{name}")]
+async fn get_demand_template(
+    market: Data<Arc<MarketService>>,
+    path: Path<PathDemandTemplate>,
+    id: Identity,
+) -> impl Responder {
+    demand_template::get(&market.db, id.identity, path.into_inner().name)
+        .await
+        .map(|template| HttpResponse::Ok().json(template))
+}
+
+#[actix_web::put("/demandTemplates/{name}")]
+async fn save_demand_template(
+    market: Data<Arc<MarketService>>,
+    path: Path<PathDemandTemplate>,
+    body: Json<DemandTemplateBody>,
+    id: Identity,
+) -> impl Responder {
+    let body = body.into_inner();
+    let template = DemandTemplate {
+        name: path.into_inner().name,
+        owner_id: id.identity,
+        properties: body.properties,
+        constraints: body.constraints,
+        payment_platform: body.payment_platform,
+        expiration_secs: body.expiration_secs,
+        debit_note_accept_timeout_secs: body.debit_note_accept_timeout_secs,
+        budget: body.budget,
+        updated_at: Utc::now(),
+    };
+    demand_template::save(&market.db, template)
+        .await
+        .log_err()
+        .map(|template| HttpResponse::Ok().json(template))
+}
+
+#[actix_web::delete("/demandTemplates/{name}")]
+async fn delete_demand_template(
+    market: Data<Arc<MarketService>>,
+    path: Path<PathDemandTemplate>,
+    id: Identity,
+) -> impl Responder {
+    demand_template::delete(&market.db, id.identity, path.into_inner().name)
+        .await
+        .map(|_| HttpResponse::NoContent().finish())
+}
+
+/// Subscribes Demand built from the template, as `POST /demands` would.
+#[actix_web::post("/demandTemplates/{name}/demands")]
+async fn subscribe_demand_template(
+    market: Data<Arc<MarketService>>,
+    path: Path<PathDemandTemplate>,
+    id: Identity,
+) -> impl Responder {
+    demand_template::subscribe(&market, path.into_inner().name, &id)
+        .await
+        .log_err()
+        .map(|id| HttpResponse::Created().json(id))
+}
@@ -1,7 +1,7 @@
 use actix_web::{http::StatusCode, web::Bytes};
 use chrono::{Duration, Utc};
 
-use ya_client::model::market::Role;
+use ya_client::model::market::{proposal::State, Role};
 use ya_core_model::market;
 use ya_market::assert_err_eq;
 use ya_market::testing::{
@@ -113,6 +113,55 @@ async fn test_gsb_list_agreements() {
     assert_eq!(agreements[0].role, Role::Requestor);
 }
 
+#[cfg_attr(not(feature = "test-suite"), ignore)]
+#[serial_test::serial]
+async fn test_gsb_get_negotiation_history() {
+    let network = MarketsNetwork::new(None)
+        .await
+        .add_market_instance(REQ_NAME)
+        .await
+        .add_market_instance(PROV_NAME)
+        .await;
+
+    let proposal_id = exchange_draft_proposals(&network, REQ_NAME, PROV_NAME)
+        .await
+        .unwrap()
+        .proposal_id;
+    let req_market = network.get_market(REQ_NAME);
+    let req_engine = &req_market.requestor_engine;
+    let req_id = network.get_default_id(REQ_NAME);
+    let prov_id = network.get_default_id(PROV_NAME);
+
+    let agreement_id = req_engine
+        .create_agreement(
+            req_id.clone(),
+            &proposal_id,
+            Utc::now() + Duration::hours(1),
+        )
+        .await
+        .unwrap();
+
+    let history = bus::service(network.node_gsb_prefixes(REQ_NAME).0)
+        .send(market::GetNegotiationHistory {
+            agreement_id: agreement_id.into_client(),
+        })
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Initial Proposal, Requestor's counter Proposal and Provider's counter Proposal.
+    assert_eq!(history.len(), 3);
+    assert_eq!(history[0].prev_proposal_id, None);
+    assert_eq!(
+        history[1].prev_proposal_id,
+        Some(history[0].proposal_id.clone())
+    );
+    assert_eq!(history[1].issuer_id, req_id.identity);
+    assert_eq!(history[2].proposal_id, proposal_id.to_string());
+    assert_eq!(history[2].issuer_id, prov_id.identity);
+    assert_eq!(history[2].state, State::Accepted);
+}
+
 #[cfg_attr(not(feature = "test-suite"), ignore)]
 #[serial_test::serial]
 async fn test_get_agreement() {
@@ -0,0 +1,39 @@
+use actix_web::{web, App, HttpResponse, HttpServer};
+use std::net::SocketAddr;
+
+/// Starts standalone http server exposing metrics in Prometheus text format
+/// under `/metrics`, so they can be scraped without yagna application key.
+pub fn spawn(addr: Option<SocketAddr>) {
+    let addr = match addr {
+        Some(addr) => addr,
+        None => {
+            log::debug!("Metrics exposer disabled");
+            return;
+        }
+    };
+
+    let server = match HttpServer::new(|| App::new().route("/metrics", web::get().to(metrics)))
+        .workers(1)
+        .disable_signals()
+        .bind(addr)
+    {
+        Ok(server) => server.run(),
+        Err(e) => {
+            log::warn!("Metrics exposer init failure: Binding {}: {}", addr, e);
+            return;
+        }
+    };
+
+    tokio::task::spawn_local(async move {
+        if let Err(e) = server.await {
+            log::warn!("Metrics exposer stopped: {}", e);
+        }
+    });
+    log::info!("Metrics exposed on http://{}/metrics", addr);
+}
+
+async fn metrics() -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type("text/plain; version=0.0.4")
+        .body(crate::service::export_metrics().await)
+}
@@ -1,4 +1,5 @@
 mod exporter;
+pub(crate) mod exposer;
 mod metrics;
 pub(crate) mod pusher;
 mod service;
@@ -89,7 +89,7 @@ pub async fn push(client: &Client, push_url: String) {
 
 async fn get_default_id() -> anyhow::Result<IdentityInfo> {
     let default_id = bus::service(identity::BUS_ID)
-        .call(identity::Get::ByDefault)
+        .call(identity::Get::ByRole(identity::IdentityRole::NodeId))
         .await??
         .ok_or_else(|| anyhow::anyhow!("Default identity not found"))?;
     Ok(default_id)
@@ -140,6 +140,7 @@ mod test {
                 node_id: Default::default(),
                 is_locked: false,
                 is_default: false,
+                roles: vec![],
             },
             "community.1",
         )
@@ -156,6 +157,7 @@ mod test {
                 node_id: Default::default(),
                 is_locked: false,
                 is_default: false,
+                roles: vec![],
             },
             "community.1",
         )
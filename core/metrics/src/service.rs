@@ -1,5 +1,6 @@
 use futures::lock::Mutex;
 use lazy_static::lazy_static;
+use std::net::SocketAddr;
 use std::sync::Arc;
 use url::Url;
 
@@ -10,6 +11,7 @@ use crate::metrics::Metrics;
 
 const YAGNA_METRICS_URL_ENV_VAR: &str = "YAGNA_METRICS_URL";
 const DEFAULT_YAGNA_METRICS_URL: &str = "https://metrics.golem.network:9092/";
+const YAGNA_METRICS_EXPOSE_ADDR_ENV_VAR: &str = "YAGNA_METRICS_EXPOSE_ADDR";
 
 // TODO: enable showing metrics also via CLI
 #[derive(structopt::StructOpt, Debug)]
@@ -28,6 +30,11 @@ pub struct MetricsPusherOpts {
     /// Metrics job name, which allows to distinguish different groups of Nodes.
     #[structopt(long, env = "YAGNA_METRICS_JOB_NAME", default_value = "community.1")]
     pub metrics_job_name: String,
+
+    /// Address of the unauthenticated Prometheus scrape endpoint (`/metrics`).
+    /// If unset, metrics are available only via REST API and pushgateway.
+    #[structopt(long, env = YAGNA_METRICS_EXPOSE_ADDR_ENV_VAR)]
+    pub metrics_expose_addr: Option<SocketAddr>,
 }
 
 impl From<&MetricsPusherOpts> for MetricsCtx {
@@ -36,6 +43,7 @@ impl From<&MetricsPusherOpts> for MetricsCtx {
             push_enabled: !opts.disable_metrics_push,
             push_host_url: Some(opts.metrics_push_url.clone()),
             job: opts.metrics_job_name.clone(),
+            expose_addr: opts.metrics_expose_addr,
         }
     }
 }
@@ -51,12 +59,12 @@ impl MetricsService {
         // This should initialize Metrics. We need to do this before all other services will start.
         let _ = METRICS.clone();
 
-        crate::pusher::spawn(
-            context
-                .component()
-                .metrics_ctx
-                .expect("Metrics pusher needs CLI ctx"),
-        );
+        let metrics_ctx = context
+            .component()
+            .metrics_ctx
+            .expect("Metrics pusher needs CLI ctx");
+        crate::exposer::spawn(metrics_ctx.expose_addr);
+        crate::pusher::spawn(metrics_ctx);
         Ok(())
     }
 
@@ -143,6 +143,55 @@ impl RpcMessage for GetUsage {
     type Error = RpcMessageError;
 }
 
+/// Get usage samples reported by the ExeUnit and retained by the Provider,
+/// oldest first.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetUsageHistory {
+    pub activity_id: String,
+    pub timeout: Option<f32>,
+}
+
+impl RpcMessage for GetUsageHistory {
+    const ID: &'static str = "GetActivityUsageHistory";
+    type Item = Vec<ActivityUsage>;
+    type Error = RpcMessageError;
+}
+
+/// Usage counters reported by the ExeUnit and signed with the Provider identity,
+/// to be checked against usage billed in Debit Notes.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignedUsage {
+    pub activity_id: String,
+    pub usage: ActivityUsage,
+    pub signature: Vec<u8>,
+}
+
+impl SignedUsage {
+    /// Hash of the usage snapshot, which is signed. Fields are hashed in a fixed
+    /// big-endian encoding: length-prefixed activity id, timestamp, number of
+    /// counters (`u64::MAX` when there is no usage) and bits of each counter.
+    pub fn hash(activity_id: &str, usage: &ActivityUsage) -> Vec<u8> {
+        use sha3::{Digest, Sha3_256};
+
+        let mut hasher = Sha3_256::new();
+        hasher.input((activity_id.len() as u64).to_be_bytes());
+        hasher.input(activity_id.as_bytes());
+        hasher.input(usage.timestamp.to_be_bytes());
+        match &usage.current_usage {
+            Some(counters) => {
+                hasher.input((counters.len() as u64).to_be_bytes());
+                for counter in counters {
+                    hasher.input(counter.to_bits().to_be_bytes());
+                }
+            }
+            None => hasher.input(u64::MAX.to_be_bytes()),
+        }
+        hasher.result().to_vec()
+    }
+}
+
 /// Update remote network configuration
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -155,6 +204,11 @@ pub enum VpnControl {
         network_id: String,
         node_ids: HashSet<String>,
     },
+    /// Replaces firewall rules of the network. Empty rules allow all traffic.
+    SetRules {
+        network_id: String,
+        rules: Vec<VpnRule>,
+    },
 }
 
 impl VpnControl {
@@ -173,6 +227,44 @@ impl VpnControl {
     }
 }
 
+/// Traffic direction from the perspective of the node enforcing the rule.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum VpnDirection {
+    Ingress,
+    Egress,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum VpnProtocol {
+    Tcp,
+    Udp,
+    Icmp,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VpnPortRange {
+    pub start: u16,
+    pub end: u16,
+}
+
+/// Allows traffic within VPN network. Rules are stateless and apply to
+/// both directions of a connection: `Ingress` rule allows remote nodes to connect
+/// to local `ports`, `Egress` rule allows connecting to remote `ports`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VpnRule {
+    pub direction: VpnDirection,
+    /// All protocols if not set.
+    pub protocol: Option<VpnProtocol>,
+    /// All ports if not set. Rules with ports match only TCP and UDP packets.
+    pub ports: Option<VpnPortRange>,
+    /// Remote IP address or CIDR. All nodes in the network if not set.
+    pub peer: Option<String>,
+}
+
 impl RpcMessage for VpnControl {
     const ID: &'static str = "VpnControl";
     type Item = ();
@@ -210,7 +302,8 @@ pub mod sgx {
 
 /// Execute a script within the activity. Returns `batch_id`.
 ///
-/// Commands are executed sequentially.
+/// Commands are executed sequentially. Without `pipeline` the batch stops
+/// on the first failed command.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Exec {
@@ -218,6 +311,66 @@ pub struct Exec {
     pub batch_id: String,
     pub exe_script: Vec<ExeScriptCommand>,
     pub timeout: Option<f32>,
+    #[serde(default)]
+    pub pipeline: Option<ExecPipeline>,
+}
+
+/// What happens to the rest of the batch when one of its commands fails.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FailurePolicy {
+    /// No more commands are executed.
+    Stop,
+    /// Commands not depending on the failed one are still executed.
+    Continue,
+}
+
+impl Default for FailurePolicy {
+    fn default() -> Self {
+        FailurePolicy::Stop
+    }
+}
+
+/// Dependencies between commands of the batch, which let the ExeUnit run
+/// the whole script without waiting for the Requestor between commands.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecPipeline {
+    /// Indices of earlier commands each command depends on.
+    /// Commands past the end of the list depend on the preceding command.
+    #[serde(default)]
+    pub depends_on: Vec<Vec<usize>>,
+    #[serde(default)]
+    pub on_failure: FailurePolicy,
+}
+
+impl ExecPipeline {
+    pub fn validate(&self, commands: usize) -> Result<(), String> {
+        if self.depends_on.len() > commands {
+            return Err(format!(
+                "Dependencies given for {} commands, script has {}",
+                self.depends_on.len(),
+                commands
+            ));
+        }
+        for (idx, deps) in self.depends_on.iter().enumerate() {
+            if let Some(dep) = deps.iter().find(|dep| **dep >= idx) {
+                return Err(format!(
+                    "Command {} can only depend on earlier commands, not on {}",
+                    idx, dep
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// The first dependency of command `idx` which failed or was skipped.
+    pub fn blocked_by(&self, idx: usize, failed: &HashSet<usize>) -> Option<usize> {
+        match self.depends_on.get(idx) {
+            Some(deps) => deps.iter().find(|dep| failed.contains(dep)).copied(),
+            None => idx.checked_sub(1).filter(|prev| failed.contains(prev)),
+        }
+    }
 }
 
 impl RpcMessage for Exec {
@@ -258,6 +411,50 @@ impl RpcStreamMessage for StreamExecBatchResults {
     type Error = RpcMessageError;
 }
 
+/// Read a file written by the Activity, before its commands finish.
+/// `path` is a path within one of the container volumes.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadOutputFile {
+    pub activity_id: String,
+    pub path: String,
+    pub offset: u64,
+    /// Capped by the ExeUnit.
+    pub max_bytes: Option<u64>,
+    pub timeout: Option<f32>,
+}
+
+impl RpcMessage for ReadOutputFile {
+    const ID: &'static str = "ReadOutputFile";
+    type Item = OutputFileChunk;
+    type Error = RpcMessageError;
+}
+
+/// Stream data appended to a file written by the Activity, starting at `offset`.
+/// Stream ends when the file is removed or the Activity is destroyed.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamOutputFile {
+    pub activity_id: String,
+    pub path: String,
+    pub offset: u64,
+}
+
+impl RpcStreamMessage for StreamOutputFile {
+    const ID: &'static str = "StreamOutputFile";
+    type Item = OutputFileChunk;
+    type Error = RpcMessageError;
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutputFileChunk {
+    pub offset: u64,
+    pub data: Vec<u8>,
+    /// Size of the file when the chunk was read.
+    pub size: u64,
+}
+
 /// Get currently running command and its state.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -358,6 +555,9 @@ pub mod local {
         pub activity_id: String,
         pub usage: ActivityUsage,
         pub timeout: Option<f32>,
+        /// Signature of [`SignedUsage::hash`] of `usage`, made with the Provider identity.
+        #[serde(default)]
+        pub signature: Option<Vec<u8>>,
     }
 
     impl RpcMessage for SetUsage {
@@ -366,6 +566,19 @@ pub mod local {
         type Error = RpcMessageError;
     }
 
+    /// Get the last usage counters of the activity signed by its ExeUnit.
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct GetSignedUsage {
+        pub activity_id: String,
+    }
+
+    impl RpcMessage for GetSignedUsage {
+        const ID: &'static str = "GetSignedActivityUsage";
+        type Item = Option<SignedUsage>;
+        type Error = RpcMessageError;
+    }
+
     /// Get agreement ID of the activity.
     #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
     #[serde(rename_all = "camelCase")]
@@ -401,3 +614,63 @@ pub enum RpcMessageError {
     #[error("Timeout")]
     Timeout,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pipeline_validate() {
+        let pipeline = ExecPipeline {
+            depends_on: vec![vec![], vec![0], vec![0, 1]],
+            on_failure: FailurePolicy::Continue,
+        };
+        assert!(pipeline.validate(3).is_ok());
+        assert!(pipeline.validate(2).is_err());
+
+        let cyclic = ExecPipeline {
+            depends_on: vec![vec![], vec![1]],
+            ..Default::default()
+        };
+        assert!(cyclic.validate(2).is_err());
+    }
+
+    #[test]
+    fn test_pipeline_blocked_by() {
+        let pipeline = ExecPipeline {
+            depends_on: vec![vec![], vec![], vec![0, 1]],
+            on_failure: FailurePolicy::Continue,
+        };
+        let failed = [1].iter().copied().collect::<HashSet<_>>();
+        assert_eq!(pipeline.blocked_by(0, &failed), None);
+        assert_eq!(pipeline.blocked_by(2, &failed), Some(1));
+        // Commands without declared dependencies follow the preceding one.
+        assert_eq!(pipeline.blocked_by(3, &failed), None);
+        assert_eq!(pipeline.blocked_by(2, &HashSet::new()), None);
+    }
+
+    #[test]
+    fn test_signed_usage_hash() {
+        let usage = |current_usage| ActivityUsage {
+            current_usage,
+            timestamp: 1_650_000_000,
+        };
+        let hash = SignedUsage::hash("activity", &usage(Some(vec![1.5, 0.1])));
+
+        use sha3::{Digest, Sha3_256};
+        let mut payload = Vec::new();
+        payload.extend_from_slice(&8u64.to_be_bytes());
+        payload.extend_from_slice(b"activity");
+        payload.extend_from_slice(&1_650_000_000i64.to_be_bytes());
+        payload.extend_from_slice(&2u64.to_be_bytes());
+        payload.extend_from_slice(&1.5f64.to_bits().to_be_bytes());
+        payload.extend_from_slice(&0.1f64.to_bits().to_be_bytes());
+        assert_eq!(hash, Sha3_256::digest(&payload).to_vec());
+
+        assert_ne!(hash, SignedUsage::hash("activity", &usage(None)));
+        assert_ne!(
+            SignedUsage::hash("activity", &usage(Some(vec![]))),
+            SignedUsage::hash("activity", &usage(None))
+        );
+    }
+}
@@ -41,6 +41,9 @@ pub struct Create {
     pub role: String,
     pub identity: NodeId,
     pub allow_origins: Vec<String>,
+    /// Restricts key to given REST API scopes (see [`Scope`]). Empty means unrestricted.
+    #[serde(default)]
+    pub scopes: Vec<String>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -95,6 +98,80 @@ pub struct AppKey {
     pub identity: NodeId,
     pub created_date: NaiveDateTime,
     pub allow_origins: Vec<String>,
+    #[serde(default)]
+    pub scopes: Vec<String>,
+}
+
+impl AppKey {
+    /// Checks whether this key grants access to REST API `path`.
+    /// Keys without scopes are unrestricted.
+    pub fn allows(&self, path: &str, read_only: bool) -> bool {
+        self.scopes.is_empty()
+            || self
+                .scopes
+                .iter()
+                .filter_map(|s| s.parse::<Scope>().ok())
+                .any(|scope| scope.allows(path, read_only))
+    }
+}
+
+/// REST API scope of an application key, written as `<api>` for full access
+/// or `<api>:read` for read-only (GET) access, e.g. `market`, `payment:read`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Scope {
+    pub api: String,
+    pub read_only: bool,
+}
+
+pub const SCOPE_APIS: &[&str] = &[
+    "market", "activity", "payment", "net", "gsb", "identity", "journal",
+];
+
+impl Scope {
+    fn allows(&self, path: &str, read_only: bool) -> bool {
+        if self.read_only && !read_only {
+            return false;
+        }
+        let path = path.trim_start_matches('/');
+        match self.api.as_str() {
+            // `/me` endpoint and GSB bridge are bound directly to the root of the REST API.
+            "identity" => path == "me" || path.starts_with("identity-api/"),
+            "gsb" => path.starts_with("gsb-api/") || path.starts_with("_gsb/"),
+            api => path
+                .strip_prefix(api)
+                .map(|rest| rest.starts_with("-api/"))
+                .unwrap_or(false),
+        }
+    }
+}
+
+impl std::str::FromStr for Scope {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (api, read_only) = match s.split_once(':') {
+            Some((api, "read")) => (api, true),
+            Some((_, access)) => {
+                return Err(Error::bad_request(format!(
+                    "invalid access `{}` in scope `{}`, only `read` is supported",
+                    access, s
+                )))
+            }
+            None => (s, false),
+        };
+        if !SCOPE_APIS.contains(&api) {
+            return Err(Error::bad_request(format!(
+                "unknown api `{}` in scope `{}`, expected one of: {}",
+                api,
+                s,
+                SCOPE_APIS.join(", ")
+            )));
+        }
+        Ok(Scope {
+            api: api.to_string(),
+            read_only,
+        })
+    }
 }
 
 impl RpcMessage for Create {
@@ -158,3 +235,54 @@ pub mod event {
         type Error = Error;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(scopes: &[&str]) -> AppKey {
+        AppKey {
+            name: "test".into(),
+            key: "key".into(),
+            role: DEFAULT_ROLE.into(),
+            identity: Default::default(),
+            created_date: chrono::Utc::now().naive_utc(),
+            allow_origins: vec![],
+            scopes: scopes.iter().map(ToString::to_string).collect(),
+        }
+    }
+
+    #[test]
+    fn test_unrestricted_key() {
+        let key = key(&[]);
+        assert!(key.allows("/payment-api/v1/allocations", false));
+        assert!(key.allows("/me", true));
+    }
+
+    #[test]
+    fn test_scoped_key() {
+        let key = key(&["market", "payment:read"]);
+        assert!(key.allows("/market-api/v1/offers", false));
+        assert!(key.allows("/payment-api/v1/invoices", true));
+        assert!(!key.allows("/payment-api/v1/allocations", false));
+        assert!(!key.allows("/activity-api/v1/activity", true));
+        assert!(!key.allows("/marketing-api/v1/offers", true));
+
+        let key = key(&["journal:read"]);
+        assert!(key.allows("/journal-api/v1/events", true));
+        assert!(!key.allows("/payment-api/v1/invoices", true));
+    }
+
+    #[test]
+    fn test_parse_scope() {
+        assert_eq!(
+            "payment:read".parse::<Scope>().unwrap(),
+            Scope {
+                api: "payment".into(),
+                read_only: true
+            }
+        );
+        assert!("payment:write".parse::<Scope>().is_err());
+        assert!("wallet".parse::<Scope>().is_err());
+    }
+}
@@ -11,6 +11,10 @@ pub fn driver_bus_id<T: Display>(driver_name: T) -> String {
     format!("/local/driver/{}", driver_name)
 }
 
+/// Version of the protocol between payment service and drivers running as separate
+/// processes. Bumped on changes incompatible with drivers built for older versions.
+pub const PROTOCOL_VERSION: u32 = 1;
+
 // ************************** ERROR **************************
 
 #[derive(Clone, Debug, Serialize, Deserialize, thiserror::Error)]
@@ -43,6 +47,32 @@ bitflags! {
     }
 }
 
+// ************************** CAPABILITIES **************************
+
+bitflags! {
+    /// Optional operations a driver supports. Other operations (`Init`, `GetAccountBalance`,
+    /// `SchedulePayment`, `VerifyPayment`, `ValidateAllocation`, `SignPayment`,
+    /// `VerifySignature`, `ShutDown`, `Ping`) are required.
+    #[derive(Serialize, Deserialize)]
+    pub struct DriverCapabilities : usize {
+        const NONE = 0b00000;
+        const FUND = 0b00001;
+        const TRANSFER = 0b00010;
+        const ENTER = 0b00100;
+        const EXIT = 0b01000;
+        const GAS_BALANCE = 0b10000;
+        const READINESS = 0b100000;
+        const FEE_ESTIMATE = 0b1000000;
+        const ALL = Self::FUND.bits
+            | Self::TRANSFER.bits
+            | Self::ENTER.bits
+            | Self::EXIT.bits
+            | Self::GAS_BALANCE.bits
+            | Self::READINESS.bits
+            | Self::FEE_ESTIMATE.bits;
+    }
+}
+
 // ************************** PAYMENT **************************
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -124,6 +154,153 @@ impl RpcMessage for GetAccountGasBalance {
     type Error = GenericError;
 }
 
+// ************************** GET TOKEN INFO **************************
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GetTokenInfo {
+    platform: String,
+}
+
+impl GetTokenInfo {
+    pub fn new(platform: String) -> Self {
+        Self { platform }
+    }
+}
+
+impl GetTokenInfo {
+    pub fn platform(&self) -> String {
+        self.platform.clone()
+    }
+}
+
+impl RpcMessage for GetTokenInfo {
+    const ID: &'static str = "GetTokenInfo";
+    type Item = TokenInfo;
+    type Error = GenericError;
+}
+
+/// Token used for payments on a platform, as reported by its contract.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TokenInfo {
+    pub symbol: String,
+    pub decimals: u32,
+}
+
+// ************************** GET READINESS **************************
+
+/// Checks whether the account has funds to send payments on `platform`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GetReadiness {
+    address: String,
+    platform: String,
+    /// Amount of tokens to be spent, any positive balance is enough when not given.
+    #[serde(default)]
+    amount: Option<BigDecimal>,
+}
+
+impl GetReadiness {
+    pub fn new(address: String, platform: String) -> Self {
+        Self {
+            address,
+            platform,
+            amount: None,
+        }
+    }
+    pub fn with_amount(mut self, amount: Option<BigDecimal>) -> Self {
+        self.amount = amount;
+        self
+    }
+    pub fn address(&self) -> String {
+        self.address.clone()
+    }
+    pub fn platform(&self) -> String {
+        self.platform.clone()
+    }
+    pub fn amount(&self) -> Option<BigDecimal> {
+        self.amount.clone()
+    }
+}
+
+impl RpcMessage for GetReadiness {
+    const ID: &'static str = "GetReadiness";
+    type Item = Readiness;
+    type Error = GenericError;
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FundsCheck {
+    pub currency: String,
+    pub required: BigDecimal,
+    pub available: BigDecimal,
+}
+
+impl FundsCheck {
+    pub fn sufficient(&self) -> bool {
+        self.available > BigDecimal::from(0) && self.available >= self.required
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Readiness {
+    pub network: String,
+    pub ready: bool,
+    pub token: FundsCheck,
+    /// Native currency paying for gas, `required` is the estimated cost of a single transfer.
+    /// `None` when payments do not need gas on the account, e.g. they are relayed.
+    pub gas: Option<FundsCheck>,
+    /// Ways to get the missing funds.
+    pub suggestions: Vec<String>,
+}
+
+// ************************** GET FEE ESTIMATE **************************
+
+/// Estimates current cost of settling `payments` payments on `platform`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GetFeeEstimate {
+    platform: String,
+    payments: u32,
+}
+
+impl GetFeeEstimate {
+    pub fn new(platform: String, payments: u32) -> Self {
+        Self { platform, payments }
+    }
+    pub fn platform(&self) -> String {
+        self.platform.clone()
+    }
+    pub fn payments(&self) -> u32 {
+        self.payments
+    }
+}
+
+impl RpcMessage for GetFeeEstimate {
+    const ID: &'static str = "GetFeeEstimate";
+    type Item = FeeEstimate;
+    type Error = GenericError;
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeEstimate {
+    pub platform: String,
+    pub network: String,
+    /// Native currency paying for gas.
+    pub currency: String,
+    /// Current gas price in gwei.
+    pub gas_price: BigDecimal,
+    /// Gas used by a typical payment.
+    pub gas_per_payment: u64,
+    /// Cost of all payments in `currency`.
+    pub cost: BigDecimal,
+    pub fiat_currency: String,
+    /// Price of `currency`, `None` when it couldn't be fetched.
+    pub fiat_price: Option<BigDecimal>,
+    /// Cost of all payments in `fiat_currency`.
+    pub fiat_cost: Option<BigDecimal>,
+}
+
 // ************************** GET TRANSACTION BALANCE **************************
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -199,6 +376,8 @@ pub struct Fund {
     address: String,
     network: Option<String>,
     token: Option<String>,
+    #[serde(default)]
+    progress_id: Option<String>,
 }
 
 impl Fund {
@@ -207,8 +386,14 @@ impl Fund {
             address,
             network,
             token,
+            progress_id: None,
         }
     }
+    /// Reports progress of the operation, see [`GetProgress`].
+    pub fn with_progress_id(mut self, progress_id: Option<String>) -> Self {
+        self.progress_id = progress_id;
+        self
+    }
     pub fn address(&self) -> String {
         self.address.clone()
     }
@@ -218,6 +403,9 @@ impl Fund {
     pub fn token(&self) -> Option<String> {
         self.token.clone()
     }
+    pub fn progress_id(&self) -> Option<String> {
+        self.progress_id.clone()
+    }
 }
 
 impl RpcMessage for Fund {
@@ -226,6 +414,37 @@ impl RpcMessage for Fund {
     type Error = GenericError;
 }
 
+// ************************** PROGRESS **************************
+
+/// Polls progress of a long-running operation (e.g. `Fund`, `Init`) started
+/// with the same `progress_id`. `None` until the driver reports the first stage.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetProgress {
+    pub progress_id: String,
+}
+
+impl RpcMessage for GetProgress {
+    const ID: &'static str = "GetProgress";
+    type Item = Option<Progress>;
+    type Error = GenericError;
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Progress {
+    /// Current stage, counted from 1.
+    pub step: u32,
+    pub total_steps: u32,
+    pub message: String,
+    /// Confirmations of the transaction awaited in the current stage.
+    pub confirmations: Option<(u64, u64)>,
+    /// Estimated time left of the current stage.
+    pub eta: Option<Duration>,
+    pub finished: bool,
+    pub updated_at: DateTime<Utc>,
+}
+
 // ************************** INIT **************************
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -234,6 +453,8 @@ pub struct Init {
     network: Option<String>,
     token: Option<String>,
     mode: AccountMode,
+    #[serde(default)]
+    progress_id: Option<String>,
 }
 
 impl Init {
@@ -248,8 +469,14 @@ impl Init {
             network,
             token,
             mode,
+            progress_id: None,
         }
     }
+    /// Reports progress of the operation, see [`GetProgress`].
+    pub fn with_progress_id(mut self, progress_id: Option<String>) -> Self {
+        self.progress_id = progress_id;
+        self
+    }
     pub fn address(&self) -> String {
         self.address.clone()
     }
@@ -262,6 +489,9 @@ impl Init {
     pub fn mode(&self) -> AccountMode {
         self.mode
     }
+    pub fn progress_id(&self) -> Option<String> {
+        self.progress_id.clone()
+    }
 }
 
 impl RpcMessage for Init {
@@ -279,6 +509,8 @@ pub struct SchedulePayment {
     recipient: String,
     platform: String,
     due_date: DateTime<Utc>,
+    #[serde(default)]
+    priority: PaymentPriority,
 }
 
 impl SchedulePayment {
@@ -295,9 +527,15 @@ impl SchedulePayment {
             recipient,
             platform,
             due_date,
+            priority: Default::default(),
         }
     }
 
+    pub fn with_priority(mut self, priority: PaymentPriority) -> Self {
+        self.priority = priority;
+        self
+    }
+
     pub fn amount(&self) -> BigDecimal {
         self.amount.clone()
     }
@@ -317,6 +555,10 @@ impl SchedulePayment {
     pub fn due_date(&self) -> DateTime<Utc> {
         self.due_date
     }
+
+    pub fn priority(&self) -> PaymentPriority {
+        self.priority
+    }
 }
 
 impl RpcMessage for SchedulePayment {
@@ -325,6 +567,70 @@ impl RpcMessage for SchedulePayment {
     type Error = GenericError;
 }
 
+/// Class of a scheduled payment. Pending payments are sent in order of priority,
+/// the ones due earlier first within the same priority.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    strum_macros::Display,
+    strum_macros::EnumString,
+    strum_macros::EnumVariantNames,
+)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum PaymentPriority {
+    /// Invoice settlements.
+    High = 0,
+    /// Debit notes and payments without a specific class.
+    Normal = 1,
+    /// Payments which can wait, e.g. test transfers.
+    Low = 2,
+}
+
+impl Default for PaymentPriority {
+    fn default() -> Self {
+        PaymentPriority::Normal
+    }
+}
+
+// ************************** SET PAYMENT PRIORITY **************************
+
+/// Changes priority of a scheduled payment, which was not sent yet.
+/// Returns `false` when there is no such pending payment.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetPaymentPriority {
+    pub order_id: String,
+    pub priority: PaymentPriority,
+}
+
+impl RpcMessage for SetPaymentPriority {
+    const ID: &'static str = "SetPaymentPriority";
+    type Item = bool;
+    type Error = GenericError;
+}
+
+// ************************** FLUSH PAYMENTS **************************
+
+/// Sends out pending payments on `network` in the next cycle, regardless of the payout schedule.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FlushPayments {
+    pub network: String,
+}
+
+impl RpcMessage for FlushPayments {
+    const ID: &'static str = "FlushPayments";
+    type Item = ();
+    type Error = GenericError;
+}
+
 // ************************** VALIDATE ALLOCATION **************************
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -450,6 +756,12 @@ pub struct Transfer {
     pub max_gas_price: Option<BigDecimal>,
     pub gas_limit: Option<u32>,
     pub gasless: bool,
+    /// Gas price strategy, overridden by explicit `gas_price` and `max_gas_price`.
+    #[serde(default)]
+    pub priority: Option<GasPriority>,
+    /// Reply only after the transaction is confirmed, with its receipt.
+    #[serde(default)]
+    pub wait: bool,
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -475,8 +787,40 @@ impl Transfer {
             max_gas_price,
             gas_limit,
             gasless,
+            priority: None,
+            wait: false,
         }
     }
+
+    pub fn with_priority(mut self, priority: Option<GasPriority>) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    pub fn with_wait(mut self, wait: bool) -> Self {
+        self.wait = wait;
+        self
+    }
+}
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    strum_macros::Display,
+    strum_macros::EnumString,
+    strum_macros::EnumVariantNames,
+)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum GasPriority {
+    Slow,
+    Fast,
+    Express,
 }
 
 impl RpcMessage for Transfer {
@@ -485,6 +829,115 @@ impl RpcMessage for Transfer {
     type Error = GenericError;
 }
 
+// ************************** BRIDGE **************************
+
+/// Moves tokens of `sender` between a root chain and its sidechain, e.g. GLM
+/// from Ethereum mainnet to Polygon. Returns the bridge transfer ID, the transfer
+/// continues in the background, see [`GetBridgeTransfers`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Bridge {
+    sender: String,
+    amount: BigDecimal,
+    from_network: String,
+    /// Counterpart of `from_network` when not given.
+    #[serde(default)]
+    to_network: Option<String>,
+}
+
+impl Bridge {
+    pub fn new(
+        sender: String,
+        amount: BigDecimal,
+        from_network: String,
+        to_network: Option<String>,
+    ) -> Self {
+        Self {
+            sender,
+            amount,
+            from_network,
+            to_network,
+        }
+    }
+    pub fn sender(&self) -> String {
+        self.sender.clone()
+    }
+    pub fn amount(&self) -> BigDecimal {
+        self.amount.clone()
+    }
+    pub fn from_network(&self) -> String {
+        self.from_network.clone()
+    }
+    pub fn to_network(&self) -> Option<String> {
+        self.to_network.clone()
+    }
+}
+
+impl RpcMessage for Bridge {
+    const ID: &'static str = "Bridge";
+    type Item = String; // Bridge transfer ID
+    type Error = GenericError;
+}
+
+/// Lists bridge transfers, newest first.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetBridgeTransfers {
+    pub sender: Option<String>,
+    pub bridge_id: Option<String>,
+}
+
+impl RpcMessage for GetBridgeTransfers {
+    const ID: &'static str = "GetBridgeTransfers";
+    type Item = Vec<BridgeTransfer>;
+    type Error = GenericError;
+}
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    strum_macros::Display,
+    strum_macros::EnumString,
+    strum_macros::EnumVariantNames,
+)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum BridgeStatus {
+    /// Transaction on the source network is not confirmed yet.
+    Pending,
+    /// Withdrawal is burnt on the sidechain, waiting for its checkpoint on the root chain.
+    Checkpoint,
+    /// Exit transaction on the root chain is not confirmed yet.
+    Exiting,
+    /// Deposit is confirmed on the root chain, tokens arrive on the sidechain
+    /// with the next state sync.
+    Deposited,
+    Completed,
+    Failed,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BridgeTransfer {
+    pub bridge_id: String,
+    pub sender: String,
+    pub amount: BigDecimal,
+    pub from_network: String,
+    pub to_network: String,
+    pub status: BridgeStatus,
+    /// Hash of the transaction on `from_network`.
+    pub source_tx: Option<String>,
+    /// Hash of the transaction on `to_network`, withdrawals only.
+    pub target_tx: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub error: Option<String>,
+}
+
 // ************************ SIGN PAYMENT ************************
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -541,6 +994,18 @@ impl RpcMessage for ShutDown {
     type Error = GenericError;
 }
 
+// ************************* PING *************************
+
+/// Liveness check sent by payment service to drivers running as separate processes.
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+pub struct Ping {}
+
+impl RpcMessage for Ping {
+    const ID: &'static str = "Ping";
+    type Item = ();
+    type Error = GenericError;
+}
+
 // ************************* GAS DETAILS *************************
 
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
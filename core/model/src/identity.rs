@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumString, EnumVariantNames};
 use thiserror::Error;
 use ya_client_model::NodeId;
 use ya_service_bus::RpcMessage;
@@ -22,6 +23,8 @@ pub enum Error {
     BadKeyStoreFormat(String),
     #[error("invalid password")]
     InvalidPassword,
+    #[error("too many failed unlock attempts, try again later")]
+    UnlockBlocked,
 }
 
 impl Error {
@@ -38,6 +41,32 @@ impl Error {
     }
 }
 
+/// Purpose an identity is used for. Roles without explicitly assigned
+/// identity are served by the default identity.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    Display,
+    EnumString,
+    EnumVariantNames,
+)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum IdentityRole {
+    /// Receives and sends payments.
+    Payment,
+    /// Identifies the node in the network, market and VPN. Takes effect after restart.
+    NodeId,
+    /// Signs files with `yagna id sign`, unless other identity is given.
+    Signing,
+}
+
 /// Lists identities.
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct List {}
@@ -50,6 +79,8 @@ pub struct IdentityInfo {
     pub node_id: NodeId,
     pub is_locked: bool,
     pub is_default: bool,
+    #[serde(default)]
+    pub roles: Vec<IdentityRole>,
 }
 
 impl RpcMessage for List {
@@ -63,6 +94,17 @@ impl RpcMessage for List {
 pub struct CreateGenerated {
     pub alias: Option<String>,
     pub from_keystore: Option<String>,
+    /// Registers key held by external signing service instead of storing it locally.
+    #[serde(default)]
+    pub remote_signer: Option<RemoteSigner>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteSigner {
+    /// JSON-RPC endpoint of the signing service.
+    pub url: String,
+    pub address: NodeId,
 }
 
 impl RpcMessage for CreateGenerated {
@@ -78,6 +120,7 @@ pub enum Get {
     ByNodeId(NodeId),
     ByAlias(String),
     ByDefault,
+    ByRole(IdentityRole),
 }
 
 impl RpcMessage for Get {
@@ -121,6 +164,20 @@ impl RpcMessage for Update {
     type Error = Error;
 }
 
+/// Assigns `role` to given identity. `None` restores the default identity for the role.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetRole {
+    pub role: IdentityRole,
+    pub node_id: Option<NodeId>,
+}
+
+impl RpcMessage for SetRole {
+    const ID: &'static str = "SetRole";
+    type Item = Ack;
+    type Error = Error;
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 #[non_exhaustive]
@@ -201,6 +258,35 @@ impl RpcMessage for Sign {
     type Error = Error;
 }
 
+/// EIP-712 signing domain. Fields set to `None` are left out of the `EIP712Domain` type.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Eip712Domain {
+    pub name: Option<String>,
+    pub version: Option<String>,
+    pub chain_id: Option<u64>,
+    pub verifying_contract: Option<[u8; 20]>,
+    pub salt: Option<[u8; 32]>,
+}
+
+/// Signs EIP-712 typed data: `keccak256(0x1901 || domainSeparator || structHash)`.
+///
+/// The signature has the same `v || r || s` layout as for [`Sign`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignTypedData {
+    pub node_id: NodeId,
+    pub domain: Eip712Domain,
+    /// `hashStruct` of the message, 32 bytes.
+    pub struct_hash: Vec<u8>,
+}
+
+impl RpcMessage for SignTypedData {
+    const ID: &'static str = "SignTypedData";
+    type Item = Vec<u8>;
+    type Error = Error;
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Subscribe {
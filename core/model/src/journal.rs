@@ -0,0 +1,188 @@
+//! Event journal service bus API.
+//!
+//! Services record notable events (agreements, activities, payments, identity changes)
+//! so they can be queried later. Journal is best-effort: recording never fails the
+//! operation being recorded.
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumString, EnumVariantNames};
+
+use ya_client_model::{ErrorMessage, NodeId};
+use ya_service_bus::{typed as bus, RpcMessage};
+
+pub const BUS_ID: &str = "/local/journal";
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    Display,
+    EnumString,
+    EnumVariantNames,
+)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum Category {
+    Market,
+    Activity,
+    Payment,
+    Identity,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Event {
+    pub timestamp: NaiveDateTime,
+    pub category: Category,
+    /// Kebab-case event name, e.g. `agreement-approved`.
+    pub kind: String,
+    /// Id of the object the event refers to (agreement, activity, invoice, ...).
+    pub subject: Option<String>,
+    /// Identity owning the object.
+    pub node_id: Option<NodeId>,
+    pub details: Option<serde_json::Value>,
+}
+
+impl Event {
+    pub fn new(category: Category, kind: impl ToString) -> Self {
+        Event {
+            timestamp: Utc::now().naive_utc(),
+            category,
+            kind: kind.to_string(),
+            subject: None,
+            node_id: None,
+            details: None,
+        }
+    }
+
+    pub fn subject(mut self, subject: impl ToString) -> Self {
+        self.subject = Some(subject.to_string());
+        self
+    }
+
+    pub fn node_id(mut self, node_id: NodeId) -> Self {
+        self.node_id = Some(node_id);
+        self
+    }
+
+    pub fn details(mut self, details: serde_json::Value) -> Self {
+        self.details = Some(details);
+        self
+    }
+
+    /// Sends the event to the journal. Failures are only logged.
+    pub async fn record(self) {
+        let kind = self.kind.clone();
+        match bus::service(BUS_ID).send(Record { event: self }).await {
+            Ok(Ok(())) => (),
+            Ok(Err(e)) => log::debug!("Failed to record journal event {kind}: {e}"),
+            Err(e) => log::debug!("Failed to record journal event {kind}: {e}"),
+        }
+    }
+}
+
+/// Appends event to the journal.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Record {
+    pub event: Event,
+}
+
+impl RpcMessage for Record {
+    const ID: &'static str = "Record";
+    type Item = ();
+    type Error = ErrorMessage;
+}
+
+/// Journal entry as returned by the query API.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Entry {
+    pub id: i64,
+    pub timestamp: NaiveDateTime,
+    pub category: Category,
+    pub kind: String,
+    pub subject: Option<String>,
+    pub node_id: Option<NodeId>,
+    pub details: Option<serde_json::Value>,
+}
+
+/// Lists journal entries in the order they were recorded, all filters are optional.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListEvents {
+    pub category: Option<Category>,
+    pub kind: Option<String>,
+    pub subject: Option<String>,
+    pub node_id: Option<NodeId>,
+    pub after_timestamp: Option<DateTime<Utc>>,
+    pub max_items: Option<u32>,
+}
+
+impl RpcMessage for ListEvents {
+    const ID: &'static str = "ListEvents";
+    type Item = Vec<Entry>;
+    type Error = ErrorMessage;
+}
+
+/// Number of own Offers currently subscribed on the market.
+/// Sampled periodically by the market, so journal can tell how long the node was available.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordAvailability {
+    pub offers: u32,
+    /// Time until the next sample is expected.
+    pub interval_secs: u32,
+}
+
+impl RpcMessage for RecordAvailability {
+    const ID: &'static str = "RecordAvailability";
+    type Item = ();
+    type Error = ErrorMessage;
+}
+
+/// Provider uptime statistics for each of the rolling windows, given in hours.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetUptime {
+    pub window_hours: Vec<u32>,
+}
+
+impl RpcMessage for GetUptime {
+    const ID: &'static str = "GetUptime";
+    type Item = Vec<UptimeWindow>;
+    type Error = ErrorMessage;
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UptimeWindow {
+    pub window_hours: u32,
+    /// Fraction of the window (since the first availability sample, if later)
+    /// in which at least one Offer was published. `None` if nothing was sampled.
+    pub offer_published: Option<f64>,
+    /// Agreements approved by us as a Provider.
+    pub agreements_served: u64,
+    /// Activities created on us as a Provider.
+    pub activities_started: u64,
+    /// Activities which reported an error or were destroyed as unresponsive.
+    pub activities_failed: u64,
+}
+
+impl UptimeWindow {
+    pub fn activity_success_rate(&self) -> Option<f64> {
+        if self.activities_started == 0 {
+            return None;
+        }
+        let succeeded = self
+            .activities_started
+            .saturating_sub(self.activities_failed);
+        Some(succeeded as f64 / self.activities_started as f64)
+    }
+}
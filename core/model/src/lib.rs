@@ -13,6 +13,9 @@ pub mod driver;
 #[cfg(feature = "identity")]
 pub mod identity;
 
+#[cfg(feature = "journal")]
+pub mod journal;
+
 #[cfg(feature = "market")]
 pub mod market;
 
@@ -1,9 +1,11 @@
 //! Market service bus API.
+use bigdecimal::BigDecimal;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use ya_client_model::market::{agreement::State, Role};
-pub use ya_client_model::market::{Agreement, AgreementListEntry};
+use ya_client_model::market::{agreement::State, Reason, Role};
+pub use ya_client_model::market::{Agreement, AgreementListEntry, Proposal};
+use ya_client_model::NodeId;
 use ya_service_bus::RpcMessage;
 
 /// Public Market bus address.
@@ -58,6 +60,203 @@ impl RpcMessage for ListAgreements {
     type Error = RpcMessageError;
 }
 
+/// Returns chain of Proposals, that ended with the Agreement.
+/// Should be sent to `local::BUS_ID`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetNegotiationHistory {
+    pub agreement_id: String,
+}
+
+impl RpcMessage for GetNegotiationHistory {
+    const ID: &'static str = "GetNegotiationHistory";
+    type Item = Vec<Proposal>;
+    type Error = RpcMessageError;
+}
+
+/// Counts terminated Agreements by our role, terminating party and class
+/// of the termination reason.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTerminationStats {
+    pub after_date: Option<DateTime<Utc>>,
+}
+
+impl RpcMessage for GetTerminationStats {
+    const ID: &'static str = "GetTerminationStats";
+    type Item = Vec<TerminationStats>;
+    type Error = RpcMessageError;
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TerminationStats {
+    /// Our role in the Agreements.
+    pub role: Role,
+    pub terminator: Role,
+    /// Class of the termination reason, e.g. `payment-timeout`.
+    pub reason: String,
+    pub count: u32,
+}
+
+/// Result of cooperation with other node, that affects its reputation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Outcome {
+    AgreementCompleted,
+    PaymentOnTime,
+    PaymentLate,
+    ActivityFailed,
+}
+
+/// Records Outcome of cooperation with `node_id` in local reputation.
+/// Outcome is counted only once for the same `subject_id` (Agreement or Activity id).
+/// Should be sent to `local::BUS_ID`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordOutcome {
+    pub node_id: NodeId,
+    pub subject_id: String,
+    pub outcome: Outcome,
+}
+
+impl RpcMessage for RecordOutcome {
+    const ID: &'static str = "RecordOutcome";
+    type Item = ();
+    type Error = RpcMessageError;
+}
+
+/// Returns local reputation of the node. Should be sent to `local::BUS_ID`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetReputation {
+    pub node_id: NodeId,
+}
+
+impl RpcMessage for GetReputation {
+    const ID: &'static str = "GetReputation";
+    type Item = Reputation;
+    type Error = RpcMessageError;
+}
+
+/// Returns local reputation of all nodes with recorded Outcomes.
+/// Should be sent to `local::BUS_ID`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ListReputation {}
+
+impl RpcMessage for ListReputation {
+    const ID: &'static str = "ListReputation";
+    type Item = Vec<Reputation>;
+    type Error = RpcMessageError;
+}
+
+/// Terminates the Agreement on behalf of `node_id`, as REST API would.
+/// Should be sent to `local::BUS_ID`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TerminateAgreement {
+    pub agreement_id: String,
+    pub node_id: NodeId,
+    pub reason: Option<Reason>,
+}
+
+impl RpcMessage for TerminateAgreement {
+    const ID: &'static str = "TerminateAgreement";
+    type Item = ();
+    type Error = RpcMessageError;
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Reputation {
+    pub node_id: NodeId,
+    /// Score in range [0, 1]. Nodes without any history have score 0.5.
+    pub score: f64,
+    pub agreements_completed: u32,
+    pub payments_on_time: u32,
+    pub payments_late: u32,
+    pub activity_failures: u32,
+}
+
+/// Requestor's saved negotiation profile, from which Demands can be subscribed
+/// without rebuilding their properties and constraints.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DemandTemplate {
+    pub name: String,
+    pub owner_id: NodeId,
+    /// Demand properties, nested or with flat keys.
+    #[serde(default)]
+    pub properties: serde_json::Value,
+    #[serde(default)]
+    pub constraints: String,
+    /// Platform the Requestor pays with, e.g. `erc20-polygon-glm`.
+    pub payment_platform: Option<String>,
+    /// Demand expiration, counted from the subscription.
+    pub expiration_secs: Option<u64>,
+    /// Time the Requestor has for accepting each Debit Note.
+    pub debit_note_accept_timeout_secs: Option<u64>,
+    /// Amount the Requestor intends to spend per Agreement.
+    /// Not advertised to Providers.
+    pub budget: Option<BigDecimal>,
+    #[serde(default = "Utc::now")]
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Creates or replaces the Demand template. Should be sent to `local::BUS_ID`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SaveDemandTemplate(pub DemandTemplate);
+
+impl RpcMessage for SaveDemandTemplate {
+    const ID: &'static str = "SaveDemandTemplate";
+    type Item = DemandTemplate;
+    type Error = RpcMessageError;
+}
+
+/// Returns Demand templates of the node. Should be sent to `local::BUS_ID`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListDemandTemplates {
+    pub owner_id: NodeId,
+}
+
+impl RpcMessage for ListDemandTemplates {
+    const ID: &'static str = "ListDemandTemplates";
+    type Item = Vec<DemandTemplate>;
+    type Error = RpcMessageError;
+}
+
+/// Should be sent to `local::BUS_ID`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteDemandTemplate {
+    pub owner_id: NodeId,
+    pub name: String,
+}
+
+impl RpcMessage for DeleteDemandTemplate {
+    const ID: &'static str = "DeleteDemandTemplate";
+    type Item = ();
+    type Error = RpcMessageError;
+}
+
+/// Subscribes Demand built from the template, returns its subscription id.
+/// Should be sent to `local::BUS_ID`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubscribeDemandTemplate {
+    pub owner_id: NodeId,
+    pub name: String,
+}
+
+impl RpcMessage for SubscribeDemandTemplate {
+    const ID: &'static str = "SubscribeDemandTemplate";
+    type Item = String;
+    type Error = RpcMessageError;
+}
+
 /// Error message for market service bus API.
 #[derive(thiserror::Error, Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -183,6 +183,9 @@ pub mod local {
         pub seen: Duration,
         pub duration: Duration,
         pub ping: Duration,
+        /// Time since the last traffic, not known for relay server session.
+        #[serde(default)]
+        pub idle: Option<Duration>,
         pub metrics: StatusMetrics,
     }
 
@@ -288,6 +291,109 @@ pub mod local {
         type Error = GenericNetError;
     }
 
+    /// Checks which path (p2p or relay) is used to communicate with Node and why.
+    /// Connection will be established if it doesn't exist yet.
+    #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq, Hash)]
+    #[serde(rename_all = "camelCase")]
+    pub struct Diagnose {
+        pub node_id: NodeId,
+    }
+
+    impl RpcMessage for Diagnose {
+        const ID: &'static str = "Diagnose";
+        type Item = DiagnoseResponse;
+        type Error = StatusError;
+    }
+
+    /// Result of the last attempt to establish connection with Node.
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct ConnectAttempt {
+        /// `p2p` if hole punching succeeded, `relay` otherwise.
+        pub path: String,
+        pub duration: Duration,
+        /// Time elapsed since the attempt.
+        pub ago: Duration,
+        pub error: Option<String>,
+    }
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct DiagnoseResponse {
+        pub node_id: NodeId,
+        /// `p2p`, `relay` or `none` if Node is unreachable.
+        pub path: String,
+        pub reason: String,
+        pub relay_fallback: String,
+        pub public_address: Option<SocketAddr>,
+        pub remote_endpoints: Vec<SocketAddr>,
+        pub last_attempt: Option<ConnectAttempt>,
+        pub tcp_ping: Option<Duration>,
+        pub udp_ping: Option<Duration>,
+    }
+
+    /// Relay servers considered by the Node, with results of their last probe.
+    #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq, Hash)]
+    #[serde(rename_all = "camelCase")]
+    pub struct Relays {}
+
+    impl RpcMessage for Relays {
+        const ID: &'static str = "Relays";
+        type Item = Vec<RelayResponse>;
+        type Error = StatusError;
+    }
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct RelayResponse {
+        /// `host:port` as configured or resolved from SRV record.
+        pub host: String,
+        pub address: Option<SocketAddr>,
+        /// Session with this relay server is used by the Node.
+        pub selected: bool,
+        pub rtt: Option<Duration>,
+        /// Share of failed probes, in range [0, 1].
+        pub loss: f32,
+        /// Time elapsed since the last probe.
+        pub probed: Option<Duration>,
+        pub error: Option<String>,
+    }
+
+    /// Bytes of GSB messages exchanged with other Nodes since net service start.
+    #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq, Hash)]
+    #[serde(rename_all = "camelCase")]
+    pub struct Bandwidth {}
+
+    impl RpcMessage for Bandwidth {
+        const ID: &'static str = "Bandwidth";
+        type Item = BandwidthResponse;
+        type Error = StatusError;
+    }
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct PeerBandwidth {
+        pub node_id: NodeId,
+        pub tx_total: u64,
+        pub rx_total: u64,
+    }
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct ServiceBandwidth {
+        /// GSB address prefix, i.e. `/public/market` or `broadcast/<topic>`.
+        pub prefix: String,
+        pub tx_total: u64,
+        pub rx_total: u64,
+    }
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct BandwidthResponse {
+        pub peers: Vec<PeerBandwidth>,
+        pub services: Vec<ServiceBandwidth>,
+    }
+
     #[derive(Clone, Serialize, Deserialize)]
     #[serde(rename_all = "camelCase")]
     pub struct NewNeighbour;
@@ -19,7 +19,10 @@ pub enum RpcMessageError {
 
 pub mod local {
     use super::*;
-    use crate::driver::{AccountMode, GasDetails, PaymentConfirmation};
+    use crate::driver::{
+        AccountMode, DriverCapabilities, FeeEstimate, GasDetails, PaymentConfirmation,
+        PaymentPriority, Readiness,
+    };
     use bigdecimal::{BigDecimal, Zero};
     use chrono::{DateTime, Utc};
     use std::fmt::Display;
@@ -158,6 +161,10 @@ pub mod local {
         InvalidDefaultToken(String, String),
         #[error("Invalid default network specified: {0}")]
         InvalidDefaultNetwork(String),
+        #[error("Unsupported driver protocol version: {0}, expected {1}")]
+        UnsupportedProtocolVersion(u32, u32),
+        #[error("Driver already registered: {0}")]
+        AlreadyRegistered(String),
     }
 
     impl RpcMessage for RegisterDriver {
@@ -166,6 +173,26 @@ pub mod local {
         type Error = RegisterDriverError;
     }
 
+    /// Registers a driver running as a separate process connected to the service bus.
+    /// The driver binds its endpoints under `driver_bus_id(driver_name)` before registering.
+    /// It's unregistered once it stops answering `driver::Ping`, so it has to register
+    /// again after a restart.
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct RegisterExternalDriver {
+        pub driver_name: String,
+        /// `driver::PROTOCOL_VERSION` the driver was built with.
+        pub protocol_version: u32,
+        pub capabilities: DriverCapabilities,
+        pub details: DriverDetails,
+    }
+
+    impl RpcMessage for RegisterExternalDriver {
+        const ID: &'static str = "RegisterExternalDriver";
+        type Item = ();
+        type Error = RegisterDriverError;
+    }
+
     #[derive(Clone, Debug, Serialize, Deserialize)]
     pub struct UnregisterDriver(pub String);
 
@@ -225,6 +252,9 @@ pub mod local {
         pub recipient: String,
         pub order_ids: Vec<String>,
         pub confirmation: PaymentConfirmation,
+        /// Payment was only simulated in payments dry-run mode, nothing was transferred.
+        #[serde(default)]
+        pub simulated: bool,
     }
 
     impl RpcMessage for NotifyPayment {
@@ -321,6 +351,44 @@ pub mod local {
         }
     }
 
+    /// Checks whether `address` has funds to pay `amount` on the network.
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct GetReadiness {
+        pub address: String,
+        pub driver: String,
+        pub network: Option<String>,
+        pub amount: Option<BigDecimal>,
+    }
+
+    impl RpcMessage for GetReadiness {
+        const ID: &'static str = "GetReadiness";
+        type Item = Readiness;
+        type Error = GenericError;
+    }
+
+    /// Compares cost of settling `payments` payments on each platform `address` sends from.
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub struct CompareFees {
+        pub address: String,
+        pub payments: u32,
+    }
+
+    impl RpcMessage for CompareFees {
+        const ID: &'static str = "CompareFees";
+        type Item = FeeComparison;
+        type Error = GenericError;
+    }
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct FeeComparison {
+        pub payments: u32,
+        /// Estimates of platforms which reported one, cheapest first.
+        pub estimates: Vec<FeeEstimate>,
+        /// Platform with the lowest cost in fiat, `None` when costs can't be compared.
+        pub recommended: Option<String>,
+    }
+
     #[derive(Clone, Debug, Serialize, Deserialize)]
     pub struct GetAccounts {}
 
@@ -374,6 +442,60 @@ pub mod local {
         pub provider: InvoiceStatusNotes,
     }
 
+    /// Provider's unpaid accepted Invoices grouped by Requestor.
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct GetInvoiceAging {
+        pub owner_id: NodeId,
+    }
+
+    impl RpcMessage for GetInvoiceAging {
+        const ID: &'static str = "GetInvoiceAging";
+        type Item = Vec<RequestorAging>;
+        type Error = GenericError;
+    }
+
+    /// Unpaid amounts by time past the payment due date.
+    #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct RequestorAging {
+        pub requestor_id: NodeId,
+        pub unpaid_count: u64,
+        pub not_due: BigDecimal,
+        pub overdue_up_to_day: BigDecimal,
+        pub overdue_up_to_week: BigDecimal,
+        pub overdue_more: BigDecimal,
+        pub reminders_sent: u64,
+        /// Share of accepted Invoices settled without any reminder, in range [0, 1].
+        pub reliability: f64,
+    }
+
+    /// Provider's unpaid accepted Invoices, to diagnose why they aren't paid.
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct GetUnpaidInvoices {
+        pub owner_id: NodeId,
+    }
+
+    impl RpcMessage for GetUnpaidInvoices {
+        const ID: &'static str = "GetUnpaidInvoices";
+        type Item = Vec<UnpaidInvoice>;
+        type Error = GenericError;
+    }
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct UnpaidInvoice {
+        pub invoice_id: String,
+        pub agreement_id: String,
+        pub requestor_id: NodeId,
+        pub payment_platform: String,
+        pub payee_addr: String,
+        pub amount: BigDecimal,
+        pub payment_due_date: DateTime<Utc>,
+        pub reminders_sent: u64,
+    }
+
     #[derive(Clone, Debug, Serialize, Deserialize)]
     pub struct ValidateAllocation {
         pub platform: String,
@@ -430,6 +552,256 @@ pub mod local {
         type Error = GenericError;
     }
 
+    /// Check usage billed in a received Debit Note against usage signed by the Provider's ExeUnit.
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct VerifyDebitNoteUsage {
+        pub debit_note_id: String,
+        pub owner_id: NodeId,
+    }
+
+    impl RpcMessage for VerifyDebitNoteUsage {
+        const ID: &'static str = "VerifyDebitNoteUsage";
+        type Item = UsageVerification;
+        type Error = GenericError;
+    }
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct UsageVerification {
+        pub debit_note_id: String,
+        pub status: UsageVerificationStatus,
+        pub billed_usage: Option<Vec<f64>>,
+        pub signed_usage: Option<crate::activity::SignedUsage>,
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Display)]
+    #[serde(rename_all = "kebab-case")]
+    #[strum(serialize_all = "kebab-case")]
+    pub enum UsageVerificationStatus {
+        /// Billed usage doesn't exceed the signed usage.
+        Verified,
+        /// No signed usage was attached to the Debit Note.
+        Unsigned,
+        /// Signed usage isn't signed by the Debit Note issuer or is for another Activity.
+        InvalidSignature,
+        /// Billed usage exceeds the signed usage.
+        UsageExceeded,
+    }
+
+    /// Sets budget watchdog settings of the Agreement. Fields left empty keep current values.
+    #[derive(Clone, Debug, Default, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct SetAgreementBudget {
+        pub agreement_id: String,
+        pub owner_id: NodeId,
+        pub allocation_id: Option<String>,
+        pub max_amount: Option<BigDecimal>,
+        pub threshold: Option<f64>,
+        pub enabled: Option<bool>,
+    }
+
+    impl RpcMessage for SetAgreementBudget {
+        const ID: &'static str = "SetAgreementBudget";
+        type Item = AgreementBudget;
+        type Error = GenericError;
+    }
+
+    /// Requestor's Agreement is terminated when its amount due reaches `threshold`
+    /// of `max_amount`, or when the amount not yet scheduled for payment reaches
+    /// `threshold` of what remains in the Allocation.
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct AgreementBudget {
+        pub agreement_id: String,
+        pub owner_id: NodeId,
+        /// Allocation used to accept Debit Notes, set on acceptance when not given.
+        pub allocation_id: Option<String>,
+        pub max_amount: Option<BigDecimal>,
+        /// Share of the limit in range (0, 1]. Defaults to `PAYMENT_BUDGET_THRESHOLD`.
+        pub threshold: Option<f64>,
+        pub enabled: bool,
+        pub terminated_at: Option<DateTime<Utc>>,
+    }
+
+    /// Sets cost ceiling of the Requestor's Activity.
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct SetActivityBudget {
+        pub activity_id: String,
+        pub agreement_id: String,
+        pub owner_id: NodeId,
+        pub max_amount: BigDecimal,
+    }
+
+    impl RpcMessage for SetActivityBudget {
+        const ID: &'static str = "SetActivityBudget";
+        type Item = ActivityBudget;
+        type Error = GenericError;
+    }
+
+    /// Requestor's Activity is destroyed and its Agreement terminated once amount
+    /// due in Debit Notes of the Activity reaches `max_amount`.
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct ActivityBudget {
+        pub activity_id: String,
+        pub agreement_id: String,
+        pub owner_id: NodeId,
+        pub max_amount: BigDecimal,
+        pub terminated_at: Option<DateTime<Utc>>,
+    }
+
+    /// Sets auto-refill policy of the Allocation. Fields left empty keep current values.
+    #[derive(Clone, Debug, Default, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct SetAllocationRefill {
+        pub allocation_id: String,
+        pub owner_id: NodeId,
+        pub amount: Option<BigDecimal>,
+        pub threshold: Option<BigDecimal>,
+        pub max_total_amount: Option<BigDecimal>,
+        pub enabled: Option<bool>,
+    }
+
+    impl RpcMessage for SetAllocationRefill {
+        const ID: &'static str = "SetAllocationRefill";
+        type Item = AllocationRefill;
+        type Error = GenericError;
+    }
+
+    /// Requestor's Allocation is topped up by `amount` from unreserved funds of its account
+    /// whenever its remaining amount drops below `threshold`, as long as its total amount
+    /// stays within `max_total_amount`.
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct AllocationRefill {
+        pub allocation_id: String,
+        pub owner_id: NodeId,
+        pub amount: BigDecimal,
+        /// Defaults to `amount`.
+        pub threshold: BigDecimal,
+        pub max_total_amount: Option<BigDecimal>,
+        /// Sum of all top-ups so far.
+        pub refilled_amount: BigDecimal,
+        pub enabled: bool,
+        pub refilled_at: Option<DateTime<Utc>>,
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Display, EnumString)]
+    #[serde(rename_all = "kebab-case")]
+    #[strum(serialize_all = "kebab-case")]
+    pub enum DocumentType {
+        Invoice,
+        DebitNote,
+    }
+
+    /// Rejected Invoice or Debit Note waits for the issuer's response (`Open`),
+    /// then for the recipient's decision (`Responded`). Accepting or cancelling
+    /// the document resolves the dispute.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Display, EnumString)]
+    #[serde(rename_all = "kebab-case")]
+    #[strum(serialize_all = "kebab-case")]
+    pub enum DisputeStatus {
+        Open,
+        Responded,
+        Resolved,
+    }
+
+    #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct DisputeResponse {
+        pub message: Option<String>,
+        /// Document issued by the issuer to replace the disputed one.
+        pub amended_document_id: Option<String>,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct Dispute {
+        pub document_id: String,
+        pub document_type: DocumentType,
+        pub owner_id: NodeId,
+        pub peer_id: NodeId,
+        pub status: DisputeStatus,
+        pub rejection: Rejection,
+        pub response: Option<DisputeResponse>,
+        pub created_at: DateTime<Utc>,
+        pub updated_at: DateTime<Utc>,
+    }
+
+    /// Lists disputes of Invoices and Debit Notes issued or received by `owner_id`.
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct ListDisputes {
+        pub owner_id: NodeId,
+        pub status: Option<DisputeStatus>,
+    }
+
+    impl RpcMessage for ListDisputes {
+        const ID: &'static str = "ListDisputes";
+        type Item = Vec<Dispute>;
+        type Error = GenericError;
+    }
+
+    /// Moves active Allocations from the `from` payment platform to `to`.
+    /// Without both, every configured platform alias is applied.
+    #[derive(Clone, Debug, Default, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct MigratePlatform {
+        pub from: Option<String>,
+        pub to: Option<String>,
+        pub dry_run: bool,
+    }
+
+    impl RpcMessage for MigratePlatform {
+        const ID: &'static str = "MigratePlatform";
+        type Item = Vec<PlatformMigration>;
+        type Error = GenericError;
+    }
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct PlatformMigration {
+        pub from: String,
+        pub to: String,
+        pub allocations: usize,
+    }
+
+    /// Enables or disables sending and receiving of the account on the network.
+    /// Modes left `None` are kept. The change is persisted in the accounts file.
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct SetAccountMode {
+        pub address: String,
+        pub driver: String,
+        pub network: Option<String>,
+        pub token: Option<String>,
+        pub send: Option<bool>,
+        pub receive: Option<bool>,
+    }
+
+    impl RpcMessage for SetAccountMode {
+        const ID: &'static str = "SetAccountMode";
+        type Item = Account;
+        type Error = GenericError;
+    }
+
+    /// Changes priority of pending payments of an Invoice or a Debit Note.
+    /// Returns number of re-prioritized payments, already sent ones are not affected.
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct PrioritizePayments {
+        pub document_id: String,
+        pub priority: PaymentPriority,
+    }
+
+    impl RpcMessage for PrioritizePayments {
+        const ID: &'static str = "PrioritizePayments";
+        type Item = usize;
+        type Error = GenericError;
+    }
+
     /// Experimental. In future releases this might change or be removed.
     #[derive(
         EnumString,
@@ -578,6 +950,21 @@ pub mod public {
         type Error = SendError;
     }
 
+    /// Usage signed by the ExeUnit, attached to a Debit Note already sent.
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct SendDebitNoteUsage {
+        pub debit_note_id: String,
+        pub recipient_id: NodeId,
+        pub signed_usage: crate::activity::SignedUsage,
+    }
+
+    impl RpcMessage for SendDebitNoteUsage {
+        const ID: &'static str = "SendDebitNoteUsage";
+        type Item = Ack;
+        type Error = SendError;
+    }
+
     #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
     #[serde(rename_all = "camelCase")]
     pub struct AcceptDebitNote {
@@ -607,6 +994,7 @@ pub mod public {
     pub struct RejectDebitNote {
         pub debit_note_id: String,
         pub rejection: Rejection,
+        pub issuer_id: NodeId,
     }
 
     impl RpcMessage for RejectDebitNote {
@@ -666,6 +1054,7 @@ pub mod public {
     pub struct RejectInvoice {
         pub invoice_id: String,
         pub rejection: Rejection,
+        pub issuer_id: NodeId,
     }
 
     impl RpcMessage for RejectInvoice {
@@ -674,6 +1063,23 @@ pub mod public {
         type Error = AcceptRejectError;
     }
 
+    // *************************** DISPUTE ****************************
+    /// Issuer's response to the rejection of an Invoice or a Debit Note.
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct RespondToDispute {
+        pub document_id: String,
+        pub document_type: local::DocumentType,
+        pub recipient_id: NodeId,
+        pub response: local::DisputeResponse,
+    }
+
+    impl RpcMessage for RespondToDispute {
+        const ID: &'static str = "RespondToDispute";
+        type Item = Ack;
+        type Error = AcceptRejectError;
+    }
+
     #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
     #[serde(rename_all = "camelCase")]
     pub struct CancelInvoice {
@@ -687,6 +1093,22 @@ pub mod public {
         type Error = CancelError;
     }
 
+    /// Provider reminds the Requestor of an accepted Invoice not paid by its due date.
+    /// `reminder` is the escalation level, starting from 1.
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct RemindInvoice {
+        pub invoice_id: String,
+        pub agreement_id: String,
+        pub reminder: u32,
+    }
+
+    impl RpcMessage for RemindInvoice {
+        const ID: &'static str = "RemindInvoice";
+        type Item = Ack;
+        type Error = SendError;
+    }
+
     // *************************** PAYMENT ****************************
     #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
     pub struct SendPayment {
@@ -10,11 +10,6 @@ pub struct CentralBusHandler<C, E> {
 
 impl<C, E> CentralBusHandler<C, E> {
     pub fn new(call_handler: C, event_handler: E) -> (Self, oneshot::Receiver<()>) {
-        // Initialize counters to 0 value. Otherwise they won't appear on metrics endpoint
-        // until first change to value will be made.
-        metrics::counter!("net.connect", 0);
-        metrics::counter!("net.disconnect", 0);
-
         let (tx, rx) = oneshot::channel();
         (
             Self {
@@ -21,6 +21,7 @@ use crate::bcast::BCastService;
 use crate::central::handler::CentralBusHandler;
 use crate::central::SUBSCRIPTIONS;
 use crate::config::Config;
+use crate::metric_keys;
 
 const CENTRAL_ADDR_ENV_VAR: &str = "CENTRAL_NET_HOST";
 
@@ -378,15 +379,15 @@ where
             Ok(dc_rx) => {
                 if let Some(start) = reconnect.borrow_mut().last_disconnect {
                     let end = Instant::now();
-                    metrics::timing!("net.reconnect.time", start, end);
+                    metrics::timing!(metric_keys::RECONNECT_TIME, start, end);
                 }
                 reconnect.replace(Default::default());
-                metrics::counter!("net.connect", 1);
+                metrics::counter!(metric_keys::CONNECT, 1);
 
                 let reconnect_clone = reconnect.clone();
                 tokio::task::spawn_local(async move {
                     if dc_rx.await.is_ok() {
-                        metrics::counter!("net.disconnect", 1);
+                        metrics::counter!(metric_keys::DISCONNECT, 1);
                         reconnect_clone.borrow_mut().last_disconnect = Some(Instant::now());
                         log::warn!("Handlers disconnected");
                         (*unbind_clone.borrow_mut())().await;
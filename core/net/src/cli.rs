@@ -42,6 +42,16 @@ pub enum NetCommand {
     },
     /// Disconnect Node
     Disconnect { node_id: String },
+    /// Show bytes exchanged with other Nodes
+    Bandwidth {
+        /// Group by GSB service instead of Node
+        #[structopt(long)]
+        services: bool,
+    },
+    /// Show relay servers with measured round-trip time
+    Relays,
+    /// Show which path (p2p or relay) is used to communicate with Node and why
+    Diagnose { node_id: String },
 }
 
 impl NetCommand {
@@ -83,6 +93,7 @@ impl NetCommand {
                         "seen".into(),
                         "time".into(),
                         "ping".into(),
+                        "idle".into(),
                         "in [MiB]".into(),
                         "out [MiB]".into(),
                     ],
@@ -92,6 +103,12 @@ impl NetCommand {
                             let seen = Duration::from_secs(s.seen.as_secs());
                             let duration = Duration::from_secs(s.duration.as_secs());
                             let ping = Duration::from_millis(s.ping.as_millis() as u64);
+                            let idle = s
+                                .idle
+                                .map(|idle| {
+                                    format_duration(Duration::from_secs(idle.as_secs())).to_string()
+                                })
+                                .unwrap_or_default();
 
                             serde_json::json! {[
                                 s.node_id.map(|id| id.to_string()).unwrap_or_default(),
@@ -100,6 +117,7 @@ impl NetCommand {
                                 format_duration(seen).to_string(),
                                 format_duration(duration).to_string(),
                                 format_duration(ping).to_string(),
+                                idle,
                                 to_mib(s.metrics.tx_total, is_json),
                                 to_mib(s.metrics.rx_total, is_json),
                             ]}
@@ -215,6 +233,112 @@ impl NetCommand {
                     .map_err(anyhow::Error::msg)??;
                 Ok(CommandOutput::NoOutput)
             }
+            NetCommand::Bandwidth { services } => {
+                let usage: model::BandwidthResponse = bus::service(model::BUS_ID)
+                    .send(model::Bandwidth {})
+                    .await
+                    .map_err(anyhow::Error::msg)??;
+
+                let (key, values) = match services {
+                    true => (
+                        "service",
+                        usage
+                            .services
+                            .into_iter()
+                            .map(|s| (s.prefix, s.tx_total, s.rx_total))
+                            .collect::<Vec<_>>(),
+                    ),
+                    false => (
+                        "nodeId",
+                        usage
+                            .peers
+                            .into_iter()
+                            .map(|p| (p.node_id.to_string(), p.tx_total, p.rx_total))
+                            .collect::<Vec<_>>(),
+                    ),
+                };
+
+                Ok(ResponseTable {
+                    columns: vec![key.into(), "out [MiB]".into(), "in [MiB]".into()],
+                    values: values
+                        .into_iter()
+                        .map(|(key, tx, rx)| {
+                            serde_json::json! {[
+                                key,
+                                to_mib(tx as usize, is_json),
+                                to_mib(rx as usize, is_json),
+                            ]}
+                        })
+                        .collect(),
+                }
+                .into())
+            }
+            NetCommand::Relays => {
+                let relays: Vec<model::RelayResponse> = bus::service(model::BUS_ID)
+                    .send(model::Relays {})
+                    .await
+                    .map_err(anyhow::Error::msg)??;
+
+                Ok(ResponseTable {
+                    columns: vec![
+                        "host".into(),
+                        "address".into(),
+                        "selected".into(),
+                        "rtt".into(),
+                        "loss".into(),
+                        "probed".into(),
+                        "error".into(),
+                    ],
+                    values: relays
+                        .into_iter()
+                        .map(|r| {
+                            serde_json::json! {[
+                                r.host,
+                                r.address.map(|a| a.to_string()).unwrap_or_default(),
+                                if r.selected { "X" } else { "" },
+                                r.rtt
+                                    .map(|rtt| format_duration(Duration::from_millis(rtt.as_millis() as u64)).to_string())
+                                    .unwrap_or_default(),
+                                format!("{:.0}%", r.loss * 100.0),
+                                r.probed
+                                    .map(|ago| format_duration(Duration::from_secs(ago.as_secs())).to_string())
+                                    .unwrap_or_default(),
+                                r.error.unwrap_or_default(),
+                            ]}
+                        })
+                        .collect(),
+                }
+                .into())
+            }
+            NetCommand::Diagnose { node_id } => {
+                let diagnosis: model::DiagnoseResponse = bus::service(model::BUS_ID)
+                    .send(model::Diagnose {
+                        node_id: NodeId::from_str(&node_id)?,
+                    })
+                    .await
+                    .map_err(anyhow::Error::msg)??;
+
+                let format_ping = |ping: Duration| {
+                    format_duration(Duration::from_millis(ping.as_millis() as u64))
+                };
+
+                CommandOutput::object(serde_json::json!({
+                    "nodeId": diagnosis.node_id,
+                    "path": diagnosis.path,
+                    "reason": diagnosis.reason,
+                    "relayFallback": diagnosis.relay_fallback,
+                    "publicAddress": diagnosis.public_address,
+                    "remoteEndpoints": diagnosis.remote_endpoints.into_iter().map(|n| n.to_string()).collect::<Vec<_>>(),
+                    "lastAttempt": diagnosis.last_attempt.map(|attempt| serde_json::json!({
+                        "path": attempt.path,
+                        "duration": format_ping(attempt.duration).to_string(),
+                        "ago": format_duration(Duration::from_secs(attempt.ago.as_secs())).to_string(),
+                        "error": attempt.error,
+                    })),
+                    "ping (tcp)": diagnosis.tcp_ping.map(|ping| format_ping(ping).to_string()),
+                    "ping (udp)": diagnosis.udp_ping.map(|ping| format_ping(ping).to_string()),
+                }))
+            }
         }
     }
 }
@@ -13,19 +13,49 @@ pub enum NetType {
     Hybrid,
 }
 
+/// Decides whether messages can be forwarded through relay server,
+/// when direct connection (hole punching) to other Node fails.
+#[derive(
+    StructOpt, EnumString, EnumVariantNames, IntoStaticStr, Copy, Clone, Eq, PartialEq, Debug,
+)]
+#[strum(serialize_all = "lowercase")]
+pub enum RelayFallback {
+    Always,
+    Never,
+}
+
 #[derive(StructOpt, Clone)]
 #[structopt(rename_all = "kebab-case")]
 pub struct Config {
     #[structopt(env = "YA_NET_TYPE", possible_values = NetType::VARIANTS, default_value = NetType::default().into())]
     pub net_type: NetType,
+    /// Relay server `host:port`. With several comma separated servers the fastest one is used.
     #[structopt(env = "YA_NET_RELAY_HOST")]
     pub host: Option<String>,
+    /// Connection attempts made to each relay server when choosing among them.
+    #[structopt(env = "YA_NET_RELAY_PROBES", default_value = "3")]
+    pub relay_probes: u32,
+    #[structopt(env = "YA_NET_RELAY_REEVALUATION", parse(try_from_str = humantime::parse_duration), default_value = "30min")]
+    pub relay_reevaluation: Duration,
     #[structopt(env = "YA_NET_BIND_URL", default_value = "udp://0.0.0.0:11500")]
     pub bind_url: Url,
     #[structopt(env = "YA_NET_BROADCAST_SIZE", default_value = "10")]
     pub broadcast_size: u32,
     #[structopt(env = "YA_NET_SESSION_EXPIRATION", parse(try_from_str = humantime::parse_duration), default_value = "15s")]
     pub session_expiration: Duration,
+    /// Sessions without traffic for this long are closed.
+    #[structopt(env = "YA_NET_SESSION_IDLE_TIMEOUT", parse(try_from_str = humantime::parse_duration), default_value = "5min")]
+    pub session_idle_timeout: Duration,
+    /// Above this count the most idle sessions are closed.
+    #[structopt(env = "YA_NET_MAX_SESSIONS")]
+    pub max_sessions: Option<usize>,
+    /// Delay after the first failed connection attempt to a Node, doubled with each next one.
+    #[structopt(env = "YA_NET_RECONNECT_BACKOFF", parse(try_from_str = humantime::parse_duration), default_value = "1s")]
+    pub reconnect_backoff: Duration,
+    #[structopt(env = "YA_NET_RECONNECT_BACKOFF_MAX", parse(try_from_str = humantime::parse_duration), default_value = "5min")]
+    pub reconnect_backoff_max: Duration,
+    #[structopt(env = "YA_NET_RELAY_FALLBACK", possible_values = RelayFallback::VARIANTS, default_value = "always")]
+    pub relay_fallback: RelayFallback,
 }
 
 impl Config {
@@ -0,0 +1,123 @@
+//! Bytes exchanged with other Nodes, grouped by peer and by GSB service.
+//!
+//! Only GSB payloads are counted, so numbers don't include transport
+//! overhead, which is visible in session metrics.
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use ya_core_model::net::local as model;
+use ya_core_model::NodeId;
+
+lazy_static::lazy_static! {
+    static ref USAGE: Mutex<Usage> = Default::default();
+}
+
+#[derive(Default)]
+struct Usage {
+    peers: HashMap<NodeId, Counters>,
+    services: HashMap<String, Counters>,
+}
+
+#[derive(Default, Clone, Copy)]
+struct Counters {
+    tx: u64,
+    rx: u64,
+}
+
+impl Usage {
+    fn update(&mut self, peer: Option<NodeId>, service: String, f: impl Fn(&mut Counters)) {
+        if let Some(peer) = peer {
+            f(self.peers.entry(peer).or_default());
+        }
+        f(self.services.entry(service).or_default());
+    }
+}
+
+pub(crate) fn sent(peer: NodeId, address: &str, bytes: usize) {
+    let mut usage = USAGE.lock().unwrap();
+    usage.update(Some(peer), service_prefix(address), |c| {
+        c.tx += bytes as u64
+    });
+}
+
+pub(crate) fn received(peer: NodeId, address: &str, bytes: usize) {
+    let mut usage = USAGE.lock().unwrap();
+    usage.update(Some(peer), service_prefix(address), |c| {
+        c.rx += bytes as u64
+    });
+}
+
+/// Broadcast recipients are chosen by relay client, so we can't attribute
+/// sent broadcasts to peers.
+pub(crate) fn broadcast_sent(topic: &str, bytes: usize) {
+    let mut usage = USAGE.lock().unwrap();
+    usage.update(None, broadcast_key(topic), |c| c.tx += bytes as u64);
+}
+
+pub(crate) fn broadcast_received(peer: NodeId, topic: &str, bytes: usize) {
+    let mut usage = USAGE.lock().unwrap();
+    usage.update(Some(peer), broadcast_key(topic), |c| c.rx += bytes as u64);
+}
+
+pub(crate) fn usage() -> model::BandwidthResponse {
+    let usage = USAGE.lock().unwrap();
+
+    let mut peers = usage
+        .peers
+        .iter()
+        .map(|(node_id, c)| model::PeerBandwidth {
+            node_id: *node_id,
+            tx_total: c.tx,
+            rx_total: c.rx,
+        })
+        .collect::<Vec<_>>();
+    peers.sort_by_key(|p| std::cmp::Reverse(p.tx_total + p.rx_total));
+
+    let mut services = usage
+        .services
+        .iter()
+        .map(|(prefix, c)| model::ServiceBandwidth {
+            prefix: prefix.clone(),
+            tx_total: c.tx,
+            rx_total: c.rx,
+        })
+        .collect::<Vec<_>>();
+    services.sort_by_key(|s| std::cmp::Reverse(s.tx_total + s.rx_total));
+
+    model::BandwidthResponse { peers, services }
+}
+
+fn broadcast_key(topic: &str) -> String {
+    format!("broadcast/{topic}")
+}
+
+/// Groups addresses by first two segments after transport prefix,
+/// for example: `/transfer/public/gftp/<hash>` -> `/public/gftp`.
+fn service_prefix(address: &str) -> String {
+    let mut segments = address.split('/').filter(|s| !s.is_empty()).peekable();
+    if let Some(&"udp") | Some(&"transfer") = segments.peek() {
+        segments.next();
+    }
+    segments
+        .take(2)
+        .fold(String::new(), |prefix, s| prefix + "/" + s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_service_prefix() {
+        assert_eq!(
+            service_prefix("/public/market/protocol/mk1/discovery"),
+            "/public/market"
+        );
+        assert_eq!(service_prefix("/transfer/public/gftp/abcd"), "/public/gftp");
+        assert_eq!(
+            service_prefix("/udp/public/diagnostic/net"),
+            "/public/diagnostic"
+        );
+        assert_eq!(service_prefix("/public"), "/public");
+    }
+}
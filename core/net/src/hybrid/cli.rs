@@ -14,7 +14,7 @@ use ya_service_bus::timeout::IntoTimeoutFuture;
 use ya_service_bus::typed::ServiceBinder;
 use ya_service_bus::{typed as bus, RpcEndpoint};
 
-use crate::hybrid::Net;
+use crate::hybrid::{accounting, pool, relay, traversal, Net};
 
 pub(crate) fn bind_service() {
     let _ = bus::bind(model::BUS_ID, |ping: model::GsbPing| {
@@ -80,6 +80,7 @@ pub(crate) fn bind_service() {
                     seen: now - session.last_seen,
                     duration: now - session.created,
                     ping: session.last_ping,
+                    idle: node_id.as_ref().and_then(pool::idle),
                     metrics: to_status_metrics(&mut metric),
                 });
             }
@@ -110,6 +111,15 @@ pub(crate) fn bind_service() {
         }
         .map_err(status_err)
     });
+    let _ = bus::bind(model::BUS_ID, move |_: model::Bandwidth| async move {
+        Ok(accounting::usage())
+    });
+    let _ = bus::bind(model::BUS_ID, move |_: model::Relays| async move {
+        Ok(relay::relays())
+    });
+    let _ = bus::bind(model::BUS_ID, move |msg: model::Diagnose| {
+        diagnose(msg.node_id).map_err(status_err)
+    });
     let _ = bus::bind(model::BUS_ID, move |find: model::FindNode| {
         async move {
             let client = Net::client().await?;
@@ -148,6 +158,56 @@ pub async fn disconnect(node: NodeId) -> anyhow::Result<()> {
     client.disconnect(node).await?
 }
 
+pub async fn diagnose(node_id: NodeId) -> anyhow::Result<model::DiagnoseResponse> {
+    log::info!("Diagnosing connection to Node: {node_id}");
+
+    let client = Net::client().await?;
+    let public_address = client.public_addr().await?;
+    let remote_endpoints = match client.find_node(node_id).await? {
+        Ok(node) => node.endpoints,
+        Err(e) => {
+            log::debug!("Diagnose: can't find Node [{node_id}]: {e}");
+            vec![]
+        }
+    };
+
+    let connected = connect(model::Connect {
+        node: node_id,
+        keep: false,
+        reliable_channel: true,
+        transfer_channel: false,
+    })
+    .await
+    .map_err(|e| e.to_string());
+
+    let (p2p, tcp_ping, udp_ping) = match connected {
+        Ok(_) => match cli_ping(vec![node_id]).await?.pop() {
+            Some(ping) => (ping.is_p2p, Some(ping.tcp_ping), Some(ping.udp_ping)),
+            None => (client.is_p2p(node_id).await?, None, None),
+        },
+        Err(_) => (false, None, None),
+    };
+
+    let error = connected.err();
+    let path = match error {
+        Some(_) => "none",
+        None => traversal::path(p2p),
+    };
+    let relay_fallback: &str = traversal::policy().into();
+
+    Ok(model::DiagnoseResponse {
+        node_id,
+        path: path.to_string(),
+        reason: traversal::reason(p2p, error.as_deref(), public_address, &remote_endpoints),
+        relay_fallback: relay_fallback.to_string(),
+        public_address,
+        remote_endpoints,
+        last_attempt: traversal::last_attempt(&node_id),
+        tcp_ping,
+        udp_ping,
+    })
+}
+
 pub async fn cli_ping(nodes: Vec<NodeId>) -> anyhow::Result<Vec<GsbPingResponse>> {
     let client = Net::client().await?;
 
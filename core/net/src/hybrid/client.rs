@@ -12,6 +12,8 @@ use ya_core_model::net::local::FindNodeResponse;
 use ya_core_model::NodeId;
 use ya_relay_client::{ChannelMetrics, Client, SessionDesc, SocketDesc, SocketState};
 
+use crate::hybrid::traversal;
+
 lazy_static::lazy_static! {
     static ref ADDRESS: Arc<RwLock<Option<Addr<ClientActor >>>> = Default::default();
 }
@@ -117,18 +119,22 @@ proxy!(
     Connect(model::Connect) -> anyhow::Result<()>,
     connect,
     |client: Client, msg: Connect| async move {
-        if msg.0.reliable_channel {
-            let _ = client.forward(msg.0.node).await?;
-        }
+        let node_id = msg.0.node;
+        traversal::connect(&client, node_id, async {
+            if msg.0.reliable_channel {
+                let _ = client.forward(node_id).await?;
+            }
 
-        if msg.0.transfer_channel {
-            let _ = client.forward_transfer(msg.0.node).await?;
-        }
+            if msg.0.transfer_channel {
+                let _ = client.forward_transfer(node_id).await?;
+            }
 
-        if !msg.0.reliable_channel && !msg.0.transfer_channel {
-            let _ = client.forward_unreliable(msg.0.node).await?;
-        }
-        Ok(())
+            if !msg.0.reliable_channel && !msg.0.transfer_channel {
+                let _ = client.forward_unreliable(node_id).await?;
+            }
+            Ok(())
+        })
+        .await
     }
 );
 proxy!(
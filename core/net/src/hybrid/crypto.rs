@@ -46,6 +46,7 @@ impl CryptoProvider for IdentityCryptoProvider {
         }
 
         let aliases_rfc = self.aliases.clone();
+        let default_id = self.default_id;
         async move {
             let identities = ya_service_bus::typed::service(identity::BUS_ID)
                 .send(identity::List {})
@@ -54,7 +55,7 @@ impl CryptoProvider for IdentityCryptoProvider {
 
             let node_ids: Vec<_> = identities
                 .into_iter()
-                .filter(|info| !(info.is_default || info.is_locked))
+                .filter(|info| !(info.node_id == default_id || info.is_locked))
                 .map(|info| info.node_id)
                 .collect();
 
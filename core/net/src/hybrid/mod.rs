@@ -1,10 +1,14 @@
+mod accounting;
 mod api;
 pub(crate) mod cli;
 mod client;
 mod codec;
 mod crypto;
+mod pool;
+mod relay;
 mod rest_api;
 mod service;
+mod traversal;
 
 pub use api::*;
 pub use rest_api::web_scope;
@@ -0,0 +1,200 @@
+//! Limits on sessions with other Nodes.
+//!
+//! Sessions without traffic for longer than idle timeout are closed, as well as
+//! the most idle ones above maximum session count. Failed connection attempts
+//! back off exponentially, so unreachable Nodes don't keep relay server busy.
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+use anyhow::bail;
+use metrics::{counter, gauge};
+
+use ya_core_model::NodeId;
+
+use crate::config::Config;
+use crate::hybrid::Net;
+use crate::metric_keys;
+
+lazy_static::lazy_static! {
+    static ref LIMITS: RwLock<Limits> = RwLock::new(Limits {
+        idle_timeout: Duration::from_secs(300),
+        max_sessions: None,
+        backoff: Duration::from_secs(1),
+        max_backoff: Duration::from_secs(300),
+    });
+    static ref ACTIVITY: RwLock<HashMap<NodeId, Activity>> = Default::default();
+    static ref BACKOFF: RwLock<HashMap<NodeId, Backoff>> = Default::default();
+}
+
+#[derive(Clone, Copy)]
+struct Limits {
+    idle_timeout: Duration,
+    max_sessions: Option<usize>,
+    backoff: Duration,
+    max_backoff: Duration,
+}
+
+/// Traffic of a session at the time it last changed.
+struct Activity {
+    bytes: u64,
+    changed: Instant,
+}
+
+struct Backoff {
+    failures: u32,
+    until: Instant,
+}
+
+pub(crate) fn set_limits(config: &Config) {
+    let limits = Limits {
+        idle_timeout: config.session_idle_timeout,
+        max_sessions: config.max_sessions,
+        backoff: config.reconnect_backoff,
+        max_backoff: config.reconnect_backoff_max,
+    };
+    log::info!(
+        "Hybrid NET session limits: idle timeout {:?}, max sessions {:?}, reconnect backoff {:?}..{:?}",
+        limits.idle_timeout,
+        limits.max_sessions,
+        limits.backoff,
+        limits.max_backoff,
+    );
+    *LIMITS.write().unwrap() = limits;
+}
+
+/// Fails if previous attempts to connect to the Node failed recently.
+pub(crate) fn check_backoff(node_id: NodeId) -> anyhow::Result<()> {
+    if let Some(backoff) = BACKOFF.read().unwrap().get(&node_id) {
+        let now = Instant::now();
+        if backoff.until > now {
+            counter!(metric_keys::CONNECTIONS_BACKOFF, 1);
+            bail!(
+                "Connecting to [{node_id}] backed off for {:?} after {} failed attempt(s)",
+                backoff.until - now,
+                backoff.failures
+            );
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn attempted(node_id: NodeId, success: bool) {
+    let mut backoffs = BACKOFF.write().unwrap();
+    if success {
+        backoffs.remove(&node_id);
+        return;
+    }
+
+    let limits = *LIMITS.read().unwrap();
+    let backoff = backoffs.entry(node_id).or_insert(Backoff {
+        failures: 0,
+        until: Instant::now(),
+    });
+    backoff.failures += 1;
+    backoff.until = Instant::now() + delay(backoff.failures, limits.backoff, limits.max_backoff);
+}
+
+/// Time since the last traffic in session with the Node, if it was tracked.
+pub(crate) fn idle(node_id: &NodeId) -> Option<Duration> {
+    ACTIVITY
+        .read()
+        .unwrap()
+        .get(node_id)
+        .map(|activity| activity.changed.elapsed())
+}
+
+/// Periodically closes idle sessions and these above maximum session count.
+pub(crate) async fn reap_sessions() {
+    loop {
+        let interval = (LIMITS.read().unwrap().idle_timeout / 4).max(Duration::from_secs(5));
+        tokio::time::sleep(interval).await;
+
+        if let Err(e) = reap().await {
+            log::debug!("Reaping idle sessions failed: {e}");
+        }
+    }
+}
+
+async fn reap() -> anyhow::Result<()> {
+    let client = Net::client().await?;
+    let limits = *LIMITS.read().unwrap();
+    let now = Instant::now();
+
+    let mut nodes = Vec::new();
+    for session in client.sessions().await? {
+        if let Some(node_id) = client.remote_id(session.remote).await? {
+            nodes.push(node_id);
+        }
+    }
+    let metrics = client.session_metrics().await?;
+
+    let mut idle = {
+        let mut activity = ACTIVITY.write().unwrap();
+        activity.retain(|node_id, _| nodes.contains(node_id));
+
+        nodes
+            .into_iter()
+            .map(|node_id| {
+                let bytes = metrics
+                    .get(&node_id)
+                    .map(|m| (m.tx.long.sum() + m.rx.long.sum()) as u64)
+                    .unwrap_or_default();
+                let entry = activity.entry(node_id).or_insert(Activity {
+                    bytes,
+                    changed: now,
+                });
+                if entry.bytes != bytes {
+                    entry.bytes = bytes;
+                    entry.changed = now;
+                }
+                (node_id, now - entry.changed)
+            })
+            .collect::<Vec<_>>()
+    };
+    gauge!(metric_keys::SESSIONS, idle.len() as i64);
+
+    // Most idle first.
+    idle.sort_by_key(|(_, idle)| std::cmp::Reverse(*idle));
+    let excess = limits
+        .max_sessions
+        .map(|max| idle.len().saturating_sub(max))
+        .unwrap_or(0);
+
+    for (i, (node_id, idle)) in idle.into_iter().enumerate() {
+        if i >= excess && idle < limits.idle_timeout {
+            continue;
+        }
+        log::debug!("Closing session with [{node_id}], idle for {idle:?}");
+        match client.disconnect(node_id).await? {
+            Ok(_) => {
+                counter!(metric_keys::SESSIONS_REAPED, 1);
+                ACTIVITY.write().unwrap().remove(&node_id);
+            }
+            Err(e) => log::debug!("Closing session with [{node_id}] failed: {e}"),
+        }
+    }
+    Ok(())
+}
+
+fn delay(failures: u32, backoff: Duration, max_backoff: Duration) -> Duration {
+    let factor = 2u32.saturating_pow(failures.saturating_sub(1));
+    backoff.saturating_mul(factor).min(max_backoff)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delay() {
+        let base = Duration::from_secs(1);
+        let max = Duration::from_secs(60);
+
+        assert_eq!(delay(1, base, max), Duration::from_secs(1));
+        assert_eq!(delay(2, base, max), Duration::from_secs(2));
+        assert_eq!(delay(4, base, max), Duration::from_secs(8));
+        assert_eq!(delay(7, base, max), max);
+        assert_eq!(delay(100, base, max), max);
+    }
+}
@@ -0,0 +1,280 @@
+//! Selection of relay server among configured candidates.
+//!
+//! Each candidate is probed a few times with a short-lived client, and the one
+//! with the lowest round-trip time, penalized by share of failed probes, is used.
+//! Candidates are re-probed periodically. A better relay server found this way
+//! is reported, but the Node keeps its session until restart.
+use std::net::{SocketAddr, ToSocketAddrs};
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Context};
+use url::Url;
+
+use ya_core_model::net::local as model;
+use ya_relay_client::ClientBuilder;
+use ya_service_bus::timeout::IntoTimeoutFuture;
+use ya_utils_networking::resolver;
+
+use crate::config::Config;
+use crate::hybrid::crypto::IdentityCryptoProvider;
+use crate::hybrid::Net;
+
+const DEFAULT_NET_RELAY_HOST: &str = "127.0.0.1:7464";
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+lazy_static::lazy_static! {
+    static ref RELAYS: RwLock<Vec<Relay>> = Default::default();
+}
+
+#[derive(Clone, Default)]
+struct Relay {
+    host: String,
+    address: Option<SocketAddr>,
+    selected: bool,
+    rtt: Option<Duration>,
+    loss: f32,
+    probed: Option<Instant>,
+    error: Option<String>,
+}
+
+impl Relay {
+    /// Round-trip time inflated by failed probes, `None` if all of them failed.
+    fn score(&self) -> Option<Duration> {
+        self.rtt.map(|rtt| rtt.mul_f32(1.0 + 4.0 * self.loss))
+    }
+}
+
+/// Comma separated `YA_NET_RELAY_HOST` or relay server resolved from SRV record.
+async fn candidates(config: &Config) -> Vec<String> {
+    match &config.host {
+        Some(hosts) => hosts
+            .split(',')
+            .map(|host| host.trim().to_string())
+            .filter(|host| !host.is_empty())
+            .collect(),
+        None => vec![resolver::resolve_yagna_srv_record("_net_relay._udp")
+            .await
+            // FIXME: remove
+            .unwrap_or_else(|_| DEFAULT_NET_RELAY_HOST.to_string())],
+    }
+}
+
+async fn resolve(host_port: &str) -> anyhow::Result<SocketAddr> {
+    let (host, port) = &host_port
+        .split_once(':')
+        .context("Please use host:port format")?;
+    let ip = resolver::try_resolve_dns_record(host).await;
+    let socket = format!("{}:{}", ip, port)
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| anyhow!("Invalid relay address: {ip}:{port}"))?;
+    Ok(socket)
+}
+
+/// Connects to relay server and measures round-trip time of its session.
+async fn ping(address: SocketAddr, crypto: IdentityCryptoProvider) -> anyhow::Result<Duration> {
+    let mut client = ClientBuilder::from_url(Url::parse(&format!("udp://{address}"))?)
+        .crypto(crypto)
+        .listen(Url::parse("udp://0.0.0.0:0")?)
+        .connect()
+        .build()
+        .timeout(Some(PROBE_TIMEOUT))
+        .await
+        .map_err(|_| anyhow!("Connecting timed out after {PROBE_TIMEOUT:?}"))??;
+
+    client.ping_sessions().await;
+    let rtt = client
+        .sessions()
+        .await
+        .into_iter()
+        .find(|session| session.remote == address)
+        .map(|session| session.last_ping);
+    let _ = client.shutdown().await;
+
+    rtt.ok_or_else(|| anyhow!("No session with relay server"))
+}
+
+async fn probe(host: String, probes: u32, crypto: &IdentityCryptoProvider) -> Relay {
+    let mut relay = Relay {
+        host,
+        probed: Some(Instant::now()),
+        ..Default::default()
+    };
+    let address = match resolve(&relay.host).await {
+        Ok(address) => address,
+        Err(e) => {
+            relay.loss = 1.0;
+            relay.error = Some(e.to_string());
+            return relay;
+        }
+    };
+    relay.address = Some(address);
+
+    let mut rtts = Vec::new();
+    for _ in 0..probes.max(1) {
+        match ping(address, crypto.clone()).await {
+            Ok(rtt) => rtts.push(rtt),
+            Err(e) => relay.error = Some(e.to_string()),
+        }
+    }
+    relay.loss = 1.0 - rtts.len() as f32 / probes.max(1) as f32;
+    if !rtts.is_empty() {
+        relay.rtt = Some(rtts.iter().sum::<Duration>() / rtts.len() as u32);
+    }
+    log::debug!(
+        "Probed relay server {} ({address}): rtt {:?}, loss {:.0}%",
+        relay.host,
+        relay.rtt,
+        relay.loss * 100.0
+    );
+    relay
+}
+
+fn best(relays: &[Relay]) -> Option<&Relay> {
+    relays
+        .iter()
+        .filter_map(|relay| relay.score().map(|score| (score, relay)))
+        .min_by_key(|(score, _)| *score)
+        .map(|(_, relay)| relay)
+}
+
+/// Address of relay server the Node should use. A single candidate isn't probed.
+pub(crate) async fn select(
+    config: &Config,
+    crypto: &IdentityCryptoProvider,
+) -> anyhow::Result<SocketAddr> {
+    let hosts = candidates(config).await;
+    let mut relays = match hosts.len() {
+        0 => anyhow::bail!("No relay server configured"),
+        1 => vec![Relay {
+            address: Some(resolve(&hosts[0]).await?),
+            host: hosts[0].clone(),
+            ..Default::default()
+        }],
+        _ => {
+            let mut relays = Vec::new();
+            for host in hosts {
+                relays.push(probe(host, config.relay_probes, crypto).await);
+            }
+            relays
+        }
+    };
+
+    let index = match best(&relays) {
+        Some(best) => relays.iter().position(|r| r.host == best.host),
+        None => relays.iter().position(|r| r.address.is_some()),
+    }
+    .ok_or_else(|| anyhow!("None of relay servers is reachable"))?;
+    relays[index].selected = true;
+    let selected = &relays[index];
+    let address = selected.address.expect("selected relay is resolved");
+
+    log::info!(
+        "Hybrid NET relay server configured on url: udp://{} ({address})",
+        selected.host
+    );
+    *RELAYS.write().unwrap() = relays;
+    Ok(address)
+}
+
+/// Periodically probes relay servers other than the selected one.
+pub(crate) async fn reevaluate(config: std::sync::Arc<Config>, crypto: IdentityCryptoProvider) {
+    if RELAYS.read().unwrap().len() < 2 {
+        return;
+    }
+
+    loop {
+        tokio::time::sleep(config.relay_reevaluation).await;
+
+        let relays = RELAYS.read().unwrap().clone();
+        let mut probed = Vec::with_capacity(relays.len());
+        for relay in relays {
+            probed.push(match relay.selected {
+                true => current(relay).await,
+                false => probe(relay.host, config.relay_probes, &crypto).await,
+            });
+        }
+
+        if let Some(best) = best(&probed).filter(|best| !best.selected) {
+            log::info!(
+                "Relay server {} responds faster than the one in use ({:?}), it will be used after restart",
+                best.host,
+                best.rtt
+            );
+        }
+        *RELAYS.write().unwrap() = probed;
+    }
+}
+
+/// Session with the selected relay server is measured with the running client.
+async fn current(mut relay: Relay) -> Relay {
+    relay.probed = Some(Instant::now());
+    let rtt = async {
+        let client = Net::client().await?;
+        client.ping_sessions().await?;
+        anyhow::Ok(
+            client
+                .sessions()
+                .await?
+                .into_iter()
+                .find(|session| Some(session.remote) == relay.address)
+                .map(|session| session.last_ping),
+        )
+    }
+    .await;
+
+    match rtt {
+        Ok(Some(rtt)) => {
+            relay.rtt = Some(rtt);
+            relay.loss = 0.0;
+            relay.error = None;
+        }
+        Ok(None) => relay.error = Some("No session with relay server".to_string()),
+        Err(e) => relay.error = Some(e.to_string()),
+    }
+    relay
+}
+
+pub(crate) fn relays() -> Vec<model::RelayResponse> {
+    RELAYS
+        .read()
+        .unwrap()
+        .iter()
+        .map(|relay| model::RelayResponse {
+            host: relay.host.clone(),
+            address: relay.address,
+            selected: relay.selected,
+            rtt: relay.rtt,
+            loss: relay.loss,
+            probed: relay.probed.map(|probed| probed.elapsed()),
+            error: relay.error.clone(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn relay(host: &str, rtt: Option<u64>, loss: f32) -> Relay {
+        Relay {
+            host: host.to_string(),
+            rtt: rtt.map(Duration::from_millis),
+            loss,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_best() {
+        let relays = vec![
+            relay("a:1", Some(40), 0.0),
+            relay("b:1", Some(20), 0.5),
+            relay("c:1", None, 1.0),
+            relay("d:1", Some(30), 0.0),
+        ];
+        assert_eq!(best(&relays).unwrap().host, "d:1");
+        assert!(best(&relays[2..3]).is_none());
+    }
+}
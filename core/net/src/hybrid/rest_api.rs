@@ -3,12 +3,14 @@ use ya_client_model::net::{Status, NET_API_V2_NET_PATH};
 
 use crate::error::Result;
 
+use super::accounting;
 use super::client::ClientProxy;
 
 pub fn web_scope() -> Scope {
     actix_web::web::scope(NET_API_V2_NET_PATH)
         .app_data(Data::new(ClientProxy::new().unwrap()))
         .service(get_info)
+        .service(get_bandwidth)
 }
 
 #[actix_web::get("/status")]
@@ -21,3 +23,8 @@ async fn get_info(client: Data<ClientProxy>) -> Result<impl Responder> {
     };
     Ok(HttpResponse::Ok().json(status))
 }
+
+#[actix_web::get("/bandwidth")]
+async fn get_bandwidth() -> Result<impl Responder> {
+    Ok(HttpResponse::Ok().json(accounting::usage()))
+}
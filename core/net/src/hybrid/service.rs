@@ -1,7 +1,6 @@
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 use std::future::Future;
-use std::net::{SocketAddr, ToSocketAddrs};
 use std::rc::Rc;
 use std::str::FromStr;
 use std::sync::atomic::AtomicBool;
@@ -23,7 +22,6 @@ use ya_core_model::net::local::{
 };
 use ya_core_model::{identity, net, NodeId};
 use ya_relay_client::codec::forward::{PrefixedSink, PrefixedStream, SinkKind};
-use ya_relay_client::crypto::CryptoProvider;
 use ya_relay_client::proto::Payload;
 use ya_relay_client::{Client, ClientBuilder, ForwardReceiver, TransportType};
 use ya_sb_proto::codec::GsbMessage;
@@ -34,19 +32,19 @@ use ya_service_bus::untyped::{Fn4HandlerExt, Fn4StreamHandlerExt};
 use ya_service_bus::{
     serialization, typed, untyped as local_bus, Error, ResponseChunk, RpcEndpoint, RpcMessage,
 };
-use ya_utils_networking::resolver;
 
 use crate::bcast::BCastService;
 use crate::config::Config;
+use crate::hybrid::accounting;
 use crate::hybrid::client::{ClientActor, ClientProxy};
 use crate::hybrid::codec;
 use crate::hybrid::codec::encode_message;
 use crate::hybrid::crypto::IdentityCryptoProvider;
+use crate::hybrid::{pool, relay, traversal};
+use crate::metric_keys;
 use crate::service::NET_TYPE;
 use crate::{bind_broadcast_with_caller, broadcast, NetType};
 
-const DEFAULT_NET_RELAY_HOST: &str = "127.0.0.1:7464";
-
 type BusSender = mpsc::Sender<ResponseChunk>;
 type BusReceiver = mpsc::Receiver<ResponseChunk>;
 type NetSender = mpsc::Sender<Payload>;
@@ -62,6 +60,10 @@ thread_local! {
     static CLIENT: RefCell<Option<Client>> = Default::default();
 }
 
+/// Cleared on shutdown. Requests from other nodes are rejected afterwards,
+/// while local services still finish communication with them.
+static ACCEPT_REQUESTS: AtomicBool = AtomicBool::new(true);
+
 pub struct Net;
 
 impl Net {
@@ -98,6 +100,11 @@ impl Net {
             .map_err(|_| anyhow!("Error starting network"))?
     }
 
+    pub fn stop_accepting_requests() {
+        log::info!("NET: rejecting further requests from other nodes");
+        ACCEPT_REQUESTS.store(false, Relaxed);
+    }
+
     pub async fn shutdown() -> anyhow::Result<()> {
         if let Ok(client) = Self::client().await {
             if client
@@ -121,14 +128,13 @@ pub async fn start_network(
     default_id: NodeId,
     ids: Vec<NodeId>,
 ) -> anyhow::Result<()> {
-    counter!("net.connections.p2p", 0);
-    counter!("net.connections.relay", 0);
-
     log::info!("Starting network (hybrid) with identity: {}", default_id);
 
     let broadcast_size = config.broadcast_size;
+    traversal::set_policy(config.relay_fallback);
+    pool::set_limits(&config);
     let crypto = IdentityCryptoProvider::new(default_id);
-    let client = build_client(config, crypto.clone()).await?;
+    let client = build_client(config.clone(), crypto.clone()).await?;
 
     CLIENT.with(|inner| {
         inner.borrow_mut().replace(client.clone());
@@ -203,6 +209,8 @@ pub async fn start_network(
     );
 
     tokio::task::spawn_local(forward_handler(receiver, state.clone()));
+    tokio::task::spawn_local(pool::reap_sessions());
+    tokio::task::spawn_local(relay::reevaluate(config, crypto.clone()));
 
     bind_broadcast_handlers(broadcast_size);
     bind_identity_event_handler(crypto).await;
@@ -210,9 +218,9 @@ pub async fn start_network(
 
     if let Some(address) = client.public_addr().await {
         log::info!("Public address: {}", address);
-        counter!("net.public-addresses", 1);
+        counter!(metric_keys::PUBLIC_ADDRESSES, 1);
     } else {
-        counter!("net.public-addresses", 0);
+        counter!(metric_keys::PUBLIC_ADDRESSES, 0);
     }
 
     Ok(())
@@ -220,9 +228,9 @@ pub async fn start_network(
 
 async fn build_client(
     config: Arc<Config>,
-    crypto: impl CryptoProvider + 'static,
+    crypto: IdentityCryptoProvider,
 ) -> anyhow::Result<Client> {
-    let addr = relay_addr(&config)
+    let addr = relay::select(&config, &crypto)
         .await
         .map_err(|e| anyhow!("Resolving hybrid NET relay server failed. Error: {}", e))?;
     let url = Url::parse(&format!("udp://{addr}"))?;
@@ -236,28 +244,6 @@ async fn build_client(
         .await
 }
 
-async fn relay_addr(config: &Config) -> anyhow::Result<SocketAddr> {
-    let host_port = match &config.host {
-        Some(val) => val.to_string(),
-        None => resolver::resolve_yagna_srv_record("_net_relay._udp")
-            .await
-            // FIXME: remove
-            .unwrap_or_else(|_| DEFAULT_NET_RELAY_HOST.to_string()),
-    };
-
-    log::info!("Hybrid NET relay server configured on url: udp://{host_port}");
-
-    let (host, port) = &host_port
-        .split_once(':')
-        .context("Please use host:port format")?;
-    let ip = resolver::try_resolve_dns_record(host).await;
-    let socket = format!("{}:{}", ip, port)
-        .to_socket_addrs()?
-        .next()
-        .ok_or_else(|| anyhow!("Invalid relay address: {ip}:{port}"))?;
-    Ok(socket)
-}
-
 fn bind_local_bus<F>(address: &'static str, state: State, transport: TransportType, resolver: F)
 where
     F: Fn(&str, &str) -> anyhow::Result<(NodeId, NodeId, String)> + 'static,
@@ -513,10 +499,14 @@ fn forward_bus_to_net(
 
         match state.forward_sink(remote_id, transport).await {
             Ok(mut sink) => {
-                let _ = sink.send(msg).await.map_err(|_| {
-                    let err = "Net: error sending message: session closed".to_string();
-                    handler_reply_service_err(request_id, err, tx);
-                });
+                let len = msg.len();
+                match sink.send(msg).await {
+                    Ok(_) => accounting::sent(remote_id, &address, len),
+                    Err(_) => {
+                        let err = "Net: error sending message: session closed".to_string();
+                        handler_reply_service_err(request_id, err, tx);
+                    }
+                }
             }
             Err(error) => {
                 let err = format!("Net: error forwarding message: {}", error);
@@ -566,9 +556,11 @@ fn push_bus_to_net(
 
         match state.forward_sink(remote_id, transport).await {
             Ok(mut sink) => {
-                let _ = sink.send(msg).await.map_err(|_| {
-                    log::debug!("Net: error sending message: session closed");
-                });
+                let len = msg.len();
+                match sink.send(msg).await {
+                    Ok(_) => accounting::sent(remote_id, &address, len),
+                    Err(_) => log::debug!("Net: error sending message: session closed"),
+                }
             }
             Err(error) => {
                 log::debug!("Net: error forwarding message: {}", error);
@@ -591,14 +583,16 @@ fn broadcast_handler(
         let stub: SendBroadcastStub = serialization::from_slice(&message)
             .map_err(|e| Error::GsbFailure(format!("Invalid broadcast message: {e}")))?;
 
+        let topic = stub.topic;
         let request = GsbMessage::BroadcastRequest(ya_sb_proto::BroadcastRequest {
             //data: serialization::to_vec(&message)?,
             data: message,
             caller,
-            topic: stub.topic,
+            topic: topic.clone(),
         });
 
         let payload = encode_message(request).map_err(|e| Error::EncodingProblem(e.to_string()))?;
+        let len = payload.len();
 
         let client = CLIENT
             .with(|c| c.borrow().clone())
@@ -607,6 +601,7 @@ fn broadcast_handler(
             .broadcast(payload, broadcast_size)
             .await
             .map_err(|e| Error::GsbFailure(format!("Broadcast failed: {e}")))?;
+        accounting::broadcast_sent(&topic, len);
 
         Ok(serialization::to_vec(&Ok::<(), ()>(())).unwrap())
     }
@@ -775,6 +770,12 @@ fn handle_push(
     let caller_id = caller_id.unwrap();
 
     log::debug!("Handle push request {request_id} to {address} from {remote_id}");
+    accounting::received(remote_id, &address, request.data.len());
+
+    if !ACCEPT_REQUESTS.load(Relaxed) {
+        log::trace!("Handle push request rejected, shutting down: {address}");
+        return Err(Error::GsbFailure("Node is shutting down".to_string()).into());
+    }
 
     let fut = match state.get_public_service(address.as_str()) {
         Some(address) => {
@@ -818,11 +819,18 @@ fn handle_request(
     let request_id_sent = request_id.clone();
 
     log::debug!("Handle request {request_id} to {address} from {remote_id}");
+    accounting::received(remote_id, &address, request.data.len());
+    let address_sent = address.clone();
 
     let eos = Rc::new(AtomicBool::new(false));
     let eos_map = eos.clone();
 
     let stream = match state.get_public_service(address.as_str()) {
+        _ if !ACCEPT_REQUESTS.load(Relaxed) => {
+            log::trace!("Handle request rejected, shutting down: {address}");
+            let err = Error::GsbFailure("Node is shutting down".to_string());
+            futures::stream::once(futures::future::err(err)).right_stream()
+        }
         Some(address) => {
             log::trace!("Handle request: calling: {address}");
             local_bus::call_stream(&address, &request.caller, &request.data).left_stream()
@@ -881,7 +889,11 @@ fn handle_request(
 
             //stream.forward(sink).await?;
             while let Some(item) = stream.next().await {
-                sink.send(item?).await.ok();
+                let item = item?;
+                let len = item.len();
+                if sink.send(item).await.is_ok() {
+                    accounting::sent(caller_id, &address_sent, len);
+                }
                 log::debug!("Handled request: {request_id_sent} from: {caller_id}");
             }
 
@@ -926,6 +938,7 @@ fn handle_reply(
         }
         None => anyhow::bail!("invalid reply request id: {}", reply.request_id),
     };
+    accounting::received(remote_id, &request.address, reply.data.len());
 
     let request_id = reply.request_id.clone();
     let data = if reply.code == CallReplyCode::CallReplyOk as i32 {
@@ -964,6 +977,7 @@ fn handle_broadcast(
         &request.caller
     );
 
+    accounting::broadcast_received(remote_id, &request.topic, request.data.len());
     let caller = caller_id.unwrap().to_string();
 
     tokio::task::spawn_local(async move {
@@ -1024,22 +1038,19 @@ impl State {
             .with(|c| c.borrow().clone())
             .ok_or_else(|| anyhow::anyhow!("network not started"))?;
 
-        let forward: NetSinkKind = match transport {
-            TransportType::Unreliable => client.forward_unreliable(remote_id).await?.into(),
-            TransportType::Reliable => PrefixedSink::new(client.forward(remote_id).await?).into(),
-            TransportType::Transfer => {
-                PrefixedSink::new(client.forward_transfer(remote_id).await?).into()
-            }
-        };
-
-        // FIXME: yagna daemon doesn't handle connections; ya-relay-client does
-        // if client.sessions.has_p2p_connection(remote_id).await {
-        //     counter!("net.connections.p2p", 1)
-        // } else {
-        //     counter!("net.connections.relay", 1)
-        // };
-
-        Ok(forward)
+        traversal::connect(&client, remote_id, async {
+            let forward: NetSinkKind = match transport {
+                TransportType::Unreliable => client.forward_unreliable(remote_id).await?.into(),
+                TransportType::Reliable => {
+                    PrefixedSink::new(client.forward(remote_id).await?).into()
+                }
+                TransportType::Transfer => {
+                    PrefixedSink::new(client.forward_transfer(remote_id).await?).into()
+                }
+            };
+            Ok(forward)
+        })
+        .await
     }
 
     fn get_public_service(&self, addr: &str) -> Option<String> {
@@ -1061,7 +1072,6 @@ struct Request<S: Clone> {
     caller_id: NodeId,
     #[allow(unused)]
     remote_id: NodeId,
-    #[allow(unused)]
     address: String,
     tx: S,
 }
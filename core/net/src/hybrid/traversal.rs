@@ -0,0 +1,177 @@
+//! Tracks how connections to other Nodes were established.
+//!
+//! `ya-relay-client` tries to establish direct session first (hole punching)
+//! and falls back to forwarding through relay server. We record results of
+//! these attempts and apply configured relay fallback policy on top of them.
+use std::collections::HashMap;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+use anyhow::bail;
+use metrics::counter;
+
+use ya_core_model::net::local as model;
+use ya_core_model::NodeId;
+use ya_relay_client::Client;
+
+use crate::config::RelayFallback;
+use crate::hybrid::pool;
+use crate::metric_keys;
+
+lazy_static::lazy_static! {
+    static ref POLICY: RwLock<RelayFallback> = RwLock::new(RelayFallback::Always);
+    static ref ATTEMPTS: RwLock<HashMap<NodeId, Attempt>> = Default::default();
+}
+
+#[derive(Clone)]
+struct Attempt {
+    p2p: bool,
+    duration: Duration,
+    timestamp: Instant,
+    error: Option<String>,
+}
+
+pub(crate) fn set_policy(policy: RelayFallback) {
+    log::info!("Hybrid NET relay fallback policy: {:?}", policy);
+    *POLICY.write().unwrap() = policy;
+}
+
+pub(crate) fn policy() -> RelayFallback {
+    *POLICY.read().unwrap()
+}
+
+/// Awaits `connect` future and records which path it ended up with,
+/// if there was no session with the Node before.
+/// Fails if direct connection couldn't be established and relay fallback is disabled.
+pub(crate) async fn connect<F, T>(client: &Client, node_id: NodeId, connect: F) -> anyhow::Result<T>
+where
+    F: Future<Output = anyhow::Result<T>>,
+{
+    let new = client.sessions.get_node(node_id).await.is_err();
+    if new {
+        pool::check_backoff(node_id)?;
+    }
+    let started = Instant::now();
+
+    let result = connect.await;
+    let p2p = client.sessions.is_p2p(&node_id).await;
+
+    if new {
+        let duration = started.elapsed();
+        let error = result.as_ref().err().map(|e| e.to_string());
+        pool::attempted(node_id, error.is_none());
+
+        match (&error, p2p) {
+            (Some(e), _) => {
+                log::debug!("Connecting to [{node_id}] failed after {duration:?}: {e}");
+                counter!(metric_keys::CONNECTIONS_FAILED, 1);
+            }
+            (None, true) => {
+                log::debug!("Direct session with [{node_id}] established in {duration:?}");
+                counter!(metric_keys::CONNECTIONS_P2P, 1);
+            }
+            (None, false) => {
+                log::debug!("Hole punching to [{node_id}] failed, using relay server");
+                counter!(metric_keys::CONNECTIONS_RELAY, 1);
+            }
+        }
+
+        ATTEMPTS.write().unwrap().insert(
+            node_id,
+            Attempt {
+                p2p,
+                duration,
+                timestamp: started,
+                error,
+            },
+        );
+    }
+
+    let value = result?;
+    if !p2p && policy() == RelayFallback::Never {
+        counter!(metric_keys::CONNECTIONS_REFUSED, 1);
+        bail!("Direct connection to [{node_id}] not established and relay fallback is disabled");
+    }
+    Ok(value)
+}
+
+pub(crate) fn last_attempt(node_id: &NodeId) -> Option<model::ConnectAttempt> {
+    ATTEMPTS
+        .read()
+        .unwrap()
+        .get(node_id)
+        .map(|attempt| model::ConnectAttempt {
+            path: path(attempt.p2p).to_string(),
+            duration: attempt.duration,
+            ago: attempt.timestamp.elapsed(),
+            error: attempt.error.clone(),
+        })
+}
+
+pub(crate) fn path(p2p: bool) -> &'static str {
+    match p2p {
+        true => "p2p",
+        false => "relay",
+    }
+}
+
+/// Explains why given path was chosen, based on public addresses of both Nodes.
+pub(crate) fn reason(
+    p2p: bool,
+    error: Option<&str>,
+    public_addr: Option<SocketAddr>,
+    remote_endpoints: &[SocketAddr],
+) -> String {
+    if let Some(e) = error {
+        return format!("Connection failed: {e}");
+    }
+
+    match (p2p, public_addr.is_some(), !remote_endpoints.is_empty()) {
+        (true, _, true) => "Direct session with public endpoint of remote Node".to_string(),
+        (true, true, false) => {
+            "Direct session established by remote Node connecting back to our public address"
+                .to_string()
+        }
+        (true, false, false) => "Direct session established by hole punching".to_string(),
+        (false, false, false) => {
+            "Neither Node has public address, forwarding through relay server".to_string()
+        }
+        (false, true, false) => {
+            "Remote Node has no public address and didn't connect back, forwarding through relay server"
+                .to_string()
+        }
+        (false, _, true) => {
+            "Public endpoints of remote Node are unreachable, forwarding through relay server"
+                .to_string()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reason() {
+        let addr: SocketAddr = "1.2.3.4:11500".parse().unwrap();
+
+        assert_eq!(
+            reason(false, Some("timeout"), None, &[]),
+            "Connection failed: timeout"
+        );
+        assert_eq!(
+            reason(true, None, None, &[addr]),
+            "Direct session with public endpoint of remote Node"
+        );
+        assert_eq!(
+            reason(false, None, None, &[]),
+            "Neither Node has public address, forwarding through relay server"
+        );
+        assert_eq!(
+            reason(false, None, Some(addr), &[addr]),
+            "Public endpoints of remote Node are unreachable, forwarding through relay server"
+        );
+    }
+}
@@ -13,3 +13,4 @@ mod service;
 mod cli;
 mod config;
 mod error;
+mod metric_keys;
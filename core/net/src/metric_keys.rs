@@ -0,0 +1,29 @@
+//! Names of the metrics reported by the Net service.
+
+use metrics::counter;
+
+pub(crate) const CONNECT: &str = "net.connect";
+pub(crate) const CONNECTIONS_BACKOFF: &str = "net.connections.backoff";
+pub(crate) const CONNECTIONS_FAILED: &str = "net.connections.failed";
+pub(crate) const CONNECTIONS_P2P: &str = "net.connections.p2p";
+pub(crate) const CONNECTIONS_REFUSED: &str = "net.connections.refused";
+pub(crate) const CONNECTIONS_RELAY: &str = "net.connections.relay";
+pub(crate) const DISCONNECT: &str = "net.disconnect";
+pub(crate) const PUBLIC_ADDRESSES: &str = "net.public-addresses";
+pub(crate) const RECONNECT_TIME: &str = "net.reconnect.time";
+pub(crate) const SESSIONS: &str = "net.sessions";
+pub(crate) const SESSIONS_REAPED: &str = "net.sessions.reaped";
+
+/// Initializes counters to 0 value. Otherwise they won't appear on metrics endpoint
+/// until first change to value will be made.
+pub(crate) fn init() {
+    counter!(CONNECT, 0);
+    counter!(CONNECTIONS_BACKOFF, 0);
+    counter!(CONNECTIONS_FAILED, 0);
+    counter!(CONNECTIONS_P2P, 0);
+    counter!(CONNECTIONS_REFUSED, 0);
+    counter!(CONNECTIONS_RELAY, 0);
+    counter!(DISCONNECT, 0);
+    counter!(PUBLIC_ADDRESSES, 0);
+    counter!(SESSIONS_REAPED, 0);
+}
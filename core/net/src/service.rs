@@ -13,18 +13,17 @@ pub(crate) async fn identities() -> anyhow::Result<(NodeId, Vec<NodeId>)> {
         .await
         .map_err(anyhow::Error::msg)??;
 
-    let mut default_id = None;
+    // Node is identified by the identity with node-id role, if assigned.
+    let default_id = ids
+        .iter()
+        .find(|id| id.roles.contains(&identity::IdentityRole::NodeId))
+        .or_else(|| ids.iter().find(|id| id.is_default))
+        .map(|id| id.node_id)
+        .ok_or_else(|| anyhow::anyhow!("no default identity"))?;
     let ids = ids
         .into_iter()
-        .map(|id| {
-            if id.is_default {
-                default_id = Some(id.node_id);
-            }
-            id.node_id
-        })
+        .map(|id| id.node_id)
         .collect::<Vec<NodeId>>();
-
-    let default_id = default_id.ok_or_else(|| anyhow::anyhow!("no default identity"))?;
     Ok((default_id, ids))
 }
 
@@ -47,6 +46,7 @@ impl Net {
         {
             (*NET_TYPE.write().unwrap()) = config.net_type;
         }
+        crate::metric_keys::init();
 
         match &config.net_type {
             NetType::Central => {
@@ -68,6 +68,15 @@ impl Net {
         }
     }
 
+    /// Rejects requests from other nodes. Local services can still call them.
+    pub fn stop_accepting_requests() {
+        let net_type = { *NET_TYPE.read().unwrap() };
+        match net_type {
+            NetType::Central => (),
+            NetType::Hybrid => crate::hybrid::Net::stop_accepting_requests(),
+        }
+    }
+
     pub async fn shutdown() -> anyhow::Result<()> {
         let config = Config::from_env()?;
 
@@ -0,0 +1,5 @@
+DROP TABLE payment;
+DROP TABLE "transaction";
+DROP TABLE transaction_type;
+DROP TABLE transaction_status;
+DROP TABLE payment_status;
@@ -0,0 +1,87 @@
+-- Schema equivalent to SQLite migrations up to 2021-10-13-033400_payment_driver_improvements.
+
+CREATE TABLE payment_status(
+    status_id INTEGER NOT NULL PRIMARY KEY,
+    status TEXT NOT NULL
+);
+
+INSERT INTO payment_status(status_id, status) VALUES
+    (1, 'REQUESTED'),
+    (2, 'DONE'),
+    (3, 'NOT_ENOUGH_FUNDS'),
+    (4, 'NOT_ENOUGH_GAS'),
+    (5, 'FAILED');
+
+CREATE TABLE transaction_status(
+    status_id INTEGER NOT NULL PRIMARY KEY,
+    status TEXT NOT NULL
+);
+
+INSERT INTO transaction_status(status_id, status) VALUES
+    (0, 'UNUSED'),
+    (1, 'CREATED'),
+    (2, 'SENT'),
+    (3, 'PENDING'),
+    (4, 'CONFIRMED'),
+    (5, 'RESEND'),
+    (6, 'RESENDANDBUMPGAS'),
+    (10, 'ERRORSENT'),
+    (11, 'ERRORONCHAIN'),
+    (12, 'ERRORNONCETOOLOW');
+
+CREATE TABLE transaction_type(
+    type_id INTEGER NOT NULL PRIMARY KEY,
+    tx_type TEXT NOT NULL
+);
+
+INSERT INTO transaction_type(type_id, tx_type) VALUES
+    (0, 'FAUCET'),
+    (1, 'TRANSFER');
+
+CREATE TABLE "transaction"(
+    tx_id TEXT NOT NULL PRIMARY KEY,
+    sender TEXT NOT NULL,
+    nonce INTEGER NOT NULL DEFAULT -1,
+    status INTEGER NOT NULL,
+    tx_type INTEGER NOT NULL,
+    tmp_onchain_txs TEXT NULL,
+    final_tx TEXT NULL,
+    starting_gas_price TEXT NULL,
+    current_gas_price TEXT NULL,
+    max_gas_price TEXT NULL,
+    final_gas_used INTEGER NULL,
+    amount_base TEXT NULL,
+    amount_erc20 TEXT NULL,
+    gas_limit INTEGER NULL,
+    time_created TIMESTAMP NOT NULL DEFAULT timezone('utc', now()),
+    time_last_action TIMESTAMP NOT NULL DEFAULT timezone('utc', now()),
+    time_sent TIMESTAMP NULL,
+    time_confirmed TIMESTAMP NULL,
+    network INTEGER NOT NULL DEFAULT 4,
+    last_error_msg TEXT NULL,
+    resent_times INTEGER NOT NULL DEFAULT 0,
+    signature TEXT NULL,
+    encoded TEXT NOT NULL,
+    FOREIGN KEY (status) REFERENCES transaction_status (status_id),
+    FOREIGN KEY (tx_type) REFERENCES transaction_type (type_id)
+);
+
+CREATE TABLE payment(
+    order_id TEXT NOT NULL PRIMARY KEY,
+    amount TEXT NOT NULL,
+    gas TEXT NOT NULL,
+    sender TEXT NOT NULL,
+    recipient TEXT NOT NULL,
+    payment_due_date TIMESTAMP NOT NULL,
+    status INTEGER NOT NULL,
+    tx_id TEXT,
+    network INTEGER NOT NULL DEFAULT 4,
+    FOREIGN KEY (tx_id) REFERENCES "transaction" (tx_id),
+    FOREIGN KEY (status) REFERENCES payment_status (status_id)
+);
+
+CREATE INDEX payment_sender_idx ON payment (sender);
+CREATE INDEX payment_tx_idx ON payment (tx_id);
+CREATE INDEX transaction_tx_hash_idx ON "transaction" (final_tx);
+CREATE INDEX transaction_sender_idx ON "transaction" (sender);
+CREATE INDEX transaction_status_idx ON "transaction" (status);
@@ -0,0 +1,2 @@
+INSERT INTO transaction_type(type_id, tx_type) VALUES (2, 'METATRANSFER')
+ON CONFLICT DO NOTHING;
@@ -0,0 +1 @@
+DELETE FROM transaction_type WHERE type_id = 3;
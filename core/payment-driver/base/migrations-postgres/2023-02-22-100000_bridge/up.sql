@@ -0,0 +1,19 @@
+INSERT INTO transaction_type(type_id, tx_type) VALUES
+    (4, 'BRIDGE')
+ON CONFLICT DO NOTHING;
+
+CREATE TABLE bridge(
+    bridge_id TEXT NOT NULL PRIMARY KEY,
+    sender TEXT NOT NULL,
+    amount TEXT NOT NULL,
+    from_network INTEGER NOT NULL,
+    to_network INTEGER NOT NULL,
+    source_tx_id TEXT NOT NULL,
+    target_tx_id TEXT NULL,
+    time_created TIMESTAMP NOT NULL DEFAULT timezone('utc', now()),
+    last_error_msg TEXT NULL,
+    FOREIGN KEY (source_tx_id) REFERENCES "transaction" (tx_id),
+    FOREIGN KEY (target_tx_id) REFERENCES "transaction" (tx_id)
+);
+
+CREATE INDEX bridge_sender_idx ON bridge (sender);
@@ -0,0 +1,3 @@
+INSERT INTO transaction_type(type_id, tx_type) VALUES
+    (5, 'SWEEP')
+ON CONFLICT DO NOTHING;
@@ -0,0 +1 @@
+DELETE FROM transaction_status WHERE status_id = 7;
@@ -0,0 +1,3 @@
+INSERT INTO transaction_status(status_id, status) VALUES
+    (7, 'SIMULATED')
+ON CONFLICT DO NOTHING;
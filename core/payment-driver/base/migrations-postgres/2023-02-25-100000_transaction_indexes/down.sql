@@ -0,0 +1,6 @@
+CREATE INDEX transaction_sender_idx ON "transaction" (sender);
+CREATE INDEX payment_sender_idx ON payment (sender);
+
+DROP INDEX transaction_sender_network_status_nonce_idx;
+DROP INDEX transaction_network_status_idx;
+DROP INDEX payment_sender_network_status_idx;
@@ -0,0 +1,7 @@
+-- Covers lookups by sender, the nonce queries and paging of pending transactions.
+CREATE INDEX transaction_sender_network_status_nonce_idx ON "transaction" (sender, network, status, nonce);
+CREATE INDEX transaction_network_status_idx ON "transaction" (network, status);
+CREATE INDEX payment_sender_network_status_idx ON payment (sender, network, status);
+
+DROP INDEX transaction_sender_idx;
+DROP INDEX payment_sender_idx;
@@ -0,0 +1,3 @@
+CREATE INDEX transaction_network_status_idx ON "transaction" (network, status);
+
+DROP INDEX transaction_network_status_created_idx;
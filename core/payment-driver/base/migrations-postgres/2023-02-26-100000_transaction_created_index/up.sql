@@ -0,0 +1,4 @@
+-- Covers lookups of expired transactions, prefix covers lookups by status.
+CREATE INDEX transaction_network_status_created_idx ON "transaction" (network, status, time_created);
+
+DROP INDEX transaction_network_status_idx;
@@ -0,0 +1,31 @@
+-- HACK: All this code below is just to drop column priority from table payment
+
+PRAGMA foreign_keys=off;
+
+CREATE TABLE payment_tmp(
+  	order_id VARCHAR(50) NOT NULL PRIMARY KEY,
+  	-- U256 in big endian hex
+  	amount VARCHAR(64) NOT NULL,
+  	-- U256 in big endian hex
+  	gas VARCHAR(64) NOT NULL,
+  	sender VARCHAR(40) NOT NULL,
+  	recipient VARCHAR(40) NOT NULL,
+  	payment_due_date DATETIME NOT NULL,
+  	status INTEGER NOT NULL,
+  	tx_id VARCHAR(128),
+  	network INTEGER NOT NULL DEFAULT 4,
+  	FOREIGN KEY(tx_id) REFERENCES `transaction` (tx_id),
+  	FOREIGN KEY(status) REFERENCES `payment_status` (status_id)
+);
+
+INSERT INTO payment_tmp(order_id, amount, gas, sender, recipient, payment_due_date, status, tx_id, network)
+SELECT order_id, amount, gas, sender, recipient, payment_due_date, status, tx_id, network FROM payment;
+
+DROP TABLE payment;
+
+ALTER TABLE payment_tmp RENAME TO payment;
+
+create index if not exists payment_sender_idx on payment (sender);
+create index if not exists payment_tx_idx on payment (tx_id);
+
+PRAGMA foreign_keys=on;
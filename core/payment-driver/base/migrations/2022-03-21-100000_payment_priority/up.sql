@@ -0,0 +1 @@
+ALTER TABLE payment ADD COLUMN priority INTEGER NOT NULL DEFAULT 1;  -- 1 is normal priority
@@ -0,0 +1,3 @@
+DROP TABLE bridge;
+
+DELETE FROM `transaction_type` WHERE type_id = 4;
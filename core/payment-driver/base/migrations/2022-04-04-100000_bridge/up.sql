@@ -0,0 +1,19 @@
+INSERT OR IGNORE INTO `transaction_type` (type_id, tx_type) VALUES(4, "BRIDGE");
+
+CREATE TABLE bridge(
+    bridge_id TEXT NOT NULL PRIMARY KEY,
+    sender TEXT NOT NULL,
+    amount TEXT NOT NULL,
+    from_network INTEGER NOT NULL,
+    to_network INTEGER NOT NULL,
+    -- Deposit on the root chain or burn on the sidechain
+    source_tx_id TEXT NOT NULL,
+    -- Exit on the root chain, withdrawals only
+    target_tx_id TEXT NULL,
+    time_created DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    last_error_msg TEXT NULL,
+    FOREIGN KEY(source_tx_id) REFERENCES `transaction` (tx_id),
+    FOREIGN KEY(target_tx_id) REFERENCES `transaction` (tx_id)
+);
+
+create index if not exists bridge_sender_idx on bridge (sender);
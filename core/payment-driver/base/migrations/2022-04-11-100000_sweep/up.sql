@@ -0,0 +1 @@
+INSERT OR IGNORE INTO `transaction_type` (type_id, tx_type) VALUES(5, "SWEEP");
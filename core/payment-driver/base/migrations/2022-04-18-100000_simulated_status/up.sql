@@ -0,0 +1 @@
+INSERT OR IGNORE INTO `transaction_status` (status_id, status) VALUES(7, "SIMULATED");
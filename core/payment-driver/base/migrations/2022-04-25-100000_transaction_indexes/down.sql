@@ -0,0 +1,6 @@
+create index if not exists transaction_sender_idx on "transaction" (sender);
+create index if not exists payment_sender_idx on payment (sender);
+
+drop index if exists transaction_sender_network_status_nonce_idx;
+drop index if exists transaction_network_status_idx;
+drop index if exists payment_sender_network_status_idx;
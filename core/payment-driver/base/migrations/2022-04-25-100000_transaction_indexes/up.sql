@@ -0,0 +1,7 @@
+-- Covers lookups by sender, the nonce queries and paging of pending transactions.
+create index if not exists transaction_sender_network_status_nonce_idx on "transaction" (sender, network, status, nonce);
+create index if not exists transaction_network_status_idx on "transaction" (network, status);
+create index if not exists payment_sender_network_status_idx on payment (sender, network, status);
+
+drop index if exists transaction_sender_idx;
+drop index if exists payment_sender_idx;
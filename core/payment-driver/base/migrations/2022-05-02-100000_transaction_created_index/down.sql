@@ -0,0 +1,3 @@
+create index if not exists transaction_network_status_idx on "transaction" (network, status);
+
+drop index if exists transaction_network_status_created_idx;
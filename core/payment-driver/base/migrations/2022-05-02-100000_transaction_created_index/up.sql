@@ -0,0 +1,4 @@
+-- Covers lookups of expired transactions, prefix covers lookups by status.
+create index if not exists transaction_network_status_created_idx on "transaction" (network, status, time_created);
+
+drop index if exists transaction_network_status_idx;
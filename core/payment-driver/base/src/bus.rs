@@ -11,9 +11,11 @@ use std::sync::Arc;
 use ya_client_model::payment::driver_details::DriverDetails;
 use ya_client_model::NodeId;
 use ya_core_model::driver::{
-    driver_bus_id, AccountMode, GenericError, PaymentConfirmation, PaymentDetails,
+    driver_bus_id, AccountMode, DriverCapabilities, GenericError, GetProgress, PaymentConfirmation,
+    PaymentDetails, Ping, PROTOCOL_VERSION,
 };
 use ya_core_model::identity;
+pub use ya_core_model::identity::Eip712Domain;
 use ya_core_model::payment::local as payment_srv;
 use ya_service_bus::{
     typed::{service, ServiceBinder},
@@ -23,10 +25,74 @@ use ya_service_bus::{
 // Local uses
 use crate::dao::DbExecutor;
 use crate::driver::PaymentDriver;
+use crate::progress;
 
 pub async fn bind_service<Driver: PaymentDriver + 'static>(
     db: &DbExecutor,
     driver: Arc<Driver>,
+) -> anyhow::Result<()> {
+    bind_endpoints(db, driver.clone(), DriverCapabilities::ALL).await?;
+
+    log::debug!("Registering driver in payment service...");
+    let message = payment_srv::RegisterDriver {
+        driver_name: driver.get_name(),
+        details: driver_details(driver.as_ref()),
+    };
+    service(payment_srv::BUS_ID).send(message).await?.unwrap(); // Unwrap on purpose because it's NoError
+    log::debug!("Successfully registered driver in payment service.");
+
+    Ok(())
+}
+
+/// Binds driver running as a separate process, connected to yagna service bus router.
+/// Optional operations missing from `capabilities` are refused.
+pub async fn bind_external_service<Driver: PaymentDriver + 'static>(
+    db: &DbExecutor,
+    driver: Arc<Driver>,
+    capabilities: DriverCapabilities,
+) -> anyhow::Result<()> {
+    bind_endpoints(db, driver.clone(), capabilities).await?;
+
+    log::debug!("Registering external driver in payment service...");
+    let message = payment_srv::RegisterExternalDriver {
+        driver_name: driver.get_name(),
+        protocol_version: PROTOCOL_VERSION,
+        capabilities,
+        details: driver_details(driver.as_ref()),
+    };
+    service(payment_srv::BUS_ID).send(message).await??;
+    log::debug!("Successfully registered external driver in payment service.");
+
+    Ok(())
+}
+
+fn driver_details<Driver: PaymentDriver>(driver: &Driver) -> DriverDetails {
+    DriverDetails {
+        default_network: driver.get_default_network(),
+        networks: driver.get_networks(),
+        recv_init_required: driver.recv_init_required(),
+    }
+}
+
+/// Refuses an optional operation the driver didn't declare in its capabilities.
+fn check_capability(
+    capabilities: DriverCapabilities,
+    capability: DriverCapabilities,
+    operation: &str,
+) -> Result<(), GenericError> {
+    match capabilities.contains(capability) {
+        true => Ok(()),
+        false => Err(GenericError::new(format!(
+            "{} is not supported by the driver",
+            operation
+        ))),
+    }
+}
+
+async fn bind_endpoints<Driver: PaymentDriver + 'static>(
+    db: &DbExecutor,
+    driver: Arc<Driver>,
+    capabilities: DriverCapabilities,
 ) -> anyhow::Result<()> {
     log::debug!("Binding payment driver service to service bus...");
     let bus_id = driver_bus_id(driver.get_name());
@@ -43,29 +109,71 @@ pub async fn bind_service<Driver: PaymentDriver + 'static>(
             move |db, dr, c, m| async move { dr.account_event(db, c, m).await }
         )
         .bind_with_processor(
-            move |db, dr, c, m| async move { dr.enter(db, c, m).await }
+            move |db, dr, c, m| async move {
+                check_capability(capabilities, DriverCapabilities::ENTER, "Enter")?;
+                dr.enter(db, c, m).await
+            }
         )
         .bind_with_processor(
-            move |db, dr, c, m| async move { dr.exit(db, c, m).await }
+            move |db, dr, c, m| async move {
+                check_capability(capabilities, DriverCapabilities::EXIT, "Exit")?;
+                dr.exit(db, c, m).await
+            }
         )
         .bind_with_processor(
-            move |db, dr, c, m| async move { dr.fund(db, c, m).await }
+            move |db, dr, c, m| async move {
+                check_capability(capabilities, DriverCapabilities::FUND, "Fund")?;
+                dr.fund(db, c, m).await
+            }
         )
         .bind_with_processor(
             move |db, dr, c, m| async move { dr.get_account_balance(db, c, m).await }
         )
         .bind_with_processor(
-            move |db, dr, c, m| async move { dr.get_account_gas_balance(db, c, m).await }
+            move |db, dr, c, m| async move {
+                check_capability(capabilities, DriverCapabilities::GAS_BALANCE, "GetAccountGasBalance")?;
+                dr.get_account_gas_balance(db, c, m).await
+            }
+        )
+        .bind_with_processor(
+            move |db, dr, c, m| async move { dr.get_token_info(db, c, m).await }
+        )
+        .bind_with_processor(
+            move |db, dr, c, m| async move {
+                check_capability(capabilities, DriverCapabilities::READINESS, "GetReadiness")?;
+                dr.get_readiness(db, c, m).await
+            }
+        )
+        .bind_with_processor(
+            move |db, dr, c, m| async move {
+                check_capability(capabilities, DriverCapabilities::FEE_ESTIMATE, "GetFeeEstimate")?;
+                dr.get_fee_estimate(db, c, m).await
+            }
         )
         .bind_with_processor(
             move |db, dr, c, m| async move { dr.init(db, c, m).await }
         )
         .bind_with_processor(
-            move |db, dr, c, m| async move { dr.transfer(db, c, m).await }
+            move |db, dr, c, m| async move {
+                check_capability(capabilities, DriverCapabilities::TRANSFER, "Transfer")?;
+                dr.transfer(db, c, m).await
+            }
+        )
+        .bind_with_processor(
+            move |db, dr, c, m| async move { dr.bridge(db, c, m).await }
+        )
+        .bind_with_processor(
+            move |db, dr, c, m| async move { dr.get_bridge_transfers(db, c, m).await }
         )
         .bind_with_processor(
             move |db, dr, c, m| async move { dr.schedule_payment(db, c, m).await }
         )
+        .bind_with_processor(
+            move |db, dr, c, m| async move { dr.set_payment_priority(db, c, m).await }
+        )
+        .bind_with_processor(
+            move |db, dr, c, m| async move { dr.flush_payments(db, c, m).await }
+        )
         .bind_with_processor(
             move |db, dr, c, m| async move { dr.verify_payment(db, c, m).await }
         )
@@ -80,6 +188,12 @@ pub async fn bind_service<Driver: PaymentDriver + 'static>(
         )
         .bind_with_processor(
             move |db, dr, c, m| async move { dr.shut_down(db, c, m).await }
+        )
+        .bind_with_processor(
+            move |_db, _dr, _c, _m: Ping| async move { Ok(()) }
+        )
+        .bind_with_processor(
+            move |_db, _dr, _c, m: GetProgress| async move { Ok(progress::get(&m.progress_id)) }
         );
 
     log::debug!("Successfully bound payment driver service to service bus.");
@@ -89,18 +203,6 @@ pub async fn bind_service<Driver: PaymentDriver + 'static>(
     service(identity::BUS_ID).send(message).await??;
     log::debug!("Successfully subscribed payment driver service to identity events.");
 
-    log::debug!("Registering driver in payment service...");
-    let message = payment_srv::RegisterDriver {
-        driver_name: driver.get_name(),
-        details: DriverDetails {
-            default_network: driver.get_default_network(),
-            networks: driver.get_networks(),
-            recv_init_required: driver.recv_init_required(),
-        },
-    };
-    service(payment_srv::BUS_ID).send(message).await?.unwrap(); // Unwrap on purpose because it's NoError
-    log::debug!("Successfully registered driver in payment service.");
-
     Ok(())
 }
 
@@ -155,12 +257,68 @@ pub async fn sign(node_id: NodeId, payload: Vec<u8>) -> Result<Vec<u8>, GenericE
     Ok(signature)
 }
 
+pub async fn sign_typed_data(
+    node_id: NodeId,
+    domain: Eip712Domain,
+    struct_hash: Vec<u8>,
+) -> Result<Vec<u8>, GenericError> {
+    let signature = service(identity::BUS_ID)
+        .send(identity::SignTypedData {
+            node_id,
+            domain,
+            struct_hash,
+        })
+        .await
+        .map_err(GenericError::new)?
+        .map_err(GenericError::new)?;
+    Ok(signature)
+}
+
 pub async fn notify_payment(
     driver_name: &str,
     platform: &str,
     order_ids: Vec<String>,
     details: &PaymentDetails,
     confirmation: Vec<u8>,
+) -> Result<(), GenericError> {
+    send_notify_payment(
+        driver_name,
+        platform,
+        order_ids,
+        details,
+        confirmation,
+        false,
+    )
+    .await
+}
+
+/// Reports payment of transaction simulated in payments dry-run mode, see `utils::dry_run`.
+/// Payment service settles its orders, but doesn't count it as a real payment.
+pub async fn notify_simulated_payment(
+    driver_name: &str,
+    platform: &str,
+    order_ids: Vec<String>,
+    details: &PaymentDetails,
+    confirmation: Vec<u8>,
+) -> Result<(), GenericError> {
+    send_notify_payment(
+        driver_name,
+        platform,
+        order_ids,
+        details,
+        confirmation,
+        true,
+    )
+    .await
+}
+
+async fn send_notify_payment(
+    driver_name: &str,
+    platform: &str,
+    order_ids: Vec<String>,
+    details: &PaymentDetails,
+    confirmation: Vec<u8>,
+    simulated: bool,
 ) -> Result<(), GenericError> {
     let msg = payment_srv::NotifyPayment {
         driver: driver_name.to_string(),
@@ -170,6 +328,7 @@ pub async fn notify_payment(
         recipient: details.recipient.clone(),
         order_ids,
         confirmation: PaymentConfirmation { confirmation },
+        simulated,
     };
     service(payment_srv::BUS_ID)
         .send(msg)
@@ -8,8 +8,16 @@ use actix::{
     prelude::{Addr, Context},
     Actor,
 };
+use metrics::{counter, timing};
+use std::cell::Cell;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+
+// Workspace uses
+use ya_core_model::journal;
+
+// Local uses
+use crate::driver::PaymentDriver;
 
 pub use async_trait::async_trait;
 
@@ -17,15 +25,117 @@ pub use async_trait::async_trait;
 pub trait PaymentDriverCron {
     fn sendout_interval(&self) -> Duration;
     fn confirmation_interval(&self) -> Duration;
-    async fn send_out_payments(&self);
-    async fn confirm_payments(&self);
+    async fn send_out_payments(&self, report: &CycleReport);
+    async fn confirm_payments(&self, report: &CycleReport);
+}
+
+/// Work done by the driver in a single cron cycle.
+#[derive(Default)]
+pub struct CycleReport {
+    processed: Cell<u64>,
+    sent: Cell<u64>,
+    bumped: Cell<u64>,
+    confirmed: Cell<u64>,
+    failed: Cell<u64>,
+    rpc_calls: Cell<u64>,
+}
+
+impl CycleReport {
+    /// Payments turned into transactions.
+    pub fn processed(&self, count: u64) {
+        self.processed.set(self.processed.get() + count);
+    }
+
+    /// Transactions sent to the network.
+    pub fn sent(&self, count: u64) {
+        self.sent.set(self.sent.get() + count);
+    }
+
+    /// Transactions scheduled to be resent with a higher gas price.
+    pub fn bumped(&self, count: u64) {
+        self.bumped.set(self.bumped.get() + count);
+    }
+
+    /// Transactions confirmed successfully.
+    pub fn confirmed(&self, count: u64) {
+        self.confirmed.set(self.confirmed.get() + count);
+    }
+
+    /// Transactions or payments which failed permanently.
+    pub fn failed(&self, count: u64) {
+        self.failed.set(self.failed.get() + count);
+    }
+
+    /// Calls to blockchain RPC endpoints.
+    pub fn rpc_calls(&self, count: u64) {
+        self.rpc_calls.set(self.rpc_calls.get() + count);
+    }
+
+    fn is_idle(&self) -> bool {
+        self.processed.get() == 0
+            && self.sent.get() == 0
+            && self.bumped.get() == 0
+            && self.confirmed.get() == 0
+            && self.failed.get() == 0
+    }
+
+    fn to_json(&self, duration: Duration) -> serde_json::Value {
+        serde_json::json!({
+            "processed": self.processed.get(),
+            "sent": self.sent.get(),
+            "bumped": self.bumped.get(),
+            "confirmed": self.confirmed.get(),
+            "failed": self.failed.get(),
+            "rpcCalls": self.rpc_calls.get(),
+            "durationMs": duration.as_millis() as u64,
+        })
+    }
+
+    /// Logs the report and records it in metrics, cycles which did anything also in the journal.
+    async fn emit(self, driver: String, duration: Duration, interval: Duration) {
+        let idle = self.is_idle();
+        let summary = format!(
+            "driver={} processed={} sent={} bumped={} confirmed={} failed={} rpc_calls={} duration={:?}",
+            driver,
+            self.processed.get(),
+            self.sent.get(),
+            self.bumped.get(),
+            self.confirmed.get(),
+            self.failed.get(),
+            self.rpc_calls.get(),
+            duration
+        );
+        if duration > interval {
+            log::warn!("Driver cycle took longer than its interval. {}", summary);
+        } else if idle {
+            log::debug!("Driver cycle report. {}", summary);
+        } else {
+            log::info!("Driver cycle report. {}", summary);
+        }
+
+        timing!("payment.driver.cycle.time", duration.as_nanos() as u64, "driver" => driver.clone());
+        counter!("payment.driver.cycle.processed", self.processed.get(), "driver" => driver.clone());
+        counter!("payment.driver.cycle.sent", self.sent.get(), "driver" => driver.clone());
+        counter!("payment.driver.cycle.bumped", self.bumped.get(), "driver" => driver.clone());
+        counter!("payment.driver.cycle.confirmed", self.confirmed.get(), "driver" => driver.clone());
+        counter!("payment.driver.cycle.failed", self.failed.get(), "driver" => driver.clone());
+        counter!("payment.driver.cycle.rpc_calls", self.rpc_calls.get(), "driver" => driver.clone());
+
+        if !idle {
+            journal::Event::new(journal::Category::Payment, "driver-cycle")
+                .subject(&driver)
+                .details(self.to_json(duration))
+                .record()
+                .await;
+        }
+    }
 }
 
-pub struct Cron<D: PaymentDriverCron + 'static> {
+pub struct Cron<D: PaymentDriverCron + PaymentDriver + 'static> {
     driver: Arc<D>,
 }
 
-impl<D: PaymentDriverCron + 'static> Cron<D> {
+impl<D: PaymentDriverCron + PaymentDriver + 'static> Cron<D> {
     pub fn new(driver: Arc<D>) -> Addr<Self> {
         log::trace!("Creating Cron for PaymentDriver.");
         let me = Self { driver };
@@ -36,14 +146,23 @@ impl<D: PaymentDriverCron + 'static> Cron<D> {
         let _ = ctx.run_interval(self.driver.confirmation_interval(), |act, _ctx| {
             let driver = act.driver.clone();
             tokio::task::spawn_local(async move {
-                driver.confirm_payments().await;
-                driver.send_out_payments().await;
+                let report = CycleReport::default();
+                let started = Instant::now();
+                driver.confirm_payments(&report).await;
+                driver.send_out_payments(&report).await;
+                report
+                    .emit(
+                        driver.get_name(),
+                        started.elapsed(),
+                        driver.confirmation_interval(),
+                    )
+                    .await;
             });
         });
     }
 }
 
-impl<D: PaymentDriverCron + 'static> Actor for Cron<D> {
+impl<D: PaymentDriverCron + PaymentDriver + 'static> Actor for Cron<D> {
     type Context = Context<Self>;
 
     fn started(&mut self, ctx: &mut Self::Context) {
@@ -0,0 +1,127 @@
+/*
+    Data access object for bridge transfers, linking `BridgeEntity` with `bridge`
+*/
+
+// External crates
+use diesel::{self, ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl};
+
+// Workspace uses
+use ya_persistence::executor::{do_with_transaction, readonly_transaction, AsDao, PoolType};
+
+// Local uses
+use crate::{
+    dao::DbResult,
+    db::{
+        models::{BridgeEntity, Network, TransactionEntity, TransactionStatus, TxType},
+        schema::bridge::dsl,
+        schema::transaction::dsl as tx_dsl,
+    },
+};
+
+/// Bridge transfer with its source and target leg.
+pub type BridgeLegs = (BridgeEntity, TransactionEntity, Option<TransactionEntity>);
+
+pub struct BridgeDao<'c> {
+    pool: &'c PoolType,
+}
+
+impl<'c> AsDao<'c> for BridgeDao<'c> {
+    fn as_dao(pool: &'c PoolType) -> Self {
+        Self { pool }
+    }
+}
+
+impl<'c> BridgeDao<'c> {
+    /// Stores the bridge transfer along with transactions of its source leg.
+    pub async fn insert(&self, bridge: BridgeEntity, txs: Vec<TransactionEntity>) -> DbResult<()> {
+        do_with_transaction(self.pool, move |conn| {
+            for tx in txs {
+                diesel::insert_into(tx_dsl::transaction)
+                    .values(tx)
+                    .execute(conn)?;
+            }
+            diesel::insert_into(dsl::bridge)
+                .values(bridge)
+                .execute(conn)?;
+            Ok(())
+        })
+        .await
+    }
+
+    /// Newest first, optionally filtered by sender or ID.
+    pub async fn list(
+        &self,
+        sender: Option<String>,
+        bridge_id: Option<String>,
+    ) -> DbResult<Vec<BridgeLegs>> {
+        readonly_transaction(self.pool, move |conn| {
+            let mut query = dsl::bridge.into_boxed();
+            if let Some(sender) = sender {
+                query = query.filter(dsl::sender.eq(sender));
+            }
+            if let Some(bridge_id) = bridge_id {
+                query = query.filter(dsl::bridge_id.eq(bridge_id));
+            }
+            let bridges: Vec<BridgeEntity> = query.order(dsl::time_created.desc()).load(conn)?;
+
+            let mut legs = Vec::with_capacity(bridges.len());
+            for bridge in bridges {
+                let source: TransactionEntity =
+                    tx_dsl::transaction.find(&bridge.source_tx_id).first(conn)?;
+                let target: Option<TransactionEntity> = match &bridge.target_tx_id {
+                    Some(tx_id) => tx_dsl::transaction.find(tx_id).first(conn).optional()?,
+                    None => None,
+                };
+                legs.push((bridge, source, target));
+            }
+            Ok(legs)
+        })
+        .await
+    }
+
+    /// Withdrawals to `network` with confirmed source leg and no exit yet.
+    pub async fn get_awaiting_exit(
+        &self,
+        network: Network,
+    ) -> DbResult<Vec<(BridgeEntity, TransactionEntity)>> {
+        readonly_transaction(self.pool, move |conn| {
+            let bridges: Vec<(BridgeEntity, TransactionEntity)> = dsl::bridge
+                .inner_join(tx_dsl::transaction)
+                .filter(dsl::to_network.eq(network))
+                .filter(dsl::target_tx_id.is_null())
+                .filter(tx_dsl::tx_type.eq(TxType::Bridge as i32))
+                .filter(tx_dsl::status.eq(TransactionStatus::Confirmed as i32))
+                .load(conn)?;
+            Ok(bridges)
+        })
+        .await
+    }
+
+    /// Stores the exit transaction as the target leg.
+    pub async fn exit_created(&self, bridge_id: String, tx: TransactionEntity) -> DbResult<()> {
+        do_with_transaction(self.pool, move |conn| {
+            let tx_id = tx.tx_id.clone();
+            diesel::insert_into(tx_dsl::transaction)
+                .values(tx)
+                .execute(conn)?;
+            diesel::update(dsl::bridge.find(bridge_id))
+                .set((
+                    dsl::target_tx_id.eq(tx_id),
+                    dsl::last_error_msg.eq(None::<String>),
+                ))
+                .execute(conn)?;
+            Ok(())
+        })
+        .await
+    }
+
+    pub async fn update_error(&self, bridge_id: String, error: String) -> DbResult<()> {
+        do_with_transaction(self.pool, move |conn| {
+            diesel::update(dsl::bridge.find(bridge_id))
+                .set(dsl::last_error_msg.eq(error))
+                .execute(conn)?;
+            Ok(())
+        })
+        .await
+    }
+}
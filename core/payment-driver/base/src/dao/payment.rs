@@ -39,7 +39,6 @@ impl<'c> PaymentDao<'c> {
                 .filter(dsl::sender.eq(address))
                 .filter(dsl::status.eq(PAYMENT_STATUS_NOT_YET))
                 .filter(dsl::network.eq(network))
-                .order(dsl::payment_due_date.asc())
                 .load(conn)?;
             Ok(payments)
         })
@@ -66,6 +65,35 @@ impl<'c> PaymentDao<'c> {
         .await
     }
 
+    /// Sets `status` of payments `order_ids` in a single update.
+    pub async fn update_status_bulk(&self, order_ids: Vec<String>, status: i32) -> DbResult<()> {
+        if order_ids.is_empty() {
+            return Ok(());
+        }
+        do_with_transaction(self.pool, move |conn| {
+            diesel::update(dsl::payment.filter(dsl::order_id.eq_any(order_ids)))
+                .set(dsl::status.eq(status))
+                .execute(conn)?;
+            Ok(())
+        })
+        .await
+    }
+
+    /// Changes priority of a payment not processed yet. Returns `false` if there is none.
+    pub async fn update_priority(&self, order_id: String, priority: i32) -> DbResult<bool> {
+        do_with_transaction(self.pool, move |conn| {
+            let updated = diesel::update(
+                dsl::payment
+                    .filter(dsl::order_id.eq(order_id))
+                    .filter(dsl::status.eq(PAYMENT_STATUS_NOT_YET)),
+            )
+            .set(dsl::priority.eq(priority))
+            .execute(conn)?;
+            Ok(updated > 0)
+        })
+        .await
+    }
+
     pub async fn update_tx_id(&self, order_id: String, tx_id: String) -> DbResult<()> {
         do_with_transaction(self.pool, move |conn| {
             diesel::update(dsl::payment.find(order_id))
@@ -76,6 +104,20 @@ impl<'c> PaymentDao<'c> {
         .await
     }
 
+    /// Detaches payments from the transaction, so they are planned again.
+    pub async fn release_by_tx_id(&self, tx_id: String) -> DbResult<usize> {
+        do_with_transaction(self.pool, move |conn| {
+            let released = diesel::update(dsl::payment.filter(dsl::tx_id.eq(tx_id)))
+                .set((
+                    dsl::tx_id.eq::<Option<String>>(None),
+                    dsl::status.eq(PAYMENT_STATUS_NOT_YET),
+                ))
+                .execute(conn)?;
+            Ok(released)
+        })
+        .await
+    }
+
     pub async fn get_by_tx_id(&self, tx_id: String) -> DbResult<Vec<PaymentEntity>> {
         readonly_transaction(self.pool, move |conn| {
             let payments: Vec<PaymentEntity> =
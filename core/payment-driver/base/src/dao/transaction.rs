@@ -22,6 +22,36 @@ use crate::{
 };
 use chrono::Utc;
 
+const UNSENT_STATUSES: &[TransactionStatus] = &[
+    TransactionStatus::Created,
+    TransactionStatus::Resend,
+    TransactionStatus::ResendAndBumpGas,
+];
+
+const UNCONFIRMED_STATUSES: &[TransactionStatus] = &[
+    TransactionStatus::Sent,
+    TransactionStatus::ErrorSent,
+    TransactionStatus::Pending,
+];
+
+/// Position of the last transaction of a page, see `TransactionDao::get_unconfirmed_txs_page`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PageCursor {
+    pub sender: String,
+    pub nonce: i32,
+    pub tx_id: String,
+}
+
+impl From<&TransactionEntity> for PageCursor {
+    fn from(tx: &TransactionEntity) -> Self {
+        PageCursor {
+            sender: tx.sender.clone(),
+            nonce: tx.nonce,
+            tx_id: tx.tx_id.clone(),
+        }
+    }
+}
+
 #[allow(unused)]
 pub struct TransactionDao<'c> {
     pool: &'c PoolType,
@@ -64,6 +94,7 @@ impl<'c> TransactionDao<'c> {
                     dsl::sender
                         .eq(address)
                         .and(dsl::network.eq(network))
+                        .and(dsl::tx_type.ne(TxType::MetaTransfer as i32))
                         .and(dsl::time_created.gt(not_older_than)),
                 )
                 .select(dsl::nonce)
@@ -74,6 +105,29 @@ impl<'c> TransactionDao<'c> {
         .await
     }
 
+    /// Forwarder nonces used by meta-transactions, kept apart from the account nonces.
+    pub async fn get_used_forwarder_nonces(
+        &self,
+        address: &str,
+        network: Network,
+    ) -> DbResult<Vec<i32>> {
+        let address = address.to_string();
+        readonly_transaction(self.pool, move |conn| {
+            let nonces: Vec<i32> = dsl::transaction
+                .filter(
+                    dsl::sender
+                        .eq(address)
+                        .and(dsl::network.eq(network))
+                        .and(dsl::tx_type.eq(TxType::MetaTransfer as i32)),
+                )
+                .select(dsl::nonce)
+                .order(dsl::nonce.asc())
+                .load(conn)?;
+            Ok(nonces)
+        })
+        .await
+    }
+
     pub async fn get_pending_faucet_txs(
         &self,
         node_id: &str,
@@ -100,22 +154,51 @@ impl<'c> TransactionDao<'c> {
     }
 
     pub async fn get_unsent_txs(&self, network: Network) -> DbResult<Vec<TransactionEntity>> {
-        self.get_by_statuses(
-            TransactionStatus::Created,
-            TransactionStatus::Resend,
-            TransactionStatus::ResendAndBumpGas,
-            network,
-        )
-        .await
+        self.get_by_statuses(UNSENT_STATUSES, network).await
     }
 
     pub async fn get_unconfirmed_txs(&self, network: Network) -> DbResult<Vec<TransactionEntity>> {
-        self.get_by_statuses(
-            TransactionStatus::Sent,
-            TransactionStatus::ErrorSent,
-            TransactionStatus::Pending,
-            network,
-        )
+        self.get_by_statuses(UNCONFIRMED_STATUSES, network).await
+    }
+
+    /// Unsent and unconfirmed transactions created before `created_before`.
+    pub async fn get_unconfirmed_txs_created_before(
+        &self,
+        network: Network,
+        created_before: NaiveDateTime,
+    ) -> DbResult<Vec<TransactionEntity>> {
+        let statuses = UNSENT_STATUSES
+            .iter()
+            .chain(UNCONFIRMED_STATUSES)
+            .map(|&s| s as i32)
+            .collect::<Vec<_>>();
+        readonly_transaction(self.pool, move |conn| {
+            let txs: Vec<TransactionEntity> = dsl::transaction
+                .filter(
+                    dsl::network
+                        .eq(network)
+                        .and(dsl::status.eq_any(statuses))
+                        .and(dsl::time_created.lt(created_before)),
+                )
+                .load(conn)?;
+            Ok(txs)
+        })
+        .await
+    }
+
+    /// Simulated transactions, which payments weren't reported yet.
+    pub async fn get_simulated_txs(&self, network: Network) -> DbResult<Vec<TransactionEntity>> {
+        readonly_transaction(self.pool, move |conn| {
+            let txs: Vec<TransactionEntity> = dsl::transaction
+                .filter(
+                    dsl::status
+                        .eq(TransactionStatus::Simulated as i32)
+                        .and(dsl::network.eq(network))
+                        .and(dsl::tmp_onchain_txs.is_not_null()),
+                )
+                .load(conn)?;
+            Ok(txs)
+        })
         .await
     }
 
@@ -130,6 +213,24 @@ impl<'c> TransactionDao<'c> {
         .await
     }
 
+    /// Transactions on all networks, which are neither confirmed nor failed permanently.
+    pub async fn get_unfinished_txs(&self) -> DbResult<Vec<TransactionEntity>> {
+        readonly_transaction(self.pool, move |conn| {
+            let txs: Vec<TransactionEntity> = dsl::transaction
+                .filter(dsl::status.eq_any(vec![
+                    TransactionStatus::Created as i32,
+                    TransactionStatus::Sent as i32,
+                    TransactionStatus::Pending as i32,
+                    TransactionStatus::Resend as i32,
+                    TransactionStatus::ResendAndBumpGas as i32,
+                    TransactionStatus::ErrorSent as i32,
+                ]))
+                .load(conn)?;
+            Ok(txs)
+        })
+        .await
+    }
+
     pub async fn get_by_status(
         &self,
         status: TransactionStatus,
@@ -146,27 +247,88 @@ impl<'c> TransactionDao<'c> {
 
     async fn get_by_statuses(
         &self,
-        status1: TransactionStatus,
-        status2: TransactionStatus,
-        status3: TransactionStatus,
+        statuses: &[TransactionStatus],
         network: Network,
     ) -> DbResult<Vec<TransactionEntity>> {
+        let statuses = statuses.iter().map(|&s| s as i32).collect::<Vec<_>>();
         readonly_transaction(self.pool, move |conn| {
             let txs: Vec<TransactionEntity> = dsl::transaction
-                .filter(
-                    (dsl::status
-                        .eq(status1 as i32)
-                        .or(dsl::status.eq(status2 as i32))
-                        .or(dsl::status.eq(status3 as i32)))
-                    .and(dsl::network.eq(network)),
-                )
+                .filter(dsl::status.eq_any(statuses).and(dsl::network.eq(network)))
                 .load(conn)?;
             Ok(txs)
         })
         .await
     }
 
+    /// Up to `limit` unconfirmed transactions following `after`, ordered by sender and nonce.
+    pub async fn get_unconfirmed_txs_page(
+        &self,
+        network: Network,
+        after: Option<PageCursor>,
+        limit: i64,
+    ) -> DbResult<Vec<TransactionEntity>> {
+        let statuses = UNCONFIRMED_STATUSES
+            .iter()
+            .map(|&s| s as i32)
+            .collect::<Vec<_>>();
+        readonly_transaction(self.pool, move |conn| {
+            let mut query = dsl::transaction
+                .filter(dsl::status.eq_any(statuses).and(dsl::network.eq(network)))
+                .order((dsl::sender, dsl::nonce, dsl::tx_id))
+                .limit(limit)
+                .into_boxed();
+            if let Some(after) = after {
+                query = query.filter(
+                    dsl::sender
+                        .gt(after.sender.clone())
+                        .or(dsl::sender.eq(after.sender).and(
+                            dsl::nonce
+                                .gt(after.nonce)
+                                .or(dsl::nonce.eq(after.nonce).and(dsl::tx_id.gt(after.tx_id))),
+                        )),
+                );
+            }
+            let txs: Vec<TransactionEntity> = query.load(conn)?;
+            Ok(txs)
+        })
+        .await
+    }
+
     pub async fn update_tx_send_again(&self, tx_id: String, bump_gas: bool) -> DbResult<()> {
+        self.update_txs_send_again(vec![tx_id], bump_gas).await
+    }
+
+    /// Queues transactions `tx_ids` for sending again in a single update.
+    pub async fn update_txs_send_again(&self, tx_ids: Vec<String>, bump_gas: bool) -> DbResult<()> {
+        if tx_ids.is_empty() {
+            return Ok(());
+        }
+        let current_time = Utc::now().naive_utc();
+        let new_status = match bump_gas {
+            true => TransactionStatus::ResendAndBumpGas as i32,
+            false => TransactionStatus::Resend as i32,
+        };
+        do_with_transaction(self.pool, move |conn| {
+            diesel::update(dsl::transaction.filter(dsl::tx_id.eq_any(tx_ids)))
+                .set((
+                    dsl::status.eq(new_status),
+                    dsl::time_last_action.eq(current_time),
+                    dsl::time_sent.eq::<Option<NaiveDateTime>>(None),
+                ))
+                .execute(conn)?;
+            Ok(())
+        })
+        .await
+    }
+
+    /// Turns the transaction into a `TxType::Cancel` encoded as `encoded` and queues it for sending.
+    pub async fn cancel_tx(
+        &self,
+        tx_id: String,
+        encoded: String,
+        gas_limit: i32,
+        bump_gas: bool,
+    ) -> DbResult<()> {
         let current_time = Utc::now().naive_utc();
         let new_status = match bump_gas {
             true => TransactionStatus::ResendAndBumpGas as i32,
@@ -175,6 +337,10 @@ impl<'c> TransactionDao<'c> {
         do_with_transaction(self.pool, move |conn| {
             diesel::update(dsl::transaction.find(tx_id))
                 .set((
+                    dsl::tx_type.eq(TxType::Cancel as i32),
+                    dsl::encoded.eq(encoded),
+                    dsl::gas_limit.eq(gas_limit),
+                    dsl::amount_erc20.eq::<Option<String>>(None),
                     dsl::status.eq(new_status),
                     dsl::time_last_action.eq(current_time),
                     dsl::time_sent.eq::<Option<NaiveDateTime>>(None),
@@ -207,6 +373,30 @@ impl<'c> TransactionDao<'c> {
         .await
     }
 
+    /// Records the hash of a transaction signed in dry-run mode, which is confirmed without
+    /// broadcasting it, see `get_simulated_txs`.
+    pub async fn update_tx_simulated(
+        &self,
+        tx_id: String,
+        tx_hash: String,
+        gas_price: Option<String>,
+    ) -> DbResult<()> {
+        let current_time = Utc::now().naive_utc();
+        do_with_transaction(self.pool, move |conn| {
+            diesel::update(dsl::transaction.find(tx_id))
+                .set((
+                    dsl::status.eq(TransactionStatus::Simulated as i32),
+                    dsl::time_last_action.eq(current_time),
+                    dsl::time_sent.eq(current_time),
+                    dsl::tmp_onchain_txs.eq(tx_hash),
+                    dsl::current_gas_price.eq(gas_price),
+                ))
+                .execute(conn)?;
+            Ok(())
+        })
+        .await
+    }
+
     pub async fn confirm_tx(
         &self,
         tx_id: String,
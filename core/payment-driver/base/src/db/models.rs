@@ -28,9 +28,17 @@ pub const PAYMENT_STATUS_FAILED: i32 = 5;
 pub enum TxType {
     Faucet = 0,
     Transfer = 1,
+    /// Transfer relayed through a trusted forwarder (EIP-2771), `nonce` is the forwarder nonce.
+    MetaTransfer = 2,
+    /// Zero value transaction to self, which takes over the nonce of an expired transaction.
+    Cancel = 3,
+    /// Leg of a transfer between a root chain and its sidechain, see `BridgeEntity`.
+    Bridge = 4,
+    /// Transfer of earned funds above the float to a cold address, see `<NETWORK>_SWEEP_TO_ADDRESS`.
+    Sweep = 5,
 }
 
-#[derive(FromPrimitive)]
+#[derive(Clone, Copy, FromPrimitive)]
 pub enum TransactionStatus {
     Unused = 0, //previous failure
     Created = 1,
@@ -39,6 +47,8 @@ pub enum TransactionStatus {
     Confirmed = 4,
     Resend = 5,
     ResendAndBumpGas = 6,
+    /// Signed and recorded, but not broadcast in payments dry-run mode, see `utils::dry_run`.
+    Simulated = 7,
     ErrorSent = 10,
     ErrorOnChain = 11,
     ErrorNonceTooLow = 12,
@@ -95,9 +105,32 @@ pub struct PaymentEntity {
     pub status: i32,
     pub tx_id: Option<String>,
     pub network: Network,
+    /// `PaymentPriority` as integer, lower is sent first.
+    pub priority: i32,
 }
 
-#[derive(AsExpression, FromSqlRow, PartialEq, Eq, Debug, Clone, Copy, FromPrimitive, Default)]
+/// Transfer of tokens between a root chain and its sidechain. Deposits take a single
+/// transaction on the root chain, withdrawals are burnt on the sidechain (`source_tx_id`)
+/// and exited on the root chain (`target_tx_id`) once checkpointed.
+#[derive(Queryable, Clone, Debug, Identifiable, Insertable, PartialEq, Eq)]
+#[primary_key(bridge_id)]
+#[table_name = "bridge"]
+pub struct BridgeEntity {
+    pub bridge_id: String,
+    pub sender: String,
+    /// Amount of tokens as decimal.
+    pub amount: String,
+    pub from_network: Network,
+    pub to_network: Network,
+    pub source_tx_id: String,
+    pub target_tx_id: Option<String>,
+    pub time_created: NaiveDateTime,
+    pub last_error_msg: Option<String>,
+}
+
+#[derive(
+    AsExpression, FromSqlRow, PartialEq, Eq, Hash, Debug, Clone, Copy, FromPrimitive, Default,
+)]
 #[sql_type = "Integer"]
 pub enum Network {
     Mainnet = 1, //Main Ethereum chain
@@ -1,3 +1,17 @@
+table! {
+    bridge (bridge_id) {
+        bridge_id -> Text,
+        sender -> Text,
+        amount -> Text,
+        from_network -> Integer,
+        to_network -> Integer,
+        source_tx_id -> Text,
+        target_tx_id -> Nullable<Text>,
+        time_created -> Timestamp,
+        last_error_msg -> Nullable<Text>,
+    }
+}
+
 table! {
     payment (order_id) {
         order_id -> Text,
@@ -9,6 +23,7 @@ table! {
         status -> Integer,
         tx_id -> Nullable<Text>,
         network -> Integer,
+        priority -> Integer,
     }
 }
 
@@ -61,12 +76,14 @@ table! {
     }
 }
 
+joinable!(bridge -> transaction (source_tx_id));
 joinable!(payment -> payment_status (status));
 joinable!(payment -> transaction (tx_id));
 joinable!(transaction -> transaction_status (status));
 joinable!(transaction -> transaction_type (tx_type));
 
 allow_tables_to_appear_in_same_query!(
+    bridge,
     payment,
     payment_status,
     transaction,
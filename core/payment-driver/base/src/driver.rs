@@ -11,7 +11,7 @@ use std::convert::TryInto;
 
 // Local uses
 use crate::bus;
-use crate::dao::DbExecutor;
+use crate::dao::{payment::PaymentDao, DbExecutor};
 use crate::model::*;
 use crate::utils;
 
@@ -45,6 +45,42 @@ pub trait PaymentDriver {
         msg: GetAccountGasBalance,
     ) -> Result<Option<GasDetails>, GenericError>;
 
+    async fn get_token_info(
+        &self,
+        _db: DbExecutor,
+        _caller: String,
+        msg: GetTokenInfo,
+    ) -> Result<TokenInfo, GenericError> {
+        Err(GenericError::new(format!(
+            "Token info not available for platform: {}",
+            msg.platform()
+        )))
+    }
+
+    async fn get_readiness(
+        &self,
+        _db: DbExecutor,
+        _caller: String,
+        msg: GetReadiness,
+    ) -> Result<Readiness, GenericError> {
+        Err(GenericError::new(format!(
+            "Readiness check not available for platform: {}",
+            msg.platform()
+        )))
+    }
+
+    async fn get_fee_estimate(
+        &self,
+        _db: DbExecutor,
+        _caller: String,
+        msg: GetFeeEstimate,
+    ) -> Result<FeeEstimate, GenericError> {
+        Err(GenericError::new(format!(
+            "Fee estimate not available for platform: {}",
+            msg.platform()
+        )))
+    }
+
     async fn enter(
         &self,
         db: DbExecutor,
@@ -78,6 +114,28 @@ pub trait PaymentDriver {
         msg: Transfer,
     ) -> Result<String, GenericError>;
 
+    async fn bridge(
+        &self,
+        _db: DbExecutor,
+        _caller: String,
+        msg: Bridge,
+    ) -> Result<String, GenericError> {
+        Err(GenericError::new(format!(
+            "Bridge from {} not supported by {} driver",
+            msg.from_network(),
+            self.get_name()
+        )))
+    }
+
+    async fn get_bridge_transfers(
+        &self,
+        _db: DbExecutor,
+        _caller: String,
+        _msg: GetBridgeTransfers,
+    ) -> Result<Vec<BridgeTransfer>, GenericError> {
+        Ok(vec![])
+    }
+
     async fn schedule_payment(
         &self,
         db: DbExecutor,
@@ -85,6 +143,30 @@ pub trait PaymentDriver {
         msg: SchedulePayment,
     ) -> Result<String, GenericError>;
 
+    /// Pending payments are stored in the shared `payment` table, drivers keeping
+    /// them elsewhere should override this.
+    async fn set_payment_priority(
+        &self,
+        db: DbExecutor,
+        _caller: String,
+        msg: SetPaymentPriority,
+    ) -> Result<bool, GenericError> {
+        db.as_dao::<PaymentDao>()
+            .update_priority(msg.order_id, msg.priority as i32)
+            .await
+            .map_err(GenericError::new)
+    }
+
+    /// Drivers sending out payments right away have nothing to flush.
+    async fn flush_payments(
+        &self,
+        _db: DbExecutor,
+        _caller: String,
+        _msg: FlushPayments,
+    ) -> Result<(), GenericError> {
+        Ok(())
+    }
+
     async fn verify_payment(
         &self,
         db: DbExecutor,
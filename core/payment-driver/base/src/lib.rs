@@ -18,6 +18,10 @@ pub mod cron;
 pub mod dao;
 pub mod db;
 pub mod driver;
+pub mod progress;
+pub mod queue;
+pub mod schedule;
+pub mod testing;
 pub mod utils;
 
 pub use ya_core_model::driver as model;
@@ -0,0 +1,102 @@
+/*
+    Progress of long-running driver operations, polled by the CLI with `GetProgress`.
+*/
+
+// External crates
+use chrono::{Duration, Utc};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+// Workspace uses
+use ya_core_model::driver::Progress;
+
+lazy_static::lazy_static! {
+    static ref PROGRESS: Mutex<HashMap<String, Progress>> = Default::default();
+    static ref FINISHED_TTL: Duration = Duration::minutes(5);
+}
+
+pub fn get(progress_id: &str) -> Option<Progress> {
+    PROGRESS.lock().unwrap().get(progress_id).cloned()
+}
+
+/// Reports stages of an operation. Without `progress_id` nothing is reported.
+pub struct ProgressReporter {
+    progress_id: Option<String>,
+    total_steps: u32,
+}
+
+impl ProgressReporter {
+    pub fn new(progress_id: Option<String>, total_steps: u32) -> Self {
+        Self {
+            progress_id,
+            total_steps,
+        }
+    }
+
+    pub fn stage(&self, step: u32, message: impl ToString) {
+        self.update(step, message.to_string(), None, None, false);
+    }
+
+    /// Stage awaiting `confirmations` of `required`, each expected to take `block_time`.
+    pub fn confirmations(
+        &self,
+        step: u32,
+        message: impl ToString,
+        confirmations: u64,
+        required: u64,
+        block_time: std::time::Duration,
+    ) {
+        let eta = block_time * required.saturating_sub(confirmations) as u32;
+        self.update(
+            step,
+            message.to_string(),
+            Some((confirmations, required)),
+            Some(eta),
+            false,
+        );
+    }
+
+    pub fn finish(&self, message: impl ToString) {
+        self.update(self.total_steps, message.to_string(), None, None, true);
+    }
+
+    fn update(
+        &self,
+        step: u32,
+        message: String,
+        confirmations: Option<(u64, u64)>,
+        eta: Option<std::time::Duration>,
+        finished: bool,
+    ) {
+        let progress_id = match &self.progress_id {
+            Some(progress_id) => progress_id.clone(),
+            None => return,
+        };
+        let now = Utc::now();
+        let mut progress = PROGRESS.lock().unwrap();
+        progress.retain(|_, p| !p.finished || now - p.updated_at < *FINISHED_TTL);
+        progress.insert(
+            progress_id,
+            Progress {
+                step,
+                total_steps: self.total_steps,
+                message,
+                confirmations,
+                eta,
+                finished,
+                updated_at: now,
+            },
+        );
+    }
+}
+
+impl Drop for ProgressReporter {
+    /// Operation failing half-way still has to stop the CLI from polling.
+    fn drop(&mut self) {
+        if let Some(progress) = self.progress_id.as_deref().and_then(get) {
+            if !progress.finished {
+                self.update(progress.step, progress.message, None, None, true);
+            }
+        }
+    }
+}
@@ -0,0 +1,119 @@
+/*
+    Queue of pending payments, ordered by priority and due date.
+*/
+
+// External crates
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+use std::iter::FromIterator;
+
+// Local uses
+use crate::db::models::PaymentEntity;
+
+/// Pops payments with the highest priority first and within the same priority
+/// the oldest obligations first.
+#[derive(Default)]
+pub struct PaymentQueue {
+    heap: BinaryHeap<Reverse<Queued>>,
+}
+
+impl PaymentQueue {
+    pub fn push(&mut self, payment: PaymentEntity) {
+        self.heap.push(Reverse(Queued(payment)));
+    }
+
+    pub fn pop(&mut self) -> Option<PaymentEntity> {
+        self.heap.pop().map(|Reverse(Queued(payment))| payment)
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+}
+
+impl FromIterator<PaymentEntity> for PaymentQueue {
+    fn from_iter<I: IntoIterator<Item = PaymentEntity>>(iter: I) -> Self {
+        let mut queue = PaymentQueue::default();
+        iter.into_iter().for_each(|payment| queue.push(payment));
+        queue
+    }
+}
+
+impl Iterator for PaymentQueue {
+    type Item = PaymentEntity;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.pop()
+    }
+}
+
+struct Queued(PaymentEntity);
+
+impl Queued {
+    fn key(&self) -> (i32, chrono::NaiveDateTime, &str) {
+        (self.0.priority, self.0.payment_due_date, &self.0.order_id)
+    }
+}
+
+impl PartialEq for Queued {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for Queued {}
+
+impl PartialOrd for Queued {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Queued {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::models::{Network, PAYMENT_STATUS_NOT_YET};
+    use crate::model::PaymentPriority;
+    use chrono::NaiveDate;
+
+    fn payment(order_id: &str, priority: PaymentPriority, day: u32) -> PaymentEntity {
+        PaymentEntity {
+            order_id: order_id.to_string(),
+            amount: String::new(),
+            gas: String::new(),
+            sender: String::new(),
+            recipient: String::new(),
+            payment_due_date: NaiveDate::from_ymd(2022, 3, day).and_hms(0, 0, 0),
+            status: PAYMENT_STATUS_NOT_YET,
+            tx_id: None,
+            network: Network::Rinkeby,
+            priority: priority as i32,
+        }
+    }
+
+    #[test]
+    fn test_payment_queue_order() {
+        let queue: PaymentQueue = vec![
+            payment("low", PaymentPriority::Low, 1),
+            payment("normal-new", PaymentPriority::Normal, 3),
+            payment("high", PaymentPriority::High, 4),
+            payment("normal-old", PaymentPriority::Normal, 2),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(queue.len(), 4);
+
+        let order: Vec<String> = queue.map(|payment| payment.order_id).collect();
+        assert_eq!(order, vec!["high", "normal-old", "normal-new", "low"]);
+    }
+}
@@ -0,0 +1,113 @@
+/*
+    Payout schedule, lets operators settle pending payments once per interval instead of
+    every send-out cycle.
+*/
+
+// External crates
+use chrono::{DateTime, TimeZone, Utc};
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+
+/// Networks with `<NETWORK>_PAYOUT_INTERVAL_SECS` set hold pending payments until the next
+/// multiple of the interval since the UNIX epoch, e.g. `86400` pays out daily at midnight UTC.
+/// Payments held before a restart wait for the next slot after it.
+#[derive(Default)]
+pub struct PayoutSchedule {
+    intervals: HashMap<String, i64>,
+    /// Last slot settled per network.
+    settled: Mutex<HashMap<String, i64>>,
+    flushed: Mutex<HashSet<String>>,
+}
+
+impl PayoutSchedule {
+    pub fn new<'a>(networks: impl IntoIterator<Item = &'a String>) -> Self {
+        let intervals: HashMap<String, i64> = networks
+            .into_iter()
+            .filter_map(|network| {
+                let var = format!("{}_PAYOUT_INTERVAL_SECS", network.to_uppercase());
+                let interval = std::env::var(&var).ok()?;
+                match interval.parse::<i64>() {
+                    Ok(secs) if secs > 0 => Some((network.clone(), secs)),
+                    _ => {
+                        log::warn!("Invalid {}={}, paying out right away", var, interval);
+                        None
+                    }
+                }
+            })
+            .collect();
+        for (network, secs) in &intervals {
+            log::info!("Payouts on {} scheduled every {} seconds", network, secs);
+        }
+        Self {
+            intervals,
+            ..Default::default()
+        }
+    }
+
+    fn slot(interval: i64) -> i64 {
+        Utc::now().timestamp() / interval
+    }
+
+    /// Whether pending payments on `network` should be sent out in this cycle.
+    pub fn is_due(&self, network: &str) -> bool {
+        if self.flushed.lock().unwrap().contains(network) {
+            return true;
+        }
+        let interval = match self.intervals.get(network) {
+            Some(interval) => *interval,
+            None => return true,
+        };
+        let slot = Self::slot(interval);
+        let mut settled = self.settled.lock().unwrap();
+        *settled.entry(network.to_string()).or_insert(slot) < slot
+    }
+
+    /// Pending payments on `network` were sent out, the next ones wait for the next slot.
+    pub fn settled(&self, network: &str) {
+        self.flushed.lock().unwrap().remove(network);
+        if let Some(interval) = self.intervals.get(network) {
+            self.settled
+                .lock()
+                .unwrap()
+                .insert(network.to_string(), Self::slot(*interval));
+        }
+    }
+
+    /// Sends out pending payments on `network` in the next cycle regardless of the schedule.
+    pub fn flush(&self, network: &str) {
+        self.flushed.lock().unwrap().insert(network.to_string());
+    }
+
+    /// `None` when payments on `network` are sent out right away.
+    pub fn next_payout(&self, network: &str) -> Option<DateTime<Utc>> {
+        let interval = self.intervals.get(network)?;
+        Some(Utc.timestamp((Self::slot(*interval) + 1) * interval, 0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn daily(network: &str) -> PayoutSchedule {
+        PayoutSchedule {
+            intervals: vec![(network.to_string(), 86400)].into_iter().collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_payout_schedule() {
+        let schedule = daily("polygon");
+        assert!(schedule.is_due("mainnet"));
+        assert!(schedule.next_payout("mainnet").is_none());
+
+        assert!(!schedule.is_due("polygon"));
+        assert!(schedule.next_payout("polygon").unwrap() > Utc::now());
+
+        schedule.flush("polygon");
+        assert!(schedule.is_due("polygon"));
+        schedule.settled("polygon");
+        assert!(!schedule.is_due("polygon"));
+    }
+}
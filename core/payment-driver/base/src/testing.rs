@@ -0,0 +1,39 @@
+//! This module is to be used only in tests.
+use chrono::Utc;
+
+use crate::db::models::{Network, TransactionEntity, TransactionStatus, TxType};
+
+/// Polygon transfer created now, with all optional fields empty.
+pub fn transaction(
+    tx_id: &str,
+    sender: &str,
+    nonce: i32,
+    status: TransactionStatus,
+) -> TransactionEntity {
+    let now = Utc::now().naive_utc();
+    TransactionEntity {
+        tx_id: tx_id.to_string(),
+        sender: sender.to_string(),
+        nonce,
+        status: status as i32,
+        tx_type: TxType::Transfer as i32,
+        tmp_onchain_txs: None,
+        final_tx: None,
+        network: Network::Polygon,
+        starting_gas_price: None,
+        current_gas_price: None,
+        max_gas_price: None,
+        final_gas_used: None,
+        amount_base: None,
+        amount_erc20: None,
+        gas_limit: None,
+        time_created: now,
+        time_last_action: now,
+        time_sent: None,
+        time_confirmed: None,
+        last_error_msg: None,
+        resent_times: 0,
+        signature: None,
+        encoded: String::new(),
+    }
+}
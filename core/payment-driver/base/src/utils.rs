@@ -16,6 +16,17 @@ use ya_client_model::payment::Payment;
 
 const PRECISION: u64 = 1_000_000_000_000_000_000;
 
+/// Set by `yagna service run --payments-dry-run`.
+pub const DRY_RUN_ENV_VAR: &str = "YAGNA_PAYMENTS_DRY_RUN";
+
+/// Whether drivers should sign, record and simulate transactions without broadcasting them.
+pub fn dry_run() -> bool {
+    matches!(
+        std::env::var(DRY_RUN_ENV_VAR).as_deref(),
+        Ok("1") | Ok("true")
+    )
+}
+
 pub fn msg_to_payment_details(
     msg: &SchedulePayment,
     date: Option<DateTime<Utc>>,
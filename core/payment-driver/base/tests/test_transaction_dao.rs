@@ -0,0 +1,109 @@
+use chrono::{Duration, Utc};
+
+use ya_payment_driver::dao::{
+    self,
+    transaction::{PageCursor, TransactionDao},
+    DbExecutor,
+};
+use ya_payment_driver::db::models::{Network, TransactionEntity, TransactionStatus};
+use ya_payment_driver::testing::transaction;
+
+#[actix_rt::test]
+async fn test_unconfirmed_txs_page() {
+    let db = DbExecutor::in_memory("test_unconfirmed_txs_page").unwrap();
+    dao::init(&db).await.unwrap();
+    let dao: TransactionDao = db.as_dao();
+
+    let mut txs = vec![
+        transaction("a2", "0xa", 2, TransactionStatus::Pending),
+        transaction("b0", "0xb", 0, TransactionStatus::Sent),
+        transaction("a0", "0xa", 0, TransactionStatus::ErrorSent),
+        transaction("a1", "0xa", 1, TransactionStatus::Confirmed),
+        // Same nonce, e.g. a cancellation, is ordered by id.
+        transaction("a3y", "0xa", 3, TransactionStatus::Sent),
+        transaction("a3x", "0xa", 3, TransactionStatus::Sent),
+    ];
+    let mut other_network = transaction("c0", "0xc", 0, TransactionStatus::Sent);
+    other_network.network = Network::Mainnet;
+    txs.push(other_network);
+    dao.insert_transactions(txs).await.unwrap();
+
+    let mut pages = Vec::new();
+    let mut after: Option<PageCursor> = None;
+    loop {
+        let page = dao
+            .get_unconfirmed_txs_page(Network::Polygon, after.clone(), 2)
+            .await
+            .unwrap();
+        if page.is_empty() {
+            break;
+        }
+        after = page.last().map(PageCursor::from);
+        pages.push(
+            page.into_iter()
+                .map(|tx| tx.tx_id)
+                .collect::<Vec<_>>()
+                .join(","),
+        );
+    }
+    assert_eq!(pages, vec!["a0,a2", "a3x,a3y", "b0"]);
+}
+
+#[actix_rt::test]
+async fn test_update_txs_send_again() {
+    let db = DbExecutor::in_memory("test_update_txs_send_again").unwrap();
+    dao::init(&db).await.unwrap();
+    let dao: TransactionDao = db.as_dao();
+
+    dao.insert_transactions(vec![
+        transaction("a0", "0xa", 0, TransactionStatus::Pending),
+        transaction("a1", "0xa", 1, TransactionStatus::Pending),
+        transaction("a2", "0xa", 2, TransactionStatus::Pending),
+    ])
+    .await
+    .unwrap();
+    dao.update_txs_send_again(vec!["a0".to_string(), "a2".to_string()], true)
+        .await
+        .unwrap();
+    dao.update_txs_send_again(vec![], false).await.unwrap();
+
+    let status = |tx: Option<TransactionEntity>| tx.unwrap().status;
+    let bumped = TransactionStatus::ResendAndBumpGas as i32;
+    assert_eq!(status(dao.get("a0".to_string()).await.unwrap()), bumped);
+    assert_eq!(
+        status(dao.get("a1".to_string()).await.unwrap()),
+        TransactionStatus::Pending as i32
+    );
+    assert_eq!(status(dao.get("a2".to_string()).await.unwrap()), bumped);
+}
+
+#[actix_rt::test]
+async fn test_unconfirmed_txs_created_before() {
+    let db = DbExecutor::in_memory("test_unconfirmed_txs_created_before").unwrap();
+    dao::init(&db).await.unwrap();
+    let dao: TransactionDao = db.as_dao();
+
+    let created_before = Utc::now().naive_utc() - Duration::hours(1);
+    let old = |mut tx: TransactionEntity| {
+        tx.time_created = created_before - Duration::hours(1);
+        tx
+    };
+    dao.insert_transactions(vec![
+        old(transaction("a0", "0xa", 0, TransactionStatus::Created)),
+        old(transaction("a1", "0xa", 1, TransactionStatus::Pending)),
+        old(transaction("a2", "0xa", 2, TransactionStatus::Confirmed)),
+        transaction("a3", "0xa", 3, TransactionStatus::Sent),
+    ])
+    .await
+    .unwrap();
+
+    let mut expired = dao
+        .get_unconfirmed_txs_created_before(Network::Polygon, created_before)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|tx| tx.tx_id)
+        .collect::<Vec<_>>();
+    expired.sort();
+    assert_eq!(expired, vec!["a0", "a1"]);
+}
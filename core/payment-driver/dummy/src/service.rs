@@ -107,6 +107,7 @@ async fn schedule_payment(
         recipient: details.recipient,
         order_ids: vec![order_id.clone()],
         confirmation: PaymentConfirmation { confirmation },
+        simulated: false,
     };
 
     // Spawned because calling payment service while handling a call from payment service
@@ -0,0 +1,50 @@
+/*
+    Replays a trace of driver decisions (ERC20_DECISION_TRACE) against the current decision code.
+
+    cargo run --example replay_trace -- decisions.jsonl
+*/
+
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+use ya_erc20_driver::erc20::decision;
+
+#[derive(StructOpt)]
+struct Args {
+    /// Trace written by the driver with ERC20_DECISION_TRACE set
+    trace: PathBuf,
+    /// Print every decision, not only the ones with a different outcome
+    #[structopt(long)]
+    verbose: bool,
+}
+
+fn main() -> anyhow::Result<()> {
+    let args = Args::from_args();
+    let entries = decision::read_trace(&args.trace)?;
+
+    if args.verbose {
+        for entry in &entries {
+            println!(
+                "{} {} {:?} -> {:?}",
+                entry.time, entry.network, entry.inputs, entry.outcome
+            );
+        }
+    }
+
+    let mismatches = decision::replay(&entries);
+    for (entry, outcome) in &mismatches {
+        println!(
+            "MISMATCH {} {} {:?}: traced {:?}, replayed {:?}",
+            entry.time, entry.network, entry.inputs, entry.outcome, outcome
+        );
+    }
+    println!(
+        "Replayed {} decisions, {} with a different outcome",
+        entries.len(),
+        mismatches.len()
+    );
+    if !mismatches.is_empty() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
@@ -2,14 +2,20 @@
     Database Access Object, all you need to interact with the database.
 */
 
+use chrono::NaiveDateTime;
 use web3::types::U256;
 
 // Workspace uses
 use ya_payment_driver::{
-    dao::{payment::PaymentDao, transaction::TransactionDao, DbExecutor},
+    dao::{
+        bridge::{BridgeDao, BridgeLegs},
+        payment::PaymentDao,
+        transaction::{PageCursor, TransactionDao},
+        DbExecutor,
+    },
     db::models::{
-        Network, PaymentEntity, TransactionEntity, TransactionStatus, PAYMENT_STATUS_FAILED,
-        PAYMENT_STATUS_NOT_YET,
+        BridgeEntity, Network, PaymentEntity, TransactionEntity, TransactionStatus,
+        PAYMENT_STATUS_FAILED, PAYMENT_STATUS_NOT_YET,
     },
     model::{GenericError, SchedulePayment},
     utils,
@@ -34,6 +40,10 @@ impl Erc20Dao {
         self.db.as_dao::<TransactionDao>()
     }
 
+    fn bridge(&self) -> BridgeDao {
+        self.db.as_dao::<BridgeDao>()
+    }
+
     pub async fn get_pending_payments(
         &self,
         node_id: &str,
@@ -76,6 +86,7 @@ impl Erc20Dao {
             status: PAYMENT_STATUS_NOT_YET,
             tx_id: None,
             network,
+            priority: msg.priority() as i32,
         };
         if let Err(e) = self.payment().insert(payment).await {
             log::error!(
@@ -110,6 +121,25 @@ impl Erc20Dao {
         Ok(next_nonce)
     }
 
+    pub async fn get_next_forwarder_nonce(
+        &self,
+        address: &str,
+        network: Network,
+    ) -> Result<U256, GenericError> {
+        let list_of_nonces = self
+            .transaction()
+            .get_used_forwarder_nonces(address, network)
+            .await
+            .map_err(GenericError::new)?;
+
+        let next_nonce = match list_of_nonces.into_iter().max() {
+            Some(nonce) => U256::from(nonce + 1),
+            None => U256::from(0),
+        };
+
+        Ok(next_nonce)
+    }
+
     pub async fn insert_raw_transaction(&self, tx: TransactionEntity) -> String {
         let tx_id = tx.tx_id.clone();
 
@@ -120,6 +150,16 @@ impl Erc20Dao {
         tx_id
     }
 
+    pub async fn get_transaction(&self, tx_id: &str) -> Option<TransactionEntity> {
+        match self.transaction().get(tx_id.to_string()).await {
+            Ok(tx) => tx,
+            Err(e) => {
+                log::error!("Failed to fetch transaction {} : {:?}", tx_id, e);
+                None
+            }
+        }
+    }
+
     pub async fn get_payments_based_on_tx(&self, tx_id: &str) -> Vec<PaymentEntity> {
         match self.payment().get_by_tx_id(tx_id.to_string()).await {
             Ok(payments) => payments,
@@ -225,6 +265,43 @@ impl Erc20Dao {
         }
     }
 
+    pub async fn retry_send_transactions(&self, tx_ids: &[String], bump_gas: bool) {
+        if let Err(e) = self
+            .transaction()
+            .update_txs_send_again(tx_ids.to_vec(), bump_gas)
+            .await
+        {
+            log::error!("Failed to update transactions {:?} : {:?}", tx_ids, e)
+        }
+    }
+
+    pub async fn transaction_cancelled(
+        &self,
+        tx_id: &str,
+        encoded: String,
+        gas_limit: i32,
+        bump_gas: bool,
+    ) {
+        if let Err(e) = self
+            .transaction()
+            .cancel_tx(tx_id.to_string(), encoded, gas_limit, bump_gas)
+            .await
+        {
+            log::error!("Failed to cancel transaction {:?} : {:?}", tx_id, e)
+            // TO CHECK: Should it continue or stop the process...
+        }
+    }
+
+    pub async fn release_payments(&self, tx_id: &str) -> usize {
+        match self.payment().release_by_tx_id(tx_id.to_string()).await {
+            Ok(released) => released,
+            Err(e) => {
+                log::error!("Failed to release `payments` for tx {:?} : {:?}", tx_id, e);
+                0
+            }
+        }
+    }
+
     pub async fn update_tx_fields(
         &self,
         tx_id: &str,
@@ -271,6 +348,42 @@ impl Erc20Dao {
         }
     }
 
+    pub async fn transaction_simulated(
+        &self,
+        tx_id: &str,
+        tx_hash: &str,
+        gas_price: Option<String>,
+    ) {
+        if let Err(e) = self
+            .transaction()
+            .update_tx_simulated(tx_id.to_string(), tx_hash.to_string(), gas_price)
+            .await
+        {
+            log::error!("Failed to update for transaction {:?} : {:?}", tx_id, e)
+        }
+    }
+
+    pub async fn simulation_confirmed(
+        &self,
+        tx_id: &str,
+        final_hash: &str,
+        final_gas_price: Option<String>,
+    ) {
+        if let Err(e) = self
+            .transaction()
+            .confirm_tx(
+                tx_id.to_string(),
+                TransactionStatus::Simulated,
+                None,
+                Some(final_hash.to_string()),
+                final_gas_price,
+            )
+            .await
+        {
+            log::error!("Failed to update tx status for {:?} : {:?}", tx_id, e)
+        }
+    }
+
     pub async fn transaction_failed_send(&self, tx_id: &str, new_resent_count: i32, error: &str) {
         if let Err(e) = self
             .transaction()
@@ -306,6 +419,20 @@ impl Erc20Dao {
         }
     }
 
+    pub async fn payments_failed(&self, order_ids: &[String]) {
+        if let Err(e) = self
+            .payment()
+            .update_status_bulk(order_ids.to_vec(), PAYMENT_STATUS_FAILED)
+            .await
+        {
+            log::error!(
+                "Failed to update transactions failed in `payment` {:?} : {:?}",
+                order_ids,
+                e
+            )
+        }
+    }
+
     pub async fn get_unsent_txs(&self, network: Network) -> Vec<TransactionEntity> {
         match self.transaction().get_unsent_txs(network).await {
             Ok(txs) => txs,
@@ -326,6 +453,53 @@ impl Erc20Dao {
         }
     }
 
+    pub async fn get_unconfirmed_txs_created_before(
+        &self,
+        network: Network,
+        created_before: NaiveDateTime,
+    ) -> Vec<TransactionEntity> {
+        match self
+            .transaction()
+            .get_unconfirmed_txs_created_before(network, created_before)
+            .await
+        {
+            Ok(txs) => txs,
+            Err(e) => {
+                log::error!("Failed to fetch unconfirmed transactions : {:?}", e);
+                vec![]
+            }
+        }
+    }
+
+    pub async fn get_unconfirmed_txs_page(
+        &self,
+        network: Network,
+        after: Option<PageCursor>,
+        limit: i64,
+    ) -> Vec<TransactionEntity> {
+        match self
+            .transaction()
+            .get_unconfirmed_txs_page(network, after, limit)
+            .await
+        {
+            Ok(txs) => txs,
+            Err(e) => {
+                log::error!("Failed to fetch unconfirmed transactions : {:?}", e);
+                vec![]
+            }
+        }
+    }
+
+    pub async fn get_simulated_txs(&self, network: Network) -> Vec<TransactionEntity> {
+        match self.transaction().get_simulated_txs(network).await {
+            Ok(txs) => txs,
+            Err(e) => {
+                log::error!("Failed to fetch simulated transactions : {:?}", e);
+                vec![]
+            }
+        }
+    }
+
     pub async fn has_unconfirmed_txs(&self) -> Result<bool, GenericError> {
         self.transaction()
             .has_unconfirmed_txs()
@@ -350,4 +524,59 @@ impl Erc20Dao {
             }
         }
     }
+
+    pub async fn insert_bridge(
+        &self,
+        bridge: BridgeEntity,
+        txs: Vec<TransactionEntity>,
+    ) -> Result<(), GenericError> {
+        self.bridge()
+            .insert(bridge, txs)
+            .await
+            .map_err(GenericError::new)
+    }
+
+    pub async fn get_bridge_transfers(
+        &self,
+        sender: Option<String>,
+        bridge_id: Option<String>,
+    ) -> Result<Vec<BridgeLegs>, GenericError> {
+        self.bridge()
+            .list(sender, bridge_id)
+            .await
+            .map_err(GenericError::new)
+    }
+
+    pub async fn get_bridges_awaiting_exit(
+        &self,
+        network: Network,
+    ) -> Vec<(BridgeEntity, TransactionEntity)> {
+        match self.bridge().get_awaiting_exit(network).await {
+            Ok(bridges) => bridges,
+            Err(e) => {
+                log::error!("Failed to fetch bridge transfers awaiting exit : {:?}", e);
+                vec![]
+            }
+        }
+    }
+
+    pub async fn bridge_exit_created(&self, bridge_id: &str, tx: TransactionEntity) {
+        if let Err(e) = self.bridge().exit_created(bridge_id.to_string(), tx).await {
+            log::error!(
+                "Failed to store exit of bridge transfer {} : {:?}",
+                bridge_id,
+                e
+            )
+        }
+    }
+
+    pub async fn bridge_failed(&self, bridge_id: &str, error: &str) {
+        if let Err(e) = self
+            .bridge()
+            .update_error(bridge_id.to_string(), error.to_string())
+            .await
+        {
+            log::error!("Failed to update bridge transfer {} : {:?}", bridge_id, e)
+        }
+    }
 }
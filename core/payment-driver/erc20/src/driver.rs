@@ -13,7 +13,7 @@ use std::str::FromStr;
 use ya_payment_driver::{
     account::{Accounts, AccountsRc},
     bus,
-    cron::PaymentDriverCron,
+    cron::{CycleReport, PaymentDriverCron},
     dao::DbExecutor,
     db::models::Network,
     driver::{
@@ -21,14 +21,21 @@ use ya_payment_driver::{
         PaymentDriver,
     },
     model::*,
+    schedule::PayoutSchedule,
 };
 
 // Local uses
-use crate::{dao::Erc20Dao, network::SUPPORTED_NETWORKS, DRIVER_NAME, RINKEBY_NETWORK};
+use crate::{
+    dao::Erc20Dao,
+    erc20::ethereum,
+    network::{self, SUPPORTED_NETWORKS},
+    DRIVER_NAME,
+};
 
 mod api;
 mod cli;
 mod cron;
+mod sweep;
 
 lazy_static::lazy_static! {
     static ref TX_SENDOUT_INTERVAL: std::time::Duration = std::time::Duration::from_secs(
@@ -49,6 +56,8 @@ lazy_static::lazy_static! {
 pub struct Erc20Driver {
     active_accounts: AccountsRc,
     dao: Erc20Dao,
+    payout_schedule: PayoutSchedule,
+    sweep_schedule: sweep::SweepSchedule,
     sendout_lock: Mutex<()>,
     confirmation_lock: Mutex<()>,
 }
@@ -58,6 +67,8 @@ impl Erc20Driver {
         Self {
             active_accounts: Accounts::new_rc(),
             dao: Erc20Dao::new(db),
+            payout_schedule: PayoutSchedule::new(SUPPORTED_NETWORKS.keys()),
+            sweep_schedule: sweep::SweepSchedule::new(SUPPORTED_NETWORKS.keys()),
             sendout_lock: Default::default(),
             confirmation_lock: Default::default(),
         }
@@ -134,12 +145,39 @@ impl PaymentDriver for Erc20Driver {
         api::get_account_gas_balance(msg).await
     }
 
+    async fn get_token_info(
+        &self,
+        _db: DbExecutor,
+        _caller: String,
+        msg: GetTokenInfo,
+    ) -> Result<TokenInfo, GenericError> {
+        api::get_token_info(msg).await
+    }
+
+    async fn get_readiness(
+        &self,
+        _db: DbExecutor,
+        _caller: String,
+        msg: GetReadiness,
+    ) -> Result<Readiness, GenericError> {
+        api::get_readiness(msg).await
+    }
+
+    async fn get_fee_estimate(
+        &self,
+        _db: DbExecutor,
+        _caller: String,
+        msg: GetFeeEstimate,
+    ) -> Result<FeeEstimate, GenericError> {
+        api::get_fee_estimate(msg).await
+    }
+
     fn get_name(&self) -> String {
         DRIVER_NAME.to_string()
     }
 
     fn get_default_network(&self) -> String {
-        RINKEBY_NETWORK.to_string()
+        network::default_network()
     }
 
     fn get_networks(&self) -> HashMap<String, NetworkConfig> {
@@ -174,6 +212,25 @@ impl PaymentDriver for Erc20Driver {
         cli::transfer(&self.dao, msg).await
     }
 
+    async fn bridge(
+        &self,
+        _db: DbExecutor,
+        _caller: String,
+        msg: Bridge,
+    ) -> Result<String, GenericError> {
+        self.is_account_active(&msg.sender())?;
+        cli::bridge(&self.dao, msg).await
+    }
+
+    async fn get_bridge_transfers(
+        &self,
+        _db: DbExecutor,
+        _caller: String,
+        msg: GetBridgeTransfers,
+    ) -> Result<Vec<BridgeTransfer>, GenericError> {
+        cli::get_bridge_transfers(&self.dao, msg).await
+    }
+
     async fn schedule_payment(
         &self,
         _db: DbExecutor,
@@ -186,6 +243,15 @@ impl PaymentDriver for Erc20Driver {
         api::schedule_payment(&self.dao, msg).await
     }
 
+    async fn flush_payments(
+        &self,
+        _db: DbExecutor,
+        _caller: String,
+        msg: FlushPayments,
+    ) -> Result<(), GenericError> {
+        cli::flush_payments(&self.payout_schedule, msg)
+    }
+
     async fn verify_payment(
         &self,
         _db: DbExecutor,
@@ -210,14 +276,14 @@ impl PaymentDriver for Erc20Driver {
         _caller: String,
         msg: ShutDown,
     ) -> Result<(), GenericError> {
-        self.send_out_payments().await;
+        self.send_out_payments(&CycleReport::default()).await;
         // HACK: Make sure that send-out job did complete. It might have just been running in another thread (cron). In such case .send_out_payments() would not block.
         self.sendout_lock.lock().await;
         let timeout = Duration::from_std(msg.timeout)
             .map_err(|e| GenericError::new(format!("Invalid shutdown timeout: {}", e)))?;
         let deadline = Utc::now() + timeout - Duration::seconds(1);
         while {
-            self.confirm_payments().await; // Run it at least once
+            self.confirm_payments(&CycleReport::default()).await; // Run it at least once
             Utc::now() < deadline && self.dao.has_unconfirmed_txs().await? // Stop if deadline passes or there are no more transactions to confirm
         } {
             tokio::time::sleep(std::time::Duration::from_secs(1)).await;
@@ -228,7 +294,7 @@ impl PaymentDriver for Erc20Driver {
 
 #[async_trait(?Send)]
 impl PaymentDriverCron for Erc20Driver {
-    async fn confirm_payments(&self) {
+    async fn confirm_payments(&self, report: &CycleReport) {
         let guard = match self.confirmation_lock.try_lock() {
             None => {
                 log::trace!("ERC-20 confirmation job in progress.");
@@ -237,14 +303,16 @@ impl PaymentDriverCron for Erc20Driver {
             Some(guard) => guard,
         };
         log::trace!("Running ERC-20 confirmation job...");
+        let rpc_calls = ethereum::rpc_calls();
         for network_key in self.get_networks().keys() {
-            cron::confirm_payments(&self.dao, &self.get_name(), network_key).await;
+            cron::confirm_payments(&self.dao, &self.get_name(), network_key, report).await;
         }
+        report.rpc_calls(ethereum::rpc_calls() - rpc_calls);
         log::trace!("ERC-20 confirmation job complete.");
         drop(guard); // Explicit drop to tell Rust that guard is not unused variable
     }
 
-    async fn send_out_payments(&self) {
+    async fn send_out_payments(&self, report: &CycleReport) {
         let guard = match self.sendout_lock.try_lock() {
             None => {
                 log::trace!("ERC-20 send-out job in progress.");
@@ -254,25 +322,34 @@ impl PaymentDriverCron for Erc20Driver {
         };
 
         log::trace!("Running ERC-20 send-out job...");
+        let rpc_calls = ethereum::rpc_calls();
         'outer: for network_key in self.get_networks().keys() {
             let network = Network::from_str(network_key).unwrap();
             // Process payment rows
-            let accounts = self.active_accounts.borrow().list_accounts();
-            for node_id in accounts {
-                if let Err(e) =
-                    cron::process_payments_for_account(&self.dao, &node_id, network).await
-                {
-                    log::error!(
-                        "Cron: processing payment for account [{}] failed with error: {}",
-                        node_id,
-                        e
-                    );
-                    continue 'outer;
-                };
+            if self.payout_schedule.is_due(network_key) {
+                let accounts = self.active_accounts.borrow().list_accounts();
+                for node_id in accounts {
+                    if let Err(e) =
+                        cron::process_payments_for_account(&self.dao, &node_id, network, report)
+                            .await
+                    {
+                        log::error!(
+                            "Cron: processing payment for account [{}] failed with error: {}",
+                            node_id,
+                            e
+                        );
+                        continue 'outer;
+                    };
+                }
+                self.payout_schedule.settled(network_key);
             }
+            cron::process_bridge_exits(&self.dao, network, report).await;
+            let accounts = self.active_accounts.borrow().list_accounts();
+            sweep::sweep_accounts(&self.dao, &self.sweep_schedule, accounts, network, report).await;
             // Process transaction rows
-            cron::process_transactions(&self.dao, network).await;
+            cron::process_transactions(&self.dao, network, report).await;
         }
+        report.rpc_calls(ethereum::rpc_calls() - rpc_calls);
         log::trace!("ERC-20 send-out job complete.");
 
         drop(guard); // Explicit drop to tell Rust that guard is not unused variable
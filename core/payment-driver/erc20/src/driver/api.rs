@@ -10,7 +10,8 @@ use uuid::Uuid;
 use ya_payment_driver::{
     driver::BigDecimal,
     model::{
-        GasDetails, GenericError, GetAccountBalance, GetAccountGasBalance, SchedulePayment,
+        FeeEstimate, GasDetails, GenericError, GetAccountBalance, GetAccountGasBalance,
+        GetFeeEstimate, GetReadiness, GetTokenInfo, Readiness, SchedulePayment, TokenInfo,
         ValidateAllocation, VerifyPayment,
     },
 };
@@ -19,7 +20,7 @@ use ya_payment_driver::{
 use crate::{
     dao::Erc20Dao,
     driver::PaymentDetails,
-    erc20::{utils, wallet},
+    erc20::{recipient, token, utils, wallet},
     network,
 };
 
@@ -77,12 +78,34 @@ pub async fn get_account_gas_balance(
     }))
 }
 
+pub async fn get_token_info(msg: GetTokenInfo) -> Result<TokenInfo, GenericError> {
+    log::debug!("get_token_info: {:?}", msg);
+    let (network, _) = network::platform_to_network_token(msg.platform())?;
+    token::get(network).await
+}
+
+pub async fn get_readiness(msg: GetReadiness) -> Result<Readiness, GenericError> {
+    log::debug!("get_readiness: {:?}", msg);
+    let (network, _) = network::platform_to_network_token(msg.platform())?;
+    let address = utils::str_to_addr(&msg.address())?;
+    wallet::readiness(address, network, msg.amount()).await
+}
+
+pub async fn get_fee_estimate(msg: GetFeeEstimate) -> Result<FeeEstimate, GenericError> {
+    log::debug!("get_fee_estimate: {:?}", msg);
+    let (network, _) = network::platform_to_network_token(msg.platform())?;
+    wallet::fee_estimate(network, msg.payments()).await
+}
+
 pub async fn schedule_payment(
     dao: &Erc20Dao,
     msg: SchedulePayment,
 ) -> Result<String, GenericError> {
     log::debug!("schedule_payment {msg:?}");
 
+    let (network, _) = network::platform_to_network_token(msg.platform())?;
+    recipient::check(utils::str_to_addr(&msg.recipient())?, network).await?;
+
     let order_id = Uuid::new_v4().to_string();
     dao.insert_payment(&order_id, &msg).await?;
     Ok(order_id)
@@ -4,13 +4,25 @@
     Please limit the logic in this file, use local mods to handle the calls.
 */
 // Extrnal crates
-use chrono::Utc;
+use chrono::{TimeZone, Utc};
+use std::convert::TryFrom;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+use tokio::time::sleep;
+use uuid::Uuid;
+use web3::types::H256;
 
 // Workspace uses
 use ya_payment_driver::{
     bus,
-    db::models::Network,
-    model::{AccountMode, Fund, GenericError, Init, PaymentDetails, Transfer},
+    db::models::{BridgeEntity, Network, TransactionEntity, TransactionStatus},
+    driver::BigDecimal,
+    model::{
+        AccountMode, Bridge, BridgeStatus, BridgeTransfer, FlushPayments, Fund, GenericError,
+        GetBridgeTransfers, Init, PaymentDetails, Transfer,
+    },
+    progress::ProgressReporter,
+    schedule::PayoutSchedule,
 };
 use ya_utils_futures::timeout::IntoTimeoutFuture;
 
@@ -18,28 +30,49 @@ use ya_utils_futures::timeout::IntoTimeoutFuture;
 use crate::{
     dao::Erc20Dao,
     driver::Erc20Driver,
-    erc20::{utils, wallet},
+    erc20::{bridge, ethereum, ethereum::PolygonPriority, faucet, recipient, token, utils, wallet},
     network, DRIVER_NAME,
 };
 
+lazy_static::lazy_static! {
+    static ref TRANSFER_WAIT_TIMEOUT: Duration = Duration::from_secs(
+        std::env::var("ERC20_TRANSFER_WAIT_TIMEOUT_SECS")
+            .ok()
+            .and_then(|x| x.parse().ok())
+            .unwrap_or(600),
+    );
+}
+const TRANSFER_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Steps of `init` reported to the CLI.
+const INIT_STEPS: u32 = 3;
+
 pub async fn init(driver: &Erc20Driver, msg: Init) -> Result<(), GenericError> {
     log::debug!("init: {:?}", msg);
     let mode = msg.mode();
     let address = msg.address();
+    let network = network::redirect_deprecated_network(
+        msg.network().unwrap_or_else(network::default_network),
+    );
+    let msg = Init::new(address.clone(), Some(network), msg.token(), mode)
+        .with_progress_id(msg.progress_id());
+    let progress = ProgressReporter::new(msg.progress_id(), INIT_STEPS);
 
     // Ensure account is unlock before initialising send mode
     if mode.contains(AccountMode::SEND) {
         driver.is_account_active(&address)?
     }
 
-    wallet::init_wallet(&msg)
+    wallet::init_wallet(&msg, &progress)
         .timeout(Some(30))
         .await
         .map_err(GenericError::new)??;
 
     let network = network::network_like_to_network(msg.network());
     let token = network::get_network_token(network, msg.token());
+    progress.stage(3, "Registering account in payment service");
     bus::register_account(driver, &msg.address(), &network.to_string(), &token, mode).await?;
+    progress.finish("Account initialised");
 
     log::info!(
         "Initialised payment account. mode={:?}, address={}, driver={}, network={}, token={}",
@@ -52,6 +85,21 @@ pub async fn init(driver: &Erc20Driver, msg: Init) -> Result<(), GenericError> {
     Ok(())
 }
 
+pub fn flush_payments(schedule: &PayoutSchedule, msg: FlushPayments) -> Result<(), GenericError> {
+    log::debug!("flush_payments: {:?}", msg);
+    let network = Network::from_str(&msg.network).map_err(GenericError::new)?;
+    let network = network.to_string();
+    if let Some(next_payout) = schedule.next_payout(&network) {
+        log::info!(
+            "Flushing payments on {} ahead of scheduled payout at {}",
+            network,
+            next_payout
+        );
+    }
+    schedule.flush(&network);
+    Ok(())
+}
+
 pub async fn fund(dao: &Erc20Dao, msg: Fund) -> Result<String, GenericError> {
     log::debug!("fund: {:?}", msg);
     let address = msg.address();
@@ -64,7 +112,8 @@ pub async fn fund(dao: &Erc20Dao, msg: Fund) -> Result<String, GenericError> {
                 &network,
                 &address
             );
-            wallet::fund(dao, address, network)
+            let progress = ProgressReporter::new(msg.progress_id(), faucet::FUND_STEPS);
+            wallet::fund(dao, address, network, &progress)
                 .timeout(Some(60)) // Regular scenario =~ 30s
                 .await
                 .map_err(GenericError::new)??;
@@ -129,7 +178,10 @@ pub async fn transfer(dao: &Erc20Dao, msg: Transfer) -> Result<String, GenericEr
     let gas_price = msg.gas_price;
     let max_gas_price = msg.max_gas_price;
     let gasless = msg.gasless;
+    let priority = msg.priority.map(PolygonPriority::from);
+    let wait = msg.wait;
     let glm_balance = wallet::account_balance(sender_h160, network).await?;
+    recipient::check(utils::str_to_addr(&recipient)?, network).await?;
 
     if amount > glm_balance {
         return Err(GenericError::new(format!(
@@ -147,16 +199,11 @@ pub async fn transfer(dao: &Erc20Dao, msg: Transfer) -> Result<String, GenericEr
 
     if gasless {
         let tx_id = wallet::make_gasless_transfer(&details, network).await?;
+        if wait {
+            return wait_for_receipt(tx_id, network).await;
+        }
 
-        let endpoint = match network {
-            Network::Polygon => "https://polygonscan.com/tx/",
-            Network::Mainnet => "https://etherscan.io/tx/",
-            Network::Rinkeby => "https://rinkeby.etherscan.io/tx/",
-            Network::Goerli => "https://goerli.etherscan.io/tx/",
-            Network::Mumbai => "https://mumbai.polygonscan.com/tx/",
-        };
-
-        let message = format!("Follow your transaction: {}0x{:x}", endpoint, tx_id);
+        let message = format!("Follow your transaction: {}", tx_url(network, tx_id));
         Ok(message)
     } else {
         let nonce = wallet::get_next_nonce(dao, sender_h160, network).await?;
@@ -168,6 +215,7 @@ pub async fn transfer(dao: &Erc20Dao, msg: Transfer) -> Result<String, GenericEr
             gas_price,
             max_gas_price,
             gas_limit,
+            priority,
         )
         .await?;
 
@@ -183,6 +231,229 @@ pub async fn transfer(dao: &Erc20Dao, msg: Transfer) -> Result<String, GenericEr
         );
         log::info!("{}", message);
         log::debug!("tx_id={}", tx_id);
+        if wait {
+            return wait_for_transaction(dao, &tx_id, network).await;
+        }
         Ok(message)
     }
 }
+
+pub async fn bridge(dao: &Erc20Dao, msg: Bridge) -> Result<String, GenericError> {
+    log::debug!("bridge: {:?}", msg);
+    let from_network = Network::from_str(&msg.from_network()).map_err(GenericError::new)?;
+    let counterpart = bridge::counterpart(from_network)
+        .ok_or_else(|| GenericError::new(format!("No bridge from {}", from_network)))?;
+    let to_network = match msg.to_network() {
+        Some(to_network) => Network::from_str(&to_network).map_err(GenericError::new)?,
+        None => counterpart,
+    };
+    if to_network != counterpart {
+        return Err(GenericError::new(format!(
+            "No bridge from {} to {}, funds can be moved to {}",
+            from_network, to_network, counterpart
+        )));
+    }
+
+    let sender = utils::str_to_addr(&msg.sender())?;
+    let amount = msg.amount();
+    if amount <= BigDecimal::from(0) {
+        return Err(GenericError::new(format!(
+            "Invalid amount to bridge: {}",
+            amount
+        )));
+    }
+    let glm_balance = wallet::account_balance(sender, from_network).await?;
+    if amount > glm_balance {
+        return Err(GenericError::new(format!(
+            "Not enough balance for bridge transfer. balance={}, amount={}, address=0x{:x}, network={}",
+            glm_balance, amount, sender, from_network
+        )));
+    }
+
+    let amount_u256 = token::to_u256(&amount, from_network).await?;
+    let nonce = wallet::get_next_nonce(dao, sender, from_network).await?;
+    let txs = if bridge::is_root_chain(from_network) {
+        bridge::make_deposit(sender, amount_u256, from_network, nonce).await?
+    } else {
+        vec![bridge::make_withdraw(sender, amount_u256, from_network, nonce).await?]
+    };
+    for db_tx in &txs {
+        wallet::has_enough_eth_for_gas(db_tx, from_network).await?;
+    }
+
+    // Deposit is completed by its last transaction, preceding ones only approve it.
+    let source_tx_id = match txs.last() {
+        Some(db_tx) => db_tx.tx_id.clone(),
+        None => return Err(GenericError::new("No bridge transactions")),
+    };
+    let bridge_id = Uuid::new_v4().to_string();
+    let entity = BridgeEntity {
+        bridge_id: bridge_id.clone(),
+        sender: format!("0x{:x}", sender),
+        amount: amount.to_string(),
+        from_network,
+        to_network,
+        source_tx_id,
+        target_tx_id: None,
+        time_created: Utc::now().naive_utc(),
+        last_error_msg: None,
+    };
+    dao.insert_bridge(entity, txs).await?;
+
+    log::info!(
+        "Started bridge transfer. id={}, amount={}, from={}, to={}, address=0x{:x}",
+        bridge_id,
+        amount,
+        from_network,
+        to_network,
+        sender
+    );
+    Ok(bridge_id)
+}
+
+pub async fn get_bridge_transfers(
+    dao: &Erc20Dao,
+    msg: GetBridgeTransfers,
+) -> Result<Vec<BridgeTransfer>, GenericError> {
+    let sender = match msg.sender {
+        Some(sender) => Some(format!("0x{:x}", utils::str_to_addr(&sender)?)),
+        None => None,
+    };
+    dao.get_bridge_transfers(sender, msg.bridge_id)
+        .await?
+        .into_iter()
+        .map(|(entity, source, target)| {
+            Ok(BridgeTransfer {
+                status: bridge_status(&entity, &source, target.as_ref()),
+                bridge_id: entity.bridge_id,
+                sender: entity.sender,
+                amount: BigDecimal::from_str(&entity.amount).map_err(GenericError::new)?,
+                from_network: entity.from_network.to_string(),
+                to_network: entity.to_network.to_string(),
+                source_tx: source.final_tx,
+                target_tx: target.and_then(|target| target.final_tx),
+                created_at: Utc.from_utc_datetime(&entity.time_created),
+                error: entity.last_error_msg,
+            })
+        })
+        .collect()
+}
+
+fn bridge_status(
+    entity: &BridgeEntity,
+    source: &TransactionEntity,
+    target: Option<&TransactionEntity>,
+) -> BridgeStatus {
+    let confirmed = |tx: &TransactionEntity| {
+        tx.status == TransactionStatus::Confirmed as i32
+            || tx.status == TransactionStatus::Simulated as i32
+    };
+    let failed = |tx: &TransactionEntity| {
+        matches!(
+            TransactionStatus::try_from(tx.status),
+            Ok(TransactionStatus::ErrorOnChain)
+                | Ok(TransactionStatus::ErrorNonceTooLow)
+                | Ok(TransactionStatus::Unused)
+        )
+    };
+    match target {
+        Some(target) if failed(target) => BridgeStatus::Failed,
+        Some(target) if confirmed(target) => BridgeStatus::Completed,
+        Some(_) => BridgeStatus::Exiting,
+        None if failed(source) => BridgeStatus::Failed,
+        None if !confirmed(source) => BridgeStatus::Pending,
+        None if bridge::is_root_chain(entity.from_network) => BridgeStatus::Deposited,
+        None => BridgeStatus::Checkpoint,
+    }
+}
+
+fn tx_url(network: Network, tx_hash: H256) -> String {
+    let endpoint = match network {
+        Network::Polygon => "https://polygonscan.com/tx/",
+        Network::Mainnet => "https://etherscan.io/tx/",
+        Network::Rinkeby => "https://rinkeby.etherscan.io/tx/",
+        Network::Goerli => "https://goerli.etherscan.io/tx/",
+        Network::Mumbai => "https://mumbai.polygonscan.com/tx/",
+    };
+    format!("{}0x{:x}", endpoint, tx_hash)
+}
+
+/// Waits until the queued transaction is confirmed or fails.
+async fn wait_for_transaction(
+    dao: &Erc20Dao,
+    tx_id: &str,
+    network: Network,
+) -> Result<String, GenericError> {
+    let deadline = Instant::now() + *TRANSFER_WAIT_TIMEOUT;
+    while Instant::now() < deadline {
+        let tx = dao
+            .get_transaction(tx_id)
+            .await
+            .ok_or_else(|| GenericError::new(format!("Transaction {} not found", tx_id)))?;
+        match TransactionStatus::try_from(tx.status).map_err(GenericError::new)? {
+            TransactionStatus::Confirmed | TransactionStatus::ErrorOnChain => {
+                let tx_hash = tx
+                    .final_tx
+                    .as_deref()
+                    .and_then(|tx_hash| H256::from_str(tx_hash.trim_start_matches("0x")).ok())
+                    .ok_or_else(|| {
+                        GenericError::new(format!("Transaction {} has no hash", tx_id))
+                    })?;
+                return receipt(tx_hash, network).await;
+            }
+            TransactionStatus::ErrorNonceTooLow | TransactionStatus::Unused => {
+                return Err(GenericError::new(format!(
+                    "Transaction {} failed: {}",
+                    tx_id,
+                    tx.last_error_msg.unwrap_or_default()
+                )));
+            }
+            _ => sleep(TRANSFER_POLL_INTERVAL).await,
+        }
+    }
+    Err(GenericError::new(format!(
+        "Transaction {} not confirmed in {}s, it is still processed in the background",
+        tx_id,
+        TRANSFER_WAIT_TIMEOUT.as_secs()
+    )))
+}
+
+/// Waits until the sent transaction is mined.
+async fn wait_for_receipt(tx_hash: H256, network: Network) -> Result<String, GenericError> {
+    let deadline = Instant::now() + *TRANSFER_WAIT_TIMEOUT;
+    while Instant::now() < deadline {
+        if ethereum::get_tx_receipt(tx_hash, network).await?.is_some() {
+            return receipt(tx_hash, network).await;
+        }
+        sleep(TRANSFER_POLL_INTERVAL).await;
+    }
+    Err(GenericError::new(format!(
+        "Transaction not mined in {}s: {}",
+        TRANSFER_WAIT_TIMEOUT.as_secs(),
+        tx_url(network, tx_hash)
+    )))
+}
+
+async fn receipt(tx_hash: H256, network: Network) -> Result<String, GenericError> {
+    let receipt = ethereum::get_tx_receipt(tx_hash, network)
+        .await?
+        .ok_or_else(|| GenericError::new(format!("No receipt of transaction 0x{:x}", tx_hash)))?;
+    let status = match receipt.status.map(|status| status.as_u64()) {
+        Some(1) => "succeeded",
+        _ => "failed",
+    };
+    Ok(format!(
+        "Transaction {}. tx_hash=0x{:x}, block={}, gas_used={}, url={}",
+        status,
+        tx_hash,
+        receipt
+            .block_number
+            .map(|block| block.to_string())
+            .unwrap_or_default(),
+        receipt
+            .gas_used
+            .map(|gas| gas.to_string())
+            .unwrap_or_default(),
+        tx_url(network, tx_hash)
+    ))
+}
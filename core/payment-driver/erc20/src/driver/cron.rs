@@ -3,27 +3,38 @@
 */
 // Extrnal crates
 use anyhow::anyhow;
-use chrono::{Duration, TimeZone, Utc};
+use chrono::{Duration, NaiveDateTime, TimeZone, Utc};
 use lazy_static::lazy_static;
+use metrics::timing;
 use std::str::FromStr;
 use web3::types::{H256, U256};
 
 // Workspace uses
 use ya_payment_driver::{
     bus,
+    cron::CycleReport,
+    dao::transaction::PageCursor,
     db::models::{Network, PaymentEntity, TransactionEntity, TxType},
     driver::BigDecimal,
+    queue::PaymentQueue,
     utils,
 };
 
 // Local uses
 use crate::{
     dao::Erc20Dao,
-    erc20::{ethereum, wallet},
+    erc20::{
+        bridge,
+        decision::{self, Inputs},
+        ethereum, wallet,
+    },
     network,
 };
 use ya_payment_driver::db::models::TransactionStatus;
 
+/// Unconfirmed transactions checked at once, bounding memory and the size of bulk updates.
+const CONFIRM_PAGE_SIZE: i64 = 200;
+
 lazy_static! {
     static ref TX_SUMBIT_TIMEOUT: Duration = Duration::minutes(15);
     static ref ERC20_WAIT_FOR_TRANSACTION_ON_NETWORK: Duration = match std::env::var(
@@ -47,26 +58,47 @@ lazy_static! {
         Ok(Ok(seconds)) => Duration::seconds(seconds),
         _ => Duration::seconds(200),
     };
+    static ref BRIDGE_CHECKPOINT_DELAY: Duration = Duration::minutes(20);
+    static ref ERC20_TRANSACTION_TTL: Duration =
+        match std::env::var("ERC20_TRANSACTION_TTL").map(|str| str.parse::<i64>()) {
+            Ok(Ok(seconds)) => Duration::seconds(seconds),
+            _ => Duration::hours(24),
+        };
 }
 
-pub async fn confirm_payments(dao: &Erc20Dao, name: &str, network_key: &str) {
+pub async fn confirm_payments(dao: &Erc20Dao, name: &str, network_key: &str, report: &CycleReport) {
     let network = Network::from_str(network_key).unwrap();
-    let txs = dao.get_unconfirmed_txs(network).await;
+    confirm_simulated(dao, name, network, report).await;
+
+    let mut txs = dao
+        .get_unconfirmed_txs_page(network, None, CONFIRM_PAGE_SIZE)
+        .await;
     //log::debug!("confirm_payments {:?}", txs);
     let current_time = Utc::now().naive_utc();
 
-    if !txs.is_empty() {
-        // TODO: Store block number and continue only on new block
-        let block_number = match wallet::get_block_number(network).await {
-            Ok(block_number) => Some(block_number.as_u64()),
-            Err(err) => {
-                log::error!(
-                    "No block info can be downloaded, probably no connection to RPC: {:?}",
-                    err
-                );
-                None
-            }
+    if txs.is_empty() {
+        return;
+    }
+    // TODO: Store block number and continue only on new block
+    let block_number = match wallet::get_block_number(network).await {
+        Ok(block_number) => Some(block_number.as_u64()),
+        Err(err) => {
+            log::error!(
+                "No block info can be downloaded, probably no connection to RPC: {:?}",
+                err
+            );
+            None
+        }
+    };
+
+    while !txs.is_empty() {
+        let next_page = match txs.len() as i64 >= CONFIRM_PAGE_SIZE {
+            true => txs.last().map(PageCursor::from),
+            false => None,
         };
+        // Transactions to send again are updated at once, after the whole page is checked.
+        let mut resend = Vec::new();
+        let mut resend_bump = Vec::new();
 
         'main_tx_loop: for tx in txs {
             log::debug!("checking tx {:?}", &tx);
@@ -128,7 +160,9 @@ pub async fn confirm_payments(dao: &Erc20Dao, name: &str, network_key: &str) {
                     time_elapsed_from_sent
                 );
                 log::warn!("Time since last action {:?}", time_elapsed_from_last_action);
-                dao.retry_send_transaction(&tx.tx_id, false).await;
+                // None of the sent hashes is confirmed, see above, so it isn't checked again.
+                resend.push(tx.tx_id.clone());
+                continue;
             }
 
             if tmp_onchain_txs_vec.is_empty() {
@@ -167,6 +201,14 @@ pub async fn confirm_payments(dao: &Erc20Dao, name: &str, network_key: &str) {
 
             let final_gas_price = s.gas_price.map(|gas_price| gas_price.to_string());
 
+            // Forwarder does not revert when the relayed call fails, only the transfer log tells.
+            let succeeded =
+                if s.confirmed && s.succeeded && tx.tx_type == TxType::MetaTransfer as i32 {
+                    wallet::verify_tx(newest_tx, network).await.is_ok()
+                } else {
+                    s.succeeded
+                };
+
             if !s.exists_on_chain {
                 log::info!("Transaction not found on chain");
                 if time_elapsed_from_last_action > *ERC20_WAIT_FOR_TRANSACTION_ON_NETWORK {
@@ -175,7 +217,7 @@ pub async fn confirm_payments(dao: &Erc20Dao, name: &str, network_key: &str) {
                         time_elapsed_from_sent
                     );
                     log::warn!("Time since last action {:?}", time_elapsed_from_last_action);
-                    dao.retry_send_transaction(&tx.tx_id, false).await;
+                    resend.push(tx.tx_id.clone());
                 }
 
                 continue;
@@ -191,97 +233,65 @@ pub async fn confirm_payments(dao: &Erc20Dao, name: &str, network_key: &str) {
                         .and_then(|str| U256::from_dec_str(&str).ok())
                         .unwrap_or_default();
 
-                    if cur_gas_price.is_zero() || max_gas_price.is_zero() {
+                    let inputs = Inputs::Bump {
+                        current: cur_gas_price,
+                        max: max_gas_price,
+                    };
+                    if !decision::decide(network, inputs).flag() {
                         log::debug!(
-                            "Wrong gas prices: cur_gas_price: {} max_gas_price: {}",
+                            "Cannot bump gas: current_gas_price: {} max_gas_price: {}",
                             cur_gas_price,
                             max_gas_price
                         );
                         continue;
                     }
-                    if cur_gas_price >= max_gas_price {
-                        log::debug!("Cannot bump gas more: Current gas price current_gas_price: {} max_gas_price: {}", cur_gas_price, max_gas_price);
-                        continue;
-                    }
-
                     log::warn!(
                         "Transaction not found on chain for {:?}",
                         time_elapsed_from_sent
                     );
                     log::warn!("Time since last action {:?}", time_elapsed_from_last_action);
-                    dao.retry_send_transaction(&tx.tx_id, true).await;
+                    resend_bump.push(tx.tx_id.clone());
+                    report.bumped(1);
                 }
 
                 continue;
             } else if !s.confirmed {
                 log::info!("Transaction is commited, but we are waiting for confirmations");
                 continue;
-            } else if s.succeeded {
+            } else if succeeded {
+                if tx.tx_type == TxType::Cancel as i32 {
+                    match wallet::is_cancellation(newest_tx, &tx.sender, network).await {
+                        Ok(true) => {
+                            log::info!("Cancellation of expired transaction confirmed");
+                            dao.transaction_confirmed(&tx.tx_id, newest_tx, final_gas_price)
+                                .await;
+                            report.confirmed(1);
+                            let released = dao.release_payments(&tx.tx_id).await;
+                            log::info!(
+                                "Returned {} payment(s) of tx [{}] for re-planning",
+                                released,
+                                tx.tx_id
+                            );
+                            continue;
+                        }
+                        // Replaced transaction was mined first, it is handled as usual.
+                        Ok(false) => (),
+                        Err(e) => {
+                            log::error!("Error when checking cancellation: {:?}", e);
+                            continue;
+                        }
+                    }
+                }
                 log::info!("Transaction confirmed and succeeded");
+                record_confirmation_time(&tx, network);
 
                 dao.transaction_confirmed(&tx.tx_id, newest_tx, final_gas_price)
                     .await;
-                // Faucet can stop here IF the tx was a success.
-                if tx.tx_type == TxType::Faucet as i32 {
-                    log::debug!("Faucet tx confirmed, exit early. hash={}", &newest_tx);
-                    continue;
-                }
-
-                let payments = dao.get_payments_based_on_tx(&tx.tx_id).await;
-
-                // CLI Transfer ( no related payments ) can stop here IF the tx was a success.
-                if tx.tx_type == TxType::Transfer as i32 && payments.is_empty() {
-                    log::debug!("Transfer confirmed, exit early. hash={}", &newest_tx);
-                    continue;
-                }
-                let order_ids: Vec<String> = payments
-                    .iter()
-                    .map(|payment| payment.order_id.clone())
-                    .collect();
-
-                let platform = match network::network_token_to_platform(Some(network), None) {
-                    Ok(platform) => platform,
-                    Err(e) => {
-                        log::error!(
-                            "Error when converting network_token_to_platform. hash={}. Err={:?}",
-                            &newest_tx,
-                            e
-                        );
-                        continue;
-                    }
-                };
-                let details = match wallet::verify_tx(newest_tx, network).await {
-                    Ok(a) => a,
-                    Err(e) => {
-                        log::warn!("Failed to get transaction details from erc20, creating bespoke details. Error={}", e);
-
-                        let first_payment: PaymentEntity =
-                            match dao.get_first_payment(newest_tx).await {
-                                Some(p) => p,
-                                None => continue,
-                            };
-
-                        //Create bespoke payment details:
-                        // - Sender + receiver are the same
-                        // - Date is always now
-                        // - Amount needs to be updated to total of all PaymentEntity's
-                        let mut details = utils::db_to_payment_details(&first_payment);
-                        details.amount = payments
-                            .into_iter()
-                            .map(|payment| utils::db_amount_to_big_dec(payment.amount))
-                            .sum::<BigDecimal>();
-                        details
-                    }
-                };
-
-                let newest_tx = hex::decode(&newest_tx[2..]).unwrap();
-                if let Err(e) =
-                    bus::notify_payment(name, &platform, order_ids, &details, newest_tx).await
-                {
-                    log::error!("{}", e)
-                };
+                report.confirmed(1);
+                notify_payments(dao, name, network, &tx, newest_tx).await;
             } else {
                 log::info!("Transaction confirmed, but resulted in error");
+                record_confirmation_time(&tx, network);
 
                 dao.transaction_confirmed_and_failed(
                     &tx.tx_id,
@@ -290,6 +300,7 @@ pub async fn confirm_payments(dao: &Erc20Dao, name: &str, network_key: &str) {
                     "Failure on chain during execution",
                 )
                 .await;
+                report.failed(1);
 
                 let payments = dao.get_payments_based_on_tx(&tx.tx_id).await;
 
@@ -297,19 +308,139 @@ pub async fn confirm_payments(dao: &Erc20Dao, name: &str, network_key: &str) {
                     .iter()
                     .map(|payment| payment.order_id.clone())
                     .collect();
-                for order_id in order_ids.iter() {
-                    dao.payment_failed(order_id).await;
-                }
+                dao.payments_failed(&order_ids).await;
                 continue;
             }
         }
+
+        dao.retry_send_transactions(&resend, false).await;
+        dao.retry_send_transactions(&resend_bump, true).await;
+
+        txs = match next_page {
+            Some(after) => {
+                dao.get_unconfirmed_txs_page(network, Some(after), CONFIRM_PAGE_SIZE)
+                    .await
+            }
+            None => Vec::new(),
+        };
+    }
+}
+
+/// Confirms transactions simulated in payments dry-run mode, without looking them up on chain.
+/// Their payments are reported as simulated, so they settle invoices without counting
+/// as payments that happened.
+async fn confirm_simulated(dao: &Erc20Dao, name: &str, network: Network, report: &CycleReport) {
+    for tx in dao.get_simulated_txs(network).await {
+        let tx_hash = match &tx.tmp_onchain_txs {
+            Some(tx_hash) => tx_hash.clone(),
+            None => continue,
+        };
+        log::info!("Simulated transaction confirmed. hash={}", &tx_hash);
+        dao.simulation_confirmed(&tx.tx_id, &tx_hash, tx.current_gas_price.clone())
+            .await;
+        report.confirmed(1);
+        notify_payments(dao, name, network, &tx, &tx_hash).await;
+    }
+}
+
+/// Reports payments of transaction `tx` confirmed with hash `newest_tx`.
+async fn notify_payments(
+    dao: &Erc20Dao,
+    name: &str,
+    network: Network,
+    tx: &TransactionEntity,
+    newest_tx: &str,
+) {
+    // Faucet can stop here IF the tx was a success.
+    if tx.tx_type == TxType::Faucet as i32 {
+        log::debug!("Faucet tx confirmed, exit early. hash={}", &newest_tx);
+        return;
+    }
+    // Bridge legs are followed by `process_bridge_exits`.
+    if tx.tx_type == TxType::Bridge as i32 {
+        log::debug!("Bridge tx confirmed, exit early. hash={}", &newest_tx);
+        return;
+    }
+    if tx.tx_type == TxType::Sweep as i32 {
+        log::info!("Sweep to cold address confirmed. hash={}", &newest_tx);
+        return;
+    }
+
+    let payments = dao.get_payments_based_on_tx(&tx.tx_id).await;
+
+    // CLI Transfer ( no related payments ) can stop here IF the tx was a success.
+    if (tx.tx_type == TxType::Transfer as i32 || tx.tx_type == TxType::Cancel as i32)
+        && payments.is_empty()
+    {
+        log::debug!("Transfer confirmed, exit early. hash={}", &newest_tx);
+        return;
     }
+    let order_ids: Vec<String> = payments
+        .iter()
+        .map(|payment| payment.order_id.clone())
+        .collect();
+
+    let platform = match network::network_token_to_platform(Some(network), None) {
+        Ok(platform) => platform,
+        Err(e) => {
+            log::error!(
+                "Error when converting network_token_to_platform. hash={}. Err={:?}",
+                &newest_tx,
+                e
+            );
+            return;
+        }
+    };
+    // Simulated transactions aren't on chain.
+    let simulated = tx.status == TransactionStatus::Simulated as i32;
+    let verified = match simulated {
+        true => None,
+        false => match wallet::verify_tx(newest_tx, network).await {
+            Ok(a) => Some(a),
+            Err(e) => {
+                log::warn!("Failed to get transaction details from erc20, creating bespoke details. Error={}", e);
+                None
+            }
+        },
+    };
+    let details = match verified {
+        Some(details) => details,
+        None => {
+            let first_payment: PaymentEntity = match dao.get_first_payment(newest_tx).await {
+                Some(p) => p,
+                None => return,
+            };
+
+            //Create bespoke payment details:
+            // - Sender + receiver are the same
+            // - Date is always now
+            // - Amount needs to be updated to total of all PaymentEntity's
+            let mut details = utils::db_to_payment_details(&first_payment);
+            details.amount = payments
+                .into_iter()
+                .map(|payment| utils::db_amount_to_big_dec(payment.amount))
+                .sum::<BigDecimal>();
+            details
+        }
+    };
+
+    let newest_tx = hex::decode(&newest_tx[2..]).unwrap();
+    let notified = match simulated {
+        true => {
+            bus::notify_simulated_payment(name, &platform, order_ids, &details, newest_tx).await
+        }
+        false => bus::notify_payment(name, &platform, order_ids, &details, newest_tx).await,
+    };
+    if let Err(e) = notified {
+        log::error!("{}", e)
+    };
 }
 
 pub async fn process_payments_for_account(
     dao: &Erc20Dao,
     node_id: &str,
     network: Network,
+    report: &CycleReport,
 ) -> anyhow::Result<()> {
     log::trace!(
         "Processing payments for node_id={}, network={}",
@@ -339,41 +470,204 @@ pub async fn process_payments_for_account(
             )
         })?;
 
-        log::debug!("Payments: nonce={}, details={:?}", &nonce, payments);
-        for payment in payments {
-            handle_payment(dao, payment, &mut nonce).await;
+        let meta_tx = wallet::use_meta_transactions(
+            crate::erc20::utils::str_to_addr(node_id).unwrap(),
+            network,
+        )
+        .await
+        .unwrap_or_else(|e| {
+            log::warn!(
+                "Failed to check meta-transactions for account [{}] ({}). Error: {}",
+                node_id,
+                network,
+                e
+            );
+            false
+        });
+
+        log::debug!(
+            "Payments: nonce={}, meta_tx={}, details={:?}",
+            &nonce,
+            meta_tx,
+            payments
+        );
+        for payment in payments.into_iter().collect::<PaymentQueue>() {
+            handle_payment(dao, payment, &mut nonce, meta_tx, report).await;
         }
     }
     Ok(())
 }
 
-pub async fn process_transactions(dao: &Erc20Dao, network: Network) {
-    let transactions: Vec<TransactionEntity> = dao.get_unsent_txs(network).await;
+pub async fn process_transactions(dao: &Erc20Dao, network: Network, report: &CycleReport) {
+    abandon_expired_transactions(dao, network).await;
+
+    let mut transactions: Vec<TransactionEntity> = dao.get_unsent_txs(network).await;
+    // Nonces were assigned in order of payment priority, lower ones have to go first.
+    transactions.sort_by_key(|tx| tx.nonce);
 
     if !transactions.is_empty() {
         log::debug!("transactions: {:?}", transactions);
         match wallet::send_transactions(dao, transactions, network).await {
-            Ok(()) => log::debug!("transactions sent!"),
+            Ok(sent) => {
+                log::debug!("transactions sent! count={}", sent);
+                report.sent(sent);
+            }
             Err(e) => log::error!("transactions sent ERROR: {:?}", e),
         };
     }
 }
 
-async fn handle_payment(dao: &Erc20Dao, payment: PaymentEntity, nonce: &mut U256) {
+/// Sends exits of withdrawals to root chain `network`, once their burn is checkpointed.
+pub async fn process_bridge_exits(dao: &Erc20Dao, network: Network, report: &CycleReport) {
+    if !bridge::is_root_chain(network) {
+        return;
+    }
+    let current_time = Utc::now().naive_utc();
+
+    for (transfer, burn_tx) in dao.get_bridges_awaiting_exit(network).await {
+        // Checkpoints are submitted every ~30 minutes, no point in asking before.
+        let checkpoint_possible = burn_tx
+            .time_confirmed
+            .map(|time_confirmed| current_time - time_confirmed > *BRIDGE_CHECKPOINT_DELAY)
+            .unwrap_or(false);
+        let burn_tx_hash = match &burn_tx.final_tx {
+            Some(tx_hash) if checkpoint_possible => tx_hash,
+            _ => continue,
+        };
+
+        let payload = match bridge::get_exit_payload(burn_tx_hash, burn_tx.network).await {
+            Ok(Some(payload)) => payload,
+            Ok(None) => continue,
+            Err(e) => {
+                log::warn!(
+                    "Failed to get exit proof of bridge transfer [{}]: {}",
+                    transfer.bridge_id,
+                    e
+                );
+                continue;
+            }
+        };
+
+        let exit = async {
+            let sender = crate::erc20::utils::str_to_addr(&transfer.sender)?;
+            let nonce = wallet::get_next_nonce(dao, sender, network).await?;
+            bridge::make_exit(sender, payload, network, nonce).await
+        };
+        match exit.await {
+            Ok(db_tx) => {
+                log::info!(
+                    "Withdrawal [{}] checkpointed, exiting on {}",
+                    transfer.bridge_id,
+                    network
+                );
+                dao.bridge_exit_created(&transfer.bridge_id, db_tx).await;
+                report.processed(1);
+            }
+            Err(e) => {
+                log::error!(
+                    "Failed to create exit of bridge transfer [{}]: {}",
+                    transfer.bridge_id,
+                    e
+                );
+                dao.bridge_failed(&transfer.bridge_id, &e.to_string()).await;
+            }
+        }
+    }
+}
+
+/// Cancels transactions not confirmed within `ERC20_TRANSACTION_TTL`, so they stop
+/// blocking the nonce sequence. Each one is replaced by a zero value transaction to self.
+/// Payments of a transaction never broadcast are planned again right away, the others
+/// once the cancellation gets confirmed, as the original transaction can still be mined.
+async fn abandon_expired_transactions(dao: &Erc20Dao, network: Network) {
+    let current_time = Utc::now().naive_utc();
+    let txs = dao
+        .get_unconfirmed_txs_created_before(network, current_time - *ERC20_TRANSACTION_TTL)
+        .await;
+
+    for tx in txs {
+        if !is_expired(&tx, current_time, *ERC20_TRANSACTION_TTL) {
+            continue;
+        }
+
+        let (encoded, gas_limit) = match wallet::make_cancellation(&tx) {
+            Ok(cancellation) => cancellation,
+            Err(e) => {
+                log::error!("Failed to cancel expired tx [{}]: {}", tx.tx_id, e);
+                continue;
+            }
+        };
+        let broadcast = tx
+            .tmp_onchain_txs
+            .as_ref()
+            .filter(|v| !v.is_empty())
+            .is_some();
+        log::warn!(
+            "Transaction [{}] with nonce {} expired after {}, cancelling. network={}",
+            tx.tx_id,
+            tx.nonce,
+            current_time - tx.time_created,
+            network
+        );
+        dao.transaction_cancelled(&tx.tx_id, encoded, gas_limit, broadcast)
+            .await;
+
+        if !broadcast {
+            let released = dao.release_payments(&tx.tx_id).await;
+            log::info!(
+                "Returned {} payment(s) of tx [{}] for re-planning",
+                released,
+                tx.tx_id
+            );
+        }
+    }
+}
+
+/// Tells whether `tx` wasn't confirmed within `ttl` and should be cancelled.
+fn is_expired(tx: &TransactionEntity, current_time: NaiveDateTime, ttl: Duration) -> bool {
+    // Meta-transactions are broadcast by the relay, cancellations are never abandoned.
+    // Bridge legs stay, a cancelled exit would leave the burnt tokens behind.
+    if tx.tx_type == TxType::MetaTransfer as i32
+        || tx.tx_type == TxType::Cancel as i32
+        || tx.tx_type == TxType::Bridge as i32
+    {
+        return false;
+    }
+    current_time - tx.time_created > ttl
+}
+
+async fn handle_payment(
+    dao: &Erc20Dao,
+    payment: PaymentEntity,
+    nonce: &mut U256,
+    meta_tx: bool,
+    report: &CycleReport,
+) {
     let details = utils::db_to_payment_details(&payment);
     let tx_nonce = nonce.to_owned();
 
-    match wallet::make_transfer(&details, tx_nonce, payment.network, None, None, None).await {
+    let result = if meta_tx {
+        wallet::make_meta_transfer(dao, &details, payment.network).await
+    } else {
+        wallet::make_transfer(&details, tx_nonce, payment.network, None, None, None, None).await
+    };
+
+    match result {
         Ok(db_tx) => {
             let tx_id = dao.insert_raw_transaction(db_tx).await;
             dao.transaction_saved(&tx_id, &payment.order_id).await;
-            *nonce += U256::from(1);
+            report.processed(1);
+            // Meta-transactions use the forwarder nonce, the account one stays untouched.
+            if !meta_tx {
+                *nonce += U256::from(1);
+            }
         }
         Err(e) => {
             let deadline = Utc.from_utc_datetime(&payment.payment_due_date) + *TX_SUMBIT_TIMEOUT;
             if Utc::now() > deadline {
                 log::error!("Failed to submit erc20 transaction. Retry deadline reached. details={:?} error={}", payment, e);
                 dao.payment_failed(&payment.order_id).await;
+                report.failed(1);
             } else {
                 log::warn!(
                     "Failed to submit erc20 transaction. Payment will be retried until {}. details={:?} error={}",
@@ -383,3 +677,178 @@ async fn handle_payment(dao: &Erc20Dao, payment: PaymentEntity, nonce: &mut U256
         }
     };
 }
+
+/// Time from the first send to confirmation, labeled with the gas price ladder
+/// used (`POLYGON_PRIORITY`), so operators can tune `POLYGON_PREFERRED_GAS_PRICES_*`.
+fn record_confirmation_time(tx: &TransactionEntity, network: Network) {
+    let time_sent = match tx.time_sent {
+        Some(time_sent) => Utc.from_utc_datetime(&time_sent),
+        None => return,
+    };
+    let latency = match (Utc::now() - time_sent).to_std() {
+        Ok(latency) => latency,
+        Err(_) => return,
+    };
+    let priority = if tx.tx_type == TxType::MetaTransfer as i32 {
+        "meta"
+    } else if network == Network::Polygon || network == Network::Mumbai {
+        ethereum::get_polygon_priority().as_str()
+    } else {
+        "default"
+    };
+    timing!(
+        "payment.erc20.confirmation.time",
+        latency.as_nanos() as u64,
+        "network" => network.to_string(),
+        "priority" => priority
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::erc20::transaction::YagnaRawTransaction;
+    use ya_payment_driver::dao::{self, DbExecutor};
+    use ya_payment_driver::db::models::PAYMENT_STATUS_OK;
+    use ya_payment_driver::testing;
+
+    const SENDER: &str = "0xd39a168f0480b8502c2531b2ffd8588c592d713a";
+
+    fn tx(tx_id: &str, tx_type: TxType, age: Duration) -> TransactionEntity {
+        let now = Utc::now().naive_utc();
+        let raw_tx = YagnaRawTransaction {
+            gas_price: U256::from(30_000_000_000u64),
+            ..Default::default()
+        };
+        TransactionEntity {
+            tx_type: tx_type as i32,
+            amount_erc20: Some("1".to_string()),
+            time_created: now - age,
+            time_last_action: now - age,
+            encoded: serde_json::to_string(&raw_tx).unwrap(),
+            ..testing::transaction(tx_id, SENDER, 0, TransactionStatus::Created)
+        }
+    }
+
+    fn payment(order_id: &str, tx_id: &str) -> PaymentEntity {
+        PaymentEntity {
+            order_id: order_id.to_string(),
+            amount: "1".to_string(),
+            gas: "0".to_string(),
+            sender: SENDER.to_string(),
+            recipient: SENDER.to_string(),
+            payment_due_date: Utc::now().naive_utc(),
+            status: PAYMENT_STATUS_OK,
+            tx_id: Some(tx_id.to_string()),
+            network: Network::Polygon,
+            priority: 0,
+        }
+    }
+
+    async fn setup(name: &str, tx: TransactionEntity) -> (DbExecutor, Erc20Dao) {
+        let db = DbExecutor::in_memory(name).unwrap();
+        dao::init(&db).await.unwrap();
+        let tx_id = tx.tx_id.clone();
+        db.as_dao::<dao::transaction::TransactionDao>()
+            .insert_transactions(vec![tx])
+            .await
+            .unwrap();
+        db.as_dao::<dao::payment::PaymentDao>()
+            .insert(payment("order", &tx_id))
+            .await
+            .unwrap();
+        (db.clone(), Erc20Dao::new(db))
+    }
+
+    async fn get_tx(db: &DbExecutor, tx_id: &str) -> TransactionEntity {
+        db.as_dao::<dao::transaction::TransactionDao>()
+            .get(tx_id.to_string())
+            .await
+            .unwrap()
+            .unwrap()
+    }
+
+    async fn get_payments(db: &DbExecutor, tx_id: &str) -> Vec<PaymentEntity> {
+        db.as_dao::<dao::payment::PaymentDao>()
+            .get_by_tx_id(tx_id.to_string())
+            .await
+            .unwrap()
+    }
+
+    async fn get_pending_payments(db: &DbExecutor) -> Vec<PaymentEntity> {
+        db.as_dao::<dao::payment::PaymentDao>()
+            .get_pending_payments(SENDER.to_string(), Network::Polygon)
+            .await
+            .unwrap()
+    }
+
+    #[test]
+    fn test_is_expired() {
+        let now = Utc::now().naive_utc();
+        let ttl = Duration::hours(1);
+        let old = Duration::hours(2);
+
+        assert!(is_expired(&tx("a", TxType::Transfer, old), now, ttl));
+        assert!(!is_expired(
+            &tx("b", TxType::Transfer, Duration::minutes(30)),
+            now,
+            ttl
+        ));
+        assert!(!is_expired(&tx("c", TxType::MetaTransfer, old), now, ttl));
+        assert!(!is_expired(&tx("d", TxType::Cancel, old), now, ttl));
+        assert!(!is_expired(&tx("e", TxType::Bridge, old), now, ttl));
+    }
+
+    #[actix_rt::test]
+    async fn test_abandon_not_broadcast() {
+        let age = *ERC20_TRANSACTION_TTL + Duration::minutes(1);
+        let (db, dao) = setup(
+            "test_abandon_not_broadcast",
+            tx("tx", TxType::Transfer, age),
+        )
+        .await;
+
+        abandon_expired_transactions(&dao, Network::Polygon).await;
+
+        // Never broadcast, payment is planned again right away.
+        let tx = get_tx(&db, "tx").await;
+        assert_eq!(tx.tx_type, TxType::Cancel as i32);
+        assert_eq!(tx.status, TransactionStatus::Resend as i32);
+        assert!(get_payments(&db, "tx").await.is_empty());
+        let pending = get_pending_payments(&db).await;
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].tx_id, None);
+    }
+
+    #[actix_rt::test]
+    async fn test_abandon_broadcast() {
+        let age = *ERC20_TRANSACTION_TTL + Duration::minutes(1);
+        let mut tx = tx("tx", TxType::Transfer, age);
+        tx.status = TransactionStatus::Sent as i32;
+        tx.tmp_onchain_txs = Some("0x01".to_string());
+        let (db, dao) = setup("test_abandon_broadcast", tx).await;
+
+        abandon_expired_transactions(&dao, Network::Polygon).await;
+
+        // Original can still be mined, payment waits for the cancellation.
+        let tx = get_tx(&db, "tx").await;
+        assert_eq!(tx.tx_type, TxType::Cancel as i32);
+        assert_eq!(tx.status, TransactionStatus::ResendAndBumpGas as i32);
+        assert_eq!(get_payments(&db, "tx").await.len(), 1);
+        assert!(get_pending_payments(&db).await.is_empty());
+    }
+
+    #[actix_rt::test]
+    async fn test_abandon_skips_recent() {
+        let (db, dao) = setup(
+            "test_abandon_skips_recent",
+            tx("tx", TxType::Transfer, Duration::minutes(1)),
+        )
+        .await;
+
+        abandon_expired_transactions(&dao, Network::Polygon).await;
+
+        assert_eq!(get_tx(&db, "tx").await.tx_type, TxType::Transfer as i32);
+        assert_eq!(get_payments(&db, "tx").await.len(), 1);
+    }
+}
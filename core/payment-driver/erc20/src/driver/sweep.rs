@@ -0,0 +1,255 @@
+/*
+    Sweeps earned funds from the hot payout identity to a cold address.
+
+    Networks with `<NETWORK>_SWEEP_TO_ADDRESS` set transfer GLM above
+    `<NETWORK>_SWEEP_FLOAT` once per `<NETWORK>_SWEEP_INTERVAL_SECS`.
+*/
+// Extrnal crates
+use chrono::Utc;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use web3::types::H160;
+
+// Workspace uses
+use ya_payment_driver::{
+    cron::CycleReport,
+    db::models::{Network, TxType},
+    driver::BigDecimal,
+    model::{GenericError, PaymentDetails},
+    utils,
+};
+
+// Local uses
+use crate::{dao::Erc20Dao, erc20::wallet};
+
+const DEFAULT_SWEEP_INTERVAL_SECS: i64 = 86400;
+
+#[derive(Clone, Debug, PartialEq)]
+struct SweepPolicy {
+    cold_address: H160,
+    float: BigDecimal,
+    interval: i64,
+}
+
+impl SweepPolicy {
+    fn from_env(network: &str) -> Option<Self> {
+        let prefix = network.to_uppercase();
+        let var = |name: &str| std::env::var(format!("{}_{}", prefix, name)).ok();
+
+        let cold_address = var("SWEEP_TO_ADDRESS")?;
+        let cold_address = match utils::str_to_addr(&cold_address) {
+            Ok(address) => address,
+            Err(e) => {
+                log::warn!("Invalid {}_SWEEP_TO_ADDRESS, not sweeping: {}", prefix, e);
+                return None;
+            }
+        };
+        let float = match var("SWEEP_FLOAT").map(|v| v.parse::<BigDecimal>()) {
+            None => BigDecimal::from(0),
+            Some(Ok(float)) if float >= BigDecimal::from(0) => float,
+            Some(_) => {
+                log::warn!("Invalid {}_SWEEP_FLOAT, not sweeping", prefix);
+                return None;
+            }
+        };
+        let interval = match var("SWEEP_INTERVAL_SECS").map(|v| v.parse::<i64>()) {
+            None => DEFAULT_SWEEP_INTERVAL_SECS,
+            Some(Ok(secs)) if secs > 0 => secs,
+            Some(_) => {
+                log::warn!("Invalid {}_SWEEP_INTERVAL_SECS, not sweeping", prefix);
+                return None;
+            }
+        };
+        Some(Self {
+            cold_address,
+            float,
+            interval,
+        })
+    }
+
+    /// GLM to sweep out of `balance` keeping the float and funds of pending payments.
+    fn amount(&self, balance: BigDecimal, pending: BigDecimal) -> Option<BigDecimal> {
+        let amount = balance - &self.float - pending;
+        match amount > BigDecimal::from(0) {
+            true => Some(amount),
+            false => None,
+        }
+    }
+}
+
+/// Like payouts, sweeps happen at multiples of the interval since the UNIX epoch.
+#[derive(Default)]
+pub struct SweepSchedule {
+    policies: HashMap<String, SweepPolicy>,
+    /// Last slot swept per network.
+    swept: Mutex<HashMap<String, i64>>,
+}
+
+impl SweepSchedule {
+    pub fn new<'a>(networks: impl IntoIterator<Item = &'a String>) -> Self {
+        let policies: HashMap<String, SweepPolicy> = networks
+            .into_iter()
+            .filter_map(|network| Some((network.clone(), SweepPolicy::from_env(network)?)))
+            .collect();
+        for (network, policy) in &policies {
+            log::info!(
+                "Funds on {} above {} swept to {:#x} every {} seconds",
+                network,
+                policy.float,
+                policy.cold_address,
+                policy.interval
+            );
+        }
+        Self {
+            policies,
+            ..Default::default()
+        }
+    }
+
+    fn slot(interval: i64) -> i64 {
+        Utc::now().timestamp() / interval
+    }
+
+    /// Policy of `network` if a sweep is due, the first one right after start.
+    fn due(&self, network: &str) -> Option<&SweepPolicy> {
+        let policy = self.policies.get(network)?;
+        let slot = Self::slot(policy.interval);
+        match self.swept.lock().unwrap().get(network) {
+            Some(swept) if *swept >= slot => None,
+            _ => Some(policy),
+        }
+    }
+
+    fn swept(&self, network: &str) {
+        if let Some(policy) = self.policies.get(network) {
+            self.swept
+                .lock()
+                .unwrap()
+                .insert(network.to_string(), Self::slot(policy.interval));
+        }
+    }
+}
+
+/// Sweeps every active account on `network`, if due.
+/// Accounts with transactions in flight are skipped until the next slot.
+pub async fn sweep_accounts(
+    dao: &Erc20Dao,
+    schedule: &SweepSchedule,
+    accounts: Vec<String>,
+    network: Network,
+    report: &CycleReport,
+) {
+    let network_key = network.to_string();
+    let policy = match schedule.due(&network_key) {
+        Some(policy) => policy,
+        None => return,
+    };
+
+    let mut in_flight = dao.get_unsent_txs(network).await;
+    in_flight.extend(dao.get_unconfirmed_txs(network).await);
+    for node_id in accounts {
+        let sender = node_id.to_lowercase();
+        if in_flight
+            .iter()
+            .any(|tx| tx.sender.to_lowercase() == sender)
+        {
+            log::debug!("Not sweeping [{}], it has transactions in flight.", node_id);
+            continue;
+        }
+        match sweep_account(dao, policy, &node_id, network).await {
+            Ok(Some(_)) => report.processed(1),
+            Ok(None) => (),
+            Err(e) => log::error!("Sweeping [{}] on {} failed: {}", node_id, network, e),
+        }
+    }
+    schedule.swept(&network_key);
+}
+
+async fn sweep_account(
+    dao: &Erc20Dao,
+    policy: &SweepPolicy,
+    node_id: &str,
+    network: Network,
+) -> Result<Option<String>, GenericError> {
+    let sender = utils::str_to_addr(node_id)?;
+    if sender == policy.cold_address {
+        return Ok(None);
+    }
+    let balance = wallet::account_balance(sender, network).await?;
+    let pending = dao
+        .get_pending_payments(node_id, network)
+        .await
+        .into_iter()
+        .map(|payment| utils::db_amount_to_big_dec(payment.amount))
+        .sum::<BigDecimal>();
+    let amount = match policy.amount(balance, pending) {
+        Some(amount) => amount,
+        None => return Ok(None),
+    };
+
+    let details = PaymentDetails {
+        recipient: format!("{:#x}", policy.cold_address),
+        sender: node_id.to_string(),
+        amount,
+        date: Some(Utc::now()),
+    };
+    let nonce = wallet::get_next_nonce(dao, sender, network).await?;
+    let mut db_tx = wallet::make_transfer(&details, nonce, network, None, None, None, None).await?;
+    db_tx.tx_type = TxType::Sweep as i32;
+    let tx_id = dao.insert_raw_transaction(db_tx).await;
+
+    log::info!(
+        "Sweeping {} GLM from {} to {:#x} on {}. tx_id={}",
+        details.amount,
+        node_id,
+        policy.cold_address,
+        network,
+        tx_id
+    );
+    metrics::counter!("payment.erc20.sweeps", 1);
+    Ok(Some(tx_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(float: i64) -> SweepPolicy {
+        SweepPolicy {
+            cold_address: H160::repeat_byte(0xc0),
+            float: BigDecimal::from(float),
+            interval: 86400,
+        }
+    }
+
+    #[test]
+    fn test_sweep_amount() {
+        let policy = policy(10);
+        assert_eq!(
+            policy.amount(BigDecimal::from(25), BigDecimal::from(5)),
+            Some(BigDecimal::from(10))
+        );
+        assert_eq!(
+            policy.amount(BigDecimal::from(15), BigDecimal::from(5)),
+            None
+        );
+        assert_eq!(
+            policy.amount(BigDecimal::from(3), BigDecimal::from(0)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_sweep_schedule() {
+        let schedule = SweepSchedule {
+            policies: vec![("polygon".to_string(), policy(0))]
+                .into_iter()
+                .collect(),
+            ..Default::default()
+        };
+        assert!(schedule.due("mainnet").is_none());
+        assert!(schedule.due("polygon").is_some());
+        schedule.swept("polygon");
+        assert!(schedule.due("polygon").is_none());
+    }
+}
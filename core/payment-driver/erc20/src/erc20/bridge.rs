@@ -0,0 +1,262 @@
+/*
+    Transfers of GLM between Ethereum and Polygon through the Polygon PoS bridge.
+
+    Deposit: `approve` of the `ERC20Predicate` and `RootChainManager.depositFor` on the root
+    chain, tokens are minted on Polygon with one of the next state syncs.
+    Withdrawal: `withdraw` burns tokens on Polygon. Once the burn is checkpointed on the root
+    chain, `RootChainManager.exit` releases them with the proof from the proof generator API.
+*/
+
+use std::env;
+
+use awc::http;
+use chrono::Utc;
+use ethabi::{Contract, Token};
+use lazy_static::lazy_static;
+use serde::Deserialize;
+use web3::types::{H160, U256};
+
+use ya_payment_driver::{
+    db::models::{Network, TransactionEntity, TxType},
+    model::GenericError,
+};
+
+use crate::erc20::{ethereum, utils};
+
+/// Signature of the ERC-20 `Transfer` event emitted by the burn.
+const TRANSFER_EVENT_SIGNATURE: &str =
+    "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+
+lazy_static! {
+    static ref BRIDGE_APPROVE_GAS: U256 = U256::from(60_000);
+    static ref BRIDGE_DEPOSIT_GAS: U256 = U256::from(200_000);
+    static ref BRIDGE_WITHDRAW_GAS: U256 = U256::from(100_000);
+    static ref BRIDGE_EXIT_GAS: U256 = U256::from(500_000);
+    static ref IERC20: Contract =
+        Contract::load(&include_bytes!("../contracts/ierc20.json")[..]).unwrap();
+    static ref ROOT_CHAIN_MANAGER: Contract =
+        Contract::load(&include_bytes!("../contracts/root_chain_manager.json")[..]).unwrap();
+    static ref CHILD_ERC20: Contract =
+        Contract::load(&include_bytes!("../contracts/child_erc20.json")[..]).unwrap();
+}
+
+#[derive(Clone, Copy, Debug)]
+struct RootChainConfig {
+    root_chain_manager: H160,
+    erc20_predicate: H160,
+}
+
+#[derive(Deserialize, Debug)]
+struct ProofResponse {
+    result: String,
+}
+
+/// Network on the other side of the bridge.
+pub fn counterpart(network: Network) -> Option<Network> {
+    match network {
+        Network::Mainnet => Some(Network::Polygon),
+        Network::Polygon => Some(Network::Mainnet),
+        Network::Goerli => Some(Network::Mumbai),
+        Network::Mumbai => Some(Network::Goerli),
+        Network::Rinkeby => None,
+    }
+}
+
+/// Deposits go from the root chain, withdrawals from its sidechain.
+pub fn is_root_chain(network: Network) -> bool {
+    matches!(network, Network::Mainnet | Network::Goerli)
+}
+
+fn address_from(env: &str, default: &str) -> Result<H160, GenericError> {
+    utils::str_to_addr(&env::var(env).unwrap_or_else(|_| default.to_string()))
+}
+
+fn get_root_chain_config(network: Network) -> Result<RootChainConfig, GenericError> {
+    match network {
+        Network::Mainnet => Ok(RootChainConfig {
+            root_chain_manager: address_from(
+                "MAINNET_ROOT_CHAIN_MANAGER_ADDRESS",
+                "0xA0c68C638235ee32657e8f720a23ceC1bFc77C77",
+            )?,
+            erc20_predicate: address_from(
+                "MAINNET_ERC20_PREDICATE_ADDRESS",
+                "0x40ec5B33f54e0E8A33A975908C5BA1c14e5BbbDf",
+            )?,
+        }),
+        Network::Goerli => Ok(RootChainConfig {
+            root_chain_manager: address_from(
+                "GOERLI_ROOT_CHAIN_MANAGER_ADDRESS",
+                "0xBbD7cBFA79faee899Eaf900F13C9065bF03B1A74",
+            )?,
+            erc20_predicate: address_from(
+                "GOERLI_ERC20_PREDICATE_ADDRESS",
+                "0xdD6596F2029e6233DEFfaCa316e6A95217d4Dc34",
+            )?,
+        }),
+        _ => Err(GenericError::new(format!(
+            "{} is not a root chain of the bridge",
+            network
+        ))),
+    }
+}
+
+fn get_proof_api_url(network: Network) -> String {
+    match network {
+        Network::Mumbai => env::var("MUMBAI_BRIDGE_PROOF_API").unwrap_or_else(|_| {
+            "https://proof-generator.polygon.technology/api/v1/mumbai/exit-payload".to_string()
+        }),
+        _ => env::var("POLYGON_BRIDGE_PROOF_API").unwrap_or_else(|_| {
+            "https://proof-generator.polygon.technology/api/v1/matic/exit-payload".to_string()
+        }),
+    }
+}
+
+fn encode(contract: &Contract, func: &str, params: &[Token]) -> Result<Vec<u8>, GenericError> {
+    contract
+        .function(func)
+        .and_then(|function| function.encode_input(params))
+        .map_err(GenericError::new)
+}
+
+async fn make_call(
+    sender: H160,
+    to: H160,
+    data: Vec<u8>,
+    gas: U256,
+    nonce: U256,
+    network: Network,
+) -> Result<TransactionEntity, GenericError> {
+    let raw_tx = ethereum::prepare_raw_call(to, data, gas, nonce, network).await?;
+    let max_gas_price = match network {
+        Network::Polygon | Network::Mumbai => Some(
+            utils::convert_float_gas_to_u256(ethereum::get_polygon_maximum_price()).to_string(),
+        ),
+        _ => None,
+    };
+    Ok(ethereum::create_dao_entity(
+        nonce,
+        sender,
+        raw_tx.gas_price.to_string(),
+        max_gas_price,
+        raw_tx.gas.as_u32() as i32,
+        serde_json::to_string(&raw_tx).map_err(GenericError::new)?,
+        network,
+        Utc::now(),
+        TxType::Bridge,
+        None,
+    ))
+}
+
+/// Deposit of `amount` from root chain `network`, `approve` followed by `depositFor`.
+pub async fn make_deposit(
+    sender: H160,
+    amount: U256,
+    network: Network,
+    nonce: U256,
+) -> Result<Vec<TransactionEntity>, GenericError> {
+    let config = get_root_chain_config(network)?;
+    let token = ethereum::get_glm_contract_address(network);
+    let approve = encode(
+        &IERC20,
+        "approve",
+        &[Token::Address(config.erc20_predicate), Token::Uint(amount)],
+    )?;
+    let deposit = encode(
+        &ROOT_CHAIN_MANAGER,
+        "depositFor",
+        &[
+            Token::Address(sender),
+            Token::Address(token),
+            Token::Bytes(ethabi::encode(&[Token::Uint(amount)])),
+        ],
+    )?;
+    Ok(vec![
+        make_call(sender, token, approve, *BRIDGE_APPROVE_GAS, nonce, network).await?,
+        make_call(
+            sender,
+            config.root_chain_manager,
+            deposit,
+            *BRIDGE_DEPOSIT_GAS,
+            nonce + 1,
+            network,
+        )
+        .await?,
+    ])
+}
+
+/// Burn of `amount` on sidechain `network`, which starts a withdrawal.
+pub async fn make_withdraw(
+    sender: H160,
+    amount: U256,
+    network: Network,
+    nonce: U256,
+) -> Result<TransactionEntity, GenericError> {
+    let token = ethereum::get_glm_contract_address(network);
+    let withdraw = encode(&CHILD_ERC20, "withdraw", &[Token::Uint(amount)])?;
+    make_call(
+        sender,
+        token,
+        withdraw,
+        *BRIDGE_WITHDRAW_GAS,
+        nonce,
+        network,
+    )
+    .await
+}
+
+/// Exit on root chain `network`, which completes a withdrawal.
+pub async fn make_exit(
+    sender: H160,
+    payload: Vec<u8>,
+    network: Network,
+    nonce: U256,
+) -> Result<TransactionEntity, GenericError> {
+    let config = get_root_chain_config(network)?;
+    let exit = encode(&ROOT_CHAIN_MANAGER, "exit", &[Token::Bytes(payload)])?;
+    make_call(
+        sender,
+        config.root_chain_manager,
+        exit,
+        *BRIDGE_EXIT_GAS,
+        nonce,
+        network,
+    )
+    .await
+}
+
+/// Proof of burn `tx_hash` on sidechain `network`, `None` until it is checkpointed.
+pub async fn get_exit_payload(
+    tx_hash: &str,
+    network: Network,
+) -> Result<Option<Vec<u8>>, GenericError> {
+    let url = format!(
+        "{}/{}?eventSignature={}",
+        get_proof_api_url(network).trim_end_matches('/'),
+        tx_hash,
+        TRANSFER_EVENT_SIGNATURE
+    );
+    let mut resp = awc::Client::new().get(url).send().await.map_err(|e| {
+        GenericError::new(format!("While sending a request to the proof API: {}", e))
+    })?;
+
+    match resp.status() {
+        http::StatusCode::OK => {
+            let body: ProofResponse = resp.json().await.map_err(|e| {
+                GenericError::new(format!("While parsing proof API response: {}", e))
+            })?;
+            let payload =
+                hex::decode(body.result.trim_start_matches("0x")).map_err(GenericError::new)?;
+            Ok(Some(payload))
+        }
+        status => {
+            let body = resp.body().await.map_err(GenericError::new)?;
+            log::debug!(
+                "No exit proof of {} yet, status code: {}, body {}",
+                tx_hash,
+                status,
+                String::from_utf8_lossy(body.as_ref())
+            );
+            Ok(None)
+        }
+    }
+}
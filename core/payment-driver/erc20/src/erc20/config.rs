@@ -16,9 +16,17 @@ use crate::erc20::utils;
 pub struct EnvConfiguration {
     pub glm_contract_address: Address,
     pub glm_faucet_address: Option<Address>,
+    /// Trusted forwarder (EIP-2771) used to relay meta-transactions, if deployed.
+    pub glm_forwarder_address: Option<Address>,
     pub required_confirmations: u64,
 }
 
+fn forwarder_address_from(env: &str) -> Option<Address> {
+    env::var(env)
+        .ok()
+        .map(|address| utils::str_to_addr(&address).unwrap())
+}
+
 lazy_static! {
     pub static ref RINKEBY_CONFIG: EnvConfiguration = EnvConfiguration {
         glm_contract_address: utils::str_to_addr(
@@ -33,6 +41,7 @@ lazy_static! {
             )
             .unwrap()
         ),
+        glm_forwarder_address: forwarder_address_from("RINKEBY_TGLM_FORWARDER_ADDRESS"),
         required_confirmations: {
             match env::var("ERC20_RINKEBY_REQUIRED_CONFIRMATIONS").map(|s| s.parse()) {
                 Ok(Ok(x)) => x,
@@ -47,6 +56,7 @@ lazy_static! {
         )
         .unwrap(),
         glm_faucet_address: None,
+        glm_forwarder_address: forwarder_address_from("MAINNET_GLM_FORWARDER_ADDRESS"),
         required_confirmations: {
             match env::var("ERC20_MAINNET_REQUIRED_CONFIRMATIONS").map(|s| s.parse()) {
                 Ok(Ok(x)) => x,
@@ -61,6 +71,7 @@ lazy_static! {
         )
         .unwrap(),
         glm_faucet_address: None,
+        glm_forwarder_address: forwarder_address_from("GOERLI_TGLM_FORWARDER_ADDRESS"),
         required_confirmations: {
             match env::var("ERC20_GOERLI_REQUIRED_CONFIRMATIONS").map(|s| s.parse()) {
                 Ok(Ok(x)) => x,
@@ -75,6 +86,7 @@ lazy_static! {
         )
         .unwrap(),
         glm_faucet_address: None,
+        glm_forwarder_address: forwarder_address_from("MUMBAI_TGLM_FORWARDER_ADDRESS"),
         required_confirmations: {
             match env::var("ERC20_MUMBAI_REQUIRED_CONFIRMATIONS").map(|s| s.parse()) {
                 Ok(Ok(x)) => x,
@@ -89,6 +101,7 @@ lazy_static! {
         )
         .unwrap(),
         glm_faucet_address: None,
+        glm_forwarder_address: forwarder_address_from("POLYGON_GLM_FORWARDER_ADDRESS"),
         required_confirmations: {
             match env::var("ERC20_POLYGON_REQUIRED_CONFIRMATIONS").map(|s| s.parse()) {
                 Ok(Ok(x)) => x,
@@ -0,0 +1,286 @@
+/*
+    Scheduling and gas decisions of the driver, as pure functions of their inputs.
+
+    With ERC20_DECISION_TRACE set, inputs and outcome of every decision are appended to
+    that file as JSON lines, so a trace reported by a user can be replayed with
+    `cargo run --example replay_trace -- <trace>`.
+*/
+
+// External crates
+use chrono::{DateTime, Utc};
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::sync::Mutex;
+use web3::types::U256;
+
+// Workspace uses
+use ya_payment_driver::db::models::Network;
+
+// Local uses
+use crate::erc20::ethereum::{PolygonGasPriceMethod, PolygonPriority};
+use crate::erc20::utils::convert_float_gas_to_u256;
+
+pub const DECISION_TRACE_ENV_VAR: &str = "ERC20_DECISION_TRACE";
+
+lazy_static! {
+    static ref TRACE: Mutex<Option<File>> = Mutex::new(open_trace());
+}
+
+/// Inputs of a decision, read from the database, the network or the configuration.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum Inputs {
+    /// Nonce of the next transaction of an account.
+    Nonce { network_nonce: U256, db_nonce: U256 },
+    /// Gas price of a new transfer, from the gas price reported by the network.
+    OracleGasPrice { oracle: U256 },
+    /// Gas price of a new transfer, capped with the maximum price.
+    TransferGasPrice { proposed: U256, max: Option<U256> },
+    /// Gas price of a transaction about to be (re)sent.
+    SendGasPrice {
+        bump: bool,
+        current: Option<U256>,
+        starting: Option<U256>,
+        method: PolygonGasPriceMethod,
+        priority: PolygonPriority,
+    },
+    /// Whether a transaction pending for too long is sent again with a higher gas price.
+    Bump { current: U256, max: U256 },
+    /// Whether payments are relayed, when the account can't cover gas of a transfer.
+    MetaTx {
+        balance: U256,
+        gas_price: U256,
+        gas: U256,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Outcome {
+    Flag(bool),
+    Value(U256),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TraceEntry {
+    pub time: DateTime<Utc>,
+    pub network: String,
+    #[serde(flatten)]
+    pub inputs: Inputs,
+    pub outcome: Outcome,
+}
+
+impl Inputs {
+    pub fn decide(&self) -> Outcome {
+        match self {
+            Inputs::Nonce {
+                network_nonce,
+                db_nonce,
+            } => Outcome::Value(*network_nonce.max(db_nonce)),
+            Inputs::OracleGasPrice { oracle } => {
+                // Small addition to be first in queue.
+                let small_gas_bump = U256::from(1000);
+                match *oracle / 1000 > small_gas_bump {
+                    true => Outcome::Value(*oracle + small_gas_bump),
+                    false => Outcome::Value(*oracle),
+                }
+            }
+            Inputs::TransferGasPrice { proposed, max } => match max {
+                Some(max) if proposed > max => Outcome::Value(*max),
+                _ => Outcome::Value(*proposed),
+            },
+            Inputs::SendGasPrice {
+                bump,
+                current,
+                starting,
+                method,
+                priority,
+            } => Outcome::Value(match (current, starting) {
+                (Some(current), _) if *bump => bump_gas_price(*current, *method, *priority),
+                (Some(current), _) => *current,
+                (None, Some(starting)) => *starting,
+                (None, None) => convert_float_gas_to_u256(priority.prices()[1]),
+            }),
+            Inputs::Bump { current, max } => {
+                Outcome::Flag(!current.is_zero() && !max.is_zero() && current < max)
+            }
+            Inputs::MetaTx {
+                balance,
+                gas_price,
+                gas,
+            } => Outcome::Flag(*balance < *gas_price * *gas),
+        }
+    }
+}
+
+impl Outcome {
+    pub fn flag(&self) -> bool {
+        matches!(self, Outcome::Flag(true))
+    }
+
+    pub fn value(&self) -> U256 {
+        match self {
+            Outcome::Value(value) => *value,
+            Outcome::Flag(flag) => U256::from(*flag as u8),
+        }
+    }
+}
+
+/// Decides on `inputs` and appends the decision to the trace, when enabled.
+pub fn decide(network: Network, inputs: Inputs) -> Outcome {
+    let outcome = inputs.decide();
+    if let Ok(mut trace) = TRACE.lock() {
+        if let Some(file) = trace.as_mut() {
+            let entry = TraceEntry {
+                time: Utc::now(),
+                network: network.to_string(),
+                inputs,
+                outcome: outcome.clone(),
+            };
+            let written = serde_json::to_string(&entry)
+                .map_err(std::io::Error::from)
+                .and_then(|line| writeln!(file, "{}", line));
+            if let Err(e) = written {
+                log::warn!("Unable to write decision trace, disabling it: {}", e);
+                trace.take();
+            }
+        }
+    }
+    outcome
+}
+
+fn open_trace() -> Option<File> {
+    let path = std::env::var(DECISION_TRACE_ENV_VAR).ok()?;
+    match OpenOptions::new().create(true).append(true).open(&path) {
+        Ok(file) => {
+            log::info!("Tracing erc20 driver decisions to {}", path);
+            Some(file)
+        }
+        Err(e) => {
+            log::warn!("Unable to open decision trace {}: {}", path, e);
+            None
+        }
+    }
+}
+
+pub fn read_trace(path: &Path) -> anyhow::Result<Vec<TraceEntry>> {
+    BufReader::new(File::open(path)?)
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, Ok(line) if line.trim().is_empty()))
+        .map(|(n, line)| {
+            serde_json::from_str(&line?)
+                .map_err(|e| anyhow::anyhow!("invalid entry on line {}: {}", n + 1, e))
+        })
+        .collect()
+}
+
+/// Decides again on inputs of traced decisions, returning ones with a different outcome.
+pub fn replay(entries: &[TraceEntry]) -> Vec<(&TraceEntry, Outcome)> {
+    entries
+        .iter()
+        .filter_map(|entry| {
+            let outcome = entry.inputs.decide();
+            match outcome == entry.outcome {
+                true => None,
+                false => Some((entry, outcome)),
+            }
+        })
+        .collect()
+}
+
+/// At least 11% more, the next step of the price table with the static method.
+pub fn bump_gas_price(
+    gas_price: U256,
+    method: PolygonGasPriceMethod,
+    priority: PolygonPriority,
+) -> U256 {
+    let min_bump_num: U256 = U256::from(111u64);
+    let min_bump_den: U256 = U256::from(100u64);
+    let min_gas = gas_price * min_bump_num / min_bump_den;
+
+    match method {
+        PolygonGasPriceMethod::PolygonGasPriceDynamic => {
+            //ignore maximum gas price, because we have to bump at least 10% so the transaction will be accepted
+            min_gas
+        }
+        PolygonGasPriceMethod::PolygonGasPriceStatic => priority
+            .prices()
+            .iter()
+            .map(|&f| convert_float_gas_to_u256(f))
+            .find(|&gas_price_step| gas_price_step > min_gas)
+            .unwrap_or(min_gas),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gwei(gwei: u64) -> U256 {
+        U256::from(gwei) * U256::exp10(9)
+    }
+
+    #[test]
+    fn test_bump_gas_price() {
+        let bumped = bump_gas_price(
+            gwei(30),
+            PolygonGasPriceMethod::PolygonGasPriceStatic,
+            PolygonPriority::PolygonPriorityFast,
+        );
+        assert_eq!(bumped, convert_float_gas_to_u256(40.01));
+        let bumped = bump_gas_price(
+            gwei(100),
+            PolygonGasPriceMethod::PolygonGasPriceDynamic,
+            PolygonPriority::PolygonPriorityFast,
+        );
+        assert_eq!(bumped, gwei(111));
+    }
+
+    #[test]
+    fn test_replay() {
+        let inputs = Inputs::SendGasPrice {
+            bump: true,
+            current: Some(gwei(100)),
+            starting: Some(gwei(30)),
+            method: PolygonGasPriceMethod::PolygonGasPriceDynamic,
+            priority: PolygonPriority::PolygonPrioritySlow,
+        };
+        let entry = TraceEntry {
+            time: Utc::now(),
+            network: Network::Polygon.to_string(),
+            outcome: inputs.decide(),
+            inputs,
+        };
+        let line = serde_json::to_string(&entry).unwrap();
+        let entry: TraceEntry = serde_json::from_str(&line).unwrap();
+        assert!(replay(&[entry.clone()]).is_empty());
+
+        let changed = TraceEntry {
+            outcome: Outcome::Value(gwei(100)),
+            ..entry
+        };
+        let mismatches = replay(std::slice::from_ref(&changed));
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].1, Outcome::Value(gwei(111)));
+    }
+
+    #[test]
+    fn test_bump_decision() {
+        let bump = |current, max| {
+            Inputs::Bump {
+                current: gwei(current),
+                max: gwei(max),
+            }
+            .decide()
+            .flag()
+        };
+        assert!(bump(30, 40));
+        assert!(!bump(40, 40));
+        assert!(!bump(0, 40));
+        assert!(!bump(30, 0));
+    }
+}
@@ -0,0 +1,66 @@
+/*
+    EIP-712 hashing of typed structured data.
+
+    Only messages are hashed here, the domain separator and the final digest are computed
+    by the identity service when signing, see `ethereum::sign_typed_data`.
+*/
+
+use ethabi::Token;
+use web3::types::H256;
+
+use crate::erc20::eth_utils::keccak256_hash;
+
+/// `hashStruct` of a struct described by `type_signature`, with `fields` already encoded
+/// (dynamic values and nested structs hashed, see [`hash_bytes`]).
+pub fn hash_struct(type_signature: &str, fields: Vec<Token>) -> H256 {
+    let mut tokens = vec![hash_bytes(type_signature.as_bytes())];
+    tokens.extend(fields);
+    H256::from_slice(&keccak256_hash(&ethabi::encode(&tokens)))
+}
+
+/// Encoding of a `bytes` or `string` member.
+pub fn hash_bytes(bytes: &[u8]) -> Token {
+    Token::FixedBytes(keccak256_hash(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use web3::types::H160;
+
+    use super::*;
+
+    const PERSON_TYPE: &str = "Person(string name,address wallet)";
+    const MAIL_TYPE: &str =
+        "Mail(Person from,Person to,string contents)Person(string name,address wallet)";
+
+    fn person(name: &str, wallet: &str) -> Token {
+        let hash = hash_struct(
+            PERSON_TYPE,
+            vec![
+                hash_bytes(name.as_bytes()),
+                Token::Address(H160::from_str(wallet).unwrap()),
+            ],
+        );
+        Token::FixedBytes(hash.as_bytes().to_vec())
+    }
+
+    // Example from the EIP-712 specification.
+    #[test]
+    fn test_hash_struct() {
+        let mail = hash_struct(
+            MAIL_TYPE,
+            vec![
+                person("Cow", "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"),
+                person("Bob", "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"),
+                hash_bytes(b"Hello, Bob!"),
+            ],
+        );
+
+        assert_eq!(
+            hex::encode(mail),
+            "c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e"
+        );
+    }
+}
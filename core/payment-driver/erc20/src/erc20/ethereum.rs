@@ -1,28 +1,36 @@
 #![allow(clippy::too_many_arguments)]
 
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
-use bigdecimal::BigDecimal;
 use chrono::{DateTime, Utc};
 use ethabi::Token;
 use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
 use uuid::Uuid;
 use web3::{
     contract::{tokens::Tokenize, Contract, Options},
     error::Error,
     transports::Http,
-    types::{Bytes, Transaction, TransactionId, TransactionReceipt, H160, H256, U256, U64},
+    types::{
+        Bytes, CallRequest, Transaction, TransactionId, TransactionReceipt, H160, H256, U256, U64,
+    },
     Web3,
 };
 
 use ya_client_model::NodeId;
 use ya_payment_driver::db::models::{Network, TransactionEntity, TransactionStatus, TxType};
-use ya_payment_driver::utils::big_dec_to_u256;
-use ya_payment_driver::{bus, model::GenericError};
+use ya_payment_driver::{
+    bus::{self, Eip712Domain},
+    model::{GasPriority, GenericError},
+};
+use ya_service_api::reload;
 
+use crate::erc20::decision::{self, Inputs};
 use crate::erc20::eth_utils::keccak256_hash;
+use crate::erc20::forwarder::ForwardRequest;
 use crate::erc20::transaction::YagnaRawTransaction;
 use crate::erc20::{config, eth_utils};
 
@@ -55,14 +63,50 @@ impl From<ClientError> for GenericError {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PolygonPriority {
+    #[serde(rename = "slow")]
     PolygonPrioritySlow,
+    #[serde(rename = "fast")]
     PolygonPriorityFast,
+    #[serde(rename = "express")]
     PolygonPriorityExpress,
 }
 
+impl PolygonPriority {
+    /// Static gas price table in Gwei, starting with the 0.0 sentinel.
+    pub fn prices(&self) -> &'static [f64] {
+        match self {
+            PolygonPriority::PolygonPrioritySlow => &POLYGON_PREFERRED_GAS_PRICES_SLOW[..],
+            PolygonPriority::PolygonPriorityFast => &POLYGON_PREFERRED_GAS_PRICES_FAST[..],
+            PolygonPriority::PolygonPriorityExpress => &POLYGON_PREFERRED_GAS_PRICES_EXPRESS[..],
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PolygonPriority::PolygonPrioritySlow => "slow",
+            PolygonPriority::PolygonPriorityFast => "fast",
+            PolygonPriority::PolygonPriorityExpress => "express",
+        }
+    }
+}
+
+impl From<GasPriority> for PolygonPriority {
+    fn from(priority: GasPriority) -> Self {
+        match priority {
+            GasPriority::Slow => PolygonPriority::PolygonPrioritySlow,
+            GasPriority::Fast => PolygonPriority::PolygonPriorityFast,
+            GasPriority::Express => PolygonPriority::PolygonPriorityExpress,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum PolygonGasPriceMethod {
+    #[serde(rename = "static")]
     PolygonGasPriceStatic,
+    #[serde(rename = "dynamic")]
     PolygonGasPriceDynamic,
 }
 
@@ -74,11 +118,19 @@ lazy_static! {
     pub static ref GLM_FAUCET_GAS: U256 = U256::from(90_000);
     pub static ref GLM_TRANSFER_GAS: U256 = U256::from(55_000);
     pub static ref GLM_POLYGON_GAS_LIMIT: U256 = U256::from(100_000);
+    pub static ref CANCEL_TX_GAS: U256 = U256::from(21_000);
     static ref WEB3_CLIENT_MAP: Arc<RwLock<HashMap<String, Web3<Http>>>> = Default::default();
+    /// RPC endpoints serving another chain than configured, with the error to report.
+    static ref WRONG_CHAIN_RPC_MAP: Arc<RwLock<HashMap<String, String>>> = Default::default();
 }
+/// RPC calls made since the start, successful or not.
+static RPC_CALLS: AtomicU64 = AtomicU64::new(0);
+
 const CREATE_FAUCET_FUNCTION: &str = "create";
 const BALANCE_ERC20_FUNCTION: &str = "balanceOf";
 const TRANSFER_ERC20_FUNCTION: &str = "transfer";
+const DECIMALS_ERC20_FUNCTION: &str = "decimals";
+const SYMBOL_ERC20_FUNCTION: &str = "symbol";
 const GET_DOMAIN_SEPARATOR_FUNCTION: &str = "getDomainSeperator";
 const GET_NONCE_FUNCTION: &str = "getNonce";
 
@@ -90,6 +142,12 @@ pub fn get_polygon_starting_price() -> f64 {
     }
 }
 
+/// Starting and maximum gas price of the static Polygon price table for `priority`.
+pub fn get_polygon_priority_prices(priority: &PolygonPriority) -> (f64, f64) {
+    let gas_prices = priority.prices();
+    (gas_prices[1], gas_prices[gas_prices.len() - 1])
+}
+
 pub fn get_polygon_maximum_price() -> f64 {
     match get_polygon_gas_price_method() {
         PolygonGasPriceMethod::PolygonGasPriceStatic => match get_polygon_priority() {
@@ -108,15 +166,13 @@ pub fn get_polygon_maximum_price() -> f64 {
 }
 
 pub fn get_polygon_max_gas_price_dynamic() -> f64 {
-    std::env::var("POLYGON_MAX_GAS_PRICE_DYNAMIC")
-        .ok()
+    reload::var("POLYGON_MAX_GAS_PRICE_DYNAMIC")
         .and_then(|v| v.parse().ok())
         .unwrap_or(1000.0f64)
 }
 
 pub fn get_polygon_gas_price_method() -> PolygonGasPriceMethod {
-    match std::env::var("POLYGON_GAS_PRICE_METHOD")
-        .ok()
+    match reload::var("POLYGON_GAS_PRICE_METHOD")
         .map(|v| v.to_lowercase())
         .as_ref()
         .map(AsRef::as_ref) // Option<&str>
@@ -128,8 +184,8 @@ pub fn get_polygon_gas_price_method() -> PolygonGasPriceMethod {
 }
 
 pub fn get_polygon_priority() -> PolygonPriority {
-    match std::env::var("POLYGON_PRIORITY")
-        .unwrap_or_else(|_| "default".to_string())
+    match reload::var("POLYGON_PRIORITY")
+        .unwrap_or_else(|| "default".to_string())
         .to_lowercase()
         .as_str()
     {
@@ -166,6 +222,26 @@ async fn get_glm_balance_with(
         .map_err(Into::into)
 }
 
+/// Symbol and decimals of the configured token contract.
+pub async fn get_token_info(network: Network) -> Result<(String, u32), GenericError> {
+    with_clients(network, |client| get_token_info_with(client, network)).await
+}
+
+async fn get_token_info_with(
+    client: Web3<Http>,
+    network: Network,
+) -> Result<(String, u32), ClientError> {
+    let env = get_env(network);
+    let glm_contract = prepare_erc20_contract(&client, &env)?;
+    let symbol: String = glm_contract
+        .query(SYMBOL_ERC20_FUNCTION, (), None, Options::default(), None)
+        .await?;
+    let decimals: U256 = glm_contract
+        .query(DECIMALS_ERC20_FUNCTION, (), None, Options::default(), None)
+        .await?;
+    Ok((symbol, decimals.as_u32()))
+}
+
 pub async fn get_balance(address: H160, network: Network) -> Result<U256, GenericError> {
     with_clients(network, |client| get_balance_with(address, client)).await
 }
@@ -178,6 +254,40 @@ async fn get_balance_with(address: H160, client: Web3<Http>) -> Result<U256, Cli
         .map_err(Into::into)
 }
 
+/// Code deployed at the address, empty for externally owned accounts.
+pub async fn get_code(address: H160, network: Network) -> Result<Bytes, GenericError> {
+    with_clients(network, |client| get_code_with(address, client)).await
+}
+
+async fn get_code_with(address: H160, client: Web3<Http>) -> Result<Bytes, ClientError> {
+    client.eth().code(address, None).await.map_err(Into::into)
+}
+
+/// Read-only call of the contract with raw ABI encoded `data`.
+pub async fn call_contract(
+    address: H160,
+    data: Vec<u8>,
+    network: Network,
+) -> Result<Bytes, GenericError> {
+    with_clients(network, |client| {
+        call_contract_with(address, data.clone(), client)
+    })
+    .await
+}
+
+async fn call_contract_with(
+    address: H160,
+    data: Vec<u8>,
+    client: Web3<Http>,
+) -> Result<Bytes, ClientError> {
+    let request = CallRequest {
+        to: Some(address),
+        data: Some(Bytes(data)),
+        ..Default::default()
+    };
+    client.eth().call(request, None).await.map_err(Into::into)
+}
+
 pub async fn get_next_nonce_pending(address: H160, network: Network) -> Result<U256, GenericError> {
     with_clients(network, |client| {
         get_next_nonce_pending_with(client, address)
@@ -205,6 +315,7 @@ where
     let mut last_err: Option<ClientError> = None;
 
     for client in clients {
+        RPC_CALLS.fetch_add(1, Ordering::Relaxed);
         match f(client).await {
             Ok(result) => return Ok(result),
             Err(ClientError::Web3(e)) => match e {
@@ -223,6 +334,10 @@ where
     }
 }
 
+pub fn rpc_calls() -> u64 {
+    RPC_CALLS.load(Ordering::Relaxed)
+}
+
 pub async fn block_number(network: Network) -> Result<U64, GenericError> {
     with_clients(network, block_number_with).await
 }
@@ -305,6 +420,18 @@ pub async fn sign_hash_of_data(address: H160, hash: Vec<u8>) -> Result<Vec<u8>,
     Ok(signature)
 }
 
+/// Signs EIP-712 typed data, the digest is computed by the identity service.
+pub async fn sign_typed_data(
+    address: H160,
+    domain: Eip712Domain,
+    struct_hash: H256,
+) -> Result<Vec<u8>, GenericError> {
+    let node_id = NodeId::from(address.as_ref());
+
+    let signature = bus::sign_typed_data(node_id, domain, struct_hash.as_bytes().to_vec()).await?;
+    Ok(signature)
+}
+
 pub async fn prepare_raw_transaction(
     _address: H160,
     recipient: H160,
@@ -348,15 +475,10 @@ async fn prepare_raw_transaction_with(
     let gas_price = match gas_price_override {
         Some(gas_price_new) => gas_price_new,
         None => {
-            let small_gas_bump = U256::from(1000);
-            let mut gas_price_from_network =
-                client.eth().gas_price().await.map_err(GenericError::new)?;
-
-            //add small amount of gas to be first in queue
-            if gas_price_from_network / 1000 > small_gas_bump {
-                gas_price_from_network += small_gas_bump;
-            }
-            gas_price_from_network
+            let inputs = Inputs::OracleGasPrice {
+                oracle: client.eth().gas_price().await.map_err(GenericError::new)?,
+            };
+            decision::decide(network, inputs).value()
         }
     };
 
@@ -378,6 +500,34 @@ async fn prepare_raw_transaction_with(
     Ok(tx)
 }
 
+/// Call of `data` on contract `to`, priced with the current gas price of the network.
+pub async fn prepare_raw_call(
+    to: H160,
+    data: Vec<u8>,
+    gas: U256,
+    nonce: U256,
+    network: Network,
+) -> Result<YagnaRawTransaction, GenericError> {
+    let gas_price = get_gas_price(network).await?;
+    Ok(YagnaRawTransaction {
+        nonce,
+        to: Some(to),
+        value: U256::zero(),
+        gas_price,
+        gas,
+        data,
+    })
+}
+
+/// Gas price reported by the network.
+pub async fn get_gas_price(network: Network) -> Result<U256, GenericError> {
+    with_clients(network, gas_price_with).await
+}
+
+async fn gas_price_with(client: Web3<Http>) -> Result<U256, ClientError> {
+    client.eth().gas_price().await.map_err(Into::into)
+}
+
 pub async fn send_tx(signed_tx: Vec<u8>, network: Network) -> Result<H256, GenericError> {
     with_clients(network, |client| send_tx_with(client, signed_tx.clone())).await
 }
@@ -390,6 +540,29 @@ async fn send_tx_with(client: Web3<Http>, signed_tx: Vec<u8>) -> Result<H256, Cl
         .map_err(Into::into)
 }
 
+/// Executes `raw_tx` from `from` against the latest block without broadcasting it.
+pub async fn simulate_tx(
+    from: H160,
+    raw_tx: &YagnaRawTransaction,
+    network: Network,
+) -> Result<(), GenericError> {
+    let request = CallRequest {
+        from: Some(from),
+        to: raw_tx.to,
+        gas: Some(raw_tx.gas),
+        gas_price: Some(raw_tx.gas_price),
+        value: Some(raw_tx.value),
+        data: Some(Bytes(raw_tx.data.clone())),
+        ..Default::default()
+    };
+    with_clients(network, |client| simulate_tx_with(client, request.clone())).await
+}
+
+async fn simulate_tx_with(client: Web3<Http>, request: CallRequest) -> Result<(), ClientError> {
+    client.eth().call(request, None).await?;
+    Ok(())
+}
+
 pub struct TransactionChainStatus {
     pub exists_on_chain: bool,
     pub pending: bool,
@@ -451,6 +624,28 @@ pub async fn get_tx_on_chain_status(
     Ok(res)
 }
 
+/// Confirmations of a mined transaction and confirmations required on the network.
+pub async fn get_tx_confirmations(
+    tx_hash: H256,
+    network: Network,
+) -> Result<Option<(u64, u64)>, GenericError> {
+    let env = get_env(network);
+    let tx_bn = match get_tx_receipt(tx_hash, network)
+        .await?
+        .and_then(|tx| tx.block_number)
+    {
+        Some(tx_bn) => tx_bn.as_u64(),
+        None => return Ok(None),
+    };
+    let current_block = block_number(network).await?.as_u64();
+    // tx.block_number is the first confirmation
+    let confirmations = (current_block + 1).saturating_sub(tx_bn);
+    Ok(Some((
+        confirmations.min(env.required_confirmations),
+        env.required_confirmations,
+    )))
+}
+
 //unused but tested that it is working for transfers
 pub async fn decode_encoded_transaction_data(
     network: Network,
@@ -550,8 +745,7 @@ fn get_rpc_addr_from_env(network: Network) -> Vec<String> {
 }
 
 fn collect_rpc_addr_from(env: &str, default: &str) -> Vec<String> {
-    std::env::var(env)
-        .ok()
+    reload::var(env)
         .unwrap_or_else(|| default.to_string())
         .split(',')
         .map(|path| path.to_string())
@@ -561,6 +755,7 @@ fn collect_rpc_addr_from(env: &str, default: &str) -> Vec<String> {
 async fn get_clients(network: Network) -> Result<Vec<Web3<Http>>, GenericError> {
     let geth_addrs = get_rpc_addr_from_env(network);
     let mut clients: Vec<Web3<Http>> = Default::default();
+    let mut mismatch: Option<String> = None;
 
     for geth_addr in geth_addrs {
         {
@@ -570,6 +765,10 @@ async fn get_clients(network: Network) -> Result<Vec<Web3<Http>>, GenericError>
                 continue;
             }
         }
+        if let Some(msg) = WRONG_CHAIN_RPC_MAP.read().await.get(&geth_addr) {
+            mismatch.replace(msg.clone());
+            continue;
+        }
 
         let transport = match web3::transports::Http::new(&geth_addr) {
             Ok(t) => t,
@@ -578,13 +777,47 @@ async fn get_clients(network: Network) -> Result<Vec<Web3<Http>>, GenericError>
 
         let client = Web3::new(transport);
 
+        // Transactions are signed for the chain of `network`, the endpoint has to serve it.
+        RPC_CALLS.fetch_add(1, Ordering::Relaxed);
+        match client.eth().chain_id().await {
+            Ok(chain_id) if chain_id == U256::from(network as u64) => (),
+            Ok(chain_id) => {
+                let msg = format!(
+                    "RPC endpoint {} serves chain id {}, but network {} has chain id {}. Check {}_GETH_ADDR.",
+                    geth_addr,
+                    chain_id,
+                    network,
+                    network as u64,
+                    network.to_string().to_uppercase()
+                );
+                log::error!("{}", msg);
+                WRONG_CHAIN_RPC_MAP
+                    .write()
+                    .await
+                    .insert(geth_addr, msg.clone());
+                mismatch.replace(msg);
+                continue;
+            }
+            Err(e) => {
+                log::warn!(
+                    "Failed to verify chain id of RPC endpoint {}, skipping it. Error: {}",
+                    geth_addr,
+                    e
+                );
+                continue;
+            }
+        }
+
         let mut client_map = WEB3_CLIENT_MAP.write().await;
         client_map.insert(geth_addr, client.clone());
 
         clients.push(client);
     }
 
-    Ok(clients)
+    match (clients.is_empty(), mismatch) {
+        (true, Some(msg)) => Err(GenericError::new(msg)),
+        _ => Ok(clients),
+    }
 }
 
 fn get_env(network: Network) -> config::EnvConfiguration {
@@ -656,6 +889,17 @@ fn prepare_meta_transaction_contract(
     )
 }
 
+fn prepare_forwarder_contract(
+    ethereum_client: &Web3<Http>,
+    forwarder_address: H160,
+) -> Result<Contract<Http>, GenericError> {
+    prepare_contract(
+        ethereum_client,
+        forwarder_address,
+        include_bytes!("../contracts/forwarder.json"),
+    )
+}
+
 pub fn create_dao_entity(
     nonce: U256,
     sender: H160,
@@ -666,7 +910,7 @@ pub fn create_dao_entity(
     network: Network,
     timestamp: DateTime<Utc>,
     tx_type: TxType,
-    amount: Option<BigDecimal>,
+    amount: Option<U256>,
 ) -> TransactionEntity {
     let current_naive_time = timestamp.naive_utc();
     TransactionEntity {
@@ -680,7 +924,7 @@ pub fn create_dao_entity(
         max_gas_price,
         final_gas_used: None,
         amount_base: Some("0".to_string()),
-        amount_erc20: amount.as_ref().map(|a| big_dec_to_u256(a).to_string()),
+        amount_erc20: amount.map(|a| a.to_string()),
         gas_limit: Some(gas_limit),
         starting_gas_price: Some(starting_gas_price),
         current_gas_price: None,
@@ -732,6 +976,61 @@ pub async fn get_nonce_from_contract(
     .await
 }
 
+pub fn get_glm_contract_address(network: Network) -> H160 {
+    get_env(network).glm_contract_address
+}
+
+pub fn get_forwarder_address(network: Network) -> Option<H160> {
+    get_env(network).glm_forwarder_address
+}
+
+pub async fn get_forwarder_nonce(address: H160, network: Network) -> Result<U256, GenericError> {
+    let forwarder_address = get_forwarder_address(network).ok_or_else(|| {
+        GenericError::new(format!("No trusted forwarder configured for {}", network))
+    })?;
+
+    with_clients(network, |client| async move {
+        let forwarder_contract = prepare_forwarder_contract(&client, forwarder_address)?;
+        let nonce: U256 = forwarder_contract
+            .query(
+                GET_NONCE_FUNCTION,
+                (address,),
+                None,
+                Options::default(),
+                None,
+            )
+            .await
+            .map_err(GenericError::new)?;
+
+        Ok(nonce)
+    })
+    .await
+}
+
+/// Creates a request for the trusted forwarder to call GLM `transfer` on behalf of `sender`.
+pub async fn prepare_forward_request(
+    sender: H160,
+    recipient: H160,
+    amount: U256,
+    nonce: U256,
+    network: Network,
+) -> Result<ForwardRequest, GenericError> {
+    let data = encode_transfer_abi(recipient, amount, network).await?;
+    let gas = match network {
+        Network::Polygon | Network::Mumbai => *GLM_POLYGON_GAS_LIMIT,
+        _ => *GLM_TRANSFER_GAS,
+    };
+
+    Ok(ForwardRequest {
+        from: sender,
+        to: get_env(network).glm_contract_address,
+        value: U256::zero(),
+        gas,
+        nonce,
+        data: Bytes::from(data),
+    })
+}
+
 pub async fn encode_transfer_abi(
     recipient: H160,
     amount: U256,
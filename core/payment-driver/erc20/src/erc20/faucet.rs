@@ -6,12 +6,15 @@
 use bigdecimal::{BigDecimal, FromPrimitive};
 use chrono::{Duration, Utc};
 use lazy_static::lazy_static;
+use std::str::FromStr;
 use std::{env, time};
 use tokio::time::sleep;
-use web3::types::{H160, U256};
+use web3::types::{H160, H256, U256};
 
 // Workspace uses
-use ya_payment_driver::{db::models::Network, model::GenericError, utils};
+use ya_payment_driver::{
+    db::models::Network, model::GenericError, progress::ProgressReporter, utils,
+};
 use ya_utils_networking::resolver;
 
 // Local uses
@@ -22,9 +25,12 @@ const DEFAULT_FAUCET_SRV_PREFIX: &str = "_eth-faucet._tcp";
 const DEFAULT_ETH_FAUCET_HOST: &str = "faucet.testnet.golem.network";
 const FAUCET_ADDR_ENVAR: &str = "ETH_FAUCET_ADDRESS";
 const MAX_FAUCET_REQUESTS: u32 = 6;
+/// Steps of `request_glm` reported to the CLI.
+pub const FUND_STEPS: u32 = 4;
+const BLOCK_TIME: time::Duration = time::Duration::from_secs(15);
 
 lazy_static! {
-    static ref MIN_GLM_BALANCE: U256 = utils::big_dec_to_u256(&BigDecimal::from(50));
+    static ref MIN_GLM_BALANCE: BigDecimal = BigDecimal::from(50);
     static ref MIN_ETH_BALANCE: U256 =
         utils::big_dec_to_u256(&BigDecimal::from_f64(0.005).unwrap());
     static ref MAX_WAIT: Duration = Duration::minutes(1);
@@ -34,6 +40,7 @@ pub async fn request_glm(
     dao: &Erc20Dao,
     address: H160,
     network: Network,
+    progress: &ProgressReporter,
 ) -> Result<(), GenericError> {
     let str_addr = format!("0x{:x}", address);
     let balance = ethereum::get_balance(address, network).await?;
@@ -44,6 +51,7 @@ pub async fn request_glm(
             "Requesting tETH from erc20 faucet... address = {}",
             &str_addr
         );
+        progress.stage(1, "Requesting tETH from faucet");
 
         for i in 0..MAX_FAUCET_REQUESTS {
             match faucet_donate(address, network).await {
@@ -64,28 +72,39 @@ pub async fn request_glm(
                             MAX_FAUCET_REQUESTS,
                             e
                         );
+                        progress.stage(
+                            1,
+                            format!(
+                                "Requesting tETH from faucet, retry {}/{}",
+                                i + 1,
+                                MAX_FAUCET_REQUESTS - 1
+                            ),
+                        );
                         sleep(time::Duration::from_secs(10)).await;
                     }
                 }
             }
         }
-        wait_for_eth(address, network).await?;
+        wait_for_eth(address, network, progress).await?;
     }
-    let glm_balance = ethereum::get_glm_balance(address, network).await?;
+    let glm_balance = wallet::account_balance(address, network).await?;
 
     if glm_balance >= *MIN_GLM_BALANCE {
         log::info!("Enough tGLM balance.");
+        progress.finish("Enough tGLM balance");
         return Ok(());
     }
     let pending = dao.get_pending_faucet_txs(&str_addr, network).await;
     if !pending.is_empty() {
         log::info!("Already pending a mint transactin.");
+        progress.finish("Already pending a mint transaction");
         return Ok(());
     }
     log::info!(
         "Requesting tGLM from erc20 faucet... address = {}",
         &str_addr
     );
+    progress.stage(3, "Sending tGLM mint transaction");
 
     let nonce = wallet::get_next_nonce(dao, address, network).await?;
     let db_tx = ethereum::sign_faucet_tx(address, network, nonce).await?;
@@ -97,12 +116,17 @@ pub async fn request_glm(
     // - blocks are mined every 15 seconds
     sleep(time::Duration::from_secs(10)).await;
 
-    wait_for_glm(address, network).await?;
+    wait_for_glm(dao, address, network, progress).await?;
+    progress.finish("Received tGLM from faucet");
 
     Ok(())
 }
 
-async fn wait_for_eth(address: H160, network: Network) -> Result<(), GenericError> {
+async fn wait_for_eth(
+    address: H160,
+    network: Network,
+    progress: &ProgressReporter,
+) -> Result<(), GenericError> {
     log::info!("Waiting for tETH from faucet...");
     let wait_until = Utc::now() + *MAX_WAIT;
     while Utc::now() < wait_until {
@@ -110,6 +134,13 @@ async fn wait_for_eth(address: H160, network: Network) -> Result<(), GenericErro
             log::info!("Received tETH from faucet.");
             return Ok(());
         }
+        progress.stage(
+            2,
+            format!(
+                "Waiting for tETH from faucet, timeout in {}s",
+                (wait_until - Utc::now()).num_seconds().max(0)
+            ),
+        );
         sleep(time::Duration::from_secs(3)).await;
     }
     let msg = "Waiting for tETH timed out.";
@@ -117,14 +148,29 @@ async fn wait_for_eth(address: H160, network: Network) -> Result<(), GenericErro
     Err(GenericError::new(msg))
 }
 
-async fn wait_for_glm(address: H160, network: Network) -> Result<(), GenericError> {
+async fn wait_for_glm(
+    dao: &Erc20Dao,
+    address: H160,
+    network: Network,
+    progress: &ProgressReporter,
+) -> Result<(), GenericError> {
     log::info!("Waiting for tGLM from faucet...");
     let wait_until = Utc::now() + *MAX_WAIT;
     while Utc::now() < wait_until {
-        if ethereum::get_glm_balance(address, network).await? >= *MIN_GLM_BALANCE {
+        if wallet::account_balance(address, network).await? >= *MIN_GLM_BALANCE {
             log::info!("Received tGLM from faucet.");
             return Ok(());
         }
+        match faucet_tx_confirmations(dao, address, network).await {
+            Some((confirmations, required)) => progress.confirmations(
+                4,
+                "Waiting for tGLM mint transaction confirmations",
+                confirmations,
+                required,
+                BLOCK_TIME,
+            ),
+            None => progress.stage(3, "tGLM mint transaction sent, waiting to be mined"),
+        }
         sleep(time::Duration::from_secs(3)).await;
     }
     let msg = "Waiting for tGLM timed out.";
@@ -132,6 +178,30 @@ async fn wait_for_glm(address: H160, network: Network) -> Result<(), GenericErro
     Err(GenericError::new(msg))
 }
 
+/// Confirmations of the latest sent faucet mint transaction.
+async fn faucet_tx_confirmations(
+    dao: &Erc20Dao,
+    address: H160,
+    network: Network,
+) -> Option<(u64, u64)> {
+    let str_addr = format!("0x{:x}", address);
+    let tx = dao
+        .get_pending_faucet_txs(&str_addr, network)
+        .await
+        .into_iter()
+        .next()?;
+    let tx_hash = tx
+        .tmp_onchain_txs?
+        .split(';')
+        .filter(|tx_hash| tx_hash.len() > 2)
+        .last()
+        .and_then(|tx_hash| H256::from_str(&tx_hash[2..]).ok())?;
+    ethereum::get_tx_confirmations(tx_hash, network)
+        .await
+        .ok()
+        .flatten()
+}
+
 async fn faucet_donate(address: H160, _network: Network) -> Result<(), GenericError> {
     // TODO: Reduce timeout to 20-30 seconds when transfer is used.
     let client = awc::Client::builder()
@@ -0,0 +1,214 @@
+/*
+    Meta-transactions relayed through a trusted forwarder (EIP-2771).
+
+    The payer signs a `ForwardRequest` (EIP-712) and a relay submits it to the forwarder
+    contract, paying for the gas. Compatible with OpenZeppelin's `MinimalForwarder`.
+*/
+
+use std::env;
+
+use awc::http;
+use ethabi::Token;
+use serde::{Deserialize, Serialize};
+use web3::types::{Bytes, H160, H256, U256};
+
+use ya_payment_driver::{bus::Eip712Domain, db::models::Network, model::GenericError, utils};
+use ya_utils_networking::resolver;
+
+use crate::erc20::{eip712, eth_utils::keccak256_hash, ethereum};
+
+const FORWARD_REQUEST_TYPE: &str =
+    "ForwardRequest(address from,address to,uint256 value,uint256 gas,uint256 nonce,bytes data)";
+const FORWARDER_EXECUTE: &str = "execute((address,address,uint256,uint256,uint256,bytes),bytes)";
+const ETH_V_OFFSET: u8 = 27;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MetaTxMode {
+    /// Relay only payments from accounts without enough native token for gas.
+    Fallback,
+    /// Relay every payment.
+    Always,
+}
+
+pub fn get_meta_tx_mode() -> MetaTxMode {
+    match env::var("ERC20_META_TX_MODE")
+        .unwrap_or_else(|_| "fallback".to_string())
+        .to_lowercase()
+        .as_str()
+    {
+        "always" => MetaTxMode::Always,
+        _ => MetaTxMode::Fallback,
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ForwardRequest {
+    pub from: H160,
+    pub to: H160,
+    pub value: U256,
+    pub gas: U256,
+    pub nonce: U256,
+    pub data: Bytes,
+}
+
+impl ForwardRequest {
+    pub fn hash_struct(&self) -> H256 {
+        eip712::hash_struct(
+            FORWARD_REQUEST_TYPE,
+            vec![
+                Token::Address(self.from),
+                Token::Address(self.to),
+                Token::Uint(self.value),
+                Token::Uint(self.gas),
+                Token::Uint(self.nonce),
+                eip712::hash_bytes(&self.data.0),
+            ],
+        )
+    }
+}
+
+#[derive(Serialize, Debug)]
+struct RelayRequest<'a> {
+    request: &'a ForwardRequest,
+    signature: String,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct RelayResponse {
+    tx_hash: H256,
+}
+
+fn domain(forwarder: H160, network: Network) -> Eip712Domain {
+    Eip712Domain {
+        name: Some(
+            env::var("ERC20_FORWARDER_DOMAIN_NAME")
+                .unwrap_or_else(|_| "MinimalForwarder".to_string()),
+        ),
+        version: Some(
+            env::var("ERC20_FORWARDER_DOMAIN_VERSION").unwrap_or_else(|_| "0.0.1".to_string()),
+        ),
+        chain_id: Some(network as u64),
+        verifying_contract: Some(forwarder.to_fixed_bytes()),
+        salt: None,
+    }
+}
+
+fn get_relay_urls(network: Network) -> Vec<String> {
+    let env = format!("{}_META_TX_RELAY_URL", network.to_string().to_uppercase());
+    env::var(env)
+        .ok()
+        .map(|urls| {
+            urls.split(',')
+                .map(|url| url.trim().to_string())
+                .filter(|url| !url.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Meta-transactions need both a forwarder contract and at least one relay.
+pub fn is_enabled(network: Network) -> bool {
+    ethereum::get_forwarder_address(network).is_some() && !get_relay_urls(network).is_empty()
+}
+
+/// Signs `request` as its sender and hands it over to the first relay that accepts it.
+/// In payments dry-run mode it's only executed against the latest block, identified by the
+/// hash of its signature.
+pub async fn send_meta_transaction(
+    request: &ForwardRequest,
+    network: Network,
+) -> Result<H256, GenericError> {
+    let forwarder = ethereum::get_forwarder_address(network).ok_or_else(|| {
+        GenericError::new(format!("No trusted forwarder configured for {}", network))
+    })?;
+
+    // Signature comes as `v || r || s`, forwarder expects `r || s || v`.
+    let signature = ethereum::sign_typed_data(
+        request.from,
+        domain(forwarder, network),
+        request.hash_struct(),
+    )
+    .await?;
+    let mut packed = signature[1..].to_vec();
+    packed.push(signature[0] + ETH_V_OFFSET);
+
+    if utils::dry_run() {
+        simulate(forwarder, request, &packed, network).await?;
+        return Ok(H256::from_slice(&keccak256_hash(&packed)));
+    }
+
+    let relay_request = RelayRequest {
+        request,
+        signature: format!("0x{}", hex::encode(&packed)),
+    };
+
+    let mut last_err = GenericError::new(format!("No meta-transaction relay for {}", network));
+    for url in get_relay_urls(network) {
+        match send_to_relay(&url, &relay_request).await {
+            Ok(tx_hash) => return Ok(tx_hash),
+            Err(e) => {
+                log::warn!("Meta-transaction relay {} failed: {}", url, e);
+                last_err = e;
+            }
+        }
+    }
+    Err(last_err)
+}
+
+async fn simulate(
+    forwarder: H160,
+    request: &ForwardRequest,
+    signature: &[u8],
+    network: Network,
+) -> Result<(), GenericError> {
+    let mut data = keccak256_hash(FORWARDER_EXECUTE.as_bytes())[..4].to_vec();
+    data.extend(ethabi::encode(&[
+        Token::Tuple(vec![
+            Token::Address(request.from),
+            Token::Address(request.to),
+            Token::Uint(request.value),
+            Token::Uint(request.gas),
+            Token::Uint(request.nonce),
+            Token::Bytes(request.data.0.clone()),
+        ]),
+        Token::Bytes(signature.to_vec()),
+    ]));
+    // Forwarder returns whether the relayed call succeeded as the first word.
+    let result = ethereum::call_contract(forwarder, data, network).await?;
+    match result.0.get(31) {
+        Some(1) => Ok(()),
+        _ => Err(GenericError::new(
+            "Relayed call of the meta-transaction failed",
+        )),
+    }
+}
+
+async fn send_to_relay(url: &str, request: &RelayRequest<'_>) -> Result<H256, GenericError> {
+    let url = resolver::try_resolve_dns_record(url).await;
+    log::debug!("Sending meta-transaction to {}: {:?}", url, request);
+
+    let mut resp = awc::Client::new()
+        .post(url)
+        .send_json(request)
+        .await
+        .map_err(|e| GenericError::new(format!("While sending a request to the relay: {}", e)))?;
+
+    match resp.status() {
+        http::StatusCode::OK => {
+            let body: RelayResponse = resp
+                .json()
+                .await
+                .map_err(|e| GenericError::new(format!("While parsing relay response: {}", e)))?;
+            Ok(body.tx_hash)
+        }
+        status => {
+            let body = resp.body().await.map_err(GenericError::new)?;
+            Err(GenericError::new(format!(
+                "Invalid relay response, status code: {}, body {}",
+                status,
+                String::from_utf8_lossy(body.as_ref())
+            )))
+        }
+    }
+}
@@ -7,11 +7,7 @@ use serde::{Deserialize, Serialize};
 use ya_payment_driver::model::GenericError;
 use ya_utils_networking::resolver;
 
-use crate::erc20::{
-    eth_utils::keccak256_hash,
-    ethereum,
-    utils::{big_dec_to_u256, str_to_addr},
-};
+use crate::erc20::{eth_utils::keccak256_hash, ethereum, token, utils::str_to_addr};
 
 const DEFAULT_GASLESS_HOST: &str = "http://gasless.golem.network";
 const GASLESS_ADDR_ENVAR: &str = "GASLESS_SERVER_ADDRESS";
@@ -50,7 +46,7 @@ async fn create_gasless_request(
 ) -> Result<GaslessRequest, GenericError> {
     let sender = str_to_addr(&details.sender)?;
     let recipient = str_to_addr(&details.recipient)?;
-    let amount = big_dec_to_u256(&details.amount)?;
+    let amount = token::to_u256(&details.amount, network).await?;
 
     let nonce = ethereum::get_nonce_from_contract(sender, network).await?;
     let transfer_abi = ethereum::encode_transfer_abi(recipient, amount, network).await?;
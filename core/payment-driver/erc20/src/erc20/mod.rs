@@ -2,12 +2,19 @@
     Private mod to encapsulate all erc20 logic, revealed from the `wallet`.
 */
 
+pub mod bridge;
+pub mod decision;
 pub mod ethereum;
 pub mod faucet;
+pub mod recipient;
 pub mod utils;
 pub mod wallet;
 
 mod config;
+mod eip712;
 pub mod eth_utils;
+mod forwarder;
 mod gasless_transfer;
+mod price;
+pub mod token;
 pub mod transaction;
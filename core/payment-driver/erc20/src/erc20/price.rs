@@ -0,0 +1,91 @@
+/*
+    Fiat prices of the native currencies paying for gas, queried from a price API and cached.
+*/
+
+// External crates
+use awc::http;
+use bigdecimal::BigDecimal;
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+use std::env;
+use std::str::FromStr;
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+// Workspace uses
+use ya_payment_driver::{db::models::Network, model::GenericError};
+
+const DEFAULT_PRICE_API: &str = "https://api.coingecko.com/api/v3/simple/price";
+const PRICE_TTL: Duration = Duration::from_secs(300);
+
+lazy_static! {
+    static ref PRICES: RwLock<HashMap<(String, String), (Instant, BigDecimal)>> =
+        Default::default();
+}
+
+/// Currency fiat prices are given in, `ERC20_FIAT_CURRENCY` (default usd).
+pub fn fiat_currency() -> String {
+    env::var("ERC20_FIAT_CURRENCY")
+        .map(|currency| currency.to_lowercase())
+        .unwrap_or_else(|_| "usd".to_string())
+}
+
+/// Id of the native currency of `network` in the price API. Test networks are priced
+/// like their mainnets, so fees can be compared before going to production.
+fn coin_id(network: Network) -> &'static str {
+    match network {
+        Network::Mainnet | Network::Rinkeby | Network::Goerli => "ethereum",
+        Network::Polygon | Network::Mumbai => "matic-network",
+    }
+}
+
+/// Price of the native currency of `network`, `{NETWORK}_NATIVE_FIAT_PRICE` when set.
+pub async fn native_price(network: Network) -> Result<BigDecimal, GenericError> {
+    let env = format!("{}_NATIVE_FIAT_PRICE", network.to_string().to_uppercase());
+    if let Ok(price) = env::var(&env) {
+        return BigDecimal::from_str(&price)
+            .map_err(|e| GenericError::new(format!("Invalid {}: {}", env, e)));
+    }
+
+    let key = (coin_id(network).to_string(), fiat_currency());
+    let cached = PRICES.read().unwrap().get(&key).cloned();
+    match cached {
+        Some((fetched, price)) if fetched.elapsed() < PRICE_TTL => Ok(price),
+        _ => {
+            let price = fetch(&key.0, &key.1).await?;
+            PRICES
+                .write()
+                .unwrap()
+                .insert(key, (Instant::now(), price.clone()));
+            Ok(price)
+        }
+    }
+}
+
+async fn fetch(coin: &str, currency: &str) -> Result<BigDecimal, GenericError> {
+    let url = format!(
+        "{}?ids={}&vs_currencies={}",
+        env::var("ERC20_FIAT_PRICE_API").unwrap_or_else(|_| DEFAULT_PRICE_API.to_string()),
+        coin,
+        currency
+    );
+    let mut resp = awc::Client::new().get(url).send().await.map_err(|e| {
+        GenericError::new(format!("While sending a request to the price API: {}", e))
+    })?;
+    if resp.status() != http::StatusCode::OK {
+        return Err(GenericError::new(format!(
+            "Price API responded with {}",
+            resp.status()
+        )));
+    }
+
+    // {"ethereum":{"usd":1234.56}}
+    let body: HashMap<String, HashMap<String, f64>> = resp
+        .json()
+        .await
+        .map_err(|e| GenericError::new(format!("While parsing price API response: {}", e)))?;
+    body.get(coin)
+        .and_then(|prices| prices.get(currency))
+        .and_then(|price| BigDecimal::from_str(&price.to_string()).ok())
+        .ok_or_else(|| GenericError::new(format!("No {} price of {}", currency, coin)))
+}
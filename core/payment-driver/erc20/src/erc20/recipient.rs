@@ -0,0 +1,179 @@
+/*
+    Checks of payment recipients.
+
+    Tokens sent to a contract, which can't transfer them, are lost. Contracts are
+    paid only when ERC20_CONTRACT_RECIPIENTS allows it. Contract wallets, which
+    validate signatures (ERC-1271) or have owners (Gnosis Safe), are told apart
+    from other contracts, as they can move tokens.
+*/
+
+use ethabi::Token;
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Mutex;
+use web3::types::H160;
+
+use ya_payment_driver::db::models::Network;
+use ya_payment_driver::model::GenericError;
+
+use crate::erc20::ethereum;
+
+const CONTRACT_RECIPIENTS_ENV_VAR: &str = "ERC20_CONTRACT_RECIPIENTS";
+/// `isValidSignature(bytes32,bytes)`, also its magic return value.
+const ERC1271_IS_VALID_SIGNATURE: [u8; 4] = [0x16, 0x26, 0xba, 0x7e];
+/// Gnosis Safe `getThreshold()`.
+const SAFE_GET_THRESHOLD: [u8; 4] = [0xe7, 0x52, 0x35, 0xb8];
+
+lazy_static! {
+    /// Wallets don't change their kind. Accounts may get code deployed later and
+    /// other contracts may be misclassified due to a failed call, so they're re-checked.
+    static ref CONTRACT_WALLETS: Mutex<HashMap<(Network, H160), RecipientKind>> = Default::default();
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RecipientKind {
+    Account,
+    ContractWallet,
+    Contract,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ContractPolicy {
+    /// Pay externally owned accounts only.
+    Deny,
+    /// Pay accounts and contract wallets.
+    Wallets,
+    /// Pay any address.
+    Allow,
+}
+
+impl FromStr for ContractPolicy {
+    type Err = GenericError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "deny" => Ok(ContractPolicy::Deny),
+            "wallets" => Ok(ContractPolicy::Wallets),
+            "allow" => Ok(ContractPolicy::Allow),
+            _ => Err(GenericError::new(format!(
+                "Invalid {}: {}, expected deny, wallets or allow",
+                CONTRACT_RECIPIENTS_ENV_VAR, s
+            ))),
+        }
+    }
+}
+
+impl ContractPolicy {
+    pub fn from_env() -> Self {
+        std::env::var(CONTRACT_RECIPIENTS_ENV_VAR)
+            .ok()
+            .and_then(|v| {
+                v.parse()
+                    .map_err(|e| log::warn!("{}, contract wallets are allowed.", e))
+                    .ok()
+            })
+            .unwrap_or(ContractPolicy::Wallets)
+    }
+
+    pub fn allows(&self, kind: RecipientKind) -> bool {
+        match (self, kind) {
+            (_, RecipientKind::Account) => true,
+            (ContractPolicy::Deny, _) => false,
+            (ContractPolicy::Wallets, RecipientKind::ContractWallet) => true,
+            (ContractPolicy::Wallets, RecipientKind::Contract) => false,
+            (ContractPolicy::Allow, _) => true,
+        }
+    }
+}
+
+pub async fn classify(address: H160, network: Network) -> Result<RecipientKind, GenericError> {
+    if let Some(kind) = CONTRACT_WALLETS.lock().unwrap().get(&(network, address)) {
+        return Ok(*kind);
+    }
+    if ethereum::get_code(address, network).await?.0.is_empty() {
+        return Ok(RecipientKind::Account);
+    }
+
+    let kind = match is_contract_wallet(address, network).await {
+        true => RecipientKind::ContractWallet,
+        false => RecipientKind::Contract,
+    };
+    if kind == RecipientKind::ContractWallet {
+        CONTRACT_WALLETS
+            .lock()
+            .unwrap()
+            .insert((network, address), kind);
+    }
+    Ok(kind)
+}
+
+/// Refuses recipients, which the configured policy doesn't allow to pay.
+/// Recipients which can't be checked are paid, as before the check existed.
+pub async fn check(address: H160, network: Network) -> Result<(), GenericError> {
+    let kind = match classify(address, network).await {
+        Ok(kind) => kind,
+        Err(e) => {
+            log::warn!("Can't check recipient {:#x} on {}: {}", address, network, e);
+            return Ok(());
+        }
+    };
+    let policy = ContractPolicy::from_env();
+    if !policy.allows(kind) {
+        return Err(GenericError::new(format!(
+            "Recipient {:#x} on {} is {}, paying it is denied by {}={:?}",
+            address,
+            network,
+            match kind {
+                RecipientKind::ContractWallet => "a contract wallet",
+                _ => "a contract",
+            },
+            CONTRACT_RECIPIENTS_ENV_VAR,
+            policy
+        )));
+    }
+    if kind != RecipientKind::Account {
+        log::debug!("Paying {:?} {:#x} on {}.", kind, address, network);
+    }
+    Ok(())
+}
+
+async fn is_contract_wallet(address: H160, network: Network) -> bool {
+    // Wallet answers with a bytes4 for any signature, calls of missing functions revert.
+    let mut is_valid_signature = ERC1271_IS_VALID_SIGNATURE.to_vec();
+    is_valid_signature.extend(ethabi::encode(&[
+        Token::FixedBytes(vec![0u8; 32]),
+        Token::Bytes(Vec::new()),
+    ]));
+    if answers_word(address, is_valid_signature, network).await {
+        return true;
+    }
+    answers_word(address, SAFE_GET_THRESHOLD.to_vec(), network).await
+}
+
+async fn answers_word(address: H160, data: Vec<u8>, network: Network) -> bool {
+    match ethereum::call_contract(address, data, network).await {
+        Ok(result) => result.0.len() == 32,
+        Err(e) => {
+            log::trace!("Call of {:#x} failed: {}", address, e);
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_policy_allows() {
+        let policy: ContractPolicy = "Wallets".parse().unwrap();
+        assert!(policy.allows(RecipientKind::Account));
+        assert!(policy.allows(RecipientKind::ContractWallet));
+        assert!(!policy.allows(RecipientKind::Contract));
+
+        assert!(!ContractPolicy::Deny.allows(RecipientKind::ContractWallet));
+        assert!(ContractPolicy::Allow.allows(RecipientKind::Contract));
+        assert!("pay".parse::<ContractPolicy>().is_err());
+    }
+}
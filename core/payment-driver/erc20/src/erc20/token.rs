@@ -0,0 +1,57 @@
+/*
+    Symbol and decimals of the token used for payments, queried from its contract and cached.
+*/
+
+// External crates
+use bigdecimal::BigDecimal;
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+use std::sync::RwLock;
+use web3::types::U256;
+
+// Workspace uses
+use ya_payment_driver::{
+    db::models::Network,
+    model::{GenericError, TokenInfo},
+};
+
+// Local uses
+use crate::erc20::{ethereum, utils};
+
+lazy_static! {
+    static ref TOKEN_INFO: RwLock<HashMap<Network, TokenInfo>> = Default::default();
+}
+
+/// Queries the token contract, replacing any cached info.
+pub async fn load(network: Network) -> Result<TokenInfo, GenericError> {
+    let (symbol, decimals) = ethereum::get_token_info(network).await?;
+    let info = TokenInfo { symbol, decimals };
+    log::info!(
+        "Token on {}: symbol={}, decimals={}",
+        network,
+        info.symbol,
+        info.decimals
+    );
+    TOKEN_INFO.write().unwrap().insert(network, info.clone());
+    Ok(info)
+}
+
+/// Cached token info, queried from the contract when missing. Amounts are never converted
+/// with assumed decimals.
+pub async fn get(network: Network) -> Result<TokenInfo, GenericError> {
+    let cached = TOKEN_INFO.read().unwrap().get(&network).cloned();
+    match cached {
+        Some(info) => Ok(info),
+        None => load(network).await,
+    }
+}
+
+pub async fn to_u256(amount: &BigDecimal, network: Network) -> Result<U256, GenericError> {
+    let info = get(network).await?;
+    utils::big_dec_to_u256_with_decimals(amount, info.decimals)
+}
+
+pub async fn to_big_dec(amount: U256, network: Network) -> Result<BigDecimal, GenericError> {
+    let info = get(network).await?;
+    utils::u256_to_big_dec_with_decimals(amount, info.decimals)
+}
@@ -13,7 +13,7 @@ use web3::types::{Address, H160, H256, U256};
 use ya_payment_driver::model::GenericError;
 
 lazy_static! {
-    // TODO: Get token decimals from erc20-provider / wallet
+    // Native currency (ETH, MATIC) precision, token amounts use `token::TokenInfo::decimals`.
     pub static ref PRECISION: BigDecimal = BigDecimal::from(1_000_000_000_000_000_000u64);
     pub static ref GWEI_PRECISION: BigDecimal = BigDecimal::from(1_000_000_000u64);
 }
@@ -41,6 +41,24 @@ pub fn u256_to_big_dec(v: U256) -> Result<BigDecimal, GenericError> {
     Ok(v / &(*PRECISION))
 }
 
+fn precision(decimals: u32) -> BigDecimal {
+    BigDecimal::new(BigInt::from(1), -(decimals as i64))
+}
+
+pub fn big_dec_to_u256_with_decimals(v: &BigDecimal, decimals: u32) -> Result<U256, GenericError> {
+    let v = v * precision(decimals);
+    let v = v
+        .to_bigint()
+        .ok_or_else(|| GenericError::new("Failed to convert to bigint"))?;
+    let v = &v.to_string();
+    U256::from_dec_str(v).map_err(GenericError::new)
+}
+
+pub fn u256_to_big_dec_with_decimals(v: U256, decimals: u32) -> Result<BigDecimal, GenericError> {
+    let v: BigDecimal = v.to_string().parse().map_err(GenericError::new)?;
+    Ok(v / precision(decimals))
+}
+
 pub fn big_uint_to_big_dec(v: BigUint) -> BigDecimal {
     let v: BigDecimal = Into::<BigInt>::into(v).into();
     v / &(*PRECISION)
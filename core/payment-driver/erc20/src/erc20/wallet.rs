@@ -4,24 +4,26 @@
 
 // External crates
 use crate::erc20::{
+    decision::{self, Inputs},
     ethereum::{
         get_polygon_gas_price_method, get_polygon_maximum_price, get_polygon_priority,
-        get_polygon_starting_price, PolygonGasPriceMethod, PolygonPriority,
-        POLYGON_PREFERRED_GAS_PRICES_EXPRESS, POLYGON_PREFERRED_GAS_PRICES_FAST,
-        POLYGON_PREFERRED_GAS_PRICES_SLOW,
+        get_polygon_priority_prices, get_polygon_starting_price, PolygonGasPriceMethod,
+        PolygonPriority,
     },
+    forwarder::{self, ForwardRequest, MetaTxMode},
     gasless_transfer,
 };
 use bigdecimal::BigDecimal;
 use chrono::Utc;
-use num_bigint::BigUint;
 use std::str::FromStr;
-use web3::types::{H160, H256, U256, U64};
+use web3::types::{Transaction, H160, H256, U256, U64};
 
 // Workspace uses
 use ya_payment_driver::{
     db::models::{Network, TransactionEntity, TxType},
-    model::{GenericError, Init, PaymentDetails},
+    model::{AccountMode, FeeEstimate, FundsCheck, GenericError, Init, PaymentDetails, Readiness},
+    progress::ProgressReporter,
+    utils,
 };
 
 // Local uses
@@ -29,20 +31,19 @@ use crate::erc20::transaction::YagnaRawTransaction;
 use crate::{
     dao::Erc20Dao,
     erc20::{
-        eth_utils, ethereum, faucet,
+        bridge, eth_utils, ethereum, faucet, price, token,
         utils::{
-            big_dec_gwei_to_u256, big_dec_to_u256, big_uint_to_big_dec, convert_float_gas_to_u256,
-            convert_u256_gas_to_float, str_to_addr, topic_to_str_address, u256_to_big_dec,
+            big_dec_gwei_to_u256, convert_float_gas_to_u256, convert_u256_gas_to_float,
+            str_to_addr, topic_to_str_address, u256_to_big_dec, u256_to_big_dec_with_decimals,
         },
     },
-    RINKEBY_NETWORK,
 };
 use ya_payment_driver::db::models::TransactionStatus;
 
 pub async fn account_balance(address: H160, network: Network) -> Result<BigDecimal, GenericError> {
     let balance_com = ethereum::get_glm_balance(address, network).await?;
 
-    let balance = u256_to_big_dec(balance_com)?;
+    let balance = token::to_big_dec(balance_com, network).await?;
     log::debug!(
         "account_balance. address={}, network={}, balance={}",
         address,
@@ -70,25 +71,209 @@ pub async fn account_gas_balance(
     Ok(balance)
 }
 
-pub async fn init_wallet(msg: &Init) -> Result<(), GenericError> {
+pub async fn init_wallet(msg: &Init, progress: &ProgressReporter) -> Result<(), GenericError> {
     log::debug!("init_wallet. msg={:?}", msg);
     let address = msg.address();
-    let network = msg.network().unwrap_or_else(|| RINKEBY_NETWORK.to_string());
+    let network = msg
+        .network()
+        .unwrap_or_else(crate::network::default_network);
     let network = Network::from_str(&network).map_err(GenericError::new)?;
 
     // Validate address and that checking balance of GLM and ETH works.
     let h160_addr = str_to_addr(&address)?;
-    let _glm_balance = ethereum::get_glm_balance(h160_addr, network).await?;
-    let _eth_balance = ethereum::get_balance(h160_addr, network).await?;
+    progress.stage(1, format!("Checking funds on {}", network));
+    let readiness = readiness(h160_addr, network, None).await?;
+
+    if msg.mode().contains(AccountMode::SEND) && !readiness.ready {
+        progress.stage(
+            2,
+            format!("Not enough funds to send payments on {}", network),
+        );
+        log::warn!(
+            "Account {} is not funded on {}, token={}/{} {}, gas={}. {}",
+            address,
+            network,
+            readiness.token.available,
+            readiness.token.required,
+            readiness.token.currency,
+            readiness
+                .gas
+                .as_ref()
+                .map(|gas| format!("{}/{} {}", gas.available, gas.required, gas.currency))
+                .unwrap_or_else(|| "relayed".to_string()),
+            readiness.suggestions.join(" ")
+        );
+    }
 
     Ok(())
 }
 
-pub async fn fund(dao: &Erc20Dao, address: H160, network: Network) -> Result<(), GenericError> {
+/// Funds of `address` against `amount` of tokens and the gas of a single transfer.
+pub async fn readiness(
+    address: H160,
+    network: Network,
+    amount: Option<BigDecimal>,
+) -> Result<Readiness, GenericError> {
+    let token = FundsCheck {
+        currency: token::get(network).await?.symbol,
+        required: amount.unwrap_or_else(|| BigDecimal::from(0)),
+        available: account_balance(address, network).await?,
+    };
+
+    let gas = match use_meta_transactions(address, network).await? {
+        true => None,
+        false => {
+            let gas_price = match (network, get_polygon_gas_price_method()) {
+                (Network::Polygon, PolygonGasPriceMethod::PolygonGasPriceStatic) => {
+                    Some(convert_float_gas_to_u256(get_polygon_starting_price()))
+                }
+                _ => None,
+            };
+            let raw_tx = ethereum::prepare_raw_transaction(
+                address,
+                address,
+                U256::zero(),
+                network,
+                U256::zero(),
+                gas_price,
+                None,
+            )
+            .await?;
+            let platform = crate::network::network_token_to_platform(Some(network), None)?;
+            Some(FundsCheck {
+                currency: crate::network::platform_to_currency(platform)?.0,
+                required: u256_to_big_dec(raw_tx.gas_price * raw_tx.gas)?,
+                available: account_gas_balance(address, network).await?,
+            })
+        }
+    };
+
+    let ready = token.sufficient() && gas.as_ref().map(FundsCheck::sufficient).unwrap_or(true);
+    let suggestions = match ready {
+        true => vec![],
+        false => readiness_suggestions(address, network, &token, gas.as_ref()).await,
+    };
+
+    Ok(Readiness {
+        network: network.to_string(),
+        ready,
+        token,
+        gas,
+        suggestions,
+    })
+}
+
+async fn readiness_suggestions(
+    address: H160,
+    network: Network,
+    token: &FundsCheck,
+    gas: Option<&FundsCheck>,
+) -> Vec<String> {
+    let mut suggestions = vec![];
+
+    if !token.sufficient() {
+        let missing = &token.required - &token.available;
+        if let Some(counterpart) = bridge::counterpart(network) {
+            // Balance on the other side is only a hint, failure to get it is not an error.
+            let bridgeable = account_balance(address, counterpart)
+                .await
+                .unwrap_or_else(|_| BigDecimal::from(0));
+            if bridgeable > BigDecimal::from(0) {
+                let amount = match missing > BigDecimal::from(0) && missing < bridgeable {
+                    true => missing,
+                    false => bridgeable,
+                };
+                suggestions.push(format!(
+                    "Move {} {} from {} with `yagna payment bridge send --network {} --amount {}`.",
+                    amount, token.currency, counterpart, counterpart, amount
+                ));
+            }
+        }
+        suggestions.push(match network {
+            Network::Rinkeby => {
+                "Get test tokens with `yagna payment fund --network rinkeby`.".to_string()
+            }
+            Network::Goerli | Network::Mumbai => format!(
+                "There is no {} faucet on {}, send {} to {:#x} or use `--network rinkeby`.",
+                token.currency, network, token.currency, address
+            ),
+            Network::Polygon => format!(
+                "Request {} on the #funding channel of https://chat.golem.network or buy it and send it to {:#x}.",
+                token.currency, address
+            ),
+            Network::Mainnet => format!(
+                "Buy {} and send it to {:#x}.",
+                token.currency, address
+            ),
+        });
+    }
+
+    if let Some(gas) = gas.filter(|gas| !gas.sufficient()) {
+        suggestions.push(match network {
+            Network::Mainnet | Network::Polygon => format!(
+                "Buy at least {} {} for gas on {} and send it to {:#x}.",
+                &gas.required - &gas.available,
+                gas.currency,
+                network,
+                address
+            ),
+            _ => format!(
+                "Get at least {} {} for gas on {} from a public faucet.",
+                &gas.required - &gas.available,
+                gas.currency,
+                network
+            ),
+        });
+    }
+
+    suggestions
+}
+
+/// Gas cost of `payments` transfers on `network` at the current gas price, with its fiat value.
+pub async fn fee_estimate(network: Network, payments: u32) -> Result<FeeEstimate, GenericError> {
+    let gas_price = match (network, get_polygon_gas_price_method()) {
+        (Network::Polygon, PolygonGasPriceMethod::PolygonGasPriceStatic) => {
+            convert_float_gas_to_u256(get_polygon_starting_price())
+        }
+        _ => ethereum::get_gas_price(network).await?,
+    };
+    let gas_per_payment = *ethereum::GLM_TRANSFER_GAS;
+    let cost = u256_to_big_dec(gas_price * gas_per_payment * U256::from(payments))?;
+
+    let platform = crate::network::network_token_to_platform(Some(network), None)?;
+    let currency = crate::network::platform_to_currency(platform.clone())?.0;
+    // Without a price fees can't be compared, but the estimate is still useful.
+    let fiat_price = match price::native_price(network).await {
+        Ok(price) => Some(price),
+        Err(e) => {
+            log::warn!("Unable to get fiat price of {}: {}", currency, e);
+            None
+        }
+    };
+
+    Ok(FeeEstimate {
+        platform,
+        network: network.to_string(),
+        currency,
+        gas_price: u256_to_big_dec_with_decimals(gas_price, 9)?,
+        gas_per_payment: gas_per_payment.as_u64(),
+        fiat_cost: fiat_price.as_ref().map(|price| &cost * price),
+        cost,
+        fiat_currency: price::fiat_currency(),
+        fiat_price,
+    })
+}
+
+pub async fn fund(
+    dao: &Erc20Dao,
+    address: H160,
+    network: Network,
+    progress: &ProgressReporter,
+) -> Result<(), GenericError> {
     if network == Network::Mainnet {
         return Err(GenericError::new("Wallet can not be funded on mainnet."));
     }
-    faucet::request_glm(dao, address, network).await?;
+    faucet::request_glm(dao, address, network, progress).await?;
     Ok(())
 }
 
@@ -106,10 +291,54 @@ pub async fn get_next_nonce(
             "Network nonce different than db nonce: {} != {}",
             network_nonce, db_nonce
         );
-        return Ok(db_nonce);
     }
 
-    Ok(network_nonce)
+    let inputs = Inputs::Nonce {
+        network_nonce,
+        db_nonce,
+    };
+    Ok(decision::decide(network, inputs).value())
+}
+
+pub async fn get_next_forwarder_nonce(
+    dao: &Erc20Dao,
+    address: H160,
+    network: Network,
+) -> Result<U256, GenericError> {
+    let forwarder_nonce = ethereum::get_forwarder_nonce(address, network).await?;
+    let str_addr = format!("0x{:x}", &address);
+    let db_nonce = dao.get_next_forwarder_nonce(&str_addr, network).await?;
+
+    Ok(std::cmp::max(forwarder_nonce, db_nonce))
+}
+
+/// Decides whether payments from `address` go through the trusted forwarder.
+pub async fn use_meta_transactions(address: H160, network: Network) -> Result<bool, GenericError> {
+    if !forwarder::is_enabled(network) {
+        return Ok(false);
+    }
+
+    match forwarder::get_meta_tx_mode() {
+        MetaTxMode::Always => Ok(true),
+        MetaTxMode::Fallback => {
+            let raw_tx = ethereum::prepare_raw_transaction(
+                address,
+                address,
+                U256::zero(),
+                network,
+                U256::zero(),
+                None,
+                None,
+            )
+            .await?;
+            let inputs = Inputs::MetaTx {
+                balance: ethereum::get_balance(address, network).await?,
+                gas_price: raw_tx.gas_price,
+                gas: raw_tx.gas,
+            };
+            Ok(decision::decide(network, inputs).flag())
+        }
+    }
 }
 
 pub async fn has_enough_eth_for_gas(
@@ -146,6 +375,7 @@ pub async fn make_transfer(
     gas_price: Option<BigDecimal>,
     max_gas_price: Option<BigDecimal>,
     gas_limit: Option<u32>,
+    priority: Option<PolygonPriority>,
 ) -> Result<TransactionEntity, GenericError> {
     log::debug!(
         "make_transfer(). network={}, nonce={}, details={:?}",
@@ -154,10 +384,31 @@ pub async fn make_transfer(
         &details
     );
     let amount_big_dec = details.amount.clone();
-    let amount = big_dec_to_u256(&amount_big_dec)?;
+    let amount = token::to_u256(&amount_big_dec, network).await?;
 
-    let (gas_price, max_gas_price) = match network {
-        Network::Polygon => match get_polygon_gas_price_method() {
+    if priority.is_some() && network != Network::Polygon {
+        log::warn!(
+            "Gas priority is supported only on Polygon, ignoring it on {}.",
+            network
+        );
+    }
+
+    let (gas_price, max_gas_price) = match (network, &priority) {
+        // Explicit priority selects the static price table regardless of the method.
+        (Network::Polygon, Some(priority)) => {
+            let (starting_price, maximum_price) = get_polygon_priority_prices(priority);
+            (
+                Some(match gas_price {
+                    Some(v) => big_dec_gwei_to_u256(v)?,
+                    None => convert_float_gas_to_u256(starting_price),
+                }),
+                Some(match max_gas_price {
+                    Some(v) => big_dec_gwei_to_u256(v)?,
+                    None => convert_float_gas_to_u256(maximum_price),
+                }),
+            )
+        }
+        (Network::Polygon, None) => match get_polygon_gas_price_method() {
             PolygonGasPriceMethod::PolygonGasPriceStatic => (
                 Some(match gas_price {
                     Some(v) => big_dec_gwei_to_u256(v)?,
@@ -179,7 +430,7 @@ pub async fn make_transfer(
                 }),
             ),
         },
-        _ => (
+        (_, _) => (
             match gas_price {
                 None => None,
                 Some(v) => Some(big_dec_gwei_to_u256(v)?),
@@ -200,11 +451,11 @@ pub async fn make_transfer(
     )
     .await?;
 
-    if let Some(max_gas_price) = max_gas_price {
-        if raw_tx.gas_price > max_gas_price {
-            raw_tx.gas_price = max_gas_price;
-        }
-    }
+    let inputs = Inputs::TransferGasPrice {
+        proposed: raw_tx.gas_price,
+        max: max_gas_price,
+    };
+    raw_tx.gas_price = decision::decide(network, inputs).value();
 
     Ok(ethereum::create_dao_entity(
         nonce,
@@ -216,7 +467,79 @@ pub async fn make_transfer(
         network,
         Utc::now(),
         TxType::Transfer,
-        Some(amount_big_dec),
+        Some(amount),
+    ))
+}
+
+/// Zero value transaction to self with the nonce of `tx`, returns its encoding and gas limit.
+pub fn make_cancellation(tx: &TransactionEntity) -> Result<(String, i32), GenericError> {
+    let raw_tx = YagnaRawTransaction {
+        nonce: U256::from(tx.nonce as u32),
+        to: Some(str_to_addr(&tx.sender)?),
+        value: U256::zero(),
+        gas_price: ethereum::get_gas_price_from_db_tx(tx)?,
+        gas: *ethereum::CANCEL_TX_GAS,
+        data: vec![],
+    };
+    Ok((
+        serde_json::to_string(&raw_tx).map_err(GenericError::new)?,
+        raw_tx.gas.as_u32() as i32,
+    ))
+}
+
+/// Tells whether confirmed `tx_hash` of a cancelled transaction is the cancellation itself,
+/// rather than the replaced transaction mined in the meantime.
+pub async fn is_cancellation(
+    tx_hash: &str,
+    sender: &str,
+    network: Network,
+) -> Result<bool, GenericError> {
+    let hex_hash = H256::from_str(&tx_hash[2..]).map_err(GenericError::new)?;
+    let sender = str_to_addr(sender)?;
+    match ethereum::get_tx_from_network(hex_hash, network).await? {
+        Some(tx) => Ok(is_cancellation_tx(&tx, sender)),
+        None => Err(GenericError::new(format!(
+            "Transaction {} not found on chain",
+            tx_hash
+        ))),
+    }
+}
+
+/// Cancellation is a zero value transaction to self, without any call data.
+fn is_cancellation_tx(tx: &Transaction, sender: H160) -> bool {
+    tx.to == Some(sender) && tx.value.is_zero() && tx.input.0.is_empty()
+}
+
+pub async fn make_meta_transfer(
+    dao: &Erc20Dao,
+    details: &PaymentDetails,
+    network: Network,
+) -> Result<TransactionEntity, GenericError> {
+    log::debug!(
+        "make_meta_transfer(). network={}, details={:?}",
+        &network,
+        &details
+    );
+    let amount_big_dec = details.amount.clone();
+    let amount = token::to_u256(&amount_big_dec, network).await?;
+    let address = str_to_addr(&details.sender)?;
+    let recipient = str_to_addr(&details.recipient)?;
+
+    let nonce = get_next_forwarder_nonce(dao, address, network).await?;
+    let request =
+        ethereum::prepare_forward_request(address, recipient, amount, nonce, network).await?;
+
+    Ok(ethereum::create_dao_entity(
+        nonce,
+        address,
+        "0".to_string(),
+        None,
+        request.gas.as_u32() as i32,
+        serde_json::to_string(&request).map_err(GenericError::new)?,
+        network,
+        Utc::now(),
+        TxType::MetaTransfer,
+        Some(amount),
     ))
 }
 
@@ -239,43 +562,22 @@ pub async fn make_gasless_transfer(
     gasless_transfer::send_gasless_transfer(details, network).await
 }
 
-fn bump_gas_price(gas_in_gwei: U256) -> U256 {
-    let min_bump_num: U256 = U256::from(111u64);
-    let min_bump_den: U256 = U256::from(100u64);
-    let min_gas = gas_in_gwei * min_bump_num / min_bump_den;
-
-    match get_polygon_gas_price_method() {
-        PolygonGasPriceMethod::PolygonGasPriceDynamic => {
-            //ignore maximum gas price, because we have to bump at least 10% so the transaction will be accepted
-            min_gas
-        }
-        PolygonGasPriceMethod::PolygonGasPriceStatic => {
-            let polygon_prices = get_polygon_priority();
-
-            let gas_prices: &[f64] = match polygon_prices {
-                PolygonPriority::PolygonPriorityExpress => {
-                    &POLYGON_PREFERRED_GAS_PRICES_EXPRESS[..]
-                }
-                PolygonPriority::PolygonPriorityFast => &POLYGON_PREFERRED_GAS_PRICES_FAST[..],
-                PolygonPriority::PolygonPrioritySlow => &POLYGON_PREFERRED_GAS_PRICES_SLOW[..],
-            };
-
-            gas_prices
-                .iter()
-                .map(|&f| convert_float_gas_to_u256(f))
-                .find(|&gas_price_step| gas_price_step > min_gas)
-                .unwrap_or(min_gas)
-        }
-    }
-}
-
+/// Returns number of transactions accepted by the network.
 pub async fn send_transactions(
     dao: &Erc20Dao,
     txs: Vec<TransactionEntity>,
     network: Network,
-) -> Result<(), GenericError> {
+) -> Result<u64, GenericError> {
+    let mut sent = 0;
     // TODO: Use batch sending?
     for tx in txs {
+        if tx.tx_type == TxType::MetaTransfer as i32 {
+            if send_meta_transaction(dao, tx, network).await {
+                sent += 1;
+            }
+            continue;
+        }
+
         let mut raw_tx: YagnaRawTransaction =
             match serde_json::from_str::<YagnaRawTransaction>(&tx.encoded) {
                 Ok(raw_tx) => raw_tx,
@@ -298,35 +600,28 @@ pub async fn send_transactions(
 
         let address = str_to_addr(&tx.sender)?;
 
-        let new_gas_price = if let Some(current_gas_price) = tx.current_gas_price {
-            if tx.status == TransactionStatus::ResendAndBumpGas as i32 {
-                let gas_u256 = U256::from_dec_str(&current_gas_price).map_err(GenericError::new)?;
-
-                let max_gas_u256 = match tx.max_gas_price {
-                    Some(max_gas_price) => {
-                        Some(U256::from_dec_str(&max_gas_price).map_err(GenericError::new)?)
-                    }
-                    None => None,
-                };
-                let new_gas = bump_gas_price(gas_u256);
-                if let Some(max_gas_u256) = max_gas_u256 {
-                    if gas_u256 > max_gas_u256 {
-                        log::warn!(
-                            "bump gas ({}) larger than max gas ({}) price",
-                            gas_u256,
-                            max_gas_u256
-                        )
-                    }
-                }
-                new_gas
-            } else {
-                U256::from_dec_str(&current_gas_price).map_err(GenericError::new)?
+        let parse_gas_price = |gas_price: Option<String>| {
+            gas_price
+                .map(|gas_price| U256::from_dec_str(&gas_price).map_err(GenericError::new))
+                .transpose()
+        };
+        let bump = tx.status == TransactionStatus::ResendAndBumpGas as i32;
+        let current = parse_gas_price(tx.current_gas_price)?;
+        if let (true, Some(current), Some(max)) =
+            (bump, current, parse_gas_price(tx.max_gas_price)?)
+        {
+            if current > max {
+                log::warn!("bump gas ({}) larger than max gas ({}) price", current, max)
             }
-        } else if let Some(starting_gas_price) = tx.starting_gas_price {
-            U256::from_dec_str(&starting_gas_price).map_err(GenericError::new)?
-        } else {
-            convert_float_gas_to_u256(get_polygon_starting_price())
+        }
+        let inputs = Inputs::SendGasPrice {
+            bump,
+            current,
+            starting: parse_gas_price(tx.starting_gas_price)?,
+            method: get_polygon_gas_price_method(),
+            priority: get_polygon_priority(),
         };
+        let new_gas_price = decision::decide(network, inputs).value();
         raw_tx.gas_price = new_gas_price;
 
         let encoded = serde_json::to_string(&raw_tx).map_err(GenericError::new)?;
@@ -343,6 +638,13 @@ pub async fn send_transactions(
 
         let signed = eth_utils::encode_signed_tx(&raw_tx, signature, network as u64);
 
+        if utils::dry_run() {
+            if simulate_transaction(dao, &tx.tx_id, address, &raw_tx, &signed, network).await {
+                sent += 1;
+            }
+            continue;
+        }
+
         match ethereum::send_tx(signed, network).await {
             Ok(tx_hash) => {
                 let str_tx_hash = format!("0x{:x}", &tx_hash);
@@ -355,6 +657,7 @@ pub async fn send_transactions(
                     .await;
                 log::info!("Send transaction. hash={}", &str_tx_hash);
                 log::debug!("id={}", &tx.tx_id);
+                sent += 1;
             }
             Err(e) => {
                 log::error!("Error sending transaction: {:?}", e);
@@ -391,7 +694,106 @@ pub async fn send_transactions(
             }
         }
     }
-    Ok(())
+    Ok(sent)
+}
+
+/// Records `signed` transaction as simulated, when it executes successfully against the latest
+/// block, and as failed with its payments otherwise.
+async fn simulate_transaction(
+    dao: &Erc20Dao,
+    tx_id: &str,
+    address: H160,
+    raw_tx: &YagnaRawTransaction,
+    signed: &[u8],
+    network: Network,
+) -> bool {
+    let tx_hash = format!("0x{}", hex::encode(eth_utils::keccak256_hash(signed)));
+    match ethereum::simulate_tx(address, raw_tx, network).await {
+        Ok(()) => {
+            dao.transaction_simulated(tx_id, &tx_hash, Some(raw_tx.gas_price.to_string()))
+                .await;
+            log::info!("Simulated transaction, not broadcast. hash={}", &tx_hash);
+            log::debug!("id={}", tx_id);
+            true
+        }
+        Err(e) => {
+            log::error!("Simulation of transaction failed: {:?}", e);
+            simulation_failed(dao, tx_id, &tx_hash, e).await;
+            false
+        }
+    }
+}
+
+async fn simulation_failed(dao: &Erc20Dao, tx_id: &str, tx_hash: &str, e: GenericError) {
+    dao.transaction_confirmed_and_failed(
+        tx_id,
+        tx_hash,
+        None,
+        &format!("Simulation failed: {}", e),
+    )
+    .await;
+    let order_ids = dao
+        .get_payments_based_on_tx(tx_id)
+        .await
+        .into_iter()
+        .map(|payment| payment.order_id)
+        .collect::<Vec<_>>();
+    dao.payments_failed(&order_ids).await;
+}
+
+async fn send_meta_transaction(dao: &Erc20Dao, tx: TransactionEntity, network: Network) -> bool {
+    let request: ForwardRequest = match serde_json::from_str(&tx.encoded) {
+        Ok(request) => request,
+        Err(err) => {
+            log::error!(
+                "send_meta_transaction - ForwardRequest serialization failed: {:?}",
+                err
+            );
+            dao.transaction_confirmed_and_failed(
+                &tx.tx_id,
+                "",
+                None,
+                "Json parse failed, unrecoverable error",
+            )
+            .await;
+            return false;
+        }
+    };
+
+    match forwarder::send_meta_transaction(&request, network).await {
+        Ok(tx_hash) => {
+            let str_tx_hash = format!("0x{:x}", &tx_hash);
+            let str_tx_hash = if let Some(tmp_onchain_txs) = tx.tmp_onchain_txs {
+                tmp_onchain_txs + ";" + str_tx_hash.as_str()
+            } else {
+                str_tx_hash
+            };
+            if utils::dry_run() {
+                dao.transaction_simulated(&tx.tx_id, &str_tx_hash, None)
+                    .await;
+                log::info!(
+                    "Simulated meta-transaction, not relayed. hash={}",
+                    &str_tx_hash
+                );
+            } else {
+                dao.transaction_sent(&tx.tx_id, &str_tx_hash, None).await;
+                log::info!("Send meta-transaction. hash={}", &str_tx_hash);
+            }
+            log::debug!("id={}", &tx.tx_id);
+            true
+        }
+        Err(e) if utils::dry_run() => {
+            log::error!("Simulation of meta-transaction failed: {:?}", e);
+            simulation_failed(dao, &tx.tx_id, "", e).await;
+            false
+        }
+        Err(e) => {
+            log::error!("Error sending meta-transaction: {:?}", e);
+            dao.transaction_failed_send(&tx.tx_id, tx.resent_times, e.to_string().as_str())
+                .await;
+            false
+        }
+    }
 }
 
 // TODO: calculate fee. Below commented out reference to zkSync implementation
@@ -447,7 +849,13 @@ pub async fn verify_tx(tx_hash: &str, network: Network) -> Result<PaymentDetails
         let sender = topic_to_str_address(topic1);
         let recipient = topic_to_str_address(topic2);
 
-        let amount = big_uint_to_big_dec(BigUint::from_bytes_be(&tx_log.data.0));
+        if tx_log.data.0.len() > 32 {
+            return Err(GenericError::new(format!(
+                "Failure when parsing tx_log.data: {} ",
+                tx_hash
+            )));
+        }
+        let amount = token::to_big_dec(U256::from_big_endian(&tx_log.data.0), network).await?;
 
         if let Some(_block_number) = tx_log.block_number {
             // TODO: Get date from block
@@ -470,3 +878,39 @@ pub async fn verify_tx(tx_hash: &str, network: Network) -> Result<PaymentDetails
         )))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use web3::types::Bytes;
+
+    #[test]
+    fn test_is_cancellation_tx() {
+        let sender = H160::repeat_byte(0x0a);
+        let cancellation = Transaction {
+            to: Some(sender),
+            ..Default::default()
+        };
+        assert!(is_cancellation_tx(&cancellation, sender));
+
+        // Replaced GLM transfer, calling the token contract.
+        let transfer = Transaction {
+            to: Some(H160::repeat_byte(0x0c)),
+            input: Bytes(vec![0xa9, 0x05, 0x9c, 0xbb]),
+            ..Default::default()
+        };
+        assert!(!is_cancellation_tx(&transfer, sender));
+
+        let call_to_self = Transaction {
+            input: Bytes(vec![0x01]),
+            ..cancellation.clone()
+        };
+        assert!(!is_cancellation_tx(&call_to_self, sender));
+
+        let value_to_self = Transaction {
+            value: U256::from(1),
+            ..cancellation
+        };
+        assert!(!is_cancellation_tx(&value_to_self, sender));
+    }
+}
@@ -54,6 +54,76 @@ lazy_static::lazy_static! {
     pub static ref MAINNET_DB_NETWORK: DbNetwork = DbNetwork::from_str(MAINNET_NETWORK).unwrap();
     pub static ref MUMBAI_DB_NETWORK: DbNetwork = DbNetwork::from_str(MUMBAI_NETWORK).unwrap();
     pub static ref POLYGON_MAINNET_DB_NETWORK: DbNetwork = DbNetwork::from_str(POLYGON_MAINNET_NETWORK).unwrap();
+
+    /// Deprecated network -> replacement, e.g. `rinkeby=goerli,mumbai=polygon`.
+    static ref DEPRECATED_NETWORKS: HashMap<String, String> = parse_deprecated_networks(
+        &std::env::var("ERC20_DEPRECATED_NETWORKS")
+            .unwrap_or_else(|_| format!("{}={}", RINKEBY_NETWORK, GOERLI_NETWORK))
+    );
+    static ref SWITCH_DEPRECATED_NETWORKS: bool = std::env::var("ERC20_SWITCH_DEPRECATED_NETWORKS")
+        .ok()
+        .and_then(|x| x.parse().ok())
+        .unwrap_or(false);
+}
+
+fn parse_deprecated_networks(networks: &str) -> HashMap<String, String> {
+    networks
+        .split(',')
+        .filter_map(|entry| {
+            let (network, replacement) = entry.split_once('=')?;
+            let (network, replacement) = (network.trim(), replacement.trim());
+            match SUPPORTED_NETWORKS.contains_key(replacement) && network != replacement {
+                true => Some((network.to_string(), replacement.to_string())),
+                false => {
+                    log::warn!("Invalid deprecated network entry [{}]", entry);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// Replacement of the deprecated `network`.
+pub fn deprecated_network_replacement(network: &str) -> Option<String> {
+    DEPRECATED_NETWORKS.get(network).cloned()
+}
+
+/// Warns when `network` is deprecated and switches to its replacement
+/// if `ERC20_SWITCH_DEPRECATED_NETWORKS` is enabled.
+pub fn redirect_deprecated_network(network: String) -> String {
+    let replacement = match deprecated_network_replacement(&network) {
+        Some(replacement) => replacement,
+        None => return network,
+    };
+    if *SWITCH_DEPRECATED_NETWORKS {
+        log::warn!(
+            "Network {} is deprecated, using {} instead.",
+            network,
+            replacement
+        );
+        return replacement;
+    }
+    log::warn!(
+        "Network {} is deprecated, consider switching to {}: `yagna payment init --network {}` \
+         or set ERC20_SWITCH_DEPRECATED_NETWORKS=true to switch automatically. \
+         Remaining funds can be moved with `yagna payment transfer --network {} --to-address <address> --amount all`.",
+        network,
+        replacement,
+        replacement,
+        network
+    );
+    network
+}
+
+/// Default network of the driver, redirected when deprecated.
+pub fn default_network() -> String {
+    match (
+        *SWITCH_DEPRECATED_NETWORKS,
+        deprecated_network_replacement(RINKEBY_NETWORK),
+    ) {
+        (true, Some(replacement)) => replacement,
+        _ => RINKEBY_NETWORK.to_string(),
+    }
 }
 
 pub fn platform_to_network_token(platform: String) -> Result<(DbNetwork, String), GenericError> {
@@ -140,8 +210,23 @@ pub fn get_network_token(network: DbNetwork, token: Option<String>) -> String {
 }
 
 pub fn network_like_to_network(network_like: Option<String>) -> DbNetwork {
+    let default_network = DbNetwork::from_str(&default_network()).unwrap_or(*RINKEBY_DB_NETWORK);
     match network_like {
-        Some(n) => DbNetwork::from_str(&n).unwrap_or(*RINKEBY_DB_NETWORK),
-        None => *RINKEBY_DB_NETWORK,
+        Some(n) => DbNetwork::from_str(&n).unwrap_or(default_network),
+        None => default_network,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_deprecated_networks() {
+        let networks = parse_deprecated_networks("rinkeby=goerli, mumbai = goerli,goerli=holesky");
+        assert_eq!(networks.len(), 2);
+        assert_eq!(networks.get("rinkeby"), Some(&GOERLI_NETWORK.to_string()));
+        assert_eq!(networks.get("mumbai"), Some(&GOERLI_NETWORK.to_string()));
+        assert!(parse_deprecated_networks("").is_empty());
     }
 }
@@ -3,19 +3,23 @@
 */
 
 // Extrernal crates
+use std::convert::TryFrom;
+use std::str::FromStr;
 use std::sync::Arc;
 
 // Workspace uses
 use ya_payment_driver::{
     bus,
     cron::Cron,
-    dao::{init, DbExecutor},
+    dao::{init, transaction::TransactionDao, DbExecutor},
+    db::models::{Network, TransactionStatus},
     model::GenericError,
 };
+use ya_service_api::recovery::{RecoveryAction, RecoveryItem};
 use ya_service_api_interfaces::Provider;
 
 // Local uses
-use crate::driver::Erc20Driver;
+use crate::{driver::Erc20Driver, erc20::token, network::SUPPORTED_NETWORKS, DRIVER_NAME};
 
 pub struct Erc20Service;
 
@@ -38,6 +42,19 @@ impl Erc20Service {
         bus::bind_service(&db, driver_rc.clone()).await?;
         log::debug!("Driver loaded");
 
+        // Token decimals are needed for every amount, query them upfront
+        tokio::task::spawn_local(async {
+            for network in SUPPORTED_NETWORKS.keys() {
+                let network = match Network::from_str(network) {
+                    Ok(network) => network,
+                    Err(_) => continue,
+                };
+                if let Err(e) = token::load(network).await {
+                    log::warn!("Failed to load token info for {}: {}", network, e);
+                }
+            }
+        });
+
         // Start cron
         Cron::new(driver_rc.clone());
         log::debug!("Cron started");
@@ -45,4 +62,34 @@ impl Erc20Service {
         log::info!("Successfully connected Erc20Service to gsb.");
         Ok(())
     }
+
+    /// Reports transactions left unfinished by the previous run. Cron continues them.
+    pub async fn recover(db: &DbExecutor) -> anyhow::Result<Vec<RecoveryItem>> {
+        let txs = db
+            .as_dao::<TransactionDao>()
+            .get_unfinished_txs()
+            .await
+            .map_err(GenericError::new)?;
+
+        Ok(txs
+            .into_iter()
+            .filter_map(|tx| {
+                let (state, details) = match TransactionStatus::try_from(tx.status).ok()? {
+                    TransactionStatus::Created => ("Created", "Will be sent"),
+                    TransactionStatus::Resend | TransactionStatus::ResendAndBumpGas => {
+                        ("Resend", "Will be sent again")
+                    }
+                    TransactionStatus::Sent => ("Sent", "Waiting for confirmation"),
+                    TransactionStatus::Pending => ("Pending", "Waiting for confirmation"),
+                    TransactionStatus::ErrorSent => {
+                        ("ErrorSent", "Will be looked up on chain and sent again")
+                    }
+                    _ => return None,
+                };
+                let item = RecoveryItem::new(DRIVER_NAME, tx.tx_id, state, RecoveryAction::Resumed)
+                    .details(format!("{} on {}", details, tx.network));
+                Some(item)
+            })
+            .collect())
+    }
 }
@@ -78,6 +78,7 @@ impl ZksyncDao {
             status: PAYMENT_STATUS_NOT_YET,
             tx_id: None,
             network,
+            priority: msg.priority() as i32,
         };
         if let Err(e) = self.payment().insert(payment).await {
             log::error!(
@@ -17,11 +17,12 @@ use uuid::Uuid;
 use ya_payment_driver::{
     account::{Accounts, AccountsRc},
     bus,
-    cron::PaymentDriverCron,
+    cron::{CycleReport, PaymentDriverCron},
     dao::DbExecutor,
     db::models::{Network as DbNetwork, PaymentEntity, TxType},
     driver::{async_trait, BigDecimal, IdentityError, IdentityEvent, Network, PaymentDriver},
     model::*,
+    queue::PaymentQueue,
     utils,
 };
 use ya_utils_futures::timeout::IntoTimeoutFuture;
@@ -101,7 +102,7 @@ impl ZksyncDriver {
             .is_some()
     }
 
-    async fn process_payments_for_account(&self, node_id: &str) {
+    async fn process_payments_for_account(&self, node_id: &str, report: &CycleReport) {
         log::trace!("Processing payments for node_id={}", node_id);
         for network_key in self.get_networks().keys() {
             let network = DbNetwork::from_str(network_key).unwrap();
@@ -119,13 +120,13 @@ impl ZksyncDriver {
                 nonce = wallet::get_nonce(node_id, network).await;
                 log::debug!("Payments: nonce={}, details={:?}", &nonce, payments);
             }
-            for payment in payments {
-                self.handle_payment(payment, &mut nonce).await;
+            for payment in payments.into_iter().collect::<PaymentQueue>() {
+                self.handle_payment(payment, &mut nonce, report).await;
             }
         }
     }
 
-    async fn handle_payment(&self, payment: PaymentEntity, nonce: &mut u32) {
+    async fn handle_payment(&self, payment: PaymentEntity, nonce: &mut u32, report: &CycleReport) {
         let details = utils::db_to_payment_details(&payment);
         let tx_nonce = nonce.to_owned();
 
@@ -138,6 +139,8 @@ impl ZksyncDriver {
                 self.dao
                     .transaction_sent(&tx_id, &tx_hash, &payment.order_id)
                     .await;
+                report.processed(1);
+                report.sent(1);
                 *nonce += 1;
             }
             Err(e) => {
@@ -146,6 +149,7 @@ impl ZksyncDriver {
                 if Utc::now() > deadline {
                     log::error!("Failed to submit zkSync transaction. Retry deadline reached. details={:?} error={}", payment, e);
                     self.dao.payment_failed(&payment.order_id).await;
+                    report.failed(1);
                 } else {
                     log::warn!(
                         "Failed to submit zkSync transaction. Payment will be retried until {}. details={:?} error={}",
@@ -408,14 +412,14 @@ To be able to use zkSync driver please send some GLM tokens and optionally ETH f
         _caller: String,
         msg: ShutDown,
     ) -> Result<(), GenericError> {
-        self.send_out_payments().await;
+        self.send_out_payments(&CycleReport::default()).await;
         // HACK: Make sure that send-out job did complete. It might have just been running in another thread (cron). In such case .send_out_payments() would not block.
         self.sendout_lock.lock().await;
         let timeout = Duration::from_std(msg.timeout)
             .map_err(|e| GenericError::new(format!("Invalid shutdown timeout: {}", e)))?;
         let deadline = Utc::now() + timeout - Duration::seconds(1);
         while {
-            self.confirm_payments().await; // Run it at least once
+            self.confirm_payments(&CycleReport::default()).await; // Run it at least once
             Utc::now() < deadline && self.dao.has_unconfirmed_txs().await? // Stop if deadline passes or there are no more transactions to confirm
         } {
             tokio::time::sleep(std::time::Duration::from_secs(1)).await;
@@ -426,7 +430,7 @@ To be able to use zkSync driver please send some GLM tokens and optionally ETH f
 
 #[async_trait(?Send)]
 impl PaymentDriverCron for ZksyncDriver {
-    async fn confirm_payments(&self) {
+    async fn confirm_payments(&self, report: &CycleReport) {
         let guard = match self.confirmation_lock.try_lock() {
             None => {
                 log::trace!("ZkSync confirmation job in progress.");
@@ -468,6 +472,9 @@ impl PaymentDriverCron for ZksyncDriver {
                 };
 
                 let payments = self.dao.transaction_confirmed(&tx.tx_id).await;
+                if tx_success.is_ok() {
+                    report.confirmed(1);
+                }
 
                 // Faucet can stop here IF the tx was a success.
                 if tx.tx_type == TxType::Faucet as i32 && tx_success.is_ok() {
@@ -504,6 +511,7 @@ impl PaymentDriverCron for ZksyncDriver {
                         for order_id in order_ids.iter() {
                             self.dao.payment_failed(order_id).await;
                         }
+                        report.failed(1);
                     }
 
                     self.dao
@@ -557,7 +565,7 @@ impl PaymentDriverCron for ZksyncDriver {
         drop(guard); // Explicit drop to tell Rust that guard is not unused variable
     }
 
-    async fn send_out_payments(&self) {
+    async fn send_out_payments(&self, report: &CycleReport) {
         let guard = match self.sendout_lock.try_lock() {
             None => {
                 log::trace!("ZkSync send-out job in progress.");
@@ -568,7 +576,7 @@ impl PaymentDriverCron for ZksyncDriver {
         log::trace!("Running zkSync send-out job...");
         let accounts = self.active_accounts.borrow().list_accounts();
         for node_id in accounts {
-            self.process_payments_for_account(&node_id).await;
+            self.process_payments_for_account(&node_id, report).await;
         }
         log::trace!("ZkSync send-out job complete.");
         drop(guard); // Explicit drop to tell Rust that guard is not unused variable
@@ -132,6 +132,7 @@ fn fake_list_identities(identities: Vec<NodeId>) {
                 node_id: id,
                 is_default: false,
                 is_locked: false,
+                roles: vec![],
             });
         }
         async move { Ok(accounts) }
@@ -0,0 +1,16 @@
+DROP VIEW pay_invoice_event_read;
+DROP VIEW pay_debit_note_event_read;
+DROP TABLE pay_order;
+DROP TABLE pay_invoice_event;
+DROP TABLE pay_debit_note_event;
+DROP TABLE pay_event_type;
+DROP TABLE pay_agreement_payment;
+DROP TABLE pay_activity_payment;
+DROP TABLE pay_payment;
+DROP TABLE pay_allocation;
+DROP TABLE pay_invoice_x_activity;
+DROP TABLE pay_invoice;
+DROP TABLE pay_debit_note;
+DROP TABLE pay_document_status;
+DROP TABLE pay_activity;
+DROP TABLE pay_agreement;
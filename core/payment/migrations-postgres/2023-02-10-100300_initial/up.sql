@@ -0,0 +1,240 @@
+-- Schema equivalent to SQLite migrations up to 2022-04-04-095553_role_id-in-events.
+
+CREATE TABLE pay_agreement(
+    id TEXT NOT NULL,
+    owner_id TEXT NOT NULL,
+    role CHAR(1) NOT NULL CHECK (role in ('R', 'P')),
+    peer_id TEXT NOT NULL,
+    payee_addr TEXT NOT NULL,
+    payer_addr TEXT NOT NULL,
+    payment_platform TEXT NOT NULL,
+    total_amount_due TEXT NOT NULL,
+    total_amount_accepted TEXT NOT NULL,
+    total_amount_scheduled TEXT NOT NULL,
+    total_amount_paid TEXT NOT NULL,
+    app_session_id TEXT NULL,
+    PRIMARY KEY (owner_id, id),
+    UNIQUE (id, role)
+);
+
+CREATE TABLE pay_activity(
+    id TEXT NOT NULL,
+    owner_id TEXT NOT NULL,
+    role CHAR(1) NOT NULL CHECK (role in ('R', 'P')),
+    agreement_id TEXT NOT NULL,
+    total_amount_due TEXT NOT NULL,
+    total_amount_accepted TEXT NOT NULL,
+    total_amount_scheduled TEXT NOT NULL,
+    total_amount_paid TEXT NOT NULL,
+    PRIMARY KEY (owner_id, id),
+    UNIQUE (id, role),
+    FOREIGN KEY (owner_id, agreement_id) REFERENCES pay_agreement (owner_id, id)
+);
+
+CREATE TABLE pay_document_status(
+    status TEXT NOT NULL PRIMARY KEY
+);
+
+INSERT INTO pay_document_status(status) VALUES
+    ('ISSUED'),
+    ('RECEIVED'),
+    ('ACCEPTED'),
+    ('REJECTED'),
+    ('FAILED'),
+    ('SETTLED'),
+    ('CANCELLED');
+
+CREATE TABLE pay_debit_note(
+    id TEXT NOT NULL,
+    owner_id TEXT NOT NULL,
+    role CHAR(1) NOT NULL CHECK (role in ('R', 'P')),
+    previous_debit_note_id TEXT NULL,
+    activity_id TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'ISSUED',
+    timestamp TIMESTAMP NOT NULL DEFAULT timezone('utc', now()),
+    total_amount_due TEXT NOT NULL,
+    usage_counter_vector BYTEA NULL,
+    payment_due_date TIMESTAMP NULL,
+    PRIMARY KEY (owner_id, id),
+    UNIQUE (id, role),
+    FOREIGN KEY (owner_id, previous_debit_note_id) REFERENCES pay_debit_note (owner_id, id),
+    FOREIGN KEY (owner_id, activity_id) REFERENCES pay_activity (owner_id, id),
+    FOREIGN KEY (status) REFERENCES pay_document_status (status)
+);
+
+CREATE TABLE pay_invoice(
+    id TEXT NOT NULL,
+    owner_id TEXT NOT NULL,
+    role CHAR(1) NOT NULL CHECK (role in ('R', 'P')),
+    agreement_id TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'ISSUED',
+    timestamp TIMESTAMP NOT NULL DEFAULT timezone('utc', now()),
+    amount TEXT NOT NULL,
+    payment_due_date TIMESTAMP NOT NULL,
+    PRIMARY KEY (owner_id, id),
+    UNIQUE (id, role),
+    FOREIGN KEY (owner_id, agreement_id) REFERENCES pay_agreement (owner_id, id),
+    FOREIGN KEY (status) REFERENCES pay_document_status (status)
+);
+
+CREATE TABLE pay_invoice_x_activity(
+    invoice_id TEXT NOT NULL,
+    activity_id TEXT NOT NULL,
+    owner_id TEXT NOT NULL,
+    PRIMARY KEY (owner_id, invoice_id, activity_id),
+    FOREIGN KEY (owner_id, invoice_id) REFERENCES pay_invoice (owner_id, id),
+    FOREIGN KEY (owner_id, activity_id) REFERENCES pay_activity (owner_id, id)
+);
+
+CREATE TABLE pay_allocation(
+    id TEXT NOT NULL PRIMARY KEY,
+    owner_id TEXT NOT NULL,
+    payment_platform TEXT NOT NULL,
+    address TEXT NOT NULL,
+    total_amount TEXT NOT NULL,
+    spent_amount TEXT NOT NULL,
+    remaining_amount TEXT NOT NULL,
+    timestamp TIMESTAMP NOT NULL DEFAULT timezone('utc', now()),
+    timeout TIMESTAMP NULL,
+    make_deposit BOOLEAN NOT NULL,
+    released BOOLEAN NOT NULL DEFAULT FALSE
+);
+
+CREATE TABLE pay_payment(
+    id TEXT NOT NULL,
+    owner_id TEXT NOT NULL,
+    peer_id TEXT NOT NULL,
+    payee_addr TEXT NOT NULL,
+    payer_addr TEXT NOT NULL,
+    payment_platform TEXT NOT NULL,
+    role CHAR(1) NOT NULL CHECK (role in ('R', 'P')),
+    amount TEXT NOT NULL,
+    timestamp TIMESTAMP NOT NULL DEFAULT timezone('utc', now()),
+    details BYTEA NOT NULL,
+    PRIMARY KEY (owner_id, id),
+    UNIQUE (id, role)
+);
+
+CREATE TABLE pay_activity_payment(
+    payment_id TEXT NOT NULL,
+    activity_id TEXT NOT NULL,
+    owner_id TEXT NOT NULL,
+    amount TEXT NOT NULL,
+    allocation_id TEXT NULL,
+    PRIMARY KEY (owner_id, payment_id, activity_id),
+    FOREIGN KEY (owner_id, payment_id) REFERENCES pay_payment (owner_id, id),
+    FOREIGN KEY (owner_id, activity_id) REFERENCES pay_activity (owner_id, id),
+    FOREIGN KEY (allocation_id) REFERENCES pay_allocation (id) ON DELETE SET NULL
+);
+
+CREATE TABLE pay_agreement_payment(
+    payment_id TEXT NOT NULL,
+    agreement_id TEXT NOT NULL,
+    owner_id TEXT NOT NULL,
+    amount TEXT NOT NULL,
+    allocation_id TEXT NULL,
+    PRIMARY KEY (owner_id, payment_id, agreement_id),
+    FOREIGN KEY (owner_id, payment_id) REFERENCES pay_payment (owner_id, id),
+    FOREIGN KEY (owner_id, agreement_id) REFERENCES pay_agreement (owner_id, id),
+    FOREIGN KEY (allocation_id) REFERENCES pay_allocation (id) ON DELETE SET NULL
+);
+
+CREATE TABLE pay_event_type(
+    event_type TEXT NOT NULL PRIMARY KEY,
+    role CHAR(1) NOT NULL CHECK (role in ('R', 'P'))
+);
+
+INSERT INTO pay_event_type(event_type, role) VALUES
+    ('RECEIVED', 'R'),
+    ('ACCEPTED', 'P'),
+    ('REJECTED', 'P'),
+    ('CANCELLED', 'R'),
+    ('SETTLED', 'P');
+
+CREATE TABLE pay_debit_note_event(
+    debit_note_id TEXT NOT NULL,
+    owner_id TEXT NOT NULL,
+    event_type TEXT NOT NULL,
+    timestamp TIMESTAMP NOT NULL DEFAULT timezone('utc', now()),
+    details TEXT NULL,
+    PRIMARY KEY (debit_note_id, event_type),
+    FOREIGN KEY (owner_id, debit_note_id) REFERENCES pay_debit_note (owner_id, id),
+    FOREIGN KEY (event_type) REFERENCES pay_event_type (event_type)
+);
+
+CREATE TABLE pay_invoice_event(
+    invoice_id TEXT NOT NULL,
+    owner_id TEXT NOT NULL,
+    event_type TEXT NOT NULL,
+    timestamp TIMESTAMP NOT NULL DEFAULT timezone('utc', now()),
+    details TEXT NULL,
+    PRIMARY KEY (invoice_id, event_type),
+    FOREIGN KEY (owner_id, invoice_id) REFERENCES pay_invoice (owner_id, id),
+    FOREIGN KEY (event_type) REFERENCES pay_event_type (event_type)
+);
+
+CREATE TABLE pay_order(
+    id TEXT NOT NULL,
+    driver TEXT NOT NULL,
+    amount TEXT NOT NULL,
+    payee_id TEXT NOT NULL,
+    payer_id TEXT NOT NULL,
+    payee_addr TEXT NOT NULL,
+    payer_addr TEXT NOT NULL,
+    payment_platform TEXT NOT NULL,
+    invoice_id TEXT NULL UNIQUE,
+    debit_note_id TEXT NULL UNIQUE,
+    allocation_id TEXT NOT NULL,
+    is_paid BOOLEAN NOT NULL DEFAULT FALSE,
+    PRIMARY KEY (id, driver),
+    FOREIGN KEY (payer_id, invoice_id) REFERENCES pay_invoice (owner_id, id),
+    FOREIGN KEY (payer_id, debit_note_id) REFERENCES pay_debit_note (owner_id, id),
+    FOREIGN KEY (allocation_id) REFERENCES pay_allocation (id),
+    CHECK ((invoice_id IS NULL) <> (debit_note_id IS NULL))
+);
+
+CREATE VIEW pay_debit_note_event_read AS
+SELECT
+    dn.role,
+    dne.debit_note_id,
+    dne.owner_id,
+    dne.event_type,
+    dne.timestamp,
+    dne.details,
+    agr.app_session_id
+FROM
+    pay_debit_note_event dne
+    INNER JOIN pay_debit_note dn ON dne.owner_id = dn.owner_id AND dne.debit_note_id = dn.id
+    INNER JOIN pay_activity act ON dne.owner_id = act.owner_id AND dn.activity_id = act.id
+    INNER JOIN pay_agreement agr ON dne.owner_id = agr.owner_id AND act.agreement_id = agr.id;
+
+CREATE VIEW pay_invoice_event_read AS
+SELECT
+    inv.role,
+    ie.invoice_id,
+    ie.owner_id,
+    ie.event_type,
+    ie.timestamp,
+    ie.details,
+    agr.app_session_id
+FROM
+    pay_invoice_event ie
+    INNER JOIN pay_invoice inv ON ie.owner_id = inv.owner_id AND ie.invoice_id = inv.id
+    INNER JOIN pay_agreement agr ON ie.owner_id = agr.owner_id AND inv.agreement_id = agr.id;
+
+CREATE INDEX pay_agreement_session_idx ON pay_agreement (app_session_id);
+CREATE INDEX pay_agreement_payment_platform_payee_idx ON pay_agreement (payment_platform, payee_addr);
+CREATE INDEX pay_agreement_payment_platform_payer_idx ON pay_agreement (payment_platform, payer_addr);
+CREATE INDEX pay_allocation_owner_idx ON pay_allocation (owner_id);
+CREATE INDEX pay_allocation_timestamp_idx ON pay_allocation (timestamp);
+CREATE INDEX pay_allocation_payment_platform_address_idx ON pay_allocation (payment_platform, address);
+CREATE INDEX pay_debit_note_activity_owner_idx ON pay_debit_note (activity_id, owner_id);
+CREATE INDEX pay_debit_note_timestamp_idx ON pay_debit_note (timestamp);
+CREATE INDEX pay_debit_note_event_owner_idx ON pay_debit_note_event (owner_id);
+CREATE INDEX pay_debit_note_event_timestamp_idx ON pay_debit_note_event (timestamp);
+CREATE INDEX pay_invoice_timestamp_idx ON pay_invoice (timestamp);
+CREATE INDEX pay_invoice_agreement_id_timestamp_idx ON pay_invoice (agreement_id, timestamp);
+CREATE INDEX pay_invoice_event_owner_idx ON pay_invoice_event (owner_id);
+CREATE INDEX pay_invoice_event_timestamp_idx ON pay_invoice_event (timestamp);
+CREATE INDEX pay_activity_payment_payment_owner_idx ON pay_activity_payment (payment_id, owner_id);
+CREATE INDEX pay_agreement_payment_payment_owner_idx ON pay_agreement_payment (payment_id, owner_id);
@@ -0,0 +1,7 @@
+CREATE TABLE pay_debit_note_usage(
+    debit_note_id TEXT NOT NULL,
+    owner_id TEXT NOT NULL,
+    signed_usage TEXT NOT NULL,
+    PRIMARY KEY (debit_note_id, owner_id),
+    FOREIGN KEY (owner_id, debit_note_id) REFERENCES pay_debit_note (owner_id, id)
+);
@@ -0,0 +1,10 @@
+CREATE TABLE pay_agreement_budget(
+    agreement_id TEXT NOT NULL,
+    owner_id TEXT NOT NULL,
+    allocation_id TEXT NULL,
+    max_amount TEXT NULL,
+    threshold DOUBLE PRECISION NULL,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    terminated_ts TIMESTAMP NULL,
+    PRIMARY KEY (agreement_id, owner_id)
+);
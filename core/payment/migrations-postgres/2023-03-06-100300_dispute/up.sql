@@ -0,0 +1,12 @@
+CREATE TABLE pay_dispute(
+    document_id TEXT NOT NULL,
+    owner_id TEXT NOT NULL,
+    document_type TEXT NOT NULL,
+    peer_id TEXT NOT NULL,
+    status TEXT NOT NULL,
+    rejection TEXT NOT NULL,
+    response TEXT NULL,
+    created_ts TIMESTAMP NOT NULL DEFAULT timezone('utc', now()),
+    updated_ts TIMESTAMP NOT NULL DEFAULT timezone('utc', now()),
+    PRIMARY KEY (document_id, owner_id)
+);
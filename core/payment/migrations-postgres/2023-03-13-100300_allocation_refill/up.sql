@@ -0,0 +1,11 @@
+CREATE TABLE pay_allocation_refill(
+    allocation_id TEXT NOT NULL,
+    owner_id TEXT NOT NULL,
+    amount TEXT NOT NULL,
+    threshold TEXT NOT NULL,
+    max_total_amount TEXT NULL,
+    refilled_amount TEXT NOT NULL DEFAULT '0',
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    refilled_ts TIMESTAMP NULL,
+    PRIMARY KEY (allocation_id, owner_id)
+);
@@ -0,0 +1,7 @@
+CREATE TABLE pay_invoice_reminder(
+    invoice_id TEXT NOT NULL,
+    owner_id TEXT NOT NULL,
+    reminders INTEGER NOT NULL,
+    last_reminder_ts TIMESTAMP NOT NULL,
+    PRIMARY KEY (invoice_id, owner_id)
+);
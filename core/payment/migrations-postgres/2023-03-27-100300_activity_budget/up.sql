@@ -0,0 +1,8 @@
+CREATE TABLE pay_activity_budget(
+    activity_id TEXT NOT NULL,
+    owner_id TEXT NOT NULL,
+    agreement_id TEXT NOT NULL,
+    max_amount TEXT NOT NULL,
+    terminated_ts TIMESTAMP NULL,
+    PRIMARY KEY (activity_id, owner_id)
+);
@@ -0,0 +1 @@
+DROP TABLE pay_debit_note_usage;
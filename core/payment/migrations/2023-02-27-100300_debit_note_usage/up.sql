@@ -0,0 +1,7 @@
+CREATE TABLE pay_debit_note_usage(
+    debit_note_id VARCHAR(50) NOT NULL,
+    owner_id VARCHAR(50) NOT NULL,
+    signed_usage TEXT NOT NULL,
+    PRIMARY KEY(debit_note_id, owner_id),
+    FOREIGN KEY(owner_id, debit_note_id) REFERENCES pay_debit_note (owner_id, id)
+);
@@ -0,0 +1,10 @@
+CREATE TABLE pay_agreement_budget(
+    agreement_id VARCHAR(50) NOT NULL,
+    owner_id VARCHAR(50) NOT NULL,
+    allocation_id VARCHAR(50) NULL,
+    max_amount VARCHAR(32) NULL,
+    threshold DOUBLE NULL,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    terminated_ts DATETIME NULL,
+    PRIMARY KEY(agreement_id, owner_id)
+);
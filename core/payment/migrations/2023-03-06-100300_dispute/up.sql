@@ -0,0 +1,12 @@
+CREATE TABLE pay_dispute(
+    document_id VARCHAR(50) NOT NULL,
+    owner_id VARCHAR(50) NOT NULL,
+    document_type VARCHAR(50) NOT NULL,
+    peer_id VARCHAR(50) NOT NULL,
+    status VARCHAR(50) NOT NULL,
+    rejection TEXT NOT NULL,
+    response TEXT NULL,
+    created_ts DATETIME NOT NULL DEFAULT(STRFTIME('%Y-%m-%d %H:%M:%f', 'NOW')),
+    updated_ts DATETIME NOT NULL DEFAULT(STRFTIME('%Y-%m-%d %H:%M:%f', 'NOW')),
+    PRIMARY KEY(document_id, owner_id)
+);
@@ -0,0 +1,11 @@
+CREATE TABLE pay_allocation_refill(
+    allocation_id VARCHAR(50) NOT NULL,
+    owner_id VARCHAR(50) NOT NULL,
+    amount VARCHAR(32) NOT NULL,
+    threshold VARCHAR(32) NOT NULL,
+    max_total_amount VARCHAR(32) NULL,
+    refilled_amount VARCHAR(32) NOT NULL DEFAULT '0',
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    refilled_ts DATETIME NULL,
+    PRIMARY KEY(allocation_id, owner_id)
+);
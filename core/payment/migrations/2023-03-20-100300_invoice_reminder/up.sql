@@ -0,0 +1,7 @@
+CREATE TABLE pay_invoice_reminder(
+    invoice_id VARCHAR(50) NOT NULL,
+    owner_id VARCHAR(50) NOT NULL,
+    reminders INTEGER NOT NULL,
+    last_reminder_ts DATETIME NOT NULL,
+    PRIMARY KEY(invoice_id, owner_id)
+);
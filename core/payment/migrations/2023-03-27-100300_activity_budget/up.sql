@@ -0,0 +1,8 @@
+CREATE TABLE pay_activity_budget(
+    activity_id VARCHAR(50) NOT NULL,
+    owner_id VARCHAR(50) NOT NULL,
+    agreement_id VARCHAR(50) NOT NULL,
+    max_amount VARCHAR(32) NOT NULL,
+    terminated_ts DATETIME NULL,
+    PRIMARY KEY(activity_id, owner_id)
+);
@@ -1,3 +1,4 @@
+use futures::lock::Mutex;
 use serde::{Deserialize, Serialize};
 use std::env;
 use std::path::{Path, PathBuf};
@@ -6,6 +7,13 @@ use ya_core_model::driver::{driver_bus_id, AccountMode, Init};
 use ya_core_model::identity;
 use ya_service_bus::typed as bus;
 
+use crate::platform;
+
+lazy_static::lazy_static! {
+    /// Accounts file read on start, where runtime changes of accounts are saved.
+    static ref ACCOUNTS_FILE: Mutex<Option<PathBuf>> = Mutex::new(None);
+}
+
 fn accounts_path(data_dir: &Path) -> PathBuf {
     match env::var("ACCOUNT_LIST").ok() {
         Some(path) => PathBuf::from(path),
@@ -26,17 +34,23 @@ pub(crate) struct Account {
 }
 
 pub(crate) async fn init_account(account: Account) -> anyhow::Result<()> {
+    init_account_with_progress(account, None).await
+}
+
+/// Initializes the account, driver reports progress of the initialization under `progress_id`.
+pub(crate) async fn init_account_with_progress(
+    account: Account,
+    progress_id: Option<String>,
+) -> anyhow::Result<()> {
     log::debug!("Initializing payment account {:?}...", account);
     let mut mode = AccountMode::NONE;
     mode.set(AccountMode::SEND, account.send);
     mode.set(AccountMode::RECV, account.receive);
     bus::service(driver_bus_id(account.driver))
-        .call(Init::new(
-            account.address,
-            account.network,
-            account.token,
-            mode,
-        ))
+        .call(
+            Init::new(account.address, account.network, account.token, mode)
+                .with_progress_id(progress_id),
+        )
         .await??;
     log::debug!("Account initialized.");
     Ok(())
@@ -49,16 +63,54 @@ pub async fn init_accounts(data_dir: &Path) -> anyhow::Result<()> {
         "Initializing payment accounts from file {} ...",
         accounts_path.display()
     );
+    *ACCOUNTS_FILE.lock().await = Some(accounts_path.clone());
     let text = fs::read(accounts_path).await?;
     let accounts: Vec<Account> = serde_json::from_slice(&text)?;
 
-    for account in accounts {
+    // Accounts disabled at runtime are kept in the file, so they can be enabled again.
+    for account in accounts.into_iter().filter(|a| a.send || a.receive) {
         init_account(account).await?;
     }
     log::debug!("Payment accounts initialized.");
     Ok(())
 }
 
+/// Saves modes of the account in the accounts file, replacing its entry for the network.
+/// Entries without network match `default_network` of the driver.
+pub(crate) async fn save_account(account: Account, default_network: &str) -> anyhow::Result<()> {
+    let accounts_file = ACCOUNTS_FILE.lock().await;
+    let accounts_path = match accounts_file.as_ref() {
+        Some(path) => path,
+        None => {
+            log::warn!("Accounts file not initialized, account mode won't persist restart.");
+            return Ok(());
+        }
+    };
+    let mut accounts: Vec<Account> = match fs::read(accounts_path).await {
+        Ok(text) => serde_json::from_slice(&text)?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+        Err(e) => return Err(e.into()),
+    };
+
+    let network = account.network.as_deref().unwrap_or(default_network);
+    let same_network = |entry: &Account| {
+        entry.driver == account.driver
+            && entry.address == account.address
+            && entry.network.as_deref().unwrap_or(default_network) == network
+    };
+    match accounts.iter_mut().find(|entry| same_network(entry)) {
+        Some(entry) => {
+            entry.send = account.send;
+            entry.receive = account.receive;
+        }
+        None => accounts.push(account),
+    }
+
+    let text = serde_json::to_string(&accounts)?;
+    fs::write(accounts_path, text).await?;
+    Ok(())
+}
+
 /// Get default node ID from identity service and save it in `ACCOUNT_LIST` file as default payment account for every driver.
 /// If `ACCOUNT_LIST` file already exists, do nothing.
 pub async fn save_default_account(data_dir: &Path, drivers: Vec<String>) -> anyhow::Result<()> {
@@ -73,9 +125,9 @@ pub async fn save_default_account(data_dir: &Path, drivers: Vec<String>) -> anyh
         accounts_path.display()
     );
     let default_node_id = bus::service(identity::BUS_ID)
-        .call(identity::Get::ByDefault)
+        .call(identity::Get::ByRole(identity::IdentityRole::Payment))
         .await??
-        .ok_or_else(|| anyhow::anyhow!("Default identity not found"))?
+        .ok_or_else(|| anyhow::anyhow!("Payment identity not found"))?
         .node_id;
     let default_accounts: Vec<Account> = drivers
         .into_iter()
@@ -93,3 +145,42 @@ pub async fn save_default_account(data_dir: &Path, drivers: Vec<String>) -> anyh
     log::debug!("Default payment account saved successfully.");
     Ok(())
 }
+
+/// Moves accounts in `ACCOUNT_LIST` file to networks given by platform `aliases`.
+/// Returns number of migrated accounts, the file is left intact on `dry_run`.
+pub async fn migrate_accounts(
+    data_dir: &Path,
+    aliases: &[(String, String)],
+    dry_run: bool,
+) -> anyhow::Result<usize> {
+    let accounts_path = accounts_path(data_dir);
+    if !accounts_path.exists() {
+        return Ok(0);
+    }
+    let text = fs::read(&accounts_path).await?;
+    let mut accounts: Vec<Account> = serde_json::from_slice(&text)?;
+
+    let mut migrated = 0;
+    for account in accounts.iter_mut() {
+        let network = match &account.network {
+            Some(network) => network,
+            None => continue,
+        };
+        if let Some(network) = platform::resolve_network(aliases, &account.driver, network) {
+            log::debug!(
+                "Migrating payment account {} of driver {} to network {}.",
+                account.address,
+                account.driver,
+                network
+            );
+            account.network = Some(network);
+            migrated += 1;
+        }
+    }
+
+    if !dry_run && migrated > 0 {
+        let text = serde_json::to_string(&accounts)?;
+        fs::write(accounts_path, text).await?;
+    }
+    Ok(migrated)
+}
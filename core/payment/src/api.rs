@@ -6,7 +6,9 @@ use ya_service_api_web::scope::ExtendableScope;
 
 mod accounts;
 pub mod allocations;
+mod cost;
 mod debit_notes;
+mod disputes;
 mod invoices;
 mod payments;
 
@@ -14,7 +16,9 @@ pub fn api_scope(scope: Scope) -> Scope {
     scope
         .extend(accounts::register_endpoints)
         .extend(allocations::register_endpoints)
+        .extend(cost::register_endpoints)
         .extend(debit_notes::register_endpoints)
+        .extend(disputes::register_endpoints)
         .extend(invoices::register_endpoints)
         .extend(payments::register_endpoints)
 }
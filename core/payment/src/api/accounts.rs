@@ -1,9 +1,14 @@
 // Extrnal crates
+use actix_web::web::{Json, Query};
 use actix_web::{HttpResponse, Scope};
+use bigdecimal::BigDecimal;
+use serde::Deserialize;
 
 // Workspace uses
 use ya_client_model::payment::*;
-use ya_core_model::payment::local::{GetAccounts, BUS_ID as LOCAL_SERVICE};
+use ya_core_model::payment::local::{
+    CompareFees, DriverName, GetAccounts, GetReadiness, SetAccountMode, BUS_ID as LOCAL_SERVICE,
+};
 use ya_service_api_web::middleware::Identity;
 use ya_service_bus::{typed as bus, RpcEndpoint};
 
@@ -17,6 +22,37 @@ pub fn register_endpoints(scope: Scope) -> Scope {
     scope
         .service(get_provider_accounts)
         .service(get_requestor_accounts)
+        .service(get_account_readiness)
+        .service(get_fee_comparison)
+        .service(get_accounts)
+        .service(set_account_mode)
+}
+
+#[derive(Deserialize)]
+struct ReadinessParams {
+    driver: Option<String>,
+    network: Option<String>,
+    amount: Option<BigDecimal>,
+}
+
+#[derive(Deserialize)]
+struct FeeComparisonParams {
+    #[serde(default = "default_payments")]
+    payments: u32,
+}
+
+fn default_payments() -> u32 {
+    1
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AccountModeBody {
+    driver: Option<String>,
+    network: Option<String>,
+    token: Option<String>,
+    send: Option<bool>,
+    receive: Option<bool>,
 }
 
 #[actix_web::get("/providerAccounts")]
@@ -49,3 +85,70 @@ async fn get_requestor_accounts(db: Data<DbExecutor>, id: Identity) -> HttpRespo
         .collect();
     response::ok(recv_accounts)
 }
+
+#[actix_web::get("/accountReadiness")]
+async fn get_account_readiness(query: Query<ReadinessParams>, id: Identity) -> HttpResponse {
+    let query = query.into_inner();
+    let msg = GetReadiness {
+        address: id.identity.to_string(),
+        driver: query
+            .driver
+            .unwrap_or_else(|| DriverName::Erc20.to_string()),
+        network: query.network,
+        amount: query.amount,
+    };
+    match bus::service(LOCAL_SERVICE).send(msg).await {
+        Ok(Ok(readiness)) => response::ok(readiness),
+        Ok(Err(e)) => response::server_error(&e),
+        Err(e) => response::server_error(&e),
+    }
+}
+
+/// Cost of sending `payments` payments on each platform the identity sends from,
+/// with the cheapest one recommended.
+#[actix_web::get("/feeComparison")]
+async fn get_fee_comparison(query: Query<FeeComparisonParams>, id: Identity) -> HttpResponse {
+    let msg = CompareFees {
+        address: id.identity.to_string(),
+        payments: query.into_inner().payments,
+    };
+    match bus::service(LOCAL_SERVICE).send(msg).await {
+        Ok(Ok(comparison)) => response::ok(comparison),
+        Ok(Err(e)) => response::server_error(&e),
+        Err(e) => response::server_error(&e),
+    }
+}
+
+/// All accounts of the identity, with their modes on each network.
+#[actix_web::get("/accounts")]
+async fn get_accounts(id: Identity) -> HttpResponse {
+    let node_id = id.identity.to_string();
+    match bus::service(LOCAL_SERVICE).send(GetAccounts {}).await {
+        Ok(Ok(accounts)) => response::ok(
+            accounts
+                .into_iter()
+                .filter(|account| account.address == node_id)
+                .collect::<Vec<Account>>(),
+        ),
+        Ok(Err(e)) => response::server_error(&e),
+        Err(e) => response::server_error(&e),
+    }
+}
+
+#[actix_web::put("/accountMode")]
+async fn set_account_mode(body: Json<AccountModeBody>, id: Identity) -> HttpResponse {
+    let body = body.into_inner();
+    let msg = SetAccountMode {
+        address: id.identity.to_string(),
+        driver: body.driver.unwrap_or_else(|| DriverName::Erc20.to_string()),
+        network: body.network,
+        token: body.token,
+        send: body.send,
+        receive: body.receive,
+    };
+    match bus::service(LOCAL_SERVICE).send(msg).await {
+        Ok(Ok(account)) => response::ok(account),
+        Ok(Err(e)) => response::bad_request(&e),
+        Err(e) => response::server_error(&e),
+    }
+}
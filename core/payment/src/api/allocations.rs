@@ -1,4 +1,3 @@
-use std::convert::TryInto;
 use std::time::Duration;
 // External crates
 use actix_web::web::{delete, get, post, put, Data, Json, Path, Query};
@@ -22,6 +21,7 @@ use ya_service_bus::{typed as bus, RpcEndpoint};
 use crate::accounts::{init_account, Account};
 use crate::dao::*;
 use crate::error::{DbError, Error};
+use crate::platform;
 use crate::utils::response;
 use crate::DEFAULT_PAYMENT_PLATFORM;
 
@@ -46,10 +46,12 @@ async fn create_allocation(
     // TODO: Handle deposits & timeouts
     let allocation = body.into_inner();
     let node_id = id.identity;
-    let payment_platform = allocation
-        .payment_platform
-        .clone()
-        .unwrap_or_else(|| DEFAULT_PAYMENT_PLATFORM.to_string());
+    let payment_platform = platform::resolve(
+        allocation
+            .payment_platform
+            .as_deref()
+            .unwrap_or(DEFAULT_PAYMENT_PLATFORM),
+    );
     let address = allocation
         .address
         .clone()
@@ -57,18 +59,17 @@ async fn create_allocation(
 
     // If the request contains information about the payment platform, initialize the account
     // by setting the `send` field to `true`, as it is implied by the intent behing allocation of funds.
-    if let Some(platform) = &allocation.payment_platform {
+    if allocation.payment_platform.is_some() {
         // payment_platform is of the form driver-network-token
         // eg. erc20-rinkeby-tglm
-        let [driver, network, _token]: [&str; 3] =
-            match platform.split('-').collect::<Vec<_>>().try_into() {
-                Ok(arr) => arr,
-                Err(_e) => {
-                    return response::bad_request(
-                        &"paymentPlatform must be of the form driver-network-token",
-                    )
-                }
-            };
+        let (driver, network, _token) = match platform::split(&payment_platform) {
+            Some(parts) => parts,
+            None => {
+                return response::bad_request(
+                    &"paymentPlatform must be of the form driver-network-token",
+                )
+            }
+        };
 
         let acc = Account {
             driver: driver.to_owned(),
@@ -0,0 +1,62 @@
+// Extrnal crates
+use actix_web::web::Json;
+use actix_web::{HttpResponse, Scope};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+
+// Workspace uses
+use ya_agreement_utils::agreement::{expand, TypedPointer};
+use ya_client_model::market::Role;
+use ya_service_api_web::middleware::Identity;
+
+// Local uses
+use crate::cost;
+use crate::utils::*;
+
+pub fn register_endpoints(scope: Scope) -> Scope {
+    scope.service(get_cost_preview)
+}
+
+/// Either Agreement to preview, or properties of Offer from a Proposal.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CostPreviewBody {
+    agreement_id: Option<String>,
+    properties: Option<Value>,
+    payment_platform: Option<String>,
+    /// Estimated usage per counter, e.g. `golem.usage.duration_sec`.
+    #[serde(default)]
+    usage: HashMap<String, f64>,
+}
+
+#[actix_web::post("/costPreview")]
+async fn get_cost_preview(body: Json<CostPreviewBody>, id: Identity) -> HttpResponse {
+    let body = body.into_inner();
+    let (properties, payment_platform) = match (body.agreement_id, body.properties) {
+        (Some(agreement_id), None) => {
+            let agreement = match get_agreement(agreement_id.clone(), Role::Requestor).await {
+                Ok(Some(agreement)) => agreement,
+                Ok(None) => return response::not_found(),
+                Err(e) => return response::server_error(&e),
+            };
+            if agreement.requestor_id() != &id.identity {
+                return response::unauthorized();
+            }
+            let payment_platform = expand(agreement.demand.properties)
+                .pointer("/golem/com/payment/chosen-platform")
+                .as_typed(Value::as_str)
+                .ok()
+                .map(ToOwned::to_owned)
+                .or(body.payment_platform);
+            (agreement.offer.properties, payment_platform)
+        }
+        (None, Some(properties)) => (properties, body.payment_platform),
+        _ => return response::bad_request(&"Expected either agreementId or properties"),
+    };
+
+    match cost::preview(properties, &body.usage, payment_platform) {
+        Ok(preview) => response::ok(preview),
+        Err(e) => response::bad_request(&e),
+    }
+}
@@ -1,4 +1,3 @@
-use std::borrow::Cow;
 // Extrnal crates
 use actix_web::web::{get, post, Data, Json, Path, Query};
 use actix_web::{HttpResponse, Scope};
@@ -8,9 +7,13 @@ use std::time::Instant;
 // Workspace uses
 use metrics::{counter, timing};
 use ya_client_model::payment::*;
+use ya_client_model::NodeId;
+use ya_core_model::activity;
+use ya_core_model::journal;
 use ya_core_model::payment::local::{SchedulePayment, BUS_ID as LOCAL_SERVICE};
 use ya_core_model::payment::public::{
-    AcceptDebitNote, AcceptRejectError, SendDebitNote, SendError, BUS_ID as PUBLIC_SERVICE,
+    AcceptDebitNote, AcceptRejectError, RejectDebitNote, SendDebitNote, SendDebitNoteUsage,
+    SendError, BUS_ID as PUBLIC_SERVICE,
 };
 use ya_core_model::payment::RpcMessageError;
 use ya_net::RemoteEndpoint;
@@ -36,6 +39,10 @@ pub fn register_endpoints(scope: Scope) -> Scope {
             get().to(get_debit_note_payments),
         )
         .route("/debitNoteEvents", get().to(get_debit_note_events))
+        .route(
+            "/debitNoteEvents/stream",
+            get().to(stream_debit_note_events),
+        )
         // Provider
         .route("/debitNotes", post().to(issue_debit_note))
         .route(
@@ -103,26 +110,7 @@ async fn get_debit_note_events(
     req: actix_web::HttpRequest,
     id: Identity,
 ) -> HttpResponse {
-    let requestor_events: Vec<Cow<'static, str>> = req
-        .headers()
-        .get("X-Requestor-Events")
-        .and_then(|v| v.to_str().ok())
-        .map(|v| v.split(',').map(|s| Cow::Owned(s.to_owned())).collect())
-        .unwrap_or_else(|| vec!["RECEIVED".into(), "CANCELLED".into()]);
-
-    let provider_events: Vec<Cow<'static, str>> = req
-        .headers()
-        .get("X-Provider-Events")
-        .and_then(|v| v.to_str().ok())
-        .map(|v| v.split(',').map(|s| Cow::Owned(s.to_owned())).collect())
-        .unwrap_or_else(|| {
-            vec![
-                "ACCEPTED".into(),
-                "REJECTED".into(),
-                "SETTLED".into(),
-                "CANCELLED".into(),
-            ]
-        });
+    let (requestor_events, provider_events) = event_types(&req);
     let node_id = id.identity;
     let timeout_secs = query.timeout.unwrap_or(params::DEFAULT_EVENT_TIMEOUT);
     let after_timestamp = query.after_timestamp.map(|d| d.naive_utc());
@@ -148,6 +136,40 @@ async fn get_debit_note_events(
     }
 }
 
+async fn stream_debit_note_events(
+    db: Data<DbExecutor>,
+    query: Query<params::EventParams>,
+    req: actix_web::HttpRequest,
+    id: Identity,
+) -> HttpResponse {
+    let (requestor_events, provider_events) = event_types(&req);
+    let node_id = id.identity;
+    let after_timestamp = query.after_timestamp.map(|d| d.naive_utc());
+    let app_session_id = query.app_session_id.clone();
+
+    let db = db.get_ref().clone();
+    let getter = move |after_timestamp| {
+        let db = db.clone();
+        let app_session_id = app_session_id.clone();
+        let requestor_events = requestor_events.clone();
+        let provider_events = provider_events.clone();
+        async move {
+            let dao: DebitNoteEventDao = db.as_dao();
+            dao.get_for_node_id(
+                node_id,
+                after_timestamp,
+                None,
+                app_session_id,
+                requestor_events,
+                provider_events,
+            )
+            .await
+        }
+    };
+
+    stream_events(getter, after_timestamp)
+}
+
 // Provider
 
 async fn issue_debit_note(
@@ -238,13 +260,16 @@ async fn send_debit_note(
             );
 
             let debit_note_id = debit_note.debit_note_id.clone();
+            let activity_id = debit_note.activity_id.clone();
+            let recipient_id = debit_note.recipient_id;
 
             ya_net::from(node_id)
-                .to(debit_note.recipient_id)
+                .to(recipient_id)
                 .service(PUBLIC_SERVICE)
                 .call(SendDebitNote(debit_note))
                 .await??;
             dao.mark_received(debit_note_id.clone(), node_id).await?;
+            send_signed_usage(&dao, debit_note_id, activity_id, node_id, recipient_id).await;
             Ok(())
         }
         .timeout(Some(timeout))
@@ -274,6 +299,69 @@ async fn send_debit_note(
     result
 }
 
+/// Attaches the last usage signed by the ExeUnit to the Debit Note already sent.
+/// Debit Notes without signed usage are still valid, so failures are only logged.
+async fn send_signed_usage(
+    dao: &DebitNoteDao<'_>,
+    debit_note_id: String,
+    activity_id: String,
+    node_id: NodeId,
+    recipient_id: NodeId,
+) {
+    let signed_usage = match bus::service(activity::local::BUS_ID)
+        .send(activity::local::GetSignedUsage {
+            activity_id: activity_id.clone(),
+        })
+        .await
+    {
+        Ok(Ok(Some(signed_usage))) => signed_usage,
+        Ok(Ok(None)) => return,
+        Ok(Err(e)) => {
+            log::debug!("No signed usage of Activity [{}]: {}", activity_id, e);
+            return;
+        }
+        Err(e) => {
+            log::debug!("No signed usage of Activity [{}]: {}", activity_id, e);
+            return;
+        }
+    };
+
+    if let Err(e) = dao
+        .set_signed_usage(debit_note_id.clone(), node_id, signed_usage.clone())
+        .await
+    {
+        log::warn!(
+            "Failed to store signed usage of DebitNote [{}]: {}",
+            debit_note_id,
+            e
+        );
+    }
+
+    let msg = SendDebitNoteUsage {
+        debit_note_id: debit_note_id.clone(),
+        recipient_id,
+        signed_usage,
+    };
+    match ya_net::from(node_id)
+        .to(recipient_id)
+        .service(PUBLIC_SERVICE)
+        .call(msg)
+        .await
+    {
+        Ok(Ok(_)) => log::debug!("Signed usage of DebitNote [{}] sent.", debit_note_id),
+        Ok(Err(e)) => log::debug!(
+            "Failed to send signed usage of DebitNote [{}]: {}",
+            debit_note_id,
+            e
+        ),
+        Err(e) => log::debug!(
+            "Failed to send signed usage of DebitNote [{}]: {}",
+            debit_note_id,
+            e
+        ),
+    }
+}
+
 async fn cancel_debit_note(
     db: Data<DbExecutor>,
     path: Path<params::DebitNoteId>,
@@ -370,6 +458,12 @@ async fn accept_debit_note(
         return response::bad_request(&msg);
     }
 
+    let agreement_id = activity.agreement_id.clone();
+    let budget_dao: AgreementBudgetDao = db.as_dao();
+    let budget_allocation_id = allocation_id.clone();
+    let watchdog =
+        crate::budget::check_agreement(db.get_ref().clone(), agreement_id.clone(), node_id);
+
     let timeout = query.timeout.unwrap_or(params::DEFAULT_ACK_TIMEOUT);
     let result = async move {
         let issuer_id = debit_note.issuer_id;
@@ -406,6 +500,25 @@ async fn accept_debit_note(
                     activity_id
                 );
                 counter!("payment.debit_notes.requestor.accepted", 1);
+
+                journal::Event::new(journal::Category::Payment, "debit-note-accepted")
+                    .subject(&path.debit_note_id)
+                    .node_id(node_id)
+                    .details(serde_json::json!({ "activityId": activity_id }))
+                    .record()
+                    .await;
+
+                if let Err(e) = budget_dao
+                    .set_allocation_if_missing(agreement_id.clone(), node_id, budget_allocation_id)
+                    .await
+                {
+                    log::warn!(
+                        "Failed to set Allocation of Agreement [{}] budget: {}",
+                        agreement_id,
+                        e
+                    );
+                }
+                tokio::task::spawn_local(watchdog);
                 response::ok(Null)
             }
             Ok(Err(Error::Rpc(RpcMessageError::AcceptReject(AcceptRejectError::BadRequest(
@@ -430,6 +543,69 @@ async fn reject_debit_note(
     path: Path<params::DebitNoteId>,
     query: Query<params::Timeout>,
     body: Json<Rejection>,
+    id: Identity,
 ) -> HttpResponse {
-    response::not_implemented() // TODO
+    let debit_note_id = path.debit_note_id.clone();
+    let node_id = id.identity;
+    let rejection = body.into_inner();
+
+    log::debug!("Requested reject DebitNote [{}]", debit_note_id);
+
+    let dao: DebitNoteDao = db.as_dao();
+    let debit_note = match dao.get(debit_note_id.clone(), node_id).await {
+        Ok(Some(debit_note)) => debit_note,
+        Ok(None) => return response::not_found(),
+        Err(e) => return response::server_error(&e),
+    };
+
+    match debit_note.status {
+        DocumentStatus::Received => (),
+        DocumentStatus::Rejected => (),
+        DocumentStatus::Failed => (),
+        DocumentStatus::Accepted => return response::bad_request(&"Debit note already accepted"),
+        DocumentStatus::Settled => return response::bad_request(&"Debit note already settled"),
+        DocumentStatus::Issued => return response::server_error(&"Illegal status: issued"),
+        DocumentStatus::Cancelled => return response::bad_request(&"Debit note cancelled"),
+    }
+
+    let timeout = query.timeout.unwrap_or(params::DEFAULT_ACK_TIMEOUT);
+    let issuer_id = debit_note.issuer_id;
+    let reject_msg = RejectDebitNote {
+        debit_note_id: debit_note_id.clone(),
+        rejection: rejection.clone(),
+        issuer_id,
+    };
+    match async move {
+        ya_net::from(node_id)
+            .to(issuer_id)
+            .service(PUBLIC_SERVICE)
+            .call(reject_msg)
+            .await??;
+        dao.reject(debit_note_id.clone(), node_id, issuer_id, rejection)
+            .await?;
+        Ok(())
+    }
+    .timeout(Some(timeout))
+    .await
+    {
+        Ok(Ok(_)) => {
+            log::info!(
+                "DebitNote [{}] for Activity [{}] rejected.",
+                path.debit_note_id,
+                debit_note.activity_id
+            );
+            journal::Event::new(journal::Category::Payment, "debit-note-rejected")
+                .subject(&path.debit_note_id)
+                .node_id(node_id)
+                .details(serde_json::json!({ "activityId": debit_note.activity_id }))
+                .record()
+                .await;
+            response::ok(Null)
+        }
+        Ok(Err(Error::Rpc(RpcMessageError::AcceptReject(AcceptRejectError::BadRequest(e))))) => {
+            response::bad_request(&e)
+        }
+        Ok(Err(e)) => response::server_error(&e),
+        Err(_) => response::timeout(&"Timeout rejecting Debit Note on remote Node."),
+    }
 }
@@ -0,0 +1,216 @@
+// External crates
+use actix_web::web::{get, post, Data, Json, Path, Query};
+use actix_web::{HttpResponse, Scope};
+use serde::Deserialize;
+use serde_json::value::Value::Null;
+
+// Workspace uses
+use ya_client_model::payment::*;
+use ya_client_model::NodeId;
+use ya_core_model::journal;
+use ya_core_model::payment::local::{DisputeResponse, DisputeStatus, DocumentType};
+use ya_core_model::payment::public::{
+    AcceptRejectError, RespondToDispute, BUS_ID as PUBLIC_SERVICE,
+};
+use ya_core_model::payment::RpcMessageError;
+use ya_net::RemoteEndpoint;
+use ya_persistence::executor::DbExecutor;
+use ya_service_api_web::middleware::Identity;
+use ya_service_bus::timeout::IntoTimeoutFuture;
+
+// Local uses
+use crate::dao::*;
+use crate::error::{DbResult, Error};
+use crate::utils::*;
+
+pub fn register_endpoints(scope: Scope) -> Scope {
+    scope
+        // Shared
+        .route("/disputes", get().to(get_disputes))
+        .route(
+            "/invoices/{invoice_id}/dispute",
+            get().to(get_invoice_dispute),
+        )
+        .route(
+            "/debitNotes/{debit_note_id}/dispute",
+            get().to(get_debit_note_dispute),
+        )
+        // Provider
+        .route(
+            "/invoices/{invoice_id}/dispute/response",
+            post().to(respond_to_invoice_dispute),
+        )
+        .route(
+            "/debitNotes/{debit_note_id}/dispute/response",
+            post().to(respond_to_debit_note_dispute),
+        )
+}
+
+#[derive(Deserialize)]
+struct DisputeParams {
+    status: Option<DisputeStatus>,
+}
+
+async fn get_disputes(
+    db: Data<DbExecutor>,
+    query: Query<DisputeParams>,
+    id: Identity,
+) -> HttpResponse {
+    let dao: DisputeDao = db.as_dao();
+    match dao.list(id.identity, query.status).await {
+        Ok(disputes) => response::ok(disputes),
+        Err(e) => response::server_error(&e),
+    }
+}
+
+async fn get_invoice_dispute(
+    db: Data<DbExecutor>,
+    path: Path<params::InvoiceId>,
+    id: Identity,
+) -> HttpResponse {
+    get_dispute(db, path.invoice_id.clone(), id.identity).await
+}
+
+async fn get_debit_note_dispute(
+    db: Data<DbExecutor>,
+    path: Path<params::DebitNoteId>,
+    id: Identity,
+) -> HttpResponse {
+    get_dispute(db, path.debit_note_id.clone(), id.identity).await
+}
+
+async fn get_dispute(db: Data<DbExecutor>, document_id: String, node_id: NodeId) -> HttpResponse {
+    let dao: DisputeDao = db.as_dao();
+    match dao.get(document_id, node_id).await {
+        Ok(Some(dispute)) => response::ok(dispute),
+        Ok(None) => response::not_found(),
+        Err(e) => response::server_error(&e),
+    }
+}
+
+async fn respond_to_invoice_dispute(
+    db: Data<DbExecutor>,
+    path: Path<params::InvoiceId>,
+    query: Query<params::Timeout>,
+    body: Json<DisputeResponse>,
+    id: Identity,
+) -> HttpResponse {
+    let issuer_id = db
+        .as_dao::<InvoiceDao>()
+        .get(path.invoice_id.clone(), id.identity)
+        .await
+        .map(|invoice| invoice.map(|invoice| invoice.issuer_id));
+    respond_to_dispute(
+        db,
+        path.invoice_id.clone(),
+        DocumentType::Invoice,
+        issuer_id,
+        query.timeout,
+        body.into_inner(),
+        id.identity,
+    )
+    .await
+}
+
+async fn respond_to_debit_note_dispute(
+    db: Data<DbExecutor>,
+    path: Path<params::DebitNoteId>,
+    query: Query<params::Timeout>,
+    body: Json<DisputeResponse>,
+    id: Identity,
+) -> HttpResponse {
+    let issuer_id = db
+        .as_dao::<DebitNoteDao>()
+        .get(path.debit_note_id.clone(), id.identity)
+        .await
+        .map(|debit_note| debit_note.map(|debit_note| debit_note.issuer_id));
+    respond_to_dispute(
+        db,
+        path.debit_note_id.clone(),
+        DocumentType::DebitNote,
+        issuer_id,
+        query.timeout,
+        body.into_inner(),
+        id.identity,
+    )
+    .await
+}
+
+async fn respond_to_dispute(
+    db: Data<DbExecutor>,
+    document_id: String,
+    document_type: DocumentType,
+    issuer_id: DbResult<Option<NodeId>>,
+    timeout: Option<f64>,
+    dispute_response: DisputeResponse,
+    node_id: NodeId,
+) -> HttpResponse {
+    log::debug!(
+        "Requested respond to dispute of {} [{}]",
+        document_type,
+        document_id
+    );
+
+    match issuer_id {
+        Ok(Some(issuer_id)) if issuer_id == node_id => (),
+        Ok(Some(_)) => return response::bad_request(&"Only issuer can respond to dispute"),
+        Ok(None) => return response::not_found(),
+        Err(e) => return response::server_error(&e),
+    }
+
+    let dao: DisputeDao = db.as_dao();
+    let dispute = match dao.get(document_id.clone(), node_id).await {
+        Ok(Some(dispute)) => dispute,
+        Ok(None) => return response::not_found(),
+        Err(e) => return response::server_error(&e),
+    };
+    if dispute.status == DisputeStatus::Resolved {
+        return response::bad_request(&"Dispute already resolved");
+    }
+
+    let timeout = timeout.unwrap_or(params::DEFAULT_ACK_TIMEOUT);
+    let recipient_id = dispute.peer_id;
+    let msg = RespondToDispute {
+        document_id: document_id.clone(),
+        document_type,
+        recipient_id,
+        response: dispute_response.clone(),
+    };
+    match async move {
+        ya_net::from(node_id)
+            .to(recipient_id)
+            .service(PUBLIC_SERVICE)
+            .call(msg)
+            .await??;
+        dao.respond(document_id.clone(), node_id, dispute_response)
+            .await?;
+        Ok(())
+    }
+    .timeout(Some(timeout))
+    .await
+    {
+        Ok(Ok(_)) => {
+            log::info!(
+                "Responded to dispute of {} [{}] with [{}].",
+                document_type,
+                dispute.document_id,
+                recipient_id
+            );
+            journal::Event::new(journal::Category::Payment, "dispute-responded")
+                .subject(&dispute.document_id)
+                .node_id(node_id)
+                .details(serde_json::json!({
+                    "documentType": document_type,
+                    "recipientId": recipient_id,
+                }))
+                .record()
+                .await;
+            response::ok(Null)
+        }
+        Ok(Err(Error::Rpc(RpcMessageError::AcceptReject(AcceptRejectError::BadRequest(e))))) => {
+            response::bad_request(&e)
+        }
+        Ok(Err(e)) => response::server_error(&e),
+        Err(_) => response::timeout(&"Timeout responding to dispute on remote Node."),
+    }
+}
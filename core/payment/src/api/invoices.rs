@@ -2,16 +2,16 @@
 use actix_web::web::{get, post, Data, Json, Path, Query};
 use actix_web::{HttpResponse, Scope};
 use serde_json::value::Value::Null;
-use std::borrow::Cow;
 use std::time::Instant;
 
 // Workspace uses
 use metrics::{counter, timing};
 use ya_client_model::payment::*;
+use ya_core_model::journal;
 use ya_core_model::payment::local::{SchedulePayment, BUS_ID as LOCAL_SERVICE};
 use ya_core_model::payment::public::{
-    AcceptInvoice, AcceptRejectError, CancelError, CancelInvoice, SendError, SendInvoice,
-    BUS_ID as PUBLIC_SERVICE,
+    AcceptInvoice, AcceptRejectError, CancelError, CancelInvoice, RejectInvoice, SendError,
+    SendInvoice, BUS_ID as PUBLIC_SERVICE,
 };
 use ya_core_model::payment::RpcMessageError;
 use ya_net::RemoteEndpoint;
@@ -37,10 +37,13 @@ pub fn register_endpoints(scope: Scope) -> Scope {
             get().to(get_invoice_payments),
         )
         .route("/invoiceEvents", get().to(get_invoice_events))
+        .route("/invoiceEvents/stream", get().to(stream_invoice_events))
         // Provider
         .route("/invoices", post().to(issue_invoice))
         .route("/invoices/{invoice_id}/send", post().to(send_invoice))
         .route("/invoices/{invoice_id}/cancel", post().to(cancel_invoice))
+        .route("/invoiceAging", get().to(get_invoice_aging))
+        .route("/deferredOperations", get().to(get_deferred_operations))
         // Requestor
         .route("/invoices/{invoice_id}/accept", post().to(accept_invoice))
         .route("/invoices/{invoice_id}/reject", post().to(reject_invoice))
@@ -89,26 +92,7 @@ async fn get_invoice_events(
     req: actix_web::HttpRequest,
     id: Identity,
 ) -> HttpResponse {
-    let requestor_events: Vec<Cow<'static, str>> = req
-        .headers()
-        .get("X-Requestor-Events")
-        .and_then(|v| v.to_str().ok())
-        .map(|v| v.split(',').map(|s| Cow::Owned(s.to_owned())).collect())
-        .unwrap_or_else(|| vec!["RECEIVED".into(), "CANCELLED".into()]);
-
-    let provider_events: Vec<Cow<'static, str>> = req
-        .headers()
-        .get("X-Provider-Events")
-        .and_then(|v| v.to_str().ok())
-        .map(|v| v.split(',').map(|s| Cow::Owned(s.to_owned())).collect())
-        .unwrap_or_else(|| {
-            vec![
-                "ACCEPTED".into(),
-                "REJECTED".into(),
-                "SETTLED".into(),
-                "CANCELLED".into(),
-            ]
-        });
+    let (requestor_events, provider_events) = event_types(&req);
     let node_id = id.identity;
     let timeout_secs = query.timeout.unwrap_or(params::DEFAULT_EVENT_TIMEOUT);
     let after_timestamp = query.after_timestamp.map(|d| d.naive_utc());
@@ -134,6 +118,40 @@ async fn get_invoice_events(
     }
 }
 
+async fn stream_invoice_events(
+    db: Data<DbExecutor>,
+    query: Query<params::EventParams>,
+    req: actix_web::HttpRequest,
+    id: Identity,
+) -> HttpResponse {
+    let (requestor_events, provider_events) = event_types(&req);
+    let node_id = id.identity;
+    let after_timestamp = query.after_timestamp.map(|d| d.naive_utc());
+    let app_session_id = query.app_session_id.clone();
+
+    let db = db.get_ref().clone();
+    let getter = move |after_timestamp| {
+        let db = db.clone();
+        let app_session_id = app_session_id.clone();
+        let requestor_events = requestor_events.clone();
+        let provider_events = provider_events.clone();
+        async move {
+            let dao: InvoiceEventDao = db.as_dao();
+            dao.get_for_node_id(
+                node_id,
+                after_timestamp,
+                None,
+                app_session_id,
+                requestor_events,
+                provider_events,
+            )
+            .await
+        }
+    };
+
+    stream_events(getter, after_timestamp)
+}
+
 // Provider
 
 async fn issue_invoice(db: Data<DbExecutor>, body: Json<NewInvoice>, id: Identity) -> HttpResponse {
@@ -189,6 +207,13 @@ async fn issue_invoice(db: Data<DbExecutor>, body: Json<NewInvoice>, id: Identit
 
         log::info!("Invoice [{invoice_id}] for Agreement [{agreement_id}] issued.");
         counter!("payment.invoices.provider.issued", 1);
+
+        journal::Event::new(journal::Category::Payment, "invoice-issued")
+            .subject(&invoice_id)
+            .node_id(node_id)
+            .details(serde_json::json!({ "agreementId": agreement_id }))
+            .record()
+            .await;
         Ok(invoice)
     }
     .await
@@ -253,13 +278,29 @@ async fn send_invoice(
                     "Invoice [{invoice_id}] for Agreement [{agreement_id}] sent to [{recipient_id}]."
                 );
                 counter!("payment.invoices.provider.sent", 1);
+
+                journal::Event::new(journal::Category::Payment, "invoice-sent")
+                    .subject(&invoice_id)
+                    .node_id(node_id)
+                    .details(serde_json::json!({
+                        "agreementId": agreement_id,
+                        "recipientId": recipient_id,
+                    }))
+                    .record()
+                    .await;
                 response::ok(Null)
             }
             Ok(Err(Error::Rpc(RpcMessageError::Send(SendError::BadRequest(e))))) => {
                 response::bad_request(&e)
             }
-            Ok(Err(e)) => response::server_error(&e),
-            Err(_) => response::timeout(&"Timeout sending Invoice to remote Node."),
+            Ok(Err(e)) => {
+                crate::deferred::defer(&invoice_id, e.to_string()).await;
+                response::server_error(&e)
+            }
+            Err(_) => {
+                crate::deferred::defer(&invoice_id, "Timeout".to_string()).await;
+                response::timeout(&"Timeout sending Invoice to remote Node.")
+            }
         }
     }
     .await;
@@ -327,6 +368,13 @@ async fn cancel_invoice(
             Ok(Ok(_)) => {
                 counter!("payment.invoices.provider.cancelled", 1);
                 log::info!("Invoice [{invoice_id}] for Agreement [{agreement_id}] cancelled.");
+
+                journal::Event::new(journal::Category::Payment, "invoice-cancelled")
+                    .subject(&invoice_id)
+                    .node_id(node_id)
+                    .details(serde_json::json!({ "agreementId": agreement_id }))
+                    .record()
+                    .await;
                 response::ok(Null)
             }
             Ok(Err(Error::Rpc(RpcMessageError::Cancel(CancelError::Conflict)))) => {
@@ -346,6 +394,20 @@ async fn cancel_invoice(
     result
 }
 
+async fn get_invoice_aging(db: Data<DbExecutor>, id: Identity) -> HttpResponse {
+    match crate::dunning::aging_report(&db, id.identity).await {
+        Ok(report) => response::ok(report),
+        Err(e) => response::server_error(&e),
+    }
+}
+
+async fn get_deferred_operations(db: Data<DbExecutor>, id: Identity) -> HttpResponse {
+    match crate::deferred::status(&db, id.identity).await {
+        Ok(operations) => response::ok(operations),
+        Err(e) => response::server_error(&e),
+    }
+}
+
 // Requestor
 
 async fn accept_invoice(
@@ -481,6 +543,13 @@ async fn accept_invoice(
                     path.invoice_id,
                     agreement_id
                 );
+
+                journal::Event::new(journal::Category::Payment, "invoice-accepted")
+                    .subject(&path.invoice_id)
+                    .node_id(node_id)
+                    .details(serde_json::json!({ "agreementId": agreement_id }))
+                    .record()
+                    .await;
                 response::ok(Null)
             }
             Ok(Err(Error::Rpc(RpcMessageError::AcceptReject(AcceptRejectError::BadRequest(
@@ -505,6 +574,69 @@ async fn reject_invoice(
     path: Path<params::InvoiceId>,
     query: Query<params::Timeout>,
     body: Json<Rejection>,
+    id: Identity,
 ) -> HttpResponse {
-    response::not_implemented() // TODO
+    let invoice_id = path.invoice_id.clone();
+    let node_id = id.identity;
+    let rejection = body.into_inner();
+
+    log::debug!("Requested reject Invoice [{}]", invoice_id);
+
+    let dao: InvoiceDao = db.as_dao();
+    let invoice = match dao.get(invoice_id.clone(), node_id).await {
+        Ok(Some(invoice)) => invoice,
+        Ok(None) => return response::not_found(),
+        Err(e) => return response::server_error(&e),
+    };
+
+    match invoice.status {
+        DocumentStatus::Received => (),
+        DocumentStatus::Rejected => (),
+        DocumentStatus::Failed => (),
+        DocumentStatus::Accepted => return response::bad_request(&"Invoice already accepted"),
+        DocumentStatus::Settled => return response::bad_request(&"Invoice already settled"),
+        DocumentStatus::Issued => return response::server_error(&"Illegal status: issued"),
+        DocumentStatus::Cancelled => return response::bad_request(&"Invoice cancelled"),
+    }
+
+    let timeout = query.timeout.unwrap_or(params::DEFAULT_ACK_TIMEOUT);
+    let issuer_id = invoice.issuer_id;
+    let reject_msg = RejectInvoice {
+        invoice_id: invoice_id.clone(),
+        rejection: rejection.clone(),
+        issuer_id,
+    };
+    match async move {
+        ya_net::from(node_id)
+            .to(issuer_id)
+            .service(PUBLIC_SERVICE)
+            .call(reject_msg)
+            .await??;
+        dao.reject(invoice_id.clone(), node_id, issuer_id, rejection)
+            .await?;
+        Ok(())
+    }
+    .timeout(Some(timeout))
+    .await
+    {
+        Ok(Ok(_)) => {
+            log::info!(
+                "Invoice [{}] for Agreement [{}] rejected.",
+                path.invoice_id,
+                invoice.agreement_id
+            );
+            journal::Event::new(journal::Category::Payment, "invoice-rejected")
+                .subject(&path.invoice_id)
+                .node_id(node_id)
+                .details(serde_json::json!({ "agreementId": invoice.agreement_id }))
+                .record()
+                .await;
+            response::ok(Null)
+        }
+        Ok(Err(Error::Rpc(RpcMessageError::AcceptReject(AcceptRejectError::BadRequest(e))))) => {
+            response::bad_request(&e)
+        }
+        Ok(Err(e)) => response::server_error(&e),
+        Err(_) => response::timeout(&"Timeout rejecting Invoice on remote Node."),
+    }
 }
@@ -0,0 +1,263 @@
+//! Requestor budget watchdog. Agreements whose Debit Notes approach the budget
+//! are terminated, so a misbehaving task can't overrun the Allocation.
+//! Activities reaching their cost ceiling are destroyed along with their Agreement.
+use bigdecimal::{BigDecimal, Zero};
+use std::str::FromStr;
+
+use ya_client_model::market::Reason;
+use ya_client_model::NodeId;
+use ya_core_model::activity;
+use ya_core_model::journal;
+use ya_core_model::market;
+use ya_core_model::payment::local::{ActivityBudget, AgreementBudget};
+use ya_net::RemoteEndpoint;
+use ya_persistence::executor::DbExecutor;
+use ya_service_bus::{typed as bus, RpcEndpoint};
+
+use crate::dao::{
+    ActivityBudgetDao, ActivityDao, AgreementBudgetDao, AgreementDao, AllocationDao,
+    AllocationStatus,
+};
+use crate::error::DbResult;
+use crate::models::activity as activity_model;
+
+/// Seconds the Provider has to destroy the Activity.
+const DESTROY_TIMEOUT: f32 = 10.0;
+
+lazy_static::lazy_static! {
+    static ref DEFAULT_THRESHOLD: f64 = std::env::var("PAYMENT_BUDGET_THRESHOLD")
+        .ok()
+        .and_then(|x| x.parse().ok())
+        .unwrap_or(0.9);
+}
+
+/// Terminates the Agreement if its amount due approaches the budget.
+/// Agreements without known Allocation nor `max_amount` aren't checked.
+pub async fn check_agreement(db: DbExecutor, agreement_id: String, owner_id: NodeId) {
+    let reason = match exceeded(&db, &agreement_id, owner_id).await {
+        Ok(Some(reason)) => reason,
+        Ok(None) => return,
+        Err(e) => {
+            log::warn!(
+                "Failed to check budget of Agreement [{}]: {}",
+                agreement_id,
+                e
+            );
+            return;
+        }
+    };
+
+    log::warn!(
+        "Terminating Agreement [{}] by budget watchdog. {}",
+        agreement_id,
+        reason
+    );
+    let msg = market::TerminateAgreement {
+        agreement_id: agreement_id.clone(),
+        node_id: owner_id,
+        reason: Some(Reason::new(format!("Budget exceeded. {}", reason))),
+    };
+    let result = match bus::service(market::local::BUS_ID).send(msg).await {
+        Ok(result) => result.map_err(|e| e.to_string()),
+        Err(e) => Err(e.to_string()),
+    };
+    if let Err(e) = result {
+        log::warn!("Failed to terminate Agreement [{}]: {}", agreement_id, e);
+        return;
+    }
+
+    if let Err(e) = db
+        .as_dao::<AgreementBudgetDao>()
+        .mark_terminated(agreement_id.clone(), owner_id)
+        .await
+    {
+        log::warn!(
+            "Failed to mark Agreement [{}] as terminated: {}",
+            agreement_id,
+            e
+        );
+    }
+    journal::Event::new(journal::Category::Payment, "agreement-budget-exceeded")
+        .subject(&agreement_id)
+        .node_id(owner_id)
+        .details(serde_json::json!({ "reason": reason }))
+        .record()
+        .await;
+}
+
+/// Destroys the Activity and terminates its Agreement once Debit Notes of the
+/// Activity reach its cost ceiling.
+pub async fn check_activity(db: DbExecutor, activity_id: String, owner_id: NodeId) {
+    let (budget, activity) = match ceiling_reached(&db, &activity_id, owner_id).await {
+        Ok(Some(exceeded)) => exceeded,
+        Ok(None) => return,
+        Err(e) => {
+            log::warn!(
+                "Failed to check cost ceiling of Activity [{}]: {}",
+                activity_id,
+                e
+            );
+            return;
+        }
+    };
+
+    let amount_due = activity.total_amount_due.0;
+    log::warn!(
+        "Destroying Activity [{}]. Amount due {} reached cost ceiling {}.",
+        activity_id,
+        amount_due,
+        budget.max_amount
+    );
+    let msg = activity::Destroy {
+        activity_id: activity_id.clone(),
+        agreement_id: budget.agreement_id.clone(),
+        timeout: Some(DESTROY_TIMEOUT),
+    };
+    let result = match ya_net::from(owner_id)
+        .to(activity.peer_id)
+        .service(activity::BUS_ID)
+        .send(msg)
+        .await
+    {
+        Ok(result) => result.map_err(|e| e.to_string()),
+        Err(e) => Err(e.to_string()),
+    };
+    if let Err(e) = result {
+        log::warn!("Failed to destroy Activity [{}]: {}", activity_id, e);
+    }
+
+    let mut reason = Reason::new(format!(
+        "Cost ceiling of Activity {} reached. Amount due {}, limit {}.",
+        activity_id, amount_due, budget.max_amount
+    ));
+    reason.extra = serde_json::json!({ "golem.requestor.code": "CostCeilingReached" });
+    let msg = market::TerminateAgreement {
+        agreement_id: budget.agreement_id.clone(),
+        node_id: owner_id,
+        reason: Some(reason),
+    };
+    let result = match bus::service(market::local::BUS_ID).send(msg).await {
+        Ok(result) => result.map_err(|e| e.to_string()),
+        Err(e) => Err(e.to_string()),
+    };
+    if let Err(e) = result {
+        log::warn!(
+            "Failed to terminate Agreement [{}]: {}",
+            budget.agreement_id,
+            e
+        );
+    }
+
+    if let Err(e) = db
+        .as_dao::<ActivityBudgetDao>()
+        .mark_terminated(activity_id.clone(), owner_id)
+        .await
+    {
+        log::warn!(
+            "Failed to mark Activity [{}] as terminated: {}",
+            activity_id,
+            e
+        );
+    }
+    journal::Event::new(journal::Category::Payment, "activity-cost-ceiling-reached")
+        .subject(&activity_id)
+        .node_id(owner_id)
+        .details(serde_json::json!({
+            "agreementId": budget.agreement_id,
+            "amountDue": amount_due.to_string(),
+            "maxAmount": budget.max_amount.to_string(),
+        }))
+        .record()
+        .await;
+}
+
+async fn ceiling_reached(
+    db: &DbExecutor,
+    activity_id: &str,
+    owner_id: NodeId,
+) -> DbResult<Option<(ActivityBudget, activity_model::ReadObj)>> {
+    let budget = match db
+        .as_dao::<ActivityBudgetDao>()
+        .get(activity_id.to_string(), owner_id)
+        .await?
+    {
+        Some(budget) if budget.terminated_at.is_none() => budget,
+        _ => return Ok(None),
+    };
+    let activity = match db
+        .as_dao::<ActivityDao>()
+        .get(activity_id.to_string(), owner_id)
+        .await?
+    {
+        Some(activity) => activity,
+        None => return Ok(None),
+    };
+
+    match activity.total_amount_due.0 >= budget.max_amount {
+        true => Ok(Some((budget, activity))),
+        false => Ok(None),
+    }
+}
+
+async fn exceeded(
+    db: &DbExecutor,
+    agreement_id: &str,
+    owner_id: NodeId,
+) -> DbResult<Option<String>> {
+    let budget = match db
+        .as_dao::<AgreementBudgetDao>()
+        .get(agreement_id.to_string(), owner_id)
+        .await?
+    {
+        Some(budget) if budget.enabled && budget.terminated_at.is_none() => budget,
+        _ => return Ok(None),
+    };
+    let agreement = match db
+        .as_dao::<AgreementDao>()
+        .get(agreement_id.to_string(), owner_id)
+        .await?
+    {
+        Some(agreement) => agreement,
+        None => return Ok(None),
+    };
+
+    let threshold = threshold(&budget);
+    let amount_due = agreement.total_amount_due.0;
+    if let Some(max_amount) = &budget.max_amount {
+        if amount_due >= max_amount * &threshold {
+            return Ok(Some(format!(
+                "Amount due {} approaches limit {}.",
+                amount_due, max_amount
+            )));
+        }
+    }
+
+    if let Some(allocation_id) = budget.allocation_id {
+        if let AllocationStatus::Active(allocation) = db
+            .as_dao::<AllocationDao>()
+            .get(allocation_id.clone(), owner_id)
+            .await?
+        {
+            let not_scheduled = &amount_due - &agreement.total_amount_scheduled.0;
+            if not_scheduled > BigDecimal::zero()
+                && not_scheduled >= &allocation.remaining_amount * &threshold
+            {
+                return Ok(Some(format!(
+                    "Amount due {} approaches {} remaining in Allocation [{}].",
+                    not_scheduled, allocation.remaining_amount, allocation_id
+                )));
+            }
+        }
+    }
+    Ok(None)
+}
+
+fn threshold(budget: &AgreementBudget) -> BigDecimal {
+    let threshold = budget.threshold.unwrap_or(*DEFAULT_THRESHOLD);
+    let threshold = if threshold > 0.0 && threshold <= 1.0 {
+        threshold
+    } else {
+        1.0
+    };
+    BigDecimal::from_str(&threshold.to_string()).unwrap_or_else(|_| 1.into())
+}
@@ -6,19 +6,23 @@ use std::time::UNIX_EPOCH;
 use structopt::*;
 
 // Workspace uses
+use ya_core_model::driver::{GasPriority, PaymentPriority};
 use ya_core_model::{identity as id_api, payment::local as pay};
 use ya_service_api::{CliCtx, CommandOutput, ResponseTable};
 use ya_service_bus::{typed as bus, RpcEndpoint};
 
 // Local uses
-use crate::accounts::{init_account, Account};
+use crate::accounts::{init_account_with_progress, migrate_accounts, Account};
 use crate::wallet;
 
 /// Payment management.
 #[derive(StructOpt, Debug)]
 pub enum PaymentCli {
-    /// List active payment accounts
-    Accounts,
+    /// List payment accounts, or enable and disable them on networks
+    Accounts {
+        #[structopt(subcommand)]
+        command: Option<AccountsCommand>,
+    },
 
     /// Supply payment account with funds
     Fund {
@@ -44,6 +48,22 @@ pub enum PaymentCli {
         last: Option<humantime::Duration>,
     },
 
+    /// Check whether account has funds to send payments, with hints how to get the missing ones
+    Readiness {
+        #[structopt(flatten)]
+        account: pay::AccountCli,
+        #[structopt(long, help = "Amount to be spent, for example 1.45")]
+        amount: Option<String>,
+    },
+
+    /// Compare cost of sending payments on each network the account sends from
+    Fees {
+        #[structopt(long, help = "Requestor address [default: <DEFAULT_IDENTITY>]")]
+        address: Option<String>,
+        #[structopt(long, help = "Number of payments to send", default_value = "1")]
+        payments: u32,
+    },
+
     /// Enter layer 2 (deposit funds to layer 2 network)
     Enter {
         #[structopt(flatten)]
@@ -70,7 +90,10 @@ pub enum PaymentCli {
         account: pay::AccountCli,
         #[structopt(long, help = "Recipient address")]
         to_address: String,
-        #[structopt(long, help = "Amount in GLM for example 1.45")]
+        #[structopt(
+            long,
+            help = "Amount in GLM for example 1.45, or `all` to move whole unreserved balance"
+        )]
         amount: String,
         #[structopt(long, help = "Override gas price (in Gwei)", default_value = "auto")]
         gas_price: String,
@@ -90,29 +113,193 @@ pub enum PaymentCli {
         #[structopt(
             long,
             help = "Use gasless forwarder, no gas on account is required",
-            conflicts_with_all(&["gas-limit", "max-gas-price", "gas-price"])
+            conflicts_with_all(&["gas-limit", "max-gas-price", "gas-price", "priority"])
         )]
         gasless: bool,
+        #[structopt(
+            long,
+            help = "Gas price strategy on Polygon, explicit gas prices take precedence",
+            possible_values = &["slow", "fast", "express"],
+            case_insensitive = true
+        )]
+        priority: Option<GasPriority>,
+        #[structopt(
+            long,
+            help = "Wait for the transaction to be confirmed and print its receipt"
+        )]
+        wait: bool,
+    },
+    /// Send out pending payments now, regardless of the payout schedule of the network
+    Flush {
+        #[structopt(flatten)]
+        account: pay::AccountCli,
+    },
+    /// Move GLM between Ethereum and Polygon through the PoS bridge
+    Bridge {
+        #[structopt(subcommand)]
+        command: BridgeCommand,
     },
     Invoice {
         address: Option<String>,
         #[structopt(subcommand)]
         command: InvoiceCommand,
     },
+    DebitNote {
+        address: Option<String>,
+        #[structopt(subcommand)]
+        command: DebitNoteCommand,
+    },
+
+    /// Set budget watchdog of an Agreement, which terminates it when Debit Notes approach the budget
+    Budget {
+        agreement_id: String,
+        #[structopt(long, help = "Requestor address [default: <DEFAULT_IDENTITY>]")]
+        address: Option<String>,
+        #[structopt(long, help = "Allocation used to accept Debit Notes of the Agreement")]
+        allocation_id: Option<String>,
+        #[structopt(long, help = "Maximum amount due of the Agreement, for example 1.45")]
+        max_amount: Option<String>,
+        #[structopt(
+            long,
+            help = "Share of the budget after which the Agreement is terminated"
+        )]
+        threshold: Option<f64>,
+        #[structopt(long, conflicts_with = "enable")]
+        disable: bool,
+        #[structopt(long)]
+        enable: bool,
+    },
+
+    /// Set auto-refill of an Allocation, which tops it up from the account when it runs low
+    Refill {
+        allocation_id: String,
+        #[structopt(long, help = "Requestor address [default: <DEFAULT_IDENTITY>]")]
+        address: Option<String>,
+        #[structopt(long, help = "Amount added to the Allocation, for example 1.45")]
+        amount: Option<String>,
+        #[structopt(
+            long,
+            help = "Remaining amount below which the Allocation is refilled [default: <AMOUNT>]"
+        )]
+        threshold: Option<String>,
+        #[structopt(long, help = "Total amount the Allocation may grow to")]
+        max_total_amount: Option<String>,
+        #[structopt(long, conflicts_with = "enable")]
+        disable: bool,
+        #[structopt(long)]
+        enable: bool,
+    },
+
+    /// List disputes of rejected Invoices and Debit Notes
+    Disputes {
+        #[structopt(long, help = "Node address [default: <DEFAULT_IDENTITY>]")]
+        address: Option<String>,
+        #[structopt(long, help = "Only disputes with status: open, responded, resolved")]
+        status: Option<pay::DisputeStatus>,
+    },
 
     /// List registered drivers, networks, tokens and platforms
     Drivers,
 
+    /// Change priority of pending payments of an Invoice or a Debit Note
+    Prioritize {
+        /// Invoice or Debit Note ID
+        document_id: String,
+        #[structopt(
+            long,
+            help = "Payments are sent in order of priority, oldest first",
+            possible_values = &["high", "normal", "low"],
+            case_insensitive = true
+        )]
+        priority: PaymentPriority,
+    },
+
+    /// Move allocations and accounts of a renamed or deprecated payment platform.
+    /// Without `--from` and `--to` aliases from `PAYMENT_PLATFORM_ALIASES` are applied
+    MigratePlatform {
+        #[structopt(
+            long,
+            requires = "to",
+            help = "Platform to migrate from, e.g. erc20-rinkeby-tglm"
+        )]
+        from: Option<String>,
+        #[structopt(
+            long,
+            requires = "from",
+            help = "Platform to migrate to, e.g. erc20-goerli-tglm"
+        )]
+        to: Option<String>,
+        #[structopt(long, help = "Only show what would be migrated")]
+        dry_run: bool,
+    },
+
     /// Clear all existing allocations
     ReleaseAllocations,
 }
 
+#[derive(StructOpt, Debug)]
+pub enum AccountsCommand {
+    /// List active payment accounts with their modes on each network
+    List,
+    /// Enable sending and receiving on the network, both if neither is given
+    Enable {
+        #[structopt(flatten)]
+        account: pay::AccountCli,
+        #[structopt(long)]
+        send: bool,
+        #[structopt(long)]
+        receive: bool,
+    },
+    /// Disable sending and receiving on the network, both if neither is given
+    Disable {
+        #[structopt(flatten)]
+        account: pay::AccountCli,
+        #[structopt(long)]
+        send: bool,
+        #[structopt(long)]
+        receive: bool,
+    },
+}
+
 #[derive(StructOpt, Debug)]
 pub enum InvoiceCommand {
     Status {
         #[structopt(long, help = "Display invoice status from the given period of time")]
         last: Option<humantime::Duration>,
     },
+    /// Show unpaid accepted invoices by requestor and time past due date
+    Aging,
+    /// List unpaid accepted invoices, oldest due first
+    Unpaid,
+}
+
+#[derive(StructOpt, Debug)]
+pub enum BridgeCommand {
+    /// Start a transfer from `--network`, e.g. mainnet to polygon or polygon to mainnet
+    Send {
+        #[structopt(flatten)]
+        account: pay::AccountCli,
+        #[structopt(long, help = "Amount in GLM for example 1.45")]
+        amount: String,
+        #[structopt(
+            long,
+            help = "Network to move funds to [default: <COUNTERPART_OF_NETWORK>]"
+        )]
+        to_network: Option<String>,
+    },
+    /// Show bridge transfers of the account
+    Status {
+        #[structopt(flatten)]
+        account: pay::AccountCli,
+        /// Show a single transfer
+        bridge_id: Option<String>,
+    },
+}
+
+#[derive(StructOpt, Debug)]
+pub enum DebitNoteCommand {
+    /// Check billed usage against usage signed by the Provider's ExeUnit
+    VerifyUsage { debit_note_id: String },
 }
 
 impl PaymentCli {
@@ -121,18 +308,38 @@ impl PaymentCli {
             PaymentCli::Fund { account } => {
                 let address = resolve_address(account.address()).await?;
 
-                init_account(Account {
-                    driver: account.driver(),
-                    address: address.clone(),
-                    network: Some(account.network()),
-                    token: None, // Use default -- we don't yet support other tokens than GLM
-                    send: true,
-                    receive: false,
-                })
+                let progress_id = progress_id(ctx);
+                wallet::with_progress(
+                    account.driver(),
+                    progress_id.clone(),
+                    init_account_with_progress(
+                        Account {
+                            driver: account.driver(),
+                            address: address.clone(),
+                            network: Some(account.network()),
+                            token: None, // Use default -- we don't yet support other tokens than GLM
+                            send: true,
+                            receive: false,
+                        },
+                        progress_id,
+                    ),
+                )
                 .await?;
 
+                let progress_id = progress_id(ctx);
                 CommandOutput::object(
-                    wallet::fund(address, account.driver(), Some(account.network()), None).await?,
+                    wallet::with_progress(
+                        account.driver(),
+                        progress_id.clone(),
+                        wallet::fund(
+                            address,
+                            account.driver(),
+                            Some(account.network()),
+                            None,
+                            progress_id,
+                        ),
+                    )
+                    .await?,
                 )
             }
             PaymentCli::Init {
@@ -148,7 +355,13 @@ impl PaymentCli {
                     send: sender,
                     receive: receiver,
                 };
-                init_account(account).await?;
+                let progress_id = progress_id(ctx);
+                wallet::with_progress(
+                    account.driver.clone(),
+                    progress_id.clone(),
+                    init_account_with_progress(account, progress_id),
+                )
+                .await?;
                 Ok(CommandOutput::NoOutput)
             }
             PaymentCli::Status { account, last } => {
@@ -217,7 +430,74 @@ impl PaymentCli {
                 }
                 .with_header(format!("\nStatus for account: {}\n", address)))
             }
-            PaymentCli::Accounts => {
+            PaymentCli::Readiness { account, amount } => {
+                let address = resolve_address(account.address()).await?;
+                let amount = match amount {
+                    None => None,
+                    Some(a) => Some(BigDecimal::from_str(&a)?),
+                };
+                let readiness = bus::service(pay::BUS_ID)
+                    .call(pay::GetReadiness {
+                        address: address.clone(),
+                        driver: account.driver(),
+                        network: Some(account.network()),
+                        amount,
+                    })
+                    .await??;
+                if ctx.json_output {
+                    return CommandOutput::object(readiness);
+                }
+
+                let mut header = format!(
+                    "\nAccount {} is {}ready to send payments on {}\n",
+                    address,
+                    if readiness.ready { "" } else { "not " },
+                    readiness.network
+                );
+                for suggestion in &readiness.suggestions {
+                    header.push_str(&format!("  - {}\n", suggestion));
+                }
+
+                let values = std::iter::once(("token", &readiness.token))
+                    .chain(readiness.gas.as_ref().map(|gas| ("gas", gas)))
+                    .map(|(funds, check)| {
+                        serde_json::json! {[
+                            funds,
+                            format!("{} {}", check.required, check.currency),
+                            format!("{} {}", check.available, check.currency),
+                            if check.sufficient() { "yes" } else { "no" },
+                        ]}
+                    })
+                    .collect();
+
+                Ok(ResponseTable {
+                    columns: vec![
+                        "funds".to_owned(),
+                        "required".to_owned(),
+                        "available".to_owned(),
+                        "sufficient".to_owned(),
+                    ],
+                    values,
+                }
+                .with_header(header))
+            }
+            PaymentCli::Accounts {
+                command:
+                    Some(AccountsCommand::Enable {
+                        account,
+                        send,
+                        receive,
+                    }),
+            } => set_account_mode(account, send, receive, true).await,
+            PaymentCli::Accounts {
+                command:
+                    Some(AccountsCommand::Disable {
+                        account,
+                        send,
+                        receive,
+                    }),
+            } => set_account_mode(account, send, receive, false).await,
+            PaymentCli::Accounts { .. } => {
                 let accounts = bus::service(pay::BUS_ID)
                     .call(pay::GetAccounts {})
                     .await??;
@@ -265,6 +545,153 @@ impl PaymentCli {
                         .await??,
                 )
             }
+            PaymentCli::Invoice {
+                address,
+                command: InvoiceCommand::Aging,
+            } => {
+                let address = resolve_address(address).await?;
+                let report = bus::service(pay::BUS_ID)
+                    .call(pay::GetInvoiceAging {
+                        owner_id: address.parse()?,
+                    })
+                    .await??;
+                if ctx.json_output {
+                    return CommandOutput::object(report);
+                }
+
+                Ok(ResponseTable {
+                    columns: vec![
+                        "requestor".to_owned(),
+                        "unpaid".to_owned(),
+                        "not due".to_owned(),
+                        "overdue <1d".to_owned(),
+                        "overdue <7d".to_owned(),
+                        "overdue >7d".to_owned(),
+                        "reminders".to_owned(),
+                        "reliability".to_owned(),
+                    ],
+                    values: report
+                        .into_iter()
+                        .map(|aging| {
+                            serde_json::json! {[
+                                aging.requestor_id,
+                                aging.unpaid_count,
+                                aging.not_due.to_string(),
+                                aging.overdue_up_to_day.to_string(),
+                                aging.overdue_up_to_week.to_string(),
+                                aging.overdue_more.to_string(),
+                                aging.reminders_sent,
+                                format!("{:.0}%", aging.reliability * 100.0),
+                            ]}
+                        })
+                        .collect(),
+                }
+                .into())
+            }
+            PaymentCli::Invoice {
+                address,
+                command: InvoiceCommand::Unpaid,
+            } => {
+                let address = resolve_address(address).await?;
+                let unpaid = bus::service(pay::BUS_ID)
+                    .call(pay::GetUnpaidInvoices {
+                        owner_id: address.parse()?,
+                    })
+                    .await??;
+                if ctx.json_output {
+                    return CommandOutput::object(unpaid);
+                }
+
+                Ok(ResponseTable {
+                    columns: vec![
+                        "invoice".to_owned(),
+                        "requestor".to_owned(),
+                        "platform".to_owned(),
+                        "amount".to_owned(),
+                        "due".to_owned(),
+                        "reminders".to_owned(),
+                    ],
+                    values: unpaid
+                        .into_iter()
+                        .map(|invoice| {
+                            serde_json::json! {[
+                                invoice.invoice_id,
+                                invoice.requestor_id,
+                                invoice.payment_platform,
+                                invoice.amount.to_string(),
+                                invoice.payment_due_date.to_rfc3339(),
+                                invoice.reminders_sent,
+                            ]}
+                        })
+                        .collect(),
+                }
+                .into())
+            }
+            PaymentCli::DebitNote {
+                address,
+                command: DebitNoteCommand::VerifyUsage { debit_note_id },
+            } => {
+                let address = resolve_address(address).await?;
+                CommandOutput::object(
+                    bus::service(pay::BUS_ID)
+                        .call(pay::VerifyDebitNoteUsage {
+                            debit_note_id,
+                            owner_id: address.parse()?,
+                        })
+                        .await??,
+                )
+            }
+            PaymentCli::Fees { address, payments } => {
+                let address = resolve_address(address).await?;
+                let comparison = bus::service(pay::BUS_ID)
+                    .call(pay::CompareFees {
+                        address: address.clone(),
+                        payments,
+                    })
+                    .await??;
+                if ctx.json_output {
+                    return CommandOutput::object(comparison);
+                }
+
+                let header = match &comparison.recommended {
+                    Some(platform) => format!(
+                        "\nCheapest platform to send {} payment(s) from {}: {}\n",
+                        payments, address, platform
+                    ),
+                    None => format!(
+                        "\nFees of {} payment(s) from {} can't be compared\n",
+                        payments, address
+                    ),
+                };
+                Ok(ResponseTable {
+                    columns: vec![
+                        "platform".to_owned(),
+                        "gas price".to_owned(),
+                        "cost".to_owned(),
+                        "fiat cost".to_owned(),
+                    ],
+                    values: comparison
+                        .estimates
+                        .iter()
+                        .map(|estimate| {
+                            serde_json::json! {[
+                                estimate.platform,
+                                format!("{} gwei", estimate.gas_price),
+                                format!("{} {}", estimate.cost, estimate.currency),
+                                match &estimate.fiat_cost {
+                                    Some(cost) => format!(
+                                        "{} {}",
+                                        cost.with_scale(4),
+                                        estimate.fiat_currency
+                                    ),
+                                    None => "unknown".to_owned(),
+                                },
+                            ]}
+                        })
+                        .collect(),
+                }
+                .with_header(header))
+            }
             PaymentCli::Enter { account, amount } => CommandOutput::object(
                 wallet::enter(
                     BigDecimal::from_str(&amount)?,
@@ -304,9 +731,21 @@ impl PaymentCli {
                 max_gas_price,
                 gas_limit,
                 gasless,
+                priority,
+                wait,
             } => {
                 let address = resolve_address(account.address()).await?;
-                let amount = BigDecimal::from_str(&amount)?;
+                let amount = match amount.as_str() {
+                    "all" => {
+                        wallet::unreserved_balance(
+                            address.clone(),
+                            account.driver(),
+                            Some(account.network()),
+                        )
+                        .await?
+                    }
+                    amount => BigDecimal::from_str(amount)?,
+                };
 
                 let gas_price = if gas_price.is_empty() || gas_price == "auto" {
                     None
@@ -337,10 +776,80 @@ impl PaymentCli {
                         max_gas_price,
                         gas_limit,
                         gasless,
+                        priority,
+                        wait,
                     )
                     .await?,
                 )
             }
+            PaymentCli::Flush { account } => {
+                wallet::flush(account.driver(), account.network()).await?;
+                CommandOutput::object(format!(
+                    "Pending payments on {} will be sent out in the next cycle",
+                    account.network()
+                ))
+            }
+            PaymentCli::Bridge { command } => match command {
+                BridgeCommand::Send {
+                    account,
+                    amount,
+                    to_network,
+                } => {
+                    let address = resolve_address(account.address()).await?;
+                    let bridge_id = wallet::bridge(
+                        address,
+                        BigDecimal::from_str(&amount)?,
+                        account.driver(),
+                        account.network(),
+                        to_network,
+                    )
+                    .await?;
+                    CommandOutput::object(format!(
+                        "Bridge transfer {} started, follow it with `yagna payment bridge status {}`",
+                        bridge_id, bridge_id
+                    ))
+                }
+                BridgeCommand::Status { account, bridge_id } => {
+                    let address = resolve_address(account.address()).await?;
+                    let transfers =
+                        wallet::bridge_transfers(address, account.driver(), bridge_id).await?;
+                    if ctx.json_output {
+                        return CommandOutput::object(transfers);
+                    }
+                    Ok(ResponseTable {
+                        columns: vec![
+                            "id".to_owned(),
+                            "from".to_owned(),
+                            "to".to_owned(),
+                            "amount".to_owned(),
+                            "status".to_owned(),
+                            "source tx".to_owned(),
+                            "target tx".to_owned(),
+                            "created".to_owned(),
+                        ],
+                        values: transfers
+                            .into_iter()
+                            .map(|transfer| {
+                                let status = match transfer.error {
+                                    Some(error) => format!("{} ({})", transfer.status, error),
+                                    None => transfer.status.to_string(),
+                                };
+                                serde_json::json! {[
+                                    transfer.bridge_id,
+                                    transfer.from_network,
+                                    transfer.to_network,
+                                    transfer.amount.to_string(),
+                                    status,
+                                    transfer.source_tx.unwrap_or_default(),
+                                    transfer.target_tx.unwrap_or_default(),
+                                    transfer.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+                                ]}
+                            })
+                            .collect(),
+                    }
+                    .into())
+                }
+            },
             PaymentCli::Drivers => {
                 let drivers = bus::service(pay::BUS_ID).call(pay::GetDrivers {}).await??;
                 if ctx.json_output {
@@ -380,6 +889,149 @@ impl PaymentCli {
                         .collect(),
                 }.into())
             }
+            PaymentCli::Budget {
+                agreement_id,
+                address,
+                allocation_id,
+                max_amount,
+                threshold,
+                disable,
+                enable,
+            } => {
+                let max_amount = match max_amount {
+                    None => None,
+                    Some(a) => Some(BigDecimal::from_str(&a)?),
+                };
+                let enabled = match (enable, disable) {
+                    (true, _) => Some(true),
+                    (_, true) => Some(false),
+                    _ => None,
+                };
+                CommandOutput::object(
+                    bus::service(pay::BUS_ID)
+                        .call(pay::SetAgreementBudget {
+                            agreement_id,
+                            owner_id: resolve_address(address).await?.parse()?,
+                            allocation_id,
+                            max_amount,
+                            threshold,
+                            enabled,
+                        })
+                        .await??,
+                )
+            }
+            PaymentCli::Refill {
+                allocation_id,
+                address,
+                amount,
+                threshold,
+                max_total_amount,
+                disable,
+                enable,
+            } => {
+                let parse = |amount: Option<String>| -> anyhow::Result<Option<BigDecimal>> {
+                    Ok(match amount {
+                        None => None,
+                        Some(a) => Some(BigDecimal::from_str(&a)?),
+                    })
+                };
+                let enabled = match (enable, disable) {
+                    (true, _) => Some(true),
+                    (_, true) => Some(false),
+                    _ => None,
+                };
+                CommandOutput::object(
+                    bus::service(pay::BUS_ID)
+                        .call(pay::SetAllocationRefill {
+                            allocation_id,
+                            owner_id: resolve_address(address).await?.parse()?,
+                            amount: parse(amount)?,
+                            threshold: parse(threshold)?,
+                            max_total_amount: parse(max_total_amount)?,
+                            enabled,
+                        })
+                        .await??,
+                )
+            }
+            PaymentCli::Disputes { address, status } => {
+                let disputes = bus::service(pay::BUS_ID)
+                    .call(pay::ListDisputes {
+                        owner_id: resolve_address(address).await?.parse()?,
+                        status,
+                    })
+                    .await??;
+                if ctx.json_output {
+                    return CommandOutput::object(disputes);
+                }
+                Ok(ResponseTable {
+                    columns: vec![
+                        "document".to_owned(),
+                        "type".to_owned(),
+                        "peer".to_owned(),
+                        "status".to_owned(),
+                        "reason".to_owned(),
+                        "updated".to_owned(),
+                    ],
+                    values: disputes
+                        .into_iter()
+                        .map(|dispute| {
+                            serde_json::json! {[
+                                dispute.document_id,
+                                dispute.document_type.to_string(),
+                                dispute.peer_id,
+                                dispute.status.to_string(),
+                                dispute.rejection.rejection_reason,
+                                dispute.updated_at.to_rfc3339(),
+                            ]}
+                        })
+                        .collect(),
+                }
+                .into())
+            }
+            PaymentCli::Prioritize {
+                document_id,
+                priority,
+            } => CommandOutput::object(
+                bus::service(pay::BUS_ID)
+                    .call(pay::PrioritizePayments {
+                        document_id,
+                        priority,
+                    })
+                    .await??,
+            ),
+            PaymentCli::MigratePlatform { from, to, dry_run } => {
+                let migrations = bus::service(pay::BUS_ID)
+                    .call(pay::MigratePlatform { from, to, dry_run })
+                    .await??;
+                let aliases = migrations
+                    .iter()
+                    .map(|migration| (migration.from.clone(), migration.to.clone()))
+                    .collect::<Vec<_>>();
+                let accounts = migrate_accounts(&ctx.data_dir, &aliases, dry_run).await?;
+                if ctx.json_output {
+                    return CommandOutput::object(serde_json::json!({
+                        "migrations": migrations,
+                        "accounts": accounts,
+                        "dryRun": dry_run,
+                    }));
+                }
+                let mut values: Vec<serde_json::Value> = migrations
+                    .into_iter()
+                    .map(|migration| {
+                        serde_json::json! {[migration.from, migration.to, migration.allocations]}
+                    })
+                    .collect();
+                values.push(serde_json::json! {["accounts", "", accounts]});
+                Ok(ResponseTable {
+                    columns: vec![
+                        "from".to_owned(),
+                        "to".to_owned(),
+                        if dry_run { "to migrate" } else { "migrated" }.to_owned(),
+                    ],
+                    values,
+                }
+                .into())
+            }
             PaymentCli::ReleaseAllocations => {
                 let _ = bus::service(pay::BUS_ID)
                     .call(pay::ReleaseAllocations {})
@@ -390,18 +1042,51 @@ impl PaymentCli {
     }
 }
 
+/// Id under which the driver reports progress of a long-running operation.
+fn progress_id(ctx: &CliCtx) -> Option<String> {
+    match ctx.quiet {
+        true => None,
+        false => Some(uuid::Uuid::new_v4().to_string()),
+    }
+}
+
 async fn resolve_address(address: Option<String>) -> anyhow::Result<String> {
     if let Some(id) = address {
         return Ok(id);
     }
 
     let id = bus::service(id_api::BUS_ID)
-        .send(id_api::Get::ByDefault)
+        .send(id_api::Get::ByRole(id_api::IdentityRole::Payment))
         .await??;
 
     if let Some(id) = id {
         return Ok(id.node_id.to_string());
     }
 
-    anyhow::bail!("Default identity not found")
+    anyhow::bail!("Payment identity not found")
+}
+
+/// Sets given modes of the account to `enable`, both modes if none is given.
+async fn set_account_mode(
+    account: pay::AccountCli,
+    send: bool,
+    receive: bool,
+    enable: bool,
+) -> anyhow::Result<CommandOutput> {
+    let both = !send && !receive;
+    let mode = |selected: bool| match selected || both {
+        true => Some(enable),
+        false => None,
+    };
+    let updated = bus::service(pay::BUS_ID)
+        .call(pay::SetAccountMode {
+            address: resolve_address(account.address()).await?,
+            driver: account.driver(),
+            network: Some(account.network()),
+            token: Some(account.token()),
+            send: mode(send),
+            receive: mode(receive),
+        })
+        .await??;
+    CommandOutput::object(updated)
 }
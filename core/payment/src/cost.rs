@@ -0,0 +1,126 @@
+//! Cost preview of Offers priced with the linear model, so Requestor agents can
+//! compare Proposals and Agreements by projected cost instead of coefficients.
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+
+use ya_agreement_utils::agreement::{expand, TypedPointer};
+
+const USAGE_VECTOR_POINTER: &str = "/golem/com/usage/vector";
+const LINEAR_COEFFS_POINTER: &str = "/golem/com/pricing/model/linear/coeffs";
+
+/// Name used for constant component of linear pricing model.
+pub const FIXED_PRICE_COUNTER: &str = "fixed";
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CostItem {
+    pub counter: String,
+    pub price: f64,
+    pub usage: f64,
+    pub cost: f64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CostPreview {
+    pub payment_platform: Option<String>,
+    pub items: Vec<CostItem>,
+    /// Counters priced in the Offer without estimated usage, counted as zero.
+    pub unestimated_counters: Vec<String>,
+    pub total: f64,
+}
+
+/// Projects cost of `usage` estimated per counter, e.g. `golem.usage.duration_sec`,
+/// with pricing from `offer` properties. Fixed price is always included once.
+pub fn preview(
+    offer: Value,
+    usage: &HashMap<String, f64>,
+    payment_platform: Option<String>,
+) -> Result<CostPreview, String> {
+    let offer = expand(offer);
+    let counters: Vec<String> = offer
+        .pointer(USAGE_VECTOR_POINTER)
+        .as_typed(Value::as_array)
+        .map_err(|_| "Offer has no usage vector".to_string())?
+        .iter()
+        .map(|counter| counter.as_str().map(ToOwned::to_owned))
+        .collect::<Option<_>>()
+        .ok_or_else(|| "Offer has invalid usage vector".to_string())?;
+    let coeffs: Vec<f64> = offer
+        .pointer(LINEAR_COEFFS_POINTER)
+        .as_typed(Value::as_array)
+        .map_err(|_| "Offer isn't priced with the linear model".to_string())?
+        .iter()
+        .map(Value::as_f64)
+        .collect::<Option<_>>()
+        .ok_or_else(|| "Offer has invalid pricing coefficients".to_string())?;
+
+    // Linear model has one coefficient for each usage counter and fixed price at the end.
+    if coeffs.len() != counters.len() + 1 {
+        return Err(format!(
+            "Offer has {} pricing coefficients for {} usage counters",
+            coeffs.len(),
+            counters.len()
+        ));
+    }
+
+    let mut unestimated_counters = Vec::new();
+    let items: Vec<CostItem> = counters
+        .into_iter()
+        .map(|counter| {
+            let usage = usage.get(&counter).copied().unwrap_or_else(|| {
+                unestimated_counters.push(counter.clone());
+                0.0
+            });
+            (counter, usage)
+        })
+        .chain(std::iter::once((FIXED_PRICE_COUNTER.to_string(), 1.0)))
+        .zip(coeffs)
+        .map(|((counter, usage), price)| CostItem {
+            counter,
+            price,
+            usage,
+            cost: price * usage,
+        })
+        .collect();
+
+    Ok(CostPreview {
+        payment_platform,
+        total: items.iter().map(|item| item.cost).sum(),
+        items,
+        unestimated_counters,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preview() {
+        let offer = serde_json::json!({
+            "golem.com.usage.vector": ["golem.usage.duration_sec", "golem.usage.cpu_sec"],
+            "golem.com.pricing.model.linear.coeffs": [0.001, 0.01, 0.5],
+        });
+        let usage = vec![("golem.usage.cpu_sec".to_string(), 100.0)]
+            .into_iter()
+            .collect();
+
+        let preview = preview(offer, &usage, None).unwrap();
+        assert_eq!(preview.items.len(), 3);
+        assert_eq!(preview.items[1].cost, 1.0);
+        assert_eq!(preview.items[2].counter, FIXED_PRICE_COUNTER);
+        assert_eq!(
+            preview.unestimated_counters,
+            vec!["golem.usage.duration_sec"]
+        );
+        assert_eq!(preview.total, 1.5);
+
+        let unpriced = serde_json::json!({
+            "golem.com.usage.vector": ["golem.usage.duration_sec"],
+            "golem.com.pricing.model.linear.coeffs": [0.001],
+        });
+        assert!(super::preview(unpriced, &usage, None).is_err());
+    }
+}
@@ -1,21 +1,32 @@
 mod activity;
+mod activity_budget;
 mod agreement;
+mod agreement_budget;
 mod allocation;
+mod allocation_refill;
 mod debit_note;
 mod debit_note_event;
+mod dispute;
 mod invoice;
 mod invoice_event;
+mod invoice_reminder;
 mod order;
 mod payment;
 
 pub use self::activity::ActivityDao;
+pub use self::activity_budget::ActivityBudgetDao;
 pub use self::agreement::AgreementDao;
+pub use self::agreement_budget::AgreementBudgetDao;
 pub use self::allocation::AllocationDao;
 pub use self::allocation::AllocationReleaseStatus;
 pub use self::allocation::AllocationStatus;
+pub use self::allocation_refill::AllocationRefillDao;
+pub use self::allocation_refill::RefillStatus;
 pub use self::debit_note::DebitNoteDao;
 pub use self::debit_note_event::DebitNoteEventDao;
+pub use self::dispute::DisputeDao;
 pub use self::invoice::InvoiceDao;
 pub use self::invoice_event::InvoiceEventDao;
+pub use self::invoice_reminder::InvoiceReminderDao;
 pub use self::order::OrderDao;
 pub use self::payment::PaymentDao;
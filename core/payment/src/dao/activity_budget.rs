@@ -0,0 +1,65 @@
+use crate::error::DbResult;
+use crate::models::activity_budget::{ReadObj, WriteObj};
+use crate::schema::pay_activity_budget::dsl;
+use chrono::Utc;
+use diesel::{ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl};
+use ya_client_model::NodeId;
+use ya_core_model::payment::local::{ActivityBudget, SetActivityBudget};
+use ya_persistence::executor::{do_with_transaction, readonly_transaction, AsDao, PoolType};
+
+pub struct ActivityBudgetDao<'c> {
+    pool: &'c PoolType,
+}
+
+impl<'c> AsDao<'c> for ActivityBudgetDao<'c> {
+    fn as_dao(pool: &'c PoolType) -> Self {
+        Self { pool }
+    }
+}
+
+impl<'c> ActivityBudgetDao<'c> {
+    pub async fn get(
+        &self,
+        activity_id: String,
+        owner_id: NodeId,
+    ) -> DbResult<Option<ActivityBudget>> {
+        readonly_transaction(self.pool, move |conn| {
+            let budget: Option<ReadObj> = dsl::pay_activity_budget
+                .find((activity_id, owner_id))
+                .first(conn)
+                .optional()?;
+            Ok(budget.map(Into::into))
+        })
+        .await
+    }
+
+    /// Sets cost ceiling of the Activity, replacing the previous one.
+    pub async fn set(&self, msg: SetActivityBudget) -> DbResult<ActivityBudget> {
+        do_with_transaction(self.pool, move |conn| {
+            let budget = WriteObj {
+                activity_id: msg.activity_id,
+                owner_id: msg.owner_id,
+                agreement_id: msg.agreement_id,
+                max_amount: msg.max_amount.into(),
+                terminated_ts: None,
+            };
+            diesel::delete(dsl::pay_activity_budget.find((&budget.activity_id, &budget.owner_id)))
+                .execute(conn)?;
+            diesel::insert_into(dsl::pay_activity_budget)
+                .values(&budget)
+                .execute(conn)?;
+            Ok(budget.into())
+        })
+        .await
+    }
+
+    pub async fn mark_terminated(&self, activity_id: String, owner_id: NodeId) -> DbResult<()> {
+        do_with_transaction(self.pool, move |conn| {
+            diesel::update(dsl::pay_activity_budget.find((activity_id, owner_id)))
+                .set(dsl::terminated_ts.eq(Utc::now().naive_utc()))
+                .execute(conn)?;
+            Ok(())
+        })
+        .await
+    }
+}
@@ -0,0 +1,110 @@
+use crate::error::DbResult;
+use crate::models::agreement_budget::{ReadObj, WriteObj};
+use crate::schema::pay_agreement_budget::dsl;
+use chrono::Utc;
+use diesel::{ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl};
+use ya_client_model::NodeId;
+use ya_core_model::payment::local::{AgreementBudget, SetAgreementBudget};
+use ya_persistence::executor::{do_with_transaction, readonly_transaction, AsDao, PoolType};
+
+pub struct AgreementBudgetDao<'c> {
+    pool: &'c PoolType,
+}
+
+impl<'c> AsDao<'c> for AgreementBudgetDao<'c> {
+    fn as_dao(pool: &'c PoolType) -> Self {
+        Self { pool }
+    }
+}
+
+impl<'c> AgreementBudgetDao<'c> {
+    pub async fn get(
+        &self,
+        agreement_id: String,
+        owner_id: NodeId,
+    ) -> DbResult<Option<AgreementBudget>> {
+        readonly_transaction(self.pool, move |conn| {
+            let budget: Option<ReadObj> = dsl::pay_agreement_budget
+                .find((agreement_id, owner_id))
+                .first(conn)
+                .optional()?;
+            Ok(budget.map(Into::into))
+        })
+        .await
+    }
+
+    /// Updates settings given in `msg`, creating the budget with defaults if needed.
+    pub async fn set(&self, msg: SetAgreementBudget) -> DbResult<AgreementBudget> {
+        do_with_transaction(self.pool, move |conn| {
+            let key = (msg.agreement_id.clone(), msg.owner_id);
+            let current: Option<ReadObj> = dsl::pay_agreement_budget
+                .find(key.clone())
+                .first(conn)
+                .optional()?;
+            let mut budget =
+                current.unwrap_or_else(|| WriteObj::new(msg.agreement_id, msg.owner_id));
+            if let Some(allocation_id) = msg.allocation_id {
+                budget.allocation_id = Some(allocation_id);
+            }
+            if let Some(max_amount) = msg.max_amount {
+                budget.max_amount = Some(max_amount.into());
+            }
+            if let Some(threshold) = msg.threshold {
+                budget.threshold = Some(threshold);
+            }
+            if let Some(enabled) = msg.enabled {
+                budget.enabled = enabled;
+            }
+
+            diesel::delete(dsl::pay_agreement_budget.find(key)).execute(conn)?;
+            diesel::insert_into(dsl::pay_agreement_budget)
+                .values(&budget)
+                .execute(conn)?;
+            Ok(budget.into())
+        })
+        .await
+    }
+
+    /// Remembers Allocation used to accept Debit Notes, unless one was already set.
+    pub async fn set_allocation_if_missing(
+        &self,
+        agreement_id: String,
+        owner_id: NodeId,
+        allocation_id: String,
+    ) -> DbResult<()> {
+        do_with_transaction(self.pool, move |conn| {
+            let exists: Option<String> = dsl::pay_agreement_budget
+                .find((&agreement_id, &owner_id))
+                .select(dsl::agreement_id)
+                .first(conn)
+                .optional()?;
+            match exists {
+                Some(_) => {
+                    diesel::update(dsl::pay_agreement_budget.find((&agreement_id, &owner_id)))
+                        .filter(dsl::allocation_id.is_null())
+                        .set(dsl::allocation_id.eq(&allocation_id))
+                        .execute(conn)?;
+                }
+                None => {
+                    let mut budget = WriteObj::new(agreement_id, owner_id);
+                    budget.allocation_id = Some(allocation_id);
+                    diesel::insert_into(dsl::pay_agreement_budget)
+                        .values(&budget)
+                        .execute(conn)?;
+                }
+            }
+            Ok(())
+        })
+        .await
+    }
+
+    pub async fn mark_terminated(&self, agreement_id: String, owner_id: NodeId) -> DbResult<()> {
+        do_with_transaction(self.pool, move |conn| {
+            diesel::update(dsl::pay_agreement_budget.find((agreement_id, owner_id)))
+                .set(dsl::terminated_ts.eq(Utc::now().naive_utc()))
+                .execute(conn)?;
+            Ok(())
+        })
+        .await
+    }
+}
@@ -196,6 +196,29 @@ impl<'c> AllocationDao<'c> {
         .await
     }
 
+    /// Moves not released allocations to another payment platform.
+    /// Returns number of affected allocations, nothing is changed on `dry_run`.
+    pub async fn migrate_platform(
+        &self,
+        from: String,
+        to: String,
+        dry_run: bool,
+    ) -> DbResult<usize> {
+        do_with_transaction(self.pool, move |conn| {
+            let query = dsl::pay_allocation
+                .filter(dsl::payment_platform.eq(from))
+                .filter(dsl::released.eq(false));
+            if dry_run {
+                let count: i64 = query.count().get_result(conn)?;
+                return Ok(count as usize);
+            }
+            Ok(diesel::update(query)
+                .set(dsl::payment_platform.eq(to))
+                .execute(conn)?)
+        })
+        .await
+    }
+
     pub async fn total_remaining_allocation(
         &self,
         platform: String,
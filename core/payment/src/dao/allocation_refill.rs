@@ -0,0 +1,174 @@
+use crate::error::{DbError, DbResult};
+use crate::models::allocation::ReadObj as AllocationObj;
+use crate::models::allocation_refill::{ReadObj, WriteObj};
+use crate::schema::pay_allocation::dsl as allocation_dsl;
+use crate::schema::pay_allocation_refill::dsl;
+use bigdecimal::{BigDecimal, Zero};
+use chrono::Utc;
+use diesel::{ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl};
+use ya_client_model::NodeId;
+use ya_core_model::payment::local::{AllocationRefill, SetAllocationRefill};
+use ya_persistence::executor::{do_with_transaction, readonly_transaction, AsDao, PoolType};
+use ya_persistence::types::{BigDecimalField, Summable};
+
+pub enum RefillStatus {
+    NotNeeded,
+    Refilled(BigDecimal),
+    Refused(String),
+}
+
+pub struct AllocationRefillDao<'c> {
+    pool: &'c PoolType,
+}
+
+impl<'c> AsDao<'c> for AllocationRefillDao<'c> {
+    fn as_dao(pool: &'c PoolType) -> Self {
+        Self { pool }
+    }
+}
+
+impl<'c> AllocationRefillDao<'c> {
+    pub async fn get(
+        &self,
+        allocation_id: String,
+        owner_id: NodeId,
+    ) -> DbResult<Option<AllocationRefill>> {
+        readonly_transaction(self.pool, move |conn| {
+            let refill: Option<ReadObj> = dsl::pay_allocation_refill
+                .find((allocation_id, owner_id))
+                .first(conn)
+                .optional()?;
+            Ok(refill.map(Into::into))
+        })
+        .await
+    }
+
+    /// Updates settings given in `msg`, creating the policy if needed, which requires `amount`.
+    pub async fn set(&self, msg: SetAllocationRefill) -> DbResult<AllocationRefill> {
+        do_with_transaction(self.pool, move |conn| {
+            let owned: Option<String> = allocation_dsl::pay_allocation
+                .find(&msg.allocation_id)
+                .filter(allocation_dsl::owner_id.eq(&msg.owner_id))
+                .select(allocation_dsl::id)
+                .first(conn)
+                .optional()?;
+            if owned.is_none() {
+                return Err(DbError::Query(format!(
+                    "Allocation {} not found",
+                    msg.allocation_id
+                )));
+            }
+
+            let key = (msg.allocation_id.clone(), msg.owner_id);
+            let current: Option<ReadObj> = dsl::pay_allocation_refill
+                .find(key.clone())
+                .first(conn)
+                .optional()?;
+            let mut refill = match (current, msg.amount.clone()) {
+                (Some(refill), _) => refill,
+                (None, Some(amount)) => {
+                    WriteObj::new(msg.allocation_id, msg.owner_id, amount.into())
+                }
+                (None, None) => {
+                    return Err(DbError::Query(
+                        "Refill amount is required to enable auto-refill".to_string(),
+                    ))
+                }
+            };
+            if let Some(amount) = msg.amount {
+                refill.amount = amount.into();
+            }
+            if let Some(threshold) = msg.threshold {
+                refill.threshold = threshold.into();
+            }
+            if let Some(max_total_amount) = msg.max_total_amount {
+                refill.max_total_amount = Some(max_total_amount.into());
+            }
+            if let Some(enabled) = msg.enabled {
+                refill.enabled = enabled;
+            }
+
+            diesel::delete(dsl::pay_allocation_refill.find(key)).execute(conn)?;
+            diesel::insert_into(dsl::pay_allocation_refill)
+                .values(&refill)
+                .execute(conn)?;
+            Ok(refill.into())
+        })
+        .await
+    }
+
+    /// Tops up the Allocation by the refill amount, capped by `max_total_amount`, if its remaining
+    /// amount is below the threshold. Checks are done within the write transaction, so concurrent
+    /// refills of the same Allocation don't add up. `balance` of the account has to cover the
+    /// top-up on top of remaining amounts of all its Allocations.
+    pub async fn refilled(
+        &self,
+        allocation_id: String,
+        owner_id: NodeId,
+        balance: BigDecimal,
+    ) -> DbResult<RefillStatus> {
+        do_with_transaction(self.pool, move |conn| {
+            let refill: Option<ReadObj> = dsl::pay_allocation_refill
+                .find((&allocation_id, &owner_id))
+                .first(conn)
+                .optional()?;
+            let refill = match refill {
+                Some(refill) if refill.enabled => refill,
+                _ => return Ok(RefillStatus::NotNeeded),
+            };
+            let allocation: Option<AllocationObj> = allocation_dsl::pay_allocation
+                .find(&allocation_id)
+                .filter(allocation_dsl::owner_id.eq(&owner_id))
+                .filter(allocation_dsl::released.eq(false))
+                .first(conn)
+                .optional()?;
+            let allocation = match allocation {
+                Some(allocation) if allocation.remaining_amount < refill.threshold => allocation,
+                _ => return Ok(RefillStatus::NotNeeded),
+            };
+
+            let mut amount = refill.amount.0.clone();
+            if let Some(max_total_amount) = &refill.max_total_amount {
+                let headroom = (max_total_amount - &allocation.total_amount).0;
+                if headroom <= BigDecimal::zero() {
+                    return Ok(RefillStatus::Refused(format!(
+                        "Total amount {} reached the cap {}.",
+                        allocation.total_amount, max_total_amount
+                    )));
+                }
+                amount = amount.min(headroom);
+            }
+
+            let reserved = allocation_dsl::pay_allocation
+                .select(allocation_dsl::remaining_amount)
+                .filter(allocation_dsl::payment_platform.eq(&allocation.payment_platform))
+                .filter(allocation_dsl::address.eq(&allocation.address))
+                .filter(allocation_dsl::released.eq(false))
+                .get_results::<BigDecimalField>(conn)?
+                .sum();
+            let unreserved = balance - reserved;
+            if unreserved < amount {
+                return Ok(RefillStatus::Refused(format!(
+                    "Only {} of {} needed is not reserved by Allocations.",
+                    unreserved, amount
+                )));
+            }
+
+            let amount = BigDecimalField(amount);
+            diesel::update(&allocation)
+                .set((
+                    allocation_dsl::total_amount.eq(&allocation.total_amount + &amount),
+                    allocation_dsl::remaining_amount.eq(&allocation.remaining_amount + &amount),
+                ))
+                .execute(conn)?;
+            diesel::update(dsl::pay_allocation_refill.find((&allocation_id, &owner_id)))
+                .set((
+                    dsl::refilled_amount.eq(&refill.refilled_amount + &amount),
+                    dsl::refilled_ts.eq(Utc::now().naive_utc()),
+                ))
+                .execute(conn)?;
+            Ok(RefillStatus::Refilled(amount.0))
+        })
+        .await
+    }
+}
@@ -1,9 +1,12 @@
-use crate::dao::{activity, debit_note_event};
+use crate::dao::{activity, debit_note_event, dispute};
 use crate::error::DbResult;
 use crate::models::debit_note::{ReadObj, WriteObj};
 use crate::schema::pay_activity::dsl as activity_dsl;
 use crate::schema::pay_agreement::dsl as agreement_dsl;
 use crate::schema::pay_debit_note::dsl;
+use crate::schema::pay_debit_note_event::dsl as event_dsl;
+use crate::schema::pay_debit_note_usage::dsl as usage_dsl;
+use crate::utils::{json_from_str, json_to_string};
 use bigdecimal::BigDecimal;
 use chrono::NaiveDateTime;
 use diesel::{
@@ -12,8 +15,12 @@ use diesel::{
 };
 use std::collections::HashMap;
 use std::convert::TryInto;
-use ya_client_model::payment::{DebitNote, DebitNoteEventType, DocumentStatus, NewDebitNote};
+use ya_client_model::payment::{
+    DebitNote, DebitNoteEventType, DocumentStatus, NewDebitNote, Rejection,
+};
 use ya_client_model::NodeId;
+use ya_core_model::activity::SignedUsage;
+use ya_core_model::payment::local::DocumentType;
 use ya_persistence::executor::{
     do_with_transaction, readonly_transaction, AsDao, ConnType, PoolType,
 };
@@ -201,6 +208,24 @@ impl<'c> DebitNoteDao<'c> {
         .await
     }
 
+    /// Timestamp of the latest Debit Note stored for the activity.
+    pub async fn last_timestamp(
+        &self,
+        activity_id: String,
+        owner_id: NodeId,
+    ) -> DbResult<Option<NaiveDateTime>> {
+        readonly_transaction(self.pool, move |conn| {
+            Ok(dsl::pay_debit_note
+                .select(dsl::timestamp)
+                .filter(dsl::activity_id.eq(activity_id))
+                .filter(dsl::owner_id.eq(owner_id))
+                .order_by(dsl::timestamp.desc())
+                .first(conn)
+                .optional()?)
+        })
+        .await
+    }
+
     pub async fn get_all(&self) -> DbResult<Vec<DebitNote>> {
         readonly_transaction(self.pool, move |conn| {
             let debit_notes: Vec<ReadObj> = query!().order_by(dsl::timestamp.desc()).load(conn)?;
@@ -230,6 +255,45 @@ impl<'c> DebitNoteDao<'c> {
         .await
     }
 
+    /// Attaches usage signed by the ExeUnit to the Debit Note, replacing the previous one.
+    pub async fn set_signed_usage(
+        &self,
+        debit_note_id: String,
+        owner_id: NodeId,
+        signed_usage: SignedUsage,
+    ) -> DbResult<()> {
+        let signed_usage = json_to_string(&signed_usage)?;
+        do_with_transaction(self.pool, move |conn| {
+            diesel::delete(usage_dsl::pay_debit_note_usage.find((debit_note_id.clone(), owner_id)))
+                .execute(conn)?;
+            diesel::insert_into(usage_dsl::pay_debit_note_usage)
+                .values((
+                    usage_dsl::debit_note_id.eq(&debit_note_id),
+                    usage_dsl::owner_id.eq(owner_id),
+                    usage_dsl::signed_usage.eq(signed_usage),
+                ))
+                .execute(conn)?;
+            Ok(())
+        })
+        .await
+    }
+
+    pub async fn get_signed_usage(
+        &self,
+        debit_note_id: String,
+        owner_id: NodeId,
+    ) -> DbResult<Option<SignedUsage>> {
+        readonly_transaction(self.pool, move |conn| {
+            let signed_usage: Option<String> = usage_dsl::pay_debit_note_usage
+                .find((debit_note_id, owner_id))
+                .select(usage_dsl::signed_usage)
+                .first(conn)
+                .optional()?;
+            signed_usage.map(|json| json_from_str(&json)).transpose()
+        })
+        .await
+    }
+
     pub async fn mark_received(&self, debit_note_id: String, owner_id: NodeId) -> DbResult<()> {
         do_with_transaction(self.pool, move |conn| {
             diesel::update(dsl::pay_debit_note.find((debit_note_id, owner_id)))
@@ -258,6 +322,7 @@ impl<'c> DebitNoteDao<'c> {
 
             update_status(&vec![debit_note_id.clone()], &owner_id, &status, conn)?;
             activity::set_amount_accepted(&activity_id, &owner_id, &amount, conn)?;
+            dispute::resolve(&debit_note_id, &owner_id, conn)?;
             for event in events {
                 debit_note_event::create::<()>(debit_note_id.clone(), owner_id, event, None, conn)?;
             }
@@ -267,30 +332,43 @@ impl<'c> DebitNoteDao<'c> {
         .await
     }
 
-    // TODO: Implement reject debit note
-    // pub async fn reject(&self, debit_note_id: String, owner_id: NodeId) -> DbResult<()> {
-    //     do_with_transaction(self.pool, move |conn| {
-    //         let (activity_id, role): (String, Role) = dsl::pay_debit_note
-    //             .find((&debit_note_id, &owner_id))
-    //             .select((dsl::activity_id, dsl::role))
-    //             .first(conn)?;
-    //         update_status(
-    //             &vec![debit_note_id.clone()],
-    //             &owner_id,
-    //             &DocumentStatus::Rejected,
-    //             conn,
-    //         )?;
-    //         if let Role::Provider = role {
-    //             debit_note_event::create::<()>(
-    //                 debit_note_id,
-    //                 owner_id,
-    //                 DebitNoteEventType::DebitNoteRejectedEvent,
-    //                 None,
-    //                 conn,
-    //             )?;
-    //         }
-    //         Ok(())
-    //     })
-    //     .await
-    // }
+    /// Marks the debit note rejected and opens its dispute with `peer_id`.
+    pub async fn reject(
+        &self,
+        debit_note_id: String,
+        owner_id: NodeId,
+        peer_id: NodeId,
+        rejection: Rejection,
+    ) -> DbResult<()> {
+        do_with_transaction(self.pool, move |conn| {
+            update_status(
+                &vec![debit_note_id.clone()],
+                &owner_id,
+                &DocumentStatus::Rejected,
+                conn,
+            )?;
+            dispute::open(
+                &debit_note_id,
+                &owner_id,
+                DocumentType::DebitNote,
+                peer_id,
+                &rejection,
+                conn,
+            )?;
+            let event_type = DebitNoteEventType::DebitNoteRejectedEvent {
+                rejection: rejection.clone(),
+            };
+            // Debit note can be rejected again after the issuer's response.
+            diesel::delete(
+                event_dsl::pay_debit_note_event
+                    .filter(event_dsl::debit_note_id.eq(&debit_note_id))
+                    .filter(event_dsl::owner_id.eq(&owner_id))
+                    .filter(event_dsl::event_type.eq(event_type.to_string())),
+            )
+            .execute(conn)?;
+            debit_note_event::create(debit_note_id, owner_id, event_type, Some(rejection), conn)?;
+            Ok(())
+        })
+        .await
+    }
 }
@@ -0,0 +1,242 @@
+use crate::error::DbResult;
+use crate::models::dispute::{ReadObj, WriteObj};
+use crate::schema::pay_dispute::dsl;
+use crate::utils::json_to_string;
+use chrono::Utc;
+use diesel::{ExpressionMethods, OptionalExtension, QueryDsl, RunQueryDsl};
+use std::convert::TryInto;
+use ya_client_model::payment::Rejection;
+use ya_client_model::NodeId;
+use ya_core_model::payment::local::{Dispute, DisputeResponse, DisputeStatus, DocumentType};
+use ya_persistence::executor::{
+    do_with_transaction, readonly_transaction, AsDao, ConnType, PoolType,
+};
+
+/// Opens a new dispute of the document, replacing the previous one.
+pub fn open(
+    document_id: &str,
+    owner_id: &NodeId,
+    document_type: DocumentType,
+    peer_id: NodeId,
+    rejection: &Rejection,
+    conn: &ConnType,
+) -> DbResult<()> {
+    let dispute = WriteObj::open(
+        document_id.to_string(),
+        *owner_id,
+        document_type,
+        peer_id,
+        rejection,
+    )?;
+    diesel::delete(dsl::pay_dispute.find((document_id, owner_id))).execute(conn)?;
+    diesel::insert_into(dsl::pay_dispute)
+        .values(dispute)
+        .execute(conn)?;
+    Ok(())
+}
+
+/// Marks the dispute of the document, if any, as resolved.
+pub fn resolve(document_id: &str, owner_id: &NodeId, conn: &ConnType) -> DbResult<()> {
+    diesel::update(dsl::pay_dispute.find((document_id, owner_id)))
+        .set((
+            dsl::status.eq(DisputeStatus::Resolved.to_string()),
+            dsl::updated_ts.eq(Utc::now().naive_utc()),
+        ))
+        .execute(conn)?;
+    Ok(())
+}
+
+pub struct DisputeDao<'c> {
+    pool: &'c PoolType,
+}
+
+impl<'c> AsDao<'c> for DisputeDao<'c> {
+    fn as_dao(pool: &'c PoolType) -> Self {
+        Self { pool }
+    }
+}
+
+impl<'c> DisputeDao<'c> {
+    pub async fn get(&self, document_id: String, owner_id: NodeId) -> DbResult<Option<Dispute>> {
+        readonly_transaction(self.pool, move |conn| {
+            let dispute: Option<ReadObj> = dsl::pay_dispute
+                .find((document_id, owner_id))
+                .first(conn)
+                .optional()?;
+            dispute.map(TryInto::try_into).transpose()
+        })
+        .await
+    }
+
+    pub async fn list(
+        &self,
+        owner_id: NodeId,
+        status: Option<DisputeStatus>,
+    ) -> DbResult<Vec<Dispute>> {
+        readonly_transaction(self.pool, move |conn| {
+            let mut query = dsl::pay_dispute
+                .filter(dsl::owner_id.eq(owner_id))
+                .into_boxed();
+            if let Some(status) = status {
+                query = query.filter(dsl::status.eq(status.to_string()));
+            }
+            let disputes: Vec<ReadObj> = query.order_by(dsl::created_ts.asc()).load(conn)?;
+            disputes.into_iter().map(TryInto::try_into).collect()
+        })
+        .await
+    }
+
+    /// Records the issuer's response of an open dispute. Returns `false` if there's no such dispute.
+    pub async fn respond(
+        &self,
+        document_id: String,
+        owner_id: NodeId,
+        response: DisputeResponse,
+    ) -> DbResult<bool> {
+        let response = json_to_string(&response)?;
+        do_with_transaction(self.pool, move |conn| {
+            let updated = diesel::update(
+                dsl::pay_dispute
+                    .find((document_id, owner_id))
+                    .filter(dsl::status.ne(DisputeStatus::Resolved.to_string())),
+            )
+            .set((
+                dsl::status.eq(DisputeStatus::Responded.to_string()),
+                dsl::response.eq(response),
+                dsl::updated_ts.eq(Utc::now().naive_utc()),
+            ))
+            .execute(conn)?;
+            Ok(updated > 0)
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{owner_id, setup_db};
+    use ya_client_model::payment::RejectionReason;
+    use ya_persistence::executor::DbExecutor;
+
+    const DOCUMENT_ID: &str = "document";
+
+    fn peer_id() -> NodeId {
+        "0x0c1b6af9e3d2c7c3ec1b0d4b0ec2f4f2d2ab2c3b"
+            .parse()
+            .unwrap()
+    }
+
+    fn rejection(message: &str) -> Rejection {
+        Rejection {
+            rejection_reason: RejectionReason::IncorrectAmount,
+            total_amount_accepted: 1.into(),
+            message: Some(message.to_string()),
+        }
+    }
+
+    async fn open_dispute(db: &DbExecutor, rejection: Rejection) {
+        db.with_transaction(move |conn| {
+            open(
+                DOCUMENT_ID,
+                &owner_id(),
+                DocumentType::Invoice,
+                peer_id(),
+                &rejection,
+                conn,
+            )
+        })
+        .await
+        .unwrap();
+    }
+
+    async fn get_dispute(db: &DbExecutor) -> Dispute {
+        db.as_dao::<DisputeDao>()
+            .get(DOCUMENT_ID.to_string(), owner_id())
+            .await
+            .unwrap()
+            .unwrap()
+    }
+
+    fn response(amended_document_id: &str) -> DisputeResponse {
+        DisputeResponse {
+            message: Some("Amended".to_string()),
+            amended_document_id: Some(amended_document_id.to_string()),
+        }
+    }
+
+    #[actix_rt::test]
+    async fn test_open_respond_resolve() {
+        let db = setup_db("test_open_respond_resolve");
+        let dao: DisputeDao = db.as_dao();
+
+        open_dispute(&db, rejection("Too much")).await;
+        let dispute = get_dispute(&db).await;
+        assert_eq!(dispute.status, DisputeStatus::Open);
+        assert_eq!(dispute.document_type, DocumentType::Invoice);
+        assert_eq!(dispute.peer_id, peer_id());
+        assert_eq!(dispute.rejection, rejection("Too much"));
+        assert_eq!(dispute.response, None);
+
+        assert!(dao
+            .respond(DOCUMENT_ID.to_string(), owner_id(), response("amended"))
+            .await
+            .unwrap());
+        let dispute = get_dispute(&db).await;
+        assert_eq!(dispute.status, DisputeStatus::Responded);
+        assert_eq!(dispute.response, Some(response("amended")));
+
+        db.with_transaction(|conn| resolve(DOCUMENT_ID, &owner_id(), conn))
+            .await
+            .unwrap();
+        assert_eq!(get_dispute(&db).await.status, DisputeStatus::Resolved);
+
+        // Resolved dispute can't be responded to anymore.
+        assert!(!dao
+            .respond(DOCUMENT_ID.to_string(), owner_id(), response("late"))
+            .await
+            .unwrap());
+        assert_eq!(get_dispute(&db).await.response, Some(response("amended")));
+
+        let resolved = dao
+            .list(owner_id(), Some(DisputeStatus::Resolved))
+            .await
+            .unwrap();
+        assert_eq!(resolved.len(), 1);
+        assert!(dao
+            .list(owner_id(), Some(DisputeStatus::Open))
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[actix_rt::test]
+    async fn test_rejection_replaces_dispute() {
+        let db = setup_db("test_rejection_replaces_dispute");
+        let dao: DisputeDao = db.as_dao();
+
+        open_dispute(&db, rejection("Too much")).await;
+        dao.respond(DOCUMENT_ID.to_string(), owner_id(), response("amended"))
+            .await
+            .unwrap();
+
+        // Rejecting the document again after the response opens a new dispute.
+        open_dispute(&db, rejection("Still too much")).await;
+        let dispute = get_dispute(&db).await;
+        assert_eq!(dispute.status, DisputeStatus::Open);
+        assert_eq!(dispute.rejection, rejection("Still too much"));
+        assert_eq!(dispute.response, None);
+        assert_eq!(dao.list(owner_id(), None).await.unwrap().len(), 1);
+    }
+
+    #[actix_rt::test]
+    async fn test_respond_without_dispute() {
+        let db = setup_db("test_respond_without_dispute");
+
+        assert!(!db
+            .as_dao::<DisputeDao>()
+            .respond(DOCUMENT_ID.to_string(), owner_id(), response("amended"))
+            .await
+            .unwrap());
+    }
+}
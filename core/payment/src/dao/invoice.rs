@@ -1,8 +1,9 @@
-use crate::dao::{agreement, invoice_event};
+use crate::dao::{agreement, dispute, invoice_event};
 use crate::error::{DbError, DbResult};
 use crate::models::invoice::{equivalent, InvoiceXActivity, ReadObj, WriteObj};
 use crate::schema::pay_agreement::dsl as agreement_dsl;
 use crate::schema::pay_invoice::dsl;
+use crate::schema::pay_invoice_event::dsl as event_dsl;
 use crate::schema::pay_invoice_x_activity::dsl as activity_dsl;
 use bigdecimal::{BigDecimal, Zero};
 use chrono::{DateTime, NaiveDateTime, Utc};
@@ -11,9 +12,9 @@ use diesel::{
 };
 use std::collections::{BTreeMap, HashMap};
 use std::convert::TryFrom;
-use ya_client_model::payment::{DocumentStatus, Invoice, InvoiceEventType, NewInvoice};
+use ya_client_model::payment::{DocumentStatus, Invoice, InvoiceEventType, NewInvoice, Rejection};
 use ya_client_model::NodeId;
-use ya_core_model::payment::local::StatValue;
+use ya_core_model::payment::local::{DocumentType, StatValue};
 use ya_persistence::executor::{
     do_with_transaction, readonly_transaction, AsDao, ConnType, PoolType,
 };
@@ -161,6 +162,23 @@ impl<'c> InvoiceDao<'c> {
         .await
     }
 
+    /// Returns payment due date of the Invoice issued for the Agreement.
+    pub async fn get_payment_due_date(
+        &self,
+        agreement_id: String,
+        owner_id: NodeId,
+    ) -> DbResult<Option<NaiveDateTime>> {
+        readonly_transaction(self.pool, move |conn| {
+            Ok(dsl::pay_invoice
+                .select(dsl::payment_due_date)
+                .filter(dsl::agreement_id.eq(agreement_id))
+                .filter(dsl::owner_id.eq(owner_id))
+                .first(conn)
+                .optional()?)
+        })
+        .await
+    }
+
     pub async fn get_many(
         &self,
         invoice_ids: Vec<String>,
@@ -177,6 +195,53 @@ impl<'c> InvoiceDao<'c> {
         .await
     }
 
+    /// Issued Invoices accepted by Requestors, without activities. Settled ones included on demand.
+    pub async fn get_accepted_issued(
+        &self,
+        owner_id: Option<NodeId>,
+        include_settled: bool,
+    ) -> DbResult<Vec<Invoice>> {
+        readonly_transaction(self.pool, move |conn| {
+            let mut statuses: Vec<String> = vec![DocumentStatus::Accepted.into()];
+            if include_settled {
+                statuses.push(DocumentStatus::Settled.into());
+            }
+            let mut query = query!()
+                .filter(dsl::role.eq(Role::Provider))
+                .filter(dsl::status.eq_any(statuses))
+                .into_boxed();
+            if let Some(owner_id) = owner_id {
+                query = query.filter(dsl::owner_id.eq(owner_id));
+            }
+            let invoices: Vec<ReadObj> = query.load(conn)?;
+            invoices
+                .into_iter()
+                .map(|invoice| invoice.into_api_model(vec![]))
+                .collect()
+        })
+        .await
+    }
+
+    /// Issued Invoices not delivered to Requestors yet, without activities.
+    pub async fn get_unsent(&self, owner_id: Option<NodeId>) -> DbResult<Vec<Invoice>> {
+        readonly_transaction(self.pool, move |conn| {
+            let status: String = DocumentStatus::Issued.into();
+            let mut query = query!()
+                .filter(dsl::role.eq(Role::Provider))
+                .filter(dsl::status.eq(status))
+                .into_boxed();
+            if let Some(owner_id) = owner_id {
+                query = query.filter(dsl::owner_id.eq(owner_id));
+            }
+            let invoices: Vec<ReadObj> = query.load(conn)?;
+            invoices
+                .into_iter()
+                .map(|invoice| invoice.into_api_model(vec![]))
+                .collect()
+        })
+        .await
+    }
+
     pub async fn get_for_node_id(
         &self,
         node_id: NodeId,
@@ -258,6 +323,7 @@ impl<'c> InvoiceDao<'c> {
 
             update_status(&invoice_id, &owner_id, &status, conn)?;
             agreement::set_amount_accepted(&agreement_id, &owner_id, &amount, conn)?;
+            dispute::resolve(&invoice_id, &owner_id, conn)?;
 
             for event in events {
                 invoice_event::create::<()>(invoice_id.clone(), owner_id, event, None, conn)?;
@@ -268,20 +334,40 @@ impl<'c> InvoiceDao<'c> {
         .await
     }
 
-    // TODO: Implement reject invoice
-    // pub async fn reject(&self, invoice_id: String, owner_id: NodeId) -> DbResult<()> {
-    //     do_with_transaction(self.pool, move |conn| {
-    //         let (agreement_id, amount, role): (String, BigDecimalField, Role) = dsl::pay_invoice
-    //             .find((&invoice_id, &owner_id))
-    //             .select((dsl::agreement_id, dsl::amount, dsl::role))
-    //             .first(conn)?;
-    //         update_status(&invoice_id, &owner_id, &DocumentStatus::Accepted, conn)?;
-    //             invoice_event::create::<()>(invoice_id, owner_id, InvoiceEventType::InvoiceRejectedEvent { ... }, None, conn)?;
-    //
-    //         Ok(())
-    //     })
-    //     .await
-    // }
+    /// Marks the invoice rejected and opens its dispute with `peer_id`.
+    pub async fn reject(
+        &self,
+        invoice_id: String,
+        owner_id: NodeId,
+        peer_id: NodeId,
+        rejection: Rejection,
+    ) -> DbResult<()> {
+        do_with_transaction(self.pool, move |conn| {
+            update_status(&invoice_id, &owner_id, &DocumentStatus::Rejected, conn)?;
+            dispute::open(
+                &invoice_id,
+                &owner_id,
+                DocumentType::Invoice,
+                peer_id,
+                &rejection,
+                conn,
+            )?;
+            let event_type = InvoiceEventType::InvoiceRejectedEvent {
+                rejection: rejection.clone(),
+            };
+            // Invoice can be rejected again after the issuer's response.
+            diesel::delete(
+                event_dsl::pay_invoice_event
+                    .filter(event_dsl::invoice_id.eq(&invoice_id))
+                    .filter(event_dsl::owner_id.eq(&owner_id))
+                    .filter(event_dsl::event_type.eq(event_type.to_string())),
+            )
+            .execute(conn)?;
+            invoice_event::create(invoice_id, owner_id, event_type, Some(rejection), conn)?;
+            Ok(())
+        })
+        .await
+    }
 
     pub async fn cancel(&self, invoice_id: String, owner_id: NodeId) -> DbResult<()> {
         do_with_transaction(self.pool, move |conn| {
@@ -293,6 +379,7 @@ impl<'c> InvoiceDao<'c> {
             agreement::compute_amount_due(&agreement_id, &owner_id, conn)?;
 
             update_status(&invoice_id, &owner_id, &DocumentStatus::Cancelled, conn)?;
+            dispute::resolve(&invoice_id, &owner_id, conn)?;
             invoice_event::create::<()>(
                 invoice_id,
                 owner_id,
@@ -0,0 +1,57 @@
+use crate::error::DbResult;
+use crate::models::invoice_reminder::{ReadObj, WriteObj};
+use crate::schema::pay_invoice_reminder::dsl;
+use chrono::Utc;
+use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl};
+use std::collections::HashMap;
+use ya_client_model::NodeId;
+use ya_persistence::executor::{do_with_transaction, readonly_transaction, AsDao, PoolType};
+
+pub struct InvoiceReminderDao<'c> {
+    pool: &'c PoolType,
+}
+
+impl<'c> AsDao<'c> for InvoiceReminderDao<'c> {
+    fn as_dao(pool: &'c PoolType) -> Self {
+        Self { pool }
+    }
+}
+
+impl<'c> InvoiceReminderDao<'c> {
+    /// Reminders sent per Invoice, all owners when `owner_id` is not given.
+    pub async fn get_counts(
+        &self,
+        owner_id: Option<NodeId>,
+    ) -> DbResult<HashMap<(String, NodeId), u32>> {
+        readonly_transaction(self.pool, move |conn| {
+            let mut query = dsl::pay_invoice_reminder.into_boxed();
+            if let Some(owner_id) = owner_id {
+                query = query.filter(dsl::owner_id.eq(owner_id));
+            }
+            let reminders: Vec<ReadObj> = query.load(conn)?;
+            Ok(reminders
+                .into_iter()
+                .map(|r| ((r.invoice_id, r.owner_id), r.reminders as u32))
+                .collect())
+        })
+        .await
+    }
+
+    pub async fn sent(&self, invoice_id: String, owner_id: NodeId, reminder: u32) -> DbResult<()> {
+        do_with_transaction(self.pool, move |conn| {
+            let reminder = WriteObj {
+                invoice_id,
+                owner_id,
+                reminders: reminder as i32,
+                last_reminder_ts: Utc::now().naive_utc(),
+            };
+            diesel::delete(dsl::pay_invoice_reminder.find((&reminder.invoice_id, &owner_id)))
+                .execute(conn)?;
+            diesel::insert_into(dsl::pay_invoice_reminder)
+                .values(&reminder)
+                .execute(conn)?;
+            Ok(())
+        })
+        .await
+    }
+}
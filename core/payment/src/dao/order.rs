@@ -90,4 +90,37 @@ impl<'c> OrderDao<'c> {
         })
         .await
     }
+
+    /// Unpaid orders of the Invoice or Debit Note as `(id, driver)` tuples.
+    pub async fn get_unpaid_for_document(
+        &self,
+        document_id: String,
+    ) -> DbResult<Vec<(String, String)>> {
+        readonly_transaction(self.pool, move |conn| {
+            let orders = dsl::pay_order
+                .filter(
+                    dsl::invoice_id
+                        .eq(document_id.clone())
+                        .or(dsl::debit_note_id.eq(document_id)),
+                )
+                .filter(dsl::is_paid.eq(false))
+                .select((dsl::id, dsl::driver))
+                .load(conn)?;
+            Ok(orders)
+        })
+        .await
+    }
+
+    /// Orders scheduled to drivers and not confirmed as paid yet.
+    /// Returns `(id, driver, payment_platform, amount)` tuples.
+    pub async fn get_unpaid(&self) -> DbResult<Vec<(String, String, String, String)>> {
+        readonly_transaction(self.pool, move |conn| {
+            let orders = dsl::pay_order
+                .filter(dsl::is_paid.eq(false))
+                .select((dsl::id, dsl::driver, dsl::payment_platform, dsl::amount))
+                .load(conn)?;
+            Ok(orders)
+        })
+        .await
+    }
 }
@@ -0,0 +1,202 @@
+//! Delivery of Invoices deferred while the Node was offline. Invoices which the Provider
+//! tried to send, but couldn't reach the Requestor, are resent until it receives them.
+//! Invoices the Provider didn't ask to send are left alone. Failed sends are kept in memory,
+//! after restart the Provider has to send them again.
+//!
+//! Only Invoice delivery is deferred. Market and net operations aren't queued.
+use chrono::{DateTime, Utc};
+use futures::lock::Mutex;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::time::Duration;
+
+use ya_client_model::payment::Invoice;
+use ya_client_model::NodeId;
+use ya_core_model::journal;
+use ya_core_model::payment::public::{SendError, SendInvoice, BUS_ID as PUBLIC_SERVICE};
+use ya_net::RemoteEndpoint;
+use ya_persistence::executor::DbExecutor;
+use ya_service_bus::timeout::IntoTimeoutFuture;
+use ya_service_bus::RpcEndpoint;
+
+use crate::dao::InvoiceDao;
+use crate::error::DbResult;
+
+lazy_static::lazy_static! {
+    /// Deferred delivery is disabled unless the interval is set.
+    static ref DEFERRED_SEND_INTERVAL: Option<Duration> =
+        std::env::var("PAYMENT_DEFERRED_SEND_INTERVAL_SECS")
+            .ok()
+            .and_then(|x| x.parse().ok())
+            .map(Duration::from_secs);
+    static ref ATTEMPTS: Mutex<HashMap<String, Attempt>> = Default::default();
+}
+
+const SEND_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Clone)]
+struct Attempt {
+    count: u32,
+    timestamp: DateTime<Utc>,
+    error: String,
+    /// Requestor refused the Invoice, so it isn't resent.
+    refused: bool,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeferredOperation {
+    pub document_type: String,
+    pub document_id: String,
+    pub agreement_id: String,
+    pub recipient_id: NodeId,
+    pub issued_at: DateTime<Utc>,
+    pub attempts: u32,
+    pub last_attempt: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    pub refused: bool,
+}
+
+pub async fn run(db: DbExecutor) {
+    let interval = match *DEFERRED_SEND_INTERVAL {
+        Some(interval) => interval,
+        None => return,
+    };
+    log::info!("Deferred Invoice delivery enabled, every {:?}", interval);
+
+    loop {
+        tokio::time::sleep(interval).await;
+        if let Err(e) = resend(&db).await {
+            log::warn!("Failed to resend deferred Invoices: {}", e);
+        }
+    }
+}
+
+/// Defers delivery of `invoice_id`, which couldn't be sent to the Requestor.
+/// Does nothing when deferred delivery is disabled.
+pub async fn defer(invoice_id: &str, error: String) {
+    if DEFERRED_SEND_INTERVAL.is_none() {
+        return;
+    }
+    log::info!(
+        "Invoice [{}] not sent, deferring delivery. {}",
+        invoice_id,
+        error
+    );
+    ATTEMPTS.lock().await.insert(
+        invoice_id.to_string(),
+        Attempt {
+            count: 1,
+            timestamp: Utc::now(),
+            error,
+            refused: false,
+        },
+    );
+}
+
+async fn resend(db: &DbExecutor) -> DbResult<()> {
+    let invoices = db.as_dao::<InvoiceDao>().get_unsent(None).await?;
+    let invoices: Vec<Invoice> = {
+        let mut attempts = ATTEMPTS.lock().await;
+        // Delivered Invoices, also sent through the REST api in the meantime.
+        attempts.retain(|invoice_id, _| invoices.iter().any(|i| &i.invoice_id == invoice_id));
+        invoices
+            .into_iter()
+            .filter(|invoice| {
+                attempts
+                    .get(&invoice.invoice_id)
+                    .map(|a| !a.refused)
+                    .unwrap_or(false)
+            })
+            .collect()
+    };
+
+    // Lock isn't held while sending, which can take long.
+    for invoice in invoices {
+        let invoice_id = invoice.invoice_id.clone();
+        let result = send(db, invoice.clone()).await;
+        let mut attempts = ATTEMPTS.lock().await;
+        match result {
+            Ok(()) => {
+                log::info!(
+                    "Deferred Invoice [{}] for Agreement [{}] sent to [{}].",
+                    invoice_id,
+                    invoice.agreement_id,
+                    invoice.recipient_id
+                );
+                attempts.remove(&invoice_id);
+                journal::Event::new(journal::Category::Payment, "invoice-sent")
+                    .subject(&invoice_id)
+                    .node_id(invoice.issuer_id)
+                    .details(serde_json::json!({
+                        "agreementId": invoice.agreement_id,
+                        "recipientId": invoice.recipient_id,
+                        "deferred": true,
+                    }))
+                    .record()
+                    .await;
+            }
+            Err((error, refused)) => {
+                log::debug!("Deferred Invoice [{}] not sent: {}", invoice_id, error);
+                let attempt = attempts.entry(invoice_id).or_insert(Attempt {
+                    count: 0,
+                    timestamp: Utc::now(),
+                    error: String::new(),
+                    refused,
+                });
+                attempt.count += 1;
+                attempt.timestamp = Utc::now();
+                attempt.error = error;
+                attempt.refused = refused;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// On failure returns the error and whether the Requestor refused the Invoice.
+async fn send(db: &DbExecutor, invoice: Invoice) -> Result<(), (String, bool)> {
+    let invoice_id = invoice.invoice_id.clone();
+    let issuer_id = invoice.issuer_id;
+    let result = ya_net::from(issuer_id)
+        .to(invoice.recipient_id)
+        .service(PUBLIC_SERVICE)
+        .call(SendInvoice(invoice))
+        .timeout(Some(SEND_TIMEOUT))
+        .await;
+    match result {
+        Ok(Ok(Ok(_))) => db
+            .as_dao::<InvoiceDao>()
+            .mark_received(invoice_id, issuer_id)
+            .await
+            .map_err(|e| (e.to_string(), false)),
+        Ok(Ok(Err(e @ SendError::BadRequest(_)))) => Err((e.to_string(), true)),
+        Ok(Ok(Err(e))) => Err((e.to_string(), false)),
+        Ok(Err(e)) => Err((e.to_string(), false)),
+        Err(_) => Err((format!("Timeout after {:?}", SEND_TIMEOUT), false)),
+    }
+}
+
+/// Invoices of `owner_id` awaiting delivery, with results of send attempts.
+pub async fn status(db: &DbExecutor, owner_id: NodeId) -> DbResult<Vec<DeferredOperation>> {
+    let invoices = db.as_dao::<InvoiceDao>().get_unsent(Some(owner_id)).await?;
+    let attempts = ATTEMPTS.lock().await.clone();
+
+    Ok(invoices
+        .into_iter()
+        .filter_map(|invoice| {
+            let attempt = attempts.get(&invoice.invoice_id)?;
+            Some(DeferredOperation {
+                document_type: "invoice".to_string(),
+                agreement_id: invoice.agreement_id,
+                recipient_id: invoice.recipient_id,
+                issued_at: invoice.timestamp,
+                attempts: attempt.count,
+                last_attempt: Some(attempt.timestamp),
+                last_error: Some(attempt.error.clone()),
+                refused: attempt.refused,
+                document_id: invoice.invoice_id,
+            })
+        })
+        .collect())
+}
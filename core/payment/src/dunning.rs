@@ -0,0 +1,205 @@
+//! Provider invoice aging. Accepted Invoices left unpaid past their due date are
+//! followed by escalating reminders sent to the Requestor.
+use bigdecimal::BigDecimal;
+use chrono::{Duration, Utc};
+use std::collections::HashMap;
+
+use ya_client_model::payment::{DocumentStatus, Invoice};
+use ya_client_model::NodeId;
+use ya_core_model::journal;
+use ya_core_model::payment::local::{RequestorAging, UnpaidInvoice};
+use ya_core_model::payment::public::{RemindInvoice, BUS_ID as PUBLIC_SERVICE};
+use ya_net::RemoteEndpoint;
+use ya_persistence::executor::DbExecutor;
+use ya_service_bus::RpcEndpoint;
+
+use crate::dao::{InvoiceDao, InvoiceReminderDao};
+use crate::error::DbResult;
+
+lazy_static::lazy_static! {
+    static ref DUNNING_INTERVAL: std::time::Duration = std::time::Duration::from_secs(
+        std::env::var("PAYMENT_DUNNING_INTERVAL_SECS")
+            .ok()
+            .and_then(|x| x.parse().ok())
+            .unwrap_or(3600),
+    );
+    /// Time past the due date after which each subsequent reminder is sent.
+    static ref DUNNING_SCHEDULE: Vec<Duration> = std::env::var("PAYMENT_DUNNING_SCHEDULE_SECS")
+        .unwrap_or_else(|_| "3600,86400,604800".to_string())
+        .split(',')
+        .filter_map(|x| x.trim().parse().ok())
+        .map(Duration::seconds)
+        .collect();
+}
+
+pub async fn run(db: DbExecutor) {
+    loop {
+        tokio::time::sleep(*DUNNING_INTERVAL).await;
+        if let Err(e) = send_reminders(&db).await {
+            log::warn!("Failed to send Invoice reminders: {}", e);
+        }
+    }
+}
+
+/// Reminder level the Invoice should have reached by now, 0 if not overdue yet.
+fn due_reminder(invoice: &Invoice) -> u32 {
+    let overdue = Utc::now() - invoice.payment_due_date;
+    DUNNING_SCHEDULE.iter().filter(|d| **d <= overdue).count() as u32
+}
+
+async fn send_reminders(db: &DbExecutor) -> DbResult<()> {
+    let invoices = db
+        .as_dao::<InvoiceDao>()
+        .get_accepted_issued(None, false)
+        .await?;
+    let sent = db.as_dao::<InvoiceReminderDao>().get_counts(None).await?;
+
+    for invoice in invoices {
+        let reminder = due_reminder(&invoice);
+        let key = (invoice.invoice_id.clone(), invoice.issuer_id);
+        if reminder <= sent.get(&key).copied().unwrap_or(0) {
+            continue;
+        }
+
+        let msg = RemindInvoice {
+            invoice_id: invoice.invoice_id.clone(),
+            agreement_id: invoice.agreement_id.clone(),
+            reminder,
+        };
+        let result = match ya_net::from(invoice.issuer_id)
+            .to(invoice.recipient_id)
+            .service(PUBLIC_SERVICE)
+            .call(msg)
+            .await
+        {
+            Ok(result) => result.map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+        if let Err(e) = result {
+            log::warn!(
+                "Failed to remind Node [{}] of Invoice [{}]: {}",
+                invoice.recipient_id,
+                invoice.invoice_id,
+                e
+            );
+            continue;
+        }
+
+        log::info!(
+            "Reminder {} of overdue Invoice [{}] sent to Node [{}]",
+            reminder,
+            invoice.invoice_id,
+            invoice.recipient_id
+        );
+        db.as_dao::<InvoiceReminderDao>()
+            .sent(invoice.invoice_id.clone(), invoice.issuer_id, reminder)
+            .await?;
+        journal::Event::new(journal::Category::Payment, "invoice-reminder-sent")
+            .subject(&invoice.invoice_id)
+            .node_id(invoice.issuer_id)
+            .details(serde_json::json!({
+                "agreementId": invoice.agreement_id,
+                "recipientId": invoice.recipient_id,
+                "reminder": reminder,
+            }))
+            .record()
+            .await;
+    }
+    Ok(())
+}
+
+/// Unpaid amounts of `owner_id` Invoices grouped by Requestor, with their reliability.
+pub async fn aging_report(db: &DbExecutor, owner_id: NodeId) -> DbResult<Vec<RequestorAging>> {
+    let invoices = db
+        .as_dao::<InvoiceDao>()
+        .get_accepted_issued(Some(owner_id), true)
+        .await?;
+    let sent = db
+        .as_dao::<InvoiceReminderDao>()
+        .get_counts(Some(owner_id))
+        .await?;
+
+    let now = Utc::now();
+    let mut report: HashMap<NodeId, RequestorAging> = HashMap::new();
+    let mut prompt: HashMap<NodeId, u64> = HashMap::new();
+    let mut total: HashMap<NodeId, u64> = HashMap::new();
+    for invoice in invoices {
+        let requestor_id = invoice.recipient_id;
+        let reminders = sent
+            .get(&(invoice.invoice_id.clone(), owner_id))
+            .copied()
+            .unwrap_or(0) as u64;
+        let aging = report
+            .entry(requestor_id)
+            .or_insert_with(|| RequestorAging {
+                requestor_id,
+                ..Default::default()
+            });
+        aging.reminders_sent += reminders;
+        *total.entry(requestor_id).or_default() += 1;
+        if reminders == 0 {
+            *prompt.entry(requestor_id).or_default() += 1;
+        }
+
+        if invoice.status != DocumentStatus::Accepted {
+            continue;
+        }
+        aging.unpaid_count += 1;
+        let overdue = now - invoice.payment_due_date;
+        let bucket: &mut BigDecimal = if overdue <= Duration::zero() {
+            &mut aging.not_due
+        } else if overdue <= Duration::days(1) {
+            &mut aging.overdue_up_to_day
+        } else if overdue <= Duration::weeks(1) {
+            &mut aging.overdue_up_to_week
+        } else {
+            &mut aging.overdue_more
+        };
+        *bucket += invoice.amount;
+    }
+
+    Ok(report
+        .into_iter()
+        .map(|(requestor_id, mut aging)| {
+            let prompt = prompt.get(&requestor_id).copied().unwrap_or(0);
+            let total = total.get(&requestor_id).copied().unwrap_or(0);
+            aging.reliability = if total > 0 {
+                prompt as f64 / total as f64
+            } else {
+                1.0
+            };
+            aging
+        })
+        .collect())
+}
+
+/// Accepted Invoices still waiting for payment, oldest due first.
+pub async fn unpaid_invoices(db: &DbExecutor, owner_id: NodeId) -> DbResult<Vec<UnpaidInvoice>> {
+    let invoices = db
+        .as_dao::<InvoiceDao>()
+        .get_accepted_issued(Some(owner_id), false)
+        .await?;
+    let sent = db
+        .as_dao::<InvoiceReminderDao>()
+        .get_counts(Some(owner_id))
+        .await?;
+
+    let mut unpaid: Vec<UnpaidInvoice> = invoices
+        .into_iter()
+        .map(|invoice| UnpaidInvoice {
+            reminders_sent: sent
+                .get(&(invoice.invoice_id.clone(), owner_id))
+                .copied()
+                .unwrap_or(0) as u64,
+            invoice_id: invoice.invoice_id,
+            agreement_id: invoice.agreement_id,
+            requestor_id: invoice.recipient_id,
+            payment_platform: invoice.payment_platform,
+            payee_addr: invoice.payee_addr,
+            amount: invoice.amount,
+            payment_due_date: invoice.payment_due_date,
+        })
+        .collect();
+    unpaid.sort_by_key(|invoice| invoice.payment_due_date);
+    Ok(unpaid)
+}
@@ -1,10 +1,11 @@
 #![allow(dead_code)] // Crate under development
 #![allow(unused_variables)] // Crate under development
+use crate::dao::OrderDao;
 use crate::processor::PaymentProcessor;
-use futures::FutureExt;
 use std::time::Duration;
 use ya_core_model::payment::local as pay_local;
 use ya_persistence::executor::DbExecutor;
+use ya_service_api::recovery::{RecoveryAction, RecoveryItem};
 use ya_service_api_interfaces::*;
 use ya_service_bus::typed as bus;
 
@@ -13,18 +14,31 @@ extern crate diesel;
 
 pub mod accounts;
 pub mod api;
+pub mod budget;
 mod cli;
+pub mod cost;
 pub mod dao;
+pub mod deferred;
+pub mod dunning;
 pub mod error;
 pub mod models;
+pub mod note_interval;
+pub mod platform;
 pub mod processor;
+pub mod refill;
 pub mod schema;
 pub mod service;
+pub mod testing;
+pub mod usage;
 pub mod utils;
 mod wallet;
 
 pub mod migrations {
     #[derive(diesel_migrations::EmbedMigrations)]
+    #[cfg_attr(
+        feature = "postgres",
+        embed_migrations_options(migrations_path = "migrations-postgres")
+    )]
     struct _Dummy;
 }
 
@@ -54,6 +68,8 @@ impl PaymentService {
 
         let processor = PaymentProcessor::new(db.clone());
         self::service::bind_service(&db, processor.clone());
+        tokio::task::spawn_local(dunning::run(db.clone()));
+        tokio::task::spawn_local(deferred::run(db.clone()));
 
         tokio::task::spawn(async move {
             processor.release_allocations(false).await;
@@ -69,16 +85,32 @@ impl PaymentService {
     pub async fn shut_down() {
         log::info!("Stopping payment service... It may take up to 10 seconds to send out all transactions. Hit Ctrl+C again to interrupt and shut down immediately.");
 
-        let timeout = tokio::time::timeout(
+        let _ = tokio::time::timeout(
             *PAYMENT_SHUTDOWN_TIMEOUT,
             bus::service(pay_local::BUS_ID)
                 .call(pay_local::ShutDown::new(*PAYMENT_SHUTDOWN_TIMEOUT)),
-        );
-
-        tokio::select! {
-            _ = timeout => {},
-            _ = tokio::signal::ctrl_c().boxed() => {},
-        }
+        )
+        .await;
         log::info!("Payment service stopped.");
     }
+
+    /// Reports payment orders which drivers haven't confirmed yet.
+    /// Drivers keep their own queues, so the orders are completed when they confirm.
+    pub async fn recover<Context: Provider<Self, DbExecutor>>(
+        context: &Context,
+    ) -> anyhow::Result<Vec<RecoveryItem>> {
+        let db: DbExecutor = context.component();
+        let orders = db.as_dao::<OrderDao>().get_unpaid().await?;
+        Ok(orders
+            .into_iter()
+            .map(|(id, driver, platform, amount)| {
+                RecoveryItem::new("payment", id, "Scheduled", RecoveryAction::Resumed).details(
+                    format!(
+                        "Waiting for {} driver to pay {} on {}",
+                        driver, amount, platform
+                    ),
+                )
+            })
+            .collect())
+    }
 }
@@ -1,9 +1,14 @@
 pub mod activity;
+pub mod activity_budget;
 pub mod agreement;
+pub mod agreement_budget;
 pub mod allocation;
+pub mod allocation_refill;
 pub mod debit_note;
 pub mod debit_note_event;
+pub mod dispute;
 pub mod invoice;
 pub mod invoice_event;
+pub mod invoice_reminder;
 pub mod order;
 pub mod payment;
@@ -0,0 +1,30 @@
+use crate::schema::pay_activity_budget;
+use chrono::{NaiveDateTime, TimeZone, Utc};
+use ya_client_model::NodeId;
+use ya_core_model::payment::local::ActivityBudget;
+use ya_persistence::types::BigDecimalField;
+
+#[derive(Queryable, Debug, Identifiable, Insertable)]
+#[table_name = "pay_activity_budget"]
+#[primary_key(activity_id, owner_id)]
+pub struct WriteObj {
+    pub activity_id: String,
+    pub owner_id: NodeId,
+    pub agreement_id: String,
+    pub max_amount: BigDecimalField,
+    pub terminated_ts: Option<NaiveDateTime>,
+}
+
+pub type ReadObj = WriteObj;
+
+impl From<ReadObj> for ActivityBudget {
+    fn from(budget: ReadObj) -> Self {
+        Self {
+            activity_id: budget.activity_id,
+            agreement_id: budget.agreement_id,
+            owner_id: budget.owner_id,
+            max_amount: budget.max_amount.into(),
+            terminated_at: budget.terminated_ts.map(|ts| Utc.from_utc_datetime(&ts)),
+        }
+    }
+}
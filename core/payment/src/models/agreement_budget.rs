@@ -0,0 +1,48 @@
+use crate::schema::pay_agreement_budget;
+use chrono::{NaiveDateTime, TimeZone, Utc};
+use ya_client_model::NodeId;
+use ya_core_model::payment::local::AgreementBudget;
+use ya_persistence::types::BigDecimalField;
+
+#[derive(Queryable, Debug, Identifiable, Insertable)]
+#[table_name = "pay_agreement_budget"]
+#[primary_key(agreement_id, owner_id)]
+pub struct WriteObj {
+    pub agreement_id: String,
+    pub owner_id: NodeId,
+    pub allocation_id: Option<String>,
+    pub max_amount: Option<BigDecimalField>,
+    pub threshold: Option<f64>,
+    pub enabled: bool,
+    pub terminated_ts: Option<NaiveDateTime>,
+}
+
+impl WriteObj {
+    pub fn new(agreement_id: String, owner_id: NodeId) -> Self {
+        Self {
+            agreement_id,
+            owner_id,
+            allocation_id: None,
+            max_amount: None,
+            threshold: None,
+            enabled: true,
+            terminated_ts: None,
+        }
+    }
+}
+
+pub type ReadObj = WriteObj;
+
+impl From<ReadObj> for AgreementBudget {
+    fn from(budget: ReadObj) -> Self {
+        Self {
+            agreement_id: budget.agreement_id,
+            owner_id: budget.owner_id,
+            allocation_id: budget.allocation_id,
+            max_amount: budget.max_amount.map(Into::into),
+            threshold: budget.threshold,
+            enabled: budget.enabled,
+            terminated_at: budget.terminated_ts.map(|ts| Utc.from_utc_datetime(&ts)),
+        }
+    }
+}
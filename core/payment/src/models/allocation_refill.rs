@@ -0,0 +1,51 @@
+use crate::schema::pay_allocation_refill;
+use chrono::{NaiveDateTime, TimeZone, Utc};
+use ya_client_model::NodeId;
+use ya_core_model::payment::local::AllocationRefill;
+use ya_persistence::types::BigDecimalField;
+
+#[derive(Queryable, Debug, Identifiable, Insertable)]
+#[table_name = "pay_allocation_refill"]
+#[primary_key(allocation_id, owner_id)]
+pub struct WriteObj {
+    pub allocation_id: String,
+    pub owner_id: NodeId,
+    pub amount: BigDecimalField,
+    pub threshold: BigDecimalField,
+    pub max_total_amount: Option<BigDecimalField>,
+    pub refilled_amount: BigDecimalField,
+    pub enabled: bool,
+    pub refilled_ts: Option<NaiveDateTime>,
+}
+
+impl WriteObj {
+    pub fn new(allocation_id: String, owner_id: NodeId, amount: BigDecimalField) -> Self {
+        Self {
+            allocation_id,
+            owner_id,
+            threshold: amount.clone(),
+            amount,
+            max_total_amount: None,
+            refilled_amount: Default::default(),
+            enabled: true,
+            refilled_ts: None,
+        }
+    }
+}
+
+pub type ReadObj = WriteObj;
+
+impl From<ReadObj> for AllocationRefill {
+    fn from(refill: ReadObj) -> Self {
+        Self {
+            allocation_id: refill.allocation_id,
+            owner_id: refill.owner_id,
+            amount: refill.amount.into(),
+            threshold: refill.threshold.into(),
+            max_total_amount: refill.max_total_amount.map(Into::into),
+            refilled_amount: refill.refilled_amount.into(),
+            enabled: refill.enabled,
+            refilled_at: refill.refilled_ts.map(|ts| Utc.from_utc_datetime(&ts)),
+        }
+    }
+}
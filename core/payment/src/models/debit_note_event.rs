@@ -63,10 +63,13 @@ impl TryFrom<ReadObj> for DebitNoteEvent {
                 &event.event_type, e
             ))
         })?;
-        // TODO Attach details when event_type=REJECTED
-        let _details = match event.details {
-            Some(s) => Some(json_from_str(&s)?),
-            None => None,
+        let event_type = match (event_type, event.details) {
+            (DebitNoteEventType::DebitNoteRejectedEvent { .. }, Some(details)) => {
+                DebitNoteEventType::DebitNoteRejectedEvent {
+                    rejection: json_from_str(&details)?,
+                }
+            }
+            (event_type, _) => event_type,
         };
         Ok(Self {
             debit_note_id: event.debit_note_id,
@@ -0,0 +1,75 @@
+use crate::error::{DbError, DbResult};
+use crate::schema::pay_dispute;
+use crate::utils::{json_from_str, json_to_string};
+use chrono::{NaiveDateTime, TimeZone, Utc};
+use std::convert::TryFrom;
+use ya_client_model::payment::Rejection;
+use ya_client_model::NodeId;
+use ya_core_model::payment::local::{Dispute, DisputeStatus, DocumentType};
+
+#[derive(Queryable, Debug, Identifiable, Insertable)]
+#[table_name = "pay_dispute"]
+#[primary_key(document_id, owner_id)]
+pub struct WriteObj {
+    pub document_id: String,
+    pub owner_id: NodeId,
+    pub document_type: String,
+    pub peer_id: NodeId,
+    pub status: String,
+    pub rejection: String,
+    pub response: Option<String>,
+    pub created_ts: NaiveDateTime,
+    pub updated_ts: NaiveDateTime,
+}
+
+impl WriteObj {
+    pub fn open(
+        document_id: String,
+        owner_id: NodeId,
+        document_type: DocumentType,
+        peer_id: NodeId,
+        rejection: &Rejection,
+    ) -> DbResult<Self> {
+        let now = Utc::now().naive_utc();
+        Ok(Self {
+            document_id,
+            owner_id,
+            document_type: document_type.to_string(),
+            peer_id,
+            status: DisputeStatus::Open.to_string(),
+            rejection: json_to_string(rejection)?,
+            response: None,
+            created_ts: now,
+            updated_ts: now,
+        })
+    }
+}
+
+pub type ReadObj = WriteObj;
+
+impl TryFrom<ReadObj> for Dispute {
+    type Error = DbError;
+
+    fn try_from(dispute: ReadObj) -> DbResult<Self> {
+        let parse_error = |field: &str, value: &str| {
+            DbError::Integrity(format!("Dispute {} `{}` parsing failed", field, value))
+        };
+        Ok(Self {
+            document_type: dispute
+                .document_type
+                .parse()
+                .map_err(|_| parse_error("document type", &dispute.document_type))?,
+            status: dispute
+                .status
+                .parse()
+                .map_err(|_| parse_error("status", &dispute.status))?,
+            rejection: json_from_str(&dispute.rejection)?,
+            response: dispute.response.as_deref().map(json_from_str).transpose()?,
+            created_at: Utc.from_utc_datetime(&dispute.created_ts),
+            updated_at: Utc.from_utc_datetime(&dispute.updated_ts),
+            document_id: dispute.document_id,
+            owner_id: dispute.owner_id,
+            peer_id: dispute.peer_id,
+        })
+    }
+}
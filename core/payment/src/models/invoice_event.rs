@@ -65,10 +65,13 @@ impl TryFrom<ReadObj> for InvoiceEvent {
             ))
         })?;
 
-        // TODO Attach details when event_type=REJECTED
-        let _details = match event.details {
-            Some(s) => Some(json_from_str(&s)?),
-            None => None,
+        let event_type = match (event_type, event.details) {
+            (InvoiceEventType::InvoiceRejectedEvent { .. }, Some(details)) => {
+                InvoiceEventType::InvoiceRejectedEvent {
+                    rejection: json_from_str(&details)?,
+                }
+            }
+            (event_type, _) => event_type,
         };
 
         Ok(Self {
@@ -0,0 +1,15 @@
+use crate::schema::pay_invoice_reminder;
+use chrono::NaiveDateTime;
+use ya_client_model::NodeId;
+
+#[derive(Queryable, Debug, Identifiable, Insertable)]
+#[table_name = "pay_invoice_reminder"]
+#[primary_key(invoice_id, owner_id)]
+pub struct WriteObj {
+    pub invoice_id: String,
+    pub owner_id: NodeId,
+    pub reminders: i32,
+    pub last_reminder_ts: NaiveDateTime,
+}
+
+pub type ReadObj = WriteObj;
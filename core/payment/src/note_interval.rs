@@ -0,0 +1,179 @@
+//! Requestor side enforcement of Debit Note terms negotiated in the Agreement.
+//! Debit Notes issued more often than `debit-note.interval-sec?` are refused,
+//! and Debit Notes left unaccepted past `debit-notes.accept-timeout?` are reported,
+//! since Provider will break the Agreement for them.
+use chrono::{DateTime, TimeZone, Utc};
+use serde_json::Value;
+use std::time::Duration;
+
+use ya_agreement_utils::agreement::{expand, TypedPointer};
+use ya_client_model::market::Agreement;
+use ya_client_model::payment::{DebitNote, DocumentStatus};
+use ya_client_model::NodeId;
+use ya_core_model::journal;
+use ya_persistence::executor::DbExecutor;
+
+use crate::dao::DebitNoteDao;
+
+const INTERVAL_POINTER: &str = "/golem/com/scheme/payu/debit-note/interval-sec?";
+const ACCEPT_TIMEOUT_POINTER: &str = "/golem/com/payment/debit-notes/accept-timeout?";
+
+lazy_static::lazy_static! {
+    /// Share of the interval, by which Debit Note may come too early,
+    /// to tolerate clock skew and delayed sending of the previous one.
+    static ref INTERVAL_TOLERANCE: f64 = std::env::var("PAYMENT_DEBIT_NOTE_INTERVAL_TOLERANCE")
+        .ok()
+        .and_then(|x| x.parse().ok())
+        .unwrap_or(0.1);
+}
+
+/// Debit Note terms of the Agreement, both are optional.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct DebitNoteTerms {
+    pub interval: Option<Duration>,
+    pub accept_timeout: Option<Duration>,
+}
+
+impl DebitNoteTerms {
+    pub fn from_agreement(agreement: &Agreement) -> Self {
+        let offer = expand(agreement.offer.properties.clone());
+        let seconds = |pointer: &str| {
+            offer
+                .pointer(pointer)
+                .as_typed(Value::as_f64)
+                .ok()
+                .filter(|secs| secs.is_finite() && *secs >= 0.0)
+                .map(Duration::from_secs_f64)
+        };
+        DebitNoteTerms {
+            interval: seconds(INTERVAL_POINTER),
+            accept_timeout: seconds(ACCEPT_TIMEOUT_POINTER),
+        }
+    }
+
+    /// Checks that `current` Debit Note wasn't issued too soon after the `previous` one.
+    pub fn check_interval(
+        &self,
+        previous: DateTime<Utc>,
+        current: DateTime<Utc>,
+    ) -> Result<(), String> {
+        let interval = match self.interval {
+            Some(interval) => interval,
+            None => return Ok(()),
+        };
+        let min_interval = interval.mul_f64(1.0 - INTERVAL_TOLERANCE.clamp(0.0, 1.0));
+        let elapsed = (current - previous).to_std().unwrap_or_default();
+        match elapsed < min_interval {
+            true => Err(format!(
+                "DebitNote issued {}s after the previous one, negotiated interval is {}s",
+                elapsed.as_secs(),
+                interval.as_secs()
+            )),
+            false => Ok(()),
+        }
+    }
+}
+
+/// Refuses Debit Note issued before the negotiated interval since the previous one.
+///
+/// Only Debit Notes stored by the Requestor count, so refused ones don't move
+/// the interval. Provider repeats sending the final Debit Note of the activity
+/// with a new timestamp until it's received, so it's delayed, but not lost.
+pub async fn check_received(
+    db: &DbExecutor,
+    terms: &DebitNoteTerms,
+    debit_note: &DebitNote,
+    owner_id: NodeId,
+) -> Result<(), String> {
+    if terms.interval.is_none() {
+        return Ok(());
+    }
+    match db
+        .as_dao::<DebitNoteDao>()
+        .last_timestamp(debit_note.activity_id.clone(), owner_id)
+        .await
+    {
+        Ok(Some(previous)) => {
+            terms.check_interval(Utc.from_utc_datetime(&previous), debit_note.timestamp)
+        }
+        Ok(None) => Ok(()),
+        Err(e) => {
+            log::warn!("Failed to check DebitNote interval: {}", e);
+            Ok(())
+        }
+    }
+}
+
+/// Reports the Debit Note if it's still not accepted, when the negotiated
+/// accept timeout elapses.
+pub async fn watch_acceptance(
+    db: DbExecutor,
+    terms: DebitNoteTerms,
+    debit_note_id: String,
+    owner_id: NodeId,
+) {
+    let timeout = match terms.accept_timeout {
+        Some(timeout) => timeout,
+        None => return,
+    };
+    tokio::time::sleep(timeout).await;
+
+    let debit_note = match db
+        .as_dao::<DebitNoteDao>()
+        .get(debit_note_id.clone(), owner_id)
+        .await
+    {
+        Ok(Some(debit_note)) => debit_note,
+        Ok(None) => return,
+        Err(e) => {
+            log::warn!("Failed to check acceptance of DebitNote [{debit_note_id}]: {e}");
+            return;
+        }
+    };
+    if !matches!(debit_note.status, DocumentStatus::Received) {
+        return;
+    }
+
+    log::warn!(
+        "DebitNote [{}] for Activity [{}] not accepted within {:?}. Provider may break Agreement [{}].",
+        debit_note_id,
+        debit_note.activity_id,
+        timeout,
+        debit_note.agreement_id
+    );
+    journal::Event::new(
+        journal::Category::Payment,
+        "debit-note-accept-deadline-missed",
+    )
+    .subject(&debit_note_id)
+    .node_id(owner_id)
+    .details(serde_json::json!({
+        "agreementId": debit_note.agreement_id,
+        "activityId": debit_note.activity_id,
+        "acceptTimeoutSecs": timeout.as_secs(),
+    }))
+    .record()
+    .await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_interval() {
+        let terms = DebitNoteTerms {
+            interval: Some(Duration::from_secs(120)),
+            accept_timeout: None,
+        };
+        let previous = Utc::now();
+        let at = |secs| previous + chrono::Duration::seconds(secs);
+
+        assert!(terms.check_interval(previous, at(120)).is_ok());
+        assert!(terms.check_interval(previous, at(110)).is_ok());
+        assert!(terms.check_interval(previous, at(60)).is_err());
+        assert!(DebitNoteTerms::default()
+            .check_interval(previous, at(1))
+            .is_ok());
+    }
+}
@@ -0,0 +1,194 @@
+//! Aliases of payment platforms, so Allocations and accounts created for a renamed
+//! or deprecated network keep working. Aliases are configured with
+//! `PAYMENT_PLATFORM_ALIASES`, e.g. `erc20-rinkeby-tglm=erc20-goerli-tglm`.
+//! Also comparison of fees on the platforms, for picking the one to pay on.
+use std::collections::HashMap;
+
+use ya_core_model::driver::FeeEstimate;
+use ya_core_model::payment::local::FeeComparison;
+
+/// Upper bound of followed aliases, guards against cyclic configuration.
+const MAX_ALIAS_DEPTH: usize = 8;
+
+lazy_static::lazy_static! {
+    static ref ALIASES: HashMap<String, String> = parse_aliases(
+        &std::env::var("PAYMENT_PLATFORM_ALIASES").unwrap_or_default()
+    );
+}
+
+fn parse_aliases(aliases: &str) -> HashMap<String, String> {
+    aliases
+        .split(',')
+        .filter_map(|alias| {
+            let (from, to) = alias.split_once('=')?;
+            let (from, to) = (from.trim(), to.trim());
+            match split(from).is_some() && split(to).is_some() && from != to {
+                true => Some((from.to_string(), to.to_string())),
+                false => {
+                    log::warn!("Invalid payment platform alias [{}]", alias);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// Splits `driver-network-token` platform into its parts.
+pub fn split(platform: &str) -> Option<(&str, &str, &str)> {
+    let mut parts = platform.split('-');
+    match (parts.next(), parts.next(), parts.next(), parts.next()) {
+        (Some(driver), Some(network), Some(token), None)
+            if !driver.is_empty() && !network.is_empty() && !token.is_empty() =>
+        {
+            Some((driver, network, token))
+        }
+        _ => None,
+    }
+}
+
+/// Configured `(from, to)` platform aliases.
+pub fn aliases() -> Vec<(String, String)> {
+    ALIASES
+        .iter()
+        .map(|(from, to)| (from.clone(), resolve_with(&ALIASES, to)))
+        .collect()
+}
+
+/// Current name of the `platform`, or `platform` itself when not aliased.
+pub fn resolve(platform: &str) -> String {
+    resolve_with(&ALIASES, platform)
+}
+
+fn resolve_with(aliases: &HashMap<String, String>, platform: &str) -> String {
+    let mut current = platform;
+    for _ in 0..MAX_ALIAS_DEPTH {
+        match aliases.get(current) {
+            Some(next) => current = next,
+            None => break,
+        }
+    }
+    current.to_string()
+}
+
+/// Network the account on `driver` and `network` should be moved to, if any.
+/// Only aliases which keep the driver are applicable to accounts.
+pub fn resolve_network(
+    aliases: &[(String, String)],
+    driver: &str,
+    network: &str,
+) -> Option<String> {
+    aliases.iter().find_map(|(from, to)| {
+        let (from_driver, from_network, _) = split(from)?;
+        let (to_driver, to_network, _) = split(to)?;
+        match from_driver == driver && from_network == network && to_driver == driver {
+            true => Some(to_network.to_string()),
+            false => None,
+        }
+    })
+}
+
+/// Orders `estimates` cheapest first and recommends the platform with the lowest fiat cost.
+/// Estimates without a fiat cost are listed last and never recommended, as their native
+/// currencies can't be compared.
+pub fn compare_fees(payments: u32, mut estimates: Vec<FeeEstimate>) -> FeeComparison {
+    estimates.sort_by(|a, b| match (&a.fiat_cost, &b.fiat_cost) {
+        (Some(a), Some(b)) => a.cmp(b),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => a.platform.cmp(&b.platform),
+    });
+    let recommended = estimates
+        .first()
+        .filter(|estimate| estimate.fiat_cost.is_some())
+        .map(|estimate| estimate.platform.clone());
+    FeeComparison {
+        payments,
+        estimates,
+        recommended,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_aliases() {
+        let aliases = parse_aliases(
+            "erc20-rinkeby-tglm=erc20-goerli-tglm, erc20-goerli-tglm=erc20-holesky-tglm,invalid=erc20-x-y",
+        );
+        assert_eq!(aliases.len(), 2);
+        assert_eq!(
+            resolve_with(&aliases, "erc20-rinkeby-tglm"),
+            "erc20-holesky-tglm"
+        );
+        assert_eq!(
+            resolve_with(&aliases, "erc20-polygon-glm"),
+            "erc20-polygon-glm"
+        );
+
+        let cyclic = parse_aliases("a-b-c=d-e-f,d-e-f=a-b-c");
+        assert_eq!(resolve_with(&cyclic, "a-b-c"), "a-b-c");
+    }
+
+    #[test]
+    fn test_resolve_network() {
+        let aliases = vec![
+            (
+                "erc20-rinkeby-tglm".to_string(),
+                "erc20-goerli-tglm".to_string(),
+            ),
+            (
+                "zksync-rinkeby-tglm".to_string(),
+                "erc20-goerli-tglm".to_string(),
+            ),
+        ];
+        assert_eq!(
+            resolve_network(&aliases, "erc20", "rinkeby"),
+            Some("goerli".to_string())
+        );
+        assert_eq!(resolve_network(&aliases, "zksync", "rinkeby"), None);
+        assert_eq!(resolve_network(&aliases, "erc20", "polygon"), None);
+    }
+
+    #[test]
+    fn test_compare_fees() {
+        let estimate = |platform: &str, fiat_cost: Option<i64>| FeeEstimate {
+            platform: platform.to_string(),
+            network: String::new(),
+            currency: String::new(),
+            gas_price: Default::default(),
+            gas_per_payment: 55_000,
+            cost: Default::default(),
+            fiat_currency: "usd".to_string(),
+            fiat_price: None,
+            fiat_cost: fiat_cost.map(Into::into),
+        };
+
+        let comparison = compare_fees(
+            10,
+            vec![
+                estimate("erc20-goerli-tglm", None),
+                estimate("erc20-mainnet-glm", Some(40)),
+                estimate("erc20-polygon-glm", Some(1)),
+            ],
+        );
+        assert_eq!(comparison.recommended.as_deref(), Some("erc20-polygon-glm"));
+        let platforms = comparison
+            .estimates
+            .iter()
+            .map(|estimate| estimate.platform.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            platforms,
+            vec![
+                "erc20-polygon-glm",
+                "erc20-mainnet-glm",
+                "erc20-goerli-tglm"
+            ]
+        );
+
+        let comparison = compare_fees(10, vec![estimate("erc20-goerli-tglm", None)]);
+        assert_eq!(comparison.recommended, None);
+    }
+}
@@ -16,12 +16,14 @@ use ya_client_model::payment::{
     Account, ActivityPayment, AgreementPayment, DriverDetails, Network, Payment,
 };
 use ya_core_model::driver::{
-    self, driver_bus_id, AccountMode, GasDetails, PaymentConfirmation, PaymentDetails, ShutDown,
-    ValidateAllocation,
+    self, driver_bus_id, AccountMode, DriverCapabilities, FeeEstimate, GasDetails,
+    PaymentConfirmation, PaymentDetails, PaymentPriority, Readiness, ShutDown, ValidateAllocation,
 };
+use ya_core_model::journal;
 use ya_core_model::payment::local::{
-    NotifyPayment, RegisterAccount, RegisterAccountError, RegisterDriver, RegisterDriverError,
-    SchedulePayment, UnregisterAccount, UnregisterDriver,
+    NotifyPayment, PaymentTitle, RegisterAccount, RegisterAccountError, RegisterDriver,
+    RegisterDriverError, RegisterExternalDriver, SchedulePayment, UnregisterAccount,
+    UnregisterDriver,
 };
 use ya_core_model::payment::public::{SendPayment, BUS_ID};
 use ya_net::RemoteEndpoint;
@@ -33,6 +35,14 @@ fn driver_endpoint(driver: &str) -> Endpoint {
     bus::service(driver_bus_id(driver))
 }
 
+/// Invoices settle Agreements, so they go before Debit Notes.
+fn payment_priority(title: &PaymentTitle) -> PaymentPriority {
+    match title {
+        PaymentTitle::Invoice(_) => PaymentPriority::High,
+        PaymentTitle::DebitNote(_) => PaymentPriority::Normal,
+    }
+}
+
 async fn validate_orders(
     orders: &Vec<DbOrder>,
     platform: &str,
@@ -79,6 +89,18 @@ struct AccountDetails {
     pub mode: AccountMode,
 }
 
+fn to_account(platform: &str, address: &str, details: &AccountDetails) -> Account {
+    Account {
+        platform: platform.to_owned(),
+        address: address.to_owned(),
+        driver: details.driver.clone(),
+        network: details.network.clone(),
+        token: details.token.clone(),
+        send: details.mode.contains(AccountMode::SEND),
+        receive: details.mode.contains(AccountMode::RECV),
+    }
+}
+
 #[derive(Clone, Default)]
 struct DriverRegistry {
     accounts: HashMap<(String, String), AccountDetails>,
@@ -86,6 +108,9 @@ struct DriverRegistry {
     drivers: HashMap<String, DriverDetails>,
     // driver_name -> details
     platforms: HashMap<String, HashMap<String, bool>>, // platform -> (driver_name -> recv_init_required)
+    capabilities: HashMap<String, DriverCapabilities>, // driver_name -> optional operations
+    external: HashMap<String, u64>, // driver_name -> registration id, for drivers in separate processes
+    last_registration: u64,
 }
 
 impl DriverRegistry {
@@ -119,10 +144,59 @@ impl DriverRegistry {
                     .insert(driver_name.clone(), details.recv_init_required);
             }
         }
+        self.capabilities
+            .insert(driver_name.clone(), DriverCapabilities::ALL);
         self.drivers.insert(driver_name, details);
         Ok(())
     }
 
+    /// Returns id of the registration, which changes when the driver registers again.
+    pub fn register_external_driver(
+        &mut self,
+        msg: RegisterExternalDriver,
+    ) -> Result<u64, RegisterDriverError> {
+        let RegisterExternalDriver {
+            driver_name,
+            protocol_version,
+            capabilities,
+            details,
+        } = msg;
+
+        if protocol_version != driver::PROTOCOL_VERSION {
+            return Err(RegisterDriverError::UnsupportedProtocolVersion(
+                protocol_version,
+                driver::PROTOCOL_VERSION,
+            ));
+        }
+        if self.drivers.contains_key(&driver_name) {
+            if !self.external.contains_key(&driver_name) {
+                return Err(RegisterDriverError::AlreadyRegistered(driver_name));
+            }
+            // Restarted driver, it registers its accounts again.
+            self.unregister_driver(UnregisterDriver(driver_name.clone()));
+        }
+
+        self.register_driver(RegisterDriver {
+            driver_name: driver_name.clone(),
+            details,
+        })?;
+        self.capabilities.insert(driver_name.clone(), capabilities);
+        self.last_registration += 1;
+        self.external.insert(driver_name, self.last_registration);
+        Ok(self.last_registration)
+    }
+
+    pub fn is_registered_external(&self, driver_name: &str, registration: u64) -> bool {
+        self.external.get(driver_name) == Some(&registration)
+    }
+
+    pub fn supports(&self, driver_name: &str, capability: DriverCapabilities) -> bool {
+        self.capabilities
+            .get(driver_name)
+            .map(|capabilities| capabilities.contains(capability))
+            .unwrap_or(false)
+    }
+
     pub fn unregister_driver(&mut self, msg: UnregisterDriver) {
         let driver_name = msg.0;
         let details = self.drivers.remove(&driver_name);
@@ -136,6 +210,8 @@ impl DriverRegistry {
                 }
             }
         }
+        self.capabilities.remove(&driver_name);
+        self.external.remove(&driver_name);
         self.accounts
             .retain(|_, details| details.driver != driver_name);
     }
@@ -195,18 +271,30 @@ impl DriverRegistry {
     pub fn get_accounts(&self) -> Vec<Account> {
         self.accounts
             .iter()
-            .map(|((platform, address), details)| Account {
-                platform: platform.clone(),
-                address: address.clone(),
-                driver: details.driver.clone(),
-                network: details.network.clone(),
-                token: details.token.clone(),
-                send: details.mode.contains(AccountMode::SEND),
-                receive: details.mode.contains(AccountMode::RECV),
-            })
+            .map(|((platform, address), details)| to_account(platform, address, details))
             .collect()
     }
 
+    pub fn get_account(&self, platform: &str, address: &str) -> Option<Account> {
+        self.accounts
+            .get(&(platform.to_owned(), address.to_owned()))
+            .map(|details| to_account(platform, address, details))
+    }
+
+    /// Replaces mode of the registered account, unlike registration which only adds modes.
+    pub fn set_account_mode(
+        &mut self,
+        platform: &str,
+        address: &str,
+        mode: AccountMode,
+    ) -> Option<Account> {
+        let details = self
+            .accounts
+            .get_mut(&(platform.to_owned(), address.to_owned()))?;
+        details.mode = mode;
+        Some(to_account(platform, address, details))
+    }
+
     pub fn get_driver(&self, driver: &str) -> Result<&DriverDetails, RegisterAccountError> {
         match self.drivers.get(driver) {
             None => Err(RegisterAccountError::DriverNotRegistered(driver.into())),
@@ -310,10 +398,22 @@ impl PaymentProcessor {
         self.registry.register_driver(msg)
     }
 
+    pub async fn register_external_driver(
+        &mut self,
+        msg: RegisterExternalDriver,
+    ) -> Result<u64, RegisterDriverError> {
+        self.registry.register_external_driver(msg)
+    }
+
     pub async fn unregister_driver(&mut self, msg: UnregisterDriver) {
         self.registry.unregister_driver(msg)
     }
 
+    pub fn is_registered_external(&self, driver_name: &str, registration: u64) -> bool {
+        self.registry
+            .is_registered_external(driver_name, registration)
+    }
+
     pub async fn register_account(
         &mut self,
         msg: RegisterAccount,
@@ -329,6 +429,19 @@ impl PaymentProcessor {
         self.registry.get_accounts()
     }
 
+    pub fn get_account(&self, platform: &str, address: &str) -> Option<Account> {
+        self.registry.get_account(platform, address)
+    }
+
+    pub fn set_account_mode(
+        &mut self,
+        platform: &str,
+        address: &str,
+        mode: AccountMode,
+    ) -> Option<Account> {
+        self.registry.set_account_mode(platform, address, mode)
+    }
+
     pub async fn get_drivers(&self) -> HashMap<String, DriverDetails> {
         self.registry.get_drivers()
     }
@@ -422,10 +535,41 @@ impl PaymentProcessor {
             activity_payment.allocation_id = None;
         }
 
+        // Simulated payments settle our orders, so the dry-run can go on, but nothing was
+        // transferred. They aren't sent to the provider and don't count as payments sent.
+        if msg.simulated {
+            log::info!(
+                "Simulated payment [{}] of {} recorded, not sending it to the provider.",
+                payment.payment_id,
+                msg.amount
+            );
+            journal::Event::new(journal::Category::Payment, "payment-simulated")
+                .subject(&payment.payment_id)
+                .node_id(payer_id)
+                .details(serde_json::json!({
+                    "payeeId": payee_id,
+                    "amount": msg.amount.to_string(),
+                    "platform": payment_platform,
+                }))
+                .record()
+                .await;
+            return Ok(());
+        }
+
         let signature = driver_endpoint(&driver)
             .send(driver::SignPayment(payment.clone()))
             .await??;
 
+        journal::Event::new(journal::Category::Payment, "payment-sent")
+            .subject(&payment.payment_id)
+            .node_id(payer_id)
+            .details(serde_json::json!({
+                "payeeId": payee_id,
+                "amount": msg.amount.to_string(),
+                "platform": payment_platform,
+            }))
+            .record()
+            .await;
         counter!("payment.amount.sent", ya_metrics::utils::cryptocurrency_to_u64(&msg.amount), "platform" => payment_platform);
         let msg = SendPayment::new(payment, signature);
 
@@ -461,13 +605,16 @@ impl PaymentProcessor {
             self.registry
                 .driver(&msg.payment_platform, &msg.payer_addr, AccountMode::SEND)?;
         let order_id = driver_endpoint(&driver)
-            .send(driver::SchedulePayment::new(
-                amount,
-                msg.payer_addr.clone(),
-                msg.payee_addr.clone(),
-                msg.payment_platform.clone(),
-                msg.due_date,
-            ))
+            .send(
+                driver::SchedulePayment::new(
+                    amount,
+                    msg.payer_addr.clone(),
+                    msg.payee_addr.clone(),
+                    msg.payment_platform.clone(),
+                    msg.due_date,
+                )
+                .with_priority(payment_priority(&msg.title)),
+            )
             .await??;
 
         self.db_executor
@@ -605,6 +752,12 @@ impl PaymentProcessor {
         let driver = self
             .registry
             .driver(&platform, &address, AccountMode::empty())?;
+        if !self
+            .registry
+            .supports(&driver, DriverCapabilities::GAS_BALANCE)
+        {
+            return Ok(None);
+        }
         let amount = driver_endpoint(&driver)
             .send(driver::GetAccountGasBalance::new(address, platform))
             .await??;
@@ -612,6 +765,52 @@ impl PaymentProcessor {
         Ok(amount)
     }
 
+    /// `None` when the driver doesn't report readiness.
+    pub async fn get_readiness(
+        &self,
+        platform: String,
+        address: String,
+        amount: Option<BigDecimal>,
+    ) -> Result<Option<Readiness>, GetStatusError> {
+        let driver = self
+            .registry
+            .driver(&platform, &address, AccountMode::empty())?;
+        if !self
+            .registry
+            .supports(&driver, DriverCapabilities::READINESS)
+        {
+            return Ok(None);
+        }
+        let readiness = driver_endpoint(&driver)
+            .send(driver::GetReadiness::new(address, platform).with_amount(amount))
+            .await??;
+
+        Ok(Some(readiness))
+    }
+
+    /// `None` when the driver doesn't estimate fees.
+    pub async fn get_fee_estimate(
+        &self,
+        platform: String,
+        address: String,
+        payments: u32,
+    ) -> Result<Option<FeeEstimate>, GetStatusError> {
+        let driver = self
+            .registry
+            .driver(&platform, &address, AccountMode::empty())?;
+        if !self
+            .registry
+            .supports(&driver, DriverCapabilities::FEE_ESTIMATE)
+        {
+            return Ok(None);
+        }
+        let estimate = driver_endpoint(&driver)
+            .send(driver::GetFeeEstimate::new(platform, payments))
+            .await??;
+
+        Ok(Some(estimate))
+    }
+
     pub async fn validate_allocation(
         &self,
         platform: String,
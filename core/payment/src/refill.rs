@@ -0,0 +1,90 @@
+//! Requestor Allocation auto-refill. Allocations running low are topped up from unreserved
+//! funds of their account, so tasks aren't interrupted by an exhausted Allocation.
+use futures::lock::Mutex;
+use std::sync::Arc;
+
+use ya_client_model::NodeId;
+use ya_core_model::journal;
+use ya_persistence::executor::DbExecutor;
+
+use crate::dao::{AllocationDao, AllocationRefillDao, AllocationStatus, RefillStatus};
+use crate::processor::PaymentProcessor;
+
+/// Tops up the Allocation if its remaining amount dropped below the refill threshold.
+/// Allocations without enabled refill policy aren't checked.
+pub async fn check_allocation(
+    db: DbExecutor,
+    processor: Arc<Mutex<PaymentProcessor>>,
+    allocation_id: String,
+    owner_id: NodeId,
+) {
+    let (event, details) = match refill(&db, &processor, &allocation_id, owner_id).await {
+        Ok(RefillStatus::NotNeeded) => return,
+        Ok(RefillStatus::Refilled(amount)) => {
+            log::info!("Allocation [{}] refilled by {}", allocation_id, amount);
+            (
+                "allocation-refilled",
+                serde_json::json!({ "amount": amount.to_string() }),
+            )
+        }
+        Ok(RefillStatus::Refused(reason)) => {
+            log::warn!(
+                "Allocation [{}] running low, not refilled. {}",
+                allocation_id,
+                reason
+            );
+            (
+                "allocation-refill-failed",
+                serde_json::json!({ "reason": reason }),
+            )
+        }
+        Err(e) => {
+            log::warn!("Failed to refill Allocation [{}]: {}", allocation_id, e);
+            return;
+        }
+    };
+    journal::Event::new(journal::Category::Payment, event)
+        .subject(&allocation_id)
+        .node_id(owner_id)
+        .details(details)
+        .record()
+        .await;
+}
+
+async fn refill(
+    db: &DbExecutor,
+    processor: &Mutex<PaymentProcessor>,
+    allocation_id: &str,
+    owner_id: NodeId,
+) -> anyhow::Result<RefillStatus> {
+    let refill = match db
+        .as_dao::<AllocationRefillDao>()
+        .get(allocation_id.to_string(), owner_id)
+        .await?
+    {
+        Some(refill) if refill.enabled => refill,
+        _ => return Ok(RefillStatus::NotNeeded),
+    };
+    let allocation = match db
+        .as_dao::<AllocationDao>()
+        .get(allocation_id.to_string(), owner_id)
+        .await?
+    {
+        AllocationStatus::Active(allocation) => allocation,
+        _ => return Ok(RefillStatus::NotNeeded),
+    };
+    // Rechecked by `refilled`, it only saves asking the driver for the balance.
+    if allocation.remaining_amount >= refill.threshold {
+        return Ok(RefillStatus::NotNeeded);
+    }
+
+    let balance = processor
+        .lock()
+        .await
+        .get_status(allocation.payment_platform, allocation.address)
+        .await?;
+    Ok(db
+        .as_dao::<AllocationRefillDao>()
+        .refilled(allocation_id.to_string(), owner_id, balance)
+        .await?)
+}
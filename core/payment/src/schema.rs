@@ -11,6 +11,16 @@ table! {
     }
 }
 
+table! {
+    pay_activity_budget (activity_id, owner_id) {
+        activity_id -> Text,
+        owner_id -> Text,
+        agreement_id -> Text,
+        max_amount -> Text,
+        terminated_ts -> Nullable<Timestamp>,
+    }
+}
+
 table! {
     pay_activity_payment (payment_id, activity_id, owner_id) {
         payment_id -> Text,
@@ -38,6 +48,18 @@ table! {
     }
 }
 
+table! {
+    pay_agreement_budget (agreement_id, owner_id) {
+        agreement_id -> Text,
+        owner_id -> Text,
+        allocation_id -> Nullable<Text>,
+        max_amount -> Nullable<Text>,
+        threshold -> Nullable<Double>,
+        enabled -> Bool,
+        terminated_ts -> Nullable<Timestamp>,
+    }
+}
+
 table! {
     pay_agreement_payment (payment_id, agreement_id, owner_id) {
         payment_id -> Text,
@@ -64,6 +86,19 @@ table! {
     }
 }
 
+table! {
+    pay_allocation_refill (allocation_id, owner_id) {
+        allocation_id -> Text,
+        owner_id -> Text,
+        amount -> Text,
+        threshold -> Text,
+        max_total_amount -> Nullable<Text>,
+        refilled_amount -> Text,
+        enabled -> Bool,
+        refilled_ts -> Nullable<Timestamp>,
+    }
+}
+
 table! {
     pay_debit_note (id, owner_id) {
         id -> Text,
@@ -101,6 +136,28 @@ table! {
     }
 }
 
+table! {
+    pay_debit_note_usage (debit_note_id, owner_id) {
+        debit_note_id -> Text,
+        owner_id -> Text,
+        signed_usage -> Text,
+    }
+}
+
+table! {
+    pay_dispute (document_id, owner_id) {
+        document_id -> Text,
+        owner_id -> Text,
+        document_type -> Text,
+        peer_id -> Text,
+        status -> Text,
+        rejection -> Text,
+        response -> Nullable<Text>,
+        created_ts -> Timestamp,
+        updated_ts -> Timestamp,
+    }
+}
+
 table! {
     pay_document_status (status) {
         status -> Text,
@@ -149,6 +206,15 @@ table! {
     }
 }
 
+table! {
+    pay_invoice_reminder (invoice_id, owner_id) {
+        invoice_id -> Text,
+        owner_id -> Text,
+        reminders -> Integer,
+        last_reminder_ts -> Timestamp,
+    }
+}
+
 table! {
     pay_invoice_x_activity (invoice_id, activity_id, owner_id) {
         invoice_id -> Text,
@@ -199,18 +265,24 @@ joinable!(pay_order -> pay_allocation (allocation_id));
 
 allow_tables_to_appear_in_same_query!(
     pay_activity,
+    pay_activity_budget,
     pay_activity_payment,
     pay_agreement,
+    pay_agreement_budget,
     pay_agreement_payment,
     pay_allocation,
+    pay_allocation_refill,
     pay_debit_note,
     pay_debit_note_event,
     pay_debit_note_event_read,
+    pay_debit_note_usage,
+    pay_dispute,
     pay_document_status,
     pay_event_type,
     pay_invoice,
     pay_invoice_event,
     pay_invoice_event_read,
+    pay_invoice_reminder,
     pay_invoice_x_activity,
     pay_order,
     pay_payment,
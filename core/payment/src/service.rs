@@ -22,9 +22,24 @@ mod local {
     use crate::dao::*;
     use chrono::NaiveDateTime;
     use std::collections::BTreeMap;
+    use std::time::Duration;
     use ya_client_model::payment::{Account, DocumentStatus, DriverDetails};
+    use ya_core_model::driver::{driver_bus_id, AccountMode, Ping, Readiness, SetPaymentPriority};
     use ya_core_model::payment::local::*;
     use ya_persistence::types::Role;
+    use ya_service_bus::{typed as bus, RpcEndpoint};
+
+    lazy_static::lazy_static! {
+        static ref DRIVER_PING_INTERVAL: Duration = Duration::from_secs(
+            std::env::var("PAYMENT_DRIVER_PING_INTERVAL_SECS")
+                .ok()
+                .and_then(|x| x.parse().ok())
+                .unwrap_or(30),
+        );
+    }
+
+    /// Failed pings in a row after which external driver is unregistered.
+    const DRIVER_MAX_MISSED_PINGS: u32 = 3;
 
     pub fn bind_service(db: &DbExecutor, processor: Arc<Mutex<PaymentProcessor>>) {
         log::debug!("Binding payment local service to service bus");
@@ -32,17 +47,30 @@ mod local {
         ServiceBinder::new(BUS_ID, db, processor)
             .bind_with_processor(schedule_payment)
             .bind_with_processor(register_driver)
+            .bind_with_processor(register_external_driver)
             .bind_with_processor(unregister_driver)
             .bind_with_processor(register_account)
             .bind_with_processor(unregister_account)
             .bind_with_processor(notify_payment)
             .bind_with_processor(get_status)
+            .bind_with_processor(get_readiness)
+            .bind_with_processor(compare_fees)
             .bind_with_processor(get_invoice_stats)
             .bind_with_processor(get_accounts)
+            .bind_with_processor(set_account_mode)
             .bind_with_processor(validate_allocation)
             .bind_with_processor(release_allocations)
             .bind_with_processor(get_drivers)
-            .bind_with_processor(shut_down);
+            .bind_with_processor(shut_down)
+            .bind_with_processor(set_allocation_refill)
+            .bind(verify_debit_note_usage)
+            .bind(set_agreement_budget)
+            .bind(set_activity_budget)
+            .bind(list_disputes)
+            .bind(get_invoice_aging)
+            .bind(get_unpaid_invoices)
+            .bind(migrate_platform)
+            .bind(prioritize_payments);
 
         // Initialize counters to 0 value. Otherwise they won't appear on metrics endpoint
         // until first change to value will be made.
@@ -91,7 +119,14 @@ mod local {
         sender: String,
         msg: SchedulePayment,
     ) -> Result<(), GenericError> {
+        let (allocation_id, owner_id) = (msg.allocation_id.clone(), msg.payer_id);
         processor.lock().await.schedule_payment(msg).await?;
+        tokio::task::spawn_local(crate::refill::check_allocation(
+            db,
+            processor,
+            allocation_id,
+            owner_id,
+        ));
         Ok(())
     }
 
@@ -104,6 +139,73 @@ mod local {
         processor.lock().await.register_driver(msg).await
     }
 
+    async fn register_external_driver(
+        db: DbExecutor,
+        processor: Arc<Mutex<PaymentProcessor>>,
+        sender: String,
+        msg: RegisterExternalDriver,
+    ) -> Result<(), RegisterDriverError> {
+        let driver_name = msg.driver_name.clone();
+        log::info!(
+            "Registering external payment driver '{}'. protocol={} capabilities={:?}",
+            driver_name,
+            msg.protocol_version,
+            msg.capabilities
+        );
+        let registration = processor.lock().await.register_external_driver(msg).await?;
+        tokio::task::spawn_local(watch_external_driver(processor, driver_name, registration));
+        Ok(())
+    }
+
+    /// Pings external driver until it's unregistered or registers again.
+    /// Unregisters the driver when it stops answering.
+    async fn watch_external_driver(
+        processor: Arc<Mutex<PaymentProcessor>>,
+        driver_name: String,
+        registration: u64,
+    ) {
+        let endpoint = bus::service(driver_bus_id(&driver_name));
+        let mut missed_pings = 0;
+        loop {
+            tokio::time::sleep(*DRIVER_PING_INTERVAL).await;
+            if !processor
+                .lock()
+                .await
+                .is_registered_external(&driver_name, registration)
+            {
+                return;
+            }
+
+            match tokio::time::timeout(*DRIVER_PING_INTERVAL, endpoint.call(Ping {})).await {
+                Ok(Ok(Ok(()))) => missed_pings = 0,
+                result => {
+                    missed_pings += 1;
+                    log::warn!(
+                        "External payment driver '{}' didn't answer ping ({}/{}): {:?}",
+                        driver_name,
+                        missed_pings,
+                        DRIVER_MAX_MISSED_PINGS,
+                        result
+                    );
+                }
+            }
+
+            if missed_pings >= DRIVER_MAX_MISSED_PINGS {
+                let mut processor = processor.lock().await;
+                if processor.is_registered_external(&driver_name, registration) {
+                    log::error!(
+                        "External payment driver '{}' is not responding. Unregistering.",
+                        driver_name
+                    );
+                    processor
+                        .unregister_driver(UnregisterDriver(driver_name))
+                        .await;
+                }
+                return;
+            }
+        }
+    }
+
     async fn unregister_driver(
         db: DbExecutor,
         processor: Arc<Mutex<PaymentProcessor>>,
@@ -142,6 +244,76 @@ mod local {
         Ok(processor.lock().await.get_accounts().await)
     }
 
+    async fn set_account_mode(
+        db: DbExecutor,
+        processor: Arc<Mutex<PaymentProcessor>>,
+        _caller: String,
+        msg: SetAccountMode,
+    ) -> Result<Account, GenericError> {
+        let (network, default_network, platform, current) = {
+            let processor = processor.lock().await;
+            let (network, _) = processor
+                .get_network(msg.driver.clone(), msg.network.clone())
+                .await
+                .map_err(GenericError::new)?;
+            let (default_network, _) = processor
+                .get_network(msg.driver.clone(), None)
+                .await
+                .map_err(GenericError::new)?;
+            let platform = processor
+                .get_platform(msg.driver.clone(), Some(network.clone()), msg.token.clone())
+                .await
+                .map_err(GenericError::new)?;
+            let current = processor.get_account(&platform, &msg.address);
+            (network, default_network, platform, current)
+        };
+        let (was_sending, was_receiving) = current
+            .map(|account| (account.send, account.receive))
+            .unwrap_or_default();
+
+        let account = crate::accounts::Account {
+            driver: msg.driver,
+            address: msg.address,
+            network: Some(network),
+            token: msg.token,
+            send: msg.send.unwrap_or(was_sending),
+            receive: msg.receive.unwrap_or(was_receiving),
+        };
+        // Driver registers the account, or its new modes, on initialization.
+        // Processor can't be locked meanwhile.
+        if (account.send && !was_sending) || (account.receive && !was_receiving) {
+            crate::accounts::init_account(account.clone())
+                .await
+                .map_err(GenericError::new)?;
+        }
+
+        let mut mode = AccountMode::NONE;
+        mode.set(AccountMode::SEND, account.send);
+        mode.set(AccountMode::RECV, account.receive);
+        let updated = processor
+            .lock()
+            .await
+            .set_account_mode(&platform, &account.address, mode)
+            .ok_or_else(|| {
+                GenericError::new(format!(
+                    "Account {} is not registered on platform {}",
+                    account.address, platform
+                ))
+            })?;
+        log::info!(
+            "Payment account {} on platform {} set to send: {}, receive: {}.",
+            updated.address,
+            platform,
+            updated.send,
+            updated.receive
+        );
+
+        crate::accounts::save_account(account, &default_network)
+            .await
+            .map_err(GenericError::new)?;
+        Ok(updated)
+    }
+
     async fn notify_payment(
         db: DbExecutor,
         processor: Arc<Mutex<PaymentProcessor>>,
@@ -246,6 +418,81 @@ mod local {
         })
     }
 
+    async fn get_readiness(
+        db: DbExecutor,
+        processor: Arc<Mutex<PaymentProcessor>>,
+        _caller: String,
+        msg: GetReadiness,
+    ) -> Result<Readiness, GenericError> {
+        log::debug!("get readiness: {:?}", msg);
+        let GetReadiness {
+            address,
+            driver,
+            network,
+            amount,
+        } = msg;
+
+        let processor = processor.lock().await;
+        let (network, network_details) = processor
+            .get_network(driver.clone(), network)
+            .await
+            .map_err(GenericError::new)?;
+        let platform = network_details
+            .tokens
+            .get(&network_details.default_token)
+            .cloned()
+            .ok_or_else(|| {
+                GenericError::new(format!(
+                    "Unsupported token. driver={} network={} token={}",
+                    driver, network, network_details.default_token
+                ))
+            })?;
+
+        processor
+            .get_readiness(platform, address, amount)
+            .await
+            .map_err(GenericError::new)?
+            .ok_or_else(|| {
+                GenericError::new(format!(
+                    "Readiness check not supported by {} driver",
+                    driver
+                ))
+            })
+    }
+
+    async fn compare_fees(
+        db: DbExecutor,
+        processor: Arc<Mutex<PaymentProcessor>>,
+        _caller: String,
+        msg: CompareFees,
+    ) -> Result<FeeComparison, GenericError> {
+        log::debug!("compare fees: {:?}", msg);
+        let processor = processor.lock().await;
+        let mut platforms = processor
+            .get_accounts()
+            .await
+            .into_iter()
+            .filter(|account| account.send && account.address == msg.address)
+            .map(|account| account.platform)
+            .collect::<Vec<_>>();
+        platforms.sort();
+        platforms.dedup();
+
+        let mut estimates = Vec::new();
+        for platform in platforms {
+            // Platform without an estimate is left out, not failing the whole comparison.
+            match processor
+                .get_fee_estimate(platform.clone(), msg.address.clone(), msg.payments)
+                .await
+            {
+                Ok(Some(estimate)) => estimates.push(estimate),
+                Ok(None) => log::debug!("No fee estimate for {}", platform),
+                Err(e) => log::warn!("Unable to estimate fees on {}: {}", platform, e),
+            }
+        }
+        Ok(crate::platform::compare_fees(msg.payments, estimates))
+    }
+
     async fn get_invoice_stats(
         db: DbExecutor,
         processor: Arc<Mutex<PaymentProcessor>>,
@@ -342,6 +589,181 @@ mod local {
         shutdown_future.await;
         Ok(())
     }
+
+    async fn verify_debit_note_usage(
+        db: DbExecutor,
+        _caller: String,
+        msg: VerifyDebitNoteUsage,
+    ) -> Result<UsageVerification, GenericError> {
+        let dao: DebitNoteDao = db.as_dao();
+        let debit_note = dao
+            .get(msg.debit_note_id.clone(), msg.owner_id)
+            .await
+            .map_err(GenericError::new)?
+            .ok_or_else(|| {
+                GenericError::new(format!("DebitNote [{}] not found", msg.debit_note_id))
+            })?;
+        let signed_usage = dao
+            .get_signed_usage(msg.debit_note_id, msg.owner_id)
+            .await
+            .map_err(GenericError::new)?;
+        Ok(crate::usage::verify_debit_note_usage(
+            &debit_note,
+            signed_usage,
+        ))
+    }
+
+    async fn set_agreement_budget(
+        db: DbExecutor,
+        _caller: String,
+        msg: SetAgreementBudget,
+    ) -> Result<AgreementBudget, GenericError> {
+        let budget = db
+            .as_dao::<AgreementBudgetDao>()
+            .set(msg)
+            .await
+            .map_err(GenericError::new)?;
+        crate::budget::check_agreement(db, budget.agreement_id.clone(), budget.owner_id).await;
+        Ok(budget)
+    }
+
+    async fn set_activity_budget(
+        db: DbExecutor,
+        _caller: String,
+        msg: SetActivityBudget,
+    ) -> Result<ActivityBudget, GenericError> {
+        let budget = db
+            .as_dao::<ActivityBudgetDao>()
+            .set(msg)
+            .await
+            .map_err(GenericError::new)?;
+        crate::budget::check_activity(db, budget.activity_id.clone(), budget.owner_id).await;
+        Ok(budget)
+    }
+
+    async fn set_allocation_refill(
+        db: DbExecutor,
+        processor: Arc<Mutex<PaymentProcessor>>,
+        _caller: String,
+        msg: SetAllocationRefill,
+    ) -> Result<AllocationRefill, GenericError> {
+        let refill = db
+            .as_dao::<AllocationRefillDao>()
+            .set(msg)
+            .await
+            .map_err(GenericError::new)?;
+        crate::refill::check_allocation(
+            db,
+            processor,
+            refill.allocation_id.clone(),
+            refill.owner_id,
+        )
+        .await;
+        Ok(refill)
+    }
+
+    async fn list_disputes(
+        db: DbExecutor,
+        _caller: String,
+        msg: ListDisputes,
+    ) -> Result<Vec<Dispute>, GenericError> {
+        db.as_dao::<DisputeDao>()
+            .list(msg.owner_id, msg.status)
+            .await
+            .map_err(GenericError::new)
+    }
+
+    async fn get_invoice_aging(
+        db: DbExecutor,
+        _caller: String,
+        msg: GetInvoiceAging,
+    ) -> Result<Vec<RequestorAging>, GenericError> {
+        crate::dunning::aging_report(&db, msg.owner_id)
+            .await
+            .map_err(GenericError::new)
+    }
+
+    async fn get_unpaid_invoices(
+        db: DbExecutor,
+        _caller: String,
+        msg: GetUnpaidInvoices,
+    ) -> Result<Vec<UnpaidInvoice>, GenericError> {
+        crate::dunning::unpaid_invoices(&db, msg.owner_id)
+            .await
+            .map_err(GenericError::new)
+    }
+
+    async fn migrate_platform(
+        db: DbExecutor,
+        _caller: String,
+        msg: MigratePlatform,
+    ) -> Result<Vec<PlatformMigration>, GenericError> {
+        let aliases = match (msg.from, msg.to) {
+            (Some(from), Some(to)) => vec![(from, to)],
+            (None, None) => crate::platform::aliases(),
+            _ => {
+                return Err(GenericError::new(
+                    "Both source and target platform are required",
+                ))
+            }
+        };
+
+        let dao: AllocationDao = db.as_dao();
+        let mut migrations = Vec::new();
+        for (from, to) in aliases {
+            let allocations = dao
+                .migrate_platform(from.clone(), to.clone(), msg.dry_run)
+                .await
+                .map_err(GenericError::new)?;
+            if !msg.dry_run && allocations > 0 {
+                log::info!(
+                    "Migrated {} allocation(s) from platform [{}] to [{}].",
+                    allocations,
+                    from,
+                    to
+                );
+            }
+            migrations.push(PlatformMigration {
+                from,
+                to,
+                allocations,
+            });
+        }
+        Ok(migrations)
+    }
+
+    async fn prioritize_payments(
+        db: DbExecutor,
+        _caller: String,
+        msg: PrioritizePayments,
+    ) -> Result<usize, GenericError> {
+        let orders = db
+            .as_dao::<OrderDao>()
+            .get_unpaid_for_document(msg.document_id.clone())
+            .await
+            .map_err(GenericError::new)?;
+
+        let mut prioritized = 0;
+        for (order_id, driver) in orders {
+            let updated = bus::service(driver_bus_id(&driver))
+                .send(SetPaymentPriority {
+                    order_id,
+                    priority: msg.priority,
+                })
+                .await
+                .map_err(GenericError::new)??;
+            if updated {
+                prioritized += 1;
+            }
+        }
+        log::info!(
+            "Set {} priority of {} payment(s) of [{}].",
+            msg.priority,
+            prioritized,
+            msg.document_id
+        );
+        Ok(prioritized)
+    }
 }
 
 mod public {
@@ -352,7 +774,12 @@ mod public {
     use crate::utils::*;
 
     use crate::error::processor::VerifyPaymentError;
+    use crate::note_interval::{self, DebitNoteTerms};
+    use chrono::Utc;
     use ya_client_model::payment::*;
+    use ya_client_model::NodeId;
+    use ya_core_model::journal;
+    use ya_core_model::payment::local::DocumentType;
     use ya_core_model::payment::public::*;
     use ya_persistence::types::Role;
 
@@ -364,10 +791,13 @@ mod public {
             .bind(accept_debit_note)
             .bind(reject_debit_note)
             .bind(cancel_debit_note)
+            .bind(send_debit_note_usage)
             .bind(send_invoice)
             .bind(accept_invoice)
             .bind(reject_invoice)
             .bind(cancel_invoice)
+            .bind(remind_invoice)
+            .bind(respond_to_dispute)
             .bind_with_processor(send_payment);
 
         log::debug!("Successfully bound payment public service to service bus");
@@ -417,6 +847,17 @@ mod public {
         }
 
         let node_id = *agreement.requestor_id();
+        let terms = DebitNoteTerms::from_agreement(&agreement);
+        if let Err(e) = note_interval::check_received(&db, &terms, &debit_note, node_id).await {
+            log::warn!("Refusing DebitNote [{debit_note_id}] for Activity [{activity_id}]: {e}");
+            counter!("payment.debit_notes.requestor.too_frequent", 1);
+            return Err(SendError::BadRequest(e));
+        }
+
+        let watchdog = crate::budget::check_agreement(db.clone(), agreement_id.clone(), node_id);
+        let ceiling = crate::budget::check_activity(db.clone(), activity_id.clone(), node_id);
+        let acceptance =
+            note_interval::watch_acceptance(db.clone(), terms, debit_note_id.clone(), node_id);
         match async move {
             db.as_dao::<AgreementDao>()
                 .create_if_not_exists(agreement, node_id, Role::Requestor)
@@ -436,7 +877,60 @@ mod public {
         }
         .await
         {
-            Ok(_) => Ok(Ack {}),
+            Ok(_) => {
+                tokio::task::spawn_local(watchdog);
+                tokio::task::spawn_local(ceiling);
+                tokio::task::spawn_local(acceptance);
+                Ok(Ack {})
+            }
+            Err(DbError::Query(e)) => Err(SendError::BadRequest(e)),
+            Err(e) => Err(SendError::ServiceError(e.to_string())),
+        }
+    }
+
+    async fn send_debit_note_usage(
+        db: DbExecutor,
+        sender_id: String,
+        msg: SendDebitNoteUsage,
+    ) -> Result<Ack, SendError> {
+        let debit_note_id = msg.debit_note_id;
+        let node_id = msg.recipient_id;
+
+        log::debug!(
+            "Got SendDebitNoteUsage [{}] from Node [{}].",
+            debit_note_id,
+            sender_id
+        );
+
+        let dao: DebitNoteDao = db.as_dao();
+        let debit_note: DebitNote = match dao.get(debit_note_id.clone(), node_id).await {
+            Ok(Some(debit_note)) => debit_note,
+            Ok(None) => {
+                return Err(SendError::BadRequest(format!(
+                    "DebitNote {} not found",
+                    debit_note_id
+                )))
+            }
+            Err(e) => return Err(SendError::ServiceError(e.to_string())),
+        };
+
+        if sender_id != debit_note.issuer_id.to_string() {
+            return Err(SendError::BadRequest("Invalid sender node ID".to_owned()));
+        }
+        if msg.signed_usage.activity_id != debit_note.activity_id
+            || !crate::usage::verify_signature(&msg.signed_usage, debit_note.issuer_id)
+        {
+            return Err(SendError::BadRequest("Invalid usage signature".to_owned()));
+        }
+
+        match dao
+            .set_signed_usage(debit_note_id.clone(), node_id, msg.signed_usage)
+            .await
+        {
+            Ok(_) => {
+                log::debug!("Signed usage of DebitNote [{debit_note_id}] received.");
+                Ok(Ack {})
+            }
             Err(DbError::Query(e)) => Err(SendError::BadRequest(e)),
             Err(e) => Err(SendError::ServiceError(e.to_string())),
         }
@@ -501,10 +995,60 @@ mod public {
 
     async fn reject_debit_note(
         db: DbExecutor,
-        sender: String,
+        sender_id: String,
         msg: RejectDebitNote,
     ) -> Result<Ack, AcceptRejectError> {
-        unimplemented!() // TODO
+        let debit_note_id = msg.debit_note_id;
+        let node_id = msg.issuer_id;
+
+        log::debug!(
+            "Got RejectDebitNote [{}] from Node [{}].",
+            debit_note_id,
+            sender_id
+        );
+
+        let dao: DebitNoteDao = db.as_dao();
+        let debit_note: DebitNote = match dao.get(debit_note_id.clone(), node_id).await {
+            Ok(Some(debit_note)) => debit_note,
+            Ok(None) => return Err(AcceptRejectError::ObjectNotFound),
+            Err(e) => return Err(AcceptRejectError::ServiceError(e.to_string())),
+        };
+
+        if sender_id != debit_note.recipient_id.to_string() {
+            return Err(AcceptRejectError::Forbidden);
+        }
+
+        match debit_note.status {
+            DocumentStatus::Issued | DocumentStatus::Received | DocumentStatus::Rejected => (),
+            status => {
+                return Err(AcceptRejectError::BadRequest(format!(
+                    "Cannot reject {:?} debit note",
+                    status
+                )));
+            }
+        }
+
+        let recipient_id = debit_note.recipient_id;
+        match dao
+            .reject(debit_note_id.clone(), node_id, recipient_id, msg.rejection)
+            .await
+        {
+            Ok(_) => {
+                log::info!("Node [{recipient_id}] rejected DebitNote [{debit_note_id}].");
+                journal::Event::new(journal::Category::Payment, "debit-note-rejected")
+                    .subject(&debit_note_id)
+                    .node_id(node_id)
+                    .details(serde_json::json!({
+                        "activityId": debit_note.activity_id,
+                        "recipientId": recipient_id,
+                    }))
+                    .record()
+                    .await;
+                Ok(Ack {})
+            }
+            Err(DbError::Query(e)) => Err(AcceptRejectError::BadRequest(e)),
+            Err(e) => Err(AcceptRejectError::ServiceError(e.to_string())),
+        }
     }
 
     async fn cancel_debit_note(
@@ -606,6 +1150,16 @@ mod public {
                 "Invoice [{invoice_id}] for Agreement [{agreement_id}] received from node [{sender_id}]."
             );
             counter!("payment.invoices.requestor.received", 1);
+
+            journal::Event::new(journal::Category::Payment, "invoice-received")
+                .subject(&invoice_id)
+                .node_id(owner_id)
+                .details(serde_json::json!({
+                    "agreementId": agreement_id,
+                    "issuerId": sender_id,
+                }))
+                .record()
+                .await;
             Ok(())
         }
         .await
@@ -671,6 +1225,16 @@ mod public {
                     invoice.agreement_id
                 );
                 counter!("payment.invoices.provider.accepted", 1);
+
+                journal::Event::new(journal::Category::Payment, "invoice-accepted")
+                    .subject(&invoice_id)
+                    .node_id(node_id)
+                    .details(serde_json::json!({
+                        "agreementId": invoice.agreement_id,
+                        "recipientId": invoice.recipient_id,
+                    }))
+                    .record()
+                    .await;
                 Ok(Ack {})
             }
             Err(DbError::Query(e)) => Err(AcceptRejectError::BadRequest(e)),
@@ -680,10 +1244,125 @@ mod public {
 
     async fn reject_invoice(
         db: DbExecutor,
-        sender: String,
+        sender_id: String,
         msg: RejectInvoice,
     ) -> Result<Ack, AcceptRejectError> {
-        unimplemented!() // TODO
+        let invoice_id = msg.invoice_id;
+        let node_id = msg.issuer_id;
+
+        log::debug!(
+            "Got RejectInvoice [{}] from Node [{}].",
+            invoice_id,
+            sender_id
+        );
+
+        let dao: InvoiceDao = db.as_dao();
+        let invoice: Invoice = match dao.get(invoice_id.clone(), node_id).await {
+            Ok(Some(invoice)) => invoice,
+            Ok(None) => return Err(AcceptRejectError::ObjectNotFound),
+            Err(e) => return Err(AcceptRejectError::ServiceError(e.to_string())),
+        };
+
+        if sender_id != invoice.recipient_id.to_string() {
+            return Err(AcceptRejectError::Forbidden);
+        }
+
+        match invoice.status {
+            DocumentStatus::Issued | DocumentStatus::Received | DocumentStatus::Rejected => (),
+            status => {
+                return Err(AcceptRejectError::BadRequest(format!(
+                    "Cannot reject {:?} invoice",
+                    status
+                )));
+            }
+        }
+
+        let recipient_id = invoice.recipient_id;
+        match dao
+            .reject(invoice_id.clone(), node_id, recipient_id, msg.rejection)
+            .await
+        {
+            Ok(_) => {
+                log::info!("Node [{recipient_id}] rejected Invoice [{invoice_id}].");
+                journal::Event::new(journal::Category::Payment, "invoice-rejected")
+                    .subject(&invoice_id)
+                    .node_id(node_id)
+                    .details(serde_json::json!({
+                        "agreementId": invoice.agreement_id,
+                        "recipientId": recipient_id,
+                    }))
+                    .record()
+                    .await;
+                Ok(Ack {})
+            }
+            Err(DbError::Query(e)) => Err(AcceptRejectError::BadRequest(e)),
+            Err(e) => Err(AcceptRejectError::ServiceError(e.to_string())),
+        }
+    }
+
+    // *************************** DISPUTE ****************************
+
+    async fn respond_to_dispute(
+        db: DbExecutor,
+        sender_id: String,
+        msg: RespondToDispute,
+    ) -> Result<Ack, AcceptRejectError> {
+        let document_id = msg.document_id;
+        let node_id = msg.recipient_id;
+
+        log::debug!(
+            "Got RespondToDispute of {} [{}] from Node [{}].",
+            msg.document_type,
+            document_id,
+            sender_id
+        );
+
+        let issuer_id = match msg.document_type {
+            DocumentType::Invoice => db
+                .as_dao::<InvoiceDao>()
+                .get(document_id.clone(), node_id)
+                .await
+                .map(|invoice| invoice.map(|invoice| invoice.issuer_id)),
+            DocumentType::DebitNote => db
+                .as_dao::<DebitNoteDao>()
+                .get(document_id.clone(), node_id)
+                .await
+                .map(|debit_note| debit_note.map(|debit_note| debit_note.issuer_id)),
+        };
+        match issuer_id {
+            Ok(Some(issuer_id)) if sender_id == issuer_id.to_string() => (),
+            Ok(Some(_)) => return Err(AcceptRejectError::Forbidden),
+            Ok(None) => return Err(AcceptRejectError::ObjectNotFound),
+            Err(e) => return Err(AcceptRejectError::ServiceError(e.to_string())),
+        }
+
+        match db
+            .as_dao::<DisputeDao>()
+            .respond(document_id.clone(), node_id, msg.response)
+            .await
+        {
+            Ok(true) => {
+                log::info!(
+                    "Node [{sender_id}] responded to dispute of {} [{document_id}].",
+                    msg.document_type
+                );
+                journal::Event::new(journal::Category::Payment, "dispute-responded")
+                    .subject(&document_id)
+                    .node_id(node_id)
+                    .details(serde_json::json!({
+                        "documentType": msg.document_type,
+                        "issuerId": sender_id,
+                    }))
+                    .record()
+                    .await;
+                Ok(Ack {})
+            }
+            Ok(false) => Err(AcceptRejectError::BadRequest(format!(
+                "No open dispute of {} {}",
+                msg.document_type, document_id
+            ))),
+            Err(e) => Err(AcceptRejectError::ServiceError(e.to_string())),
+        }
     }
 
     async fn cancel_invoice(
@@ -730,12 +1409,91 @@ mod public {
                     invoice.agreement_id
                 );
                 counter!("payment.invoices.requestor.cancelled", 1);
+
+                journal::Event::new(journal::Category::Payment, "invoice-cancelled")
+                    .subject(&invoice_id)
+                    .node_id(invoice.recipient_id)
+                    .details(serde_json::json!({
+                        "agreementId": invoice.agreement_id,
+                        "issuerId": invoice.issuer_id,
+                    }))
+                    .record()
+                    .await;
                 Ok(Ack {})
             }
             Err(e) => Err(CancelError::ServiceError(e.to_string())),
         }
     }
 
+    async fn remind_invoice(
+        db: DbExecutor,
+        sender_id: String,
+        msg: RemindInvoice,
+    ) -> Result<Ack, SendError> {
+        let invoice_id = msg.invoice_id;
+
+        log::debug!(
+            "Got RemindInvoice [{}] from Node [{}].",
+            invoice_id,
+            sender_id
+        );
+
+        let agreement = match get_agreement(
+            msg.agreement_id.clone(),
+            ya_client_model::market::Role::Requestor,
+        )
+        .await
+        {
+            Ok(Some(agreement)) => agreement,
+            Ok(None) => {
+                return Err(SendError::BadRequest(format!(
+                    "Agreement not found: {}",
+                    msg.agreement_id
+                )))
+            }
+            Err(e) => return Err(SendError::ServiceError(e.to_string())),
+        };
+        let requestor_id = *agreement.requestor_id();
+
+        let invoice: Invoice = match db
+            .as_dao::<InvoiceDao>()
+            .get(invoice_id.clone(), requestor_id)
+            .await
+        {
+            Ok(Some(invoice)) => invoice,
+            Ok(None) => {
+                return Err(SendError::BadRequest(format!(
+                    "Invoice not found: {}",
+                    invoice_id
+                )))
+            }
+            Err(e) => return Err(SendError::ServiceError(e.to_string())),
+        };
+        if sender_id != invoice.issuer_id.to_string() {
+            return Err(SendError::BadRequest("Invalid sender node ID".to_owned()));
+        }
+
+        log::warn!(
+            "Node [{}] reminds of overdue Invoice [{}] for Agreement [{}], reminder {}.",
+            sender_id,
+            invoice_id,
+            invoice.agreement_id,
+            msg.reminder
+        );
+        journal::Event::new(journal::Category::Payment, "invoice-payment-reminder")
+            .subject(&invoice_id)
+            .node_id(requestor_id)
+            .details(serde_json::json!({
+                "agreementId": invoice.agreement_id,
+                "issuerId": invoice.issuer_id,
+                "status": invoice.status,
+                "reminder": msg.reminder,
+            }))
+            .record()
+            .await;
+        Ok(Ack {})
+    }
+
     // *************************** PAYMENT ****************************
 
     async fn send_payment(
@@ -750,9 +1508,17 @@ mod public {
             return Err(SendError::BadRequest("Invalid payer ID".to_owned()));
         }
 
+        let payment_id = payment.payment_id.clone();
         let platform = payment.payment_platform.clone();
         let amount = payment.amount.clone();
         let num_paid_invoices = payment.agreement_payments.len() as u64;
+        let payer_id = payment.payer_id;
+        let payee_id = payment.payee_id;
+        let agreement_ids = payment
+            .agreement_payments
+            .iter()
+            .map(|p| p.agreement_id.clone())
+            .collect::<Vec<_>>();
         match processor
             .lock()
             .await
@@ -762,15 +1528,69 @@ mod public {
             Ok(_) => {
                 counter!("payment.amount.received", ya_metrics::utils::cryptocurrency_to_u64(&amount), "platform" => platform);
                 counter!("payment.invoices.provider.paid", num_paid_invoices);
+
+                journal::Event::new(journal::Category::Payment, "payment-received")
+                    .subject(&payment_id)
+                    .node_id(payee_id)
+                    .details(serde_json::json!({
+                        "payerId": payer_id,
+                        "amount": amount.to_string(),
+                        "agreementIds": agreement_ids,
+                    }))
+                    .record()
+                    .await;
+                record_payment_outcomes(&db, payer_id, payee_id, agreement_ids).await;
                 Ok(Ack {})
             }
-            Err(e) => match e {
-                VerifyPaymentError::ConfirmationEncoding => {
-                    Err(SendError::BadRequest(e.to_string()))
+            Err(e) => {
+                journal::Event::new(journal::Category::Payment, "payment-rejected")
+                    .subject(&payment_id)
+                    .node_id(payee_id)
+                    .details(serde_json::json!({
+                        "payerId": payer_id,
+                        "platform": platform,
+                        "amount": amount.to_string(),
+                        "agreementIds": agreement_ids,
+                        "error": e.to_string(),
+                    }))
+                    .record()
+                    .await;
+                match e {
+                    VerifyPaymentError::ConfirmationEncoding => {
+                        Err(SendError::BadRequest(e.to_string()))
+                    }
+                    VerifyPaymentError::Validation(e) => Err(SendError::BadRequest(e)),
+                    _ => Err(SendError::ServiceError(e.to_string())),
+                }
+            }
+        }
+    }
+
+    /// Payments of Invoices are compared with their due dates. Payments for Agreements
+    /// without Invoice (e.g. for Debit Notes) don't affect reputation.
+    async fn record_payment_outcomes(
+        db: &DbExecutor,
+        payer_id: NodeId,
+        payee_id: NodeId,
+        agreement_ids: Vec<String>,
+    ) {
+        let now = Utc::now().naive_utc();
+        for agreement_id in agreement_ids {
+            match db
+                .as_dao::<InvoiceDao>()
+                .get_payment_due_date(agreement_id.clone(), payee_id)
+                .await
+            {
+                Ok(Some(due_date)) => {
+                    record_payment_outcome(payer_id, agreement_id, now <= due_date).await
                 }
-                VerifyPaymentError::Validation(e) => Err(SendError::BadRequest(e)),
-                _ => Err(SendError::ServiceError(e.to_string())),
-            },
+                Ok(None) => {}
+                Err(e) => log::warn!(
+                    "Failed to get Invoice for Agreement [{}]: {}",
+                    agreement_id,
+                    e
+                ),
+            }
         }
     }
 }
@@ -0,0 +1,18 @@
+//! This module is to be used only in tests.
+use ya_client_model::NodeId;
+use ya_persistence::executor::DbExecutor;
+
+/// Address of the node owning test records, also used as its payment address.
+pub const OWNER_ADDRESS: &str = "0xd39a168f0480b8502c2531b2ffd8588c592d713a";
+
+pub fn owner_id() -> NodeId {
+    OWNER_ADDRESS.parse().unwrap()
+}
+
+/// In-memory payment database `name` with all migrations applied.
+pub fn setup_db(name: &str) -> DbExecutor {
+    let db = DbExecutor::in_memory(name).unwrap();
+    db.apply_migration(crate::migrations::run_with_output)
+        .unwrap();
+    db
+}
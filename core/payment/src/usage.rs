@@ -0,0 +1,139 @@
+//! Verification of usage billed in Debit Notes against usage counters
+//! reported and signed by the Provider's ExeUnit.
+use ethsign::Signature;
+use std::convert::TryInto;
+
+use ya_client_model::payment::DebitNote;
+use ya_client_model::NodeId;
+use ya_core_model::activity::SignedUsage;
+use ya_core_model::payment::local::{UsageVerification, UsageVerificationStatus};
+
+/// Counters are floats, billed values may be rounded differently than the signed ones.
+const USAGE_TOLERANCE: f64 = 1e-6;
+
+/// Checks that `signed` was signed by `signer`.
+pub fn verify_signature(signed: &SignedUsage, signer: NodeId) -> bool {
+    if signed.signature.len() != 65 {
+        return false;
+    }
+    let v = signed.signature[0];
+    let r: [u8; 32] = signed.signature[1..33].try_into().unwrap();
+    let s: [u8; 32] = signed.signature[33..65].try_into().unwrap();
+    let signature = Signature { v, r, s };
+
+    let payload = SignedUsage::hash(&signed.activity_id, &signed.usage);
+    match signature.recover(payload.as_slice()) {
+        Ok(pub_key) => pub_key.address() == &signer.into_array(),
+        Err(_) => false,
+    }
+}
+
+/// Compares usage billed for the Activity with usage signed by the issuer's ExeUnit.
+pub fn verify_usage(
+    billed: Option<&[f64]>,
+    activity_id: &str,
+    issuer_id: NodeId,
+    signed: Option<&SignedUsage>,
+) -> UsageVerificationStatus {
+    let signed = match signed {
+        Some(signed) => signed,
+        None => return UsageVerificationStatus::Unsigned,
+    };
+    if signed.activity_id != activity_id || !verify_signature(signed, issuer_id) {
+        return UsageVerificationStatus::InvalidSignature;
+    }
+
+    let billed = billed.unwrap_or_default();
+    let reported = signed.usage.current_usage.as_deref().unwrap_or_default();
+    let exceeded = billed
+        .iter()
+        .enumerate()
+        .any(|(i, value)| *value > reported.get(i).copied().unwrap_or(0.0) + USAGE_TOLERANCE);
+    match exceeded {
+        true => UsageVerificationStatus::UsageExceeded,
+        false => UsageVerificationStatus::Verified,
+    }
+}
+
+pub fn verify_debit_note_usage(
+    debit_note: &DebitNote,
+    signed_usage: Option<SignedUsage>,
+) -> UsageVerification {
+    let billed_usage = debit_note
+        .usage_counter_vector
+        .as_ref()
+        .and_then(|usage| serde_json::from_value::<Vec<f64>>(usage.clone()).ok());
+    let status = verify_usage(
+        billed_usage.as_deref(),
+        &debit_note.activity_id,
+        debit_note.issuer_id,
+        signed_usage.as_ref(),
+    );
+
+    UsageVerification {
+        debit_note_id: debit_note.debit_note_id.clone(),
+        status,
+        billed_usage,
+        signed_usage,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethsign::SecretKey;
+    use ya_client_model::activity::ActivityUsage;
+
+    const ACTIVITY_ID: &str = "a4c4b4f8d7e3476c9a6f8ad0e43a7e5b";
+
+    fn signed_usage(secret: &SecretKey, usage: Vec<f64>) -> SignedUsage {
+        let usage = ActivityUsage {
+            current_usage: Some(usage),
+            timestamp: 1677492000,
+        };
+        let signature = secret
+            .sign(&SignedUsage::hash(ACTIVITY_ID, &usage))
+            .unwrap();
+        let mut bytes = vec![signature.v];
+        bytes.extend_from_slice(&signature.r);
+        bytes.extend_from_slice(&signature.s);
+        SignedUsage {
+            activity_id: ACTIVITY_ID.to_string(),
+            usage,
+            signature: bytes,
+        }
+    }
+
+    #[test]
+    fn test_verify_usage() {
+        let secret = SecretKey::from_raw(&[0x11; 32]).unwrap();
+        let issuer_id = NodeId::from(secret.public().address().as_ref());
+        let signed = signed_usage(&secret, vec![120.0, 60.5]);
+        let other = SecretKey::from_raw(&[0x22; 32]).unwrap();
+        let other_id = NodeId::from(other.public().address().as_ref());
+
+        let verify = |billed: &[f64], issuer_id| {
+            verify_usage(Some(billed), ACTIVITY_ID, issuer_id, Some(&signed))
+        };
+        assert_eq!(
+            verify(&[120.0, 60.5], issuer_id),
+            UsageVerificationStatus::Verified
+        );
+        assert_eq!(
+            verify(&[100.0, 30.0], issuer_id),
+            UsageVerificationStatus::Verified
+        );
+        assert_eq!(
+            verify(&[120.0, 61.0], issuer_id),
+            UsageVerificationStatus::UsageExceeded
+        );
+        assert_eq!(
+            verify(&[120.0, 60.5], other_id),
+            UsageVerificationStatus::InvalidSignature
+        );
+        assert_eq!(
+            verify_usage(Some(&[1.0]), ACTIVITY_ID, issuer_id, None),
+            UsageVerificationStatus::Unsigned
+        );
+    }
+}
@@ -1,9 +1,16 @@
 use crate::error::{DbError, DbResult, Error, ExternalServiceError};
+use actix_web::http::header;
+use actix_web::web::Bytes;
 use actix_web::HttpResponse;
+use chrono::{DateTime, NaiveDateTime, Utc};
 use futures::Future;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::VecDeque;
 use std::time::Duration;
 use ya_client_model::market::{Agreement, Role};
+use ya_client_model::payment::{DebitNoteEvent, InvoiceEvent};
+use ya_client_model::NodeId;
 use ya_core_model::market;
 use ya_service_bus::{typed as bus, RpcEndpoint};
 
@@ -41,6 +48,32 @@ pub async fn get_agreement(agreement_id: String, role: Role) -> Result<Option<Ag
     }
 }
 
+/// Reports timeliness of payment to market, which keeps local reputation of Requestors.
+pub async fn record_payment_outcome(payer_id: NodeId, agreement_id: String, on_time: bool) {
+    let outcome = match on_time {
+        true => market::Outcome::PaymentOnTime,
+        false => market::Outcome::PaymentLate,
+    };
+    let msg = market::RecordOutcome {
+        node_id: payer_id,
+        subject_id: agreement_id.clone(),
+        outcome,
+    };
+    match bus::service(market::local::BUS_ID).send(msg).await {
+        Ok(Ok(())) => {}
+        Ok(Err(e)) => log::warn!(
+            "Failed to record payment outcome for Agreement [{}]: {}",
+            agreement_id,
+            e
+        ),
+        Err(e) => log::debug!(
+            "Can't record payment outcome for Agreement [{}]: {}",
+            agreement_id,
+            e
+        ),
+    }
+}
+
 pub mod provider {
     use crate::error::{Error, ExternalServiceError};
     use ya_client_model::market::{Agreement, Role};
@@ -173,6 +206,100 @@ pub async fn listen_for_events<T: EventGetter>(
     .unwrap_or(Ok(vec![]))
 }
 
+/// Event which can be delivered to the client as a server-sent event.
+pub trait StreamedEvent: Serialize {
+    fn event_date(&self) -> DateTime<Utc>;
+}
+
+impl StreamedEvent for InvoiceEvent {
+    fn event_date(&self) -> DateTime<Utc> {
+        self.event_date
+    }
+}
+
+impl StreamedEvent for DebitNoteEvent {
+    fn event_date(&self) -> DateTime<Utc> {
+        self.event_date
+    }
+}
+
+/// Responds with `text/event-stream` which polls `getter` for events newer than
+/// the last delivered one and pushes them to the client until it disconnects.
+pub fn stream_events<E, G, F>(getter: G, after_timestamp: Option<NaiveDateTime>) -> HttpResponse
+where
+    E: StreamedEvent + 'static,
+    G: Fn(Option<NaiveDateTime>) -> F + 'static,
+    F: Future<Output = DbResult<Vec<E>>> + 'static,
+{
+    let stream = futures::stream::unfold(
+        (getter, after_timestamp, VecDeque::new()),
+        |(getter, mut after_timestamp, mut pending)| async move {
+            loop {
+                if let Some(event) = pending.pop_front() {
+                    let line = match serde_json::to_string(&event) {
+                        Ok(json) => format!("data: {}\r\n\r\n", json),
+                        Err(e) => {
+                            log::error!("Failed to serialize payment event: {}", e);
+                            return None;
+                        }
+                    };
+                    return Some((
+                        Ok::<_, actix_web::Error>(Bytes::from(line)),
+                        (getter, after_timestamp, pending),
+                    ));
+                }
+
+                match getter(after_timestamp).await {
+                    Ok(events) if events.is_empty() => {
+                        tokio::time::sleep(Duration::from_secs(1)).await
+                    }
+                    Ok(events) => {
+                        after_timestamp = events.last().map(|e| e.event_date().naive_utc());
+                        pending.extend(events);
+                    }
+                    Err(e) => {
+                        log::error!("Payment event stream closed: {}", e);
+                        return None;
+                    }
+                }
+            }
+        },
+    );
+
+    HttpResponse::Ok()
+        .insert_header((header::CONTENT_TYPE, "text/event-stream"))
+        .insert_header((header::CACHE_CONTROL, "no-cache"))
+        .streaming(Box::pin(stream))
+}
+
+pub type EventTypes = Vec<Cow<'static, str>>;
+
+/// Reads requested event types from `X-Requestor-Events` and `X-Provider-Events` headers.
+pub fn event_types(req: &actix_web::HttpRequest) -> (EventTypes, EventTypes) {
+    let requestor_events = req
+        .headers()
+        .get("X-Requestor-Events")
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.split(',').map(|s| Cow::Owned(s.to_owned())).collect())
+        .unwrap_or_else(|| vec!["RECEIVED".into(), "CANCELLED".into()]);
+
+    let provider_events = req
+        .headers()
+        .get("X-Provider-Events")
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.split(',').map(|s| Cow::Owned(s.to_owned())).collect())
+        .unwrap_or_else(|| {
+            vec![
+                "ACCEPTED".into(),
+                "REJECTED".into(),
+                "SETTLED".into(),
+                "CANCELLED".into(),
+            ]
+        });
+
+    (requestor_events, provider_events)
+}
+
 pub mod response {
     use actix_web::HttpResponse;
     use serde::Serialize;
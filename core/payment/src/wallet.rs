@@ -1,8 +1,15 @@
 // External crates
 use bigdecimal::BigDecimal;
+use futures::future::{self, Either};
+use futures::Future;
+use std::time::Duration;
 
 // Workspace uses
-use ya_core_model::driver::{driver_bus_id, Enter, Exit, Fund, Transfer};
+use ya_core_model::driver::{
+    driver_bus_id, Bridge, BridgeTransfer, Enter, Exit, FlushPayments, Fund, GasPriority,
+    GetBridgeTransfers, GetProgress, Progress, Transfer,
+};
+use ya_core_model::payment::local as pay;
 use ya_service_bus::typed as bus;
 
 pub async fn fund(
@@ -10,9 +17,10 @@ pub async fn fund(
     driver: String,
     network: Option<String>,
     token: Option<String>,
+    progress_id: Option<String>,
 ) -> anyhow::Result<String> {
     let driver_id = driver_bus_id(driver);
-    let message = Fund::new(address, network, token);
+    let message = Fund::new(address, network, token).with_progress_id(progress_id);
     let reply = bus::service(driver_id).call(message).await??;
     Ok(reply)
 }
@@ -56,6 +64,8 @@ pub async fn transfer(
     max_gas_price: Option<BigDecimal>,
     gas_limit: Option<u32>,
     gasless: bool,
+    priority: Option<GasPriority>,
+    wait: bool,
 ) -> anyhow::Result<String> {
     let driver_id = driver_bus_id(driver);
     let message = Transfer::new(
@@ -68,7 +78,121 @@ pub async fn transfer(
         max_gas_price,
         gas_limit,
         gasless,
-    );
+    )
+    .with_priority(priority)
+    .with_wait(wait);
     let tx_id = bus::service(driver_id).call(message).await??;
     Ok(tx_id)
 }
+
+pub async fn bridge(
+    sender: String,
+    amount: BigDecimal,
+    driver: String,
+    from_network: String,
+    to_network: Option<String>,
+) -> anyhow::Result<String> {
+    let driver_id = driver_bus_id(driver);
+    let message = Bridge::new(sender, amount, from_network, to_network);
+    let bridge_id = bus::service(driver_id).call(message).await??;
+    Ok(bridge_id)
+}
+
+pub async fn bridge_transfers(
+    sender: String,
+    driver: String,
+    bridge_id: Option<String>,
+) -> anyhow::Result<Vec<BridgeTransfer>> {
+    let driver_id = driver_bus_id(driver);
+    let message = GetBridgeTransfers {
+        sender: Some(sender),
+        bridge_id,
+    };
+    let transfers = bus::service(driver_id).call(message).await??;
+    Ok(transfers)
+}
+
+pub async fn flush(driver: String, network: String) -> anyhow::Result<()> {
+    let driver_id = driver_bus_id(driver);
+    bus::service(driver_id)
+        .call(FlushPayments { network })
+        .await??;
+    Ok(())
+}
+
+/// Balance not reserved by Allocations, e.g. to drain an account on a deprecated network.
+pub async fn unreserved_balance(
+    address: String,
+    driver: String,
+    network: Option<String>,
+) -> anyhow::Result<BigDecimal> {
+    let status = bus::service(pay::BUS_ID)
+        .call(pay::GetStatus {
+            address,
+            driver,
+            network,
+            token: None,
+            after_timestamp: 0,
+        })
+        .await??;
+    let amount = status.amount - status.reserved;
+    if amount <= BigDecimal::from(0) {
+        anyhow::bail!("Nothing to transfer, whole balance is reserved by Allocations");
+    }
+    Ok(amount)
+}
+
+const PROGRESS_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Runs driver `operation` started with `progress_id`, printing its progress to stderr.
+pub async fn with_progress<T>(
+    driver: String,
+    progress_id: Option<String>,
+    operation: impl Future<Output = T>,
+) -> T {
+    let progress_id = match progress_id {
+        Some(progress_id) => progress_id,
+        None => return operation.await,
+    };
+    let driver_id = driver_bus_id(driver);
+    let poll = async move {
+        let mut last_line = String::new();
+        loop {
+            tokio::time::sleep(PROGRESS_POLL_INTERVAL).await;
+            let msg = GetProgress {
+                progress_id: progress_id.clone(),
+            };
+            if let Ok(Ok(Some(progress))) = bus::service(&driver_id).call(msg).await {
+                let line = format_progress(&progress);
+                if line != last_line {
+                    eprintln!("{}", line);
+                    last_line = line;
+                }
+            }
+        }
+    };
+
+    futures::pin_mut!(operation);
+    futures::pin_mut!(poll);
+    match future::select(operation, poll).await {
+        Either::Left((result, _)) => result,
+        Either::Right((_, operation)) => operation.await,
+    }
+}
+
+fn format_progress(progress: &Progress) -> String {
+    let mut line = format!(
+        "[{}/{}] {}",
+        progress.step, progress.total_steps, progress.message
+    );
+    if let Some((confirmations, required)) = progress.confirmations {
+        line.push_str(&format!(" {}/{} confirmations", confirmations, required));
+    }
+    if let Some(eta) = progress.eta {
+        line.push_str(&format!(
+            ", ETA {}",
+            humantime::format_duration(Duration::from_secs(eta.as_secs()))
+        ));
+    }
+    line
+}
@@ -0,0 +1,176 @@
+use bigdecimal::BigDecimal;
+
+use ya_client_model::payment::NewAllocation;
+use ya_core_model::payment::local::SetAllocationRefill;
+use ya_payment::dao::{AllocationDao, AllocationRefillDao, AllocationStatus, RefillStatus};
+use ya_payment::testing::{owner_id, setup_db, OWNER_ADDRESS as ADDRESS};
+use ya_persistence::executor::DbExecutor;
+
+const PLATFORM: &str = "erc20-rinkeby-tglm";
+
+async fn create_allocation(db: &DbExecutor, total_amount: u32) -> String {
+    let allocation = NewAllocation {
+        address: Some(ADDRESS.to_string()),
+        payment_platform: Some(PLATFORM.to_string()),
+        total_amount: total_amount.into(),
+        timeout: None,
+        make_deposit: false,
+    };
+    db.as_dao::<AllocationDao>()
+        .create(
+            allocation,
+            owner_id(),
+            PLATFORM.to_string(),
+            ADDRESS.to_string(),
+        )
+        .await
+        .unwrap()
+}
+
+async fn set_refill(
+    db: &DbExecutor,
+    allocation_id: &str,
+    amount: u32,
+    threshold: u32,
+    max_total_amount: Option<u32>,
+) {
+    db.as_dao::<AllocationRefillDao>()
+        .set(SetAllocationRefill {
+            allocation_id: allocation_id.to_string(),
+            owner_id: owner_id(),
+            amount: Some(amount.into()),
+            threshold: Some(threshold.into()),
+            max_total_amount: max_total_amount.map(Into::into),
+            enabled: Some(true),
+        })
+        .await
+        .unwrap();
+}
+
+async fn refilled(db: &DbExecutor, allocation_id: &str, balance: u32) -> RefillStatus {
+    db.as_dao::<AllocationRefillDao>()
+        .refilled(allocation_id.to_string(), owner_id(), balance.into())
+        .await
+        .unwrap()
+}
+
+async fn total_amount(db: &DbExecutor, allocation_id: &str) -> BigDecimal {
+    match db
+        .as_dao::<AllocationDao>()
+        .get(allocation_id.to_string(), owner_id())
+        .await
+        .unwrap()
+    {
+        AllocationStatus::Active(allocation) => allocation.total_amount,
+        _ => panic!("Allocation {} not active", allocation_id),
+    }
+}
+
+#[actix_rt::test]
+async fn test_refill_below_threshold_once() {
+    let db = setup_db("test_refill_below_threshold_once");
+    let allocation_id = create_allocation(&db, 10).await;
+    set_refill(&db, &allocation_id, 15, 20, None).await;
+
+    match refilled(&db, &allocation_id, 100).await {
+        RefillStatus::Refilled(amount) => assert_eq!(amount, 15.into()),
+        _ => panic!("Allocation not refilled"),
+    }
+    // Remaining 25 is above the threshold, a repeated check doesn't top up again.
+    assert!(matches!(
+        refilled(&db, &allocation_id, 100).await,
+        RefillStatus::NotNeeded
+    ));
+    assert_eq!(total_amount(&db, &allocation_id).await, 25.into());
+
+    let refill = db
+        .as_dao::<AllocationRefillDao>()
+        .get(allocation_id.clone(), owner_id())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(refill.refilled_amount, 15.into());
+    assert!(refill.refilled_at.is_some());
+}
+
+#[actix_rt::test]
+async fn test_concurrent_refills_dont_add_up() {
+    let db = setup_db("test_concurrent_refills_dont_add_up");
+    let allocation_id = create_allocation(&db, 10).await;
+    set_refill(&db, &allocation_id, 15, 20, None).await;
+
+    let (first, second) = futures::join!(
+        refilled(&db, &allocation_id, 100),
+        refilled(&db, &allocation_id, 100)
+    );
+    let refills = [first, second]
+        .iter()
+        .filter(|status| matches!(status, RefillStatus::Refilled(_)))
+        .count();
+    assert_eq!(refills, 1);
+    assert_eq!(total_amount(&db, &allocation_id).await, 25.into());
+}
+
+#[actix_rt::test]
+async fn test_refill_capped() {
+    let db = setup_db("test_refill_capped");
+    let allocation_id = create_allocation(&db, 10).await;
+    set_refill(&db, &allocation_id, 15, 30, Some(30)).await;
+
+    match refilled(&db, &allocation_id, 100).await {
+        RefillStatus::Refilled(amount) => assert_eq!(amount, 15.into()),
+        _ => panic!("Allocation not refilled"),
+    }
+    // Only 5 left under the cap.
+    match refilled(&db, &allocation_id, 100).await {
+        RefillStatus::Refilled(amount) => assert_eq!(amount, 5.into()),
+        _ => panic!("Allocation not refilled up to the cap"),
+    }
+    assert!(matches!(
+        refilled(&db, &allocation_id, 100).await,
+        RefillStatus::Refused(_)
+    ));
+    assert_eq!(total_amount(&db, &allocation_id).await, 30.into());
+}
+
+#[actix_rt::test]
+async fn test_refill_from_unreserved_funds() {
+    let db = setup_db("test_refill_from_unreserved_funds");
+    let allocation_id = create_allocation(&db, 10).await;
+    create_allocation(&db, 50).await;
+    set_refill(&db, &allocation_id, 15, 20, None).await;
+
+    // 60 of 70 is reserved by both Allocations.
+    assert!(matches!(
+        refilled(&db, &allocation_id, 70).await,
+        RefillStatus::Refused(_)
+    ));
+    assert_eq!(total_amount(&db, &allocation_id).await, 10.into());
+    assert!(matches!(
+        refilled(&db, &allocation_id, 75).await,
+        RefillStatus::Refilled(_)
+    ));
+}
+
+#[actix_rt::test]
+async fn test_refill_disabled() {
+    let db = setup_db("test_refill_disabled");
+    let allocation_id = create_allocation(&db, 10).await;
+    set_refill(&db, &allocation_id, 15, 20, None).await;
+    db.as_dao::<AllocationRefillDao>()
+        .set(SetAllocationRefill {
+            allocation_id: allocation_id.clone(),
+            owner_id: owner_id(),
+            amount: None,
+            threshold: None,
+            max_total_amount: None,
+            enabled: Some(false),
+        })
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        refilled(&db, &allocation_id, 100).await,
+        RefillStatus::NotNeeded
+    ));
+}
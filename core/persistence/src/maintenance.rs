@@ -0,0 +1,181 @@
+//! Database maintenance operations.
+//!
+//! Except for `vacuum` and `applied_migrations` these are specific to SQLite
+//! and not available with PostgreSQL, which maintains its databases itself.
+use chrono::NaiveDateTime;
+#[cfg(not(feature = "postgres"))]
+use diesel::sql_types::BigInt;
+use diesel::sql_types::{Text, Timestamp};
+use diesel::{Connection, RunQueryDsl};
+
+use crate::executor::{DbExecutor, Error};
+
+#[cfg(not(feature = "postgres"))]
+#[derive(QueryableByName)]
+struct CheckRow {
+    #[sql_type = "Text"]
+    result: String,
+}
+
+#[cfg(not(feature = "postgres"))]
+#[derive(QueryableByName)]
+struct CheckpointRow {
+    #[sql_type = "BigInt"]
+    busy: i64,
+    #[sql_type = "BigInt"]
+    log: i64,
+    #[sql_type = "BigInt"]
+    checkpointed: i64,
+}
+
+#[cfg(not(feature = "postgres"))]
+#[derive(QueryableByName)]
+struct PagesRow {
+    #[sql_type = "BigInt"]
+    page_count: i64,
+    #[sql_type = "BigInt"]
+    freelist_count: i64,
+}
+
+#[derive(QueryableByName)]
+struct MigrationRow {
+    #[sql_type = "Text"]
+    version: String,
+    #[sql_type = "Timestamp"]
+    run_on: NaiveDateTime,
+}
+
+/// Result of `wal_checkpoint` in pages.
+#[cfg(not(feature = "postgres"))]
+#[derive(Clone, Copy, Debug)]
+pub struct Checkpoint {
+    /// Checkpoint could not complete, because of concurrent readers or writers.
+    pub busy: bool,
+    pub wal_pages: i64,
+    pub checkpointed_pages: i64,
+}
+
+#[cfg(not(feature = "postgres"))]
+#[derive(Clone, Copy, Debug)]
+pub struct PageStats {
+    pub page_count: i64,
+    pub freelist_count: i64,
+}
+
+#[cfg(not(feature = "postgres"))]
+impl PageStats {
+    /// Fraction of unused pages, reclaimed by `VACUUM`.
+    pub fn free_ratio(&self) -> f64 {
+        match self.page_count {
+            0 => 0.,
+            count => self.freelist_count as f64 / count as f64,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Migration {
+    pub version: String,
+    pub run_on: NaiveDateTime,
+}
+
+impl DbExecutor {
+    /// Runs `integrity_check` or the faster `quick_check`, which skips index verification.
+    /// Returns an empty list when no problems were found.
+    #[cfg(not(feature = "postgres"))]
+    pub async fn integrity_check(&self, quick: bool) -> Result<Vec<String>, Error> {
+        let query = match quick {
+            true => "SELECT quick_check AS result FROM pragma_quick_check()",
+            false => "SELECT integrity_check AS result FROM pragma_integrity_check()",
+        };
+        self.with_connection(move |conn| {
+            let rows = diesel::sql_query(query).load::<CheckRow>(conn)?;
+            Ok(rows
+                .into_iter()
+                .map(|row| row.result)
+                .filter(|result| result != "ok")
+                .collect())
+        })
+        .await
+    }
+
+    /// Moves WAL contents into the database file and truncates the WAL.
+    #[cfg(not(feature = "postgres"))]
+    pub async fn checkpoint(&self) -> Result<Checkpoint, Error> {
+        self.with_connection(|conn| {
+            let row = diesel::sql_query("PRAGMA wal_checkpoint(TRUNCATE)")
+                .get_result::<CheckpointRow>(conn)?;
+            Ok(Checkpoint {
+                busy: row.busy != 0,
+                wal_pages: row.log,
+                checkpointed_pages: row.checkpointed,
+            })
+        })
+        .await
+    }
+
+    #[cfg(not(feature = "postgres"))]
+    pub async fn page_stats(&self) -> Result<PageStats, Error> {
+        self.with_connection(|conn| {
+            let row = diesel::sql_query(
+                "SELECT page_count, freelist_count FROM pragma_page_count(), pragma_freelist_count()",
+            )
+            .get_result::<PagesRow>(conn)?;
+            Ok(PageStats {
+                page_count: row.page_count,
+                freelist_count: row.freelist_count,
+            })
+        })
+        .await
+    }
+
+    pub async fn vacuum(&self) -> Result<(), Error> {
+        self.with_connection(|conn| {
+            conn.execute("VACUUM;")?;
+            Ok(())
+        })
+        .await
+    }
+
+    /// Lists migrations applied to the database, oldest first.
+    pub async fn applied_migrations(&self) -> Result<Vec<Migration>, Error> {
+        self.with_connection(|conn| {
+            let rows = diesel::sql_query(
+                "SELECT version, run_on FROM __diesel_schema_migrations ORDER BY version",
+            )
+            .load::<MigrationRow>(conn)?;
+            Ok(rows
+                .into_iter()
+                .map(|row| Migration {
+                    version: row.version,
+                    run_on: row.run_on,
+                })
+                .collect())
+        })
+        .await
+    }
+}
+
+#[cfg(all(test, not(feature = "postgres")))]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_maintenance() -> anyhow::Result<()> {
+        let temp_dir = tempdir::TempDir::new("maintenance")?;
+        let db = DbExecutor::from_data_dir(temp_dir.path(), "test")?;
+        db.execute("CREATE TABLE test (id INTEGER PRIMARY KEY, value TEXT);")
+            .await?;
+        db.execute("INSERT INTO test (value) VALUES ('a'), ('b');")
+            .await?;
+
+        assert!(db.integrity_check(false).await?.is_empty());
+        assert!(db.integrity_check(true).await?.is_empty());
+        assert!(!db.checkpoint().await?.busy);
+        assert!(db.page_stats().await?.page_count > 0);
+        db.vacuum().await?;
+        // No migrations were run on this database
+        assert!(db.applied_migrations().await.is_err());
+        Ok(())
+    }
+}
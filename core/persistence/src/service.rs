@@ -1,14 +1,27 @@
 #![allow(clippy::ptr_arg)]
 
+#[cfg(not(feature = "postgres"))]
 use std::path::{Path, PathBuf};
+#[cfg(not(feature = "postgres"))]
+use std::time::Duration;
 use structopt::StructOpt;
 
 use ya_service_api::{CliCtx, CommandOutput};
 use ya_service_api_interfaces::{Provider, Service};
+#[cfg(not(feature = "postgres"))]
 use ya_utils_process::lock::ProcLock;
 
+#[cfg(not(feature = "postgres"))]
 use crate::executor::DbExecutor;
 
+#[cfg(not(feature = "postgres"))]
+const MAINTENANCE_INTERVAL_ENV_VAR: &str = "YAGNA_DB_MAINTENANCE_INTERVAL";
+#[cfg(not(feature = "postgres"))]
+const DEFAULT_MAINTENANCE_INTERVAL: Duration = Duration::from_secs(24 * 3600);
+/// Databases are vacuumed in background when free pages exceed this fraction of the file.
+#[cfg(not(feature = "postgres"))]
+const VACUUM_FREE_RATIO: f64 = 0.25;
+
 /// Persistence service
 pub struct Persistence;
 
@@ -17,10 +30,23 @@ impl Service for Persistence {
 }
 
 impl Persistence {
-    /// Run DB vacuum on startup
+    /// Run DB vacuum on startup and schedule periodic maintenance
+    #[cfg(not(feature = "postgres"))]
     pub async fn gsb<Context: Provider<Self, CliCtx>>(context: &Context) -> anyhow::Result<()> {
         let ctx = context.component();
         vacuum(&ctx.data_dir, filter::wal_larger_than_db, true).await?;
+
+        let interval = match std::env::var(MAINTENANCE_INTERVAL_ENV_VAR) {
+            Ok(value) => humantime::parse_duration(&value)?,
+            Err(_) => DEFAULT_MAINTENANCE_INTERVAL,
+        };
+        tokio::task::spawn_local(maintenance_forever(ctx.data_dir, interval));
+        Ok(())
+    }
+
+    #[cfg(feature = "postgres")]
+    pub async fn gsb<Context: Provider<Self, CliCtx>>(_context: &Context) -> anyhow::Result<()> {
+        log::debug!("Using PostgreSQL, database maintenance is left to the server");
         Ok(())
     }
 }
@@ -35,22 +61,42 @@ pub enum Command {
         #[structopt(long)]
         force: bool,
     },
+    /// Check databases for corruption
+    Check {
+        /// Skip verification of indexes
+        #[structopt(long)]
+        quick: bool,
+    },
+    /// Move write-ahead log contents into databases
+    Checkpoint,
+    /// Show migrations applied to databases
+    Migrations,
 }
 
 impl Command {
+    #[cfg(not(feature = "postgres"))]
     pub async fn run_command(self, ctx: &CliCtx) -> anyhow::Result<CommandOutput> {
         match self {
             Command::Vacuum { force } => vacuum(&ctx.data_dir, filter::any, force).await,
+            Command::Check { quick } => check(&ctx.data_dir, quick).await,
+            Command::Checkpoint => checkpoint(&ctx.data_dir).await,
+            Command::Migrations => migrations(&ctx.data_dir).await,
         }
     }
+
+    #[cfg(feature = "postgres")]
+    pub async fn run_command(self, _ctx: &CliCtx) -> anyhow::Result<CommandOutput> {
+        anyhow::bail!("Database commands apply to SQLite only. Use PostgreSQL tools instead.")
+    }
 }
 
-async fn vacuum<F, P>(data_dir: P, filter: F, force: bool) -> anyhow::Result<CommandOutput>
+#[cfg(not(feature = "postgres"))]
+fn db_files<F, P>(data_dir: P, filter: F) -> anyhow::Result<Vec<PathBuf>>
 where
     F: Fn(&PathBuf) -> bool,
     P: AsRef<Path>,
 {
-    let db_files = std::fs::read_dir(&data_dir)?
+    let mut db_files = std::fs::read_dir(&data_dir)?
         .filter_map(|r| r.map(|e| e.path()).ok())
         .filter(|p| !p.is_dir())
         .filter(|p| {
@@ -63,6 +109,25 @@ where
         })
         .filter(filter)
         .collect::<Vec<_>>();
+    db_files.sort();
+    Ok(db_files)
+}
+
+#[cfg(not(feature = "postgres"))]
+fn db_name(db_file: &Path) -> String {
+    db_file
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(not(feature = "postgres"))]
+async fn vacuum<F, P>(data_dir: P, filter: F, force: bool) -> anyhow::Result<CommandOutput>
+where
+    F: Fn(&PathBuf) -> bool,
+    P: AsRef<Path>,
+{
+    let db_files = db_files(&data_dir, filter)?;
 
     if db_files.is_empty() {
         return Ok(CommandOutput::Object(serde_json::Value::String(
@@ -86,6 +151,147 @@ where
     Ok(CommandOutput::NoOutput)
 }
 
+#[cfg(not(feature = "postgres"))]
+async fn check<P: AsRef<Path>>(data_dir: P, quick: bool) -> anyhow::Result<CommandOutput> {
+    let mut values = Vec::new();
+    let mut corrupted = 0;
+    for db_file in db_files(&data_dir, filter::any)? {
+        let db = DbExecutor::new(db_file.display().to_string())?;
+        let status = match db.integrity_check(quick).await {
+            Ok(errors) if errors.is_empty() => "ok".to_string(),
+            Ok(errors) => {
+                corrupted += 1;
+                errors.join("; ")
+            }
+            Err(e) => format!("check failed: {}", e),
+        };
+        values.push(serde_json::json!([db_name(&db_file), status]));
+    }
+
+    Ok(CommandOutput::Table {
+        columns: vec!["database".into(), "status".into()],
+        values,
+        summary: vec![],
+        header: match corrupted {
+            0 => None,
+            n => Some(format!(
+                "{} database(s) corrupted. Stop the daemon and restore them from a backup.",
+                n
+            )),
+        },
+    })
+}
+
+#[cfg(not(feature = "postgres"))]
+async fn checkpoint<P: AsRef<Path>>(data_dir: P) -> anyhow::Result<CommandOutput> {
+    let mut values = Vec::new();
+    for db_file in db_files(&data_dir, filter::any)? {
+        let db = DbExecutor::new(db_file.display().to_string())?;
+        let checkpoint = db.checkpoint().await?;
+        values.push(serde_json::json!([
+            db_name(&db_file),
+            checkpoint.wal_pages,
+            checkpoint.checkpointed_pages,
+            if checkpoint.busy { "busy" } else { "ok" },
+        ]));
+    }
+
+    Ok(CommandOutput::Table {
+        columns: vec![
+            "database".into(),
+            "wal pages".into(),
+            "checkpointed".into(),
+            "status".into(),
+        ],
+        values,
+        summary: vec![],
+        header: None,
+    })
+}
+
+#[cfg(not(feature = "postgres"))]
+async fn migrations<P: AsRef<Path>>(data_dir: P) -> anyhow::Result<CommandOutput> {
+    let mut values = Vec::new();
+    for db_file in db_files(&data_dir, filter::any)? {
+        let db = DbExecutor::new(db_file.display().to_string())?;
+        // Databases without migrations (e.g. created by other tools) are listed as empty
+        let applied = db.applied_migrations().await.unwrap_or_default();
+        let latest = applied.last();
+        values.push(serde_json::json!([
+            db_name(&db_file),
+            applied.len(),
+            latest.map(|m| m.version.clone()),
+            latest.map(|m| m.run_on.format("%Y-%m-%d %H:%M:%S").to_string()),
+        ]));
+    }
+
+    Ok(CommandOutput::Table {
+        columns: vec![
+            "database".into(),
+            "applied".into(),
+            "latest".into(),
+            "applied at".into(),
+        ],
+        values,
+        summary: vec![],
+        header: None,
+    })
+}
+
+#[cfg(not(feature = "postgres"))]
+async fn maintenance_forever(data_dir: PathBuf, period: Duration) {
+    let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+    loop {
+        interval.tick().await;
+        log::debug!("Database maintenance started");
+        match db_files(&data_dir, filter::any) {
+            Ok(db_files) => {
+                for db_file in db_files {
+                    if let Err(e) = maintain(&db_file).await {
+                        log::warn!("Database {} maintenance failed: {}", db_file.display(), e);
+                    }
+                }
+            }
+            Err(e) => log::warn!("Unable to list databases: {}", e),
+        }
+        log::debug!("Database maintenance done");
+    }
+}
+
+#[cfg(not(feature = "postgres"))]
+async fn maintain(db_file: &Path) -> anyhow::Result<()> {
+    let name = db_name(db_file);
+    let db = DbExecutor::new(db_file.display().to_string())?;
+
+    let errors = db.integrity_check(true).await?;
+    if !errors.is_empty() {
+        log::error!(
+            "Database {} is corrupted: {}. Run `yagna db check` for details.",
+            name,
+            errors.join("; ")
+        );
+        return Ok(());
+    }
+
+    let checkpoint = db.checkpoint().await?;
+    if checkpoint.busy {
+        log::debug!("Database {} checkpoint incomplete, database is busy", name);
+    }
+
+    let stats = db.page_stats().await?;
+    if stats.free_ratio() > VACUUM_FREE_RATIO {
+        log::info!(
+            "Vacuuming database {} ({} of {} pages unused)",
+            name,
+            stats.freelist_count,
+            stats.page_count
+        );
+        db.vacuum().await?;
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "postgres"))]
 mod filter {
     use std::path::PathBuf;
 
@@ -110,7 +316,7 @@ mod filter {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, not(feature = "postgres")))]
 mod tests {
     use std::fs::OpenOptions;
     use std::path::Path;
@@ -36,6 +36,23 @@ impl ToSql<Timestamp, Sqlite> for TimestampAdapter {
     }
 }
 
+/// PostgreSQL stores timestamps in binary form, so no formatting is needed.
+#[cfg(feature = "postgres")]
+impl FromSql<Timestamp, diesel::pg::Pg> for TimestampAdapter {
+    fn from_sql(
+        value: Option<&<diesel::pg::Pg as Backend>::RawValue>,
+    ) -> deserialize::Result<Self> {
+        Ok(NaiveDateTime::from_sql(value)?.adapt())
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl ToSql<Timestamp, diesel::pg::Pg> for TimestampAdapter {
+    fn to_sql<W: Write>(&self, out: &mut Output<W, diesel::pg::Pg>) -> serialize::Result {
+        ToSql::<Timestamp, diesel::pg::Pg>::to_sql(&self.0, out)
+    }
+}
+
 impl TimestampAdapter {
     pub fn format(&self) -> String {
         self.0.format("%F %T.%6f").to_string()
@@ -1,5 +1,8 @@
 use std::path::PathBuf;
 
+pub mod recovery;
+pub mod reload;
+
 pub use ya_utils_cli::{CommandOutput, ResponseTable};
 
 #[derive(Clone, Debug, Default)]
@@ -7,6 +10,7 @@ pub struct MetricsCtx {
     pub push_enabled: bool,
     pub push_host_url: Option<url::Url>,
     pub job: String,
+    pub expose_addr: Option<std::net::SocketAddr>,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -0,0 +1,131 @@
+//! Startup recovery report.
+//!
+//! On startup modules look for work left unfinished by the previous run
+//! and report what they did about it.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RecoveryAction {
+    /// Processing continues where it stopped.
+    Resumed,
+    /// Interrupted operation was reverted to its previous state.
+    RolledBack,
+    /// Can't be handled automatically.
+    NeedsAttention,
+}
+
+impl fmt::Display for RecoveryAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            RecoveryAction::Resumed => "resumed",
+            RecoveryAction::RolledBack => "rolled back",
+            RecoveryAction::NeedsAttention => "needs attention",
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecoveryItem {
+    pub module: String,
+    /// Id of the recovered object (activity, agreement, transaction, ...).
+    pub subject: String,
+    /// State the object was found in.
+    pub state: String,
+    pub action: RecoveryAction,
+    pub details: Option<String>,
+}
+
+impl RecoveryItem {
+    pub fn new(
+        module: impl ToString,
+        subject: impl ToString,
+        state: impl ToString,
+        action: RecoveryAction,
+    ) -> Self {
+        RecoveryItem {
+            module: module.to_string(),
+            subject: subject.to_string(),
+            state: state.to_string(),
+            action,
+            details: None,
+        }
+    }
+
+    pub fn details(mut self, details: impl ToString) -> Self {
+        self.details = Some(details.to_string());
+        self
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecoveryFailure {
+    pub module: String,
+    pub error: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecoveryReport {
+    pub items: Vec<RecoveryItem>,
+    /// Modules which failed to inspect their state.
+    pub failures: Vec<RecoveryFailure>,
+}
+
+impl RecoveryReport {
+    /// Adds result of a module's recovery pass.
+    pub fn add(&mut self, module: &str, result: anyhow::Result<Vec<RecoveryItem>>) {
+        match result {
+            Ok(items) => self.items.extend(items),
+            Err(e) => self.failures.push(RecoveryFailure {
+                module: module.to_string(),
+                error: e.to_string(),
+            }),
+        }
+    }
+
+    pub fn count(&self, action: RecoveryAction) -> usize {
+        self.items
+            .iter()
+            .filter(|item| item.action == action)
+            .count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty() && self.failures.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_add() {
+        let mut report = RecoveryReport::default();
+        report.add(
+            "activity",
+            Ok(vec![
+                RecoveryItem::new("activity", "a1", "Ready", RecoveryAction::Resumed),
+                RecoveryItem::new("activity", "a2", "Ready", RecoveryAction::NeedsAttention),
+            ]),
+        );
+        report.add("payment", Err(anyhow::anyhow!("db locked")));
+
+        assert_eq!(report.count(RecoveryAction::Resumed), 1);
+        assert_eq!(report.count(RecoveryAction::RolledBack), 0);
+        assert_eq!(report.count(RecoveryAction::NeedsAttention), 1);
+        assert_eq!(
+            report.failures,
+            vec![RecoveryFailure {
+                module: "payment".to_string(),
+                error: "db locked".to_string(),
+            }]
+        );
+        assert!(!report.is_empty());
+    }
+}
@@ -0,0 +1,46 @@
+//! Settings reloaded from the configuration file while the daemon is running.
+//!
+//! Environment can't be modified safely once other threads run, so reloaded
+//! values are kept here. Modules read reloadable settings with [`var`]
+//! instead of `std::env::var`.
+
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+lazy_static! {
+    /// `None` when the setting was removed from the configuration file.
+    static ref RELOADED: RwLock<HashMap<String, Option<String>>> = Default::default();
+}
+
+/// Reloaded value of `name`, or the environment variable when it wasn't reloaded.
+pub fn var(name: &str) -> Option<String> {
+    match RELOADED.read().unwrap().get(name) {
+        Some(value) => value.clone(),
+        None => std::env::var(name).ok(),
+    }
+}
+
+/// Sets the reloaded value of `name`, `None` removes it.
+pub fn set(name: &str, value: Option<String>) {
+    RELOADED.write().unwrap().insert(name.to_string(), value);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reloaded_var() {
+        const NAME: &str = "YA_SERVICE_API_TEST_RELOADED_VAR";
+        std::env::set_var(NAME, "env");
+        assert_eq!(var(NAME).as_deref(), Some("env"));
+
+        set(NAME, Some("file".to_string()));
+        assert_eq!(var(NAME).as_deref(), Some("file"));
+        assert_eq!(std::env::var(NAME).as_deref(), Ok("env"));
+
+        set(NAME, None);
+        assert_eq!(var(NAME), None);
+    }
+}
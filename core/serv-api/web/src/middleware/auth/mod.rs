@@ -7,7 +7,8 @@ pub use crate::middleware::auth::resolver::AppKeyCache;
 
 use actix_service::{Service, Transform};
 use actix_web::dev::{ServiceRequest, ServiceResponse};
-use actix_web::error::{Error, ErrorUnauthorized, ParseError};
+use actix_web::error::{Error, ErrorForbidden, ErrorUnauthorized, ParseError};
+use actix_web::http::Method;
 use actix_web::{web, HttpMessage};
 use actix_web_httpauth::headers::authorization::{Bearer, Scheme};
 use futures::future::{ok, Future, Ready};
@@ -99,6 +100,18 @@ where
         Box::pin(async move {
             match header {
                 Some(key) => match cache.get_appkey(&key) {
+                    Some(app_key) if !app_key.allows(req.path(), is_read_only(req.method())) => {
+                        log::debug!(
+                            "{} {} Application key `{}` out of scope: {:?}",
+                            req.method(),
+                            req.path(),
+                            app_key.name,
+                            app_key.scopes,
+                        );
+                        Err(ErrorForbidden(
+                            "Application key not allowed to access this API",
+                        ))
+                    }
                     Some(app_key) => {
                         req.extensions_mut().insert(Identity::from(app_key));
                         let fut = { service.borrow_mut().call(req) };
@@ -122,6 +135,10 @@ where
     }
 }
 
+fn is_read_only(method: &Method) -> bool {
+    matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS)
+}
+
 pub(crate) fn parse_auth<S: Scheme, T: HttpMessage>(msg: &T) -> Result<S, ParseError> {
     let header = msg
         .headers()
@@ -0,0 +1,475 @@
+//! Layered daemon configuration.
+//!
+//! Settings are resolved from command line arguments, environment variables
+//! (including `.env` file), `yagna.toml` and built-in defaults, in that order.
+//! Values from the file are exported as environment variables which aren't
+//! already set, so services reading their configuration from environment
+//! pick them up without any changes. Reloaded values are passed through
+//! `ya_service_api::reload` instead, the environment is set only on startup.
+use anyhow::{bail, Context, Result};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+use structopt::StructOpt;
+
+use ya_sb_proto::{DEFAULT_GSB_URL, GSB_URL_ENV_VAR};
+use ya_service_api::{CliCtx, CommandOutput, ResponseTable};
+use ya_service_api_web::{DEFAULT_YAGNA_API_URL, YAGNA_API_URL_ENV_VAR};
+
+pub const CONFIG_FILE_NAME: &str = "yagna.toml";
+pub const CONFIG_ENV_VAR: &str = "YAGNA_CONFIG";
+/// Table for environment variables without a dedicated key.
+const ENV_TABLE: &str = "env";
+const RELOAD_INTERVAL: Duration = Duration::from_secs(5);
+
+struct Key {
+    name: &'static str,
+    env: &'static str,
+    default: Option<&'static str>,
+    /// Value is read with `ya_service_api::reload::var` on every use,
+    /// so it can be changed while the daemon is running.
+    reload: bool,
+}
+
+const fn key(name: &'static str, env: &'static str, default: Option<&'static str>) -> Key {
+    Key {
+        name,
+        env,
+        default,
+        reload: false,
+    }
+}
+
+const fn reloadable(name: &'static str, env: &'static str, default: Option<&'static str>) -> Key {
+    Key {
+        name,
+        env,
+        default,
+        reload: true,
+    }
+}
+
+const KEYS: &[Key] = &[
+    key(
+        "api.url",
+        YAGNA_API_URL_ENV_VAR,
+        Some(DEFAULT_YAGNA_API_URL),
+    ),
+    key("gsb.url", GSB_URL_ENV_VAR, Some(DEFAULT_GSB_URL)),
+    key("log.dir", "YAGNA_LOG_DIR", None),
+    key("log.filter", "RUST_LOG", Some("info")),
+    key("net.type", "YA_NET_TYPE", Some("hybrid")),
+    key("net.relay-host", "YA_NET_RELAY_HOST", None),
+    key(
+        "net.bind-url",
+        "YA_NET_BIND_URL",
+        Some("udp://0.0.0.0:11500"),
+    ),
+    key(
+        "net.relay-fallback",
+        "YA_NET_RELAY_FALLBACK",
+        Some("always"),
+    ),
+    key(
+        "net.session-idle-timeout",
+        "YA_NET_SESSION_IDLE_TIMEOUT",
+        Some("5min"),
+    ),
+    key("net.max-sessions", "YA_NET_MAX_SESSIONS", None),
+    key(
+        "activity.usage-history-size",
+        "ACTIVITY_USAGE_HISTORY_SIZE",
+        Some("360"),
+    ),
+    key(
+        "market.reputation-min-score",
+        "MARKET_REPUTATION_MIN_SCORE",
+        Some("0"),
+    ),
+    key(
+        "payment.shutdown-timeout-secs",
+        "PAYMENT_SHUTDOWN_TIMEOUT_SECS",
+        Some("10"),
+    ),
+    reloadable("erc20.mainnet.geth-addr", "MAINNET_GETH_ADDR", None),
+    reloadable("erc20.goerli.geth-addr", "GOERLI_GETH_ADDR", None),
+    reloadable("erc20.polygon.geth-addr", "POLYGON_GETH_ADDR", None),
+    reloadable("erc20.mumbai.geth-addr", "MUMBAI_GETH_ADDR", None),
+    reloadable(
+        "erc20.polygon.gas-price-method",
+        "POLYGON_GAS_PRICE_METHOD",
+        Some("dynamic"),
+    ),
+    reloadable(
+        "erc20.polygon.max-gas-price-dynamic",
+        "POLYGON_MAX_GAS_PRICE_DYNAMIC",
+        Some("1000"),
+    ),
+    reloadable("erc20.polygon.priority", "POLYGON_PRIORITY", Some("slow")),
+];
+
+lazy_static::lazy_static! {
+    static ref STATE: Mutex<State> = Default::default();
+}
+
+#[derive(Default)]
+struct State {
+    path: Option<PathBuf>,
+    /// Variables set by the user before the configuration file was applied.
+    user_env: HashMap<String, String>,
+    /// Variables set from the configuration file.
+    applied: HashMap<String, String>,
+}
+
+/// Configuration file contents, keyed by environment variable names.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ConfigFile {
+    vars: BTreeMap<String, Entry>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Entry {
+    key: String,
+    value: String,
+}
+
+impl ConfigFile {
+    pub fn read(path: &Path) -> Result<Option<Self>> {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e).context(format!("reading {}", path.display())),
+        };
+        Self::parse(&content)
+            .with_context(|| format!("invalid configuration file {}", path.display()))
+            .map(Some)
+    }
+
+    fn parse(content: &str) -> Result<Self> {
+        let table: toml::value::Table = toml::from_str(content)?;
+        let mut entries = Vec::new();
+        flatten("", &table, &mut entries)?;
+
+        let mut vars = BTreeMap::new();
+        for (key, value) in entries {
+            let env = match key.strip_prefix(&format!("{ENV_TABLE}.")) {
+                Some(env) => env.to_string(),
+                None => match KEYS.iter().find(|k| k.name == key) {
+                    Some(k) => k.env.to_string(),
+                    None => bail!("unknown key '{key}'"),
+                },
+            };
+            vars.insert(env, Entry { key, value });
+        }
+        Ok(ConfigFile { vars })
+    }
+}
+
+fn flatten(
+    prefix: &str,
+    table: &toml::value::Table,
+    out: &mut Vec<(String, String)>,
+) -> Result<()> {
+    for (name, value) in table {
+        let key = match prefix {
+            "" => name.clone(),
+            _ => format!("{prefix}.{name}"),
+        };
+        match value {
+            toml::Value::Table(table) => flatten(&key, table, out)?,
+            // Lists are passed to services as comma separated values
+            toml::Value::Array(values) => {
+                let values = values
+                    .iter()
+                    .map(|v| scalar(v).ok_or_else(|| anyhow::anyhow!("invalid value of '{key}'")))
+                    .collect::<Result<Vec<_>>>()?;
+                out.push((key, values.join(",")));
+            }
+            value => match scalar(value) {
+                Some(value) => out.push((key, value)),
+                None => bail!("invalid value of '{key}'"),
+            },
+        }
+    }
+    Ok(())
+}
+
+fn scalar(value: &toml::Value) -> Option<String> {
+    match value {
+        toml::Value::String(s) => Some(s.clone()),
+        toml::Value::Integer(i) => Some(i.to_string()),
+        toml::Value::Float(f) => Some(f.to_string()),
+        toml::Value::Boolean(b) => Some(b.to_string()),
+        toml::Value::Datetime(d) => Some(d.to_string()),
+        toml::Value::Array(_) | toml::Value::Table(_) => None,
+    }
+}
+
+/// `YAGNA_CONFIG` or `yagna.toml` in the data directory.
+pub fn path(data_dir: &Path) -> PathBuf {
+    std::env::var_os(CONFIG_ENV_VAR)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| data_dir.join(CONFIG_FILE_NAME))
+}
+
+/// Applies the configuration file found for `data_dir`.
+/// Returns `true` if any variable was set, which requires parsing arguments again.
+pub fn load(data_dir: &Path) -> Result<bool> {
+    let path = path(data_dir);
+    let config = ConfigFile::read(&path)?;
+
+    let mut state = STATE.lock().unwrap();
+    state.user_env = KEYS
+        .iter()
+        .map(|k| k.env.to_string())
+        .chain(config.iter().flat_map(|c| c.vars.keys().cloned()))
+        .filter_map(|env| std::env::var(&env).ok().map(|value| (env, value)))
+        .collect();
+    state.path = Some(path);
+
+    let config = match config {
+        Some(config) => config,
+        None => return Ok(false),
+    };
+    let mut changed = false;
+    for (env, entry) in config.vars {
+        if !state.user_env.contains_key(&env) {
+            std::env::set_var(&env, &entry.value);
+            state.applied.insert(env, entry.value);
+            changed = true;
+        }
+    }
+    Ok(changed)
+}
+
+/// Periodically checks the configuration file for changes
+/// and applies new values of keys which are safe to reload.
+pub fn watch(path: PathBuf) {
+    tokio::spawn(async move {
+        let mut modified = modified(&path);
+        loop {
+            tokio::time::sleep(RELOAD_INTERVAL).await;
+
+            let current = modified(&path);
+            if current == modified {
+                continue;
+            }
+            modified = current;
+
+            match ConfigFile::read(&path) {
+                Ok(config) => reload(config.unwrap_or_default()),
+                Err(e) => log::warn!("Unable to reload configuration: {e:?}"),
+            }
+        }
+    });
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+fn reload(config: ConfigFile) {
+    let mut state = STATE.lock().unwrap();
+
+    let removed = state
+        .applied
+        .keys()
+        .filter(|env| !config.vars.contains_key(*env))
+        .cloned()
+        .collect::<Vec<_>>();
+    let changed = config
+        .vars
+        .iter()
+        .filter(|(env, _)| !state.user_env.contains_key(*env))
+        .filter(|(env, entry)| state.applied.get(*env) != Some(&entry.value))
+        .map(|(env, entry)| (env.clone(), Some(entry.value.clone())))
+        .chain(removed.into_iter().map(|env| (env, None)))
+        .collect::<Vec<_>>();
+
+    for (env, value) in changed {
+        let key = KEYS.iter().find(|k| k.env == env);
+        let name = key.map(|k| k.name).unwrap_or(&env);
+
+        if !key.map(|k| k.reload).unwrap_or(false) {
+            log::warn!("Configuration key '{name}' changed, restart is required to apply it");
+            continue;
+        }
+
+        log::info!("Reloading configuration key '{name}'");
+        ya_service_api::reload::set(&env, value.clone());
+        match value {
+            Some(value) => state.applied.insert(env, value),
+            None => state.applied.remove(&env),
+        };
+    }
+}
+
+#[derive(StructOpt, Debug)]
+pub enum ConfigCommand {
+    /// Show configuration
+    Show {
+        /// Show resolved values of all keys with their sources
+        #[structopt(long)]
+        effective: bool,
+    },
+}
+
+impl ConfigCommand {
+    pub fn run_command(&self, ctx: &CliCtx) -> Result<CommandOutput> {
+        match self {
+            ConfigCommand::Show { effective: false } => show_file(),
+            ConfigCommand::Show { effective: true } => show_effective(ctx),
+        }
+    }
+}
+
+fn show_file() -> Result<CommandOutput> {
+    let path = STATE.lock().unwrap().path.clone();
+    let path = match path {
+        Some(path) => path,
+        None => bail!("Configuration wasn't loaded"),
+    };
+    let config = match ConfigFile::read(&path)? {
+        Some(config) => config,
+        None => {
+            return CommandOutput::object(format!(
+                "Configuration file {} doesn't exist",
+                path.display()
+            ))
+        }
+    };
+
+    Ok(ResponseTable {
+        columns: vec!["key".into(), "env".into(), "value".into()],
+        values: config
+            .vars
+            .iter()
+            .map(|(env, entry)| serde_json::json! {[entry.key, env, display(env, &entry.value)]})
+            .collect(),
+    }
+    .into())
+}
+
+fn show_effective(ctx: &CliCtx) -> Result<CommandOutput> {
+    let state = STATE.lock().unwrap();
+    let cli_value = |env: &str| match env {
+        GSB_URL_ENV_VAR => ctx.gsb_url.as_ref().map(|url| url.to_string()),
+        _ => None,
+    };
+
+    let mut extra = state
+        .applied
+        .keys()
+        .chain(state.user_env.keys())
+        .filter(|env| !KEYS.iter().any(|k| k.env == env.as_str()))
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect::<Vec<_>>();
+    extra.sort();
+
+    let rows = KEYS
+        .iter()
+        .map(|k| (k.name.to_string(), k.env, k.default, k.reload))
+        .chain(
+            extra
+                .into_iter()
+                .map(|env| (format!("{ENV_TABLE}.{env}"), env.as_str(), None, false)),
+        )
+        .map(|(name, env, default, reload)| {
+            let (mut value, mut source) = match (state.user_env.get(env), state.applied.get(env)) {
+                (Some(value), _) => (Some(value.clone()), "env"),
+                (None, Some(value)) => (Some(value.clone()), "file"),
+                (None, None) => (default.map(ToString::to_string), "default"),
+            };
+            if let Some(cli) = cli_value(env) {
+                if !value
+                    .as_deref()
+                    .map(|v| same_value(v, &cli))
+                    .unwrap_or(false)
+                {
+                    value = Some(cli);
+                    source = "cli";
+                }
+            }
+
+            serde_json::json! {[
+                name,
+                env,
+                value.map(|v| display(env, &v)),
+                source,
+                if reload { "x" } else { "" },
+            ]}
+        })
+        .collect();
+
+    Ok(ResponseTable {
+        columns: vec![
+            "key".into(),
+            "env".into(),
+            "value".into(),
+            "source".into(),
+            "reload".into(),
+        ],
+        values: rows,
+    }
+    .into())
+}
+
+/// Arguments parsed by `structopt` may be normalized, e.g. URLs.
+fn same_value(a: &str, b: &str) -> bool {
+    a.trim_end_matches('/') == b.trim_end_matches('/')
+}
+
+/// Hides values of variables which look like secrets.
+fn display(env: &str, value: &str) -> String {
+    let env = env.to_uppercase();
+    match ["KEY", "SECRET", "PASSWORD", "TOKEN"]
+        .iter()
+        .any(|s| env.contains(s))
+    {
+        true => "<hidden>".to_string(),
+        false => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_config() {
+        let config = ConfigFile::parse(
+            r#"
+            [api]
+            url = "http://127.0.0.1:17465"
+
+            [erc20.polygon]
+            geth-addr = ["https://a.example", "https://b.example"]
+            max-gas-price-dynamic = 500
+
+            [env]
+            YA_NET_BROADCAST_SIZE = 5
+            "#,
+        )
+        .unwrap();
+
+        let value = |env: &str| config.vars.get(env).map(|e| e.value.as_str());
+        assert_eq!(value(YAGNA_API_URL_ENV_VAR), Some("http://127.0.0.1:17465"));
+        assert_eq!(
+            value("POLYGON_GETH_ADDR"),
+            Some("https://a.example,https://b.example")
+        );
+        assert_eq!(value("POLYGON_MAX_GAS_PRICE_DYNAMIC"), Some("500"));
+        assert_eq!(value("YA_NET_BROADCAST_SIZE"), Some("5"));
+        assert_eq!(
+            config.vars["YA_NET_BROADCAST_SIZE"].key,
+            "env.YA_NET_BROADCAST_SIZE"
+        );
+    }
+
+    #[test]
+    fn test_parse_unknown_key() {
+        assert!(ConfigFile::parse("[api]\nport = 7465").is_err());
+    }
+}
@@ -0,0 +1,142 @@
+//! Runtime reconfiguration of log levels and per-request log context.
+use actix_web::{dev::ServiceRequest, web, Responder};
+use anyhow::{anyhow, Result};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use ya_file_logging::{LogContext, LoggerHandle};
+use ya_service_api_web::middleware::Identity;
+use ya_service_bus::typed as gsb;
+
+use crate::model;
+
+/// Short names of yagna services, so operators don't need to know crate names.
+const ALIASES: &[(&str, &str)] = &[
+    ("activity", "ya_activity"),
+    ("dummy", "ya_dummy_driver"),
+    ("erc20", "ya_erc20_driver"),
+    ("gsb", "ya_service_bus"),
+    ("gsb-api", "ya_gsb_api"),
+    ("identity", "ya_identity"),
+    ("market", "ya_market"),
+    ("net", "ya_net"),
+    ("payment", "ya_payment"),
+    ("relay", "ya_relay_client"),
+    ("vpn", "ya_vpn"),
+    ("zksync", "ya_zksync_driver"),
+];
+
+/// Parses `<module>=<level>` or `<level>` filter.
+pub fn parse_filter(filter: &str) -> Result<(Option<String>, log::LevelFilter)> {
+    let (module, level) = match filter.split_once('=') {
+        Some((module, level)) => (Some(module.trim()), level.trim()),
+        None => (None, filter.trim()),
+    };
+    let level = log::LevelFilter::from_str(level)
+        .map_err(|_| anyhow!("Invalid log level '{level}' in filter '{filter}'"))?;
+    let module = match module {
+        Some("") => return Err(anyhow!("Empty module name in filter '{filter}'")),
+        Some(module) => Some(
+            ALIASES
+                .iter()
+                .find(|(alias, _)| *alias == module)
+                .map(|(_, name)| name.to_string())
+                .unwrap_or_else(|| module.replace('-', "_")),
+        ),
+        None => None,
+    };
+    Ok((module, level))
+}
+
+const REQUEST_ID_HEADER: &str = "X-Request-Id";
+
+static REQUEST_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// Context attached to logs emitted while handling a REST request.
+/// Correlation id is taken from the `X-Request-Id` header when present.
+pub fn request_context(req: &ServiceRequest) -> LogContext {
+    let correlation_id = req
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(ToString::to_string)
+        .unwrap_or_else(|| {
+            let id = REQUEST_COUNTER.fetch_add(1, Ordering::Relaxed);
+            format!("{}-{id}", std::process::id())
+        });
+
+    LogContext::new("rest")
+        .correlation_id(correlation_id)
+        .field("method", req.method().as_str())
+        .field("path", req.path())
+}
+
+pub fn bind_gsb(handle: LoggerHandle) {
+    gsb::bind(model::BUS_ID, move |msg: model::SetLogLevel| {
+        let mut handle = handle.clone();
+        async move {
+            let result = match msg.reset {
+                true => ya_file_logging::reset_module_filters(&mut handle),
+                false => msg
+                    .filters
+                    .iter()
+                    .map(|filter| parse_filter(filter))
+                    .collect::<Result<Vec<_>>>()
+                    .and_then(|filters| ya_file_logging::set_module_filters(&mut handle, &filters)),
+            };
+            match result {
+                Ok(spec) => {
+                    log::info!("Log level changed to: {spec}");
+                    Ok(spec)
+                }
+                Err(e) => Err(e.to_string()),
+            }
+        }
+    });
+    gsb::bind(model::BUS_ID, |_: model::GetLogLevel| async move {
+        ya_file_logging::current_log_spec().ok_or_else(|| "Logger not started".to_string())
+    });
+}
+
+#[actix_web::get("/_control/log-level")]
+pub async fn get_log_level(_id: Identity) -> impl Responder {
+    let spec = gsb::service(model::BUS_ID)
+        .send(model::GetLogLevel {})
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .map_err(actix_web::error::ErrorInternalServerError)?;
+    Ok::<_, actix_web::Error>(web::Json(spec))
+}
+
+#[actix_web::put("/_control/log-level")]
+pub async fn set_log_level(_id: Identity, body: web::Json<model::SetLogLevel>) -> impl Responder {
+    let spec = gsb::service(model::BUS_ID)
+        .send(body.into_inner())
+        .await
+        .map_err(actix_web::error::ErrorInternalServerError)?
+        .map_err(actix_web::error::ErrorBadRequest)?;
+    Ok::<_, actix_web::Error>(web::Json(spec))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_filter() {
+        assert_eq!(
+            parse_filter("erc20=debug").unwrap(),
+            (Some("ya_erc20_driver".to_string()), log::LevelFilter::Debug)
+        );
+        assert_eq!(
+            parse_filter("ya-relay-client=TRACE").unwrap(),
+            (Some("ya_relay_client".to_string()), log::LevelFilter::Trace)
+        );
+        assert_eq!(
+            parse_filter("warn").unwrap(),
+            (None, log::LevelFilter::Warn)
+        );
+        assert!(parse_filter("market=loud").is_err());
+        assert!(parse_filter("=debug").is_err());
+    }
+}
@@ -1,6 +1,6 @@
 #![allow(clippy::obfuscated_if_else)]
 
-use actix_web::{middleware, web, App, HttpServer, Responder};
+use actix_web::{dev::Service, middleware, web, App, HttpServer, Responder};
 use anyhow::{Context, Result};
 use futures::prelude::*;
 use metrics::gauge;
@@ -19,9 +19,10 @@ use std::{
 use structopt::{clap, StructOpt};
 use url::Url;
 use ya_activity::service::Activity as ActivityService;
-use ya_file_logging::start_logger;
+use ya_file_logging::{start_logger, LogContextExt, LogFormat, LOG_FORMAT_ENV_VAR};
 use ya_gsb_api::GsbApiService;
 use ya_identity::service::Identity as IdentityService;
+use ya_journal::JournalService;
 use ya_market::MarketService;
 use ya_metrics::{MetricsPusherOpts, MetricsService};
 use ya_net::Net as NetService;
@@ -29,7 +30,7 @@ use ya_payment::{accounts as payment_accounts, PaymentService};
 use ya_persistence::executor::{DbExecutor, DbMixedExecutor};
 use ya_persistence::service::Persistence as PersistenceService;
 use ya_sb_proto::{DEFAULT_GSB_URL, GSB_URL_ENV_VAR};
-use ya_service_api::{CliCtx, CommandOutput, ResponseTable};
+use ya_service_api::{recovery::RecoveryReport, CliCtx, CommandOutput, ResponseTable};
 use ya_service_api_interfaces::Provider;
 use ya_service_api_web::{
     middleware::{auth, cors::CorsConfig, Identity},
@@ -44,9 +45,14 @@ use ya_vpn::VpnService;
 use ya_service_bus::typed as gsb;
 
 mod autocomplete;
+mod config;
 mod extension;
+mod logging;
 mod model;
+mod recovery;
+mod shutdown;
 
+use crate::config::ConfigCommand;
 use crate::extension::Extension;
 use autocomplete::CompleteCommand;
 
@@ -108,6 +114,16 @@ struct CliArgs {
     #[structopt(long, set = clap::ArgSettings::Global)]
     quiet: bool,
 
+    /// Log output format
+    #[structopt(
+        long,
+        env = LOG_FORMAT_ENV_VAR,
+        default_value = "text",
+        possible_values = LogFormat::VARIANTS,
+        set = clap::ArgSettings::Global,
+    )]
+    log_format: LogFormat,
+
     #[structopt(subcommand)]
     command: CliCommand,
 }
@@ -120,7 +136,7 @@ impl CliArgs {
     pub async fn run_command(self) -> Result<()> {
         let ctx: CliCtx = (&self).try_into()?;
 
-        ctx.output(self.command.run_command(&ctx).await?)?;
+        ctx.output(self.command.run_command(&ctx, self.log_format).await?)?;
         Ok(())
     }
 }
@@ -216,6 +232,7 @@ impl TryFrom<CliCtx> for ServiceContext {
         let dbs = [
             Self::make_entry::<ActivityService>(&ctx.data_dir, "activity")?,
             Self::make_entry::<PaymentService>(&ctx.data_dir, "payment")?,
+            Self::make_entry::<JournalService>(&ctx.data_dir, "journal")?,
         ]
         .iter()
         .cloned()
@@ -243,10 +260,13 @@ enum Services {
     // Metrics service must be activated before all other services
     // to that will use it. Identity service is used by the Metrics,
     // so must be initialized before.
-    #[enable(gsb, cli(flatten))]
+    #[enable(gsb, rest, cli(flatten))]
     Identity(IdentityService),
     #[enable(gsb, rest)]
     Metrics(MetricsService),
+    // Journal service must be activated before services recording events.
+    #[enable(gsb, rest, cli)]
+    Journal(JournalService),
     #[enable(gsb, rest, cli)]
     Version(VersionService),
     #[enable(gsb, rest, cli)]
@@ -274,7 +294,10 @@ enum Services {
 compile_error!("At least one payment driver needs to be enabled in order to make payments.");
 
 #[allow(unused)]
-async fn start_payment_drivers(data_dir: &Path) -> anyhow::Result<Vec<String>> {
+async fn start_payment_drivers(
+    data_dir: &Path,
+    recovery: &mut RecoveryReport,
+) -> anyhow::Result<Vec<String>> {
     let mut drivers = vec![];
     #[cfg(feature = "dummy-driver")]
     {
@@ -287,10 +310,16 @@ async fn start_payment_drivers(data_dir: &Path) -> anyhow::Result<Vec<String>> {
         use ya_erc20_driver::{PaymentDriverService, DRIVER_NAME};
         let db_executor = DbExecutor::from_data_dir(data_dir, "erc20-driver")?;
         PaymentDriverService::gsb(&db_executor).await?;
+        recovery.add(
+            DRIVER_NAME,
+            PaymentDriverService::recover(&db_executor).await,
+        );
         drivers.push(DRIVER_NAME.to_owned());
     }
     #[cfg(feature = "zksync-driver")]
-    {
+    if ya_payment_driver::utils::dry_run() {
+        log::warn!("zkSync driver doesn't support payments dry-run mode, not starting it");
+    } else {
         use ya_zksync_driver::{PaymentDriverService, DRIVER_NAME};
         let db_executor = DbExecutor::from_data_dir(data_dir, "zksync-driver")?;
         PaymentDriverService::gsb(&db_executor).await?;
@@ -318,21 +347,31 @@ enum CliCommand {
     #[structopt(setting = clap::AppSettings::DeriveDisplayOrder)]
     Extension(ExtensionCommand),
 
+    /// Daemon configuration
+    #[structopt(setting = clap::AppSettings::DeriveDisplayOrder)]
+    Config(ConfigCommand),
+
+    /// Miscellaneous daemon controls
+    #[structopt(setting = clap::AppSettings::DeriveDisplayOrder)]
+    Misc(MiscCommand),
+
     #[structopt(external_subcommand)]
     #[structopt(setting = structopt::clap::AppSettings::Hidden)]
     Other(Vec<String>),
 }
 
 impl CliCommand {
-    pub async fn run_command(self, ctx: &CliCtx) -> Result<CommandOutput> {
+    pub async fn run_command(self, ctx: &CliCtx, log_format: LogFormat) -> Result<CommandOutput> {
         match self {
             CliCommand::Commands(command) => {
-                start_logger("warn", None, &[], false)?;
+                start_logger("warn", None, &[], false, log_format)?;
                 command.run_command(ctx).await
             }
             CliCommand::Complete(complete) => complete.run_command(ctx),
-            CliCommand::Service(service) => service.run_command(ctx).await,
+            CliCommand::Service(service) => service.run_command(ctx, log_format).await,
             CliCommand::Extension(ext) => ext.run_command(ctx).await,
+            CliCommand::Config(config) => config.run_command(ctx),
+            CliCommand::Misc(misc) => misc.run_command(ctx).await,
             CliCommand::Other(args) => extension::run::<CliArgs>(ctx, args).await,
         }
     }
@@ -414,6 +453,40 @@ impl ExtensionCommand {
     }
 }
 
+#[derive(StructOpt, Debug)]
+enum MiscCommand {
+    /// Change log levels of the running daemon without restarting it
+    SetLogLevel {
+        /// Filters in `<module>=<level>` or `<level>` format,
+        /// e.g. `erc20=debug market=trace`
+        #[structopt(required_unless = "reset")]
+        filters: Vec<String>,
+        /// Restore log levels the daemon was started with
+        #[structopt(long, conflicts_with = "filters")]
+        reset: bool,
+    },
+}
+
+impl MiscCommand {
+    async fn run_command(&self, _ctx: &CliCtx) -> Result<CommandOutput> {
+        match self {
+            Self::SetLogLevel { filters, reset } => {
+                // Validate locally for better error messages
+                for filter in filters {
+                    logging::parse_filter(filter)?;
+                }
+                let spec = gsb::service(model::BUS_ID)
+                    .call(model::SetLogLevel {
+                        filters: filters.clone(),
+                        reset: *reset,
+                    })
+                    .await?;
+                CommandOutput::object(spec)
+            }
+        }
+    }
+}
+
 #[allow(clippy::large_enum_variant)]
 #[derive(StructOpt, Debug)]
 enum ServiceCommand {
@@ -452,6 +525,11 @@ struct ServiceCommandOpts {
 
     #[structopt(flatten)]
     cors: CorsConfig,
+
+    /// Payment drivers sign, record and simulate transactions, but don't broadcast them.
+    /// Transactions and their payments are marked as simulated, payments aren't sent to providers.
+    #[structopt(long)]
+    payments_dry_run: bool,
 }
 
 #[cfg(unix)]
@@ -483,7 +561,7 @@ async fn sd_notify(_unset_environment: bool, _state: &str) -> std::io::Result<()
 }
 
 impl ServiceCommand {
-    async fn run_command(&self, ctx: &CliCtx) -> Result<CommandOutput> {
+    async fn run_command(&self, ctx: &CliCtx, log_format: LogFormat) -> Result<CommandOutput> {
         if !ctx.accept_terms {
             prompt_terms()?;
         }
@@ -495,6 +573,7 @@ impl ServiceCommand {
                 log_dir,
                 debug,
                 cors,
+                payments_dry_run,
             }) => {
                 // workaround to silence middleware logger by default
                 // to enable it explicitly set RUST_LOG=info or more verbose
@@ -529,6 +608,7 @@ impl ServiceCommand {
                         ("mio", log::LevelFilter::Off),
                     ],
                     force_debug,
+                    log_format,
                 )?;
 
                 let app_name = clap::crate_name!();
@@ -539,6 +619,12 @@ impl ServiceCommand {
                 );
                 log::info!("Data directory: {}", ctx.data_dir.display());
 
+                let config_path = config::path(&ctx.data_dir);
+                if config_path.exists() {
+                    log::info!("Configuration file: {}", config_path.display());
+                }
+                config::watch(config_path);
+
                 let _lock = ProcLock::new(app_name, &ctx.data_dir)?.lock(std::process::id())?;
 
                 ya_sb_router::bind_gsb_router(ctx.gsb_url.clone())
@@ -548,10 +634,18 @@ impl ServiceCommand {
                 let mut context: ServiceContext = ctx.clone().try_into()?;
                 context.set_metrics_ctx(metrics_opts);
                 Services::gsb(&context).await?;
+                logging::bind_gsb(logger_handle.clone());
 
                 ya_compile_time_utils::report_version_to_metrics();
 
-                let drivers = start_payment_drivers(&ctx.data_dir).await?;
+                if *payments_dry_run {
+                    env::set_var(ya_payment_driver::utils::DRY_RUN_ENV_VAR, "1");
+                }
+                if ya_payment_driver::utils::dry_run() {
+                    log::warn!("Payments dry-run mode, transactions won't be broadcast");
+                }
+                let mut recovery_report = RecoveryReport::default();
+                let drivers = start_payment_drivers(&ctx.data_dir, &mut recovery_report).await?;
                 payment_accounts::save_default_account(&ctx.data_dir, drivers)
                     .await
                     .unwrap_or_else(|e| {
@@ -561,6 +655,10 @@ impl ServiceCommand {
                     .await
                     .unwrap_or_else(|e| log::error!("Initializing payment accounts failed: {}", e));
 
+                recovery::run(&context, &mut recovery_report).await;
+                recovery::log(&recovery_report);
+                let recovery_report = web::Data::new(recovery_report);
+
                 let api_host_port = rest_api_host_port(api_url.clone());
                 let rest_address = api_host_port.clone();
                 let cors = AppKeyCors::new(cors).await?;
@@ -574,7 +672,15 @@ impl ServiceCommand {
                         .wrap(middleware::Logger::default())
                         .wrap(auth::Auth::new(cors.cache()))
                         .wrap(cors.cors())
+                        .wrap_fn(|req, srv| {
+                            let context = logging::request_context(&req);
+                            srv.call(req).log_context(context)
+                        })
+                        .app_data(recovery_report.clone())
                         .route("/me", web::get().to(me))
+                        .service(logging::get_log_level)
+                        .service(logging::set_log_level)
+                        .service(recovery::get_report)
                         .service(forward_gsb);
                     let rest = Services::rest(app, &context);
                     log::info!("Http server thread started on: {}", rest_address);
@@ -584,12 +690,23 @@ impl ServiceCommand {
                 .keep_alive(std::time::Duration::from_secs(*max_rest_timeout))
                 .bind(api_host_port.clone())
                 .context(format!("Failed to bind http server on {:?}", api_host_port))?
+                // Signals are handled below, to stop remaining services in order.
+                .disable_signals()
                 .run();
 
                 let _ = extension::autostart(&ctx.data_dir, api_url, &ctx.gsb_url)
                     .await
                     .map_err(|e| log::warn!("Failed to autostart extensions: {e}"));
 
+                {
+                    let server_handle = server.handle();
+                    tokio::task::spawn_local(async move {
+                        let signal = shutdown::signal().await;
+                        log::info!("{} received, shutting down...", signal);
+                        server_handle.stop(true).await;
+                    });
+                }
+
                 {
                     let server_handle = server.handle();
                     gsb::bind(model::BUS_ID, move |request: model::ShutdownRequest| {
@@ -617,14 +734,10 @@ impl ServiceCommand {
 
                 future::try_join(server, sd_notify(false, "READY=1")).await?;
 
-                log::info!("{} service successfully finished!", app_name);
-
-                PaymentService::shut_down().await;
-                NetService::shutdown()
-                    .await
-                    .map_err(|e| log::error!("Error shutting down NET: {}", e))
-                    .ok();
+                let _ = sd_notify(false, "STOPPING=1").await;
+                shutdown::drain().await;
 
+                log::info!("{} service successfully finished!", app_name);
                 logger_handle.shutdown();
                 Ok(CommandOutput::NoOutput)
             }
@@ -707,6 +820,12 @@ async fn main() -> Result<()> {
     #[cfg(feature = "static-openssl")]
     openssl_probe::init_ssl_cert_env_vars();
     let args = CliArgs::from_args();
+    // Values from the configuration file are exported to environment,
+    // so options need to be parsed again to take them into account.
+    let args = match config::load(&args.get_data_dir()?)? {
+        true => CliArgs::from_args(),
+        false => args,
+    };
 
     std::env::set_var(GSB_URL_ENV_VAR, args.gsb_url.as_str()); // FIXME
 
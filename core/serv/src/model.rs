@@ -15,3 +15,29 @@ impl RpcMessage for ShutdownRequest {
     type Item = ();
     type Error = String;
 }
+
+/// Changes log levels of the running daemon.
+#[derive(Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct SetLogLevel {
+    /// Filters in `RUST_LOG` format, e.g. `erc20=debug` or `info`.
+    pub filters: Vec<String>,
+    /// Restore log levels the daemon was started with.
+    pub reset: bool,
+}
+
+impl RpcMessage for SetLogLevel {
+    const ID: &'static str = "SetLogLevel";
+    /// Resulting log specification.
+    type Item = String;
+    type Error = String;
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct GetLogLevel {}
+
+impl RpcMessage for GetLogLevel {
+    const ID: &'static str = "GetLogLevel";
+    type Item = String;
+    type Error = String;
+}
@@ -0,0 +1,65 @@
+//! Startup recovery pass.
+//!
+//! Modules report work left unfinished by the previous run: what was resumed,
+//! what was rolled back and what needs operator's attention. The report is logged
+//! and served under `/_control/recovery` until the next restart.
+
+use actix_web::{web, Responder};
+
+use ya_file_logging::LogContext;
+use ya_service_api::recovery::{RecoveryAction, RecoveryReport};
+use ya_service_api_web::middleware::Identity;
+
+use crate::{ActivityService, MarketService, PaymentService, ServiceContext};
+
+/// Runs recovery of core modules. Has to be called after their migrations are applied.
+pub async fn run(context: &ServiceContext, report: &mut RecoveryReport) {
+    report.add("activity", ActivityService::recover(context).await);
+    report.add("market", MarketService::recover(context).await);
+    report.add("payment", PaymentService::recover(context).await);
+}
+
+pub fn log(report: &RecoveryReport) {
+    for item in &report.items {
+        let context = LogContext::new("recovery")
+            .field("module", item.module.as_str())
+            .field("subject", item.subject.as_str())
+            .field("state", item.state.as_str())
+            .field("action", item.action.to_string());
+        let details = item.details.as_deref().unwrap_or_default();
+        context.scope(|| match item.action {
+            RecoveryAction::NeedsAttention => log::warn!(
+                "Recovery: {} {} ({}) needs attention. {}",
+                item.module,
+                item.subject,
+                item.state,
+                details
+            ),
+            action => log::info!(
+                "Recovery: {} {} ({}) {}. {}",
+                item.module,
+                item.subject,
+                item.state,
+                action,
+                details
+            ),
+        });
+    }
+    for failure in &report.failures {
+        log::error!("Recovery of {} failed: {}", failure.module, failure.error);
+    }
+    if !report.is_empty() {
+        log::info!(
+            "Recovery finished: {} resumed, {} rolled back, {} need attention, {} modules failed.",
+            report.count(RecoveryAction::Resumed),
+            report.count(RecoveryAction::RolledBack),
+            report.count(RecoveryAction::NeedsAttention),
+            report.failures.len()
+        );
+    }
+}
+
+#[actix_web::get("/_control/recovery")]
+pub async fn get_report(_id: Identity, report: web::Data<RecoveryReport>) -> impl Responder {
+    web::Json(report.get_ref().clone())
+}
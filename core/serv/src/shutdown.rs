@@ -0,0 +1,76 @@
+//! Ordered daemon shutdown.
+//!
+//! REST API is stopped first, by the caller. Afterwards subsystems are stopped
+//! from the outside in, so each of them can still use the ones stopped later:
+//! payment drivers notify peers over the network about sent payments.
+//! Another signal received meanwhile skips the remaining stages.
+
+use futures::prelude::*;
+use std::future::Future;
+
+use ya_market::MarketService;
+use ya_net::Net as NetService;
+use ya_payment::PaymentService;
+
+/// Waits for SIGTERM or SIGINT (Ctrl+C).
+pub async fn signal() -> &'static str {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        match signal(SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                tokio::select! {
+                    _ = sigterm.recv() => "SIGTERM",
+                    _ = tokio::signal::ctrl_c() => "SIGINT",
+                }
+            }
+            Err(e) => {
+                log::warn!("Unable to listen for SIGTERM: {}", e);
+                let _ = tokio::signal::ctrl_c().await;
+                "SIGINT"
+            }
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = tokio::signal::ctrl_c().await;
+        "Ctrl+C"
+    }
+}
+
+/// Stops subsystems after REST API stopped accepting requests.
+pub async fn drain() {
+    // Peers can't start anything new, but we can still reach them.
+    NetService::stop_accepting_requests();
+
+    let stages = vec![
+        ("payment", PaymentService::shut_down().boxed_local()),
+        ("market", MarketService::shut_down().boxed_local()),
+        ("net", net_shutdown().boxed_local()),
+    ];
+    for (name, stage) in stages {
+        if !run_stage(name, stage).await {
+            log::warn!("Shutdown interrupted, skipping remaining stages");
+            return;
+        }
+    }
+}
+
+/// Returns false if interrupted by a signal.
+async fn run_stage(name: &str, stage: impl Future<Output = ()>) -> bool {
+    log::debug!("Shutdown stage: {}", name);
+    tokio::select! {
+        _ = stage => true,
+        signal = signal() => {
+            log::warn!("{} received while stopping {}", signal, name);
+            false
+        }
+    }
+}
+
+async fn net_shutdown() {
+    if let Err(e) = NetService::shutdown().await {
+        log::error!("Error shutting down NET: {}", e);
+    }
+}
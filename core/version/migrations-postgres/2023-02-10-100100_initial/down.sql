@@ -0,0 +1,3 @@
+DROP TRIGGER version_release_update_ts ON version_release;
+DROP FUNCTION version_release_update_ts();
+DROP TABLE version_release;
@@ -0,0 +1,26 @@
+-- Schema equivalent to SQLite migrations up to 2021-01-22-093722_initial.
+-- Shares schema with identity service, hence different version than its migration.
+
+CREATE TABLE version_release (
+    version TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    seen BOOLEAN NOT NULL DEFAULT FALSE,
+    release_ts TIMESTAMP NOT NULL,
+    insertion_ts TIMESTAMP NOT NULL DEFAULT timezone('utc', now()),
+    update_ts TIMESTAMP NOT NULL DEFAULT timezone('utc', now())
+);
+
+CREATE FUNCTION version_release_update_ts() RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.update_ts <= OLD.update_ts THEN
+        NEW.update_ts = timezone('utc', now());
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER version_release_update_ts
+    BEFORE UPDATE
+    ON version_release
+    FOR EACH ROW
+    EXECUTE PROCEDURE version_release_update_ts();
@@ -0,0 +1,178 @@
+//! Measures VPN throughput with many concurrent TCP connections.
+//!
+//! Each connection sends `size` bytes to an echo service listening on `host:port`
+//! and waits until all of them are echoed back.
+use actix_web_actors::ws;
+use actix_web_actors::ws::Frame;
+use bytes::Bytes;
+use futures::{SinkExt, StreamExt};
+use std::time::{Duration, Instant};
+use structopt::StructOpt;
+use url::Url;
+use ya_client::net::NetVpnApi;
+use ya_client::web::WebClient;
+use ya_client_model::net::{Address, NewNetwork, Node};
+
+const CHUNK_SIZE: usize = 65535;
+
+#[derive(StructOpt, Clone, Debug)]
+struct Cli {
+    #[structopt(long)]
+    api_url: Option<String>,
+    #[structopt(long)]
+    app_key: Option<String>,
+    /// Re-use existing network instead of creating a new one
+    #[structopt(long)]
+    net_id: Option<String>,
+    /// Number of concurrent connections
+    #[structopt(short, long, default_value = "32")]
+    connections: usize,
+    /// Bytes sent over each connection
+    #[structopt(short, long, default_value = "1048576")]
+    size: usize,
+    id: String,
+    host: String,
+    port: u16,
+}
+
+async fn echo(
+    api: NetVpnApi,
+    net_id: String,
+    host: String,
+    port: u16,
+    size: usize,
+) -> anyhow::Result<Duration> {
+    let connection = api.connect_tcp(&net_id, &host, port).await?;
+    let (mut sink, mut stream) = connection.split();
+    let started = Instant::now();
+
+    tokio::task::spawn_local(async move {
+        let mut remaining = size;
+        while remaining > 0 {
+            let count = remaining.min(CHUNK_SIZE);
+            remaining -= count;
+            if let Err(e) = sink
+                .send(ws::Message::Binary(Bytes::from(vec![0u8; count])))
+                .await
+            {
+                eprintln!("Error sending data: {}", e);
+                break;
+            }
+        }
+    });
+
+    let mut received = 0;
+    while received < size {
+        let frame = match stream.next().await {
+            Some(frame) => frame.map_err(|e| anyhow::anyhow!("Protocol error: {}", e))?,
+            None => anyhow::bail!("Connection closed after {} B", received),
+        };
+        match frame {
+            Frame::Text(bytes) | Frame::Binary(bytes) => received += bytes.len(),
+            Frame::Close(reason) => anyhow::bail!("WebSocket connection closed: {:?}", reason),
+            Frame::Continuation(_) | Frame::Ping(_) | Frame::Pong(_) => continue,
+        }
+    }
+
+    Ok(started.elapsed())
+}
+
+#[actix_rt::main]
+async fn main() -> anyhow::Result<()> {
+    let cli = Cli::from_args();
+
+    let api_url = match &cli.api_url {
+        Some(_) => cli.api_url,
+        None => std::env::var("YAGNA_API_URL").ok(),
+    }
+    .unwrap_or_else(|| "http://127.0.0.1:7464".to_string());
+    let app_key = match &cli.app_key {
+        Some(app_key) => Some(app_key.clone()),
+        None => std::env::var("YAGNA_APPKEY").ok(),
+    }
+    .ok_or_else(|| anyhow::anyhow!("Missing application key"))?;
+
+    let client = WebClient::builder()
+        .api_url(Url::parse(&api_url)?)
+        .auth_token(&app_key)
+        .build();
+    let api: NetVpnApi = client.interface()?;
+
+    let net_id = match cli.net_id {
+        Some(net_id) => net_id,
+        None => {
+            let network = api
+                .create_network(&NewNetwork {
+                    ip: "10.0.0.0".to_string(),
+                    mask: None,
+                    gateway: None,
+                })
+                .await?;
+            api.add_address(
+                &network.id,
+                &Address {
+                    ip: "10.0.0.1".to_string(),
+                },
+            )
+            .await?;
+            api.add_node(
+                &network.id,
+                &Node {
+                    id: cli.id.clone(),
+                    ip: cli.host.clone(),
+                },
+            )
+            .await?;
+            network.id
+        }
+    };
+
+    println!(
+        "Echoing {} B over {} connections to {}:{}",
+        cli.size, cli.connections, cli.host, cli.port
+    );
+
+    let started = Instant::now();
+    let results = futures::future::join_all((0..cli.connections).map(|_| {
+        echo(
+            api.clone(),
+            net_id.clone(),
+            cli.host.clone(),
+            cli.port,
+            cli.size,
+        )
+    }))
+    .await;
+    let elapsed = started.elapsed();
+
+    let mut durations = Vec::new();
+    for result in results {
+        match result {
+            Ok(duration) => durations.push(duration),
+            Err(e) => eprintln!("Connection failed: {}", e),
+        }
+    }
+    durations.sort();
+
+    let total = (durations.len() * cli.size * 2) as f64;
+    println!(
+        "Completed connections: {}/{}",
+        durations.len(),
+        cli.connections
+    );
+    println!("Total time: {:?}", elapsed);
+    println!(
+        "Throughput (tx + rx): {:.2} MiB/s",
+        total / elapsed.as_secs_f64() / (1024. * 1024.)
+    );
+    if let (Some(min), Some(max)) = (durations.first(), durations.last()) {
+        println!(
+            "Connection time: min {:?}, median {:?}, max {:?}",
+            min,
+            durations[durations.len() / 2],
+            max
+        );
+    }
+
+    Ok(())
+}
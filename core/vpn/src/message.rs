@@ -2,6 +2,7 @@ use crate::Result;
 use actix::{Message, Recipient};
 use futures::channel::mpsc;
 use ya_client_model::net::*;
+use ya_core_model::activity::VpnRule;
 use ya_utils_networking::vpn::{
     stack::{
         connection::{Connection, ConnectionMeta},
@@ -41,6 +42,16 @@ pub struct RemoveNode {
 #[rtype(result = "Result<Vec<Connection>>")]
 pub struct GetConnections;
 
+#[derive(Debug, Message)]
+#[rtype(result = "Result<Vec<VpnRule>>")]
+pub struct GetRules;
+
+#[derive(Debug, Message)]
+#[rtype(result = "Result<()>")]
+pub struct SetRules {
+    pub rules: Vec<VpnRule>,
+}
+
 #[derive(Message)]
 #[rtype(result = "Result<UserConnection>")]
 pub struct Connect {
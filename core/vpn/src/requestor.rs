@@ -13,6 +13,7 @@ use std::sync::Arc;
 use std::time::{Duration, Instant};
 use ya_client_model::net::*;
 use ya_client_model::ErrorMessage;
+use ya_core_model::activity::VpnRule;
 use ya_service_api_web::middleware::Identity;
 use ya_utils_networking::vpn::stack::connection::ConnectionMeta;
 use ya_utils_networking::vpn::{Error as VpnError, Protocol};
@@ -50,6 +51,8 @@ fn vpn_web_scope(path: &str) -> actix_web::Scope {
         .service(get_nodes)
         .service(add_node)
         .service(remove_node)
+        .service(get_rules)
+        .service(set_rules)
         .service(connect_tcp)
 }
 
@@ -199,6 +202,41 @@ async fn remove_node(
     Ok::<_, ApiError>(web::Json(fut.await?))
 }
 
+/// Retrieves firewall rules of a virtual private network.
+#[actix_web::get("/net/{net_id}/rules")]
+async fn get_rules(
+    vpn_sup: web::Data<Arc<Mutex<VpnSupervisor>>>,
+    path: web::Path<PathNetwork>,
+    identity: Identity,
+) -> impl Responder {
+    let path = path.into_inner();
+    let vpn = {
+        let supervisor = vpn_sup.lock().await;
+        supervisor.get_network(&identity.identity, &path.net_id)?
+    };
+    let response = vpn.send(GetRules {}).await??;
+    Ok::<_, ApiError>(web::Json(response))
+}
+
+/// Replaces firewall rules of a virtual private network and propagates them to its nodes.
+/// Empty rules allow all traffic within the network.
+#[actix_web::put("/net/{net_id}/rules")]
+async fn set_rules(
+    vpn_sup: web::Data<Arc<Mutex<VpnSupervisor>>>,
+    path: web::Path<PathNetwork>,
+    model: web::Json<Vec<VpnRule>>,
+    identity: Identity,
+) -> impl Responder {
+    let path = path.into_inner();
+    let vpn = {
+        let supervisor = vpn_sup.lock().await;
+        supervisor.get_network(&identity.identity, &path.net_id)?
+    };
+    let rules = model.into_inner();
+    let response = vpn.send(SetRules { rules }).await??;
+    Ok::<_, ApiError>(web::Json(response))
+}
+
 /// Initiates a new TCP connection via WebSockets to the destination address.
 #[actix_web::get("/net/{net_id}/tcp/{ip}/{port}")]
 async fn connect_tcp(
@@ -307,8 +345,9 @@ impl StreamHandler<WsResult<ws::Message>> for VpnWebSocket {
     fn handle(&mut self, msg: WsResult<ws::Message>, ctx: &mut Self::Context) {
         self.heartbeat = Instant::now();
         match msg {
-            Ok(ws::Message::Text(text)) => self.forward(text.into_bytes().to_vec(), ctx),
-            Ok(ws::Message::Binary(bytes)) => self.forward(bytes.to_vec(), ctx),
+            // Converting uniquely owned `Bytes` into `Vec` reuses the buffer
+            Ok(ws::Message::Text(text)) => self.forward(Vec::from(text.into_bytes()), ctx),
+            Ok(ws::Message::Binary(bytes)) => self.forward(Vec::from(bytes), ctx),
             Ok(ws::Message::Ping(msg)) => {
                 ctx.pong(&msg);
             }
@@ -163,6 +163,7 @@ async fn exec_and_wait(args: &Cli) -> anyhow::Result<()> {
         batch_id: BATCH_ID.to_string(),
         exe_script: exe_script.clone(),
         timeout: None,
+        pipeline: None,
     };
 
     let _ = exe_unit_service.send(exec.clone()).await?;
@@ -244,6 +245,7 @@ async fn exec_and_wait(args: &Cli) -> anyhow::Result<()> {
             batch_id,
             exe_script: exe_script.clone(),
             timeout: None,
+            pipeline: None,
         };
 
         let _ = exe_unit_service.send(exec.clone()).await?;
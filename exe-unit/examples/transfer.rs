@@ -215,6 +215,7 @@ async fn main() -> anyhow::Result<()> {
         supervise: Default::default(),
         activity_id: None,
         acl: Default::default(),
+        inet_usage: Default::default(),
         report_url: None,
         credentials: None,
         agreement,
@@ -2,7 +2,7 @@ use actix::{Actor, Addr};
 use anyhow::{bail, Context};
 use futures::channel::oneshot;
 use std::convert::TryFrom;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use structopt::{clap, StructOpt};
 
 use ya_client_model::activity::ExeScriptCommand;
@@ -12,8 +12,9 @@ use ya_core_model::activity;
 use ya_exe_unit::agreement::Agreement;
 use ya_exe_unit::logger::*;
 use ya_exe_unit::manifest::ManifestContext;
-use ya_exe_unit::message::{GetState, GetStateResponse, Register};
+use ya_exe_unit::message::{GetState, GetStateResponse, Register, Shutdown, ShutdownReason};
 use ya_exe_unit::runtime::process::RuntimeProcess;
+use ya_exe_unit::runtime::sandbox::SecurityProfile;
 use ya_exe_unit::service::metrics::MetricsService;
 use ya_exe_unit::service::signal::SignalMonitor;
 use ya_exe_unit::service::transfer::TransferService;
@@ -92,6 +93,9 @@ enum Command {
         service_id: Option<String>,
         /// Command file path
         input: PathBuf,
+        /// Save batch results to a file and exit when the batch is finished
+        #[structopt(long)]
+        results: Option<PathBuf>,
         #[structopt(flatten)]
         args: RunArgs,
     },
@@ -119,6 +123,58 @@ struct RunArgs {
     /// Common cache directory
     #[structopt(long, short)]
     cache_dir: PathBuf,
+    /// Security profiles file, required when the Agreement selects a profile
+    #[structopt(long)]
+    security_profiles: Option<PathBuf>,
+    /// Delegated cgroup v2 directory to place the Activity cgroup in (Linux only)
+    #[structopt(long)]
+    cgroup: Option<PathBuf>,
+    /// Disk bandwidth limit in bytes per second, enforced in the Activity cgroup
+    #[structopt(long, requires = "cgroup")]
+    io_max_bps: Option<u64>,
+}
+
+/// Places runtime processes in a cgroup, which enforces negotiated limits
+/// unless hardware is managed by the runtime.
+#[cfg(target_os = "linux")]
+fn init_cgroup(
+    args: &RunArgs,
+    name: &str,
+    agreement: &Agreement,
+    work_dir: &Path,
+    enforce: bool,
+) -> anyhow::Result<()> {
+    use ya_exe_unit::process::cgroup::{self, Cgroup, CgroupLimits};
+
+    let root = match &args.cgroup {
+        Some(root) => root,
+        None => return Ok(()),
+    };
+    let cgroup = Cgroup::create(root, name)?;
+    let mut limits = match enforce {
+        true => CgroupLimits::from_infrastructure(&agreement.infrastructure),
+        false => CgroupLimits::default(),
+    };
+    limits.io_bps = args.io_max_bps.map(|bps| (work_dir.to_path_buf(), bps));
+    cgroup.limit(&limits)?;
+
+    log::info!("Activity cgroup limits: {:?}", limits);
+    cgroup::set_current(cgroup);
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn init_cgroup(
+    args: &RunArgs,
+    _name: &str,
+    _agreement: &Agreement,
+    _work_dir: &Path,
+    _enforce: bool,
+) -> anyhow::Result<()> {
+    if args.cgroup.is_some() {
+        bail!("cgroups are supported on Linux only");
+    }
+    Ok(())
 }
 
 fn create_path(path: &PathBuf) -> anyhow::Result<PathBuf> {
@@ -152,6 +208,7 @@ async fn send_script(
     exe_unit: Addr<ExeUnit<RuntimeProcess>>,
     activity_id: Option<String>,
     exe_script: Vec<ExeScriptCommand>,
+    results: Option<PathBuf>,
 ) {
     use std::time::Duration;
     use ya_exe_unit::state::{State, StatePair};
@@ -171,20 +228,63 @@ async fn send_script(
 
     log::debug!("Executing commands: {:?}", exe_script);
 
+    let activity_id = activity_id.unwrap_or_default();
+    let batch_id = hex::encode(rand::random::<[u8; 16]>());
+    let started_at = chrono::Utc::now();
     let msg = activity::Exec {
-        activity_id: activity_id.unwrap_or_default(),
-        batch_id: hex::encode(rand::random::<[u8; 16]>()),
+        activity_id: activity_id.clone(),
+        batch_id: batch_id.clone(),
         exe_script,
         timeout: None,
+        pipeline: None,
     };
     if let Err(e) = exe_unit
         .send(RpcEnvelope::with_caller(String::new(), msg))
         .await
     {
-        log::error!("Unable to execute exe script: {:?}", e);
+        return log::error!("Unable to execute exe script: {:?}", e);
+    }
+
+    if let Some(path) = results {
+        if let Err(e) = save_results(&exe_unit, activity_id, batch_id, started_at, &path).await {
+            log::error!("Unable to save results to {}: {}", path.display(), e);
+        }
+        let _ = exe_unit.send(Shutdown(ShutdownReason::Finished)).await;
     }
 }
 
+/// Waits for the batch to finish and writes its results, along with the time
+/// the batch was sent, to `path`.
+async fn save_results(
+    exe_unit: &Addr<ExeUnit<RuntimeProcess>>,
+    activity_id: String,
+    batch_id: String,
+    started_at: chrono::DateTime<chrono::Utc>,
+    path: &Path,
+) -> anyhow::Result<()> {
+    let results = loop {
+        let msg = activity::GetExecBatchResults {
+            activity_id: activity_id.clone(),
+            batch_id: batch_id.clone(),
+            timeout: Some(5.),
+            command_index: None,
+        };
+        let results = exe_unit
+            .send(RpcEnvelope::with_caller(String::new(), msg))
+            .await??;
+        if results.last().map(|r| r.is_batch_finished).unwrap_or(false) {
+            break results;
+        }
+    };
+
+    let contents = serde_json::json!({
+        "startedAt": started_at,
+        "results": results,
+    });
+    std::fs::write(path, serde_json::to_string_pretty(&contents)?)?;
+    Ok(())
+}
+
 #[cfg(feature = "packet-trace-enable")]
 fn init_packet_trace() -> anyhow::Result<()> {
     use ya_packet_trace::{set_write_target, WriteTarget};
@@ -208,6 +308,7 @@ async fn run() -> anyhow::Result<()> {
     }
 
     let mut commands = None;
+    let mut results_file = None;
     let ctx_activity_id;
     let ctx_report_url;
 
@@ -217,6 +318,7 @@ async fn run() -> anyhow::Result<()> {
             service_id,
             report_url,
             input,
+            results,
         } => {
             let contents = std::fs::read_to_string(input).map_err(|e| {
                 anyhow::anyhow!("Cannot read commands from file {}: {e}", input.display())
@@ -230,6 +332,7 @@ async fn run() -> anyhow::Result<()> {
             ctx_activity_id = service_id.clone();
             ctx_report_url = report_url.clone();
             commands = Some(contents);
+            results_file = results.clone();
             args
         }
         Command::ServiceBus {
@@ -285,11 +388,29 @@ async fn run() -> anyhow::Result<()> {
     log::info!("Manifest-enabled features: {:?}", manifest_ctx.features());
     log::info!("User-provided payload: {:?}", agreement.task_package);
 
+    let security =
+        SecurityProfile::from_agreement(&agreement.inner, args.security_profiles.as_deref())
+            .map_err(|e| anyhow::anyhow!("Invalid security profile: {e}"))?;
+    log::info!("Security profile: {:?}", security);
+
+    let cgroup_name = ctx_activity_id
+        .clone()
+        .unwrap_or_else(|| format!("exe-unit-{}", std::process::id()));
+    init_cgroup(
+        args,
+        &cgroup_name,
+        &agreement,
+        &work_dir,
+        cli.supervise.hardware,
+    )
+    .context("Cannot create the Activity cgroup")?;
+
     let ctx = ExeUnitContext {
         supervise: Supervision {
             hardware: cli.supervise.hardware,
             image: cli.supervise.image,
             manifest: manifest_ctx,
+            security,
         },
         activity_id: ctx_activity_id.clone(),
         report_url: ctx_report_url,
@@ -298,6 +419,7 @@ async fn run() -> anyhow::Result<()> {
         cache_dir,
         runtime_args: cli.runtime_arg.clone(),
         acl: Default::default(),
+        inet_usage: Default::default(),
         credentials: None,
         #[cfg(feature = "sgx")]
         crypto: init_crypto(
@@ -319,10 +441,20 @@ async fn run() -> anyhow::Result<()> {
     exe_unit.send(Register(signals)).await?;
 
     if let Some(exe_script) = commands {
-        tokio::task::spawn(send_script(exe_unit, ctx_activity_id, exe_script));
+        tokio::task::spawn(send_script(
+            exe_unit,
+            ctx_activity_id,
+            exe_script,
+            results_file,
+        ));
     }
 
-    rx.await??;
+    let result = rx.await;
+    #[cfg(target_os = "linux")]
+    if let Some(cgroup) = ya_exe_unit::process::cgroup::current() {
+        cgroup.remove();
+    }
+    result??;
     Ok(())
 }
 
@@ -16,8 +16,11 @@ use crate::error::Error;
 use crate::manifest::{ManifestValidatorExt, ScriptValidator};
 use crate::message::{GetBatchResults, GetMetrics};
 use crate::runtime::Runtime;
+use crate::service::transfer::ReadContainerFile;
 use crate::{ExeUnit, RuntimeRef};
 
+const OUTPUT_FILE_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
 impl<R: Runtime> Handler<RpcEnvelope<Exec>> for ExeUnit<R> {
     type Result = <RpcEnvelope<Exec> as Message>::Result;
 
@@ -208,6 +211,79 @@ impl<R: Runtime> Handler<RpcStreamCall<StreamExecBatchResults>> for ExeUnit<R> {
     }
 }
 
+impl<R: Runtime> Handler<RpcEnvelope<ReadOutputFile>> for ExeUnit<R> {
+    type Result = ActorResponse<Self, Result<OutputFileChunk, RpcMessageError>>;
+
+    fn handle(&mut self, msg: RpcEnvelope<ReadOutputFile>, _: &mut Self::Context) -> Self::Result {
+        if let Err(e) = self.ctx.verify_activity_id(&msg.activity_id) {
+            return ActorResponse::reply(Err(e.into()));
+        }
+
+        let msg = msg.into_inner();
+        let transfers = self.transfers.clone();
+        let fut = async move {
+            let read = ReadContainerFile {
+                path: msg.path,
+                offset: msg.offset,
+                max_bytes: msg.max_bytes,
+            };
+            Ok(transfers.send(read).await.map_err(Error::from)??)
+        };
+
+        ActorResponse::r#async(fut.into_actor(self))
+    }
+}
+
+impl<R: Runtime> Handler<RpcStreamCall<StreamOutputFile>> for ExeUnit<R> {
+    type Result = ActorResponse<Self, Result<(), RpcError>>;
+
+    fn handle(
+        &mut self,
+        msg: RpcStreamCall<StreamOutputFile>,
+        _: &mut Self::Context,
+    ) -> Self::Result {
+        if let Err(e) = self.ctx.verify_activity_id(&msg.body.activity_id) {
+            return ActorResponse::reply(Err(RpcError::GsbBadRequest(e.to_string())));
+        }
+
+        let transfers = self.transfers.clone();
+        let path = msg.body.path;
+        let mut offset = msg.body.offset;
+        let mut reply = msg.reply;
+
+        // Polls the file until it's gone or the ExeUnit stops.
+        let fut = async move {
+            loop {
+                let read = ReadContainerFile {
+                    path: path.clone(),
+                    offset,
+                    max_bytes: None,
+                };
+                let chunk = match transfers.send(read).await {
+                    Ok(Ok(chunk)) => chunk,
+                    Ok(Err(e)) => {
+                        let _ = reply.send(Err(e.into())).await;
+                        return Ok(());
+                    }
+                    Err(e) => return Err(RpcError::GsbFailure(e.to_string())),
+                };
+
+                if chunk.data.is_empty() {
+                    tokio::time::sleep(OUTPUT_FILE_POLL_INTERVAL).await;
+                    continue;
+                }
+                offset += chunk.data.len() as u64;
+                reply
+                    .send(Ok(chunk))
+                    .await
+                    .map_err(|e| RpcError::GsbFailure(e.to_string()))?;
+            }
+        };
+
+        ActorResponse::r#async(fut.into_actor(self))
+    }
+}
+
 #[cfg(feature = "sgx")]
 impl<R: Runtime> Handler<RpcEnvelope<sgx::CallEncryptedService>> for ExeUnit<R> {
     type Result = ResponseFuture<Result<Vec<u8>, RpcMessageError>>;
@@ -239,6 +315,7 @@ impl<R: Runtime> Handler<RpcEnvelope<sgx::CallEncryptedService>> for ExeUnit<R>
                         batch_id,
                         timeout,
                         exe_script,
+                        pipeline: None,
                     };
                     Response::Exec(
                         me.send(RpcEnvelope::local(msg))
@@ -1,6 +1,7 @@
 #[macro_use]
 extern crate derive_more;
 
+use std::collections::HashSet;
 use std::path::PathBuf;
 use std::time::Duration;
 
@@ -13,8 +14,10 @@ use ya_agreement_utils::agreement::OfferTemplate;
 use ya_client_model::activity::{
     activity_state::StatePair, ActivityUsage, CommandOutput, ExeScriptCommand, State,
 };
-use ya_core_model::activity;
+use ya_client_model::NodeId;
 use ya_core_model::activity::local::Credentials;
+use ya_core_model::activity::FailurePolicy;
+use ya_core_model::{activity, identity};
 use ya_runtime_api::deploy;
 use ya_service_bus::{actix_rpc, RpcEndpoint, RpcMessage};
 
@@ -22,6 +25,7 @@ use crate::acl::Acl;
 use crate::agreement::Agreement;
 use crate::error::Error;
 use crate::message::*;
+use crate::metrics::InetUsage;
 use crate::runtime::*;
 use crate::service::metrics::MetricsService;
 use crate::service::transfer::{AddVolumes, DeployImage, TransferResource, TransferService};
@@ -107,6 +111,7 @@ impl<R: Runtime> ExeUnit<R> {
         let fut = report_usage(
             self.ctx.report_url.clone().unwrap(),
             self.ctx.activity_id.clone().unwrap(),
+            self.ctx.agreement.inner.provider_id().ok(),
             context.address(),
             self.metrics.clone(),
         );
@@ -151,12 +156,25 @@ impl<R: Runtime> RuntimeRef<R> {
         mut control: oneshot::Receiver<()>,
     ) {
         let batch_id = exec.batch_id.clone();
+        let pipeline = exec.pipeline.unwrap_or_default();
+        let mut failed = HashSet::new();
         for (idx, command) in exec.exe_script.into_iter().enumerate() {
             if let Ok(Some(_)) = control.try_recv() {
                 log::warn!("Batch {} execution aborted", batch_id);
                 break;
             }
 
+            if let Some(dep) = pipeline.blocked_by(idx, &failed) {
+                log::debug!("Batch {} command {} skipped: {} failed", batch_id, idx, dep);
+                failed.insert(idx);
+                let message = format!("Skipped, command {} failed", dep);
+                let evt = RuntimeEvent::finished(batch_id.clone(), idx, -1, Some(message));
+                if let Err(e) = events.send(evt).await {
+                    log::error!("Unable to report event: {:?}", e);
+                }
+                continue;
+            }
+
             let runtime_cmd = ExecuteCommand {
                 batch_id: batch_id.clone(),
                 command: command.clone(),
@@ -190,6 +208,11 @@ impl<R: Runtime> RuntimeRef<R> {
 
             if return_code != 0 {
                 let message = message.unwrap_or_else(|| "reason unspecified".into());
+                if pipeline.on_failure == FailurePolicy::Continue {
+                    log::warn!("Batch {} command {} failed: {}", batch_id, idx, message);
+                    failed.insert(idx);
+                    continue;
+                }
                 log::warn!("Batch {} execution interrupted: {}", batch_id, message);
                 break;
             }
@@ -368,6 +391,8 @@ impl<R: Runtime> Actor for ExeUnit<R> {
             let srv_id = activity::exeunit::bus_id(activity_id);
             actix_rpc::bind::<activity::GetState>(&srv_id, addr.clone().recipient());
             actix_rpc::bind::<activity::GetUsage>(&srv_id, addr.clone().recipient());
+            actix_rpc::bind::<activity::ReadOutputFile>(&srv_id, addr.clone().recipient());
+            actix_rpc::binds::<activity::StreamOutputFile>(&srv_id, addr.clone().recipient());
 
             #[cfg(feature = "sgx")]
             {
@@ -456,6 +481,7 @@ pub struct ExeUnitContext {
     pub cache_dir: PathBuf,
     pub runtime_args: Vec<String>,
     pub acl: Acl,
+    pub inet_usage: InetUsage,
     pub credentials: Option<Credentials>,
     #[cfg(feature = "sgx")]
     #[derivative(Debug = "ignore")]
@@ -512,22 +538,56 @@ where
     }
 }
 
+/// Signs the usage snapshot with the Provider identity, so that Requestors
+/// can check it against usage billed in Debit Notes.
+async fn sign_usage(
+    provider_id: NodeId,
+    activity_id: &str,
+    usage: &ActivityUsage,
+) -> Option<Vec<u8>> {
+    let msg = identity::Sign {
+        node_id: provider_id,
+        payload: activity::SignedUsage::hash(activity_id, usage),
+    };
+    match ya_service_bus::typed::service(identity::BUS_ID)
+        .send(msg)
+        .await
+    {
+        Ok(Ok(signature)) => Some(signature),
+        Ok(Err(e)) => {
+            log::debug!("Unable to sign activity usage: {}", e);
+            None
+        }
+        Err(e) => {
+            log::debug!("Unable to sign activity usage: {}", e);
+            None
+        }
+    }
+}
+
 async fn report_usage<R: Runtime>(
     report_url: String,
     activity_id: String,
+    provider_id: Option<NodeId>,
     exe_unit: Addr<ExeUnit<R>>,
     metrics: Addr<MetricsService>,
 ) {
     match metrics.send(GetMetrics).await {
         Ok(resp) => match resp {
             Ok(data) => {
+                let usage = ActivityUsage {
+                    current_usage: Some(data),
+                    timestamp: Utc::now().timestamp(),
+                };
+                let signature = match provider_id {
+                    Some(provider_id) => sign_usage(provider_id, &activity_id, &usage).await,
+                    None => None,
+                };
                 let msg = activity::local::SetUsage {
                     activity_id,
-                    usage: ActivityUsage {
-                        current_usage: Some(data),
-                        timestamp: Utc::now().timestamp(),
-                    },
+                    usage,
                     timeout: None,
+                    signature,
                 };
                 if !report(&report_url, msg).await {
                     exe_unit.do_send(Shutdown(ShutdownReason::Error(Error::RuntimeError(
@@ -14,7 +14,9 @@ use url::Url;
 
 use ya_agreement_utils::AgreementView;
 use ya_client_model::activity::ExeScriptCommand;
-use ya_manifest_utils::{read_manifest, AppManifest, ArgMatch, Command, Feature, Script};
+use ya_manifest_utils::{
+    read_manifest, AppManifest, ArgMatch, Command, Feature, InetOutLimits, OutboundPolicy, Script,
+};
 use ya_manifest_utils::{Policy, PolicyConfig};
 use ya_utils_networking::resolver::resolve_domain_name;
 use ya_utils_networking::vpn::Protocol;
@@ -33,6 +35,8 @@ pub enum ValidationError {
 pub struct ManifestContext {
     pub manifest: Arc<Option<AppManifest>>,
     pub policy: Arc<PolicyConfig>,
+    /// Outbound access allowed by the Provider preset.
+    pub outbound: Arc<OutboundPolicy>,
     features: HashSet<Feature>,
     validators: Arc<RwLock<ValidatorMap>>,
 }
@@ -41,6 +45,8 @@ impl ManifestContext {
     pub fn try_new(agreement: &AgreementView) -> anyhow::Result<Self> {
         let policy = PolicyConfig::from_args_safe().unwrap_or_default();
         let manifest = read_manifest(agreement).context("Unable to read manifest")?;
+        let outbound =
+            OutboundPolicy::from_agreement(agreement).context("Invalid outbound policy")?;
         let features = {
             let mut features = Self::build_default_features(agreement);
             if let Some(ref manifest) = manifest {
@@ -52,6 +58,7 @@ impl ManifestContext {
         Ok(Self {
             manifest: Arc::new(manifest),
             policy: Arc::new(policy),
+            outbound: Arc::new(outbound),
             features,
             validators: Arc::new(RwLock::new(Default::default())),
         })
@@ -67,6 +74,18 @@ impl ManifestContext {
             .and_then(|m| m.find_payload(std::env::consts::ARCH, std::env::consts::OS))
     }
 
+    /// Outbound traffic caps declared in the manifest, lowered by the Provider preset policy.
+    pub fn inet_limits(&self) -> Option<InetOutLimits> {
+        let requested = (*self.manifest)
+            .as_ref()
+            .and_then(|m| m.comp_manifest.as_ref())
+            .and_then(|c| c.net.as_ref())
+            .and_then(|net| net.inet.as_ref())
+            .and_then(|inet| inet.out.as_ref())
+            .and_then(|out| out.limits.clone());
+        self.outbound.limits(requested)
+    }
+
     pub fn build_validators<'a>(&self) -> LocalBoxFuture<'a, anyhow::Result<ValidatorMap>> {
         if self.manifest.is_none()
             || self
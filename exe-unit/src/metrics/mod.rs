@@ -1,8 +1,10 @@
+use std::collections::HashSet;
 use std::fmt::Debug;
+use std::net::SocketAddr;
 use std::ops::Not;
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, SystemTime};
 use std::{fs, thread};
 
@@ -209,3 +211,81 @@ impl Drop for StorageMetric {
         self.running.store(false, Ordering::Relaxed);
     }
 }
+
+/// Traffic exchanged with external hosts by the outbound network gateway.
+#[derive(Clone, Debug, Default)]
+pub struct InetUsage {
+    bytes: Arc<AtomicU64>,
+    destinations: Arc<Mutex<HashSet<SocketAddr>>>,
+}
+
+impl InetUsage {
+    /// Returns the total number of bytes after the update.
+    pub fn add_bytes(&self, bytes: u64) -> u64 {
+        self.bytes.fetch_add(bytes, Ordering::Relaxed) + bytes
+    }
+
+    pub fn bytes(&self) -> u64 {
+        self.bytes.load(Ordering::Relaxed)
+    }
+
+    /// Registers a destination unless it would exceed `limit` distinct destinations.
+    pub fn add_destination(&self, addr: SocketAddr, limit: Option<u32>) -> bool {
+        let mut destinations = self.destinations.lock().unwrap();
+        if destinations.contains(&addr) {
+            return true;
+        }
+        match limit {
+            Some(limit) if destinations.len() >= limit as usize => false,
+            _ => destinations.insert(addr),
+        }
+    }
+
+    pub fn destinations(&self) -> usize {
+        self.destinations.lock().unwrap().len()
+    }
+}
+
+pub struct InetMetric {
+    usage: InetUsage,
+}
+
+impl InetMetric {
+    pub const ID: &'static str = "golem.usage.inet_out_bytes";
+
+    pub fn new(usage: InetUsage) -> Self {
+        InetMetric { usage }
+    }
+}
+
+impl Metric for InetMetric {
+    fn frame(&mut self) -> Result<MetricData> {
+        Ok(self.usage.bytes() as MetricData)
+    }
+
+    fn peak(&mut self) -> Result<MetricData> {
+        self.frame()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inet_usage_destinations() {
+        let usage = InetUsage::default();
+        let addr = |s: &str| s.parse::<SocketAddr>().unwrap();
+
+        assert!(usage.add_destination(addr("1.1.1.1:53"), Some(2)));
+        assert!(usage.add_destination(addr("1.2.3.4:443"), Some(2)));
+        // Known destinations are always allowed
+        assert!(usage.add_destination(addr("1.1.1.1:53"), Some(2)));
+        assert!(!usage.add_destination(addr("1.2.3.4:80"), Some(2)));
+        assert_eq!(usage.destinations(), 2);
+
+        assert_eq!(usage.add_bytes(100), 100);
+        assert_eq!(usage.add_bytes(20), 120);
+        assert_eq!(InetMetric::new(usage).frame().unwrap(), 120.);
+    }
+}
@@ -11,6 +11,10 @@ lazy_static::lazy_static! {
 const MAX_UPDATE_RESOLUTION_MS: i64 = 100;
 
 pub fn cpu_time() -> Result<Duration> {
+    #[cfg(target_os = "linux")]
+    if let Some(cgroup) = crate::process::cgroup::current() {
+        return Ok(cgroup.cpu_time()?);
+    }
     let mut metrics = (*METRICS).write().map_err(SystemError::from)?;
     metrics.sample()?;
     Ok(metrics.cpu_total)
@@ -22,6 +26,10 @@ pub fn mem_rss() -> Result<f64> {
 }
 
 pub fn mem_peak_rss() -> Result<f64> {
+    #[cfg(target_os = "linux")]
+    if let Some(cgroup) = crate::process::cgroup::current() {
+        return Ok(cgroup.mem_peak_gib()?);
+    }
     let mut metrics = (*METRICS).write().map_err(SystemError::from)?;
     metrics.sample()?;
     Ok(metrics.mem_total)
@@ -26,6 +26,7 @@ use crate::error::Error;
 use crate::state::DeploymentNetwork;
 use crate::Result;
 
+pub(crate) mod firewall;
 pub(crate) mod inet;
 pub(crate) mod vpn;
 
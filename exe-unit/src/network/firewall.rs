@@ -0,0 +1,259 @@
+use std::convert::TryFrom;
+use std::net::IpAddr;
+use std::ops::RangeInclusive;
+
+use ipnet::IpNet;
+
+use ya_core_model::activity::{VpnDirection, VpnProtocol, VpnRule};
+use ya_utils_networking::vpn::common::{ntoh, to_net};
+use ya_utils_networking::vpn::{IpPacket, PeekPacket, Protocol, TcpPacket, UdpPacket};
+
+use crate::error::Error;
+
+/// Per-network packet filter configured by the Requestor.
+/// Without rules all traffic within the network is allowed.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Firewall {
+    rules: Vec<Rule>,
+}
+
+#[derive(Clone, Debug)]
+struct Rule {
+    direction: VpnDirection,
+    /// IP protocol numbers.
+    protocol: Option<&'static [u8]>,
+    ports: Option<RangeInclusive<u16>>,
+    peer: Option<IpNet>,
+}
+
+/// Properties of the packet used for filtering.
+#[derive(Clone, Debug)]
+pub(crate) struct PacketInfo {
+    pub protocol: u8,
+    pub src: IpAddr,
+    pub dst: IpAddr,
+    pub ports: Option<(u16, u16)>,
+}
+
+impl Firewall {
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// `direction` is `Ingress` for packets received from other nodes
+    /// and `Egress` for packets sent by the runtime.
+    pub fn allows(&self, direction: VpnDirection, packet: &IpPacket) -> bool {
+        if self.is_empty() {
+            return true;
+        }
+        match PacketInfo::try_from(packet) {
+            Ok(info) => self.allows_info(direction, &info),
+            Err(e) => {
+                log::trace!("[vpn] firewall: dropping malformed packet: {e}");
+                false
+            }
+        }
+    }
+
+    fn allows_info(&self, direction: VpnDirection, packet: &PacketInfo) -> bool {
+        self.is_empty()
+            || self
+                .rules
+                .iter()
+                .any(|rule| rule.matches(direction, packet))
+    }
+}
+
+impl TryFrom<Vec<VpnRule>> for Firewall {
+    type Error = Error;
+
+    fn try_from(rules: Vec<VpnRule>) -> Result<Self, Self::Error> {
+        let rules = rules
+            .into_iter()
+            .map(Rule::try_from)
+            .collect::<Result<_, _>>()?;
+        Ok(Firewall { rules })
+    }
+}
+
+impl TryFrom<VpnRule> for Rule {
+    type Error = Error;
+
+    fn try_from(rule: VpnRule) -> Result<Self, Self::Error> {
+        // Single IPv4 address is a /32 network
+        let peer = rule
+            .peer
+            .map(|peer| to_net(&peer, Some("255.255.255.255")))
+            .transpose()?;
+
+        Ok(Rule {
+            direction: rule.direction,
+            protocol: rule.protocol.map(|protocol| match protocol {
+                VpnProtocol::Tcp => &[6][..],
+                VpnProtocol::Udp => &[17][..],
+                VpnProtocol::Icmp => &[1, 58][..],
+            }),
+            ports: rule.ports.map(|ports| ports.start..=ports.end),
+            peer,
+        })
+    }
+}
+
+impl Rule {
+    /// `Ingress` rule ports refer to the local end of a connection
+    /// and `Egress` rule ports to the remote one, regardless of packet direction.
+    fn matches(&self, direction: VpnDirection, packet: &PacketInfo) -> bool {
+        let (remote_ip, local_port, remote_port) = match direction {
+            VpnDirection::Ingress => (
+                packet.src,
+                packet.ports.map(|p| p.1),
+                packet.ports.map(|p| p.0),
+            ),
+            VpnDirection::Egress => (
+                packet.dst,
+                packet.ports.map(|p| p.0),
+                packet.ports.map(|p| p.1),
+            ),
+        };
+
+        if let Some(protocol) = self.protocol {
+            if !protocol.contains(&packet.protocol) {
+                return false;
+            }
+        }
+        if let Some(peer) = &self.peer {
+            if !peer.contains(&remote_ip) {
+                return false;
+            }
+        }
+        if let Some(ports) = &self.ports {
+            let port = match self.direction {
+                VpnDirection::Ingress => local_port,
+                VpnDirection::Egress => remote_port,
+            };
+            return port.map(|port| ports.contains(&port)).unwrap_or(false);
+        }
+        true
+    }
+}
+
+impl<'a> TryFrom<&IpPacket<'a>> for PacketInfo {
+    type Error = Error;
+
+    fn try_from(packet: &IpPacket<'a>) -> Result<Self, Self::Error> {
+        let protocol = packet.protocol();
+        let ports = match Protocol::try_from(protocol) {
+            Ok(Protocol::Tcp) => {
+                TcpPacket::peek(packet.payload())?;
+                let pkt = TcpPacket::packet(packet.payload());
+                Some((pkt.src_port(), pkt.dst_port()))
+            }
+            Ok(Protocol::Udp) => {
+                UdpPacket::peek(packet.payload())?;
+                let pkt = UdpPacket::packet(packet.payload());
+                Some((pkt.src_port(), pkt.dst_port()))
+            }
+            _ => None,
+        };
+        let address = |data: &[u8]| {
+            ntoh(data).ok_or_else(|| Error::Other(format!("invalid IP address: {data:?}")))
+        };
+
+        Ok(PacketInfo {
+            protocol,
+            src: address(packet.src_address())?,
+            dst: address(packet.dst_address())?,
+            ports,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(direction: VpnDirection, ports: Option<(u16, u16)>, peer: Option<&str>) -> VpnRule {
+        VpnRule {
+            direction,
+            protocol: Some(VpnProtocol::Tcp),
+            ports: ports.map(|(start, end)| ya_core_model::activity::VpnPortRange { start, end }),
+            peer: peer.map(ToString::to_string),
+        }
+    }
+
+    fn tcp(src: &str, dst: &str, ports: (u16, u16)) -> PacketInfo {
+        PacketInfo {
+            protocol: 6,
+            src: src.parse().unwrap(),
+            dst: dst.parse().unwrap(),
+            ports: Some(ports),
+        }
+    }
+
+    #[test]
+    fn test_empty_firewall_allows_all() {
+        let firewall = Firewall::default();
+        let packet = tcp("10.0.0.2", "10.0.0.3", (40000, 22));
+        assert!(firewall.allows_info(VpnDirection::Ingress, &packet));
+        assert!(firewall.allows_info(VpnDirection::Egress, &packet));
+    }
+
+    #[test]
+    fn test_ingress_rule() {
+        let firewall = Firewall::try_from(vec![rule(
+            VpnDirection::Ingress,
+            Some((80, 80)),
+            Some("10.0.0.0/24"),
+        )])
+        .unwrap();
+
+        // Remote node connects to local port 80 and receives replies.
+        assert!(firewall.allows_info(
+            VpnDirection::Ingress,
+            &tcp("10.0.0.2", "10.0.0.3", (40000, 80))
+        ));
+        assert!(firewall.allows_info(
+            VpnDirection::Egress,
+            &tcp("10.0.0.3", "10.0.0.2", (80, 40000))
+        ));
+        // Other ports and peers outside of the range are blocked.
+        assert!(!firewall.allows_info(
+            VpnDirection::Ingress,
+            &tcp("10.0.0.2", "10.0.0.3", (40000, 22))
+        ));
+        assert!(!firewall.allows_info(
+            VpnDirection::Ingress,
+            &tcp("10.0.1.2", "10.0.0.3", (40000, 80))
+        ));
+        // Local node can't connect to remote port 80.
+        assert!(!firewall.allows_info(
+            VpnDirection::Egress,
+            &tcp("10.0.0.3", "10.0.0.2", (40000, 80))
+        ));
+    }
+
+    #[test]
+    fn test_egress_rule() {
+        let firewall =
+            Firewall::try_from(vec![rule(VpnDirection::Egress, Some((5000, 5010)), None)]).unwrap();
+
+        assert!(firewall.allows_info(
+            VpnDirection::Egress,
+            &tcp("10.0.0.3", "10.0.0.2", (40000, 5005))
+        ));
+        assert!(firewall.allows_info(
+            VpnDirection::Ingress,
+            &tcp("10.0.0.2", "10.0.0.3", (5005, 40000))
+        ));
+        assert!(!firewall.allows_info(
+            VpnDirection::Ingress,
+            &tcp("10.0.0.2", "10.0.0.3", (40000, 5005))
+        ));
+
+        let udp = PacketInfo {
+            protocol: 17,
+            ..tcp("10.0.0.3", "10.0.0.2", (40000, 5005))
+        };
+        assert!(!firewall.allows_info(VpnDirection::Egress, &udp));
+    }
+}
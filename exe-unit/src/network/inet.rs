@@ -30,6 +30,7 @@ use net::ya_smoltcp::wire::{IpAddress, IpCidr, IpEndpoint};
 use net::{EgressReceiver, IngressEvent, IngressReceiver};
 use net::{Error as NetError, Protocol};
 
+use ya_manifest_utils::InetOutLimits;
 use ya_runtime_api::deploy::ContainerEndpoint;
 use ya_runtime_api::server::{CreateNetwork, NetworkInterface, RuntimeService};
 use ya_std_utils::LogErr;
@@ -44,8 +45,9 @@ use ya_utils_networking::vpn::{
     EtherFrame, EtherType, IpPacket, PeekPacket, SocketEndpoint, TcpPacket, UdpPacket,
 };
 
-use crate::manifest::UrlValidator;
+use crate::manifest::{UrlValidator, ValidationError};
 use crate::message::Shutdown;
+use crate::metrics::InetUsage;
 use crate::network::Endpoint;
 use crate::{Error, Result};
 
@@ -74,6 +76,9 @@ pub(crate) async fn start_inet<R: RuntimeService>(
     mut endpoint: Endpoint,
     service: &R,
     filter: Option<UrlValidator>,
+    usage: InetUsage,
+    limits: Option<InetOutLimits>,
+    ports: Vec<u16>,
 ) -> Result<Addr<Inet>> {
     use ya_runtime_api::server::Network;
 
@@ -125,7 +130,7 @@ pub(crate) async fn start_inet<R: RuntimeService>(
         }
     };
 
-    Ok(Inet::new(endpoint, filter).start())
+    Ok(Inet::new(endpoint, filter, usage, limits.unwrap_or_default(), ports).start())
 }
 
 pub(crate) struct Inet {
@@ -135,9 +140,15 @@ pub(crate) struct Inet {
 }
 
 impl Inet {
-    pub fn new(endpoint: Endpoint, filter: Option<UrlValidator>) -> Self {
+    pub fn new(
+        endpoint: Endpoint,
+        filter: Option<UrlValidator>,
+        usage: InetUsage,
+        limits: InetOutLimits,
+        ports: Vec<u16>,
+    ) -> Self {
         let network = Self::create_network();
-        let proxy = Proxy::new(network.clone(), filter);
+        let proxy = Proxy::new(network.clone(), filter, usage, limits, ports);
         Self {
             network,
             endpoint,
@@ -221,7 +232,13 @@ impl Actor for Inet {
 
     fn stopping(&mut self, _ctx: &mut Self::Context) -> Running {
         self.network = Self::create_network();
-        self.proxy = Proxy::new(self.network.clone(), self.proxy.filter.clone());
+        self.proxy = Proxy::new(
+            self.network.clone(),
+            self.proxy.filter.clone(),
+            self.proxy.usage.clone(),
+            self.proxy.limits.clone(),
+            self.proxy.ports.to_vec(),
+        );
 
         log::info!("[inet] stopping service");
         Running::Stop
@@ -472,6 +489,10 @@ fn ip_packet_to_socket_desc(ip_packet: &IpPacket) -> Result<SocketDesc> {
 struct Proxy {
     state: Arc<RwLock<ProxyState>>,
     filter: Option<UrlValidator>,
+    usage: InetUsage,
+    limits: InetOutLimits,
+    /// Destination ports allowed by the Provider, any when empty.
+    ports: Arc<[u16]>,
 }
 
 struct ConnectionState {
@@ -486,7 +507,13 @@ struct ProxyState {
 }
 
 impl Proxy {
-    fn new(network: net::Network, filter: Option<UrlValidator>) -> Self {
+    fn new(
+        network: net::Network,
+        filter: Option<UrlValidator>,
+        usage: InetUsage,
+        limits: InetOutLimits,
+        ports: Vec<u16>,
+    ) -> Self {
         let state = ProxyState {
             network,
             remotes: Default::default(),
@@ -494,9 +521,57 @@ impl Proxy {
         Self {
             state: Arc::new(RwLock::new(state)),
             filter,
+            usage,
+            limits,
+            ports: ports.into(),
+        }
+    }
+
+    fn transfer_exceeded(&self) -> bool {
+        self.limits
+            .max_bytes
+            .map(|max| self.usage.bytes() > max)
+            .unwrap_or(false)
+    }
+
+    /// Accounts traffic to and from external hosts.
+    /// Returns `false` when the traffic cap has been exceeded.
+    fn account(&self, bytes: usize) -> bool {
+        let total = self.usage.add_bytes(bytes as u64);
+        match self.limits.max_bytes {
+            Some(max) if total > max => {
+                log::warn!("[inet] outbound traffic cap exceeded: {total} B > {max} B");
+                false
+            }
+            _ => true,
         }
     }
 
+    fn check_limits(&self, ip: IpAddr, port: u16) -> Result<()> {
+        if !self.ports.is_empty() && !self.ports.contains(&port) {
+            return Err(
+                ValidationError::Url(format!("port not allowed by provider: {ip}:{port}")).into(),
+            );
+        }
+        if self.transfer_exceeded() {
+            return Err(Error::UsageLimitExceeded(format!(
+                "outbound traffic cap of {} B exceeded",
+                self.limits.max_bytes.unwrap_or_default()
+            )));
+        }
+        let addr = SocketAddr::new(ip, port);
+        if !self
+            .usage
+            .add_destination(addr, self.limits.max_destinations)
+        {
+            return Err(Error::UsageLimitExceeded(format!(
+                "outbound destination limit of {} reached, refusing {addr}",
+                self.limits.max_destinations.unwrap_or_default()
+            )));
+        }
+        Ok(())
+    }
+
     async fn exists(&self, key: &TransportKey) -> bool {
         let state = self.state.read().await;
         state.remotes.contains_key(key)
@@ -617,6 +692,8 @@ impl Proxy {
                 .validate(meta.protocol, ip, port)
                 .map_err(|e| ProxyingError::routeable(conn, e.into()))?;
         }
+        self.check_limits(ip, port)
+            .map_err(|e| ProxyingError::routeable(conn, e))?;
 
         if meta.protocol == Protocol::Udp {
             self.close_lru_udp_connections(200).await;
@@ -643,14 +720,28 @@ impl Proxy {
 
             match maybe_tx_rx {
                 Ok((mut tcp_tx, mut tcp_rx)) => {
+                    let proxy3 = proxy2.clone();
+                    let network3 = network2.clone();
                     tokio::task::spawn_local(async move {
                         while let Some(data) = proxy_rx.next().await {
+                            if !proxy3.account(data.len()) {
+                                let handle = get_handle(&network3, &meta).unwrap_or(handle);
+                                let _ = proxy3.disconnect(handle).await;
+                                break;
+                            }
                             tcp_tx.send(data).await.log_err().unwrap();
                         }
                     });
 
+                    let proxy3 = proxy2.clone();
                     tokio::task::spawn_local(async move {
                         while let Some(data) = tcp_rx.next().await {
+                            // Dropping the sender closes the connection
+                            if let Ok(bytes) = &data {
+                                if !proxy3.account(bytes.len()) {
+                                    break;
+                                }
+                            }
                             proxy_tx
                                 .send(data.map(Into::<Bytes>::into))
                                 .await
@@ -1,10 +1,11 @@
+use std::collections::HashMap;
 use std::convert::TryFrom;
 
 use actix::prelude::*;
 use futures::{future, FutureExt};
 
 use ya_client_model::NodeId;
-use ya_core_model::activity::{self, RpcMessageError, VpnControl, VpnPacket};
+use ya_core_model::activity::{self, RpcMessageError, VpnControl, VpnDirection, VpnPacket};
 use ya_core_model::identity;
 use ya_runtime_api::deploy::ContainerEndpoint;
 use ya_runtime_api::server::{CreateNetwork, NetworkInterface, RuntimeService};
@@ -17,6 +18,7 @@ use ya_utils_networking::vpn::{ArpField, ArpPacket, EtherFrame, EtherType, IpPac
 use crate::acl::Acl;
 use crate::error::Error;
 use crate::message::Shutdown;
+use crate::network::firewall::Firewall;
 use crate::network::{self, Endpoint};
 use crate::state::Deployment;
 
@@ -33,7 +35,7 @@ pub(crate) async fn start_vpn<R: RuntimeService>(
     log::info!("Starting VPN service...");
 
     let node_id = typed::service(identity::BUS_ID)
-        .send(identity::Get::ByDefault)
+        .send(identity::Get::ByRole(identity::IdentityRole::NodeId))
         .await?
         .map_err(|e| Error::Other(format!("failed to retrieve default identity: {e}")))?
         .ok_or_else(|| Error::Other("no default identity set".to_string()))?
@@ -77,6 +79,7 @@ pub(crate) struct Vpn {
     #[allow(unused)]
     acl: Acl,
     networks: Networks<DuoEndpoint<GsbEndpoint>>,
+    firewalls: HashMap<String, Firewall>,
     endpoint: Endpoint,
 }
 
@@ -106,6 +109,7 @@ impl Vpn {
             default_id: node_id.to_string(),
             acl,
             networks,
+            firewalls: Default::default(),
             endpoint,
         })
     }
@@ -130,6 +134,12 @@ impl Vpn {
                 }
                 EtherType::Ip => {
                     let pkt = IpPacket::packet(payload);
+                    if let Some(firewall) = self.firewalls.get(&network_id) {
+                        if !firewall.allows(VpnDirection::Ingress, &pkt) {
+                            log::trace!("[vpn] ingress packet from {node_id} blocked by firewall");
+                            return Ok(());
+                        }
+                    }
                     ntoh(pkt.src_address())
                 }
                 _ => None,
@@ -155,11 +165,27 @@ impl Vpn {
     fn handle_ip(
         frame: EtherFrame,
         networks: &Networks<DuoEndpoint<GsbEndpoint>>,
+        firewalls: &HashMap<String, Firewall>,
         default_id: &str,
     ) {
         let ip_pkt = IpPacket::packet(frame.payload());
         log::trace!("[vpn] egress packet to {:?}", ip_pkt.dst_address());
 
+        let blocked = ntoh(ip_pkt.dst_address())
+            .and_then(|ip| {
+                networks
+                    .as_ref()
+                    .iter()
+                    .find(|(_, network)| network.as_ref().contains(&ip))
+            })
+            .and_then(|(id, _)| firewalls.get(id))
+            .map(|firewall| !firewall.allows(VpnDirection::Egress, &ip_pkt))
+            .unwrap_or(false);
+        if blocked {
+            log::trace!("[vpn] egress packet blocked by firewall");
+            return;
+        }
+
         if ip_pkt.is_broadcast() {
             let futs = networks
                 .endpoints()
@@ -283,7 +309,9 @@ impl StreamHandler<crate::Result<Vec<u8>>> for Vpn {
         match EtherFrame::try_from(packet) {
             Ok(frame) => match &frame {
                 EtherFrame::Arp(_) => Self::handle_arp(frame, &self.networks, &self.default_id),
-                EtherFrame::Ip(_) => Self::handle_ip(frame, &self.networks, &self.default_id),
+                EtherFrame::Ip(_) => {
+                    Self::handle_ip(frame, &self.networks, &self.firewalls, &self.default_id)
+                }
                 frame => log::debug!("[vpn] unimplemented EtherType: {}", frame),
             },
             Err(err) => {
@@ -363,6 +391,15 @@ impl Handler<RpcEnvelope<VpnControl>> for Vpn {
                 let network = self.networks.get_mut(&network_id).map_err(Error::from)?;
                 node_ids.into_iter().for_each(|id| network.remove_node(&id));
             }
+            VpnControl::SetRules { network_id, rules } => {
+                self.networks.get_mut(&network_id).map_err(Error::from)?;
+                log::info!(
+                    "[vpn] setting {} firewall rules for network {network_id}",
+                    rules.len()
+                );
+                self.firewalls
+                    .insert(network_id, Firewall::try_from(rules)?);
+            }
         }
         Ok(())
     }
@@ -0,0 +1,209 @@
+//! Activity cgroup (v2), enforcing negotiated limits of runtime processes
+//! and providing their usage counters.
+use nix::libc;
+use std::collections::HashMap;
+use std::ffi::CString;
+use std::fs;
+use std::io;
+use std::os::unix::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tokio::process::Command;
+
+use crate::process::SystemError;
+
+const CONTROLLERS: &[&str] = &["cpu", "memory", "io"];
+const CPU_PERIOD_USEC: u64 = 100_000;
+
+lazy_static::lazy_static! {
+    static ref CURRENT: RwLock<Option<Arc<Cgroup>>> = RwLock::new(None);
+}
+
+/// Cgroup of the Activity, when runtime processes are placed in one.
+pub fn current() -> Option<Arc<Cgroup>> {
+    CURRENT.read().ok().and_then(|current| current.clone())
+}
+
+pub fn set_current(cgroup: Cgroup) {
+    if let Ok(mut current) = CURRENT.write() {
+        current.replace(Arc::new(cgroup));
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct CgroupLimits {
+    pub cpu_threads: Option<f64>,
+    pub mem_bytes: Option<u64>,
+    /// Read and write bandwidth of the disk holding the working directory.
+    pub io_bps: Option<(PathBuf, u64)>,
+}
+
+impl CgroupLimits {
+    /// Limits of `golem.inf` properties from the Agreement.
+    pub fn from_infrastructure(infrastructure: &HashMap<String, f64>) -> Self {
+        CgroupLimits {
+            cpu_threads: infrastructure.get("cpu.threads").cloned(),
+            mem_bytes: infrastructure
+                .get("mem.gib")
+                .map(|gib| (gib * 1024. * 1024. * 1024.) as u64),
+            io_bps: None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Cgroup {
+    path: PathBuf,
+    procs: CString,
+}
+
+impl Cgroup {
+    /// Creates cgroup `name` in a delegated `root` cgroup, which can't have processes.
+    pub fn create(root: &Path, name: &str) -> Result<Self, SystemError> {
+        let available = fs::read_to_string(root.join("cgroup.controllers")).map_err(|e| {
+            SystemError::Error(format!(
+                "{} is not a cgroup v2 directory: {e}",
+                root.display()
+            ))
+        })?;
+        let enable = CONTROLLERS
+            .iter()
+            .filter(|controller| available.split_whitespace().any(|c| &c == *controller))
+            .map(|controller| format!("+{controller}"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        fs::write(root.join("cgroup.subtree_control"), enable).map_err(|e| {
+            SystemError::Error(format!(
+                "cannot enable controllers in {}: {e}",
+                root.display()
+            ))
+        })?;
+
+        let path = root.join(name);
+        match fs::create_dir(&path) {
+            Err(e) if e.kind() != io::ErrorKind::AlreadyExists => return Err(e.into()),
+            _ => (),
+        }
+        let procs = CString::new(path.join("cgroup.procs").as_os_str().as_bytes())
+            .map_err(|e| SystemError::Error(e.to_string()))?;
+        Ok(Cgroup { path, procs })
+    }
+
+    pub fn limit(&self, limits: &CgroupLimits) -> Result<(), SystemError> {
+        if let Some(threads) = limits.cpu_threads {
+            let quota = (threads * CPU_PERIOD_USEC as f64) as u64;
+            self.write("cpu.max", format!("{quota} {CPU_PERIOD_USEC}"))?;
+        }
+        if let Some(bytes) = limits.mem_bytes {
+            self.write("memory.max", bytes.to_string())?;
+        }
+        if let Some((path, bps)) = &limits.io_bps {
+            let device = block_device(path).map_err(|e| {
+                SystemError::Error(format!("cannot find disk of {}: {e}", path.display()))
+            })?;
+            self.write("io.max", format!("{device} rbps={bps} wbps={bps}"))?;
+        }
+        Ok(())
+    }
+
+    /// Moves the process spawned by `command` to the cgroup before it's executed.
+    pub fn add_command(&self, command: &mut Command) {
+        let procs = self.procs.clone();
+        unsafe {
+            command.pre_exec(move || {
+                // Writing 0 moves the writing process.
+                let fd = libc::open(procs.as_ptr(), libc::O_WRONLY | libc::O_CLOEXEC);
+                if fd == -1 {
+                    return Err(io::Error::last_os_error());
+                }
+                let written = libc::write(fd, b"0".as_ptr().cast(), 1);
+                libc::close(fd);
+                match written {
+                    -1 => Err(io::Error::last_os_error()),
+                    _ => Ok(()),
+                }
+            });
+        }
+    }
+
+    pub fn cpu_time(&self) -> Result<Duration, SystemError> {
+        let stat = self.read("cpu.stat")?;
+        stat.lines()
+            .find_map(|line| line.strip_prefix("usage_usec "))
+            .and_then(|usec| usec.trim().parse().ok())
+            .map(Duration::from_micros)
+            .ok_or_else(|| SystemError::Error("invalid cpu.stat".to_string()))
+    }
+
+    /// Peak memory usage, current usage on kernels without `memory.peak`.
+    pub fn mem_peak_gib(&self) -> Result<f64, SystemError> {
+        let bytes = self
+            .read("memory.peak")
+            .or_else(|_| self.read("memory.current"))?;
+        let bytes: u64 = bytes
+            .trim()
+            .parse()
+            .map_err(|_| SystemError::Error("invalid memory usage".to_string()))?;
+        Ok(bytes as f64 / (1024. * 1024. * 1024.))
+    }
+
+    /// Removes the cgroup, once runtime processes have exited.
+    pub fn remove(&self) {
+        if let Err(e) = fs::remove_dir(&self.path) {
+            log::debug!("Unable to remove cgroup {}: {e}", self.path.display());
+        }
+    }
+
+    fn read(&self, file: &str) -> Result<String, SystemError> {
+        Ok(fs::read_to_string(self.path.join(file))?)
+    }
+
+    fn write(&self, file: &str, value: String) -> Result<(), SystemError> {
+        fs::write(self.path.join(file), &value)
+            .map_err(|e| SystemError::Error(format!("cannot set {file} to '{value}': {e}")))
+    }
+}
+
+/// `major:minor` of the whole disk holding `path`, as partitions can't be limited.
+fn block_device(path: &Path) -> io::Result<String> {
+    let dev = nix::sys::stat::stat(path)?.st_dev;
+    let id = format!(
+        "{}:{}",
+        nix::sys::stat::major(dev),
+        nix::sys::stat::minor(dev)
+    );
+    let sys = Path::new("/sys/dev/block").join(&id);
+    if !sys.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("{id} is not a block device"),
+        ));
+    }
+    match sys.join("partition").exists() {
+        true => Ok(fs::read_to_string(sys.join("..").join("dev"))?
+            .trim()
+            .to_string()),
+        false => Ok(id),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limits_from_infrastructure() {
+        let infrastructure = vec![
+            ("cpu.threads".to_string(), 2.),
+            ("mem.gib".to_string(), 0.5),
+        ]
+        .into_iter()
+        .collect();
+        let limits = CgroupLimits::from_infrastructure(&infrastructure);
+
+        assert_eq!(limits.cpu_threads, Some(2.));
+        assert_eq!(limits.mem_bytes, Some(512 * 1024 * 1024));
+        assert!(limits.io_bps.is_none());
+    }
+}
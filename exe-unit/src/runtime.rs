@@ -4,6 +4,7 @@ use ya_runtime_api::deploy::StartMode;
 
 mod event;
 pub mod process;
+pub mod sandbox;
 
 pub trait Runtime:
     Actor<Context = Context<Self>>
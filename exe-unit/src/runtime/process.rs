@@ -22,6 +22,7 @@ use crate::manifest::{ManifestContext, UrlValidator};
 use crate::message::{
     CommandContext, ExecuteCommand, RuntimeEvent, Shutdown, ShutdownReason, UpdateDeployment,
 };
+use crate::metrics::InetUsage;
 use crate::network::inet::start_inet;
 use crate::network::inet::Inet;
 use crate::network::vpn::{start_vpn, Vpn};
@@ -29,6 +30,7 @@ use crate::network::Endpoint;
 use crate::output::{forward_output, vec_to_string};
 use crate::process::{kill, ProcessTree, SystemError};
 use crate::runtime::event::EventMonitor;
+use crate::runtime::sandbox::SecurityProfile;
 use crate::runtime::{Runtime, RuntimeMode};
 use crate::state::Deployment;
 use crate::ExeUnitContext;
@@ -180,6 +182,7 @@ impl RuntimeProcess {
 
         let binary = self.binary.clone();
         let work_dir = self.ctx.work_dir.clone();
+        let security = self.ctx.security.clone();
 
         log::info!(
             "Executing {:?} with {:?} from path {:?}",
@@ -189,13 +192,21 @@ impl RuntimeProcess {
         );
 
         async move {
-            let mut child = Command::new(binary)
+            let mut command = Command::new(binary);
+            command
                 .current_dir(&work_dir)
                 .args(rt_args)
                 .kill_on_drop(true)
                 .stdout(Stdio::piped())
-                .stderr(Stdio::piped())
-                .spawn()?;
+                .stderr(Stdio::piped());
+            #[cfg(target_os = "linux")]
+            if let Some(cgroup) = crate::process::cgroup::current() {
+                cgroup.add_command(&mut command);
+            }
+            if let Some(profile) = &security {
+                profile.apply(&mut command)?;
+            }
+            let mut child = command.spawn()?;
 
             let idx = ctx.idx;
             let id = ctx.batch_id.clone();
@@ -295,6 +306,13 @@ impl RuntimeProcess {
             let mut command = Command::new(&rt_binary);
             command.current_dir(&rt_ctx.work_dir);
             command.args(rt_args);
+            #[cfg(target_os = "linux")]
+            if let Some(cgroup) = crate::process::cgroup::current() {
+                cgroup.add_command(&mut command);
+            }
+            if let Some(profile) = &rt_ctx.security {
+                profile.apply(&mut command)?;
+            }
 
             let service = spawn(command, monitor.clone())
                 .map_err(Error::runtime)
@@ -316,6 +334,9 @@ impl RuntimeProcess {
                         endpoint,
                         &service_,
                         rt_ctx.manifest.validator::<UrlValidator>(),
+                        rt_ctx.inet_usage.clone(),
+                        rt_ctx.manifest.inet_limits(),
+                        rt_ctx.manifest.outbound.ports.clone(),
                     )
                     .await?;
                     address.send(SetInetService(inet)).await?;
@@ -533,6 +554,8 @@ struct RuntimeProcessContext {
     supervise_hardware: bool,
     infrastructure: HashMap<String, f64>,
     manifest: ManifestContext,
+    security: Option<SecurityProfile>,
+    inet_usage: InetUsage,
 }
 
 impl<'a> From<&'a ExeUnitContext> for RuntimeProcessContext {
@@ -544,6 +567,8 @@ impl<'a> From<&'a ExeUnitContext> for RuntimeProcessContext {
             supervise_hardware: ctx.supervise.hardware,
             infrastructure: ctx.agreement.infrastructure.clone(),
             manifest: ctx.supervise.manifest.clone(),
+            security: ctx.supervise.security.clone(),
+            inet_usage: ctx.inet_usage.clone(),
         }
     }
 }
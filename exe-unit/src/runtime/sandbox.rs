@@ -0,0 +1,442 @@
+//! Security profiles applied to runtime processes before they're executed.
+//!
+//! Profiles are defined by the Provider in a JSON file keyed by profile name.
+//! Preset selects a profile with the `golem.srv.caps.security.profile` Offer property.
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+
+use ya_agreement_utils::AgreementView;
+
+use crate::error::Error;
+
+const SECURITY_PROFILE_POINTER: &str = "/offer/properties/golem/srv/caps/security/profile";
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SecurityProfile {
+    /// Runtime processes can't gain privileges, e.g. by executing setuid binaries.
+    #[serde(default)]
+    pub no_new_privs: bool,
+    /// System calls failing with `EPERM`. Implies `no_new_privs`.
+    #[serde(default)]
+    pub seccomp: Vec<SyscallSet>,
+    /// Paths re-mounted read-only in a private mount namespace.
+    /// Mounts nested below these paths stay writable.
+    #[serde(default)]
+    pub read_only: Vec<PathBuf>,
+    /// AppArmor profile the runtime is confined with when it's executed.
+    #[serde(default)]
+    pub apparmor: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SyscallSet {
+    /// Mounting file systems and changing the root directory.
+    Mount,
+    /// Loading and unloading kernel modules.
+    Modules,
+    /// Rebooting, loading a new kernel and managing swap.
+    Reboot,
+    /// Kernel keyring management.
+    Keyring,
+    /// Tracing and accessing memory of other processes.
+    Ptrace,
+    /// eBPF programs, performance events and userfaultfd.
+    Bpf,
+    /// Creating and entering namespaces.
+    Namespaces,
+    /// Setting the system clock.
+    Clock,
+}
+
+impl SecurityProfile {
+    /// Profile selected by the Agreement, defined in the `profiles` file.
+    pub fn from_agreement(
+        agreement: &AgreementView,
+        profiles: Option<&Path>,
+    ) -> Result<Option<Self>, Error> {
+        let name = match agreement.pointer_typed::<String>(SECURITY_PROFILE_POINTER) {
+            Ok(name) => name,
+            Err(_) => return Ok(None),
+        };
+        let path = profiles.ok_or_else(|| {
+            Error::other(format!(
+                "security profile [{name}] selected, but no profiles file given"
+            ))
+        })?;
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            Error::other(format!(
+                "cannot read security profiles from {}: {e}",
+                path.display()
+            ))
+        })?;
+        let mut profiles: HashMap<String, SecurityProfile> = serde_json::from_str(&content)?;
+        profiles
+            .remove(&name)
+            .map(Some)
+            .ok_or_else(|| Error::other(format!("unknown security profile [{name}]")))
+    }
+
+    /// Sets up the profile to be entered by the runtime process before it's executed.
+    #[cfg(target_os = "linux")]
+    pub fn apply(&self, command: &mut Command) -> Result<(), Error> {
+        let mut sandbox = linux::Sandbox::try_new(self)?;
+        unsafe {
+            command.pre_exec(move || sandbox.enter());
+        }
+        Ok(())
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn apply(&self, _command: &mut Command) -> Result<(), Error> {
+        Err(Error::other(
+            "security profiles are supported on Linux only",
+        ))
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use nix::libc;
+    use std::ffi::CString;
+    use std::io;
+    use std::os::unix::ffi::OsStrExt;
+
+    use super::{SecurityProfile, SyscallSet};
+    use crate::error::Error;
+
+    const BPF_LD_W_ABS: u16 = 0x20;
+    const BPF_JMP_JEQ_K: u16 = 0x15;
+    #[cfg(target_arch = "x86_64")]
+    const BPF_JMP_JGE_K: u16 = 0x35;
+    const BPF_RET_K: u16 = 0x06;
+
+    const SECCOMP_MODE_FILTER: libc::c_ulong = 2;
+    const SECCOMP_RET_KILL_PROCESS: u32 = 0x8000_0000;
+    const SECCOMP_RET_ERRNO: u32 = 0x0005_0000;
+    const SECCOMP_RET_ALLOW: u32 = 0x7fff_0000;
+    /// Offsets of `nr` and `arch` in `struct seccomp_data`.
+    const SECCOMP_DATA_NR: u32 = 0;
+    const SECCOMP_DATA_ARCH: u32 = 4;
+
+    #[cfg(target_arch = "x86_64")]
+    const AUDIT_ARCH: Option<u32> = Some(0xc000_003e);
+    #[cfg(target_arch = "aarch64")]
+    const AUDIT_ARCH: Option<u32> = Some(0xc000_00b7);
+    #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+    const AUDIT_ARCH: Option<u32> = None;
+
+    /// Syscalls of the x32 ABI on x86_64 have this bit set.
+    #[cfg(target_arch = "x86_64")]
+    const X32_SYSCALL_BIT: u32 = 0x4000_0000;
+
+    impl SyscallSet {
+        fn syscalls(&self) -> &'static [libc::c_long] {
+            match self {
+                SyscallSet::Mount => &[libc::SYS_mount, libc::SYS_umount2, libc::SYS_pivot_root],
+                SyscallSet::Modules => &[
+                    libc::SYS_init_module,
+                    libc::SYS_finit_module,
+                    libc::SYS_delete_module,
+                ],
+                SyscallSet::Reboot => &[
+                    libc::SYS_reboot,
+                    libc::SYS_kexec_load,
+                    libc::SYS_swapon,
+                    libc::SYS_swapoff,
+                ],
+                SyscallSet::Keyring => {
+                    &[libc::SYS_add_key, libc::SYS_request_key, libc::SYS_keyctl]
+                }
+                SyscallSet::Ptrace => &[
+                    libc::SYS_ptrace,
+                    libc::SYS_process_vm_readv,
+                    libc::SYS_process_vm_writev,
+                ],
+                SyscallSet::Bpf => &[
+                    libc::SYS_bpf,
+                    libc::SYS_perf_event_open,
+                    libc::SYS_userfaultfd,
+                ],
+                SyscallSet::Namespaces => &[libc::SYS_unshare, libc::SYS_setns],
+                SyscallSet::Clock => &[
+                    libc::SYS_settimeofday,
+                    libc::SYS_clock_settime,
+                    libc::SYS_clock_adjtime,
+                    libc::SYS_adjtimex,
+                ],
+            }
+        }
+    }
+
+    /// Profile prepared in the parent process, so that entering it after fork
+    /// doesn't allocate.
+    pub(super) struct Sandbox {
+        mount_ns: Option<MountNamespace>,
+        apparmor: Option<CString>,
+        no_new_privs: bool,
+        filter: Vec<libc::sock_filter>,
+    }
+
+    struct MountNamespace {
+        /// Contents of `uid_map` and `gid_map`, when the namespace is created
+        /// by an unprivileged user.
+        id_maps: Option<(CString, CString)>,
+        /// Paths with flags of their mounts, which can't be changed on remount.
+        read_only: Vec<(CString, libc::c_ulong)>,
+    }
+
+    impl Sandbox {
+        pub fn try_new(profile: &SecurityProfile) -> Result<Self, Error> {
+            let mount_ns = match profile.read_only.is_empty() {
+                true => None,
+                false => Some(MountNamespace::try_new(profile)?),
+            };
+            let apparmor = profile
+                .apparmor
+                .as_ref()
+                .map(|name| CString::new(format!("exec {name}")))
+                .transpose()
+                .map_err(Error::other)?;
+            let filter = match profile.seccomp.is_empty() {
+                true => Vec::new(),
+                false => seccomp_filter(&profile.seccomp)?,
+            };
+            Ok(Sandbox {
+                mount_ns,
+                apparmor,
+                no_new_privs: profile.no_new_privs || !filter.is_empty(),
+                filter,
+            })
+        }
+
+        /// Called in the forked child process.
+        pub fn enter(&mut self) -> io::Result<()> {
+            if let Some(mount_ns) = &self.mount_ns {
+                mount_ns.enter()?;
+            }
+            if let Some(apparmor) = &self.apparmor {
+                write_proc(b"/proc/self/attr/exec\0", apparmor.as_bytes())?;
+            }
+            if self.no_new_privs {
+                check(unsafe { libc::prctl(libc::PR_SET_NO_NEW_PRIVS, 1, 0, 0, 0) })?;
+            }
+            if !self.filter.is_empty() {
+                let program = libc::sock_fprog {
+                    len: self.filter.len() as libc::c_ushort,
+                    filter: self.filter.as_mut_ptr(),
+                };
+                check(unsafe {
+                    libc::prctl(
+                        libc::PR_SET_SECCOMP,
+                        SECCOMP_MODE_FILTER,
+                        &program as *const libc::sock_fprog,
+                    )
+                })?;
+            }
+            Ok(())
+        }
+    }
+
+    impl MountNamespace {
+        fn try_new(profile: &SecurityProfile) -> Result<Self, Error> {
+            let id_maps = match unsafe { libc::geteuid() } {
+                0 => None,
+                uid => {
+                    let gid = unsafe { libc::getegid() };
+                    Some((
+                        CString::new(format!("{uid} {uid} 1")).map_err(Error::other)?,
+                        CString::new(format!("{gid} {gid} 1")).map_err(Error::other)?,
+                    ))
+                }
+            };
+            let read_only = profile
+                .read_only
+                .iter()
+                .map(|path| {
+                    let c_path = CString::new(path.as_os_str().as_bytes()).map_err(Error::other)?;
+                    let flags = mount_flags(&c_path).map_err(|e| {
+                        Error::other(format!("cannot read-only mount {}: {e}", path.display()))
+                    })?;
+                    Ok((c_path, flags))
+                })
+                .collect::<Result<_, Error>>()?;
+            Ok(MountNamespace { id_maps, read_only })
+        }
+
+        fn enter(&self) -> io::Result<()> {
+            match &self.id_maps {
+                Some((uid_map, gid_map)) => {
+                    check(unsafe { libc::unshare(libc::CLONE_NEWUSER | libc::CLONE_NEWNS) })?;
+                    write_proc(b"/proc/self/setgroups\0", b"deny")?;
+                    write_proc(b"/proc/self/uid_map\0", uid_map.as_bytes())?;
+                    write_proc(b"/proc/self/gid_map\0", gid_map.as_bytes())?;
+                }
+                None => check(unsafe { libc::unshare(libc::CLONE_NEWNS) })?,
+            }
+            // Don't propagate mounts back to the host namespace.
+            mount(
+                None,
+                b"/\0".as_ptr().cast(),
+                libc::MS_REC | libc::MS_PRIVATE,
+            )?;
+            for (path, flags) in &self.read_only {
+                mount(
+                    Some(path.as_ptr()),
+                    path.as_ptr(),
+                    libc::MS_BIND | libc::MS_REC,
+                )?;
+                mount(
+                    None,
+                    path.as_ptr(),
+                    libc::MS_BIND | libc::MS_REMOUNT | libc::MS_RDONLY | flags,
+                )?;
+            }
+            Ok(())
+        }
+    }
+
+    /// Flags of the mount containing `path`, locked in unprivileged mount namespaces.
+    fn mount_flags(path: &CString) -> io::Result<libc::c_ulong> {
+        let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+        check(unsafe { libc::statvfs(path.as_ptr(), &mut stat) })?;
+        let flags = [
+            (libc::ST_NOSUID, libc::MS_NOSUID),
+            (libc::ST_NODEV, libc::MS_NODEV),
+            (libc::ST_NOEXEC, libc::MS_NOEXEC),
+            (libc::ST_NOATIME, libc::MS_NOATIME),
+            (libc::ST_NODIRATIME, libc::MS_NODIRATIME),
+            (libc::ST_RELATIME, libc::MS_RELATIME),
+        ];
+        Ok(flags
+            .iter()
+            .filter(|(st, _)| stat.f_flag & st != 0)
+            .fold(0, |acc, (_, ms)| acc | ms))
+    }
+
+    fn mount(
+        source: Option<*const libc::c_char>,
+        target: *const libc::c_char,
+        flags: libc::c_ulong,
+    ) -> io::Result<()> {
+        let source = source.unwrap_or(std::ptr::null());
+        check(unsafe { libc::mount(source, target, std::ptr::null(), flags, std::ptr::null()) })
+    }
+
+    /// Writes `content` to a NUL-terminated `path` without allocating.
+    fn write_proc(path: &[u8], content: &[u8]) -> io::Result<()> {
+        let fd = unsafe { libc::open(path.as_ptr().cast(), libc::O_WRONLY | libc::O_CLOEXEC) };
+        check(fd)?;
+        let written = unsafe { libc::write(fd, content.as_ptr().cast(), content.len()) };
+        unsafe { libc::close(fd) };
+        match written {
+            -1 => Err(io::Error::last_os_error()),
+            _ => Ok(()),
+        }
+    }
+
+    fn check(result: libc::c_int) -> io::Result<()> {
+        match result {
+            -1 => Err(io::Error::last_os_error()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Filter denying syscalls of `sets` with `EPERM` and killing processes
+    /// using syscalls of other architectures.
+    pub(super) fn seccomp_filter(sets: &[SyscallSet]) -> Result<Vec<libc::sock_filter>, Error> {
+        let arch = AUDIT_ARCH.ok_or_else(|| {
+            Error::other("seccomp profiles are not supported on this architecture")
+        })?;
+        let mut syscalls: Vec<u32> = sets
+            .iter()
+            .flat_map(|set| set.syscalls().iter().map(|nr| *nr as u32))
+            .collect();
+        syscalls.sort_unstable();
+        syscalls.dedup();
+        let denied = syscalls.len();
+
+        let stmt = |code, k| libc::sock_filter {
+            code,
+            jt: 0,
+            jf: 0,
+            k,
+        };
+        let jump = |code, k, jt| libc::sock_filter { code, jt, jf: 0, k };
+
+        let mut filter = vec![
+            stmt(BPF_LD_W_ABS, SECCOMP_DATA_ARCH),
+            jump(BPF_JMP_JEQ_K, arch, 1),
+            stmt(BPF_RET_K, SECCOMP_RET_KILL_PROCESS),
+            stmt(BPF_LD_W_ABS, SECCOMP_DATA_NR),
+        ];
+        // Denied syscalls could be reached with their x32 numbers.
+        #[cfg(target_arch = "x86_64")]
+        filter.push(jump(BPF_JMP_JGE_K, X32_SYSCALL_BIT, (denied + 1) as u8));
+        // Matching syscall jumps over the remaining checks and `ALLOW`.
+        for (idx, nr) in syscalls.into_iter().enumerate() {
+            filter.push(jump(BPF_JMP_JEQ_K, nr, (denied - idx) as u8));
+        }
+        filter.push(stmt(BPF_RET_K, SECCOMP_RET_ALLOW));
+        filter.push(stmt(
+            BPF_RET_K,
+            SECCOMP_RET_ERRNO | (libc::EPERM as u32 & 0xffff),
+        ));
+        Ok(filter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_profiles() {
+        let profiles: HashMap<String, SecurityProfile> = serde_json::from_str(
+            r#"{
+                "restricted": {
+                    "no-new-privs": true,
+                    "seccomp": ["mount", "namespaces"],
+                    "read-only": ["/etc"]
+                },
+                "empty": {}
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(profiles["empty"], SecurityProfile::default());
+        let restricted = &profiles["restricted"];
+        assert!(restricted.no_new_privs);
+        assert_eq!(
+            restricted.seccomp,
+            vec![SyscallSet::Mount, SyscallSet::Namespaces]
+        );
+        assert_eq!(restricted.read_only, vec![PathBuf::from("/etc")]);
+        assert!(restricted.apparmor.is_none());
+    }
+
+    #[cfg(all(
+        target_os = "linux",
+        any(target_arch = "x86_64", target_arch = "aarch64")
+    ))]
+    #[test]
+    fn seccomp_filter_jumps() {
+        let filter = linux::seccomp_filter(&[SyscallSet::Mount, SyscallSet::Mount]).unwrap();
+        let deny = filter.len() - 1;
+
+        // Duplicated sets are denied once.
+        let checks: Vec<(usize, &nix::libc::sock_filter)> = filter
+            .iter()
+            .enumerate()
+            .filter(|(_, f)| f.code == 0x15)
+            .skip(1)
+            .collect();
+        assert_eq!(checks.len(), 3);
+        for (idx, check) in checks {
+            assert_eq!(idx + 1 + check.jt as usize, deny);
+        }
+    }
+}
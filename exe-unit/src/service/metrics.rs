@@ -4,7 +4,7 @@ use crate::error::Error;
 use crate::message::{GetMetrics, SetMetric, Shutdown};
 use crate::metrics::error::MetricError;
 use crate::metrics::{
-    CpuMetric, MemMetric, Metric, MetricData, MetricReport, StorageMetric, TimeMetric,
+    CpuMetric, InetMetric, MemMetric, Metric, MetricData, MetricReport, StorageMetric, TimeMetric,
 };
 use crate::ExeUnitContext;
 use actix::prelude::*;
@@ -15,6 +15,8 @@ use std::time::Duration;
 
 pub struct MetricsService {
     usage_vector: Vec<String>,
+    /// Metrics with usage limits enforced even though they're not in the usage vector.
+    supervised: Vec<String>,
     metrics: HashMap<String, MetricProvider>,
 }
 
@@ -47,8 +49,23 @@ impl MetricsService {
             metrics.insert(m, provider);
         });
 
+        // Outbound traffic caps are declared by the Requestor in the manifest
+        // and need to be enforced regardless of metrics billed by the Provider.
+        let supervised = [InetMetric::ID]
+            .iter()
+            .filter(|id| !ctx.agreement.usage_vector.iter().any(|m| m == *id))
+            .filter(|id| {
+                metrics
+                    .get(**id)
+                    .map(|provider| provider.usage_limit.is_some())
+                    .unwrap_or(false)
+            })
+            .map(|id| id.to_string())
+            .collect();
+
         Ok(MetricsService {
             usage_vector: ctx.agreement.usage_vector.clone(),
+            supervised,
             metrics,
         })
     }
@@ -117,6 +134,18 @@ impl MetricsService {
                 TimeMetric::ID.to_string(),
                 MetricProvider::new(TimeMetric::default(), Some(1), caps(ctx, TimeMetric::ID)),
             ),
+            (
+                InetMetric::ID.to_string(),
+                MetricProvider::new(
+                    InetMetric::new(ctx.inet_usage.clone()),
+                    backlog_limit,
+                    ctx.supervise
+                        .manifest
+                        .inet_limits()
+                        .and_then(|limits| limits.max_bytes)
+                        .map(|bytes| bytes as MetricData),
+                ),
+            ),
         ]
         .into_iter()
         .collect()
@@ -140,6 +169,17 @@ impl Handler<GetMetrics> for MetricsService {
     type Result = <GetMetrics as Message>::Result;
 
     fn handle(&mut self, _: GetMetrics, _: &mut Self::Context) -> Self::Result {
+        for name in self.supervised.iter() {
+            if let Some(MetricReport::LimitExceeded(data)) =
+                self.metrics.get_mut(name).map(|metric| metric.report())
+            {
+                return Err(Error::UsageLimitExceeded(format!(
+                    "{:?} exceeded the value of {:?}",
+                    name, data
+                )));
+            }
+        }
+
         let mut metrics = vec![0f64; self.usage_vector.len()];
 
         for (i, name) in self.usage_vector.iter().enumerate() {
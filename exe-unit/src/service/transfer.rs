@@ -1,7 +1,8 @@
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
-use std::io;
-use std::path::{Path, PathBuf};
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::{Component, Path, PathBuf};
 use std::rc::Rc;
 
 use actix::prelude::*;
@@ -16,6 +17,7 @@ use crate::util::Abort;
 use crate::{ExeUnitContext, Result};
 
 use ya_client_model::activity::TransferArgs;
+use ya_core_model::activity::OutputFileChunk;
 use ya_transfer::error::Error as TransferError;
 use ya_transfer::*;
 
@@ -45,6 +47,26 @@ pub struct DeployImage;
 #[rtype(result = "()")]
 pub struct AbortTransfers;
 
+/// Reads a chunk of a file within container volumes.
+#[derive(Clone, Debug, Message)]
+#[rtype(result = "Result<OutputFileChunk>")]
+pub struct ReadContainerFile {
+    pub path: String,
+    pub offset: u64,
+    pub max_bytes: Option<u64>,
+}
+
+const OUTPUT_CHUNK_MAX_BYTES: u64 = 1024 * 1024;
+const OUTPUT_FILE_MAX_SIZE_ENV_VAR: &str = "OUTPUT_FILE_MAX_SIZE_BYTES";
+const DEFAULT_OUTPUT_FILE_MAX_SIZE: u64 = 1024 * 1024 * 1024;
+
+fn output_file_max_size() -> u64 {
+    std::env::var(OUTPUT_FILE_MAX_SIZE_ENV_VAR)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_OUTPUT_FILE_MAX_SIZE)
+}
+
 struct ContainerTransferProvider {
     file_tp: FileTransferProvider,
     dir_tp: DirTransferProvider,
@@ -104,6 +126,62 @@ impl ContainerTransferProvider {
     fn resolve_url(&self, path: &str) -> std::result::Result<Url, TransferError> {
         Ok(Url::from_file_path(self.resolve_path(path)?).unwrap())
     }
+
+    /// Resolves a path of a file in volumes, which can be read by the Requestor.
+    /// Paths leading outside of volumes, e.g. via symlinks, are refused.
+    fn resolve_output_path(
+        &self,
+        container_path: &str,
+    ) -> std::result::Result<PathBuf, TransferError> {
+        let forbidden = || {
+            TransferError::IoError(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                anyhow::anyhow!("path outside of volumes: {}", container_path),
+            ))
+        };
+
+        if Path::new(container_path)
+            .components()
+            .any(|c| c == Component::ParentDir)
+        {
+            return Err(forbidden());
+        }
+        let path = self.resolve_path(container_path)?.canonicalize()?;
+        let within_volume = self.vols.iter().any(|vol| {
+            self.work_dir
+                .join(&vol.name)
+                .canonicalize()
+                .map(|base| path.starts_with(base))
+                .unwrap_or(false)
+        });
+        match within_volume && path.is_file() {
+            true => Ok(path),
+            false => Err(forbidden()),
+        }
+    }
+}
+
+fn read_chunk(path: &Path, offset: u64, max_bytes: Option<u64>) -> Result<OutputFileChunk> {
+    let max_size = output_file_max_size();
+    if offset > max_size {
+        return Err(Error::UsageLimitExceeded(format!(
+            "output file offset {} exceeds the limit of {} B",
+            offset, max_size
+        )));
+    }
+    let max_bytes = max_bytes
+        .unwrap_or(OUTPUT_CHUNK_MAX_BYTES)
+        .min(OUTPUT_CHUNK_MAX_BYTES)
+        .min(max_size - offset);
+
+    let mut file = File::open(path)?;
+    let size = file.metadata()?.len();
+    let mut data = Vec::new();
+    if offset < size {
+        file.seek(SeekFrom::Start(offset))?;
+        file.take(max_bytes).read_to_end(&mut data)?;
+    }
+    Ok(OutputFileChunk { offset, data, size })
 }
 
 impl TransferProvider<TransferData, TransferError> for ContainerTransferProvider {
@@ -151,6 +229,7 @@ pub struct TransferService {
     work_dir: PathBuf,
     task_package: Option<String>,
     abort_handles: Rc<RefCell<HashSet<Abort>>>,
+    container: Option<Rc<ContainerTransferProvider>>,
 }
 
 impl TransferService {
@@ -161,6 +240,7 @@ impl TransferService {
             work_dir: ctx.work_dir.clone(),
             task_package: ctx.agreement.task_package.clone(),
             abort_handles: Default::default(),
+            container: None,
         }
     }
 
@@ -349,13 +429,27 @@ impl Handler<AddVolumes> for TransferService {
     fn handle(&mut self, msg: AddVolumes, _ctx: &mut Self::Context) -> Self::Result {
         log::info!("Adding volumes: {:?}", msg.0);
         let container_transfer_provider =
-            ContainerTransferProvider::new(self.work_dir.clone(), msg.0);
+            Rc::new(ContainerTransferProvider::new(self.work_dir.clone(), msg.0));
         self.providers
-            .insert("container", Rc::new(container_transfer_provider));
+            .insert("container", container_transfer_provider.clone());
+        self.container = Some(container_transfer_provider);
         Ok(())
     }
 }
 
+impl Handler<ReadContainerFile> for TransferService {
+    type Result = Result<OutputFileChunk>;
+
+    fn handle(&mut self, msg: ReadContainerFile, _: &mut Self::Context) -> Self::Result {
+        let container = self
+            .container
+            .as_ref()
+            .ok_or_else(|| Error::Other("image not deployed".to_string()))?;
+        let path = container.resolve_output_path(&msg.path)?;
+        read_chunk(&path, msg.offset, msg.max_bytes)
+    }
+}
+
 impl Handler<AbortTransfers> for TransferService {
     type Result = <AbortTransfers as Message>::Result;
 
@@ -554,4 +648,37 @@ mod test {
         );
         eprintln!("{}", c.resolve_path("/in/tasks.json").unwrap().display());
     }
+
+    #[test]
+    fn test_read_output() {
+        let work_dir = tempdir::TempDir::new("output").unwrap();
+        std::fs::create_dir(work_dir.path().join("vol-1")).unwrap();
+        std::fs::write(work_dir.path().join("vol-1").join("out.txt"), "partial").unwrap();
+        std::fs::write(work_dir.path().join("secret"), "secret").unwrap();
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(
+            work_dir.path().join("secret"),
+            work_dir.path().join("vol-1").join("link"),
+        )
+        .unwrap();
+
+        let c = ContainerTransferProvider::new(
+            work_dir.path().to_path_buf(),
+            vec![ContainerVolume {
+                name: "vol-1".into(),
+                path: "/golem/output".into(),
+            }],
+        );
+
+        let path = c.resolve_output_path("/golem/output/out.txt").unwrap();
+        let chunk = read_chunk(&path, 2, Some(3)).unwrap();
+        assert_eq!(chunk.data, b"rti".to_vec());
+        assert_eq!(chunk.size, 7);
+        assert!(read_chunk(&path, 7, None).unwrap().data.is_empty());
+
+        assert!(c.resolve_output_path("/golem/output/../secret").is_err());
+        assert!(c.resolve_output_path("/golem/output").is_err());
+        #[cfg(unix)]
+        assert!(c.resolve_output_path("/golem/output/link").is_err());
+    }
 }
@@ -22,6 +22,7 @@ use crate::error::Error;
 use crate::manifest::ManifestContext;
 use crate::notify::Notify;
 use crate::output::CapturedOutput;
+use crate::runtime::sandbox::SecurityProfile;
 use crate::runtime::RuntimeMode;
 
 fn invalid_state_err_msg(state_pair: &StatePair) -> String {
@@ -60,6 +61,7 @@ pub struct Supervision {
     pub hardware: bool,
     pub image: bool,
     pub manifest: ManifestContext,
+    pub security: Option<SecurityProfile>,
 }
 
 #[derive(Default)]
@@ -0,0 +1,58 @@
+use ansi_term::{Colour, Style};
+use anyhow::Result;
+use prettytable::{format, row, Table};
+use structopt::StructOpt;
+
+use crate::command::{BenchmarkScores, YaCommand};
+
+#[derive(StructOpt, Debug)]
+pub enum BenchmarkCommand {
+    /// Run CPU, memory and disk benchmarks, scores are published in next offers
+    Run {
+        /// Threads used by the multi-core CPU benchmark, all threads by default
+        #[structopt(long)]
+        threads: Option<usize>,
+    },
+    /// Show scores published in offers
+    Show,
+}
+
+pub async fn run(command: BenchmarkCommand) -> Result</*exit code*/ i32> {
+    let cmd = YaCommand::new()?;
+    let scores = match command {
+        BenchmarkCommand::Run { threads } => {
+            println!("Running benchmarks, it takes a few seconds...");
+            let scores = cmd.ya_provider()?.run_benchmark(threads).await?;
+            println!(
+                "Run `golemsp settings apply --live` to publish scores of a running provider."
+            );
+            scores
+        }
+        BenchmarkCommand::Show => cmd.ya_provider()?.benchmark_scores().await?,
+    };
+
+    let mut table = Table::new();
+    let format = format::FormatBuilder::new().padding(1, 1).build();
+    table.set_format(format);
+    table.add_row(row![Style::new()
+        .fg(Colour::Yellow)
+        .underline()
+        .paint("Benchmark scores (1.0 = reference machine)")]);
+    table.add_empty_row();
+    if scores.is_empty() {
+        table.add_row(row!["no benchmarks run, see `golemsp benchmark run`"]);
+    }
+    let rows = [
+        ("CPU single-core", scores.cpu_single),
+        ("CPU multi-core", scores.cpu_multi),
+        ("memory bandwidth", scores.mem_bandwidth),
+        ("disk IOPS", scores.disk_iops),
+    ];
+    for (name, score) in rows {
+        if let Some(score) = score {
+            table.add_row(row![name, r->format!("{:.2}", score)]);
+        }
+    }
+    table.printstd();
+    Ok(0)
+}
@@ -1,9 +1,11 @@
 use anyhow::Context;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::ffi::OsStr;
 use std::{collections::BTreeMap, process::Stdio};
 use tokio::process::{Child, Command};
 
+pub use ya_provider::benchmark::BenchmarkScores;
+pub use ya_provider::cli::exe_unit::TestReport;
 pub use ya_provider::GlobalsState as ProviderConfig;
 
 use crate::command::{NetworkGroup, NETWORK_GROUP_MAP};
@@ -15,7 +17,7 @@ pub struct YaProviderCommand {
     pub(super) cmd: Command,
 }
 
-#[derive(Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct Preset {
     pub name: String,
@@ -26,6 +28,14 @@ pub struct Preset {
 
 pub type UsageDef = BTreeMap<String, f64>;
 
+/// Row of `ya-provider whitelist list`: id, pattern and its type.
+pub type WhitelistEntry = (String, String, String);
+
+#[derive(Deserialize)]
+struct WhitelistTable {
+    values: Vec<WhitelistEntry>,
+}
+
 #[derive(Deserialize)]
 pub struct RuntimeInfo {
     pub name: String,
@@ -116,6 +126,27 @@ impl YaProviderCommand {
             .context("parsing ya-provider exe-unit list")
     }
 
+    pub async fn test_runtime(self, name: &str) -> anyhow::Result<TestReport> {
+        let mut cmd = self.cmd;
+
+        // Failed test exits with an error, but the report is printed anyway.
+        let output = cmd
+            .args(["--json", "exe-unit", "test", name])
+            .stderr(Stdio::piped())
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .output()
+            .await
+            .context("failed to run ya-provider exe-unit test")?;
+
+        serde_json::from_slice(output.stdout.as_slice()).with_context(|| {
+            format!(
+                "parsing ya-provider exe-unit test: {}",
+                String::from_utf8_lossy(&output.stderr)
+            )
+        })
+    }
+
     pub async fn create_preset(
         self,
         name: &str,
@@ -205,12 +236,13 @@ impl YaProviderCommand {
     pub async fn update_preset(
         mut self,
         name: &str,
+        new_name: &str,
         exeunit_name: &str,
         usage_coeffs: &UsageDef,
     ) -> anyhow::Result<()> {
         let cmd = &mut self.cmd;
         cmd.args(["preset", "update", "--no-interactive"]);
-        preset_command(cmd, name, exeunit_name, usage_coeffs);
+        preset_command(cmd, new_name, exeunit_name, usage_coeffs);
         cmd.arg("--").arg(name);
         self.exec_no_output()
             .await
@@ -360,6 +392,111 @@ impl YaProviderCommand {
             Err(anyhow::anyhow!("{}", output))
         }
     }
+    pub async fn list_whitelist(self, preset: Option<&str>) -> anyhow::Result<Vec<WhitelistEntry>> {
+        let mut cmd = self.cmd;
+        cmd.args(["--json", "whitelist", "list"]);
+        if let Some(preset) = preset {
+            cmd.arg("--preset").arg(preset);
+        }
+
+        let output = cmd
+            .stderr(Stdio::inherit())
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .output()
+            .await
+            .context("failed to get ya-provider whitelist")?;
+
+        let table: WhitelistTable = serde_json::from_slice(output.stdout.as_slice())
+            .context("parsing ya-provider whitelist list")?;
+        Ok(table.values)
+    }
+
+    pub async fn add_to_whitelist(
+        mut self,
+        preset: Option<&str>,
+        whitelist_type: &str,
+        entries: &[String],
+    ) -> anyhow::Result<()> {
+        let cmd = &mut self.cmd;
+        cmd.args(["whitelist", "add", "-t", whitelist_type]);
+        if let Some(preset) = preset {
+            cmd.arg("--preset").arg(preset);
+        }
+        cmd.arg("-p").args(entries);
+        self.exec_no_output()
+            .await
+            .context("failed adding entries to whitelist")
+    }
+
+    pub async fn remove_from_whitelist(
+        mut self,
+        preset: Option<&str>,
+        ids: &[String],
+    ) -> anyhow::Result<()> {
+        let cmd = &mut self.cmd;
+        cmd.args(["whitelist", "remove"]);
+        if let Some(preset) = preset {
+            cmd.arg("--preset").arg(preset);
+        }
+        cmd.arg("--").args(ids);
+        self.exec_no_output()
+            .await
+            .context("failed removing entries from whitelist")
+    }
+
+    pub async fn limit_outbound(
+        mut self,
+        preset: &str,
+        ports: &[u16],
+        max_bytes: Option<u64>,
+    ) -> anyhow::Result<()> {
+        let cmd = &mut self.cmd;
+        cmd.args(["whitelist", "limit", "--preset", preset]);
+        for port in ports {
+            cmd.arg("--ports").arg(port.to_string());
+        }
+        if let Some(max_bytes) = max_bytes {
+            cmd.arg("--max-bytes").arg(max_bytes.to_string());
+        }
+        self.exec_no_output()
+            .await
+            .with_context(|| format!("limit outbound of preset {}", preset))
+    }
+
+    pub async fn run_benchmark(self, threads: Option<usize>) -> anyhow::Result<BenchmarkScores> {
+        let mut cmd = self.cmd;
+        cmd.args(["--json", "benchmark", "run"]);
+        if let Some(threads) = threads {
+            cmd.arg("--threads").arg(threads.to_string());
+        }
+
+        let output = cmd
+            .stderr(Stdio::inherit())
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .output()
+            .await
+            .context("failed to run ya-provider benchmark")?;
+
+        serde_json::from_slice(output.stdout.as_slice())
+            .context("parsing ya-provider benchmark run")
+    }
+
+    pub async fn benchmark_scores(self) -> anyhow::Result<BenchmarkScores> {
+        let mut cmd = self.cmd;
+        let output = cmd
+            .args(["--json", "benchmark", "show"])
+            .stderr(Stdio::inherit())
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .output()
+            .await
+            .context("failed to get ya-provider benchmark")?;
+
+        serde_json::from_slice(output.stdout.as_slice())
+            .context("parsing ya-provider benchmark show")
+    }
 }
 
 fn preset_command<'a, 'b>(
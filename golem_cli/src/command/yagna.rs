@@ -10,8 +10,9 @@ use strum_macros::{Display, EnumString, EnumVariantNames, IntoStaticStr};
 
 use crate::setup::RunConfig;
 use tokio::process::{Child, Command};
+use ya_core_model::journal::{Entry, UptimeWindow};
 use ya_core_model::payment::local::{
-    InvoiceStats, InvoiceStatusNotes, NetworkName, StatusNotes, StatusResult,
+    InvoiceStats, InvoiceStatusNotes, NetworkName, StatusNotes, StatusResult, UnpaidInvoice,
 };
 use ya_core_model::version::VersionInfo;
 
@@ -168,6 +169,20 @@ pub struct Id {
     pub node_id: String,
 }
 
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaymentAccount {
+    pub platform: String,
+    pub address: String,
+    pub receive: bool,
+}
+
+/// `yagna net bandwidth` table, values are `[nodeId, out MiB, in MiB]`.
+#[derive(Deserialize)]
+struct BandwidthTable {
+    values: Vec<(String, f64, f64)>,
+}
+
 pub trait PaymentSummary {
     fn total_pending(&self) -> (BigDecimal, u64);
     fn unconfirmed(&self) -> (BigDecimal, u64);
@@ -303,11 +318,63 @@ impl YagnaCommand {
         self.run().await
     }
 
+    /// `last` is a duration like `30days`.
+    pub async fn invoice_status_since(mut self, last: &str) -> anyhow::Result<InvoiceStats> {
+        self.cmd
+            .args(["--json", "payment", "invoice", "status", "--last", last]);
+        self.run().await
+    }
+
+    /// Total (out, in) MiB exchanged with other nodes since the service start.
+    pub async fn bandwidth(mut self) -> anyhow::Result<(f64, f64)> {
+        self.cmd.args(["--json", "net", "bandwidth"]);
+        let table: BandwidthTable = self.run().await?;
+        Ok(table
+            .values
+            .iter()
+            .fold((0.0, 0.0), |(tx, rx), row| (tx + row.1, rx + row.2)))
+    }
+
+    pub async fn unpaid_invoices(mut self, address: &str) -> anyhow::Result<Vec<UnpaidInvoice>> {
+        self.cmd
+            .args(["--json", "payment", "invoice", address, "unpaid"]);
+        self.run().await
+    }
+
+    pub async fn payment_accounts(mut self) -> anyhow::Result<Vec<PaymentAccount>> {
+        self.cmd.args(["--json", "payment", "accounts"]);
+        self.run().await
+    }
+
+    /// Fails if the node isn't known to the network.
+    pub async fn find_node(mut self, node_id: &str) -> anyhow::Result<serde_json::Value> {
+        self.cmd.args(["--json", "net", "find", node_id]);
+        self.run().await
+    }
+
+    pub async fn journal_events(mut self, kind: &str, last: &str) -> anyhow::Result<Vec<Entry>> {
+        self.cmd
+            .args(["--json", "journal", "events", "--kind", kind]);
+        self.cmd.args(["--last", last, "--limit", "1000"]);
+        self.run().await
+    }
+
     pub async fn activity_status(mut self) -> anyhow::Result<ActivityStatus> {
         self.cmd.args(["--json", "activity", "status"]);
         self.run().await
     }
 
+    pub async fn uptime(mut self, window_hours: &[u32]) -> anyhow::Result<Vec<UptimeWindow>> {
+        let windows = window_hours
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(",");
+        self.cmd
+            .args(["--json", "journal", "uptime", "--window", &windows]);
+        self.run().await
+    }
+
     pub async fn forward(self, args: Vec<String>) -> anyhow::Result<i32> {
         let mut cmd = self.cmd;
         let output = cmd.arg("--quiet").args(args).status().await?;
@@ -330,6 +397,9 @@ impl YagnaCommand {
             cmd.arg("--log-dir");
             cmd.arg(log_dir.to_str().unwrap());
         }
+        if !run_cfg.metrics_push {
+            cmd.arg("--disable-metrics-push");
+        }
 
         cmd.stdin(Stdio::null())
             .stderr(Stdio::inherit())
@@ -0,0 +1,76 @@
+use ansi_term::{ANSIString, Colour, Style};
+use anyhow::Result;
+use prettytable::{format, row, Table};
+
+use crate::command::YaCommand;
+use crate::utils::is_yagna_running;
+
+fn check(ok: bool) -> ANSIString<'static> {
+    match ok {
+        true => Style::new().fg(Colour::Green).paint("ok"),
+        false => Style::new().fg(Colour::Red).paint("failed"),
+    }
+}
+
+/// Checks the environment and runs the self-test of every installed runtime.
+pub async fn run() -> Result</*exit code*/ i32> {
+    let cmd = YaCommand::new()?;
+    let kvm_status = crate::platform::kvm_status();
+    let mut problems = Vec::new();
+
+    let mut table = Table::new();
+    let format = format::FormatBuilder::new().padding(1, 1).build();
+    table.set_format(format);
+    table.add_row(row![Style::new()
+        .fg(Colour::Yellow)
+        .underline()
+        .paint("Doctor")]);
+    table.add_empty_row();
+
+    let is_running = is_yagna_running().await?;
+    table.add_row(row![
+        "Service",
+        match is_running {
+            true => Style::new().fg(Colour::Green).paint("is running"),
+            false => Style::new().fg(Colour::Fixed(220)).paint("is not running"),
+        }
+    ]);
+    if kvm_status.is_implemented() {
+        table.add_row(row!["VM", check(kvm_status.is_valid())]);
+        if let Some(msg) = kvm_status.problem() {
+            problems.push(format!("VM: {}", msg));
+        }
+    }
+
+    table.add_empty_row();
+    let mut passed = kvm_status.is_valid() || !kvm_status.is_implemented();
+    for runtime in cmd.ya_provider()?.list_runtimes().await? {
+        match cmd.ya_provider()?.test_runtime(&runtime.name).await {
+            Ok(report) => {
+                passed &= report.passed();
+                table.add_row(row![
+                    format!("runtime {}", report.name),
+                    check(report.passed()),
+                    r->format!("{:.3}s", report.duration_secs())
+                ]);
+                for step in report.steps {
+                    if let Some(message) = step.message {
+                        problems.push(format!("{} {}: {}", report.name, step.name, message));
+                    }
+                }
+            }
+            Err(e) => {
+                passed = false;
+                table.add_row(row![format!("runtime {}", runtime.name), check(false)]);
+                problems.push(format!("{}: {:#}", runtime.name, e));
+            }
+        }
+    }
+
+    table.printstd();
+    for problem in problems {
+        println!("\n {}", problem);
+    }
+    println!("\n Run `ya-provider exe-unit test <runtime> --image <url>` to test a whole task.");
+    Ok(if passed { 0 } else { 1 })
+}
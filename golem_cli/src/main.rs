@@ -7,16 +7,22 @@ use std::io::Write;
 use structopt::{clap, StructOpt};
 
 mod appkey;
+mod benchmark;
 mod command;
+mod doctor;
 mod manifest;
+mod payments;
 mod platform;
+mod preset;
 mod service;
 mod settings;
 mod settings_show;
 mod setup;
+mod stats;
 mod status;
 mod terminal;
 mod utils;
+mod whitelist;
 
 #[derive(StructOpt, Debug)]
 enum SettingsCommand {
@@ -24,6 +30,12 @@ enum SettingsCommand {
     Set(settings::Settings),
     /// Show current settings
     Show,
+    /// Apply changed settings
+    Apply {
+        /// Reload settings of the running provider without breaking current agreements
+        #[structopt(long)]
+        live: bool,
+    },
 }
 
 #[allow(clippy::large_enum_variant)]
@@ -45,7 +57,29 @@ enum Commands {
     Settings(SettingsCommand),
 
     /// Show provider status
-    Status,
+    Status {
+        /// Show uptime history: Offer availability, served Agreements and task success rate
+        #[structopt(long)]
+        history: bool,
+    },
+
+    /// Show statistics collected locally: uptime, tasks, earnings and bandwidth
+    Stats(stats::StatsConfig),
+
+    /// Check the environment and test installed runtimes
+    Doctor,
+
+    /// Troubleshoot payments
+    Payments(payments::PaymentsCommand),
+
+    /// Manage presets
+    Preset(preset::PresetCommand),
+
+    /// Manage domains, ports and traffic allowed for tasks' outbound network
+    Whitelist(whitelist::WhitelistCommand),
+
+    /// Benchmark this machine, so requestors can select providers by performance
+    Benchmark(benchmark::BenchmarkCommand),
 
     #[structopt(setting = structopt::clap::AppSettings::Hidden)]
     Complete(CompleteCommand),
@@ -94,8 +128,15 @@ async fn my_main() -> Result</*exit code*/ i32> {
         Commands::Settings(command) => match command {
             SettingsCommand::Set(set) => settings::run(set).await,
             SettingsCommand::Show => settings_show::run().await,
+            SettingsCommand::Apply { live } => settings::apply(live).await,
         },
-        Commands::Status => status::run().await,
+        Commands::Status { history } => status::run(history).await,
+        Commands::Doctor => doctor::run().await,
+        Commands::Stats(config) => stats::run(config).await,
+        Commands::Payments(command) => payments::run(command).await,
+        Commands::Preset(command) => preset::run(command).await,
+        Commands::Whitelist(command) => whitelist::run(command).await,
+        Commands::Benchmark(command) => benchmark::run(command).await,
         Commands::Complete(complete) => {
             let binary_name = clap::crate_name!();
             println!(
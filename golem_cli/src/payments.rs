@@ -0,0 +1,148 @@
+use ansi_term::{Colour, Style};
+use anyhow::Result;
+use chrono::Utc;
+use prettytable::{format, row, Table};
+use std::collections::HashMap;
+use structopt::StructOpt;
+
+use ya_core_model::journal::Entry;
+use ya_core_model::payment::local::UnpaidInvoice;
+use ya_core_model::NodeId;
+
+use crate::command::{PaymentAccount, YaCommand};
+use crate::utils::{is_yagna_running, payment_account};
+
+/// Payment rejections older than that aren't reported.
+const REJECTIONS_PERIOD: &str = "7d";
+
+#[derive(StructOpt, Debug)]
+pub enum PaymentsCommand {
+    /// Diagnose why accepted invoices are still unpaid
+    Doctor {
+        /// Account receiving payments [default: <DEFAULT_IDENTITY>]
+        #[structopt(long)]
+        account: Option<NodeId>,
+    },
+}
+
+pub async fn run(command: PaymentsCommand) -> Result</*exit code*/ i32> {
+    match command {
+        PaymentsCommand::Doctor { account } => doctor(account).await,
+    }
+}
+
+async fn doctor(account: Option<NodeId>) -> Result</*exit code*/ i32> {
+    if !is_yagna_running().await? {
+        println!("Golem Service is not running, start it with `golemsp run`.");
+        return Ok(1);
+    }
+    let cmd = YaCommand::new()?;
+    let address = payment_account(&cmd, &account).await?;
+    let invoices = cmd.yagna()?.unpaid_invoices(&address).await?;
+    if invoices.is_empty() {
+        println!("No unpaid invoices.");
+        return Ok(0);
+    }
+    let accounts = cmd.yagna()?.payment_accounts().await?;
+    let rejections = cmd
+        .yagna()?
+        .journal_events("payment-rejected", REJECTIONS_PERIOD)
+        .await
+        .unwrap_or_else(|e| {
+            log::warn!("Can't read rejected payments: {}", e);
+            Vec::new()
+        });
+
+    let mut reachable = HashMap::new();
+    let mut table = Table::new();
+    let format = format::FormatBuilder::new().padding(1, 1).build();
+    table.set_format(format);
+    table.add_row(row![Style::new()
+        .fg(Colour::Yellow)
+        .underline()
+        .paint("Unpaid invoices")]);
+    table.add_empty_row();
+    table.add_row(row!["invoice", "requestor", "amount", "diagnosis"]);
+
+    for invoice in &invoices {
+        let requestor_id = invoice.requestor_id.to_string();
+        if !reachable.contains_key(&requestor_id) {
+            let found = cmd.yagna()?.find_node(&requestor_id).await.is_ok();
+            reachable.insert(requestor_id.clone(), found);
+        }
+        let diagnoses = diagnose(invoice, reachable[&requestor_id], &accounts, &rejections);
+        table.add_row(row![
+            invoice.invoice_id,
+            requestor_id,
+            format!("{} ({})", invoice.amount, invoice.payment_platform),
+            diagnoses.join("\n")
+        ]);
+    }
+    table.printstd();
+    Ok(0)
+}
+
+fn diagnose(
+    invoice: &UnpaidInvoice,
+    requestor_reachable: bool,
+    accounts: &[PaymentAccount],
+    rejections: &[Entry],
+) -> Vec<String> {
+    let mut diagnoses = Vec::new();
+
+    let overdue = Utc::now() - invoice.payment_due_date;
+    if overdue.num_seconds() <= 0 {
+        diagnoses.push(format!(
+            "not due yet, payment expected by {}",
+            invoice.payment_due_date.format("%Y-%m-%d %H:%M")
+        ));
+    } else {
+        diagnoses.push(format!(
+            "overdue by {}h, {} reminder(s) sent",
+            overdue.num_hours(),
+            invoice.reminders_sent
+        ));
+    }
+
+    let receiving = accounts.iter().any(|account| {
+        account.receive
+            && account.platform == invoice.payment_platform
+            && account.address.eq_ignore_ascii_case(&invoice.payee_addr)
+    });
+    if !receiving {
+        diagnoses.push(format!(
+            "platform mismatch, account {} doesn't receive on {}",
+            invoice.payee_addr, invoice.payment_platform
+        ));
+    }
+
+    if !requestor_reachable {
+        diagnoses.push("requestor is not reachable in the network".to_string());
+    }
+
+    for rejection in rejections {
+        let details = match &rejection.details {
+            Some(details) => details,
+            None => continue,
+        };
+        let from_requestor = details["payerId"].as_str().map_or(false, |payer| {
+            payer.eq_ignore_ascii_case(&invoice.requestor_id.to_string())
+        });
+        let for_agreement = details["agreementIds"].as_array().map_or(true, |ids| {
+            ids.iter()
+                .any(|id| id.as_str() == Some(invoice.agreement_id.as_str()))
+        });
+        if from_requestor && for_agreement {
+            diagnoses.push(format!(
+                "payment rejected at {}: {}",
+                rejection.timestamp.format("%Y-%m-%d %H:%M"),
+                details["error"].as_str().unwrap_or("unknown error")
+            ));
+        }
+    }
+
+    if diagnoses.len() == 1 && overdue.num_seconds() > 0 {
+        diagnoses.push("no problem found on this side, requestor hasn't paid".to_string());
+    }
+    diagnoses
+}
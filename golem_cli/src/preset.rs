@@ -0,0 +1,173 @@
+use ansi_term::Colour;
+use anyhow::{anyhow, bail, Context, Result};
+use std::path::Path;
+use structopt::StructOpt;
+
+use crate::command::{Preset, YaCommand};
+
+#[derive(StructOpt, Debug)]
+pub enum PresetCommand {
+    /// Edit preset as TOML in $EDITOR, changes are applied after confirmation
+    Edit { name: String },
+}
+
+pub async fn run(command: PresetCommand) -> Result</*exit code*/ i32> {
+    match command {
+        PresetCommand::Edit { name } => edit(name).await,
+    }
+}
+
+async fn edit(name: String) -> Result</*exit code*/ i32> {
+    let cmd = YaCommand::new()?;
+    let current = cmd
+        .ya_provider()?
+        .list_presets()
+        .await?
+        .into_iter()
+        .find(|preset| preset.name == name)
+        .ok_or_else(|| anyhow!("Preset {:?} not found", name))?;
+    let runtimes: Vec<String> = cmd
+        .ya_provider()?
+        .list_runtimes()
+        .await?
+        .into_iter()
+        .map(|runtime| runtime.name)
+        .collect();
+
+    let current_toml = toml::to_string(&current)?;
+    let path = std::env::temp_dir().join(format!("golemsp-preset-{}.toml", name));
+    std::fs::write(&path, &current_toml)
+        .with_context(|| format!("writing preset to {}", path.display()))?;
+
+    let edited = loop {
+        open_editor(&path)?;
+        let edited_toml = std::fs::read_to_string(&path)?;
+        match parse(&edited_toml, &current, &runtimes) {
+            Ok(edited) => break edited,
+            Err(e) => {
+                println!("{} {:#}", Colour::Red.paint("Invalid preset:"), e);
+                if !promptly::prompt_default("Edit again?", true)? {
+                    let _ = std::fs::remove_file(&path);
+                    return Ok(1);
+                }
+            }
+        }
+    };
+    let _ = std::fs::remove_file(&path);
+    if edited == current {
+        println!("No changes.");
+        return Ok(0);
+    }
+    println!();
+    for line in diff(&current_toml, &toml::to_string(&edited)?) {
+        println!("{}", line);
+    }
+    println!();
+    if !promptly::prompt_default("Apply changes?", false)? {
+        println!("Changes discarded.");
+        return Ok(0);
+    }
+
+    let mut coeffs = edited.usage_coeffs.clone();
+    coeffs.insert("initial".to_string(), edited.initial_price);
+    cmd.ya_provider()?
+        .update_preset(&name, &edited.name, &edited.exeunit_name, &coeffs)
+        .await?;
+    println!("Preset {:?} updated.", edited.name);
+    Ok(0)
+}
+
+fn open_editor(path: &Path) -> Result<()> {
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| {
+            if cfg!(windows) {
+                "notepad".to_string()
+            } else {
+                "vi".to_string()
+            }
+        });
+    // Editor may be given with arguments, e.g. `code --wait`.
+    let mut args = editor.split_whitespace();
+    let program = args.next().ok_or_else(|| anyhow!("Empty $EDITOR"))?;
+    let status = std::process::Command::new(program)
+        .args(args)
+        .arg(path)
+        .status()
+        .with_context(|| format!("running editor {:?}", editor))?;
+    if !status.success() {
+        bail!("Editor {:?} exited with {}", editor, status);
+    }
+    Ok(())
+}
+
+fn parse(edited: &str, current: &Preset, runtimes: &[String]) -> Result<Preset> {
+    let preset: Preset = toml::from_str(edited)?;
+    if preset.name.trim().is_empty() {
+        bail!("name can't be empty");
+    }
+    if !runtimes.contains(&preset.exeunit_name) {
+        bail!(
+            "unknown exeunit-name {:?}, installed runtimes: {}",
+            preset.exeunit_name,
+            runtimes.join(", ")
+        );
+    }
+    let prices = std::iter::once(("initial-price", &preset.initial_price)).chain(
+        preset
+            .usage_coeffs
+            .iter()
+            .map(|(name, price)| (name.as_str(), price)),
+    );
+    for (name, price) in prices {
+        if !price.is_finite() || *price < 0.0 {
+            bail!("price of {} must be a non-negative number", name);
+        }
+    }
+    // Coefficients are merged by `preset update`, it can't remove them.
+    if let Some(removed) = current
+        .usage_coeffs
+        .keys()
+        .find(|name| !preset.usage_coeffs.contains_key(*name))
+    {
+        bail!(
+            "usage coefficient {} can't be removed, set it to 0",
+            removed
+        );
+    }
+    Ok(preset)
+}
+
+/// Line diff of `old` and `new`, based on their longest common subsequence.
+fn diff(old: &str, new: &str) -> Vec<String> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            lines.push(format!("  {}", old[i]));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            lines.push(Colour::Red.paint(format!("- {}", old[i])).to_string());
+            i += 1;
+        } else {
+            lines.push(Colour::Green.paint(format!("+ {}", new[j])).to_string());
+            j += 1;
+        }
+    }
+    lines
+}
@@ -193,6 +193,27 @@ pub async fn stop() -> Result<i32> {
     Ok(0)
 }
 
+/// Asks running provider to reload its configuration files.
+#[cfg(target_family = "unix")]
+pub fn reload_provider() -> Result<()> {
+    use nix::sys::signal::*;
+    use nix::unistd::Pid;
+    use ya_utils_path::data_dir::DataDir;
+    use ya_utils_process::lock::ProcLock;
+
+    let provider_dir = DataDir::new("ya-provider")
+        .get_or_create()
+        .expect("unable to get ya-provider data dir");
+    let provider_pid = ProcLock::new("ya-provider", &provider_dir)?
+        .read_pid()
+        .context("provider is not running")?;
+
+    kill(Pid::from_raw(provider_pid as i32), Signal::SIGHUP)
+        .context("failed to reload provider")?;
+    log::debug!("Sent SIGHUP to {}", provider_pid);
+    Ok(())
+}
+
 #[cfg(target_family = "unix")]
 async fn kill_pid(pid: i32, timeout: i64) -> Result<()> {
     use nix::sys::signal::*;
@@ -232,3 +253,8 @@ pub async fn stop() -> Result<i32> {
     // FIXME: not implemented for windows
     todo!("Implement for Windows");
 }
+
+#[cfg(not(target_family = "unix"))]
+pub fn reload_provider() -> Result<()> {
+    anyhow::bail!("Live reload is not supported on this platform, restart the provider")
+}
@@ -98,3 +98,13 @@ pub async fn run(settings: Settings) -> Result</*exit code*/ i32> {
 
     Ok(0)
 }
+
+pub async fn apply(live: bool) -> Result</*exit code*/ i32> {
+    if !live {
+        println!("Settings are applied when the provider starts, use --live to apply them to the running provider.");
+        return Ok(1);
+    }
+    crate::service::reload_provider()?;
+    println!("Settings applied to future offers, current agreements are kept.");
+    Ok(0)
+}
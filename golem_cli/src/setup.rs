@@ -42,6 +42,12 @@ pub struct RunConfig {
         set = clap::ArgSettings::Global
     )]
     pub log_dir: Option<PathBuf>,
+
+    /// push metrics to the external Golem Network metrics service,
+    /// `golemsp stats` works without it
+    #[structopt(long, env = "YA_METRICS_PUSH")]
+    #[serde(default)]
+    pub metrics_push: bool,
 }
 
 pub async fn setup(run_config: &RunConfig, force: bool) -> Result<i32> {
@@ -151,7 +157,7 @@ pub async fn setup(run_config: &RunConfig, force: bool) -> Result<i32> {
             );
             if presets.contains(&runtime.name) {
                 cmd.ya_provider()?
-                    .update_preset(&runtime.name, &runtime.name, &usage)
+                    .update_preset(&runtime.name, &runtime.name, &runtime.name, &usage)
                     .await?;
             } else {
                 cmd.ya_provider()?
@@ -0,0 +1,179 @@
+use ansi_term::{Colour, Style};
+use anyhow::Result;
+use prettytable::{format, row, Table};
+use serde::Serialize;
+use std::future::Future;
+use structopt::StructOpt;
+
+use ya_core_model::journal::UptimeWindow;
+use ya_core_model::payment::local::StatValue;
+
+use crate::command::{PaymentSummary, YaCommand};
+use crate::status::{percent, window_label, HISTORY_WINDOWS};
+use crate::utils::is_yagna_running;
+
+/// Show statistics collected by the local node, nothing is sent outside
+#[derive(StructOpt, Debug)]
+pub struct StatsConfig {
+    /// Period of earnings, e.g. `24h`, `7days`
+    #[structopt(long, default_value = "30days")]
+    earnings_period: String,
+    /// Print statistics as JSON
+    #[structopt(long)]
+    json: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Stats {
+    uptime: Option<Vec<UptimeWindow>>,
+    tasks: Option<TaskStats>,
+    earnings: Option<Earnings>,
+    bandwidth: Option<Bandwidth>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TaskStats {
+    last1h_processed: u64,
+    in_progress: u64,
+    total_processed: u64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Earnings {
+    period: String,
+    paid: StatValue,
+    pending: StatValue,
+    issued: StatValue,
+}
+
+/// MiB exchanged with other nodes since the service start.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Bandwidth {
+    out_mib: f64,
+    in_mib: f64,
+}
+
+/// Statistics which can't be read are skipped.
+async fn collect<T>(name: &str, f: impl Future<Output = Result<T>>) -> Option<T> {
+    f.await
+        .map_err(|e| log::warn!("Can't read {} statistics: {}", name, e))
+        .ok()
+}
+
+fn stat_value((total_amount, agreements_count): (bigdecimal::BigDecimal, u64)) -> StatValue {
+    StatValue {
+        total_amount,
+        agreements_count,
+    }
+}
+
+pub async fn run(config: StatsConfig) -> Result</*exit code*/ i32> {
+    if !is_yagna_running().await? {
+        println!("Golem Service is not running, start it with `golemsp run`.");
+        return Ok(1);
+    }
+    let cmd = YaCommand::new()?;
+
+    let stats = Stats {
+        uptime: collect("uptime", cmd.yagna()?.uptime(&HISTORY_WINDOWS)).await,
+        tasks: collect("task", async {
+            let status = cmd.yagna()?.activity_status().await?;
+            Ok::<_, anyhow::Error>(TaskStats {
+                last1h_processed: status.last1h_processed(),
+                in_progress: status.in_progress(),
+                total_processed: status.total_processed(),
+            })
+        })
+        .await,
+        earnings: collect("earnings", async {
+            let invoices = cmd
+                .yagna()?
+                .invoice_status_since(&config.earnings_period)
+                .await?
+                .provider;
+            Ok::<_, anyhow::Error>(Earnings {
+                period: config.earnings_period.clone(),
+                paid: invoices.settled.clone(),
+                pending: stat_value(invoices.total_pending()),
+                issued: stat_value(invoices.unconfirmed()),
+            })
+        })
+        .await,
+        bandwidth: collect("bandwidth", async {
+            let (out_mib, in_mib) = cmd.yagna()?.bandwidth().await?;
+            Ok::<_, anyhow::Error>(Bandwidth { out_mib, in_mib })
+        })
+        .await,
+    };
+
+    if config.json {
+        println!("{}", serde_json::to_string_pretty(&stats)?);
+        return Ok(0);
+    }
+    stats_table(&stats).printstd();
+    Ok(0)
+}
+
+fn header(table: &mut Table, title: &str) {
+    table.add_empty_row();
+    table.add_row(row![Style::new()
+        .fg(Colour::Yellow)
+        .underline()
+        .paint(title)]);
+}
+
+fn stats_table(stats: &Stats) -> Table {
+    let mut table = Table::new();
+    let format = format::FormatBuilder::new().padding(1, 1).build();
+    table.set_format(format);
+
+    if let Some(windows) = &stats.uptime {
+        header(&mut table, "Uptime");
+        table.add_row(row![
+            "",
+            "offer published",
+            "agreements",
+            "tasks",
+            "task success"
+        ]);
+        for window in windows {
+            table.add_row(row![
+                window_label(window.window_hours),
+                r->percent(window.offer_published),
+                r->window.agreements_served,
+                r->window.activities_started,
+                r->percent(window.activity_success_rate())
+            ]);
+        }
+    }
+    if let Some(tasks) = &stats.tasks {
+        header(&mut table, "Tasks");
+        table.add_row(row!["last 1h processed", r->tasks.last1h_processed]);
+        table.add_row(row!["in progress", r->tasks.in_progress]);
+        table.add_row(row!["total processed", r->tasks.total_processed]);
+    }
+    if let Some(earnings) = &stats.earnings {
+        header(&mut table, &format!("Earnings (last {})", earnings.period));
+        for (label, value) in [
+            ("paid", &earnings.paid),
+            ("pending", &earnings.pending),
+            ("issued", &earnings.issued),
+        ] {
+            table.add_row(row![
+                label,
+                r->value.total_amount,
+                r->format!("({} agreements)", value.agreements_count)
+            ]);
+        }
+    }
+    if let Some(bandwidth) = &stats.bandwidth {
+        header(&mut table, "Bandwidth (since service start)");
+        table.add_row(row!["out", r->format!("{:.2} MiB", bandwidth.out_mib)]);
+        table.add_row(row!["in", r->format!("{:.2} MiB", bandwidth.in_mib)]);
+    }
+    table
+}
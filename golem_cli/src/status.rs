@@ -7,6 +7,7 @@ use futures::prelude::*;
 use prettytable::{format, row, Table};
 use strum::VariantNames;
 
+use ya_core_model::journal::UptimeWindow;
 use ya_core_model::payment::local::{NetworkName, StatusResult};
 use ya_core_model::NodeId;
 
@@ -62,7 +63,52 @@ fn get_network_group(network: &NetworkName) -> NetworkGroup {
     }
 }
 
-pub async fn run() -> Result</*exit code*/ i32> {
+/// Rolling windows shown by `golemsp status --history`: last hour, day, week and month.
+pub(crate) const HISTORY_WINDOWS: [u32; 4] = [1, 24, 7 * 24, 30 * 24];
+
+pub(crate) fn window_label(hours: u32) -> String {
+    if hours % 24 == 0 {
+        format!("last {}d", hours / 24)
+    } else {
+        format!("last {}h", hours)
+    }
+}
+
+pub(crate) fn percent(fraction: Option<f64>) -> String {
+    fraction
+        .map(|f| format!("{:.1}%", f * 100.0))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+fn history_table(windows: &[UptimeWindow]) -> Table {
+    let mut table = Table::new();
+    let format = format::FormatBuilder::new().padding(1, 1).build();
+    table.set_format(format);
+    table.add_row(row![Style::new()
+        .fg(Colour::Yellow)
+        .underline()
+        .paint("History")]);
+    table.add_empty_row();
+    table.add_row(row![
+        "",
+        "offer published",
+        "agreements",
+        "tasks",
+        "task success"
+    ]);
+    for window in windows {
+        table.add_row(row![
+            window_label(window.window_hours),
+            r->percent(window.offer_published),
+            r->window.agreements_served,
+            r->window.activities_started,
+            r->percent(window.activity_success_rate())
+        ]);
+    }
+    table
+}
+
+pub async fn run(history: bool) -> Result</*exit code*/ i32> {
     let size = crossterm::terminal::size().ok().unwrap_or((80, 50));
     let cmd = YaCommand::new()?;
     let kvm_status = crate::platform::kvm_status();
@@ -225,6 +271,10 @@ pub async fn run() -> Result</*exit code*/ i32> {
     } else {
         table.add_row(row![status]);
     }
+    if history && is_running {
+        let windows = cmd.yagna()?.uptime(&HISTORY_WINDOWS).await?;
+        table.add_row(row![history_table(&windows)]);
+    }
     table.printstd();
     if let Some(msg) = kvm_status.problem() {
         println!("\n VM problem: {}", msg);
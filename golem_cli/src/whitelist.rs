@@ -0,0 +1,106 @@
+use ansi_term::{Colour, Style};
+use anyhow::Result;
+use byte_unit::Byte as Bytes;
+use prettytable::{format, row, Table};
+use structopt::StructOpt;
+
+use crate::command::YaCommand;
+
+#[derive(StructOpt, Debug)]
+pub enum WhitelistCommand {
+    /// Show domains tasks can connect to
+    Show {
+        /// Preset to show, node-global whitelist by default
+        #[structopt(long)]
+        preset: Option<String>,
+    },
+    /// Allow tasks to connect to domains
+    Add {
+        /// Preset to allow domains for, node-global whitelist by default
+        #[structopt(long)]
+        preset: Option<String>,
+        /// Treat domains as regular expressions
+        #[structopt(long)]
+        regex: bool,
+        #[structopt(required = true)]
+        domains: Vec<String>,
+    },
+    /// Remove domains by their ids listed by `show`
+    Remove {
+        /// Preset to remove domains from, node-global whitelist by default
+        #[structopt(long)]
+        preset: Option<String>,
+        #[structopt(required = true)]
+        ids: Vec<String>,
+    },
+    /// Limit ports and traffic of the preset's tasks, limits not given are removed
+    Limit {
+        #[structopt(long)]
+        preset: String,
+        /// Destination ports allowed, any port when not set
+        #[structopt(long, value_name = "port")]
+        ports: Vec<u16>,
+        /// Data exchanged by a single task
+        #[structopt(long, value_name = "bytes (like \"1.5GiB\")")]
+        max_data: Option<Bytes>,
+    },
+}
+
+pub async fn run(command: WhitelistCommand) -> Result</*exit code*/ i32> {
+    let cmd = YaCommand::new()?;
+    match command {
+        WhitelistCommand::Show { preset } => {
+            let entries = cmd.ya_provider()?.list_whitelist(preset.as_deref()).await?;
+            let title = match &preset {
+                Some(preset) => format!("Whitelist of preset {}", preset),
+                None => "Whitelist".to_string(),
+            };
+            let mut table = Table::new();
+            let format = format::FormatBuilder::new().padding(1, 1).build();
+            table.set_format(format);
+            table.add_row(row![Style::new()
+                .fg(Colour::Yellow)
+                .underline()
+                .paint(title)]);
+            table.add_empty_row();
+            if entries.is_empty() {
+                table.add_row(row!["no domains allowed"]);
+            } else {
+                table.add_row(row!["id", "domain", "match"]);
+            }
+            for (id, domain, domain_match) in entries {
+                table.add_row(row![id, domain, domain_match]);
+            }
+            table.printstd();
+        }
+        WhitelistCommand::Add {
+            preset,
+            regex,
+            domains,
+        } => {
+            let whitelist_type = if regex { "regex" } else { "strict" };
+            cmd.ya_provider()?
+                .add_to_whitelist(preset.as_deref(), whitelist_type, &domains)
+                .await?;
+            println!("Domains added.");
+        }
+        WhitelistCommand::Remove { preset, ids } => {
+            cmd.ya_provider()?
+                .remove_from_whitelist(preset.as_deref(), &ids)
+                .await?;
+            println!("Domains removed.");
+        }
+        WhitelistCommand::Limit {
+            preset,
+            ports,
+            max_data,
+        } => {
+            let max_bytes = max_data.map(|bytes| bytes.get_bytes() as u64);
+            cmd.ya_provider()?
+                .limit_outbound(&preset, &ports, max_bytes)
+                .await?;
+            println!("Outbound of preset {:?} limited.", preset);
+        }
+    }
+    Ok(0)
+}
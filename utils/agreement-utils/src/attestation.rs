@@ -0,0 +1,156 @@
+//! Hardware attestation advertised in Offers.
+//!
+//! Provider puts a quote of its trusted hardware (TPM, SEV-SNP or SGX) in
+//! `golem.inf.attestation.*` properties. Report data of the quote starts with
+//! Provider's Node id, so the quote can't be replayed by another Node.
+//! Here we only check this binding; signature of the quote has to be verified
+//! against vendor certificates, e.g. by the `/public/sgx` service for SGX.
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use ya_client_model::NodeId;
+
+use crate::{Error, ProposalView};
+
+pub const ATTESTATION_TYPE_PROPERTY: &str = "golem.inf.attestation.type";
+pub const ATTESTATION_QUOTE_PROPERTY: &str = "golem.inf.attestation.quote";
+
+/// Length of report data embedded in SGX and SEV-SNP quotes.
+pub const REPORT_DATA_LEN: usize = 64;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AttestationKind {
+    Tpm,
+    SevSnp,
+    Sgx,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attestation {
+    #[serde(rename = "type")]
+    pub kind: AttestationKind,
+    /// Hex encoded quote, as produced by the hardware.
+    pub quote: String,
+}
+
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum AttestationError {
+    #[error("Quote is not valid hex: {0}")]
+    InvalidHex(String),
+    #[error("Quote too short to contain report data")]
+    Truncated,
+    #[error("Report data of the quote doesn't match Node id {0}")]
+    NodeMismatch(NodeId),
+}
+
+impl Attestation {
+    /// Report data to be passed to the hardware when generating quote for `node_id`.
+    pub fn report_data(node_id: &NodeId) -> [u8; REPORT_DATA_LEN] {
+        let mut data = [0u8; REPORT_DATA_LEN];
+        data[..20].copy_from_slice(&node_id.into_array());
+        data
+    }
+
+    /// Attestation of the Offer, `None` if the Provider didn't include one.
+    pub fn from_offer(offer: &ProposalView) -> Result<Option<Self>, Error> {
+        let kind = match offer.get_property::<AttestationKind>(ATTESTATION_TYPE_PROPERTY) {
+            Ok(kind) => kind,
+            Err(Error::NoKey(_)) => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        let quote = offer.get_property::<String>(ATTESTATION_QUOTE_PROPERTY)?;
+        Ok(Some(Attestation { kind, quote }))
+    }
+
+    /// Checks that the quote was generated for `node_id`.
+    pub fn verify_binding(&self, node_id: &NodeId) -> Result<(), AttestationError> {
+        let quote =
+            hex::decode(&self.quote).map_err(|e| AttestationError::InvalidHex(e.to_string()))?;
+        let report_data = match self.kind {
+            // Report body follows 48 bytes of quote header, report data ends it.
+            AttestationKind::Sgx => quote.get(368..368 + REPORT_DATA_LEN),
+            AttestationKind::SevSnp => quote.get(0x50..0x50 + REPORT_DATA_LEN),
+            AttestationKind::Tpm => tpm_extra_data(&quote),
+        }
+        .ok_or(AttestationError::Truncated)?;
+
+        match report_data.starts_with(&node_id.into_array()) {
+            true => Ok(()),
+            false => Err(AttestationError::NodeMismatch(*node_id)),
+        }
+    }
+
+    pub(crate) fn write_json(self, map: &mut serde_json::Map<String, Value>) {
+        let _ = map.insert(
+            "attestation".to_string(),
+            serde_json::json!({
+                "type": self.kind,
+                "quote": self.quote,
+            }),
+        );
+    }
+}
+
+/// `extraData` of `TPMS_ATTEST`, which follows magic, type and `qualifiedSigner`.
+fn tpm_extra_data(quote: &[u8]) -> Option<&[u8]> {
+    let sized = |offset: usize| -> Option<(usize, usize)> {
+        let size = u16::from_be_bytes([*quote.get(offset)?, *quote.get(offset + 1)?]) as usize;
+        Some((offset + 2, offset + 2 + size))
+    };
+    let (_, signer_end) = sized(6)?;
+    let (start, end) = sized(signer_end)?;
+    quote.get(start..end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node_id() -> NodeId {
+        "0x5b2f3b9ae4a7b4a0c0b8d94a8a2e4c7f0e3d6a1b"
+            .parse()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_verify_binding_sev_snp() {
+        let mut quote = vec![0u8; 0x2a0];
+        quote[0x50..0x50 + REPORT_DATA_LEN].copy_from_slice(&Attestation::report_data(&node_id()));
+        let attestation = Attestation {
+            kind: AttestationKind::SevSnp,
+            quote: hex::encode(&quote),
+        };
+        assert_eq!(attestation.verify_binding(&node_id()), Ok(()));
+
+        let other: NodeId = "0x0000000000000000000000000000000000000001"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            attestation.verify_binding(&other),
+            Err(AttestationError::NodeMismatch(other))
+        );
+    }
+
+    #[test]
+    fn test_verify_binding_tpm() {
+        let mut quote = vec![0xff, 0x54, 0x43, 0x47, 0x80, 0x18, 0x00, 0x02, 0xaa, 0xbb];
+        quote.extend_from_slice(&[0x00, 20]);
+        quote.extend_from_slice(&node_id().into_array());
+        quote.extend_from_slice(&[0u8; 16]);
+        let attestation = Attestation {
+            kind: AttestationKind::Tpm,
+            quote: hex::encode(&quote),
+        };
+        assert_eq!(attestation.verify_binding(&node_id()), Ok(()));
+
+        let truncated = Attestation {
+            kind: AttestationKind::Sgx,
+            quote: hex::encode(&quote),
+        };
+        assert_eq!(
+            truncated.verify_binding(&node_id()),
+            Err(AttestationError::Truncated)
+        );
+    }
+}
@@ -0,0 +1,81 @@
+//! Performance benchmark scores advertised in Offers.
+//!
+//! Provider puts scores of benchmarks run on its machine in `golem.inf.benchmark.*`
+//! properties. Scores are normalized, 1.0 being the result of a reference machine,
+//! so Requestors can constrain on performance, e.g. `(golem.inf.benchmark.cpu-multi>=4)`.
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{Error, ProposalView};
+
+pub const BENCHMARK_CPU_SINGLE_PROPERTY: &str = "golem.inf.benchmark.cpu-single";
+pub const BENCHMARK_CPU_MULTI_PROPERTY: &str = "golem.inf.benchmark.cpu-multi";
+pub const BENCHMARK_MEM_BANDWIDTH_PROPERTY: &str = "golem.inf.benchmark.mem-bandwidth";
+pub const BENCHMARK_DISK_IOPS_PROPERTY: &str = "golem.inf.benchmark.disk-iops";
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct BenchmarkScores {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpu_single: Option<f64>,
+    /// Score of all CPU threads working concurrently.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpu_multi: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mem_bandwidth: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disk_iops: Option<f64>,
+}
+
+impl BenchmarkScores {
+    pub fn is_empty(&self) -> bool {
+        self.cpu_single.is_none()
+            && self.cpu_multi.is_none()
+            && self.mem_bandwidth.is_none()
+            && self.disk_iops.is_none()
+    }
+
+    pub fn from_offer(offer: &ProposalView) -> Result<Self, Error> {
+        let score = |property: &str| match offer.get_property::<f64>(property) {
+            Ok(score) => Ok(Some(score)),
+            Err(Error::NoKey(_)) => Ok(None),
+            Err(e) => Err(e),
+        };
+        Ok(BenchmarkScores {
+            cpu_single: score(BENCHMARK_CPU_SINGLE_PROPERTY)?,
+            cpu_multi: score(BENCHMARK_CPU_MULTI_PROPERTY)?,
+            mem_bandwidth: score(BENCHMARK_MEM_BANDWIDTH_PROPERTY)?,
+            disk_iops: score(BENCHMARK_DISK_IOPS_PROPERTY)?,
+        })
+    }
+
+    pub(crate) fn write_json(self, map: &mut serde_json::Map<String, Value>) {
+        if !self.is_empty() {
+            let _ = map.insert("benchmark".to_string(), serde_json::json!(self));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_json() {
+        let scores = BenchmarkScores {
+            cpu_single: Some(1.5),
+            disk_iops: Some(0.25),
+            ..Default::default()
+        };
+        let mut map = serde_json::Map::new();
+        scores.write_json(&mut map);
+        assert_eq!(
+            Value::Object(map),
+            serde_json::json!({"benchmark": {"cpu-single": 1.5, "disk-iops": 0.25}})
+        );
+
+        let mut map = serde_json::Map::new();
+        BenchmarkScores::default().write_json(&mut map);
+        assert!(map.is_empty());
+    }
+}
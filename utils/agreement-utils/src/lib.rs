@@ -1,10 +1,16 @@
 pub mod agreement;
+pub mod attestation;
+pub mod benchmark;
 mod constraints;
 pub mod proposal;
 pub mod template;
+pub mod termination;
 mod typed_props;
 
 pub use agreement::{AgreementView, Error, OfferTemplate};
+pub use attestation::{Attestation, AttestationKind};
+pub use benchmark::BenchmarkScores;
 pub use constraints::*;
 pub use proposal::ProposalView;
+pub use termination::TerminationReason;
 pub use typed_props::*;
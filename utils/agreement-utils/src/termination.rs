@@ -0,0 +1,153 @@
+//! Taxonomy of Agreement termination reasons.
+//!
+//! Provider and Requestor describe termination with their own codes, put in
+//! `golem.provider.code` or `golem.requestor.code` of the `Reason`. Codes are
+//! classified here, so both sides can tell why Agreements end, whoever ended them.
+//! A party may state the class explicitly in `golem.termination.reason`.
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use ya_client_model::market::Reason;
+
+pub const PROVIDER_CODE_PROPERTY: &str = "golem.provider.code";
+pub const REQUESTOR_CODE_PROPERTY: &str = "golem.requestor.code";
+pub const TERMINATION_REASON_PROPERTY: &str = "golem.termination.reason";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TerminationReason {
+    Success,
+    Expired,
+    RequestorCancelled,
+    NoActivity,
+    PaymentTimeout,
+    PaymentRejected,
+    ResourceExceeded,
+    OperatorShutdown,
+    Unreachable,
+    InitializationFailed,
+    Unspecified,
+}
+
+impl TerminationReason {
+    /// Classifies code of the Provider's `Reason`.
+    pub fn from_provider_code(code: Option<&str>) -> Self {
+        match code {
+            Some("Success") => TerminationReason::Success,
+            Some("Expired") => TerminationReason::Expired,
+            Some("NoActivity") => TerminationReason::NoActivity,
+            Some("DebitNotesDeadline") | Some("DebitNoteNotPaid") => {
+                TerminationReason::PaymentTimeout
+            }
+            Some("DebitNoteRejected") | Some("DebitNoteCancelled") => {
+                TerminationReason::PaymentRejected
+            }
+            Some("ResourceExceeded") => TerminationReason::ResourceExceeded,
+            Some("Shutdown") => TerminationReason::OperatorShutdown,
+            Some("RequestorUnreachable") => TerminationReason::Unreachable,
+            Some("InitializationError") => TerminationReason::InitializationFailed,
+            _ => TerminationReason::Unspecified,
+        }
+    }
+
+    /// Classifies code of the Requestor's `Reason`. Requestor ending
+    /// the Agreement for any other reason cancels it.
+    pub fn from_requestor_code(code: Option<&str>) -> Self {
+        match code {
+            Some("Success") => TerminationReason::Success,
+            Some("Expired") => TerminationReason::Expired,
+            Some("CostCeilingReached") => TerminationReason::ResourceExceeded,
+            Some("Shutdown") => TerminationReason::OperatorShutdown,
+            _ => TerminationReason::RequestorCancelled,
+        }
+    }
+
+    /// Classifies `reason` given by the party, which terminated the Agreement.
+    pub fn from_reason(reason: Option<&Reason>, by_requestor: bool) -> Self {
+        let property = |key: &str| {
+            reason
+                .and_then(|reason| reason.extra.get(key))
+                .and_then(|value| value.as_str())
+        };
+
+        if let Some(explicit) = property(TERMINATION_REASON_PROPERTY)
+            .and_then(|value| serde_json::from_value(value.into()).ok())
+        {
+            return explicit;
+        }
+        match by_requestor {
+            true => Self::from_requestor_code(property(REQUESTOR_CODE_PROPERTY)),
+            false => Self::from_provider_code(property(PROVIDER_CODE_PROPERTY)),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TerminationReason::Success => "success",
+            TerminationReason::Expired => "expired",
+            TerminationReason::RequestorCancelled => "requestor-cancelled",
+            TerminationReason::NoActivity => "no-activity",
+            TerminationReason::PaymentTimeout => "payment-timeout",
+            TerminationReason::PaymentRejected => "payment-rejected",
+            TerminationReason::ResourceExceeded => "resource-exceeded",
+            TerminationReason::OperatorShutdown => "operator-shutdown",
+            TerminationReason::Unreachable => "unreachable",
+            TerminationReason::InitializationFailed => "initialization-failed",
+            TerminationReason::Unspecified => "unspecified",
+        }
+    }
+}
+
+impl fmt::Display for TerminationReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reason(extra: serde_json::Value) -> Reason {
+        let mut reason = Reason::new("Terminated");
+        reason.extra = extra;
+        reason
+    }
+
+    #[test]
+    fn test_from_reason() {
+        let provider = reason(serde_json::json!({ "golem.provider.code": "DebitNoteNotPaid" }));
+        assert_eq!(
+            TerminationReason::from_reason(Some(&provider), false),
+            TerminationReason::PaymentTimeout
+        );
+
+        let requestor = reason(serde_json::json!({ "golem.requestor.code": "Interrupted" }));
+        assert_eq!(
+            TerminationReason::from_reason(Some(&requestor), true),
+            TerminationReason::RequestorCancelled
+        );
+        assert_eq!(
+            TerminationReason::from_reason(None, false),
+            TerminationReason::Unspecified
+        );
+
+        let explicit = reason(serde_json::json!({
+            "golem.provider.code": "Custom",
+            "golem.termination.reason": "resource-exceeded",
+        }));
+        assert_eq!(
+            TerminationReason::from_reason(Some(&explicit), false),
+            TerminationReason::ResourceExceeded
+        );
+    }
+
+    #[test]
+    fn test_as_str_matches_serde() {
+        let reason = TerminationReason::OperatorShutdown;
+        assert_eq!(
+            serde_json::to_value(reason).unwrap(),
+            serde_json::Value::from(reason.as_str())
+        );
+    }
+}
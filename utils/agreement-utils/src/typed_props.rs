@@ -1,4 +1,4 @@
-use crate::OfferTemplate;
+use crate::{Attestation, BenchmarkScores, OfferTemplate};
 
 use serde_json::Value;
 
@@ -123,6 +123,9 @@ pub struct InfNodeInfo {
     mem_gib: Option<f64>,
     storage_gib: Option<f64>,
     cpu_info: Option<CpuInfo>,
+    slots: Option<u32>,
+    attestation: Option<Attestation>,
+    benchmark: Option<BenchmarkScores>,
 }
 
 impl InfNodeInfo {
@@ -152,6 +155,31 @@ impl InfNodeInfo {
         }
     }
 
+    /// Number of activities which can run concurrently,
+    /// each with the resources described by the remaining properties.
+    pub fn with_slots(self, slots: u32) -> Self {
+        Self {
+            slots: Some(slots),
+            ..self
+        }
+    }
+
+    /// Quote of trusted hardware, generated for the Provider's Node id.
+    pub fn with_attestation(self, attestation: Attestation) -> Self {
+        Self {
+            attestation: Some(attestation),
+            ..self
+        }
+    }
+
+    /// Scores of benchmarks run on the Provider's machine.
+    pub fn with_benchmark(self, benchmark: BenchmarkScores) -> Self {
+        Self {
+            benchmark: Some(benchmark),
+            ..self
+        }
+    }
+
     fn write_json(self, map: &mut serde_json::Map<String, Value>) {
         let mut inf_map = serde_json::Map::new();
         if let Some(mem) = self.mem_gib {
@@ -163,6 +191,15 @@ impl InfNodeInfo {
         if let Some(cpu) = self.cpu_info {
             cpu.write_json(&mut inf_map);
         }
+        if let Some(slots) = self.slots {
+            let _ = inf_map.insert("slots".to_string(), serde_json::json!(slots));
+        }
+        if let Some(attestation) = self.attestation {
+            attestation.write_json(&mut inf_map);
+        }
+        if let Some(benchmark) = self.benchmark {
+            benchmark.write_json(&mut inf_map);
+        }
         let _ = map.insert("inf".to_string(), inf_map.into());
     }
 }
@@ -212,6 +249,7 @@ impl ComInfo {
 
 // golem.inf.mem.gib
 // golem.inf.storage.gib
+// golem.inf.slots
 // R: golem.activity.timeout_secs
 
 // golem.com.payment.scheme="payu"
@@ -231,7 +269,10 @@ mod test {
         let offer = OfferDefinition {
             node_info: NodeInfo::with_name("dany"),
             srv_info: ServiceInfo {
-                inf: InfNodeInfo::default().with_mem(5.0).with_storage(50.0),
+                inf: InfNodeInfo::default()
+                    .with_mem(5.0)
+                    .with_storage(50.0)
+                    .with_slots(2),
                 exeunit_info: serde_json::json!({"wasm.wasi.version@v".to_string(): "0.9.0".to_string()}),
                 multi_activity: false,
                 payload_manifest: true,
@@ -245,6 +286,7 @@ mod test {
         let expected_offer = json!({
             "golem.com": null,
             "golem.inf.mem.gib": 5.0,
+            "golem.inf.slots": 2,
             "golem.inf.storage.gib": 50.0,
             "golem.node.id.name": "dany",
             "golem.node.net.is-public": false,
@@ -0,0 +1,105 @@
+//! Context attached to log records in JSON format.
+//!
+//! Context is set for the duration of each poll of a wrapped future,
+//! so it follows the task between threads and doesn't leak to other
+//! tasks running on the same thread.
+use std::cell::RefCell;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use serde_json::{Map, Value};
+
+thread_local! {
+    static CURRENT: RefCell<Option<Arc<LogContext>>> = RefCell::new(None);
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct LogContext {
+    pub span: String,
+    pub correlation_id: Option<String>,
+    pub fields: Map<String, Value>,
+}
+
+impl LogContext {
+    pub fn new(span: impl ToString) -> Self {
+        LogContext {
+            span: span.to_string(),
+            ..Default::default()
+        }
+    }
+
+    pub fn correlation_id(mut self, id: impl ToString) -> Self {
+        self.correlation_id = Some(id.to_string());
+        self
+    }
+
+    pub fn field(mut self, key: impl ToString, value: impl Into<Value>) -> Self {
+        self.fields.insert(key.to_string(), value.into());
+        self
+    }
+
+    /// Runs `f` with this context set.
+    pub fn scope<R>(self, f: impl FnOnce() -> R) -> R {
+        let previous = CURRENT.with(|c| c.replace(Some(Arc::new(self))));
+        let result = f();
+        CURRENT.with(|c| c.replace(previous));
+        result
+    }
+}
+
+pub(crate) fn with_current<R>(f: impl FnOnce(Option<&LogContext>) -> R) -> R {
+    CURRENT.with(|c| f(c.borrow().as_deref()))
+}
+
+pub struct WithLogContext<F> {
+    context: Arc<LogContext>,
+    future: Pin<Box<F>>,
+}
+
+impl<F: Future> Future for WithLogContext<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let context = self.context.clone();
+        let previous = CURRENT.with(|c| c.replace(Some(context)));
+        let result = self.future.as_mut().poll(cx);
+        CURRENT.with(|c| c.replace(previous));
+        result
+    }
+}
+
+pub trait LogContextExt: Future + Sized {
+    /// Attaches `context` to log records emitted while polling this future.
+    fn log_context(self, context: LogContext) -> WithLogContext<Self> {
+        WithLogContext {
+            context: Arc::new(context),
+            future: Box::pin(self),
+        }
+    }
+}
+
+impl<F: Future> LogContextExt for F {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scope() {
+        let context = LogContext::new("test")
+            .correlation_id("abc")
+            .field("count", 3);
+
+        context.scope(|| {
+            with_current(|c| {
+                let c = c.unwrap();
+                assert_eq!(c.span, "test");
+                assert_eq!(c.correlation_id.as_deref(), Some("abc"));
+                assert_eq!(c.fields["count"], 3);
+            })
+        });
+        with_current(|c| assert!(c.is_none()));
+    }
+}
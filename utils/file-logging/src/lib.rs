@@ -7,9 +7,59 @@ use flexi_logger::{
     LogSpecification, Logger, Naming, Record,
 };
 use std::path::Path;
+use std::str::FromStr;
+use std::sync::Mutex;
 
 pub use flexi_logger::LoggerHandle;
 
+pub use context::{LogContext, LogContextExt};
+
+mod context;
+
+pub const LOG_FORMAT_ENV_VAR: &str = "YA_LOG_FORMAT";
+
+/// Format of log entries written to files and stderr.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    #[default]
+    Text,
+    /// One JSON object per line, for log shippers.
+    Json,
+}
+
+impl LogFormat {
+    pub const VARIANTS: &'static [&'static str] = &["text", "json"];
+}
+
+impl FromStr for LogFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "text" => Ok(LogFormat::Text),
+            "json" => Ok(LogFormat::Json),
+            _ => anyhow::bail!("Invalid log format '{}', expected one of: text, json", s),
+        }
+    }
+}
+
+impl std::fmt::Display for LogFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LogFormat::Text => write!(f, "text"),
+            LogFormat::Json => write!(f, "json"),
+        }
+    }
+}
+
+/// Specifications set by `start_logger` and by runtime reconfiguration.
+static LOG_SPECS: Mutex<Option<LogSpecs>> = Mutex::new(None);
+
+struct LogSpecs {
+    initial: LogSpecification,
+    current: LogSpecification,
+}
+
 #[allow(clippy::useless_conversion)]
 fn log_format_date(now: &mut DeferredNow) -> DelayedFormat<StrftimeItems> {
     //use DateTime::<Local> instead of DateTime::<UTC> to obtain local date
@@ -45,6 +95,31 @@ fn log_format(
     write!(w, "[packet-trace]{}", record.args(),)
 }
 
+fn log_format_json(
+    w: &mut dyn std::io::Write,
+    now: &mut DeferredNow,
+    record: &Record,
+) -> Result<(), std::io::Error> {
+    let mut entry = serde_json::json!({
+        "timestamp": log_format_date(now).to_string(),
+        "level": record.level().as_str(),
+        "module": record.module_path(),
+        "target": record.target(),
+        "file": record.file(),
+        "line": record.line(),
+        "thread": std::thread::current().name(),
+        "message": record.args().to_string(),
+    });
+    context::with_current(|context| {
+        if let Some(context) = context {
+            entry["span"] = context.span.clone().into();
+            entry["correlation_id"] = context.correlation_id.clone().into();
+            entry["fields"] = context.fields.clone().into();
+        }
+    });
+    write!(w, "{}", entry)
+}
+
 fn log_format_color(
     w: &mut dyn std::io::Write,
     now: &mut DeferredNow,
@@ -79,6 +154,7 @@ pub fn start_logger(
     log_dir: Option<&Path>,
     module_filters: &[(&str, log::LevelFilter)],
     force_debug: bool,
+    format: LogFormat,
 ) -> Result<LoggerHandle> {
     let log_spec = LogSpecification::env_or_parse(default_log_spec)?;
     let mut log_spec_builder = LogSpecBuilder::from_module_filters(log_spec.module_filters());
@@ -94,13 +170,97 @@ pub fn start_logger(
     }
 
     let log_spec = log_spec_builder.finalize();
-    let mut logger = Logger::with(log_spec).format(log_format);
+    *LOG_SPECS.lock().unwrap() = Some(LogSpecs {
+        initial: log_spec.clone(),
+        current: log_spec.clone(),
+    });
+
+    let mut logger = match format {
+        LogFormat::Text => Logger::with(log_spec).format(log_format),
+        LogFormat::Json => Logger::with(log_spec).format(log_format_json),
+    };
     if let Some(log_dir) = log_dir {
         logger = set_logging_to_files(logger, log_dir);
     }
-    logger = logger
-        .adaptive_format_for_stderr(AdaptiveFormat::Custom(log_format, log_format_color))
-        .set_palette("9;11;2;7;8".to_string());
+    if format == LogFormat::Text {
+        logger = logger
+            .adaptive_format_for_stderr(AdaptiveFormat::Custom(log_format, log_format_color))
+            .set_palette("9;11;2;7;8".to_string());
+    }
 
     Ok(logger.start()?)
 }
+
+/// Changes log levels of given modules without restarting the logger.
+/// Filters without a module change the default log level.
+/// Returns the resulting log specification.
+pub fn set_module_filters(
+    handle: &mut LoggerHandle,
+    filters: &[(Option<String>, log::LevelFilter)],
+) -> Result<String> {
+    let mut specs = LOG_SPECS.lock().unwrap();
+    let specs = specs
+        .as_mut()
+        .ok_or_else(|| anyhow::anyhow!("Logger not started"))?;
+
+    let mut builder = LogSpecBuilder::from_module_filters(specs.current.module_filters());
+    for (module, level) in filters {
+        match module {
+            Some(module) => builder.module(module, *level),
+            None => builder.default(*level),
+        };
+    }
+
+    specs.current = builder.finalize();
+    handle.set_new_spec(specs.current.clone());
+    Ok(format_log_spec(&specs.current))
+}
+
+/// Restores log levels the logger was started with.
+pub fn reset_module_filters(handle: &mut LoggerHandle) -> Result<String> {
+    let mut specs = LOG_SPECS.lock().unwrap();
+    let specs = specs
+        .as_mut()
+        .ok_or_else(|| anyhow::anyhow!("Logger not started"))?;
+
+    specs.current = specs.initial.clone();
+    handle.set_new_spec(specs.current.clone());
+    Ok(format_log_spec(&specs.current))
+}
+
+/// Current log specification in `RUST_LOG` format.
+pub fn current_log_spec() -> Option<String> {
+    LOG_SPECS
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|specs| format_log_spec(&specs.current))
+}
+
+fn format_log_spec(spec: &LogSpecification) -> String {
+    let mut filters = spec
+        .module_filters()
+        .iter()
+        .map(|filter| match &filter.module_spec {
+            Some(module) => format!("{}={}", module, filter.level_filter),
+            None => filter.level_filter.to_string(),
+        })
+        .collect::<Vec<_>>();
+    // Default level goes first
+    filters.sort_by_key(|filter| filter.contains('='));
+    filters.join(",").to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_log_spec() {
+        let spec = LogSpecification::parse("ya_market=debug,info,ya_net=trace").unwrap();
+        let formatted = format_log_spec(&spec);
+        assert!(formatted.starts_with("info,"));
+        assert!(formatted.contains("ya_market=debug"));
+        assert!(formatted.contains("ya_net=trace"));
+    }
+}
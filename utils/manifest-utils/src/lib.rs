@@ -1,10 +1,12 @@
 pub mod keystore;
 pub mod manifest;
 pub mod matching;
+pub mod outbound;
 pub mod policy;
 pub mod util;
 
 pub use manifest::*;
+pub use outbound::OutboundPolicy;
 // pub use keystore::
 pub use keystore::CompositeKeystore;
 pub use policy::{Policy, PolicyConfig};
@@ -283,7 +283,7 @@ impl<'de> Deserialize<'de> for Command {
 
 /// # Argument Match
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
-#[derive(Clone, Copy, Debug, Serialize, Deserialize, EnumString, AsRefStr)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, EnumString, AsRefStr)]
 #[serde(rename_all = "camelCase")]
 #[derive(Default)]
 pub enum ArgMatch {
@@ -337,6 +337,23 @@ pub struct InetOut {
     /// E.g. ["http://golemfactory.s3.amazonaws.com/file1", "http://golemfactory.s3.amazonaws.com/file2"]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub urls: Option<Vec<Url>>,
+    /// Caps on outbound traffic. Connections exceeding them are refused
+    /// and the activity is terminated when the transfer cap is exceeded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limits: Option<InetOutLimits>,
+}
+
+/// # Internet Outbound Network Limits
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InetOutLimits {
+    /// Maximum number of bytes sent to and received from external hosts.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_bytes: Option<u64>,
+    /// Maximum number of distinct external addresses (IP and port) connected to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_destinations: Option<u32>,
 }
 
 pub fn default_protocols() -> Vec<String> {
@@ -393,6 +410,7 @@ mod tests {
                         out: Some(InetOut {
                             protocols: default_protocols(),
                             urls: None,
+                            limits: None,
                         }),
                     }),
                 }),
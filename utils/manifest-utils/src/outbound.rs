@@ -0,0 +1,167 @@
+//! Outbound network access policy of a Provider preset.
+//!
+//! Policy is published in Offers, so Demands requesting other destinations can be
+//! rejected during negotiation, and enforced by the ExeUnit outbound gateway,
+//! which reads it from the Agreement.
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use url::Url;
+
+use ya_agreement_utils::{AgreementView, Error as AgreementError, ProposalView};
+
+use crate::matching::domain::DomainPattern;
+use crate::InetOutLimits;
+
+pub const OUTBOUND_WHITELIST_PROPERTY: &str = "golem.srv.caps.inet.out.whitelist";
+pub const OUTBOUND_PORTS_PROPERTY: &str = "golem.srv.caps.inet.out.ports";
+pub const OUTBOUND_MAX_BYTES_PROPERTY: &str = "golem.srv.caps.inet.out.max-bytes";
+
+const AGREEMENT_OFFER_PREFIX: &str = "offer.properties.";
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct OutboundPolicy {
+    /// Domains allowed for Activities of the preset, replacing the node-global whitelist.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub whitelist: Vec<DomainPattern>,
+    /// Destination ports allowed, any port when empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ports: Vec<u16>,
+    /// Maximum number of bytes exchanged with external hosts by a single Activity.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_bytes: Option<u64>,
+}
+
+impl OutboundPolicy {
+    pub fn is_empty(&self) -> bool {
+        self.whitelist.is_empty() && self.ports.is_empty() && self.max_bytes.is_none()
+    }
+
+    /// Offer properties describing the policy.
+    pub fn properties(&self) -> Vec<(&'static str, Value)> {
+        let mut properties = Vec::new();
+        if !self.whitelist.is_empty() {
+            properties.push((
+                OUTBOUND_WHITELIST_PROPERTY,
+                serde_json::json!(self.whitelist),
+            ));
+        }
+        if !self.ports.is_empty() {
+            properties.push((OUTBOUND_PORTS_PROPERTY, serde_json::json!(self.ports)));
+        }
+        if let Some(max_bytes) = self.max_bytes {
+            properties.push((OUTBOUND_MAX_BYTES_PROPERTY, serde_json::json!(max_bytes)));
+        }
+        properties
+    }
+
+    pub fn from_offer(offer: &ProposalView) -> Result<Self, AgreementError> {
+        Ok(OutboundPolicy {
+            whitelist: optional(offer.get_property(OUTBOUND_WHITELIST_PROPERTY))?
+                .unwrap_or_default(),
+            ports: optional(offer.get_property(OUTBOUND_PORTS_PROPERTY))?.unwrap_or_default(),
+            max_bytes: optional(offer.get_property(OUTBOUND_MAX_BYTES_PROPERTY))?,
+        })
+    }
+
+    pub fn from_agreement(agreement: &AgreementView) -> Result<Self, AgreementError> {
+        let property = |name: &str| format!("{}{}", AGREEMENT_OFFER_PREFIX, name);
+        Ok(OutboundPolicy {
+            whitelist: optional(agreement.get_property(&property(OUTBOUND_WHITELIST_PROPERTY)))?
+                .unwrap_or_default(),
+            ports: optional(agreement.get_property(&property(OUTBOUND_PORTS_PROPERTY)))?
+                .unwrap_or_default(),
+            max_bytes: optional(agreement.get_property(&property(OUTBOUND_MAX_BYTES_PROPERTY)))?,
+        })
+    }
+
+    pub fn allows_port(&self, port: u16) -> bool {
+        self.ports.is_empty() || self.ports.contains(&port)
+    }
+
+    /// Urls which can't be reached because of their port.
+    pub fn forbidden_ports<'a>(&self, urls: &'a [Url]) -> Vec<&'a Url> {
+        urls.iter()
+            .filter(|url| {
+                url.port_or_known_default()
+                    .map(|port| !self.allows_port(port))
+                    .unwrap_or(true)
+            })
+            .collect()
+    }
+
+    /// Merges the policy with limits requested in the manifest, the lower cap wins.
+    pub fn limits(&self, requested: Option<InetOutLimits>) -> Option<InetOutLimits> {
+        if requested.is_none() && self.max_bytes.is_none() {
+            return None;
+        }
+        let mut limits = requested.unwrap_or_default();
+        limits.max_bytes = match (limits.max_bytes, self.max_bytes) {
+            (Some(requested), Some(max)) => Some(requested.min(max)),
+            (requested, max) => requested.or(max),
+        };
+        Some(limits)
+    }
+}
+
+fn optional<T>(result: Result<T, AgreementError>) -> Result<Option<T>, AgreementError> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(AgreementError::NoKey(_)) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limits() {
+        let policy = OutboundPolicy {
+            max_bytes: Some(1000),
+            ..Default::default()
+        };
+        let requested = |max_bytes| {
+            Some(InetOutLimits {
+                max_bytes,
+                max_destinations: Some(3),
+            })
+        };
+
+        assert!(OutboundPolicy::default().limits(None).is_none());
+        assert_eq!(policy.limits(None).unwrap().max_bytes, Some(1000));
+        assert_eq!(
+            policy.limits(requested(None)).unwrap().max_bytes,
+            Some(1000)
+        );
+        assert_eq!(
+            policy.limits(requested(Some(10))).unwrap().max_bytes,
+            Some(10)
+        );
+        assert_eq!(
+            policy.limits(requested(Some(5000))).unwrap().max_bytes,
+            Some(1000)
+        );
+        assert_eq!(
+            policy.limits(requested(None)).unwrap().max_destinations,
+            Some(3)
+        );
+    }
+
+    #[test]
+    fn test_forbidden_ports() {
+        let policy = OutboundPolicy {
+            ports: vec![443],
+            ..Default::default()
+        };
+        let urls = vec![
+            Url::parse("https://golem.network").unwrap(),
+            Url::parse("http://golem.network").unwrap(),
+            Url::parse("https://golem.network:8443").unwrap(),
+        ];
+
+        assert_eq!(policy.forbidden_ports(&urls), vec![&urls[1], &urls[2]]);
+        assert!(OutboundPolicy::default().forbidden_ports(&urls).is_empty());
+    }
+}